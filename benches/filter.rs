@@ -0,0 +1,81 @@
+//! Benchmark of [`Html::filter`] with a [`Filter::depth`] on adversarial
+//! documents, to check that the filtering time grows linearly with the size
+//! of the document.
+//!
+//! Run with `cargo bench --bench filter`. The benchmark panics if doubling
+//! the size of a document more than triples the filtering time.
+
+#![expect(clippy::print_stdout, clippy::use_debug, reason = "benchmark report")]
+#![expect(clippy::expect_used, reason = "benchmark")]
+
+use core::hint::black_box;
+use core::time::Duration;
+use std::time::Instant;
+
+use html_filter::{Filter, Html};
+
+/// Number of times each measure is repeated.
+const ITERATIONS: u32 = 10;
+
+/// Maximum ratio between the times of a document and of a document twice
+/// smaller.
+///
+/// A linear filtering doubles the time, and a quadratic one quadruples it.
+const MAX_RATIO: u32 = 3;
+
+/// Number of nodes in the smallest documents.
+const SIZE: usize = 500;
+
+/// Checks that the time to filter the documents built by `document` grows
+/// linearly with their size.
+///
+/// `depth` gives the depth of the filter for a given size.
+fn check_linear(name: &str, document: fn(usize) -> String, depth: fn(usize) -> usize) {
+    let mut previous: Option<Duration> = None;
+    for size in [SIZE, 2 * SIZE, 4 * SIZE] {
+        let html = Html::parse(&document(size)).expect("generated document is valid");
+        let filter = Filter::new().tag_name("p").depth(depth(size));
+        let time = measure(|| html.to_filtered(&filter));
+        println!("{name} document of size {size}: {time:?}");
+        if let Some(before) = previous {
+            assert!(
+                time < before.saturating_mul(MAX_RATIO),
+                "Filtering the {name} document isn't linear: {before:?} then {time:?}"
+            );
+        }
+        previous = Some(time);
+    }
+}
+
+/// Builds a deep document: each tag contains a text and the next tag, and the
+/// wanted tag is at the bottom, after twice `size` tags.
+///
+/// With a depth of `size`, the wanted tag is too far from the first half of
+/// the tags, which used to be measured again for each of them.
+fn deep_document(size: usize) -> String {
+    let levels = size.saturating_mul(2);
+    let mut html = "<div>x".repeat(levels);
+    html.push_str("<p>found</p>");
+    html.push_str(&"</div>".repeat(levels));
+    html
+}
+
+/// Measures the average time taken by `run`.
+fn measure<F: Fn() -> Html>(run: F) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(run());
+    }
+    start.elapsed().checked_div(ITERATIONS).unwrap_or_default()
+}
+
+/// Builds a wide document: a lot of sibling sections that each contain a
+/// wanted tag, deep enough to be kept with their ancestors.
+fn wide_document(size: usize) -> String {
+    "<section><article><div><p>found</p></div></article><span>x</span></section>".repeat(size)
+}
+
+fn main() {
+    check_linear("deep", deep_document, |size| size);
+    check_linear("wide", wide_document, |_| 2);
+}
@@ -0,0 +1,55 @@
+//! Benchmark of [`Html::find`] against a full [`Html::filter`] on a large
+//! document whose first match is at the very beginning.
+//!
+//! Run with `cargo bench --bench find`.
+
+#![expect(clippy::print_stdout, clippy::use_debug, reason = "benchmark report")]
+#![expect(clippy::expect_used, reason = "benchmark")]
+
+use core::fmt::Write as _;
+use core::hint::black_box;
+use core::time::Duration;
+use std::time::Instant;
+
+use html_filter::{Filter, Html};
+
+/// Number of times each measure is repeated.
+const ITERATIONS: u32 = 20;
+
+/// Number of sections in the generated document.
+const SECTIONS: usize = 10_000;
+
+/// Builds a large document with a lot of sections.
+fn large_document() -> String {
+    let mut html = String::from("<!DOCTYPE html><html><body>");
+    for idx in 0..SECTIONS {
+        write!(
+            html,
+            r#"<section id="s{idx}"><h2>Section {idx}</h2><ul><li class="item">First</li><li class="item">Second</li></ul><!-- end --></section>"#
+        )
+        .expect("writing to a string never fails");
+    }
+    html.push_str("</body></html>");
+    html
+}
+
+/// Measures the average time taken by `run`.
+fn measure<F: Fn() -> Html>(run: F) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(run());
+    }
+    start.elapsed().checked_div(ITERATIONS).unwrap_or_default()
+}
+
+fn main() {
+    let html = Html::parse(&large_document()).expect("generated document is valid");
+    for filter in [
+        Filter::new().tag_name("li"),
+        Filter::new().attribute_value_contains("class", "item").depth(1),
+    ] {
+        let find = measure(|| html.to_found(&filter));
+        let filter_all = measure(|| html.to_filtered(&filter));
+        println!("{filter:?}\n    find:   {find:?}\n    filter: {filter_all:?}");
+    }
+}
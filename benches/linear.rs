@@ -0,0 +1,94 @@
+//! Benchmark of the operations on adversarial documents, to check that their
+//! time grows linearly with the size of the document.
+//!
+//! Run with `cargo bench --bench linear`. The benchmark panics if doubling
+//! the size of a document more than triples the time of an operation.
+
+#![expect(clippy::print_stdout, clippy::use_debug, reason = "benchmark report")]
+#![expect(clippy::expect_used, reason = "benchmark")]
+
+use core::hint::black_box;
+use core::time::Duration;
+use std::time::Instant;
+
+use html_filter::{AttributeEscaping, Html, ParseOptions};
+
+/// Number of times each measure is repeated.
+const ITERATIONS: u32 = 10;
+
+/// Maximum ratio between the times of a document and of a document twice
+/// smaller.
+///
+/// A linear operation doubles the time, and a quadratic one quadruples it.
+const MAX_RATIO: u32 = 3;
+
+/// Checks that the time of `run` on the documents built by `document` grows
+/// linearly with their size, starting at `size`.
+fn check_linear<D, T>(name: &str, size: usize, document: fn(usize) -> D, run: fn(&D) -> T) {
+    let mut previous: Option<Duration> = None;
+    for current in [size, size.saturating_mul(2), size.saturating_mul(4)] {
+        let input = document(current);
+        let time = measure(|| run(black_box(&input)));
+        println!("{name} of size {current}: {time:?}");
+        if let Some(before) = previous {
+            assert!(
+                time < before.saturating_mul(MAX_RATIO),
+                "{name} isn't linear: {before:?} then {time:?}"
+            );
+        }
+        previous = Some(time);
+    }
+}
+
+/// Measures the average time taken by `run`.
+fn measure<T, F: Fn() -> T>(run: F) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(run());
+    }
+    start.elapsed().checked_div(ITERATIONS).unwrap_or_default()
+}
+
+/// Builds a tree of nested tags, whose nodes are all at different depths.
+#[cfg(feature = "serialize")]
+fn nested_tags(size: usize) -> Html {
+    (0..size).fold(Html::text("a"), |child, _| Html::tag("b").child(child).build())
+}
+
+/// Builds a list of tags that define an attribute twice, that raise a
+/// diagnostic each.
+fn duplicate_attributes(size: usize) -> String {
+    "<p id='a' id='b'></p>\n".repeat(size)
+}
+
+/// Builds a list of links whose attribute values contain an unescaped `&`,
+/// that raise a warning each.
+fn unescaped_links(size: usize) -> String {
+    "<a href='?a=1&b=2'></a>\n".repeat(size)
+}
+
+/// Builds a long text, that the parser reads in a lot of small runs as it
+/// stops at every `-` to look for a comment.
+fn split_text(size: usize) -> String {
+    format!("<p>{}</p>", "a-".repeat(size))
+}
+
+fn main() {
+    check_linear("diagnosing the duplicate attributes", 10_000, duplicate_attributes, |input| {
+        Html::parse_with_diagnostics(input, ParseOptions::new())
+            .expect("generated document is valid")
+    });
+    #[cfg(feature = "serialize")]
+    check_linear("serialising nested tags into binary", 2000, nested_tags, Html::to_bin);
+    check_linear("parsing a split text", 20_000, split_text, |input| {
+        Html::parse(input).expect("generated document is valid")
+    });
+    check_linear("validating the attribute values", 10_000, unescaped_links, |input| {
+        let mut warnings = 0usize;
+        let options = ParseOptions::new()
+            .attribute_escaping(AttributeEscaping::Warn)
+            .on_warning(|_| warnings = warnings.saturating_add(1));
+        Html::parse_with(input, options).expect("generated document is valid");
+        warnings
+    });
+}
@@ -0,0 +1,84 @@
+//! Benchmark of [`Html::parse`] and [`Html::parse_borrowed`] on large
+//! documents, to compare the scalar search of the special characters with the
+//! one of the `simd` feature.
+//!
+//! Run with `cargo bench --bench parse`, then with
+//! `cargo bench --bench parse --features simd`, and compare the results.
+
+#![expect(clippy::print_stdout, clippy::use_debug, reason = "benchmark report")]
+#![expect(clippy::expect_used, reason = "benchmark")]
+
+use core::fmt::Write as _;
+use core::hint::black_box;
+use core::time::Duration;
+use std::fs::read_to_string;
+use std::time::Instant;
+
+use html_filter::Html;
+
+/// Number of times each measure is repeated.
+const ITERATIONS: u32 = 20;
+
+/// Number of times the fixture is repeated in the large documents.
+const REPETITIONS: usize = 200;
+
+/// Number of sections in the generated documents.
+const SECTIONS: usize = 5_000;
+
+/// Builds a large document, mostly made of long attribute values.
+fn attributes_document() -> String {
+    let mut html = String::from("<!DOCTYPE html><html><body>");
+    for idx in 0..SECTIONS {
+        write!(
+            html,
+            r#"<a href="https://example.com/articles/{idx}/a-rather-long-slug-for-the-article?utm_source=newsletter&amp;utm_medium=email" title='Read the article number {idx} about a lot of things'>{idx}</a>"#
+        )
+        .expect("writing to a string never fails");
+    }
+    html.push_str("</body></html>");
+    html
+}
+
+/// Measures the average time taken by `run`.
+fn measure<T, F: Fn() -> T>(run: F) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(run());
+    }
+    start.elapsed().checked_div(ITERATIONS).unwrap_or_default()
+}
+
+/// Builds a large document, mostly made of long texts and comments.
+fn text_document() -> String {
+    let mut html = String::from("<!DOCTYPE html><html><body>");
+    for idx in 0..SECTIONS {
+        write!(
+            html,
+            "<p>Paragraph {idx}: Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do \
+             eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, \
+             quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo.</p><!-- A \
+             comment that is long enough to be worth scanning quickly -->"
+        )
+        .expect("writing to a string never fails");
+    }
+    html.push_str("</body></html>");
+    html
+}
+
+fn main() {
+    let mode = if cfg!(feature = "simd") { "simd" } else { "scalar" };
+    let fixture = read_to_string("tests/data/index.html").expect("fixture exists");
+    for (name, html) in [
+        ("index.html", fixture.repeat(REPETITIONS)),
+        ("texts", text_document()),
+        ("attributes", attributes_document()),
+    ] {
+        let owned = measure(|| Html::parse(black_box(&html)).expect("fixture is valid"));
+        let borrowed =
+            measure(|| Html::parse_borrowed(black_box(&html)).expect("fixture is valid"));
+        println!(
+            "{name} ({} bytes, {mode})\n    owned:    {owned:?}\n    borrowed: {borrowed:?}",
+            html.len()
+        );
+    }
+}
@@ -0,0 +1,53 @@
+/*
+ * C interface of the html-filter crate, enabled with the `ffi` feature.
+ *
+ * The trees are opaque pointers, created by `hf_parse` or `hf_filter_selector`
+ * and released with `hf_free`. The strings are NUL-terminated and UTF-8
+ * encoded. The strings returned by `hf_inner_text` are released with
+ * `hf_free_string`. All the functions return NULL on failure, and accept NULL
+ * arguments.
+ */
+
+#ifndef HTML_FILTER_H
+#define HTML_FILTER_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Parsed html tree. */
+typedef struct HfHtml HfHtml;
+
+/*
+ * Filters a tree with a CSS compound selector, like `a.external[href]`, and
+ * returns a new tree with the matching tags.
+ *
+ * Returns NULL if an argument is NULL, or if the selector is invalid.
+ */
+HfHtml *hf_filter_selector(const HfHtml *html, const char *selector);
+
+/* Releases a tree. Nothing is done if html is NULL. */
+void hf_free(HfHtml *html);
+
+/* Releases a string returned by hf_inner_text. Nothing is done if text is NULL. */
+void hf_free_string(char *text);
+
+/*
+ * Returns the concatenation of the texts of a tree.
+ *
+ * Returns NULL if html is NULL, or if the texts contain a NUL character.
+ */
+char *hf_inner_text(const HfHtml *html);
+
+/*
+ * Parses a html string into a tree.
+ *
+ * Returns NULL if input is NULL, isn't valid UTF-8, or isn't valid html.
+ */
+HfHtml *hf_parse(const char *input);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* HTML_FILTER_H */
@@ -0,0 +1,229 @@
+//! Module to audit an [`Html`] tree, and warn about the markup that doesn't
+//! respect the html specification.
+//!
+//! The audits are weak validations: they don't reject the tree, but return
+//! warnings with the paths of the faulty nodes. This is useful to check trees
+//! that were assembled programmatically, before serialising them.
+//!
+//! The paths are the lists of the indices of the children to follow from the
+//! root to reach the nodes, as in [`Html::reparse_node`].
+
+use core::fmt;
+use core::slice::from_ref;
+
+use crate::Html;
+
+/// Interactive tags, that mustn't be nested in other interactive tags.
+const INTERACTIVE_TAGS: [&str; 9] =
+    ["a", "button", "details", "embed", "iframe", "input", "label", "select", "textarea"];
+
+/// Tags that must be directly inside one of the given tags.
+const PARENT_RULES: [(&str, &[&str]); 8] = [
+    ("dd", &["dl", "div"]),
+    ("dt", &["dl", "div"]),
+    ("li", &["menu", "ol", "ul"]),
+    ("option", &["datalist", "optgroup", "select"]),
+    ("tbody", &["table"]),
+    ("td", &["tr"]),
+    ("th", &["tr"]),
+    ("tr", &["table", "tbody", "tfoot", "thead"]),
+];
+
+/// Warning raised by an audit on a node of the tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    /// Description of the problem
+    message: String,
+    /// Path of the faulty node, from the root
+    path: Vec<usize>,
+}
+
+impl Warning {
+    /// Returns the description of the problem.
+    #[must_use]
+    pub const fn as_message(&self) -> &str {
+        self.message.as_str()
+    }
+
+    /// Returns the path of the faulty node.
+    ///
+    /// See [the module documentation](self) for more information on the
+    /// paths.
+    #[must_use]
+    pub fn as_path(&self) -> &[usize] {
+        &self.path
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = self.path.iter().map(ToString::to_string).collect::<Vec<_>>();
+        write!(f, "{} (at [{}])", self.message, path.join(", "))
+    }
+}
+
+/// Returns the children of the node.
+///
+/// The children of a vec are its nodes, and the children of a tag are the
+/// nodes of its content.
+fn children(node: &Html) -> &[Html] {
+    match node {
+        Html::Vec(vec) => vec,
+        Html::Tag { child, .. } => match &**child {
+            Html::Vec(vec) => vec,
+            Html::Empty => &[],
+            Html::Comment(_) | Html::Doctype { .. } | Html::Tag { .. } | Html::Text(_) =>
+                from_ref(&**child),
+        },
+        Html::Comment(_) | Html::Doctype { .. } | Html::Empty | Html::Text(_) => &[],
+    }
+}
+
+/// Checks that the tree respects the basic rules of the html content model.
+///
+/// The following rules are checked:
+///
+/// - the list items, table rows, table cells, options and description terms are
+///   directly inside their expected parents (e.g., `<li>` is in a `<menu>`, an
+///   `<ol>` or a `<ul>`, and `<td>` is in a `<tr>`);
+/// - the interactive tags (e.g., `<a>`, `<button>` or `<input>`) aren't nested
+///   inside other interactive tags.
+///
+/// The nodes at the root of the tree aren't checked against their parent,
+/// as the tree can be a fragment.
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::{audit, *};
+///
+/// let html = Html::parse(
+///     "<div><li>Item</li></div><button><a href='/'>Link</a></button><ul><li>Ok</li></ul>",
+/// )
+/// .unwrap();
+///
+/// let warnings = audit::structure(&html);
+/// assert_eq!(warnings.len(), 2);
+/// assert_eq!(
+///     warnings[0].to_string(),
+///     "<li> must be directly inside <menu>, <ol> or <ul>, not <div>. (at [0, 0])"
+/// );
+/// assert_eq!(warnings[1].as_message(), "<a> mustn't be inside the interactive tag <button>.");
+/// assert_eq!(warnings[1].as_path(), [1, 0]);
+/// ```
+#[must_use]
+pub fn structure(html: &Html) -> Vec<Warning> {
+    let mut warnings = vec![];
+    structure_aux(html, None, None, &mut vec![], &mut warnings);
+    warnings
+}
+
+/// Wrapper for [`structure`]
+///
+/// - `parent` is the name of the closest tag that contains the node;
+/// - `interactive` is the name of the closest interactive tag that contains the
+///   node;
+/// - `path` is the path of the node.
+fn structure_aux(
+    node: &Html,
+    parent: Option<&str>,
+    interactive: Option<&str>,
+    path: &mut Vec<usize>,
+    warnings: &mut Vec<Warning>,
+) {
+    let (child_parent, child_interactive) = match node {
+        Html::Tag { tag, .. } => {
+            let name = tag.as_name();
+            if let Some(parent_name) = parent
+                && let Some((_, parents)) = PARENT_RULES.iter().find(|(child, _)| *child == name)
+                && !parents.contains(&parent_name)
+            {
+                warnings.push(Warning {
+                    message: format!(
+                        "<{name}> must be directly inside {}, not <{parent_name}>.",
+                        format_names(parents)
+                    ),
+                    path: path.clone(),
+                });
+            }
+            let is_interactive = INTERACTIVE_TAGS.contains(&name);
+            if is_interactive && let Some(interactive_name) = interactive {
+                warnings.push(Warning {
+                    message: format!(
+                        "<{name}> mustn't be inside the interactive tag <{interactive_name}>."
+                    ),
+                    path: path.clone(),
+                });
+            }
+            (Some(name), if is_interactive { Some(name) } else { interactive })
+        }
+        Html::Comment(_) | Html::Doctype { .. } | Html::Empty | Html::Text(_) | Html::Vec(_) =>
+            (parent, interactive),
+    };
+    for (idx, child) in children(node).iter().enumerate() {
+        path.push(idx);
+        structure_aux(child, child_parent, child_interactive, path, warnings);
+        path.pop();
+    }
+}
+
+/// Formats a list of tag names, e.g. `<menu>, <ol> or <ul>`.
+fn format_names(names: &[&str]) -> String {
+    let mut formatted = String::new();
+    for (idx, name) in names.iter().enumerate() {
+        if idx != 0 {
+            formatted.push_str(if idx.saturating_add(1) == names.len() { " or " } else { ", " });
+        }
+        formatted.push('<');
+        formatted.push_str(name);
+        formatted.push('>');
+    }
+    formatted
+}
+
+/// Checks that the void elements, like `<br>` or `<img>`, don't have any
+/// content.
+///
+/// The parser never gives content to the void elements, but the trees parsed
+/// with a custom [`ParseOptions::void_tags`](crate::ParseOptions::void_tags),
+/// or assembled programmatically, can have some. See
+/// [`Html::check_void_elements`] to reject or repair them.
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::{audit, *};
+///
+/// let options = ParseOptions::new().void_tags(["br"]);
+/// let html = Html::parse_with("<p><img>Caption</img><br /></p>", options).unwrap();
+///
+/// let warnings = audit::void_elements(&html);
+/// assert_eq!(warnings.len(), 1);
+/// assert_eq!(warnings[0].to_string(), "Void element <img> has content. (at [0])");
+/// ```
+#[must_use]
+pub fn void_elements(html: &Html) -> Vec<Warning> {
+    let mut warnings = vec![];
+    void_elements_aux(html, &mut vec![], &mut warnings);
+    warnings
+}
+
+/// Wrapper for [`void_elements`]
+///
+/// `path` is the path of the node.
+fn void_elements_aux(node: &Html, path: &mut Vec<usize>, warnings: &mut Vec<Warning>) {
+    if let Html::Tag { tag, child } = node
+        && tag.is_void()
+        && !child.is_empty()
+    {
+        warnings.push(Warning {
+            message: format!("Void element <{}> has content.", tag.as_name()),
+            path: path.clone(),
+        });
+    }
+    for (idx, child) in children(node).iter().enumerate() {
+        path.push(idx);
+        void_elements_aux(child, path, warnings);
+        path.pop();
+    }
+}
@@ -0,0 +1,202 @@
+//! Module to define a budget, to abort the parsing or the filtering of
+//! pathological documents.
+
+use core::cell::Cell;
+use core::error::Error;
+use core::fmt;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::time::Duration;
+use std::time::Instant;
+
+/// Number of ticks between two checks of the deadline and of the cancel
+/// token, minus one.
+///
+/// This must be a power of two minus one, as it is used as a mask.
+const CHECK_MASK: usize = 0xff;
+
+/// Limits on the resources that parsing or filtering can use.
+///
+/// A budget is checked periodically by the parser (see
+/// [`ParseOptions::budget`](crate::ParseOptions::budget)) and by the filter
+/// walkers (see `Html::try_filter`, with the `filter` feature). When the
+/// budget is exceeded, the operation is aborted and a [`BudgetExceeded`]
+/// error is returned, instead of blocking the thread on a pathological
+/// document.
+///
+/// # Examples
+///
+/// ```
+/// use core::sync::atomic::AtomicBool;
+/// use core::time::Duration;
+///
+/// use html_filter::*;
+///
+/// let html = Html::parse("<ul><li>1</li><li>2</li><li>3</li></ul>").unwrap();
+///
+/// let budget = Budget::new().max_nodes(3);
+/// assert_eq!(
+///     html.try_to_filtered(&Filter::new().tag_name("li"), &budget),
+///     Err(BudgetExceeded::Nodes)
+/// );
+///
+/// let budget = Budget::new().max_nodes(100).timeout(Duration::from_secs(1));
+/// assert_eq!(
+///     html.try_to_filtered(&Filter::new().tag_name("li"), &budget).unwrap(),
+///     "<li>1</li><li>2</li><li>3</li>"
+/// );
+///
+/// let cancel = AtomicBool::new(true);
+/// let budget = Budget::new().cancel(&cancel);
+/// assert_eq!(html.try_filter(&Filter::new(), &budget), Err(BudgetExceeded::Cancelled));
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Budget<'cancel> {
+    /// Token that aborts the operation when it is set to `true`.
+    cancel: Option<&'cancel AtomicBool>,
+    /// Time after which the operation is aborted.
+    deadline: Option<Instant>,
+    /// Maximum number of nodes that can be visited.
+    max_nodes: Option<usize>,
+}
+
+impl<'cancel> Budget<'cancel> {
+    /// Sets a cancel token
+    ///
+    /// The operation is aborted as soon as possible after the token was set
+    /// to `true`, for instance by another thread.
+    #[must_use]
+    pub const fn cancel(mut self, token: &'cancel AtomicBool) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Sets the instant after which the operation is aborted.
+    #[must_use]
+    pub const fn deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Sets the maximum number of nodes that can be created by the parser or
+    /// visited by the filter.
+    #[must_use]
+    pub const fn max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = Some(max_nodes);
+        self
+    }
+
+    /// Creates an unlimited [`Budget`]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { cancel: None, deadline: None, max_nodes: None }
+    }
+
+    /// Sets the deadline to the given duration from now.
+    ///
+    /// If the deadline can't be represented, no deadline is set.
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.deadline = Instant::now().checked_add(timeout);
+        self
+    }
+}
+
+/// Reason why a [`Budget`] was exceeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetExceeded {
+    /// The cancel token was set.
+    Cancelled,
+    /// The deadline was reached.
+    Deadline,
+    /// The maximum number of nodes was reached.
+    Nodes,
+}
+
+impl fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Cancelled => "Budget exceeded: operation was cancelled.",
+            Self::Deadline => "Budget exceeded: deadline was reached.",
+            Self::Nodes => "Budget exceeded: maximum number of nodes was reached.",
+        })
+    }
+}
+
+impl Error for BudgetExceeded {}
+
+/// Consumption of a [`Budget`] during an operation.
+#[derive(Debug)]
+pub struct BudgetCounter<'budget> {
+    /// Budget to respect
+    budget: &'budget Budget<'budget>,
+    /// Reason of the failure, if the budget was exceeded.
+    exceeded: Cell<Option<BudgetExceeded>>,
+    /// Number of nodes visited so far
+    nodes: Cell<usize>,
+    /// Number of ticks so far, to check the time periodically.
+    ticks: Cell<usize>,
+}
+
+impl<'budget> BudgetCounter<'budget> {
+    /// Returns the reason of the failure, if the budget was exceeded.
+    pub const fn exceeded(&self) -> Option<BudgetExceeded> {
+        self.exceeded.get()
+    }
+
+    /// Creates a counter to follow the consumption of a budget.
+    #[cfg(feature = "filter")]
+    pub const fn new(budget: &'budget Budget<'budget>) -> Self {
+        Self::resume(budget, 0)
+    }
+
+    /// Returns the number of nodes visited so far.
+    pub const fn nodes(&self) -> usize {
+        self.nodes.get()
+    }
+
+    /// Creates a counter to follow the consumption of a budget, when the
+    /// given number of nodes were already visited.
+    pub const fn resume(budget: &'budget Budget<'budget>, nodes: usize) -> Self {
+        Self { budget, exceeded: Cell::new(None), nodes: Cell::new(nodes), ticks: Cell::new(0) }
+    }
+
+    /// Records a step of work, and checks periodically the deadline and the
+    /// cancel token.
+    ///
+    /// # Returns
+    ///
+    /// `true` iff the budget isn't exceeded.
+    pub fn tick(&self) -> bool {
+        if self.exceeded.get().is_some() {
+            return false;
+        }
+        let ticks = self.ticks.get();
+        self.ticks.set(ticks.wrapping_add(1));
+        if ticks & CHECK_MASK == 0 {
+            if self.budget.cancel.is_some_and(|token| token.load(Ordering::Relaxed)) {
+                self.exceeded.set(Some(BudgetExceeded::Cancelled));
+            } else if self.budget.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                self.exceeded.set(Some(BudgetExceeded::Deadline));
+            } else {
+                return true;
+            }
+            return false;
+        }
+        true
+    }
+
+    /// Records the visit of a node.
+    ///
+    /// # Returns
+    ///
+    /// `true` iff the budget isn't exceeded.
+    pub fn visit(&self) -> bool {
+        let nodes = self.nodes.get().saturating_add(1);
+        self.nodes.set(nodes);
+        if self.budget.max_nodes.is_some_and(|max_nodes| nodes > max_nodes) {
+            self.exceeded.set(Some(BudgetExceeded::Nodes));
+            return false;
+        }
+        self.tick()
+    }
+}
@@ -0,0 +1,109 @@
+//! Module to handle the control characters of the input, like `\0`, that
+//! aren't allowed in html documents.
+
+use alloc::borrow::Cow;
+
+use crate::types::tag::Attribute;
+use crate::{Html, Tag};
+
+/// Character that replaces the control characters, `U+FFFD REPLACEMENT
+/// CHARACTER`.
+const REPLACEMENT_CHARACTER: char = '\u{fffd}';
+
+/// Behaviour to adopt when the input contains control characters, like `\0`
+/// or `\x1b`.
+///
+/// The control characters are the characters of the `Cc` unicode category,
+/// except the tabulations, the line feeds, the form feeds and the carriage
+/// returns, that are whitespace in html.
+///
+/// Whatever the policy, the control characters are never displayed as they
+/// are: they are encoded as character references, like `&#x1B;`, where the
+/// references are decoded, i.e., in the texts and in the attribute values.
+/// Everywhere else, they are replaced by `U+FFFD`: in the names of the tags
+/// and of the attributes, in the doctypes, in the comments and in the
+/// contents of the `<script>` and `<style>` tags.
+///
+/// See [`ParseOptions::control_policy`](crate::ParseOptions::control_policy).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ControlPolicy {
+    /// Returns an error on the first control character.
+    Error,
+    /// Keeps the control characters in the tree.
+    #[default]
+    Keep,
+    /// Replaces the control characters by `U+FFFD REPLACEMENT CHARACTER`, as
+    /// browsers do for `\0`.
+    Replace,
+    /// Removes the control characters.
+    Strip,
+}
+
+/// Removes or replaces the control characters of the attribute names and
+/// values of a tag, depending on the policy.
+pub fn clean_attributes(tag: &mut Tag, policy: ControlPolicy) {
+    for attr in &mut tag.attrs {
+        match attr {
+            Attribute::NameNoValue(name) =>
+                if let Cow::Owned(cleaned) = clean_controls(name.as_str(), policy) {
+                    *name = cleaned.into();
+                },
+            Attribute::NameValue { name, value, .. } => {
+                if let Cow::Owned(cleaned) = clean_controls(name.as_str(), policy) {
+                    *name = cleaned.into();
+                }
+                if let Cow::Owned(cleaned) = clean_controls(value, policy) {
+                    *value = cleaned;
+                }
+            }
+        }
+    }
+}
+
+/// Removes or replaces the control characters of a text, depending on the
+/// policy.
+///
+/// The text is only copied if it contains control characters and the policy
+/// is [`ControlPolicy::Replace`] or [`ControlPolicy::Strip`].
+pub fn clean_controls(text: &str, policy: ControlPolicy) -> Cow<'_, str> {
+    let replacement = match policy {
+        ControlPolicy::Error | ControlPolicy::Keep => return Cow::Borrowed(text),
+        ControlPolicy::Replace => Some(REPLACEMENT_CHARACTER),
+        ControlPolicy::Strip => None,
+    };
+    if !text.contains(is_control) {
+        return Cow::Borrowed(text);
+    }
+    Cow::Owned(
+        text.chars().filter_map(|ch| if is_control(ch) { replacement } else { Some(ch) }).collect(),
+    )
+}
+
+/// Removes or replaces the control characters of the texts and comments of a
+/// tree, depending on the policy.
+pub fn clean_texts(html: &mut Html, policy: ControlPolicy) {
+    match html {
+        Html::Comment(comment) =>
+            if let Cow::Owned(cleaned) = clean_controls(comment, policy) {
+                *comment = cleaned;
+            },
+        Html::Tag { child, .. } => clean_texts(child, policy),
+        Html::Text(text) =>
+            if let Cow::Owned(cleaned) = clean_controls(text.as_str(), policy) {
+                *text = cleaned.into();
+            },
+        Html::Vec(vec) => vec.iter_mut().for_each(|child| clean_texts(child, policy)),
+        Html::Doctype { .. } | Html::Empty => (),
+    }
+}
+
+/// Returns the byte offset and the value of the first control character of a
+/// text, if any.
+pub fn find_control(text: &str) -> Option<(usize, char)> {
+    text.char_indices().find(|(_, ch)| is_control(*ch))
+}
+
+/// Checks if a character is a control character that isn't whitespace.
+pub fn is_control(ch: char) -> bool {
+    ch.is_control() && !matches!(ch, '\t' | '\n' | '\x0C' | '\r')
+}
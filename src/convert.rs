@@ -0,0 +1,407 @@
+//! Module to convert an [`Html`] tree to other markup languages, like
+//! Markdown.
+//!
+//! The conversion is lossy: the tags that have no equivalent are replaced by
+//! their content, and the scripts, the styles and the metadata are dropped.
+
+use core::iter::repeat_n;
+use core::slice::from_ref;
+
+use crate::transform::collapse_whitespace;
+use crate::{Html, Tag, decode_entities};
+
+/// Tags whose content starts and ends a block, like a paragraph.
+const BLOCK_TAGS: [&str; 25] = [
+    "address",
+    "article",
+    "aside",
+    "body",
+    "dd",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "header",
+    "html",
+    "li",
+    "main",
+    "nav",
+    "p",
+    "section",
+    "summary",
+    "td",
+    "th",
+    "tr",
+];
+
+/// Characters escaped in the texts, as they have a meaning in Markdown.
+const ESCAPED_CHARS: [char; 7] = ['*', '<', '[', '\\', ']', '_', '`'];
+
+/// Tags whose content isn't displayed, and is dropped from the Markdown.
+const HIDDEN_TAGS: [&str; 6] = ["head", "noscript", "script", "style", "template", "title"];
+
+/// Characters that start a block when they begin a line, followed by a space.
+const LINE_MARKERS: [char; 4] = ['#', '+', '-', '>'];
+
+/// Converter of a tree to Markdown.
+///
+/// The blocks are converted independently and separated by blank lines, and
+/// the inline content is accumulated until the end of its block.
+#[derive(Default)]
+struct Markdown {
+    /// Blocks that were written, in the order of the document
+    blocks: Vec<String>,
+    /// Inline content of the current block, where the line breaks are `\n`
+    inline: String,
+}
+
+impl Markdown {
+    /// Converts a tree, and returns its blocks.
+    fn blocks(html: &Html) -> Vec<String> {
+        let mut markdown = Self::default();
+        markdown.push_node(html);
+        markdown.flush();
+        markdown.blocks
+    }
+
+    /// Ends the current block, and writes its inline content as a paragraph.
+    ///
+    /// The whitespace is collapsed, and the line breaks are written as hard
+    /// breaks.
+    fn flush(&mut self) {
+        let paragraph = self
+            .inline
+            .split('\n')
+            .map(|line| collapse_whitespace(line.trim_ascii()))
+            .filter(|line| !line.is_empty())
+            .map(|line| escape_line_marker(&line))
+            .collect::<Vec<_>>()
+            .join("\\\n");
+        self.inline.clear();
+        if !paragraph.is_empty() {
+            self.blocks.push(paragraph);
+        }
+    }
+
+    /// Converts a tree, and returns its content on a single line.
+    fn inline(html: &Html) -> String {
+        Self::blocks(html).join(" ")
+    }
+
+    /// Writes a block, after the current one.
+    fn push_block(&mut self, block: String) {
+        self.flush();
+        if !block.is_empty() {
+            self.blocks.push(block);
+        }
+    }
+
+    /// Writes inline content surrounded by a delimiter, like `**` for the
+    /// bold texts.
+    ///
+    /// The spaces at the edges of the content are moved outside of the
+    /// delimiters, as Markdown ignores delimiters that are next to a space.
+    fn push_delimited(&mut self, delimiter: &str, child: &Html) {
+        let content = Self::inline(child);
+        if content.is_empty() {
+            return;
+        }
+        let text = child.iter().filter_map(Html::as_text).collect::<String>();
+        if text.starts_with(|ch: char| ch.is_ascii_whitespace()) {
+            self.inline.push(' ');
+        }
+        self.inline.push_str(delimiter);
+        self.inline.push_str(&content);
+        self.inline.push_str(delimiter);
+        if text.ends_with(|ch: char| ch.is_ascii_whitespace()) {
+            self.inline.push(' ');
+        }
+    }
+
+    /// Writes a link, or only its content if it has no target.
+    fn push_link(&mut self, tag: &Tag, child: &Html) {
+        let content = Self::inline(child);
+        let Some(href) = tag.find_attr_value("href").filter(|href| !href.is_empty()) else {
+            self.inline.push_str(&content);
+            return;
+        };
+        self.inline.push('[');
+        self.inline.push_str(&content);
+        self.inline.push_str("](");
+        push_destination(&mut self.inline, &decode_entities(href));
+        if let Some(title) = tag.find_attr_value("title") {
+            self.inline.push_str(" \"");
+            self.inline.push_str(&decode_entities(title).replace('"', "\\\""));
+            self.inline.push('"');
+        }
+        self.inline.push(')');
+    }
+
+    /// Writes a node, and its children.
+    fn push_node(&mut self, html: &Html) {
+        match html {
+            Html::Comment(_) | Html::Doctype { .. } | Html::Empty => (),
+            Html::Tag { tag, child } => self.push_tag(tag, child),
+            Html::Text(text) => push_escaped(&mut self.inline, &decode_entities(text.as_str())),
+            Html::Vec(vec) =>
+                for node in vec {
+                    self.push_node(node);
+                },
+        }
+    }
+
+    /// Writes a tag, with the Markdown equivalent of its name.
+    fn push_tag(&mut self, tag: &Tag, child: &Html) {
+        let name = tag.as_name();
+        match name {
+            "a" => self.push_link(tag, child),
+            "b" | "strong" => self.push_delimited("**", child),
+            "blockquote" => self.push_block(blockquote(child)),
+            "br" => self.inline.push('\n'),
+            "code" => push_code_span(&mut self.inline, &raw_text(child)),
+            "del" | "s" | "strike" => self.push_delimited("~~", child),
+            "em" | "i" => self.push_delimited("*", child),
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => self.push_block(heading(name, child)),
+            "hr" => self.push_block("---".to_owned()),
+            "img" => push_image(&mut self.inline, tag),
+            "ol" | "ul" => self.push_block(list(tag, child)),
+            "pre" => self.push_block(code_block(child)),
+            "table" => self.push_block(table(child)),
+            _ if HIDDEN_TAGS.contains(&name) => (),
+            _ if BLOCK_TAGS.contains(&name) => {
+                self.flush();
+                self.push_node(child);
+                self.flush();
+            }
+            _ => self.push_node(child),
+        }
+    }
+}
+
+impl Html {
+    /// Converts the tree to Markdown.
+    ///
+    /// The headings, paragraphs, emphasis, links, images, lists, code blocks,
+    /// quotes and tables are converted to their Markdown equivalent, with the
+    /// `GitHub` flavour for the tables and the strikethrough texts. The other
+    /// tags are replaced by their content, and the scripts, the styles and the
+    /// `<head>` are dropped.
+    ///
+    /// The entities of the texts are decoded, and the characters that have a
+    /// meaning in Markdown are escaped. The whitespace is collapsed, except in
+    /// the `<pre>` tags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     r#"<article><h1>Title</h1><p>Some <b>bold</b> and <a href="/x">linked</a> text.</p><ul><li>One</li><li>Two</li></ul></article>"#,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(
+    ///     html.to_markdown(),
+    ///     "# Title\n\nSome **bold** and [linked](/x) text.\n\n- One\n- Two\n"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = Markdown::blocks(self).join("\n\n");
+        if !markdown.is_empty() {
+            markdown.push('\n');
+        }
+        markdown
+    }
+}
+
+/// Converts a `<blockquote>`, prefixing every line with `>`.
+fn blockquote(child: &Html) -> String {
+    Markdown::blocks(child)
+        .join("\n\n")
+        .lines()
+        .map(|line| if line.is_empty() { ">".to_owned() } else { format!("> {line}") })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Converts a `<pre>` to a fenced code block, with the language of its
+/// `<code>` tag, if it has a `language-` or `lang-` class.
+fn code_block(child: &Html) -> String {
+    let text = raw_text(child);
+    let code = text.strip_prefix('\n').unwrap_or(&text).trim_end_matches('\n');
+    let language = child
+        .iter_tags()
+        .filter(|(tag, _)| tag.as_name() == "code")
+        .filter_map(|(tag, _)| tag.find_attr_value("class"))
+        .flat_map(|class| class.split_whitespace())
+        .find_map(|class| class.strip_prefix("language-").or_else(|| class.strip_prefix("lang-")))
+        .unwrap_or_default();
+    let fence = "`".repeat(longest_backtick_run(code).saturating_add(1).max(3));
+    format!("{fence}{language}\n{code}\n{fence}")
+}
+
+/// Escapes the characters that start a block when they begin a line, like a
+/// `#` or a `-` followed by a space.
+fn escape_line_marker(line: &str) -> String {
+    let mut chars = line.chars();
+    if chars.next().is_some_and(|first| LINE_MARKERS.contains(&first))
+        && chars.next().is_none_or(char::is_whitespace)
+    {
+        format!("\\{line}")
+    } else {
+        line.to_owned()
+    }
+}
+
+/// Converts a heading, with as many `#` as its level.
+fn heading(name: &str, child: &Html) -> String {
+    let content = Markdown::inline(child);
+    if content.is_empty() {
+        return String::new();
+    }
+    let level = name.strip_prefix('h').and_then(|level| level.parse().ok()).unwrap_or(1);
+    format!("{} {content}", "#".repeat(level))
+}
+
+/// Converts a `<ol>` or `<ul>`, with a `-` for the unordered items, and the
+/// numbers for the ordered ones, starting at the `start` attribute.
+///
+/// The continuation lines of the items, like their nested lists, are
+/// indented to their content.
+fn list(tag: &Tag, child: &Html) -> String {
+    let ordered = tag.as_name() == "ol";
+    let mut number = tag
+        .find_attr_value("start")
+        .and_then(|start| start.trim().parse::<usize>().ok())
+        .unwrap_or(1);
+    let mut items = vec![];
+    for (item, item_child) in nodes(child).iter().filter_map(Html::as_tag) {
+        if item.as_name() != "li" {
+            continue;
+        }
+        let marker = if ordered { format!("{number}. ") } else { "- ".to_owned() };
+        number = number.saturating_add(1);
+        let indent = " ".repeat(marker.len());
+        let content = Markdown::blocks(item_child).join("\n");
+        let mut lines = content.lines();
+        let mut rendered = marker;
+        rendered.push_str(lines.next().unwrap_or_default());
+        for line in lines {
+            rendered.push('\n');
+            if !line.is_empty() {
+                rendered.push_str(&indent);
+                rendered.push_str(line);
+            }
+        }
+        items.push(rendered);
+    }
+    items.join("\n")
+}
+
+/// Returns the length of the longest run of backticks of a text.
+fn longest_backtick_run(text: &str) -> usize {
+    text.split(|ch| ch != '`').map(str::len).max().unwrap_or_default()
+}
+
+/// Returns the nodes of the child of a tag.
+fn nodes(child: &Html) -> &[Html] {
+    child.as_vec().unwrap_or_else(|| from_ref(child))
+}
+
+/// Writes an inline code, with enough backticks to contain the backticks of
+/// the code.
+fn push_code_span(output: &mut String, code: &str) {
+    let collapsed = collapse_whitespace(code.trim_ascii());
+    if collapsed.is_empty() {
+        return;
+    }
+    let fence = "`".repeat(longest_backtick_run(&collapsed).saturating_add(1));
+    let padding = if collapsed.starts_with('`') || collapsed.ends_with('`') { " " } else { "" };
+    for part in [fence.as_str(), padding, &collapsed, padding, &fence] {
+        output.push_str(part);
+    }
+}
+
+/// Writes the target of a link or an image, between `<>` if it contains
+/// spaces or parentheses.
+fn push_destination(output: &mut String, url: &str) {
+    if url.contains(|ch: char| ch.is_whitespace() || ch == '(' || ch == ')') {
+        output.push('<');
+        output.push_str(url);
+        output.push('>');
+    } else {
+        output.push_str(url);
+    }
+}
+
+/// Writes a text, escaping the characters that have a meaning in Markdown.
+fn push_escaped(output: &mut String, text: &str) {
+    for ch in text.chars() {
+        if ESCAPED_CHARS.contains(&ch) {
+            output.push('\\');
+        }
+        output.push(if ch.is_ascii_whitespace() { ' ' } else { ch });
+    }
+}
+
+/// Writes an `<img>`, with its `alt` as description, if it has a `src`.
+fn push_image(output: &mut String, tag: &Tag) {
+    let Some(src) = tag.find_attr_value("src").filter(|src| !src.is_empty()) else { return };
+    output.push_str("![");
+    if let Some(alt) = tag.find_attr_value("alt") {
+        push_escaped(output, &decode_entities(alt));
+    }
+    output.push_str("](");
+    push_destination(output, &decode_entities(src));
+    output.push(')');
+}
+
+/// Returns the texts of a tree, with their entities decoded.
+fn raw_text(html: &Html) -> String {
+    html.iter().filter_map(Html::as_text).map(decode_entities).collect()
+}
+
+/// Converts a `<table>`, with its first row as header.
+///
+/// The rows of the nested tables are ignored, and the missing cells are left
+/// empty.
+fn table(child: &Html) -> String {
+    let mut rows = vec![];
+    table_rows(child, &mut rows);
+    let columns = rows.iter().map(Vec::len).max().unwrap_or_default();
+    if columns == 0 {
+        return String::new();
+    }
+    let mut lines = rows.into_iter().map(|mut row| {
+        row.resize(columns, String::new());
+        format!("| {} |", row.join(" | "))
+    });
+    let header = lines.next().unwrap_or_default();
+    let separator = format!("|{}", repeat_n(" --- |", columns).collect::<String>());
+    [header, separator].into_iter().chain(lines).collect::<Vec<_>>().join("\n")
+}
+
+/// Collects the cells of the rows of a table, without entering the nested
+/// tables.
+fn table_rows(html: &Html, rows: &mut Vec<Vec<String>>) {
+    for (tag, child) in nodes(html).iter().filter_map(Html::as_tag) {
+        match tag.as_name() {
+            "table" => (),
+            "tr" => rows.push(
+                nodes(child)
+                    .iter()
+                    .filter_map(Html::as_tag)
+                    .filter(|(cell, _)| matches!(cell.as_name(), "td" | "th"))
+                    .map(|(_, cell_child)| Markdown::inline(cell_child).replace('|', "\\|"))
+                    .collect(),
+            ),
+            _ => table_rows(child, rows),
+        }
+    }
+}
@@ -0,0 +1,243 @@
+//! Module to replay an input through every stage of the crate, to reproduce
+//! and share the bugs found by fuzzing or in production.
+//!
+//! [`replay`] runs the same pipeline on every input: it parses the bytes,
+//! filters the tree with a fixed filter, serialises the result and parses it
+//! again. The result of every stage is recorded in a [`Replay`], even if a
+//! stage panics, so that a failing input can be minimised by checking that
+//! it still fails at the same stage.
+
+use core::any::Any;
+use core::fmt;
+use core::panic::AssertUnwindSafe;
+use std::panic::catch_unwind;
+
+use crate::{Filter, Html};
+
+/// Outcome of a [`Stage`] of a [`Replay`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// The stage returned an error, with the given message.
+    Failed(String),
+    /// The stage panicked, with the given message.
+    Panicked(String),
+    /// The stage succeeded.
+    Passed,
+    /// The stage wasn't run, because a previous stage didn't succeed.
+    Skipped,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Failed(message) => write!(f, "failed: {message}"),
+            Self::Panicked(message) => write!(f, "panicked: {}", message.trim()),
+            Self::Passed => f.write_str("passed"),
+            Self::Skipped => f.write_str("skipped"),
+        }
+    }
+}
+
+/// Results of the stages of the pipeline run on an input by [`replay`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replay {
+    /// Input, decoded as UTF-8
+    input: String,
+    /// Serialised output of the filtered tree, if the stage succeeded
+    output: Option<String>,
+    /// Results of the stages, in the order of the pipeline
+    stages: Vec<StageResult>,
+}
+
+impl Replay {
+    /// Returns the first stage that didn't succeed, if any.
+    #[must_use]
+    pub fn failure(&self) -> Option<&StageResult> {
+        self.stages.iter().find(|result| !matches!(result.outcome, Outcome::Passed))
+    }
+
+    /// Returns the replayed input.
+    ///
+    /// The invalid UTF-8 sequences of the bytes are replaced by
+    /// `U+FFFD REPLACEMENT CHARACTER`.
+    #[must_use]
+    pub const fn input(&self) -> &str {
+        self.input.as_str()
+    }
+
+    /// Checks if every stage succeeded.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.failure().is_none()
+    }
+
+    /// Returns the serialised output of the filtered tree, if the
+    /// [`Stage::Serialize`] stage succeeded.
+    #[must_use]
+    pub fn output(&self) -> Option<&str> {
+        self.output.as_deref()
+    }
+
+    /// Returns the results of the stages, in the order of the pipeline.
+    #[must_use]
+    pub fn stages(&self) -> &[StageResult] {
+        &self.stages
+    }
+}
+
+impl fmt::Display for Replay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "input: \"{}\"", self.input.escape_debug())?;
+        self.stages.iter().try_for_each(|result| write!(f, "\n{result}"))?;
+        if let Some(output) = &self.output {
+            write!(f, "\noutput: \"{}\"", output.escape_debug())?;
+        }
+        Ok(())
+    }
+}
+
+/// Stage of the pipeline run by [`replay`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stage {
+    /// Filters the parsed tree with the fixed filter of [`replay`].
+    Filter,
+    /// Parses the input.
+    Parse,
+    /// Parses the serialised output again, and checks that it is serialised
+    /// to the same string.
+    Reparse,
+    /// Serialises the filtered tree into an html string.
+    Serialize,
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Filter => "filter",
+            Self::Parse => "parse",
+            Self::Reparse => "reparse",
+            Self::Serialize => "serialize",
+        })
+    }
+}
+
+/// Result of a [`Stage`] of a [`Replay`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageResult {
+    /// Outcome of the stage
+    outcome: Outcome,
+    /// Stage that was run
+    stage: Stage,
+}
+
+impl StageResult {
+    /// Returns the outcome of the stage.
+    #[must_use]
+    pub const fn outcome(&self) -> &Outcome {
+        &self.outcome
+    }
+
+    /// Returns the stage that was run.
+    #[must_use]
+    pub const fn stage(&self) -> Stage {
+        self.stage
+    }
+}
+
+impl fmt::Display for StageResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.stage, self.outcome)
+    }
+}
+
+/// Returns the message of a caught panic.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| (*message).to_owned())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic payload".to_owned())
+}
+
+/// Replays an input through the whole pipeline of the crate, and returns the
+/// results of every stage.
+///
+/// The pipeline is always the same, to make the results deterministic:
+///
+/// 1. [`Stage::Parse`]: the bytes are decoded as UTF-8, replacing the invalid
+///    sequences, and parsed with [`Html::parse`];
+/// 2. [`Stage::Filter`]: the tree is filtered with
+///    [`Filter::all_except_comment`];
+/// 3. [`Stage::Serialize`]: the filtered tree is converted into a string;
+/// 4. [`Stage::Reparse`]: the string is parsed again, and must give the same
+///    string once serialised.
+///
+/// The panics of the stages are caught and recorded as
+/// [`Outcome::Panicked`]. The stages after a stage that didn't succeed are
+/// [`Outcome::Skipped`].
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::debug::{Outcome, Stage, replay};
+///
+/// let passed = replay(b"<p>Text<!-- comment --></p>");
+/// assert!(passed.is_ok());
+/// assert_eq!(passed.output(), Some("<p>Text</p>"));
+///
+/// let failed = replay(b"<p>Text</a>");
+/// let failure = failed.failure().unwrap();
+/// assert_eq!(failure.stage(), Stage::Parse);
+/// assert!(matches!(failure.outcome(), Outcome::Failed(_)));
+/// assert_eq!(failed.stages()[1].outcome(), &Outcome::Skipped);
+/// ```
+#[must_use]
+pub fn replay(bytes: &[u8]) -> Replay {
+    let input = String::from_utf8_lossy(bytes).into_owned();
+    let mut stages = Vec::with_capacity(4);
+    let parsed =
+        run(&mut stages, Stage::Parse, || Html::parse(&input).map_err(|err| err.to_string()));
+    let filtered = parsed.and_then(|html| {
+        run(&mut stages, Stage::Filter, || Ok(html.filter(&Filter::new().all_except_comment())))
+    });
+    let output =
+        filtered.and_then(|html| run(&mut stages, Stage::Serialize, || Ok(html.to_string())));
+    if let Some(serialized) = &output {
+        run(&mut stages, Stage::Reparse, || {
+            let reparsed = Html::parse(serialized).map_err(|err| err.to_string())?.to_string();
+            if reparsed == *serialized {
+                Ok(())
+            } else {
+                Err(format!(
+                    "The output changed when parsed again, into \"{}\".",
+                    reparsed.escape_debug()
+                ))
+            }
+        });
+    }
+    for stage in [Stage::Parse, Stage::Filter, Stage::Serialize, Stage::Reparse] {
+        if !stages.iter().any(|result| result.stage == stage) {
+            stages.push(StageResult { outcome: Outcome::Skipped, stage });
+        }
+    }
+    Replay { input, output, stages }
+}
+
+/// Runs a stage, catching its panics, and records its result.
+///
+/// # Returns
+///
+/// The value returned by the stage, if it succeeded.
+fn run<T, F: FnOnce() -> Result<T, String>>(
+    stages: &mut Vec<StageResult>,
+    stage: Stage,
+    callback: F,
+) -> Option<T> {
+    let (outcome, value) = match catch_unwind(AssertUnwindSafe(callback)) {
+        Ok(Ok(value)) => (Outcome::Passed, Some(value)),
+        Ok(Err(message)) => (Outcome::Failed(message), None),
+        Err(payload) => (Outcome::Panicked(panic_message(&*payload)), None),
+    };
+    stages.push(StageResult { outcome, stage });
+    value
+}
@@ -0,0 +1,392 @@
+//! Module to decode and encode the character references of html texts, like
+//! `&amp;` or `&#x27;`.
+
+use alloc::borrow::Cow;
+
+use crate::control::is_control;
+use crate::types::tag::Attribute;
+use crate::{Html, ParseOptions, Tag};
+
+/// Named character references that are decoded, sorted by name.
+///
+/// This contains the references of the Latin-1 characters, and the most
+/// common typographic, mathematical and greek ones.
+const ENTITIES: [(&str, char); 206] = [
+    ("AElig", '\u{c6}'),
+    ("Aacute", '\u{c1}'),
+    ("Acirc", '\u{c2}'),
+    ("Agrave", '\u{c0}'),
+    ("Aring", '\u{c5}'),
+    ("Atilde", '\u{c3}'),
+    ("Auml", '\u{c4}'),
+    ("Ccedil", '\u{c7}'),
+    ("Dagger", '\u{2021}'),
+    ("Delta", '\u{394}'),
+    ("ETH", '\u{d0}'),
+    ("Eacute", '\u{c9}'),
+    ("Ecirc", '\u{ca}'),
+    ("Egrave", '\u{c8}'),
+    ("Euml", '\u{cb}'),
+    ("Iacute", '\u{cd}'),
+    ("Icirc", '\u{ce}'),
+    ("Igrave", '\u{cc}'),
+    ("Iuml", '\u{cf}'),
+    ("NewLine", '\u{a}'),
+    ("Ntilde", '\u{d1}'),
+    ("OElig", '\u{152}'),
+    ("Oacute", '\u{d3}'),
+    ("Ocirc", '\u{d4}'),
+    ("Ograve", '\u{d2}'),
+    ("Omega", '\u{3a9}'),
+    ("Oslash", '\u{d8}'),
+    ("Otilde", '\u{d5}'),
+    ("Ouml", '\u{d6}'),
+    ("Prime", '\u{2033}'),
+    ("Scaron", '\u{160}'),
+    ("THORN", '\u{de}'),
+    ("Tab", '\u{9}'),
+    ("Uacute", '\u{da}'),
+    ("Ucirc", '\u{db}'),
+    ("Ugrave", '\u{d9}'),
+    ("Uuml", '\u{dc}'),
+    ("Yacute", '\u{dd}'),
+    ("Yuml", '\u{178}'),
+    ("aacute", '\u{e1}'),
+    ("acirc", '\u{e2}'),
+    ("acute", '\u{b4}'),
+    ("aelig", '\u{e6}'),
+    ("agrave", '\u{e0}'),
+    ("alefsym", '\u{2135}'),
+    ("alpha", '\u{3b1}'),
+    ("amp", '&'),
+    ("and", '\u{2227}'),
+    ("apos", '\''),
+    ("aring", '\u{e5}'),
+    ("asymp", '\u{2248}'),
+    ("atilde", '\u{e3}'),
+    ("auml", '\u{e4}'),
+    ("bdquo", '\u{201e}'),
+    ("beta", '\u{3b2}'),
+    ("brvbar", '\u{a6}'),
+    ("bull", '\u{2022}'),
+    ("cap", '\u{2229}'),
+    ("ccedil", '\u{e7}'),
+    ("cedil", '\u{b8}'),
+    ("cent", '\u{a2}'),
+    ("check", '\u{2713}'),
+    ("circ", '\u{2c6}'),
+    ("clubs", '\u{2663}'),
+    ("colon", ':'),
+    ("copy", '\u{a9}'),
+    ("crarr", '\u{21b5}'),
+    ("cup", '\u{222a}'),
+    ("curren", '\u{a4}'),
+    ("dArr", '\u{21d3}'),
+    ("dagger", '\u{2020}'),
+    ("darr", '\u{2193}'),
+    ("deg", '\u{b0}'),
+    ("delta", '\u{3b4}'),
+    ("diams", '\u{2666}'),
+    ("divide", '\u{f7}'),
+    ("eacute", '\u{e9}'),
+    ("ecirc", '\u{ea}'),
+    ("egrave", '\u{e8}'),
+    ("empty", '\u{2205}'),
+    ("emsp", '\u{2003}'),
+    ("ensp", '\u{2002}'),
+    ("equiv", '\u{2261}'),
+    ("eth", '\u{f0}'),
+    ("euml", '\u{eb}'),
+    ("euro", '\u{20ac}'),
+    ("exist", '\u{2203}'),
+    ("fnof", '\u{192}'),
+    ("forall", '\u{2200}'),
+    ("frac12", '\u{bd}'),
+    ("frac14", '\u{bc}'),
+    ("frac34", '\u{be}'),
+    ("frasl", '\u{2044}'),
+    ("gamma", '\u{3b3}'),
+    ("ge", '\u{2265}'),
+    ("gt", '>'),
+    ("hArr", '\u{21d4}'),
+    ("harr", '\u{2194}'),
+    ("hearts", '\u{2665}'),
+    ("hellip", '\u{2026}'),
+    ("iacute", '\u{ed}'),
+    ("icirc", '\u{ee}'),
+    ("iexcl", '\u{a1}'),
+    ("igrave", '\u{ec}'),
+    ("image", '\u{2111}'),
+    ("infin", '\u{221e}'),
+    ("int", '\u{222b}'),
+    ("iquest", '\u{bf}'),
+    ("isin", '\u{2208}'),
+    ("iuml", '\u{ef}'),
+    ("lArr", '\u{21d0}'),
+    ("lang", '\u{27e8}'),
+    ("laquo", '\u{ab}'),
+    ("larr", '\u{2190}'),
+    ("lceil", '\u{2308}'),
+    ("ldquo", '\u{201c}'),
+    ("le", '\u{2264}'),
+    ("lfloor", '\u{230a}'),
+    ("loz", '\u{25ca}'),
+    ("lrm", '\u{200e}'),
+    ("lsaquo", '\u{2039}'),
+    ("lsquo", '\u{2018}'),
+    ("lt", '<'),
+    ("macr", '\u{af}'),
+    ("mdash", '\u{2014}'),
+    ("micro", '\u{b5}'),
+    ("middot", '\u{b7}'),
+    ("minus", '\u{2212}'),
+    ("mu", '\u{3bc}'),
+    ("nabla", '\u{2207}'),
+    ("nbsp", '\u{a0}'),
+    ("ndash", '\u{2013}'),
+    ("ne", '\u{2260}'),
+    ("not", '\u{ac}'),
+    ("ntilde", '\u{f1}'),
+    ("oacute", '\u{f3}'),
+    ("ocirc", '\u{f4}'),
+    ("oelig", '\u{153}'),
+    ("ograve", '\u{f2}'),
+    ("oline", '\u{203e}'),
+    ("omega", '\u{3c9}'),
+    ("oplus", '\u{2295}'),
+    ("or", '\u{2228}'),
+    ("ordf", '\u{aa}'),
+    ("ordm", '\u{ba}'),
+    ("oslash", '\u{f8}'),
+    ("otilde", '\u{f5}'),
+    ("ouml", '\u{f6}'),
+    ("para", '\u{b6}'),
+    ("part", '\u{2202}'),
+    ("permil", '\u{2030}'),
+    ("perp", '\u{22a5}'),
+    ("pi", '\u{3c0}'),
+    ("plusmn", '\u{b1}'),
+    ("pound", '\u{a3}'),
+    ("prime", '\u{2032}'),
+    ("prod", '\u{220f}'),
+    ("quot", '"'),
+    ("rArr", '\u{21d2}'),
+    ("radic", '\u{221a}'),
+    ("rang", '\u{27e9}'),
+    ("raquo", '\u{bb}'),
+    ("rarr", '\u{2192}'),
+    ("rceil", '\u{2309}'),
+    ("rdquo", '\u{201d}'),
+    ("real", '\u{211c}'),
+    ("reg", '\u{ae}'),
+    ("rfloor", '\u{230b}'),
+    ("rlm", '\u{200f}'),
+    ("rsaquo", '\u{203a}'),
+    ("rsquo", '\u{2019}'),
+    ("sbquo", '\u{201a}'),
+    ("scaron", '\u{161}'),
+    ("sdot", '\u{22c5}'),
+    ("sect", '\u{a7}'),
+    ("shy", '\u{ad}'),
+    ("sigma", '\u{3c3}'),
+    ("sim", '\u{223c}'),
+    ("spades", '\u{2660}'),
+    ("star", '\u{2606}'),
+    ("sub", '\u{2282}'),
+    ("sum", '\u{2211}'),
+    ("sup", '\u{2283}'),
+    ("sup1", '\u{b9}'),
+    ("sup2", '\u{b2}'),
+    ("sup3", '\u{b3}'),
+    ("szlig", '\u{df}'),
+    ("there4", '\u{2234}'),
+    ("thinsp", '\u{2009}'),
+    ("thorn", '\u{fe}'),
+    ("tilde", '\u{2dc}'),
+    ("times", '\u{d7}'),
+    ("trade", '\u{2122}'),
+    ("uArr", '\u{21d1}'),
+    ("uacute", '\u{fa}'),
+    ("uarr", '\u{2191}'),
+    ("ucirc", '\u{fb}'),
+    ("ugrave", '\u{f9}'),
+    ("uml", '\u{a8}'),
+    ("uuml", '\u{fc}'),
+    ("weierp", '\u{2118}'),
+    ("yacute", '\u{fd}'),
+    ("yen", '\u{a5}'),
+    ("yuml", '\u{ff}'),
+    ("zwj", '\u{200d}'),
+    ("zwnj", '\u{200c}'),
+];
+
+/// Maximum length of a character reference, without the `&` and the `;`.
+const MAX_REFERENCE_LEN: usize = 32;
+
+/// Character that replaces the invalid numeric references.
+const REPLACEMENT_CHARACTER: char = '\u{fffd}';
+
+/// Decodes the character references of a text.
+///
+/// The named references (like `&amp;` or `&nbsp;`), the decimal references
+/// (like `&#39;`) and the hexadecimal references (like `&#x27;`) are replaced
+/// by the character they represent. The references must end with a `;`. The
+/// unknown named references are kept as they are, and the invalid numeric
+/// references are replaced by `U+FFFD`.
+///
+/// The text is only copied if it contains a reference.
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// assert_eq!(decode_entities("Tom &amp; Jerry"), "Tom & Jerry");
+/// assert_eq!(decode_entities("&lt;p&gt; &#x27;quoted&#39;"), "<p> 'quoted'");
+/// assert_eq!(decode_entities("caf&eacute;&nbsp;&euro;"), "caf\u{e9}\u{a0}\u{20ac}");
+/// assert_eq!(decode_entities("&unknown; &amp"), "&unknown; &amp");
+/// assert_eq!(decode_entities("&#0;"), "\u{fffd}");
+/// ```
+#[must_use]
+pub fn decode_entities(text: &str) -> Cow<'_, str> {
+    if !text.contains('&') {
+        return Cow::Borrowed(text);
+    }
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some((before, after)) = rest.split_once('&') {
+        decoded.push_str(before);
+        if let Some((ch, len)) = decode_reference(after) {
+            decoded.push(ch);
+            rest = after.get(len..).unwrap_or_default();
+        } else {
+            decoded.push('&');
+            rest = after;
+        }
+    }
+    decoded.push_str(rest);
+    Cow::Owned(decoded)
+}
+
+/// Decodes the character references of the attribute values of a tag.
+pub fn decode_attributes(tag: &mut Tag) {
+    for attr in &mut tag.attrs {
+        if let Attribute::NameValue { value, .. } = attr
+            && let Cow::Owned(decoded) = decode_entities(value)
+        {
+            *value = decoded;
+        }
+    }
+}
+
+/// Decodes the character reference at the start of `reference`, that follows
+/// a `&`.
+///
+/// # Returns
+///
+/// The decoded character, and the length of the reference, including the
+/// final `;`.
+fn decode_reference(reference: &str) -> Option<(char, usize)> {
+    let len = reference.get(..=MAX_REFERENCE_LEN).unwrap_or(reference).find(';')?;
+    let name = reference.get(..len)?;
+    let ch = if let Some(number) = name.strip_prefix('#') {
+        let (digits, radix) = number.strip_prefix(['x', 'X']).map_or((number, 10), |hex| (hex, 16));
+        if digits.is_empty() || !digits.chars().all(|digit| digit.is_digit(radix)) {
+            return None;
+        }
+        u32::from_str_radix(digits, radix)
+            .ok()
+            .and_then(char::from_u32)
+            .filter(|ch| *ch != '\0')
+            .unwrap_or(REPLACEMENT_CHARACTER)
+    } else {
+        let idx = ENTITIES.binary_search_by_key(&name, |(entity, _)| entity).ok()?;
+        ENTITIES.get(idx)?.1
+    };
+    Some((ch, len.saturating_add(1)))
+}
+
+/// Returns the length of the character reference at the start of `text`,
+/// including the `&` and the `;`, if it is a reference that can be decoded.
+pub fn reference_len(text: &str) -> Option<usize> {
+    let (_, len) = decode_reference(text.strip_prefix('&')?)?;
+    Some(len.saturating_add(1))
+}
+
+/// Decodes the character references of the texts of a tree.
+///
+/// The contents of the raw text tags, like `<script>` or the honoured legacy
+/// `<xmp>`, are kept as they are.
+pub fn decode_texts(html: &mut Html, options: &ParseOptions<'_>) {
+    match html {
+        Html::Tag { tag, .. } if tag.is_raw_text() || options.raw_text(tag.as_name()).is_some() =>
+            (),
+        Html::Tag { child, .. } => decode_texts(child, options),
+        Html::Text(text) =>
+            if let Cow::Owned(decoded) = decode_entities(text) {
+                *text = decoded.into();
+            },
+        Html::Vec(vec) => vec.iter_mut().for_each(|child| decode_texts(child, options)),
+        Html::Comment(_) | Html::Doctype { .. } | Html::Empty => (),
+    }
+}
+
+/// Encodes the characters of a text that have a special meaning in html.
+///
+/// The `&`, `<`, `>`, `"` and `'` characters are replaced by `&amp;`, `&lt;`,
+/// `&gt;`, `&quot;` and `&#39;`, so that the text can be safely inserted in a
+/// document, or in an attribute value. The control characters, like `\0`,
+/// are replaced by numeric references, like `&#x00;`. The text is only copied
+/// if it contains one of these characters.
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// assert_eq!(encode_entities("Tom & Jerry"), "Tom &amp; Jerry");
+/// assert_eq!(encode_entities("<a href='x'>"), "&lt;a href=&#39;x&#39;&gt;");
+/// assert_eq!(decode_entities(&encode_entities("\"<&>\"")), "\"<&>\"");
+/// assert_eq!(encode_entities("a\x1bb"), "a&#x1B;b");
+/// ```
+#[must_use]
+pub fn encode_entities(text: &str) -> Cow<'_, str> {
+    escape(text, &['&', '<', '>', '"', '\''])
+}
+
+/// Pushes the numeric character reference of a control character, like
+/// `&#x1B;`.
+///
+/// The control characters are all below `U+00A0`, so two hexadecimal digits
+/// are enough.
+fn push_control_reference(escaped: &mut String, ch: char) {
+    let code = u32::from(ch);
+    escaped.push_str("&#x");
+    for digit in [code >> 4u32, code & 0xf] {
+        escaped.extend(char::from_digit(digit, 16).map(|hex| hex.to_ascii_uppercase()));
+    }
+    escaped.push(';');
+}
+
+/// Replaces the given `special` characters, and the control characters, of a
+/// text by their character references.
+///
+/// The text is only copied if it contains one of these characters.
+pub fn escape<'text>(text: &'text str, special: &[char]) -> Cow<'text, str> {
+    if !text.contains(|ch| special.contains(&ch) || is_control(ch)) {
+        return Cow::Borrowed(text);
+    }
+    let mut escaped = String::with_capacity(text.len().saturating_add(text.len() >> 2));
+    for ch in text.chars() {
+        match ch {
+            '&' if special.contains(&ch) => escaped.push_str("&amp;"),
+            '<' if special.contains(&ch) => escaped.push_str("&lt;"),
+            '>' if special.contains(&ch) => escaped.push_str("&gt;"),
+            '"' if special.contains(&ch) => escaped.push_str("&quot;"),
+            '\'' if special.contains(&ch) => escaped.push_str("&#39;"),
+            _ if is_control(ch) => push_control_reference(&mut escaped, ch),
+            _ => escaped.push(ch),
+        }
+    }
+    Cow::Owned(escaped)
+}
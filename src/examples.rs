@@ -0,0 +1,184 @@
+//! Sample documents, to try filters against realistic markup.
+//!
+//! These documents are embedded in the crate, so they can be used in
+//! documentation, tests and benchmarks without fetching any file. Each
+//! constant documents the structure of its document, to know what to search
+//! for.
+//!
+//! # Examples
+//!
+//! ```
+//! use html_filter::examples::ARTICLE_HTML;
+//! use html_filter::*;
+//!
+//! let html = Html::parse(ARTICLE_HTML).unwrap();
+//! let title = html.find(&Filter::new().tag_name("h1"));
+//! assert_eq!(title, "<h1>Parsing HTML in Rust</h1>");
+//! ```
+
+/// Blog article, with a navigation bar, an article and a footer.
+///
+/// # Structure
+///
+/// - a `<!DOCTYPE html>` and a `<head>` with a `<title>`, a `<meta
+///   name="description">` and a stylesheet `<link>`;
+/// - a `<header>` with a `<nav>` of 3 links `<a href="...">`;
+/// - an `<article>` with:
+///   - a `<h1>` title;
+///   - a `<p class="meta">` with the author and the date;
+///   - 3 paragraphs `<p>`, the second one containing a `<strong>` and an
+///     external link;
+///   - an `<img>` with `src` and `alt` attributes;
+///   - a `<ul>` of 3 `<li>`.
+/// - a `<footer>` with a comment and a `<p>`.
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::examples::ARTICLE_HTML;
+/// use html_filter::*;
+///
+/// let html = Html::parse(ARTICLE_HTML).unwrap();
+/// let paragraphs =
+///     html.filter(&Filter::new().tag_name("p").except_attribute_value("class", "meta"));
+/// assert_eq!(paragraphs.to_string().matches("<p>").count(), 4);
+/// ```
+pub const ARTICLE_HTML: &str = r#"<!DOCTYPE html>
+<html lang="en">
+    <head>
+        <meta charset="UTF-8" />
+        <title>Parsing HTML in Rust</title>
+        <meta name="description" content="How to parse and filter HTML." />
+        <link rel="stylesheet" href="/style.css" />
+    </head>
+    <body>
+        <header>
+            <nav>
+                <a href="/">Home</a>
+                <a href="/blog">Blog</a>
+                <a href="/about">About</a>
+            </nav>
+        </header>
+        <article>
+            <h1>Parsing HTML in Rust</h1>
+            <p class="meta">By Jane Doe, on 2025-01-01</p>
+            <p>HTML documents are trees of nodes.</p>
+            <p>Filtering them is <strong>easy</strong> with <a href="https://crates.io/crates/html-filter">html-filter</a>.</p>
+            <img src="/tree.png" alt="A tree" />
+            <ul>
+                <li>Parse</li>
+                <li>Filter</li>
+                <li>Extract</li>
+            </ul>
+            <p>Happy parsing!</p>
+        </article>
+        <footer>
+            <!-- footer -->
+            <p>Copyright 2025</p>
+        </footer>
+    </body>
+</html>
+"#;
+
+/// Test page with a lot of different elements, and some edge cases.
+///
+/// This is the document used by the integration tests of the crate.
+///
+/// # Structure
+///
+/// - 3 doctypes, among which an empty one `<!>`;
+/// - a `<head>` with 2 `<meta>`, a `<title>` and a complex `<style>`;
+/// - a `<header>` with a `<h1>`, 2 `<div>` with classes, and a `<nav>` with a
+///   list of links (one of them with a prefixed `xlink:href` attribute);
+/// - a `<main class="container">` with 7 `<section>`, each starting with a
+///   `<h2>`: forms, a table with a `<thead>` and a `<tbody>`, lists, divs and
+///   spans, media (`<img>`, `<video>` and `<source>`), embedded script, and
+///   forms with various inputs;
+/// - a `<footer>` and a complex `<script>`.
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::examples::INDEX_HTML;
+/// use html_filter::*;
+///
+/// let html = Html::parse(INDEX_HTML).unwrap();
+/// let names = html.to_filtered(&Filter::new().tag_name("td").attribute_name("missing"));
+/// assert_eq!(names, Html::Empty);
+///
+/// let sections = html.filter(&Filter::new().tag_name("section"));
+/// assert_eq!(sections.to_string().matches("<section>").count(), 7);
+/// ```
+pub const INDEX_HTML: &str = include_str!("../tests/data/index.html");
+
+/// Product listing of an online shop.
+///
+/// # Structure
+///
+/// - a `<div id="products">` with 3 `<div class="card product">`, each of them
+///   with:
+///   - a `data-id` and a `data-price` attribute;
+///   - a `<h3 class="title">`, a `<span class="price">` and a `<button
+///     class="buy">`.
+/// - the second card has an additional `sold-out` class and a disabled button.
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::examples::PRODUCTS_HTML;
+/// use html_filter::*;
+///
+/// let html = Html::parse(PRODUCTS_HTML).unwrap();
+/// let sold_out = html.find(&Filter::new().attribute_value_contains("class", "sold-out"));
+/// assert_eq!(sold_out.as_tag().unwrap().0.find_attr_value("data-id").unwrap(), "2");
+/// ```
+pub const PRODUCTS_HTML: &str = r#"<div id="products">
+    <div class="card product" data-id="1" data-price="12.50">
+        <h3 class="title">Keyboard</h3>
+        <span class="price">12.50</span>
+        <button class="buy">Add to cart</button>
+    </div>
+    <div class="card product sold-out" data-id="2" data-price="45.00">
+        <h3 class="title">Mouse</h3>
+        <span class="price">45.00</span>
+        <button class="buy" disabled>Sold out</button>
+    </div>
+    <div class="card product" data-id="3" data-price="99.99">
+        <h3 class="title">Screen</h3>
+        <span class="price">99.99</span>
+        <button class="buy">Add to cart</button>
+    </div>
+</div>
+"#;
+
+/// Data table, with a caption, a header and a body.
+///
+/// # Structure
+///
+/// - a `<table id="scores">` with:
+///   - a `<caption>`;
+///   - a `<thead>` with one `<tr>` of 3 `<th>`: `Name`, `Language` and `Score`;
+///   - a `<tbody>` with 3 `<tr>` of 3 `<td>` each.
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::examples::TABLE_HTML;
+/// use html_filter::*;
+///
+/// let html = Html::parse(TABLE_HTML).unwrap();
+/// let rows = html.filter(&Filter::new().tag_name("tr").trim());
+/// assert_eq!(rows.to_string().matches("<tr>").count(), 4);
+/// ```
+pub const TABLE_HTML: &str = r#"<table id="scores">
+    <caption>Scores</caption>
+    <thead>
+        <tr><th>Name</th><th>Language</th><th>Score</th></tr>
+    </thead>
+    <tbody>
+        <tr><td>Alice</td><td>Rust</td><td>98</td></tr>
+        <tr><td>Bob</td><td>Go</td><td>87</td></tr>
+        <tr><td>Carol</td><td>Zig</td><td>91</td></tr>
+    </tbody>
+</table>
+"#;
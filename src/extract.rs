@@ -0,0 +1,594 @@
+//! Module to extract structured data from an [`Html`] tree.
+//!
+//! The extractions read the tree without modifying it, and return owned
+//! values, that can outlive the tree.
+
+use core::ops::Range;
+
+use crate::{Html, ParseError, TokenKind, Tokenizer};
+
+/// Extensions of the urls that point to resources, rather than to pages.
+const ASSET_EXTENSIONS: [&str; 24] = [
+    "avif", "bmp", "css", "csv", "doc", "docx", "gif", "ico", "jpeg", "jpg", "js", "json", "mp3",
+    "mp4", "ogg", "pdf", "png", "svg", "tar", "txt", "wasm", "webm", "webp", "zip",
+];
+
+/// Tags that contain a link to follow, in their `href` attribute.
+const LINK_TAGS: [&str; 3] = ["a", "area", "link"];
+
+/// Attributes that reference an external resource, with the tags on which
+/// they do.
+const RESOURCE_ATTRIBUTES: [(&str, &[&str]); 5] = [
+    ("data", &["object"]),
+    ("href", &["link"]),
+    ("poster", &["video"]),
+    ("src", &[
+        "audio", "embed", "frame", "iframe", "img", "input", "script", "source", "track", "video",
+    ]),
+    ("srcset", &["img", "source"]),
+];
+
+/// Link found in a tree, with its target and its description.
+///
+/// See [`links`] to extract them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    /// Value of the `href` attribute
+    href: String,
+    /// Value of the `rel` attribute
+    rel: Option<String>,
+    /// Known tokens of the `rel` attribute
+    rel_flags: RelFlags,
+    /// Name of the tag, i.e., `a`, `area` or `link`
+    tag: String,
+    /// Text content of the tag
+    text: String,
+}
+
+impl Link {
+    /// Returns the target of the link, i.e., the value of its `href`
+    /// attribute.
+    #[must_use]
+    pub const fn as_href(&self) -> &str {
+        self.href.as_str()
+    }
+
+    /// Returns the value of the `rel` attribute, if there is one.
+    #[must_use]
+    pub fn as_rel(&self) -> Option<&str> {
+        self.rel.as_deref()
+    }
+
+    /// Returns the known tokens of the `rel` attribute.
+    ///
+    /// See [`RelFlags`] for more information.
+    #[must_use]
+    pub const fn as_rel_flags(&self) -> RelFlags {
+        self.rel_flags
+    }
+
+    /// Returns the name of the tag of the link, i.e., `a`, `area` or `link`.
+    #[must_use]
+    pub const fn as_tag(&self) -> &str {
+        self.tag.as_str()
+    }
+
+    /// Returns the text content of the link, with the texts of the nested
+    /// tags.
+    #[must_use]
+    pub const fn as_text(&self) -> &str {
+        self.text.as_str()
+    }
+
+    /// Classifies the target of the link.
+    ///
+    /// The `base` is the url of the page that contains the link. An absolute
+    /// link is internal if it has the same host as the base, and external
+    /// otherwise. Without base, all the absolute links are external, and all
+    /// the relative links are internal.
+    ///
+    /// The `<link>` tags, and the links to files with a known extension (e.g.
+    /// `.png` or `.pdf`), are assets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::extract::{self, LinkKind};
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     r##"<a href="/about">About</a><a href="https://other.org">Other</a><a href="#top">Top</a><a href="mailto:me@example.com">Mail</a><a href="/report.pdf">Report</a>"##,
+    /// )
+    /// .unwrap();
+    ///
+    /// let kinds = extract::links(&html)
+    ///     .iter()
+    ///     .map(|link| link.classify(Some("https://example.com/blog/")))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(kinds, [
+    ///     LinkKind::Internal,
+    ///     LinkKind::External,
+    ///     LinkKind::Anchor,
+    ///     LinkKind::Mailto,
+    ///     LinkKind::Asset
+    /// ]);
+    /// ```
+    #[must_use]
+    pub fn classify(&self, base: Option<&str>) -> LinkKind {
+        let href = self.href.trim();
+        if href.is_empty() || href.starts_with('#') {
+            return LinkKind::Anchor;
+        }
+        match scheme(href).map(str::to_ascii_lowercase).as_deref() {
+            Some("mailto") => return LinkKind::Mailto,
+            Some("tel") => return LinkKind::Tel,
+            Some("javascript") => return LinkKind::Javascript,
+            Some(_) | None => (),
+        }
+        if self.tag == "link" || has_asset_extension(href) {
+            return LinkKind::Asset;
+        }
+        match host(href) {
+            Some(link_host)
+                if base
+                    .and_then(host)
+                    .is_some_and(|base_host| base_host.eq_ignore_ascii_case(link_host)) =>
+                LinkKind::Internal,
+            Some(_) => LinkKind::External,
+            None if scheme(href).is_some() => LinkKind::External,
+            None => LinkKind::Internal,
+        }
+    }
+
+    /// Checks if a crawler may follow the link, i.e., if its `rel` attribute
+    /// doesn't contain `nofollow`, `sponsored` or `ugc`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html =
+    ///     Html::parse(r#"<a href="/a" rel="noopener">A</a><a href="/b" rel="UGC nofollow">B</a>"#)
+    ///         .unwrap();
+    ///
+    /// let links = extract::links(&html);
+    /// assert!(links[0].is_followable());
+    /// assert!(!links[1].is_followable());
+    /// ```
+    #[must_use]
+    pub const fn is_followable(&self) -> bool {
+        !self.rel_flags.contains(Rel::Nofollow)
+            && !self.rel_flags.contains(Rel::Sponsored)
+            && !self.rel_flags.contains(Rel::Ugc)
+    }
+}
+
+/// Kind of target of a [`Link`].
+///
+/// See [`Link::classify`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LinkKind {
+    /// Link to a fragment of the current page, e.g. `#top`
+    Anchor,
+    /// Link to a resource, e.g. an image, a stylesheet or a pdf
+    Asset,
+    /// Link to a page of another host
+    External,
+    /// Link to a page of the same host, or a relative link
+    Internal,
+    /// Script to run, e.g. `javascript:void(0)`
+    Javascript,
+    /// Email address, e.g. `mailto:me@example.com`
+    Mailto,
+    /// Phone number, e.g. `tel:+33100000000`
+    Tel,
+}
+
+/// Token of the `rel` attribute of a link, that changes how the link must be
+/// followed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Rel {
+    /// `external`: the link goes to another website
+    External,
+    /// `nofollow`: the link isn't endorsed by the author of the page
+    Nofollow,
+    /// `noopener`: the target page mustn't access the current page
+    Noopener,
+    /// `noreferrer`: no `Referer` header is sent when following the link
+    Noreferrer,
+    /// `sponsored`: the link is an advertisement or a paid placement
+    Sponsored,
+    /// `ugc`: the link was written by a user, e.g. in a comment
+    Ugc,
+}
+
+impl Rel {
+    /// Returns the token of the `rel` attribute, e.g. `nofollow`.
+    #[must_use]
+    pub const fn as_token(self) -> &'static str {
+        match self {
+            Self::External => "external",
+            Self::Nofollow => "nofollow",
+            Self::Noopener => "noopener",
+            Self::Noreferrer => "noreferrer",
+            Self::Sponsored => "sponsored",
+            Self::Ugc => "ugc",
+        }
+    }
+
+    /// Returns the bit of the token in a [`RelFlags`].
+    const fn bit(self) -> u8 {
+        match self {
+            Self::External => 1,
+            Self::Nofollow => 2,
+            Self::Noopener => 4,
+            Self::Noreferrer => 8,
+            Self::Sponsored => 16,
+            Self::Ugc => 32,
+        }
+    }
+
+    /// Parses a token of a `rel` attribute, ignoring the ASCII case.
+    fn from_token(token: &str) -> Option<Self> {
+        [
+            Self::External,
+            Self::Nofollow,
+            Self::Noopener,
+            Self::Noreferrer,
+            Self::Sponsored,
+            Self::Ugc,
+        ]
+        .into_iter()
+        .find(|rel| rel.as_token().eq_ignore_ascii_case(token))
+    }
+}
+
+/// Set of the known tokens of the `rel` attribute of a link.
+///
+/// The tokens are separated by whitespace and case-insensitive. The unknown
+/// tokens, like `stylesheet`, are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::extract::{Rel, RelFlags};
+///
+/// let flags = RelFlags::parse("Nofollow noopener stylesheet");
+/// assert!(flags.contains(Rel::Nofollow));
+/// assert!(flags.contains(Rel::Noopener));
+/// assert!(!flags.contains(Rel::Ugc));
+/// assert!(RelFlags::parse("stylesheet").is_empty());
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RelFlags(u8);
+
+impl RelFlags {
+    /// Checks if the token is in the set.
+    #[must_use]
+    pub const fn contains(self, rel: Rel) -> bool {
+        self.0 & rel.bit() != 0
+    }
+
+    /// Checks if the set doesn't contain any known token.
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Parses the value of a `rel` attribute.
+    #[must_use]
+    pub fn parse(rel: &str) -> Self {
+        Self(
+            rel.split_ascii_whitespace()
+                .filter_map(Rel::from_token)
+                .fold(0, |flags, token| flags | token.bit()),
+        )
+    }
+}
+
+/// Reference to an external resource, found in the source of a document, with
+/// its position.
+///
+/// See [`resources_with_spans`] to extract them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resource {
+    /// Name of the attribute that contains the url, e.g. `src`
+    attr: String,
+    /// Range of the bytes of the url in the source
+    span: Range<usize>,
+    /// Name of the tag that references the resource, e.g. `img`
+    tag: String,
+    /// Url of the resource, as written in the source
+    url: String,
+}
+
+impl Resource {
+    /// Returns the name of the attribute that contains the url, in lowercase,
+    /// e.g. `src` or `srcset`.
+    #[must_use]
+    pub const fn as_attr(&self) -> &str {
+        self.attr.as_str()
+    }
+
+    /// Returns the name of the tag that references the resource, in
+    /// lowercase, e.g. `img`.
+    #[must_use]
+    pub const fn as_tag(&self) -> &str {
+        self.tag.as_str()
+    }
+
+    /// Returns the url of the resource, as written in the source.
+    ///
+    /// The character references of the url, like `&amp;`, aren't decoded, so
+    /// that the url is exactly the bytes of [`Resource::span`].
+    #[must_use]
+    pub const fn as_url(&self) -> &str {
+        self.url.as_str()
+    }
+
+    /// Returns the range of the bytes of the url in the source, without the
+    /// quotes of the attribute value.
+    #[must_use]
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+/// Checks if the path of the url ends with the extension of an asset.
+fn has_asset_extension(url: &str) -> bool {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    let last_segment = path.rsplit('/').next().unwrap_or_default();
+    last_segment.rsplit_once('.').is_some_and(|(_, extension)| {
+        ASSET_EXTENSIONS.iter().any(|asset| asset.eq_ignore_ascii_case(extension))
+    })
+}
+
+/// Returns the host of an absolute url, e.g. `example.com` for
+/// `https://user@example.com:80/path`.
+fn host(url: &str) -> Option<&str> {
+    let after_scheme = scheme(url)
+        .map_or(url, |found| url.get(found.len().saturating_add(1)..).unwrap_or_default());
+    let authority = after_scheme.strip_prefix("//")?.split(['/', '?', '#']).next()?;
+    let without_user = authority.rsplit_once('@').map_or(authority, |(_, host_port)| host_port);
+    let without_port = without_user.split(':').next()?;
+    (!without_port.is_empty()).then_some(without_port)
+}
+
+/// Checks if a node is a comment whose trimmed content is the given marker.
+fn is_marker(node: &Html, marker: &str) -> bool {
+    node.as_comment().is_some_and(|content| content.trim() == marker)
+}
+
+/// Extracts all the links of a tree, in the order of the document.
+///
+/// The links are the `<a>`, `<area>` and `<link>` tags that have an `href`
+/// attribute.
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// let html =
+///     Html::parse(r#"<nav><a href="/home"><b>Home</b> page</a><a>No href</a></nav>"#).unwrap();
+///
+/// let links = extract::links(&html);
+/// assert_eq!(links.len(), 1);
+/// assert_eq!(links[0].as_href(), "/home");
+/// assert_eq!(links[0].as_text(), "Home page");
+/// ```
+#[must_use]
+pub fn links(html: &Html) -> Vec<Link> {
+    let mut found = vec![];
+    links_aux(html, &mut found);
+    found
+}
+
+/// Wrapper for [`links`]
+fn links_aux(node: &Html, found: &mut Vec<Link>) {
+    match node {
+        Html::Tag { tag, child } => {
+            if LINK_TAGS.contains(&tag.as_name())
+                && let Some(href) = tag.find_attr_value("href")
+            {
+                let mut text = String::new();
+                child.push_texts(&mut text);
+                let rel = tag.find_attr_value("rel").cloned();
+                found.push(Link {
+                    href: href.to_owned(),
+                    rel_flags: rel.as_deref().map(RelFlags::parse).unwrap_or_default(),
+                    rel,
+                    tag: tag.as_name().to_owned(),
+                    text,
+                });
+            }
+            links_aux(child, found);
+        }
+        Html::Vec(vec) =>
+            for child in vec {
+                links_aux(child, found);
+            },
+        Html::Comment(_) | Html::Doctype { .. } | Html::Empty | Html::Text(_) => (),
+    }
+}
+
+/// Extracts the regions of a tree that are delimited by marker comments, in
+/// the order of the document.
+///
+/// A region is the list of the sibling nodes between a comment whose content
+/// is `start_marker` and the next sibling comment whose content is
+/// `end_marker`, like `<!-- content:start -->...<!-- content:end -->`. The
+/// contents of the comments are trimmed before comparing them with the
+/// markers. The marker comments aren't included in the regions, see
+/// [`Html::to_siblings`].
+///
+/// The markers of a region must be in the same tag: a start marker without an
+/// end marker among its next siblings is ignored. The regions aren't searched
+/// for nested regions.
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// let html = Html::parse(
+///     "<body><nav>Menu</nav><!-- content:start --><h1>Title</h1><p>Text</p><!-- content:end \
+///      --><main><!-- content:start -->Intro<!-- content:end --></main></body>",
+/// )
+/// .unwrap();
+///
+/// let regions = extract::regions(&html, "content:start", "content:end");
+/// assert_eq!(regions.len(), 2);
+/// assert_eq!(regions[0], "<h1>Title</h1><p>Text</p>");
+/// assert_eq!(regions[1], "Intro");
+/// ```
+#[must_use]
+pub fn regions(html: &Html, start_marker: &str, end_marker: &str) -> Vec<Html> {
+    let mut found = vec![];
+    regions_aux(html, (start_marker, end_marker), &mut found);
+    found
+}
+
+/// Wrapper for [`regions`]
+///
+/// `markers` contains the start and end markers.
+fn regions_aux(node: &Html, markers: (&str, &str), found: &mut Vec<Html>) {
+    let (start_marker, end_marker) = markers;
+    match node {
+        Html::Tag { child, .. } => regions_aux(child, markers, found),
+        Html::Vec(vec) => {
+            let mut idx = 0;
+            while let Some(sibling) = vec.get(idx) {
+                let start = idx.saturating_add(1);
+                if is_marker(sibling, start_marker)
+                    && let Some(len) = vec
+                        .get(start..)
+                        .and_then(|rest| rest.iter().position(|next| is_marker(next, end_marker)))
+                {
+                    found.push(node.to_siblings(start..start.saturating_add(len)));
+                    idx = start.saturating_add(len).saturating_add(1);
+                } else {
+                    regions_aux(sibling, markers, found);
+                    idx = start;
+                }
+            }
+        }
+        Html::Comment(_) | Html::Doctype { .. } | Html::Empty | Html::Text(_) => (),
+    }
+}
+
+/// Extracts all the references to external resources of a document, with the
+/// byte ranges of their urls in the source, in the order of the document.
+///
+/// The resources are the urls of the attributes that the browsers load when
+/// rendering the page: the `src` of the images, scripts, frames and media,
+/// the `href` of the `<link>` tags, the `poster` of the videos, the `data` of
+/// the `<object>` tags, and every url of the `srcset` attributes. The links
+/// to other pages, like `<a href>`, aren't resources.
+///
+/// An [`Html`] tree doesn't keep the positions of its nodes, so the source is
+/// read again with the [`Tokenizer`]. The spans can then be used to rewrite
+/// the urls directly in the source bytes, e.g. to archive a page with local
+/// copies of its resources, without serialising a tree again, which would
+/// change the formatting of the document. The urls are trimmed, and the empty
+/// ones are skipped.
+///
+/// # Errors
+///
+/// Returns an error if the source has an invalid syntax, see [`Tokenizer`].
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::extract;
+///
+/// let source = r#"<img src=" a.png " srcset="b.png 2x, c.png 3x"><a href="/page">Page</a>"#;
+/// let resources = extract::resources_with_spans(source).unwrap();
+///
+/// let urls = resources.iter().map(|resource| resource.as_url()).collect::<Vec<_>>();
+/// assert_eq!(urls, ["a.png", "b.png", "c.png"]);
+/// assert_eq!(resources[1].as_attr(), "srcset");
+///
+/// let mut archived = source.to_owned();
+/// for resource in resources.iter().rev() {
+///     archived.replace_range(resource.span(), &format!("archive/{}", resource.as_url()));
+/// }
+/// assert_eq!(
+///     archived,
+///     r#"<img src=" archive/a.png " srcset="archive/b.png 2x, archive/c.png 3x"><a href="/page">Page</a>"#
+/// );
+/// ```
+pub fn resources_with_spans(source: &str) -> Result<Vec<Resource>, ParseError> {
+    let mut found = vec![];
+    for token in Tokenizer::new(source) {
+        let TokenKind::OpenTag { tag, .. } = token?.into_kind() else { continue };
+        for attr in tag.as_attrs() {
+            let Some(value) = attr.as_value() else { continue };
+            let is_resource = RESOURCE_ATTRIBUTES.iter().any(|(attr_name, tags)| {
+                attr_name.eq_ignore_ascii_case(attr.as_name())
+                    && tags.iter().any(|tag_name| tag_name.eq_ignore_ascii_case(tag.as_name()))
+            });
+            if !is_resource {
+                continue;
+            }
+            let start = value.as_ptr().addr().saturating_sub(source.as_ptr().addr());
+            let urls = if attr.as_name().eq_ignore_ascii_case("srcset") {
+                srcset_urls(value)
+            } else {
+                let trimmed = value.trim_start();
+                let offset = value.len().saturating_sub(trimmed.len());
+                vec![(offset, trimmed.trim_end())]
+            };
+            for (offset, url) in urls.into_iter().filter(|(_, url)| !url.is_empty()) {
+                let url_start = start.saturating_add(offset);
+                found.push(Resource {
+                    attr: attr.as_name().to_ascii_lowercase(),
+                    span: url_start..url_start.saturating_add(url.len()),
+                    tag: tag.as_name().to_ascii_lowercase(),
+                    url: url.to_owned(),
+                });
+            }
+        }
+    }
+    Ok(found)
+}
+
+/// Returns the scheme of the url, e.g. `https` or `mailto`, if it has one.
+fn scheme(url: &str) -> Option<&str> {
+    let (found, _) = url.split_once(':')?;
+    let mut chars = found.chars();
+    (chars.next().is_some_and(|ch| ch.is_ascii_alphabetic())
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '+' | '-' | '.')))
+    .then_some(found)
+}
+
+/// Returns the urls of a `srcset` attribute, with their byte offsets in the
+/// value.
+///
+/// The candidates are separated by commas, and their urls are followed by
+/// optional descriptors, like `2x` or `100w`. The urls can contain commas, as
+/// in `data:` urls, but can't end with one.
+fn srcset_urls(srcset: &str) -> Vec<(usize, &str)> {
+    let mut urls = vec![];
+    let mut start = 0;
+    while let Some(rest) = srcset.get(start..) {
+        let candidate = rest.trim_start_matches(|ch: char| ch.is_whitespace() || ch == ',');
+        if candidate.is_empty() {
+            break;
+        }
+        start = start.saturating_add(rest.len().saturating_sub(candidate.len()));
+        let len = candidate.find(char::is_whitespace).unwrap_or(candidate.len());
+        let url = candidate.get(..len).unwrap_or_default();
+        let trimmed = url.trim_end_matches(',');
+        urls.push((start, trimmed));
+        start = start.saturating_add(len);
+        if trimmed.len() == url.len() {
+            let descriptors = candidate.get(len..).unwrap_or_default();
+            start = start.saturating_add(descriptors.find(',').unwrap_or(descriptors.len()));
+        }
+    }
+    urls
+}
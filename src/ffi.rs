@@ -0,0 +1,156 @@
+//! Module that exposes the parser and the filters with a stable C ABI, to
+//! build bindings for other languages on top of this crate.
+//!
+//! The functions are declared in the `include/html_filter.h` header. The trees
+//! are opaque pointers, that are created by [`hf_parse`] or
+//! [`hf_filter_selector`], and must be released with [`hf_free`]. The strings
+//! are NUL-terminated and UTF-8 encoded. The strings returned by
+//! [`hf_inner_text`] must be released with [`hf_free_string`].
+//!
+//! All the functions return a null pointer on failure, and accept null
+//! pointers as arguments.
+//!
+//! The crate is only built as a Rust library, so that the crates that depend
+//! on it don't build a C library. The C library is built with
+//! `cargo rustc --release --features ffi --crate-type cdylib`, or with the
+//! `staticlib` crate type for a static library.
+//!
+//! # Examples
+//!
+//! ```c
+//! #include "html_filter.h"
+//!
+//! HfHtml *html = hf_parse("<ul><li class='a'>First</li><li>Second</li></ul>");
+//! HfHtml *found = hf_filter_selector(html, "li.a");
+//! char *text = hf_inner_text(found);
+//! // text is "First"
+//! hf_free_string(text);
+//! hf_free(found);
+//! hf_free(html);
+//! ```
+
+use alloc::ffi::CString;
+use core::ffi::{CStr, c_char};
+use core::ptr::null_mut;
+
+use crate::Html;
+use crate::filter::selector::parse_selector;
+
+/// Filters a tree with a CSS selector, and returns a new tree with the
+/// matching tags.
+///
+/// The selector is a compound selector, i.e., a tag name, or `*`, followed by
+/// any number of `#id`, `.class`, `[attr]` and `[attr=value]` conditions, like
+/// `a.external[href]`, with the `~=`, `^=`, `$=` and `*=` operators.
+/// Combinators and lists of selectors aren't supported.
+///
+/// # Returns
+///
+/// A null pointer if one of the arguments is null, or if the selector is
+/// invalid.
+///
+/// # Safety
+///
+/// `html` must be null or a live tree returned by this module. `selector` must
+/// be null or point to a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn hf_filter_selector(
+    html: *const Html,
+    selector: *const c_char,
+) -> *mut Html {
+    // SAFETY: the caller guarantees that the tree is null or alive.
+    let Some(tree) = (unsafe { html.as_ref() }) else { return null_mut() };
+    // SAFETY: the caller guarantees that the selector is null or NUL-terminated.
+    let Some(filter) = (unsafe { read_str(selector) }).and_then(parse_selector) else {
+        return null_mut();
+    };
+    Box::into_raw(Box::new(tree.to_filtered(&filter)))
+}
+
+/// Releases a tree returned by [`hf_parse`] or [`hf_filter_selector`].
+///
+/// Nothing is done if `html` is null.
+///
+/// # Safety
+///
+/// `html` must be null or a tree returned by this module, that wasn't released
+/// yet. It mustn't be used after this call.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn hf_free(html: *mut Html) {
+    if !html.is_null() {
+        // SAFETY: the caller guarantees that the tree was allocated by this
+        // module, and wasn't released yet.
+        drop(unsafe { Box::from_raw(html) });
+    }
+}
+
+/// Releases a string returned by [`hf_inner_text`].
+///
+/// Nothing is done if `text` is null.
+///
+/// # Safety
+///
+/// `text` must be null or a string returned by this module, that wasn't
+/// released yet. It mustn't be used after this call.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn hf_free_string(text: *mut c_char) {
+    if !text.is_null() {
+        // SAFETY: the caller guarantees that the string was allocated by this
+        // module, and wasn't released yet.
+        drop(unsafe { CString::from_raw(text) });
+    }
+}
+
+/// Returns the concatenation of the texts of a tree, without the tags and the
+/// comments.
+///
+/// # Returns
+///
+/// A null pointer if `html` is null, or if the texts contain a NUL character.
+///
+/// # Safety
+///
+/// `html` must be null or a live tree returned by this module.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn hf_inner_text(html: *const Html) -> *mut c_char {
+    // SAFETY: the caller guarantees that the tree is null or alive.
+    let Some(tree) = (unsafe { html.as_ref() }) else { return null_mut() };
+    let mut text = String::new();
+    tree.push_texts(&mut text);
+    CString::new(text).map_or(null_mut(), CString::into_raw)
+}
+
+/// Parses a NUL-terminated html string into a tree.
+///
+/// # Returns
+///
+/// A null pointer if `input` is null, isn't valid UTF-8, or isn't valid html.
+///
+/// # Safety
+///
+/// `input` must be null or point to a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn hf_parse(input: *const c_char) -> *mut Html {
+    // SAFETY: the caller guarantees that the input is null or NUL-terminated.
+    (unsafe { read_str(input) })
+        .and_then(|html| Html::parse(html).ok())
+        .map_or(null_mut(), |tree| Box::into_raw(Box::new(tree)))
+}
+
+/// Reads a NUL-terminated UTF-8 string.
+///
+/// # Returns
+///
+/// `None` if the pointer is null, or if the string isn't valid UTF-8.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a NUL-terminated string, that outlives the
+/// returned reference.
+unsafe fn read_str<'ptr>(ptr: *const c_char) -> Option<&'ptr str> {
+    if ptr.is_null() {
+        return None;
+    }
+    // SAFETY: the caller guarantees that the string is NUL-terminated.
+    unsafe { CStr::from_ptr(ptr) }.to_str().ok()
+}
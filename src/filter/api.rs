@@ -1,8 +1,17 @@
 //! Public API for [`Filter`]
 
-use crate::Filter;
+#[cfg(feature = "regex")]
+use regex::{Error as RegexError, Regex};
+
 use crate::filter::NodeTypeFilter;
+use crate::filter::combine::Combined;
+#[cfg(feature = "regex")]
+use crate::filter::element::Pattern;
 use crate::filter::element::{AttributeMatch, BlackWhiteList, ValueAssociateHash};
+use crate::filter::like::Like;
+use crate::filter::predicate::Predicates;
+use crate::filter::text::{TextContent, TextMatch};
+use crate::{Filter, Html, NodeTypes, Tag, TrimMode};
 
 /// Public API for [`Filter`] on node-type-filters (texts, doctypes, comments,
 /// etc.)
@@ -20,13 +29,40 @@ impl Filter {
     /// assert_eq!(Filter::new().doctype(true).text(true).comment(true), Filter::new().all(true));
     /// assert_eq!(Filter::new().doctype(false).text(false).comment(false), Filter::new().all(false));
     /// ```
+    ///
+    /// This sets a base state for the node types, that the later calls
+    /// override, see [`Filter#precedence`]. The tags aren't affected: they
+    /// are all kept until a rule on the tags is given, and [`Self::no_tags`]
+    /// removes them en masse, so `all(false).no_tags()` drops every node.
+    ///
+    /// See also [`Self::keep_types`] and [`Self::drop_types`] to set them
+    /// separately.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<!doctype html> a <p>b<!-- c --></p>").unwrap();
+    ///
+    /// assert_eq!(html.to_filtered(&Filter::new().all(false)), "<p></p>");
+    /// assert_eq!(html.to_filtered(&Filter::new().all(false).no_tags()), "");
+    /// assert_eq!(html.to_filtered(&Filter::new().all(false).text(true)), " a <p>b</p>");
+    /// assert_eq!(
+    ///     html.to_filtered(&Filter::new().tag_name("p").all(true)),
+    ///     "<!doctype html> a <p>b<!-- c --></p>"
+    /// );
+    /// ```
     #[must_use]
     pub const fn all(self, all: bool) -> Self {
-        self.comment(all).doctype(all).text(all)
+        self.keep_types(if all { NodeTypes::ALL } else { NodeTypes::NONE })
     }
 
     /// Removes the comments, and forces to keep doctypes and texts.
     ///
+    /// This is a short-hand for
+    /// [`drop_types(NodeTypes::COMMENT)`](Self::drop_types).
+    ///
     /// See also [`Self::comment`] to allow comments without forcing others to
     /// be kept.
     ///
@@ -42,11 +78,14 @@ impl Filter {
     /// ```
     #[must_use]
     pub const fn all_except_comment(self) -> Self {
-        self.all(true).comment(false)
+        self.drop_types(NodeTypes::COMMENT)
     }
 
     /// Removes the doctypes, and forces to keep comments and texts.
     ///
+    /// This is a short-hand for
+    /// [`drop_types(NodeTypes::DOCTYPE)`](Self::drop_types).
+    ///
     /// See also [`Self::doctype`] to allow doctypes without forcing others to
     /// be kept.
     ///
@@ -62,11 +101,14 @@ impl Filter {
     /// ```
     #[must_use]
     pub const fn all_except_doctype(self) -> Self {
-        self.all(true).doctype(false)
+        self.drop_types(NodeTypes::DOCTYPE)
     }
 
     /// Removes the texts, and forces to keep doctypes and comments.
     ///
+    /// This is a short-hand for
+    /// [`drop_types(NodeTypes::TEXT)`](Self::drop_types).
+    ///
     /// See also [`Self::text`] to allow comments without forcing others to
     /// be kept.
     ///
@@ -90,7 +132,7 @@ impl Filter {
     /// ```
     #[must_use]
     pub const fn all_except_text(self) -> Self {
-        self.all(true).text(false)
+        self.drop_types(NodeTypes::TEXT)
     }
 
     /// Sets the filter for comments
@@ -117,28 +159,94 @@ impl Filter {
         self
     }
 
+    /// Removes the nodes of the given types, and keeps all the others.
+    ///
+    /// Like [`Self::keep_types`], this sets the policy of every type, and the
+    /// later calls to [`Self::comment`], [`Self::doctype`] and [`Self::text`]
+    /// can still change the policy of a single type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<!doctype html> a <p> b <!-- c --></p>").unwrap();
+    ///
+    /// assert_eq!(
+    ///     html.filter(&Filter::new().tag_name("p").drop_types(NodeTypes::TEXT | NodeTypes::DOCTYPE)),
+    ///     "<p><!-- c --></p>"
+    /// );
+    /// assert_eq!(
+    ///     Filter::new().drop_types(NodeTypes::COMMENT),
+    ///     Filter::new().keep_types(NodeTypes::TEXT | NodeTypes::DOCTYPE)
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn drop_types(self, types: NodeTypes) -> Self {
+        self.keep_types(types.complement())
+    }
+
+    /// Keeps only the nodes of the given types, and removes all the others.
+    ///
+    /// The policy of every type is set at once: the types that are not given
+    /// are removed, even those that are kept by default, like the texts. The
+    /// later calls to [`Self::comment`], [`Self::doctype`] and [`Self::text`]
+    /// can still change the policy of a single type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<!doctype html> a <p> b <!-- c --></p> d").unwrap();
+    ///
+    /// assert_eq!(
+    ///     html.to_filtered(&Filter::new().tag_name("p").keep_types(NodeTypes::COMMENT)),
+    ///     "<p><!-- c --></p>"
+    /// );
+    /// assert_eq!(
+    ///     html.filter(&Filter::new().tag_name("p").keep_types(NodeTypes::TEXT | NodeTypes::COMMENT)),
+    ///     " a <p> b <!-- c --></p> d"
+    /// );
+    /// assert_eq!(
+    ///     Filter::new().keep_types(NodeTypes::TEXT).comment(true),
+    ///     Filter::new().keep_types(NodeTypes::TEXT | NodeTypes::COMMENT)
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn keep_types(mut self, types: NodeTypes) -> Self {
+        self.types.set_types(types);
+        self
+    }
+
     /// Keeps only the comments
     ///
-    /// Doctypes and texts are removed, unless said otherwise by the user.
+    /// Doctypes and texts are removed, unless said otherwise by the user. This
+    /// is a short-hand for
+    /// [`keep_types(NodeTypes::COMMENT)`](Self::keep_types).
     #[must_use]
     pub const fn none_except_comment(self) -> Self {
-        self.all(false).comment(true)
+        self.keep_types(NodeTypes::COMMENT)
     }
 
     /// Keeps only the doctypes
     ///
-    /// Comments and texts are removed, unless said otherwise by the user.
+    /// Comments and texts are removed, unless said otherwise by the user. This
+    /// is a short-hand for
+    /// [`keep_types(NodeTypes::DOCTYPE)`](Self::keep_types).
     #[must_use]
     pub const fn none_except_doctype(self) -> Self {
-        self.all(false).doctype(true)
+        self.keep_types(NodeTypes::DOCTYPE)
     }
 
     /// Keeps only the texts
     ///
     /// Comments and doctypes are removed, unless said otherwise by the user.
+    /// This is a short-hand for
+    /// [`keep_types(NodeTypes::TEXT)`](Self::keep_types).
     #[must_use]
     pub const fn none_except_text(self) -> Self {
-        self.all(false).text(true)
+        self.keep_types(NodeTypes::TEXT)
     }
 
     /// Filters texts
@@ -190,18 +298,51 @@ impl Filter {
     /// assert_eq!(tag.as_name(), "ul");
     ///
     /// let vec = child.as_vec().unwrap();
-    /// assert_eq!(vec[0], Html::Text("\n    ".to_string()));
+    /// assert_eq!(vec[0], Html::Text("\n    ".into()));
     /// assert!(matches!(vec[1], Html::Tag { .. })); // first li
-    /// assert_eq!(vec[2], Html::Text("\n    ".to_string()));
+    /// assert_eq!(vec[2], Html::Text("\n    ".into()));
     /// assert!(matches!(vec[3], Html::Tag { .. })); // second li
-    /// assert_eq!(vec[4], Html::Text("\n".to_string()));
+    /// assert_eq!(vec[4], Html::Text("\n".into()));
     /// assert_eq!(vec.len(), 5);
     /// ```
     ///
+    /// This is a short-hand for [`Filter::trim_text`] with
+    /// [`TrimMode::Edges`].
+    ///
     /// See also [`Self::collapse`]
     #[must_use]
-    pub const fn trim(mut self) -> Self {
-        self.types.trim();
+    pub const fn trim(self) -> Self {
+        self.trim_text(TrimMode::Edges)
+    }
+
+    /// Sets how the kept texts are trimmed in the output.
+    ///
+    /// With [`TrimMode::Edges`] or [`TrimMode::Collapse`], the texts that
+    /// only contain whitespaces are removed, and the other texts are trimmed,
+    /// so the results can be printed without normalising them. Contrary to
+    /// [`Filter::collapse`], which merges the successive texts, this never
+    /// changes the number of texts.
+    ///
+    /// The texts inside the tags whose whitespace is rendered as is, like
+    /// `<pre>` or `<textarea>`, are never trimmed.
+    ///
+    /// The default is [`TrimMode::None`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<ul>\n  <li>  Two\n   words </li>\n</ul>").unwrap();
+    /// let filter = |mode| Filter::new().tag_name("li").trim_text(mode);
+    ///
+    /// assert_eq!(html.to_filtered(&filter(TrimMode::None)), "<li>  Two\n   words </li>");
+    /// assert_eq!(html.to_filtered(&filter(TrimMode::Edges)), "<li>Two\n   words</li>");
+    /// assert_eq!(html.to_filtered(&filter(TrimMode::Collapse)), "<li>Two words</li>");
+    /// ```
+    #[must_use]
+    pub const fn trim_text(mut self, mode: TrimMode) -> Self {
+        self.types.trim(mode);
         self
     }
 }
@@ -224,11 +365,48 @@ impl Filter {
         self
     }
 
+    /// Specifies the name of an attribute that the wanted tags must have, with
+    /// or without value.
+    ///
+    /// To match only the attributes without value, see
+    /// [`Filter::attribute_name`].
+    ///
+    /// The deprecated namespaced attributes of the SVG elements are also
+    /// matched by their new names: `href` matches `xlink:href`, and `lang`
+    /// matches `xml:lang`, unless the tag also has the new attribute (see
+    /// [`Attribute::as_modern_name`](crate::Attribute::as_modern_name)). This
+    /// is the case for all the attribute conditions. The deprecated names only
+    /// match the deprecated attributes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(r#"<a href="/">1</a><a href>2</a><a>3</a>"#).unwrap();
+    /// assert_eq!(
+    ///     html.to_filtered(&Filter::new().attribute_present("href")),
+    ///     r#"<a href="/">1</a><a href>2</a>"#
+    /// );
+    /// assert_eq!(html.to_filtered(&Filter::new().attribute_name("href")), "<a href>2</a>");
+    ///
+    /// let svg = Html::parse(r##"<svg><use xlink:href="#a"/><use/></svg>"##).unwrap();
+    /// assert_eq!(
+    ///     svg.to_filtered(&Filter::new().attribute_present("href")),
+    ///     r##"<use xlink:href="#a"></use>"##
+    /// );
+    /// ```
+    #[must_use]
+    pub fn attribute_present<N: Into<String>>(mut self, name: N) -> Self {
+        self.attrs.push(name.into(), AttributeMatch::Present, true);
+        self
+    }
+
     /// Specifies the value of an attribute in the wanted tags.
     ///
     /// This matches only tag attributes that have the correct value for the
     /// given name. To match only one value inside that values (e.g. class
-    /// names), cf. [`Filter::attribute_value_contains`].
+    /// names), cf. [`Filter::attribute_value_contains`] and [`Filter::class`].
     ///
     /// See [`Filter`] for usage information.
     #[must_use]
@@ -241,7 +419,9 @@ impl Filter {
     ///
     /// This matches only tag attributes that have the given value as part of
     /// the space-separated values inside the attribute value (cf. example
-    /// below). To match exact value, see [`Filter::attribute_value`].
+    /// below), like the `[attr~=value]` CSS selector. To match exact value,
+    /// see [`Filter::attribute_value`], and to match any part of the value,
+    /// see [`Filter::attribute_value_substring`].
     ///
     ///
     /// # Examples
@@ -268,6 +448,137 @@ impl Filter {
         self
     }
 
+    /// Specifies the end of the value of an attribute in the wanted tags.
+    ///
+    /// This matches the tags whose attribute value ends with the given
+    /// string, like the `[attr$=value]` CSS selector. The comparison is
+    /// case-sensitive, and the tags whose attribute doesn't have a value don't
+    /// match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html =
+    ///     Html::parse(r#"<a href="/files/cv.pdf">PDF</a><a href="/cv.html">Page</a>"#).unwrap();
+    ///
+    /// assert_eq!(
+    ///     html.to_filtered(&Filter::new().attribute_value_ends_with("href", ".pdf")),
+    ///     r#"<a href="/files/cv.pdf">PDF</a>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn attribute_value_ends_with<N: Into<String>, V: Into<String>>(
+        mut self,
+        name: N,
+        suffix: V,
+    ) -> Self {
+        self.attrs.push(name.into(), AttributeMatch::Suffix(suffix.into()), true);
+        self
+    }
+
+    /// Specifies a regular expression that the value of an attribute of the
+    /// wanted tags must match.
+    ///
+    /// The pattern uses the syntax of the [`regex`] crate. It isn't anchored:
+    /// use `^` and `$` to match the whole value. The tags whose attribute
+    /// doesn't have a value don't match.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pattern isn't a valid regular expression.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     r#"<a href="https://example.com/report.pdf">1</a><a href="http://example.com/old.pdf">2</a><a href="https://example.com/page">3</a>"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// let filter = Filter::new().attribute_value_regex("href", r"^https://.*\.pdf$").unwrap();
+    /// assert_eq!(html.to_filtered(&filter), r#"<a href="https://example.com/report.pdf">1</a>"#);
+    ///
+    /// assert!(Filter::new().attribute_value_regex("href", "(unclosed").is_err());
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn attribute_value_regex<N: Into<String>>(
+        mut self,
+        name: N,
+        pattern: &str,
+    ) -> Result<Self, RegexError> {
+        let regex = Regex::new(pattern)?;
+        self.attrs.push(name.into(), AttributeMatch::Regex(Pattern(regex)), true);
+        Ok(self)
+    }
+
+    /// Specifies the start of the value of an attribute in the wanted tags.
+    ///
+    /// This matches the tags whose attribute value starts with the given
+    /// string, like the `[attr^=value]` CSS selector. The comparison is
+    /// case-sensitive, and the tags whose attribute doesn't have a value don't
+    /// match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html =
+    ///     Html::parse(r#"<a href="data:text/plain;base64,SGk=">Data</a><a href="/hi.txt">File</a>"#)
+    ///         .unwrap();
+    ///
+    /// assert_eq!(
+    ///     html.to_filtered(&Filter::new().attribute_value_starts_with("href", "data:")),
+    ///     r#"<a href="data:text/plain;base64,SGk=">Data</a>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn attribute_value_starts_with<N: Into<String>, V: Into<String>>(
+        mut self,
+        name: N,
+        prefix: V,
+    ) -> Self {
+        self.attrs.push(name.into(), AttributeMatch::Prefix(prefix.into()), true);
+        self
+    }
+
+    /// Specifies a part of the value of an attribute in the wanted tags.
+    ///
+    /// This matches the tags whose attribute value contains the given string
+    /// anywhere, like the `[attr*=value]` CSS selector. Contrary to
+    /// [`Filter::attribute_value_contains`], the string doesn't need to be a
+    /// whole space-separated word. The comparison is case-sensitive, and the
+    /// tags whose attribute doesn't have a value don't match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     r#"<a href="https://cdn.example.com/a.js">1</a><a href="https://example.org/">2</a>"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(
+    ///     html.to_filtered(&Filter::new().attribute_value_substring("href", "example.com")),
+    ///     r#"<a href="https://cdn.example.com/a.js">1</a>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn attribute_value_substring<N: Into<String>, V: Into<String>>(
+        mut self,
+        name: N,
+        value: V,
+    ) -> Self {
+        self.attrs.push(name.into(), AttributeMatch::Substring(value.into()), true);
+        self
+    }
+
     /// Collapses successive text nodes.
     ///
     /// # Examples
@@ -447,11 +758,76 @@ impl Filter {
         self
     }
 
+    /// Specifies an example of the structure of the wanted tags.
+    ///
+    /// A tag matches if its structure is at most `tolerance` edits away from
+    /// the structure of the first tag of the example. The structure is made
+    /// of the names of the tags and of the places of the non-empty texts: the
+    /// attributes, the contents of the texts, and the comments are ignored.
+    /// An edit is the renaming of a tag, or the insertion or deletion of a
+    /// node.
+    ///
+    /// This is useful to select the items of a list when their classes change
+    /// but their structure doesn't. With a tolerance of `0`, only the tags
+    /// with exactly the same structure match. If the example has no tags, no
+    /// tag matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     r#"<div class="x1"><h2>Phone</h2><span>$10</span></div><div class="y2"><h2>Case</h2><span>$5</span><b>New</b></div><div><p>Ad</p></div>"#,
+    /// )
+    /// .unwrap();
+    /// let example = Html::parse("<div><h2>Title</h2><span>Price</span></div>").unwrap();
+    ///
+    /// assert_eq!(
+    ///     html.to_filtered(&Filter::new().like(&example, 0)),
+    ///     r#"<div class="x1"><h2>Phone</h2><span>$10</span></div>"#
+    /// );
+    /// assert_eq!(
+    ///     html.to_filtered(&Filter::new().like(&example, 2)),
+    ///     r#"<div class="x1"><h2>Phone</h2><span>$10</span></div><div class="y2"><h2>Case</h2><span>$5</span><b>New</b></div>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn like(mut self, example: &Html, tolerance: usize) -> Self {
+        self.like = Some(Like::new(example, tolerance));
+        self
+    }
+
+    /// Specifies the maximum number of attributes of the wanted tags.
+    ///
+    /// This is useful to match bare structural tags, such as `<div>` wrappers
+    /// that are only used for the presentation. See also
+    /// [`Filter::no_attributes`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(r#"<p id="a" class="b">1</p><p id="c">2</p><p>3</p>"#).unwrap();
+    ///
+    /// assert_eq!(
+    ///     html.to_filtered(&Filter::new().tag_name("p").max_attributes(1)),
+    ///     r#"<p id="c">2</p><p>3</p>"#
+    /// );
+    /// assert_eq!(html.to_filtered(&Filter::new().max_attributes(0)), "<p>3</p>");
+    /// ```
+    #[must_use]
+    pub fn max_attributes(mut self, max: usize) -> Self {
+        self.attrs.set_max_count(max);
+        self
+    }
+
     /// Creates a default [`Filter`]
     ///
     /// By default, *comments* and *doctypes* are allowed, however no node is
     /// wanted, so filtering on a default filter will return an empty
-    /// [`Html`](super::Html).
+    /// [`Html`].
     ///
     /// # Examples
     ///
@@ -464,12 +840,42 @@ impl Filter {
     pub const fn new() -> Self {
         Self {
             attrs: ValueAssociateHash::new(),
+            combined: Combined::new(),
             depth: 0,
+            inside: vec![],
+            like: None,
+            limit: None,
+            max_output_bytes: None,
+            max_output_nodes: None,
+            positions: vec![],
+            predicates: Predicates::new(),
             tags: BlackWhiteList::new(),
+            text_content: TextContent::new(),
             types: NodeTypeFilter::new(),
         }
     }
 
+    /// Matches only the tags without any attributes.
+    ///
+    /// This is equivalent to [`Filter::max_attributes`] with `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(r#"<div class="card"><div><p>Text</p></div></div>"#).unwrap();
+    ///
+    /// assert_eq!(
+    ///     html.to_filtered(&Filter::new().tag_name("div").no_attributes()),
+    ///     "<div><p>Text</p></div>"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn no_attributes(self) -> Self {
+        self.max_attributes(0)
+    }
+
     /// Disable all tags, except those explicitly whitelisted
     ///
     /// # Example
@@ -494,6 +900,68 @@ impl Filter {
         self
     }
 
+    /// Specifies a custom condition on the html node of the wanted tags.
+    ///
+    /// The predicate is called on the [`Html::Tag`] nodes, with their content,
+    /// and a tag is only wanted if the predicate returns `true`. This allows
+    /// to express the conditions that the other rules can't, like a minimum
+    /// number of children. To check only the tag, without its content, prefer
+    /// [`Filter::tag_predicate`].
+    ///
+    /// Calling it several times requires all the predicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<ul><li>1</li></ul><ol><li>1</li><li>2</li><li>3</li></ol>").unwrap();
+    ///
+    /// let filter = Filter::new().node_predicate(|node| {
+    ///     node.as_tag()
+    ///         .is_some_and(|(_, child)| child.find_all(&Filter::new().tag_name("li")).len() > 2)
+    /// });
+    /// assert_eq!(html.to_filtered(&filter), "<ol><li>1</li><li>2</li><li>3</li></ol>");
+    /// ```
+    #[must_use]
+    pub fn node_predicate<F: Fn(&Html) -> bool + Send + Sync + 'static>(
+        mut self,
+        predicate: F,
+    ) -> Self {
+        self.predicates.push_node(Box::new(predicate));
+        self
+    }
+
+    /// Keeps only the tags whose content is raw text, like the `<script>` and
+    /// `<style>` tags (see [`Tag::is_raw_text`]).
+    ///
+    /// Combined with [`Html::as_raw_text`], this extracts the inline scripts
+    /// or data blocks of a page.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     r#"<head><script type="application/ld+json">{"@type": "Article"}</script><script src="/a.js"></script><style>p {}</style></head>"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// let filter = Filter::new().raw_text_tags().attribute_value("type", "application/ld+json");
+    /// let json = html
+    ///     .select(&filter)
+    ///     .filter_map(|node| Some(node.as_html().as_raw_text()?.1))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(json, [r#"{"@type": "Article"}"#]);
+    ///
+    /// assert_eq!(html.select(&Filter::new().raw_text_tags()).count(), 3);
+    /// ```
+    #[must_use]
+    pub fn raw_text_tags(self) -> Self {
+        self.tag_predicate(Tag::is_raw_text)
+    }
+
     /// Specifies the tag name of the wanted tags.
     ///
     /// See [`Filter`] for usage information.
@@ -503,4 +971,409 @@ impl Filter {
         self.tags.push(name.into(), true);
         self
     }
+
+    /// Specifies a custom condition on the wanted tags.
+    ///
+    /// The predicate is called on the tags, without their content, and a tag
+    /// is only wanted if the predicate returns `true`. This allows to express
+    /// the conditions that the other rules can't, like a minimum number of
+    /// attributes or the host of a link. To check the content of the tags,
+    /// use [`Filter::node_predicate`].
+    ///
+    /// Calling it several times requires all the predicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     r#"<a href="https://example.com/docs">Docs</a><a href="https://example.org/">Other</a>"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// let filter = Filter::new().tag_name("a").tag_predicate(|tag| {
+    ///     tag.find_attr_value("href").is_some_and(|href| {
+    ///         href.strip_prefix("https://")
+    ///             .and_then(|url| url.split('/').next())
+    ///             .is_some_and(|host| host == "example.com")
+    ///     })
+    /// });
+    /// assert_eq!(html.to_filtered(&filter), r#"<a href="https://example.com/docs">Docs</a>"#);
+    /// ```
+    #[must_use]
+    pub fn tag_predicate<F: Fn(&Tag) -> bool + Send + Sync + 'static>(
+        mut self,
+        predicate: F,
+    ) -> Self {
+        self.predicates.push_tag(Box::new(predicate));
+        self
+    }
+
+    /// Specifies a text contained in the text content of the wanted tags.
+    ///
+    /// The text content of a tag is the concatenation of the texts of its
+    /// descendants, without the comments. The whitespace of both the text
+    /// content and the given text is collapsed before comparing them, so the
+    /// line breaks and the indentation of the document are ignored. The
+    /// comparison is case-sensitive.
+    ///
+    /// The ancestors of a matching tag contain the same text: combine this
+    /// rule with others, like [`Filter::tag_name`], to select the wanted tag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     r#"<form><button id="b-x1">Save</button><button id="b-y2">
+    ///     <i class="icon"></i> Add to   cart
+    /// </button></form>"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// let filter = Filter::new().tag_name("button").text_contains("Add to cart");
+    /// assert!(html.find(&filter).to_string().starts_with(r#"<button id="b-y2">"#));
+    /// ```
+    #[must_use]
+    pub fn text_contains<T: Into<String>>(mut self, text: T) -> Self {
+        self.text_content.push(TextMatch::Contains(text.into()));
+        self
+    }
+
+    /// Specifies the text content of the wanted tags.
+    ///
+    /// The text content of a tag is the concatenation of the texts of its
+    /// descendants, without the comments. It must be equal to the given text,
+    /// once the whitespace of both is collapsed, like for
+    /// [`Filter::text_contains`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html =
+    ///     Html::parse("<ul><li>Home</li><li> <b>Home</b> page </li><li>Homepage</li></ul>").unwrap();
+    ///
+    /// assert_eq!(
+    ///     html.to_filtered(&Filter::new().tag_name("li").text_equals("Home page")),
+    ///     "<li> <b>Home</b> page </li>"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn text_equals<T: Into<String>>(mut self, text: T) -> Self {
+        self.text_content.push(TextMatch::Equals(text.into()));
+        self
+    }
+}
+
+/// Public API for [`Filter`] on the frequent global attributes
+///
+/// These methods are shortcuts for the attribute filters, e.g.,
+/// [`Filter::attribute_value`].
+impl Filter {
+    /// Specifies a class of the wanted tags.
+    ///
+    /// The `class` attribute is split on whitespace, and a tag matches if one
+    /// of its classes is exactly the given name. Contrary to
+    /// [`Filter::attribute_value`], a tag with `class="card shadow"` is thus
+    /// matched by `class("card")`. Calling it several times requires all the
+    /// given classes.
+    ///
+    /// This is equivalent to [`Filter::attribute_value_contains`] with
+    /// `"class"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     r#"<div class="card shadow">1</div><div class="card">2</div><div class="cards">3</div>"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(
+    ///     html.to_filtered(&Filter::new().class("card")),
+    ///     r#"<div class="card shadow">1</div><div class="card">2</div>"#
+    /// );
+    /// assert_eq!(
+    ///     html.to_filtered(&Filter::new().class("card").class("shadow")),
+    ///     r#"<div class="card shadow">1</div>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn class<V: Into<String>>(self, name: V) -> Self {
+        self.attribute_value_contains("class", name)
+    }
+
+    /// Specifies a class of the wanted tags, tolerating the hash suffixes
+    /// generated by the build tools.
+    ///
+    /// Tools like CSS modules or CSS-in-JS libraries mangle the class names by
+    /// appending a hash, that changes at every build: `product-card` becomes
+    /// `product-card__x1y2z`. A class matches if it is exactly the given name,
+    /// or the given name followed by `_`, `__`, `-` or `--` and a generated
+    /// suffix, i.e., at least 4 ASCII alphanumeric characters with a digit or
+    /// with mixed case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     r#"<div class="product-card__x1y2z">1</div><div class="product-card-title">2</div><div class="big product-card">3</div><div class="product-card_aBcD">4</div>"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(
+    ///     html.to_filtered(&Filter::new().class_matches_hashed("product-card")),
+    ///     r#"<div class="product-card__x1y2z">1</div><div class="big product-card">3</div><div class="product-card_aBcD">4</div>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn class_matches_hashed<V: Into<String>>(mut self, name: V) -> Self {
+        self.attrs.push("class".to_owned(), AttributeMatch::HashedWord(name.into()), true);
+        self
+    }
+
+    /// Specifies the beginning of a class of the wanted tags.
+    ///
+    /// A tag matches if one of its classes starts with the given prefix. This
+    /// is more tolerant than [`Filter::class_matches_hashed`], but can also
+    /// match unrelated classes, like `product-cards`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     r#"<div class="big product-card__x1y2z">1</div><div class="old-product-card">2</div>"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(
+    ///     html.to_filtered(&Filter::new().class_matches_prefix("product-card")),
+    ///     r#"<div class="big product-card__x1y2z">1</div>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn class_matches_prefix<V: Into<String>>(mut self, prefix: V) -> Self {
+        self.attrs.push("class".to_owned(), AttributeMatch::WordPrefix(prefix.into()), true);
+        self
+    }
+
+    /// Specifies whether the wanted tags are editable, with the
+    /// `contenteditable` attribute.
+    ///
+    /// - `true`: keeps only the tags that are editable, i.e., whose
+    ///   `contenteditable` attribute is empty, `true` or `plaintext-only`.
+    /// - `false`: dismisses the tags that are editable.
+    ///
+    /// The editability inherited from the ancestors isn't taken into account.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     r#"<p contenteditable>1</p><p contenteditable="false">2</p><p contenteditable="TRUE">3</p>"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(
+    ///     html.to_filtered(&Filter::new().contenteditable(true)),
+    ///     r#"<p contenteditable>1</p><p contenteditable="TRUE">3</p>"#
+    /// );
+    /// assert_eq!(
+    ///     html.to_filtered(&Filter::new().tag_name("p").contenteditable(false)),
+    ///     r#"<p contenteditable="false">2</p>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn contenteditable(mut self, editable: bool) -> Self {
+        self.attrs.push(
+            "contenteditable".to_owned(),
+            AttributeMatch::Keyword(&["", "true", "plaintext-only"]),
+            editable,
+        );
+        self
+    }
+
+    /// Specifies a class of the tags that must be dismissed.
+    ///
+    /// The `class` attribute is split on whitespace, and a tag is dismissed if
+    /// one of its classes is exactly the given name, like for
+    /// [`Filter::class`].
+    ///
+    /// This is equivalent to [`Filter::except_attribute_value_contains`] with
+    /// `"class"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     r#"<div class="card ad">1</div><div class="card">2</div><div class="ads">3</div>"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(
+    ///     html.to_filtered(&Filter::new().tag_name("div").except_class("ad")),
+    ///     r#"<div class="card">2</div><div class="ads">3</div>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn except_class<V: Into<String>>(self, name: V) -> Self {
+        self.except_attribute_value_contains("class", name)
+    }
+
+    /// Specifies whether the wanted tags are hidden, with the `hidden`
+    /// attribute.
+    ///
+    /// - `true`: keeps only the tags that have a `hidden` attribute, with or
+    ///   without value (e.g. `hidden="until-found"`).
+    /// - `false`: dismisses the tags that have a `hidden` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(r#"<p hidden>1</p><p>2</p><p hidden="until-found">3</p>"#).unwrap();
+    ///
+    /// assert_eq!(
+    ///     html.to_filtered(&Filter::new().hidden(true)),
+    ///     r#"<p hidden>1</p><p hidden="until-found">3</p>"#
+    /// );
+    /// assert_eq!(html.to_filtered(&Filter::new().tag_name("p").hidden(false)), "<p>2</p>");
+    /// ```
+    #[must_use]
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.attrs.push("hidden".to_owned(), AttributeMatch::Present, hidden);
+        self
+    }
+
+    /// Specifies the `id` of the wanted tag.
+    ///
+    /// This is equivalent to [`Filter::attribute_value`] with `"id"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(r#"<p id="first">1</p><p id="second">2</p>"#).unwrap();
+    /// assert_eq!(html.find(&Filter::new().id("second")), r#"<p id="second">2</p>"#);
+    /// ```
+    #[must_use]
+    pub fn id<V: Into<String>>(self, id: V) -> Self {
+        self.attribute_value("id", id)
+    }
+
+    /// Specifies a text contained in the `title` attribute of the wanted
+    /// tags.
+    ///
+    /// Contrary to [`Filter::attribute_value_contains`], the text doesn't need
+    /// to be a whole word: any substring of the title matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     r#"<abbr title="HyperText Markup Language">HTML</abbr><abbr title="Cascading Style Sheets">CSS</abbr>"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(
+    ///     html.to_filtered(&Filter::new().title_contains("Text Mark")),
+    ///     r#"<abbr title="HyperText Markup Language">HTML</abbr>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn title_contains<V: Into<String>>(mut self, text: V) -> Self {
+        self.attrs.push("title".to_owned(), AttributeMatch::Substring(text.into()), true);
+        self
+    }
+}
+
+/// Public API for [`Filter`] on the size of the output
+///
+/// These limits protect the consumers of the results from unexpectedly large
+/// matches. The results are the nodes at the root of the filtered tree: they
+/// are kept in order, until the next one would exceed a limit. The other
+/// results are dropped, and are never split.
+impl Filter {
+    /// Keeps only the first `n` results, and stops the traversal once they
+    /// are found.
+    ///
+    /// Unlike [`Filter::max_output_nodes`], which drops the extra results
+    /// after the whole tree was filtered, the siblings that follow the `n`-th
+    /// match are not visited. [`Html::find`] is equivalent to a limit of `1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<ul><li>First</li><li>Second</li><li>Third</li></ul>").unwrap();
+    ///
+    /// let filter = Filter::new().tag_name("li").limit(2);
+    /// assert_eq!(html.to_filtered(&filter), "<li>First</li><li>Second</li>");
+    /// ```
+    #[must_use]
+    pub const fn limit(mut self, n: usize) -> Self {
+        self.limit = Some(n);
+        self
+    }
+
+    /// Sets the maximum size, in bytes, of the serialised output.
+    ///
+    /// The size of a result is the length of its [`ToString::to_string`]
+    /// output. If the first result is already too large, the output is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<ul><li>First</li><li>Second</li><li>Third</li></ul>").unwrap();
+    ///
+    /// let filter = Filter::new().tag_name("li").max_output_bytes(30);
+    /// assert_eq!(html.to_filtered(&filter), "<li>First</li><li>Second</li>");
+    ///
+    /// let filter = Filter::new().tag_name("li").max_output_bytes(5);
+    /// assert_eq!(html.to_filtered(&filter), Html::Empty);
+    /// ```
+    #[must_use]
+    pub const fn max_output_bytes(mut self, max: usize) -> Self {
+        self.max_output_bytes = Some(max);
+        self
+    }
+
+    /// Sets the maximum number of results in the output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<ul><li>First</li><li>Second</li><li>Third</li></ul>").unwrap();
+    ///
+    /// let filter = Filter::new().tag_name("li").max_output_nodes(2);
+    /// assert_eq!(html.to_filtered(&filter), "<li>First</li><li>Second</li>");
+    /// ```
+    #[must_use]
+    pub const fn max_output_nodes(mut self, max: usize) -> Self {
+        self.max_output_nodes = Some(max);
+        self
+    }
 }
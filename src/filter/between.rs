@@ -0,0 +1,64 @@
+//! Module to select the sibling nodes between two marker tags.
+
+use crate::{Filter, Html};
+
+impl Html {
+    /// Selects the nodes strictly between the first tag that matches `start`
+    /// and the next sibling tag that matches `end`.
+    ///
+    /// The tags are searched in the order of the document, and the end tag
+    /// must be at the same level as the start tag, i.e., have the same parent.
+    /// The marker tags aren't in the selection. If no sibling matches `end`,
+    /// the selection goes until the last sibling of the start tag, and if no
+    /// tag matches `start`, the selection is empty.
+    ///
+    /// This allows to split a document into sections with its headings, with
+    /// the same filter for both markers, or to extract the regions delimited
+    /// by marker tags. See [`Html::to_siblings`] to select the siblings by
+    /// index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     "<main><h2>Install</h2><p>Run it.</p><pre>cargo add</pre><h2>Usage</h2><p>Call \
+    ///      it.</p></main>",
+    /// )
+    /// .unwrap();
+    ///
+    /// let heading = Filter::new().tag_name("h2");
+    /// assert_eq!(html.select_between(&heading, &heading), "<p>Run it.</p><pre>cargo add</pre>");
+    ///
+    /// let usage = Filter::new().tag_name("h2").text_equals("Usage");
+    /// assert_eq!(html.select_between(&usage, &heading), "<p>Call it.</p>");
+    /// assert_eq!(html.select_between(&Filter::new().tag_name("nav"), &heading), Html::Empty);
+    /// ```
+    #[must_use]
+    pub fn select_between(&self, start: &Filter, end: &Filter) -> Self {
+        self.select_between_aux(start, end).unwrap_or_default()
+    }
+
+    /// Wrapper for [`Html::select_between`]
+    ///
+    /// Returns `None` if no tag matched the `start` filter in this node.
+    fn select_between_aux(&self, start: &Filter, end: &Filter) -> Option<Self> {
+        match self {
+            Self::Tag { .. } if start.tag_allowed(self) => Some(Self::Empty),
+            Self::Tag { child, .. } => child.select_between_aux(start, end),
+            Self::Vec(vec) => vec.iter().enumerate().find_map(|(idx, sibling)| {
+                if !start.tag_allowed(sibling) {
+                    return sibling.select_between_aux(start, end);
+                }
+                let first = idx.saturating_add(1);
+                let last = vec
+                    .get(first..)
+                    .and_then(|rest| rest.iter().position(|next| end.tag_allowed(next)))
+                    .map_or(vec.len(), |len| first.saturating_add(len));
+                Some(self.to_siblings(first..last))
+            }),
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty | Self::Text(_) => None,
+        }
+    }
+}
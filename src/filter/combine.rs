@@ -0,0 +1,187 @@
+//! Module to combine filters with boolean operators, see [`Filter::and`],
+//! [`Filter::not`] and [`Filter::or`].
+
+use super::element::ElementState;
+use crate::{Filter, Html};
+
+/// Filters combined with the rules of a filter on the tags.
+///
+/// Only the rules on the tags of the combined filters are used: their node
+/// types, depth and output limits are ignored.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Combined {
+    /// Filters that the wanted tags must all match
+    and: Vec<Filter>,
+    /// Filters that the wanted tags must not match
+    not: Vec<Filter>,
+    /// Alternatives to the rules of the filter: a tag is wanted if it
+    /// satisfies the rules, or if it matches any of these filters.
+    or: Vec<Filter>,
+}
+
+impl Combined {
+    /// Checks if a tag matches all the [`Self::and`] filters and none of the
+    /// [`Self::not`] filters.
+    pub fn check(&self, node: &Html) -> ElementState {
+        if self.and.is_empty() && self.not.is_empty() {
+            ElementState::NotSpecified
+        } else if self.and.iter().all(|filter| filter.tag_allowed(node))
+            && !self.not.iter().any(|filter| filter.tag_allowed(node))
+        {
+            ElementState::WhiteListed
+        } else {
+            ElementState::BlackListed
+        }
+    }
+
+    /// Combines the state of a tag given by all the other rules with the
+    /// [`Self::or`] filters.
+    ///
+    /// If there are alternatives, a tag without any other rule only needs to
+    /// match one of them.
+    pub fn either(&self, node: &Html, state: ElementState) -> ElementState {
+        if self.or.is_empty() || matches!(state, ElementState::WhiteListed) {
+            state
+        } else if self.or.iter().any(|filter| filter.tag_allowed(node)) {
+            ElementState::WhiteListed
+        } else {
+            ElementState::BlackListed
+        }
+    }
+
+    /// Checks if no filters were combined.
+    pub const fn is_empty(&self) -> bool {
+        self.and.is_empty() && self.not.is_empty() && self.or.is_empty()
+    }
+
+    /// Creates an empty combination.
+    pub const fn new() -> Self {
+        Self { and: vec![], not: vec![], or: vec![] }
+    }
+
+    /// Adds a filter that the wanted tags must match.
+    pub fn push_and(&mut self, filter: Filter) {
+        self.and.push(filter);
+    }
+
+    /// Adds a filter that the wanted tags must not match.
+    pub fn push_not(&mut self, filter: Filter) {
+        self.not.push(filter);
+    }
+
+    /// Adds an alternative filter.
+    pub fn push_or(&mut self, filter: Filter) {
+        self.or.push(filter);
+    }
+
+    /// Describes every combined filter, numbered in the order they were added,
+    /// with whether a tag satisfies it.
+    pub fn rule_results(&self, node: &Html) -> Vec<(String, bool)> {
+        let and = self
+            .and
+            .iter()
+            .zip(1usize..)
+            .map(|(filter, number)| (format!("and filter {number}"), filter.tag_allowed(node)));
+        let not =
+            self.not.iter().zip(1usize..).map(|(filter, number)| {
+                (format!("not filter {number}"), !filter.tag_allowed(node))
+            });
+        let or = self
+            .or
+            .iter()
+            .zip(1usize..)
+            .map(|(filter, number)| (format!("or filter {number}"), filter.tag_allowed(node)));
+        and.chain(not).chain(or).collect()
+    }
+}
+
+/// Public API for [`Filter`] on the combination of filters
+///
+/// These methods group the rules on the tags, which is otherwise impossible:
+/// the names given to [`Filter::tag_name`] are alternatives, but they must
+/// all satisfy the rules on the attributes.
+impl Filter {
+    /// Keeps only the tags that also match another filter.
+    ///
+    /// This is useful to add a group of alternatives to the rules of a
+    /// filter. Only the rules on the tags of `other` are used, not its node
+    /// types, its depth or its output limits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     r#"<div class="card">1</div><section class="card">2</section><p class="card">3</p>"#,
+    /// )
+    /// .unwrap();
+    /// let filter = Filter::new()
+    ///     .class("card")
+    ///     .and(Filter::new().tag_name("div").or(Filter::new().tag_name("section")));
+    ///
+    /// assert_eq!(
+    ///     html.to_filtered(&filter),
+    ///     r#"<div class="card">1</div><section class="card">2</section>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn and(mut self, other: Self) -> Self {
+        self.combined.push_and(other);
+        self
+    }
+
+    /// Removes the tags that match another filter.
+    ///
+    /// Only the rules on the tags of `other` are used, not its node types, its
+    /// depth or its output limits. On an empty filter, this keeps every tag
+    /// that doesn't match `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html =
+    ///     Html::parse(r#"<a href="/about">About</a><a href="https://example.com">Out</a>"#).unwrap();
+    /// let filter = Filter::new()
+    ///     .tag_name("a")
+    ///     .not(Filter::new().attribute_value_starts_with("href", "https:"));
+    ///
+    /// assert_eq!(html.to_filtered(&filter), r#"<a href="/about">About</a>"#);
+    /// ```
+    #[must_use]
+    pub fn not(mut self, other: Self) -> Self {
+        self.combined.push_not(other);
+        self
+    }
+
+    /// Keeps the tags that match either the rules of the filter, or another
+    /// filter.
+    ///
+    /// Only the rules on the tags of `other` are used, not its node types, its
+    /// depth or its output limits. On an empty filter, this keeps only the
+    /// tags that match `other`, so `Filter::new().or(a).or(b)` keeps the tags
+    /// that match `a` or `b`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html =
+    ///     Html::parse(r#"<a href="/">Home</a><a>Top</a><video src="x.mp4"></video><video></video>"#)
+    ///         .unwrap();
+    /// let filter = Filter::new()
+    ///     .tag_name("a")
+    ///     .attribute_present("href")
+    ///     .or(Filter::new().tag_name("video").attribute_present("src"));
+    ///
+    /// assert_eq!(html.to_filtered(&filter), r#"<a href="/">Home</a><video src="x.mp4"></video>"#);
+    /// ```
+    #[must_use]
+    pub fn or(mut self, other: Self) -> Self {
+        self.combined.push_or(other);
+        self
+    }
+}
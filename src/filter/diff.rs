@@ -0,0 +1,115 @@
+//! Module to compare the matches of a filter on two versions of a document.
+
+use core::hash::BuildHasher as _;
+use std::collections::HashMap;
+use std::hash::RandomState;
+
+use crate::{Filter, Html, NodeRef};
+
+/// Matches of a filter on two versions of a document.
+///
+/// This is returned by [`filter_diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterDiff<'old, 'new> {
+    /// Matches only found in the new document
+    added: Vec<NodeRef<'new>>,
+    /// Pairs of identical matches found in both documents
+    kept: Vec<(NodeRef<'old>, NodeRef<'new>)>,
+    /// Matches only found in the old document
+    removed: Vec<NodeRef<'old>>,
+}
+
+impl<'old, 'new> FilterDiff<'old, 'new> {
+    /// Returns the matches only found in the new document, in the order of
+    /// the new document.
+    #[must_use]
+    pub fn as_added(&self) -> &[NodeRef<'new>] {
+        &self.added
+    }
+
+    /// Returns the pairs of identical matches, found in both documents, in the
+    /// order of the new document.
+    #[must_use]
+    pub fn as_kept(&self) -> &[(NodeRef<'old>, NodeRef<'new>)] {
+        &self.kept
+    }
+
+    /// Returns the matches only found in the old document, in the order of
+    /// the old document.
+    #[must_use]
+    pub fn as_removed(&self) -> &[NodeRef<'old>] {
+        &self.removed
+    }
+
+    /// Checks if the matches are the same in both documents.
+    #[must_use]
+    pub const fn is_unchanged(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Compares the matches of a filter on two versions of a document.
+///
+/// The tags that match the filter (see [`Html::select`]) in both documents
+/// are paired by content: two matches are paired if they have the same
+/// serialisation, including their attributes and their descendants. When a
+/// content appears multiple times, the matches are paired in the order of the
+/// documents.
+///
+/// This is useful for the bots that monitor a page, to report the added and
+/// removed items directly.
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// let old = Html::parse("<ul><li>Apple</li><li>Pear</li><li>Plum</li></ul>").unwrap();
+/// let new = Html::parse("<ul><li>Plum</li><li>Apple</li><li>Kiwi</li></ul>").unwrap();
+///
+/// let diff = filter_diff(&old, &new, &Filter::new().tag_name("li"));
+/// assert_eq!(diff.as_removed()[0].as_html(), &"<li>Pear</li>");
+/// assert_eq!(diff.as_added()[0].as_html(), &"<li>Kiwi</li>");
+/// assert_eq!(diff.as_kept().len(), 2);
+/// assert!(!diff.is_unchanged());
+/// ```
+#[must_use]
+pub fn filter_diff<'old, 'new>(
+    old: &'old Html,
+    new: &'new Html,
+    filter: &Filter,
+) -> FilterDiff<'old, 'new> {
+    let hasher = RandomState::new();
+    let mut old_nodes = old.select(filter).map(Some).collect::<Vec<_>>();
+    let mut buckets: HashMap<u64, Vec<usize>> = HashMap::new();
+    for (idx, node) in old_nodes.iter().enumerate() {
+        if let Some(old_node) = node {
+            buckets.entry(content_hash(&hasher, old_node.as_html())).or_default().push(idx);
+        }
+    }
+    let mut added = vec![];
+    let mut kept = vec![];
+    for node in new.select(filter) {
+        let paired = buckets.get(&content_hash(&hasher, node.as_html())).and_then(|bucket| {
+            bucket.iter().find_map(|idx| {
+                let candidate = old_nodes.get_mut(*idx)?;
+                if candidate.as_ref()?.as_html() == node.as_html() {
+                    candidate.take()
+                } else {
+                    None
+                }
+            })
+        });
+        match paired {
+            Some(old_node) => kept.push((old_node, node)),
+            None => added.push(node),
+        }
+    }
+    let removed = old_nodes.into_iter().flatten().collect();
+    FilterDiff { added, kept, removed }
+}
+
+/// Hashes the serialisation of a node.
+fn content_hash(hasher: &RandomState, html: &Html) -> u64 {
+    hasher.hash_one(html.to_string())
+}
@@ -0,0 +1,98 @@
+//! Module to measure, in a single pass, how far the wanted nodes are from
+//! every node, to filter with a [`Filter::depth`](crate::Filter::depth) in
+//! linear time.
+
+use super::Walker;
+use crate::Html;
+
+/// Distances from every node of a tree to the closest wanted tag in its
+/// subtree.
+///
+/// The nodes are numbered in pre-order, counting every [`Html`] value: a
+/// [`Html::Tag`] is followed by its child, and the children of a
+/// [`Html::Vec`] follow each other. The descendants of the wanted tags aren't
+/// numbered, as they are kept without being filtered.
+pub struct Distances {
+    /// Distance and size of the subtree of every node, by index
+    entries: Vec<Entry>,
+}
+
+impl Distances {
+    /// Returns the distance from a node to the closest wanted tag in its
+    /// subtree, if any.
+    ///
+    /// The distance is the number of tags between them: `0` for a wanted tag,
+    /// and a [`Html::Vec`] has the distance of its closest child.
+    pub fn get(&self, index: usize) -> Option<usize> {
+        self.entries.get(index)?.distance
+    }
+
+    /// Measures the distances of all the nodes of a tree.
+    ///
+    /// Every node is visited at most once, and the visits are recorded by the
+    /// walker, see [`Walker::visit`].
+    pub fn measure(html: &Html, walker: &Walker<'_>) -> Self {
+        let mut entries = vec![];
+        measure_aux(html, walker, &[], &mut entries);
+        Self { entries }
+    }
+
+    /// Returns the index of the node that follows a node and its subtree,
+    /// i.e., the index of its next sibling.
+    pub fn next_sibling(&self, index: usize) -> usize {
+        index.saturating_add(self.entries.get(index).map_or(1, |entry| entry.size))
+    }
+}
+
+/// Measures of a node
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    /// Distance to the closest wanted tag in the subtree of the node
+    distance: Option<usize>,
+    /// Number of numbered nodes in the subtree, including the node itself
+    size: usize,
+}
+
+/// Auxiliary method for [`Distances::measure`]
+///
+/// Pushes the entries of the subtree of the node in pre-order, and returns
+/// the distance of the node. `scope` is the scope of the node, see
+/// [`Filter::inside`](crate::Filter::inside).
+fn measure_aux(
+    html: &Html,
+    walker: &Walker<'_>,
+    scope: &[bool],
+    entries: &mut Vec<Entry>,
+) -> Option<usize> {
+    let index = entries.len();
+    entries.push(Entry { distance: None, size: 1 });
+    if !walker.visit() {
+        return None;
+    }
+    let filter = walker.filter;
+    let distance = match html {
+        Html::Empty | Html::Text(_) | Html::Comment(_) | Html::Doctype { .. } => None,
+        Html::Tag { .. } if filter.tag_explicitly_allowed(html, scope) => Some(0),
+        Html::Tag { child, .. } => measure_aux(
+            child,
+            walker,
+            filter.enter_scope(html, scope).as_deref().unwrap_or(scope),
+            entries,
+        )
+        .map(|depth| depth.saturating_add(1)),
+        Html::Vec(vec) => {
+            let scopes = filter.sibling_scopes(vec, scope);
+            vec.iter()
+                .enumerate()
+                .filter_map(|(idx, child)| {
+                    measure_aux(child, walker, scopes.get(idx, scope), entries)
+                })
+                .min()
+        }
+    };
+    let size = entries.len().saturating_sub(index);
+    if let Some(entry) = entries.get_mut(index) {
+        *entry = Entry { distance, size };
+    }
+    distance
+}
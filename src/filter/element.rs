@@ -4,6 +4,9 @@
 
 use std::collections::HashMap;
 
+#[cfg(feature = "regex")]
+use regex::Regex;
+
 use crate::types::tag::Attribute;
 
 /// Stores the status of an element, i.e., whether it ought to be kept or
@@ -62,6 +65,35 @@ impl BlackWhiteList {
         )
     }
 
+    /// Describes the rules on the tag names, for the statistics of the
+    /// filters.
+    pub fn description(&self) -> String {
+        let names = |keep: bool| {
+            self.items
+                .iter()
+                .filter(|(_, item_keep)| *item_keep == keep)
+                .map(|(name, _)| format!("'{name}'"))
+                .collect::<Vec<_>>()
+        };
+        let (wanted, unwanted) = (names(true), names(false));
+        let mut parts = vec![];
+        match (wanted.is_empty(), self.default) {
+            (false, _) => parts.push(format!("tag name {}", wanted.join(" or "))),
+            (true, false) => parts.push("no tags".to_owned()),
+            (true, true) => (),
+        }
+        if !unwanted.is_empty() {
+            parts.push(format!("tag name not {}", unwanted.join(" nor ")));
+        }
+        parts.join(", ")
+    }
+
+    /// Checks if the rules reject some names, i.e., if some names were
+    /// specified, or if the names are rejected by default.
+    pub const fn has_rules(&self) -> bool {
+        !self.items.is_empty() || !self.default
+    }
+
     /// Checks if no elements were specified
     pub const fn is_empty(&self) -> bool {
         self.whitelist_empty
@@ -149,20 +181,106 @@ impl ElementState {
 pub enum AttributeMatch {
     /// The tag's value must contain a word equal to the given string.
     Contains(String),
+    /// The tag's value must contain a word equal to the given string, or
+    /// equal to the given string followed by a generated hash suffix.
+    ///
+    /// See [`is_hashed_word`] for more information.
+    HashedWord(String),
     /// The tag's value must be exactly the given string.
     Is(String),
+    /// The tag's value must be one of the given keywords, ignoring the ASCII
+    /// case.
+    ///
+    /// An attribute without a value is treated as an empty value.
+    Keyword(&'static [&'static str]),
     /// The tag must not have a value.
     NoValue,
+    /// The tag's value must start with the given string.
+    Prefix(String),
+    /// The tag must have the attribute, with or without value.
+    Present,
+    /// The tag's value must match the given regular expression.
+    #[cfg(feature = "regex")]
+    Regex(Pattern),
+    /// The tag's value must contain the given string.
+    Substring(String),
+    /// The tag's value must end with the given string.
+    Suffix(String),
+    /// The tag's value must contain a word that starts with the given string.
+    WordPrefix(String),
 }
 
 impl AttributeMatch {
+    /// Describes the condition on the value, for the statistics of the
+    /// filters.
+    fn description(&self) -> String {
+        match self {
+            Self::Contains(value) => format!("contains the word '{value}'"),
+            Self::HashedWord(value) => format!("contains the hashed word '{value}'"),
+            Self::Is(value) => format!("is '{value}'"),
+            Self::Keyword(keywords) => format!("is one of '{}'", keywords.join("', '")),
+            Self::NoValue => "has no value".to_owned(),
+            Self::Prefix(value) => format!("starts with '{value}'"),
+            Self::Present => "is present".to_owned(),
+            #[cfg(feature = "regex")]
+            Self::Regex(pattern) => format!("matches '{}'", pattern.0.as_str()),
+            Self::Substring(value) => format!("contains '{value}'"),
+            Self::Suffix(value) => format!("ends with '{value}'"),
+            Self::WordPrefix(value) => format!("has a word starting with '{value}'"),
+        }
+    }
+
     /// Checks if a [`AttributeMatch`] is satisfied by a given attribute value.
     fn matches(&self, attribute_value: Option<&str>) -> bool {
-        attribute_value.map_or(matches!(self, Self::NoValue), |attr_val| match self {
-            Self::Is(this_val) => *this_val == *attr_val,
-            Self::Contains(this_val) => attr_val.split_whitespace().any(|word| word == this_val),
-            Self::NoValue => false,
-        })
+        match (self, attribute_value) {
+            (Self::Keyword(keywords), _) => {
+                let attr_val = attribute_value.unwrap_or_default();
+                keywords.iter().any(|keyword| keyword.eq_ignore_ascii_case(attr_val))
+            }
+            (Self::NoValue, None) | (Self::Present, _) => true,
+            (
+                Self::Contains(_)
+                | Self::HashedWord(_)
+                | Self::Is(_)
+                | Self::Prefix(_)
+                | Self::Substring(_)
+                | Self::Suffix(_)
+                | Self::WordPrefix(_),
+                None,
+            )
+            | (Self::NoValue, Some(_)) => false,
+            #[cfg(feature = "regex")]
+            (Self::Regex(_), None) => false,
+            #[cfg(feature = "regex")]
+            (Self::Regex(pattern), Some(attr_val)) => pattern.0.is_match(attr_val),
+            (Self::Contains(this_val), Some(attr_val)) =>
+                attr_val.split_whitespace().any(|word| word == this_val),
+            (Self::HashedWord(this_val), Some(attr_val)) =>
+                attr_val.split_whitespace().any(|word| is_hashed_word(word, this_val)),
+            (Self::Is(this_val), Some(attr_val)) => *this_val == *attr_val,
+            (Self::Prefix(this_val), Some(attr_val)) => attr_val.starts_with(this_val.as_str()),
+            (Self::Substring(this_val), Some(attr_val)) => attr_val.contains(this_val.as_str()),
+            (Self::Suffix(this_val), Some(attr_val)) => attr_val.ends_with(this_val.as_str()),
+            (Self::WordPrefix(this_val), Some(attr_val)) =>
+                attr_val.split_whitespace().any(|word| word.starts_with(this_val.as_str())),
+        }
+    }
+}
+
+/// Compiled regular expression of an [`AttributeMatch::Regex`].
+///
+/// Two patterns are equal if they were compiled from the same string.
+#[cfg(feature = "regex")]
+#[derive(Debug)]
+pub struct Pattern(pub Regex);
+
+#[cfg(feature = "regex")]
+impl Eq for Pattern {}
+
+#[cfg(feature = "regex")]
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
     }
 }
 
@@ -172,6 +290,8 @@ impl AttributeMatch {
 pub struct ValueAssociateHash {
     /// Names and attributes explicitly not wanted
     blacklist: Vec<(String, AttributeMatch)>,
+    /// Maximum number of attributes of the wanted tags
+    max_count: Option<usize>,
     /// Names and attributes explicitly wanted
     whitelist: Vec<(String, AttributeMatch)>,
 }
@@ -179,10 +299,12 @@ pub struct ValueAssociateHash {
 impl ValueAssociateHash {
     /// Checks if the attributes form a correct combination of rules
     pub fn check(&self, attrs: &[Attribute]) -> ElementState {
-        let attrs_map: HashMap<_, _> =
-            attrs.iter().map(|attr| (attr.as_name().clone(), attr.as_value())).collect();
+        if self.max_count.is_some_and(|max_count| attrs.len() > max_count) {
+            return ElementState::BlackListed;
+        }
+        let attrs_map = attributes_map(attrs);
         for (wanted_name, wanted_value) in &self.whitelist {
-            match attrs_map.get(wanted_name) {
+            match attrs_map.get(wanted_name.as_str()) {
                 None => return ElementState::BlackListed,
                 Some(found_value) if !wanted_value.matches(found_value.map(String::as_str)) =>
                     return ElementState::BlackListed,
@@ -190,7 +312,7 @@ impl ValueAssociateHash {
             }
         }
         for (wanted_name, wanted_value) in &self.blacklist {
-            match attrs_map.get(wanted_name) {
+            match attrs_map.get(wanted_name.as_str()) {
                 Some(found_value) if wanted_value.matches(found_value.map(String::as_str)) =>
                     return ElementState::BlackListed,
                 Some(_) | None => (),
@@ -199,28 +321,56 @@ impl ValueAssociateHash {
         if self.is_empty() { ElementState::NotSpecified } else { ElementState::WhiteListed }
     }
 
+    /// Describes the rules that the attributes don't satisfy.
+    pub fn failures(&self, attrs: &[Attribute]) -> Vec<String> {
+        let mut failures = vec![];
+        if let Some(max_count) = self.max_count
+            && attrs.len() > max_count
+        {
+            failures.push(format!("the tag has {} attributes, more than {max_count}", attrs.len()));
+        }
+        let attrs_map = attributes_map(attrs);
+        for (wanted_name, wanted_value) in &self.whitelist {
+            match attrs_map.get(wanted_name.as_str()) {
+                None => failures.push(format!("the attribute '{wanted_name}' is missing")),
+                Some(found_value) if !wanted_value.matches(found_value.map(String::as_str)) =>
+                    failures.push(format!("the attribute '{wanted_name}' has a wrong value")),
+                Some(_) => (),
+            }
+        }
+        for (wanted_name, wanted_value) in &self.blacklist {
+            if let Some(found_value) = attrs_map.get(wanted_name.as_str())
+                && wanted_value.matches(found_value.map(String::as_str))
+            {
+                failures.push(format!("the attribute '{wanted_name}' is unwanted"));
+            }
+        }
+        failures
+    }
+
     /// Checks if the [`ValueAssociateHash`] wasn't given any rules.
     pub const fn is_empty(&self) -> bool {
-        self.whitelist.is_empty() && self.blacklist.is_empty()
+        self.whitelist.is_empty() && self.blacklist.is_empty() && self.max_count.is_none()
     }
 
     /// Checks if one of the attributes was explicitly blacklisted
     pub fn is_explicitly_blacklisted(&self, attrs: &[Attribute]) -> bool {
-        let blacklist =
-            self.blacklist.iter().map(|(name, value)| (name, value)).collect::<HashMap<_, _>>();
-        for attr in attrs {
-            if let Some(value) = blacklist.get(&attr.as_name().clone())
-                && value.matches(attr.as_value().map(String::as_str))
-            {
-                return true;
-            }
-        }
-        false
+        self.blacklist.iter().any(|(name, value)| {
+            attrs.iter().any(|attr| {
+                is_named(attr, name, attrs) && value.matches(attr.as_value().map(String::as_str))
+            })
+        })
+    }
+
+    /// Returns the number of rules.
+    pub const fn len(&self) -> usize {
+        let max_count = if self.max_count.is_some() { 1 } else { 0 };
+        self.whitelist.len().saturating_add(self.blacklist.len()).saturating_add(max_count)
     }
 
     /// Returns a default [`Self`].
     pub const fn new() -> Self {
-        Self { blacklist: vec![], whitelist: vec![] }
+        Self { blacklist: vec![], max_count: None, whitelist: vec![] }
     }
 
     /// Adds a rule for the attribute `name`
@@ -231,4 +381,83 @@ impl ValueAssociateHash {
             self.blacklist.push((name, value));
         };
     }
+
+    /// Describes every rule on the attributes, with whether the attributes
+    /// satisfy it.
+    pub fn rule_results(&self, attrs: &[Attribute]) -> Vec<(String, bool)> {
+        let mut results = vec![];
+        if let Some(max_count) = self.max_count {
+            results.push((format!("at most {max_count} attributes"), attrs.len() <= max_count));
+        }
+        let attrs_map = attributes_map(attrs);
+        let matches = |name: &String, value: &AttributeMatch| {
+            attrs_map
+                .get(name.as_str())
+                .is_some_and(|found_value| value.matches(found_value.map(String::as_str)))
+        };
+        for (name, value) in &self.whitelist {
+            let description = format!("attribute '{name}' {}", value.description());
+            results.push((description, matches(name, value)));
+        }
+        for (name, value) in &self.blacklist {
+            let description = format!("no attribute '{name}' that {}", value.description());
+            results.push((description, !matches(name, value)));
+        }
+        results
+    }
+
+    /// Sets the maximum number of attributes of the wanted tags
+    ///
+    /// If a maximum was already set, the smallest one is kept.
+    pub fn set_max_count(&mut self, max_count: usize) {
+        self.max_count = Some(self.max_count.map_or(max_count, |old| old.min(max_count)));
+    }
+}
+
+/// Maps the names of the attributes to their values.
+///
+/// The deprecated namespaced attributes, like `xlink:href`, are also mapped
+/// with the names that replace them, like `href`, unless the tag also has the
+/// new attribute, as it takes precedence.
+fn attributes_map(attrs: &[Attribute]) -> HashMap<&str, Option<&String>> {
+    let mut map: HashMap<_, _> =
+        attrs.iter().map(|attr| (attr.as_name(), attr.as_value())).collect();
+    for attr in attrs {
+        map.entry(attr.as_modern_name()).or_insert_with(|| attr.as_value());
+    }
+    map
+}
+
+/// Checks if a word is the given name, optionally followed by a generated hash
+/// suffix.
+///
+/// Tools like CSS modules or CSS-in-JS libraries append a hash to the class
+/// names, e.g. `product-card__x1y2z` for `product-card`. A suffix is considered
+/// generated if
+/// - it is separated from the name by `_`, `__`, `-` or `--`;
+/// - it has at least 4 ASCII alphanumeric characters, and nothing else;
+/// - it contains a digit, or both lowercase and uppercase letters.
+///
+/// This means that `product-card-title` isn't matched by `product-card`.
+fn is_hashed_word(word: &str, name: &str) -> bool {
+    let Some(rest) = word.strip_prefix(name) else { return false };
+    if rest.is_empty() {
+        return true;
+    }
+    let Some(suffix) = ["__", "--", "_", "-"].iter().find_map(|sep| rest.strip_prefix(sep)) else {
+        return false;
+    };
+    suffix.len() >= 4
+        && suffix.chars().all(|ch| ch.is_ascii_alphanumeric())
+        && (suffix.chars().any(|ch| ch.is_ascii_digit())
+            || (suffix.chars().any(|ch| ch.is_ascii_lowercase())
+                && suffix.chars().any(|ch| ch.is_ascii_uppercase())))
+}
+
+/// Checks if an attribute of a tag has the given name, or is the deprecated
+/// namespaced attribute replaced by the attribute `name` if the tag doesn't
+/// have the new attribute.
+fn is_named(attr: &Attribute, name: &str, attrs: &[Attribute]) -> bool {
+    attr.as_name() == name
+        || attr.as_modern_name() == name && attrs.iter().all(|other| other.as_name() != name)
 }
@@ -0,0 +1,184 @@
+//! Module to find all the nodes that match a filter, as a flat list.
+
+use crate::{Filter, Html};
+
+impl Html {
+    /// Checks if a node of the tree matches the filter.
+    ///
+    /// The nodes are matched like with [`Html::find_all`], but the traversal
+    /// stops at the first match, and nothing is cloned or collected. This is
+    /// the cheapest way to check the existence of a node in a large document.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(r#"<form><input type="password" /></form>"#).unwrap();
+    /// assert!(html.any(&Filter::new().attribute_value("type", "password")));
+    /// assert!(!html.any(&Filter::new().tag_name("iframe")));
+    /// ```
+    #[must_use]
+    pub fn any(&self, filter: &Filter) -> bool {
+        !self.visit_matches(filter, &[], &mut |_| false)
+    }
+
+    /// Counts the nodes of the tree that match the filter.
+    ///
+    /// The nodes are matched like with [`Html::find_all`], so the nodes
+    /// nested in another match are also counted, but nothing is cloned or
+    /// collected. This is useful for analytics on large documents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<ul><li>One</li><li>Two<ul><li>Three</li></ul></li></ul>").unwrap();
+    /// assert_eq!(html.count(&Filter::new().tag_name("li")), 3);
+    /// assert_eq!(html.count(&Filter::new().tag_name("li").text_contains("Two")), 1);
+    /// assert_eq!(html.count(&Filter::new().no_tags().none_except_text()), 3);
+    /// ```
+    #[must_use]
+    pub fn count(&self, filter: &Filter) -> usize {
+        let mut count = 0usize;
+        self.visit_matches(filter, &[], &mut |_| {
+            count = count.saturating_add(1);
+            true
+        });
+        count
+    }
+
+    /// Returns references to all the nodes that match the filter, as a flat
+    /// list in the order of the document.
+    ///
+    /// Contrary to [`Html::to_filtered`], the matches aren't nested in a
+    /// pruned tree: every matching node is in the list, including the nodes
+    /// nested in another match, like a `<div>` in a `<div>`. The nodes are
+    /// returned as they are in the tree, without filtering their content.
+    ///
+    /// The texts, the comments and the doctypes are only returned when they
+    /// are explicitly allowed by the filter, for instance with
+    /// `Filter::new().no_tags().none_except_text()`.
+    ///
+    /// See [`Html::select`] to also navigate from the matched tags to their
+    /// ancestors, and [`Html::into_find_all`] to get owned nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     r#"<ul><li><a href="/home">Home</a></li><li><a href="/about">About</a></li></ul>"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// let links = html.find_all(&Filter::new().tag_name("a"));
+    /// assert_eq!(links.len(), 2);
+    /// assert_eq!(links[1], &"<a href=\"/about\">About</a>");
+    ///
+    /// let nested = Html::parse("<div><div>Inner</div></div>").unwrap();
+    /// let divs = nested.find_all(&Filter::new().tag_name("div"));
+    /// assert_eq!(divs, [&"<div><div>Inner</div></div>", &"<div>Inner</div>"]);
+    /// ```
+    #[must_use]
+    pub fn find_all(&self, filter: &Filter) -> Vec<&Self> {
+        self.find_all_in(filter, &[])
+    }
+
+    /// Wrapper for [`Html::find_all`]
+    ///
+    /// `scope` is the scope of the node, see [`Filter::inside`].
+    fn find_all_in(&self, filter: &Filter, scope: &[bool]) -> Vec<&Self> {
+        let mut found = vec![];
+        self.visit_matches(filter, scope, &mut |node| {
+            found.push(node);
+            true
+        });
+        found
+    }
+
+    /// Returns all the nodes that match the filter, as a flat list in the
+    /// order of the document.
+    ///
+    /// Equivalent of [`Html::find_all`] when the data is owned. The nodes
+    /// nested in another match are cloned, the other ones are moved out of
+    /// the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<p>First</p><div><p>Second</p></div>").unwrap();
+    /// let paragraphs: Vec<Html> = html.into_find_all(&Filter::new().tag_name("p"));
+    /// assert_eq!(paragraphs, ["<p>First</p>", "<p>Second</p>"]);
+    /// ```
+    #[must_use]
+    pub fn into_find_all(self, filter: &Filter) -> Vec<Self> {
+        let mut found = vec![];
+        self.into_find_all_aux(filter, &[], &mut found);
+        found
+    }
+
+    /// Wrapper for [`Html::into_find_all`]
+    ///
+    /// `scope` is the scope of the node, see [`Filter::inside`].
+    fn into_find_all_aux(self, filter: &Filter, scope: &[bool], found: &mut Vec<Self>) {
+        let child_scope = filter.enter_scope(&self, scope);
+        let inner_scope = child_scope.as_deref().unwrap_or(scope);
+        match self {
+            Self::Tag { .. } if filter.node_matches(&self, scope) => {
+                let nested = self.as_tag().map_or_else(Vec::new, |(_, child)| {
+                    child.find_all_in(filter, inner_scope).into_iter().cloned().collect::<Vec<_>>()
+                });
+                found.push(self);
+                found.extend(nested);
+            }
+            Self::Tag { child, .. } => child.into_find_all_aux(filter, inner_scope, found),
+            Self::Vec(vec) => {
+                let scopes = filter.sibling_scopes(&vec, scope);
+                for (idx, node) in vec.into_iter().enumerate() {
+                    node.into_find_all_aux(filter, scopes.get(idx, scope), found);
+                }
+            }
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty | Self::Text(_) =>
+                if filter.node_matches(&self, scope) {
+                    found.push(self);
+                },
+        }
+    }
+
+    /// Calls `visit` on the nodes of the tree that match the filter, in the
+    /// order of the document, while it returns `true`.
+    ///
+    /// `scope` is the scope of the node, see [`Filter::inside`].
+    ///
+    /// # Returns
+    ///
+    /// `false` iff `visit` returned `false`, and stopped the traversal.
+    fn visit_matches<'html, F: FnMut(&'html Self) -> bool>(
+        &'html self,
+        filter: &Filter,
+        scope: &[bool],
+        visit: &mut F,
+    ) -> bool {
+        if filter.node_matches(self, scope) && !visit(self) {
+            return false;
+        }
+        match self {
+            Self::Tag { child, .. } => {
+                let child_scope = filter.enter_scope(self, scope);
+                child.visit_matches(filter, child_scope.as_deref().unwrap_or(scope), visit)
+            }
+            Self::Vec(vec) => {
+                let scopes = filter.sibling_scopes(vec, scope);
+                vec.iter()
+                    .enumerate()
+                    .all(|(idx, node)| node.visit_matches(filter, scopes.get(idx, scope), visit))
+            }
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty | Self::Text(_) => true,
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Module to match the tags whose structure is similar to an example subtree.
+//!
+//! The similarity is measured with a top-down tree edit distance: the roots
+//! are always aligned, a renamed node costs 1, and an inserted or deleted
+//! subtree costs its number of nodes. The children of two aligned nodes are
+//! aligned with a classic sequence edit distance.
+
+use core::cmp::min;
+
+use super::element::ElementState;
+use crate::{Html, Tag};
+
+/// Name of the leaves that represent the non-empty texts in a [`Shape`].
+///
+/// This can't be confused with a tag name, as tag names can't contain `#`.
+const TEXT_SHAPE: &str = "#text";
+
+/// Rule to keep the tags that look like an example.
+///
+/// See [`Filter::like`](crate::Filter::like).
+#[derive(Debug, PartialEq, Eq)]
+pub struct Like {
+    /// Shape of the first tag of the example, if there is one
+    shape: Option<Shape>,
+    /// Maximum distance between the shape of a tag and the example
+    tolerance: usize,
+}
+
+impl Like {
+    /// Checks if a tag has a structure similar to the example.
+    pub fn check(&self, tag: &Tag, child: &Html) -> ElementState {
+        match &self.shape {
+            Some(shape)
+                if shape.distance(&Shape::from_tag(tag, child), self.tolerance)
+                    <= self.tolerance =>
+                ElementState::WhiteListed,
+            Some(_) | None => ElementState::BlackListed,
+        }
+    }
+
+    /// Creates a rule from an example and a tolerance.
+    pub fn new(example: &Html, tolerance: usize) -> Self {
+        Self { shape: Shape::from_first_tag(example), tolerance }
+    }
+}
+
+/// Structure of a subtree: the names of the tags, and the places of the texts.
+#[derive(Debug, PartialEq, Eq)]
+struct Shape {
+    /// Shapes of the children
+    children: Vec<Self>,
+    /// Name of the tag, or [`TEXT_SHAPE`] for texts
+    name: String,
+    /// Number of nodes in the subtree, including this one
+    size: usize,
+}
+
+impl Shape {
+    /// Computes the edit distance between two shapes.
+    ///
+    /// The computation is pruned as soon as the distance is known to exceed
+    /// the bound: the returned value is then any value greater than the
+    /// bound.
+    fn distance(&self, other: &Self, bound: usize) -> usize {
+        let exceeded = bound.saturating_add(1);
+        if self.size.abs_diff(other.size) > bound {
+            return exceeded;
+        }
+        let rename = usize::from(self.name != other.name);
+        let mut previous: Vec<usize> = vec![0];
+        for child in &other.children {
+            previous.push(previous.last().copied().unwrap_or_default().saturating_add(child.size));
+        }
+        for child in &self.children {
+            let mut current =
+                vec![previous.first().copied().unwrap_or_default().saturating_add(child.size)];
+            for ((other_child, diagonal), above) in
+                other.children.iter().zip(&previous).zip(previous.iter().skip(1))
+            {
+                let left = current.last().copied().unwrap_or_default();
+                let cost = min(
+                    min(above.saturating_add(child.size), left.saturating_add(other_child.size)),
+                    diagonal.saturating_add(child.distance(other_child, bound)),
+                );
+                current.push(min(cost, exceeded));
+            }
+            previous = current;
+        }
+        min(rename.saturating_add(previous.last().copied().unwrap_or_default()), exceeded)
+    }
+
+    /// Computes the shape of the first tag found in a tree.
+    fn from_first_tag(html: &Html) -> Option<Self> {
+        match html {
+            Html::Tag { tag, child } => Some(Self::from_tag(tag, child)),
+            Html::Vec(vec) => vec.iter().find_map(Self::from_first_tag),
+            Html::Comment(_) | Html::Doctype { .. } | Html::Empty | Html::Text(_) => None,
+        }
+    }
+
+    /// Computes the shape of a tag.
+    fn from_tag(tag: &Tag, child: &Html) -> Self {
+        let mut children = vec![];
+        push_shapes(child, &mut children);
+        let size = children.iter().fold(1, |size: usize, shape| size.saturating_add(shape.size));
+        Self { children, name: tag.as_name().to_owned(), size }
+    }
+}
+
+/// Pushes the shapes of the nodes of a tree, after unwrapping the vecs.
+///
+/// The comments, doctypes and whitespace texts aren't part of the structure.
+fn push_shapes(html: &Html, shapes: &mut Vec<Shape>) {
+    match html {
+        Html::Tag { tag, child } => shapes.push(Shape::from_tag(tag, child)),
+        Html::Text(text) if !text.trim().is_empty() =>
+            shapes.push(Shape { children: vec![], name: TEXT_SHAPE.to_owned(), size: 1 }),
+        Html::Vec(vec) =>
+            for child in vec {
+                push_shapes(child, shapes);
+            },
+        Html::Comment(_) | Html::Doctype { .. } | Html::Empty | Html::Text(_) => (),
+    }
+}
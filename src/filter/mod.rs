@@ -1,26 +1,54 @@
 //! Module to filter an HTML tree to keep or remove specific nodes, with a set
 //! of rules.
 //!
-//! You can either filter your HTML with [`Html::filter`] or find a specific
-//! node with [`Html::find`].
+//! You can either filter your HTML with [`Html::filter`], find a specific
+//! node with [`Html::find`] or find all the matching nodes with
+//! [`Html::find_all`].
 //!
 //! For more information on how to define the filtering rules, please refer to
 //! [`Filter`].
+//!
+//! The filtering is linear in the number of nodes of the tree. With a
+//! [`Filter::depth`], the distances from the nodes to the wanted tags are
+//! measured bottom-up in a first pass, see [`Distances`], so that the walk
+//! never needs to look ahead into the subtrees.
 
-extern crate alloc;
 mod api;
+mod between;
+mod combine;
+pub mod diff;
+mod distance;
 mod element;
-mod node_type;
+mod find_all;
+mod like;
+pub mod node_ref;
+pub mod node_type;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod position;
+mod predicate;
+mod preview;
+pub mod report;
+mod scope;
+pub mod scored;
+#[cfg(any(feature = "ffi", feature = "pyo3"))]
+pub mod selector;
+mod split;
+pub mod stats;
+mod text;
 pub mod types;
 
 use alloc::borrow::Cow;
+use core::cell::Cell;
 use core::cmp::Ordering;
 use core::mem::take;
 
+use distance::Distances;
 use node_type::NodeTypeFilter;
 use types::Filter;
 
-use crate::errors::{safe_expect, safe_unreachable};
+use crate::budget::{Budget, BudgetCounter, BudgetExceeded};
+use crate::errors::safe_expect;
 use crate::{Html, Tag};
 
 /// State to follow if the wanted nodes where found at what depth
@@ -95,36 +123,6 @@ impl FilterSuccess {
 }
 
 impl Html {
-    /// Method to check if a wanted node is visible
-    ///
-    /// This methods stop checking after a maximum depth, as the current node
-    /// will be discarded if it is deeper in the tree.
-    fn check_depth(&self, max_depth: usize, filter: &Filter) -> Option<usize> {
-        match self {
-            Self::Empty | Self::Text(_) | Self::Comment { .. } | Self::Doctype { .. } => None,
-            Self::Tag { tag, .. } if filter.tag_explicitly_allowed(tag) => Some(0),
-            Self::Tag { .. } | Self::Vec(_) if max_depth == 0 => None,
-            Self::Tag { child, .. } => child
-                .check_depth(
-                    #[expect(clippy::arithmetic_side_effects, reason = "non-0")]
-                    {
-                        max_depth - 1
-                    },
-                    filter,
-                )
-                .map(
-                    #[expect(clippy::arithmetic_side_effects, reason = "< initial max_depth")]
-                    |depth| depth + 1,
-                ),
-            Self::Vec(vec) => vec
-                .iter()
-                .try_fold(Some(usize::MAX), |acc, child| {
-                    if acc == Some(0) { Err(()) } else { Ok(child.check_depth(max_depth, filter)) }
-                })
-                .unwrap_or(Some(0)),
-        }
-    }
-
     /// Filters html based on a defined filter.
     ///
     /// See [`Filter`] to learn how to create filters.
@@ -136,9 +134,16 @@ impl Html {
     ///
     /// The html tree obtains by keeping only the nodes that fulfil the
     /// filter.
+    ///
+    /// # Complexity
+    ///
+    /// The filtering takes a time linear in the number of nodes: each node is
+    /// visited at most once, and once more beforehand if the filter has a
+    /// [`Filter::depth`], to measure how far the wanted tags are. These visits
+    /// are the nodes counted by [`Budget::max_nodes`].
     #[must_use]
     pub fn filter(self, filter: &Filter) -> Self {
-        filter_aux(Cow::Owned(self), filter, false).html
+        Walker::new(filter, None, None).run(Cow::Owned(self))
     }
 
     /// Finds an html node based on a defined filter.
@@ -148,27 +153,16 @@ impl Html {
     /// Filters allow you to select the portions of the html code you want to
     /// keep or remove.
     ///
+    /// The search stops as soon as the first node is found: the rest of the
+    /// tree is not filtered. This is equivalent to [`Html::filter`] with a
+    /// [`Filter::limit`] of `1`.
+    ///
     /// # Returns
     ///
     /// The first node that fulfils the filter.
     #[must_use]
     pub fn find(self, filter: &Filter) -> Self {
-        self.filter(filter).into_first()
-    }
-
-    /// Keeps only the first element of a filtered output
-    fn into_first(self) -> Self {
-        if let Self::Vec(vec) = self {
-            for elt in vec {
-                let res = elt.into_first();
-                if !res.is_empty() {
-                    return res;
-                }
-            }
-            safe_unreachable!("Filtering removes empty nodes in vec.")
-        } else {
-            self
-        }
+        Walker::new(filter, Some(1), None).run(Cow::Owned(self))
     }
 
     /// Filters html based on a defined filter.
@@ -176,16 +170,216 @@ impl Html {
     /// Equivalent of [`Html::filter`] when data is not owned.
     #[must_use]
     pub fn to_filtered(&self, filter: &Filter) -> Self {
-        filter_aux(Cow::Borrowed(self), filter, false).html
+        Walker::new(filter, None, None).run(Cow::Borrowed(self))
     }
 
     /// Finds an html node based on a defined filter.
     ///
     /// Equivalent of [`Html::find`] when data is not owned.
-    // TODO: data except first is cloned
     #[must_use]
     pub fn to_found(&self, filter: &Filter) -> Self {
-        self.to_filtered(filter).into_first()
+        Walker::new(filter, Some(1), None).run(Cow::Borrowed(self))
+    }
+
+    /// Filters html based on a defined filter, within a budget.
+    ///
+    /// Equivalent of [`Html::filter`], but the filtering is aborted if the
+    /// [`Budget`] is exceeded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the budget was exceeded before the end of the
+    /// filtering.
+    pub fn try_filter(self, filter: &Filter, budget: &Budget<'_>) -> Result<Self, BudgetExceeded> {
+        Walker::run_with_budget(Cow::Owned(self), filter, None, budget)
+    }
+
+    /// Finds an html node based on a defined filter, within a budget.
+    ///
+    /// Equivalent of [`Html::find`], but the search is aborted if the
+    /// [`Budget`] is exceeded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the budget was exceeded before the node was found.
+    pub fn try_find(self, filter: &Filter, budget: &Budget<'_>) -> Result<Self, BudgetExceeded> {
+        Walker::run_with_budget(Cow::Owned(self), filter, Some(1), budget)
+    }
+
+    /// Filters html based on a defined filter, within a budget.
+    ///
+    /// Equivalent of [`Html::try_filter`] when data is not owned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the budget was exceeded before the end of the
+    /// filtering.
+    pub fn try_to_filtered(
+        &self,
+        filter: &Filter,
+        budget: &Budget<'_>,
+    ) -> Result<Self, BudgetExceeded> {
+        Walker::run_with_budget(Cow::Borrowed(self), filter, None, budget)
+    }
+
+    /// Finds an html node based on a defined filter, within a budget.
+    ///
+    /// Equivalent of [`Html::try_find`] when data is not owned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the budget was exceeded before the node was found.
+    pub fn try_to_found(
+        &self,
+        filter: &Filter,
+        budget: &Budget<'_>,
+    ) -> Result<Self, BudgetExceeded> {
+        Walker::run_with_budget(Cow::Borrowed(self), filter, Some(1), budget)
+    }
+}
+
+/// Context of a walk through the tree to filter it
+struct Walker<'walk> {
+    /// Consumption of the budget, if a budget was given
+    budget: Option<&'walk BudgetCounter<'walk>>,
+    /// Number of results already in the output, if the output is limited
+    ///
+    /// The siblings that follow the last result are not filtered once the
+    /// limit is reached, see [`Walker::limit_reached`].
+    committed: Option<&'walk Cell<usize>>,
+    /// Distances from the nodes to the wanted tags, measured before the walk
+    /// when the filter has a depth, see [`Distances`]
+    distances: Option<&'walk Distances>,
+    /// Filter to apply
+    filter: &'walk Filter,
+    /// Maximum number of results: the limit requested by the caller before
+    /// the walk, and the limit of the output during the walk, see
+    /// [`Filter::output_limit`]
+    limit: Option<usize>,
+    /// Whether to filter the children of the first [`Html::Vec`] in
+    /// parallel, see [`Html::par_filter`]
+    #[cfg(feature = "rayon")]
+    parallel: bool,
+    /// Whether the walk is inside a tag whose whitespace is kept, like a
+    /// `<pre>`, where the texts aren't trimmed, see [`Walker::enter`]
+    preformatted: bool,
+}
+
+impl<'walk> Walker<'walk> {
+    /// Records the results of the output that were just created.
+    ///
+    /// Only the results that can't be altered by the filtering of their
+    /// ancestors are recorded, see [`count_results`].
+    fn commit(&self, html: &Html) {
+        if let Some(committed) = self.committed {
+            committed.set(committed.get().saturating_add(count_results(html)));
+        }
+    }
+
+    /// Returns the distance from a node to the closest wanted tag in its
+    /// subtree, if the distances were measured, see [`Distances::get`].
+    fn distance(&self, index: usize) -> Option<usize> {
+        self.distances?.get(index)
+    }
+
+    /// Returns the walker of the content of a tag.
+    ///
+    /// The walker is inside a tag whose whitespace is kept once it entered
+    /// one, see [`Tag::is_preformatted`].
+    fn enter(&self, tag: &Tag) -> Self {
+        Self { preformatted: self.preformatted || tag.is_preformatted(), ..*self }
+    }
+
+    /// Checks if the output already has as many results as the limit.
+    fn limit_reached(&self) -> bool {
+        self.committed.zip(self.limit).is_some_and(|(committed, limit)| committed.get() >= limit)
+    }
+
+    /// Creates a new [`Walker`]
+    ///
+    /// `limit` is the maximum number of results requested by the caller, like
+    /// `1` for [`Html::find`].
+    const fn new(
+        filter: &'walk Filter,
+        limit: Option<usize>,
+        budget: Option<&'walk BudgetCounter<'walk>>,
+    ) -> Self {
+        Self {
+            budget,
+            committed: None,
+            distances: None,
+            filter,
+            limit,
+            #[cfg(feature = "rayon")]
+            parallel: false,
+            preformatted: false,
+        }
+    }
+
+    /// Returns the index of the next sibling of a node, see
+    /// [`Distances::next_sibling`].
+    fn next_sibling(&self, index: usize) -> usize {
+        self.distances.map_or(index, |distances| distances.next_sibling(index))
+    }
+
+    /// Filters the html
+    ///
+    /// If the output is limited, see [`Filter::output_limit`], the walk stops
+    /// as soon as the limit is reached, and only the first results are
+    /// returned.
+    ///
+    /// If the filter has a depth, the distances from the nodes to the wanted
+    /// tags are measured first, so that the tree is only walked twice.
+    fn run(&self, cow_html: Cow<'_, Html>) -> Html {
+        let distances = (self.filter.as_depth() > 0).then(|| Distances::measure(&cow_html, self));
+        let limit = self.filter.output_limit(self.limit);
+        let committed = Cell::new(0);
+        let walker = Walker {
+            committed: limit.map(|_| &committed),
+            distances: distances.as_ref(),
+            limit,
+            ..*self
+        };
+        let html = filter_aux(cow_html, &walker, 0, &[]).html;
+        self.filter.limit_output(html, limit)
+    }
+
+    /// Filters the html, and aborts if the budget is exceeded.
+    fn run_with_budget(
+        cow_html: Cow<'_, Html>,
+        filter: &Filter,
+        limit: Option<usize>,
+        budget: &Budget<'_>,
+    ) -> Result<Html, BudgetExceeded> {
+        let counter = BudgetCounter::new(budget);
+        let html = Walker::new(filter, limit, Some(&counter)).run(cow_html);
+        counter.exceeded().map_or(Ok(html), Err)
+    }
+
+    /// Checks if the texts must be trimmed, see [`Filter::trim_text`].
+    ///
+    /// The texts inside a tag whose whitespace is kept are never trimmed.
+    const fn trims(&self) -> bool {
+        self.filter.should_trim() && !self.preformatted
+    }
+
+    /// Records the visit of a node
+    ///
+    /// # Returns
+    ///
+    /// `true` iff the budget isn't exceeded.
+    fn visit(&self) -> bool {
+        self.budget.is_none_or(BudgetCounter::visit)
+    }
+}
+
+/// Counts the results of a filtered tree, i.e., the nodes that remain after
+/// unwrapping the vecs.
+fn count_results(html: &Html) -> usize {
+    match html {
+        Html::Vec(vec) => vec.iter().map(count_results).fold(0, usize::saturating_add),
+        Html::Empty => 0,
+        Html::Comment(_) | Html::Doctype { .. } | Html::Tag { .. } | Html::Text(_) => 1,
     }
 }
 
@@ -200,53 +394,82 @@ impl Html {
 /// This methods returns a wrapper of the final html in a [`FilterSuccess`]
 /// to follow the current depth of the last found node. See
 /// [`FilterSuccess`] for more information.
+///
+/// `index` is the index of the node in the [`Distances`] of the walker, and
+/// `scope` is the scope of the node, see [`Filter::inside`]: the nodes out of
+/// the scope are only kept in the matched tags.
 #[allow(clippy::allow_attributes, reason = "expect is buggy")]
 #[allow(clippy::enum_glob_use, reason = "heavy syntax and Html is the main struct")]
-fn filter_aux(cow_html: Cow<'_, Html>, filter: &Filter, found: bool) -> FilterSuccess {
+fn filter_aux(
+    cow_html: Cow<'_, Html>,
+    walker: &Walker<'_>,
+    index: usize,
+    scope: &[bool],
+) -> FilterSuccess {
     use Html::*;
+    if !walker.visit() {
+        return FilterSuccess::default();
+    }
+    let filter = walker.filter;
+    let allowed = filter.tag_allowed_in(&cow_html, scope);
+    let in_scope = filter.in_scope(scope);
+    let child_scope = filter.enter_scope(&cow_html, scope);
+    let rec = (index.saturating_add(1), child_scope.as_deref().unwrap_or(scope));
     match cow_html {
         Cow::Borrowed(Comment(_)) | Cow::Owned(Comment(_))
-            if !filter.comment_explicitly_allowed() =>
+            if !filter.comment_explicitly_allowed() || !in_scope =>
             None,
-        Cow::Borrowed(Doctype { .. }) | Cow::Owned(Doctype { .. }) if !filter.doctype_allowed() =>
+        Cow::Borrowed(Doctype { .. }) | Cow::Owned(Doctype { .. })
+            if !filter.doctype_allowed() || !in_scope =>
             None,
         Cow::Borrowed(Doctype { .. } | Comment(_)) | Cow::Owned(Doctype { .. } | Comment(_)) =>
             FilterSuccess::make_none(cow_html),
-        Cow::Borrowed(Text(text)) if filter.text_explicitly_allowed() && filter.should_trim() =>
-            FilterSuccess::make_none(Cow::Owned(Html::trim_text(text))),
-        Cow::Owned(Text(text)) if filter.text_explicitly_allowed() && filter.should_trim() =>
-            FilterSuccess::make_none(Cow::Owned(Html::trim_text(&text))),
-        Cow::Borrowed(Text(_)) | Cow::Owned(Text(_)) if filter.text_explicitly_allowed() =>
+        Cow::Borrowed(Text(text))
+            if filter.text_explicitly_allowed() && in_scope && walker.trims() =>
+            FilterSuccess::make_none(Cow::Owned(Html::trim_text(text, filter.as_trim()))),
+        Cow::Owned(Text(text))
+            if filter.text_explicitly_allowed() && in_scope && walker.trims() =>
+            FilterSuccess::make_none(Cow::Owned(Html::trim_text(&text, filter.as_trim()))),
+        Cow::Borrowed(Text(_)) | Cow::Owned(Text(_))
+            if filter.text_explicitly_allowed() && in_scope =>
             FilterSuccess::make_none(cow_html),
         Cow::Borrowed(Text(_) | Empty) | Cow::Owned(Text(_) | Empty) => None,
         // incorrect
         Cow::Borrowed(Tag { tag, child }) =>
-            filter_aux_tag(Cow::Borrowed(&**child), Cow::Borrowed(tag), filter, found),
+            filter_aux_tag(Cow::Borrowed(&**child), Cow::Borrowed(tag), walker, rec, allowed),
         Cow::Owned(Tag { tag, child }) =>
-            filter_aux_tag(Cow::Owned(*child), Cow::Owned(tag), filter, found),
-        Cow::Borrowed(Vec(vec)) => filter_aux_vec(Cow::Borrowed(vec), filter),
-        Cow::Owned(Vec(vec)) => filter_aux_vec(Cow::Owned(vec), filter),
+            filter_aux_tag(Cow::Owned(*child), Cow::Owned(tag), walker, rec, allowed),
+        Cow::Borrowed(Vec(vec)) => filter_aux_vec(Cow::Borrowed(vec), walker, (index, scope)),
+        Cow::Owned(Vec(vec)) => filter_aux_vec(Cow::Owned(vec), walker, (index, scope)),
     }
     .unwrap_or_default()
 }
 
 /// Auxiliary method for [`filter_aux`] on [`Html::Tag`]
+///
+/// The tuple contains the index and the scope of the child of the tag, see
+/// [`filter_aux`].
 #[expect(clippy::arithmetic_side_effects, reason = "incr depth when smaller than filter_depth")]
 fn filter_aux_tag(
     child: Cow<'_, Html>,
     tag: Cow<'_, Tag>,
-    filter: &Filter,
-    found: bool,
+    walker: &Walker<'_>,
+    (index, scope): (usize, &[bool]),
+    allowed: bool,
 ) -> Option<FilterSuccess> {
-    if filter.tag_allowed(tag.as_ref()) {
-        FilterSuccess::make_found(Html::Tag {
-            tag: tag.into_owned(),
-            child: Box::new(filter_light(child, filter)),
-        })
+    let filter = walker.filter;
+    let inner = walker.enter(&tag);
+    if allowed {
+        let html =
+            Html::Tag { tag: tag.into_owned(), child: Box::new(filter_light(child, &inner)) };
+        if filter.as_depth() == 0 {
+            walker.commit(&html);
+        }
+        FilterSuccess::make_found(html)
     } else if filter.as_depth() == 0 {
-        filter_aux(child, filter, found).incr()
+        filter_aux(child, &inner, index, scope).incr()
     } else {
-        let rec = filter_aux(child, filter, found);
+        let rec = filter_aux(child, &inner, index, scope);
         match rec.depth {
             DepthSuccess::None => None,
             DepthSuccess::Success => Some(rec),
@@ -255,27 +478,35 @@ fn filter_aux_tag(
                     depth: DepthSuccess::Found(depth + 1),
                     html: Html::Tag { tag: tag.into_owned(), child: Box::new(rec.html) },
                 }),
-                Ordering::Equal | Ordering::Greater =>
-                    Some(FilterSuccess { depth: DepthSuccess::Success, html: rec.html }),
+                Ordering::Equal | Ordering::Greater => {
+                    walker.commit(&rec.html);
+                    Some(FilterSuccess { depth: DepthSuccess::Success, html: rec.html })
+                }
             },
         }
     }
 }
 
 /// Auxiliary method for [`filter_aux`] on [`Html::Vec`]
+///
+/// The tuple contains the index and the scope of the vec, see [`filter_aux`].
+///
+/// The distance of the vec, measured before the walk, tells whether a wanted
+/// tag is close enough to keep all the children. Without a depth, the
+/// children are filtered one by one.
 #[expect(clippy::arithmetic_side_effects, reason = "incr depth when smaller than filter_depth")]
-fn filter_aux_vec(vec: Cow<'_, Box<[Html]>>, filter: &Filter) -> Option<FilterSuccess> {
-    match vec
-        .as_ref()
-        .iter()
-        .filter_map(|child| child.check_depth(filter.as_depth() + 1, filter))
-        .min()
-    {
+fn filter_aux_vec(
+    vec: Cow<'_, Box<[Html]>>,
+    walker: &Walker<'_>,
+    (index, scope): (usize, &[bool]),
+) -> Option<FilterSuccess> {
+    let filter = walker.filter;
+    match walker.distance(index).filter(|depth| *depth <= filter.as_depth() + 1) {
         Some(depth) if depth < filter.as_depth() => Some(FilterSuccess {
             depth: DepthSuccess::Found(depth),
             html: unwrap_vec(
                 vec.iter()
-                    .map(|child| filter_light(Cow::Borrowed(child), filter))
+                    .map(|child| filter_light(Cow::Borrowed(child), walker))
                     .filter(|child| !child.is_empty())
                     .collect(),
                 filter.as_collapse(),
@@ -284,18 +515,21 @@ fn filter_aux_vec(vec: Cow<'_, Box<[Html]>>, filter: &Filter) -> Option<FilterSu
         Some(_) => Some(FilterSuccess {
             depth: DepthSuccess::Success,
             html: unwrap_vec(
-                into_iter_filter_map_collect(vec, |child| {
-                    let rec = filter_aux(child, filter, true).html;
-                    if rec.is_empty() { None } else { Some(rec) }
-                }),
+                filter_children(vec, walker, (index, scope), |rec| {
+                    if rec.depth != DepthSuccess::Success {
+                        walker.commit(&rec.html);
+                    }
+                    walker.limit_reached()
+                })
+                .into_iter()
+                .map(|rec| rec.html)
+                .collect(),
                 filter.as_collapse(),
             ),
         }),
         None => {
-            let mut filtered: Vec<FilterSuccess> = into_iter_filter_map_collect(vec, |child| {
-                let rec = filter_aux(child, filter, false);
-                if rec.html.is_empty() { None } else { Some(rec) }
-            });
+            let mut filtered =
+                filter_children(vec, walker, (index, scope), |_| walker.limit_reached());
             if filtered.len() <= 1 {
                 filtered.pop()
             } else {
@@ -311,24 +545,59 @@ fn filter_aux_vec(vec: Cow<'_, Box<[Html]>>, filter: &Filter) -> Option<FilterSu
     }
 }
 
+/// Filters the children of an [`Html::Vec`], and drops the empty results.
+///
+/// The filtering stops after the first child for which `stop` returns `true`.
+/// If the walker is parallel, the children are filtered in parallel, without
+/// stopping, and their descendants are filtered sequentially. The tuple
+/// contains the index and the scope of the vec, see [`filter_aux`]: the
+/// scopes of the children are given by [`Filter::sibling_scopes`].
+fn filter_children<S: Fn(&FilterSuccess) -> bool>(
+    vec: Cow<'_, Box<[Html]>>,
+    walker: &Walker<'_>,
+    (index, scope): (usize, &[bool]),
+    stop: S,
+) -> Vec<FilterSuccess> {
+    #[cfg(feature = "rayon")]
+    if walker.parallel {
+        return parallel::filter_children(vec, walker, (index, scope));
+    }
+    let scopes = walker.filter.sibling_scopes(&vec, scope);
+    let next = Cell::new((0usize, index.saturating_add(1)));
+    into_iter_filter_map_collect(
+        vec,
+        |child| {
+            let (idx, child_index) = next.get();
+            next.set((idx.saturating_add(1), walker.next_sibling(child_index)));
+            let rec = filter_aux(child, walker, child_index, scopes.get(idx, scope));
+            if rec.html.is_empty() { None } else { Some(rec) }
+        },
+        stop,
+    )
+}
+
 /// Light filter without complicated logic, just filtering on types.
 ///
 /// This method does take into account the [`Filter::tag_name`],
 ///   [`Filter::attribute_name`] and [`Filter::attribute_value`] methods,
-/// only the types of [`NodeTypeFilter`].
+/// only the types of [`NodeTypes`](crate::NodeTypes).
 ///
 /// The return type is [`Html`] and not [`Cow`] has it is only called on
 /// successes.
 #[allow(clippy::allow_attributes, reason = "expect is buggy")]
 #[allow(clippy::enum_glob_use, reason = "heavy syntax and Html is the main struct")]
-fn filter_light(cow_html: Cow<'_, Html>, filter: &Filter) -> Html {
+fn filter_light(cow_html: Cow<'_, Html>, walker: &Walker<'_>) -> Html {
     use Html::*;
+    if !walker.visit() {
+        return Html::Empty;
+    }
+    let filter = walker.filter;
     #[allow(clippy::ref_patterns, reason = "!")]
     match cow_html {
-        Cow::Borrowed(Text(txt)) if filter.text_allowed() && filter.should_trim() =>
-            Html::trim_text(txt),
-        Cow::Owned(Text(txt)) if filter.text_allowed() && filter.should_trim() =>
-            Html::trim_text(&txt),
+        Cow::Borrowed(Text(txt)) if filter.text_allowed() && walker.trims() =>
+            Html::trim_text(txt, filter.as_trim()),
+        Cow::Owned(Text(txt)) if filter.text_allowed() && walker.trims() =>
+            Html::trim_text(&txt, filter.as_trim()),
         Cow::Owned(Text(_)) | Cow::Borrowed(Text(_)) if filter.text_allowed() =>
             cow_html.into_owned(),
         Cow::Borrowed(Comment(_)) | Cow::Owned(Comment(_)) if filter.comment_allowed() =>
@@ -339,20 +608,22 @@ fn filter_light(cow_html: Cow<'_, Html>, filter: &Filter) -> Html {
         Cow::Owned(Tag { tag, .. }) if filter.tag_explicitly_blacklisted(&tag) => Html::Empty,
         Cow::Borrowed(Tag { tag, child }) => Tag {
             tag: tag.to_owned(),
-            child: Box::new(filter_light(Cow::Borrowed(&**child), filter)),
+            child: Box::new(filter_light(Cow::Borrowed(&**child), &walker.enter(tag))),
         },
-        Cow::Owned(Tag { tag, child }) =>
-            Tag { tag, child: Box::new(filter_light(Cow::Owned(*child), filter)) },
+        Cow::Owned(Tag { tag, child }) => {
+            let inner = walker.enter(&tag);
+            Tag { tag, child: Box::new(filter_light(Cow::Owned(*child), &inner)) }
+        }
         Cow::Borrowed(Vec(vec)) => unwrap_vec(
             vec.iter()
-                .map(|child| filter_light(Cow::Borrowed(child), filter))
+                .map(|child| filter_light(Cow::Borrowed(child), walker))
                 .filter(|html| !html.is_empty())
                 .collect(),
             filter.as_collapse(),
         ),
         Cow::Owned(Vec(vec)) => unwrap_vec(
             vec.into_iter()
-                .map(|child| filter_light(Cow::Owned(child), filter))
+                .map(|child| filter_light(Cow::Owned(child), walker))
                 .filter(|html| !html.is_empty())
                 .collect(),
             filter.as_collapse(),
@@ -372,13 +643,13 @@ fn unwrap_vec(vec: Vec<Html>, collapse: bool) -> Html {
                 previous.push_str(&text);
             } else {
                 if !previous.is_empty() {
-                    res.push(Html::Text(take(&mut previous)));
+                    res.push(Html::Text(take(&mut previous).into()));
                 }
                 res.push(this);
             }
         }
         if !previous.is_empty() {
-            res.push(Html::Text(take(&mut previous)));
+            res.push(Html::Text(take(&mut previous).into()));
         }
         res
     } else {
@@ -393,15 +664,35 @@ fn unwrap_vec(vec: Vec<Html>, collapse: bool) -> Html {
 
 /// Method to apply [`Iterator::filter_map`] on an iterator inside a Cow,
 /// without losing the Cow.
-fn into_iter_filter_map_collect<T, U, V, F>(cow: Cow<'_, Box<[T]>>, map: F) -> V
+///
+/// The iteration stops after the first mapped element that satisfies `stop`.
+fn into_iter_filter_map_collect<T, U, F, S>(cow: Cow<'_, Box<[T]>>, map: F, stop: S) -> Vec<U>
 where
     T: Clone,
-    V: FromIterator<U>,
     F: Fn(Cow<'_, T>) -> Option<U>,
+    S: Fn(&U) -> bool,
 {
+    let mut res = vec![];
+    let mut push = |elt: Option<U>| {
+        elt.is_some_and(|mapped| {
+            let done = stop(&mapped);
+            res.push(mapped);
+            done
+        })
+    };
     match cow {
         Cow::Borrowed(borrowed) =>
-            borrowed.into_iter().filter_map(|elt| map(Cow::Borrowed(elt))).collect(),
-        Cow::Owned(owned) => owned.into_iter().filter_map(|elt| map(Cow::Owned(elt))).collect(),
+            for elt in &**borrowed {
+                if push(map(Cow::Borrowed(elt))) {
+                    break;
+                }
+            },
+        Cow::Owned(owned) =>
+            for elt in owned {
+                if push(map(Cow::Owned(elt))) {
+                    break;
+                }
+            },
     }
+    res
 }
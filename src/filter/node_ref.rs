@@ -0,0 +1,606 @@
+//! Module to reference nodes of a tree without cloning them, and to navigate
+//! from these nodes to their ancestors and to their siblings.
+//!
+//! The nodes can be selected with a [`Filter`], with [`Html::select`], or by
+//! their text content, with [`Html::find_text`].
+
+use core::iter::{FusedIterator, once};
+use core::ptr;
+
+#[cfg(feature = "regex")]
+use regex::{Error as RegexError, Regex};
+
+use super::unwrap_vec;
+use crate::{Filter, Html, NodeId, Tag};
+
+/// Iterator over the ancestors of a [`NodeRef`], from the closest to the root.
+///
+/// This struct is created by [`NodeRef::ancestors`].
+#[derive(Debug, Clone)]
+pub struct Ancestors<'html> {
+    /// Remaining ancestors, from the root to the closest one
+    parents: Vec<&'html Html>,
+    /// Root of the tree
+    root: &'html Html,
+}
+
+impl<'html> Iterator for Ancestors<'html> {
+    type Item = NodeRef<'html>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.parents.pop()?;
+        Some(NodeRef { node, parents: self.parents.clone(), root: self.root })
+    }
+}
+
+impl Html {
+    /// Returns references to the tags that directly contain a text with the
+    /// given pattern, in the order of the document.
+    ///
+    /// The text is searched literally and is case-sensitive. Each tag is
+    /// returned at most once, even if it contains multiple occurrences of the
+    /// pattern. Texts that aren't in any tag are ignored.
+    ///
+    /// The returned [`NodeRef`]s remember the path to the tags, so you can
+    /// then climb up to the structure that contains the text with
+    /// [`NodeRef::closest`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     "<table><tr><th>Name</th><td>Lamp</td></tr><tr><th>Price</th><td>12$</td></tr></table>",
+    /// )
+    /// .unwrap();
+    ///
+    /// let price = html.find_text("Price");
+    /// assert_eq!(price.len(), 1);
+    /// assert_eq!(price[0].as_html(), &"<th>Price</th>");
+    ///
+    /// let row = price[0].closest(&Filter::new().tag_name("tr")).unwrap();
+    /// assert_eq!(row.as_html(), &"<tr><th>Price</th><td>12$</td></tr>");
+    /// ```
+    #[must_use]
+    pub fn find_text(&self, pattern: &str) -> Vec<NodeRef<'_>> {
+        let mut refs = vec![];
+        self.find_text_aux(&|text| text.contains(pattern), (self, &mut vec![]), &mut refs);
+        refs
+    }
+
+    /// Wrapper for [`Html::find_text`], and for `Html::find_text_regex`
+    ///
+    /// `matches` checks if a text is wanted, and `path` contains the root of
+    /// the tree, and the tags in which the current node is, from the root.
+    fn find_text_aux<'html>(
+        &'html self,
+        matches: &dyn Fn(&str) -> bool,
+        path: (&'html Self, &mut Vec<&'html Self>),
+        refs: &mut Vec<NodeRef<'html>>,
+    ) {
+        let (root, parents) = path;
+        match self {
+            Self::Tag { child, .. } => {
+                if child.has_direct_text(matches) {
+                    refs.push(NodeRef { node: self, parents: parents.clone(), root });
+                }
+                parents.push(self);
+                child.find_text_aux(matches, (root, parents), refs);
+                parents.pop();
+            }
+            Self::Vec(vec) =>
+                for child in vec {
+                    child.find_text_aux(matches, (root, parents), refs);
+                },
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty | Self::Text(_) => (),
+        }
+    }
+
+    /// Returns references to the tags that directly contain a text that
+    /// matches the given regular expression, in the order of the document.
+    ///
+    /// This is the same as [`Html::find_text`], with a pattern that uses the
+    /// syntax of the [`regex`] crate. It isn't anchored: use `^` and `$` to
+    /// match a whole text node.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pattern isn't a valid regular expression.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html =
+    ///     Html::parse("<ul><li>Order #1042</li><li>Refund</li><li>Order #1043 <b>paid</b></li></ul>")
+    ///         .unwrap();
+    ///
+    /// let orders = html.find_text_regex(r"#\d+").unwrap();
+    /// assert_eq!(orders.len(), 2);
+    /// assert_eq!(orders[0].as_html(), &"<li>Order #1042</li>");
+    ///
+    /// assert!(html.find_text_regex("(unclosed").is_err());
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn find_text_regex(&self, pattern: &str) -> Result<Vec<NodeRef<'_>>, RegexError> {
+        let regex = Regex::new(pattern)?;
+        let mut refs = vec![];
+        self.find_text_aux(&|text| regex.is_match(text), (self, &mut vec![]), &mut refs);
+        Ok(refs)
+    }
+
+    /// Checks if the node is, or directly contains, a wanted text.
+    fn has_direct_text(&self, matches: &dyn Fn(&str) -> bool) -> bool {
+        match self {
+            Self::Text(text) => matches(text),
+            Self::Vec(vec) =>
+                vec.iter().any(|child| matches!(child, Self::Text(text) if matches(text))),
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty | Self::Tag { .. } => false,
+        }
+    }
+
+    /// Returns a reference to the tag with the given identifier, if it is in
+    /// the tree.
+    ///
+    /// See [`NodeId`] for more information on the identifiers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<ul><li>One</li><li>Two</li></ul>").unwrap();
+    /// let id = html.find_text("Two")[0].id().unwrap();
+    ///
+    /// let item = html.node_by_id(id).unwrap();
+    /// assert_eq!(item.as_html(), &"<li>Two</li>");
+    /// assert_eq!(item.parent().unwrap().as_tag().unwrap().as_name(), "ul");
+    /// ```
+    #[must_use]
+    pub fn node_by_id(&self, id: NodeId) -> Option<NodeRef<'_>> {
+        self.node_by_id_aux(id, (self, &mut vec![]))
+    }
+
+    /// Wrapper for [`Html::node_by_id`]
+    ///
+    /// `path` contains the root of the tree, and the tags in which the
+    /// current node is, from the root.
+    fn node_by_id_aux<'html>(
+        &'html self,
+        id: NodeId,
+        path: (&'html Self, &mut Vec<&'html Self>),
+    ) -> Option<NodeRef<'html>> {
+        let (root, parents) = path;
+        match self {
+            Self::Tag { tag, .. } if tag.id() == id =>
+                Some(NodeRef { node: self, parents: parents.clone(), root }),
+            Self::Tag { child, .. } => {
+                parents.push(self);
+                let found = child.node_by_id_aux(id, (root, parents));
+                parents.pop();
+                found
+            }
+            Self::Vec(vec) =>
+                vec.iter().find_map(|child| child.node_by_id_aux(id, (root, &mut *parents))),
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty | Self::Text(_) => None,
+        }
+    }
+
+    /// Returns a mutable reference to the tag with the given identifier, if it
+    /// is in the tree.
+    ///
+    /// See [`NodeId`] for more information on the identifiers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let mut html = Html::parse("<ul><li>One</li><li>Two</li></ul>").unwrap();
+    /// let id = html.find_text("Two")[0].id().unwrap();
+    ///
+    /// *html.node_by_id_mut(id).unwrap() = Html::Text("Removed".into());
+    /// assert_eq!(html, "<ul><li>One</li>Removed</ul>");
+    /// ```
+    pub fn node_by_id_mut(&mut self, id: NodeId) -> Option<&mut Self> {
+        match self {
+            Self::Tag { tag, .. } if tag.id() == id => Some(self),
+            Self::Tag { child, .. } => child.node_by_id_mut(id),
+            Self::Vec(vec) => vec.iter_mut().find_map(|child| child.node_by_id_mut(id)),
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty | Self::Text(_) => None,
+        }
+    }
+
+    /// Returns references to all the tags that match the filter, in the order
+    /// of the document.
+    ///
+    /// Contrary to [`Html::to_filtered`], this doesn't clone the tree: the
+    /// returned [`NodeRef`]s borrow the document and remember the path to
+    /// the matched tags. This allows to navigate from the matches to their
+    /// ancestors, with [`NodeRef::ancestors`] and [`NodeRef::closest`].
+    ///
+    /// The matches are found lazily, while iterating over the returned
+    /// [`Matches`]: stopping after the first match doesn't traverse the rest
+    /// of the tree. Use [`Matches::to_owned_tree`] to clone the matches into
+    /// an owned [`Html`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<ul><li>One</li><li>Two</li></ul>").unwrap();
+    /// let filter = Filter::new().tag_name("li");
+    /// let items = html.select(&filter).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(items.len(), 2);
+    /// assert_eq!(items[1].as_html(), &"<li>Two</li>");
+    /// assert_eq!(items[1].parent().unwrap().as_tag().unwrap().as_name(), "ul");
+    /// ```
+    #[must_use]
+    pub fn select<'filter>(&self, filter: &'filter Filter) -> Matches<'_, 'filter> {
+        Matches { filter, parents: vec![], pending: vec![(self, 0, vec![])], root: self }
+    }
+}
+
+/// Direction of the text of a node, given by the `dir` attribute.
+///
+/// See [`NodeRef::effective_dir`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// `dir="auto"`: the direction is guessed from the text
+    Auto,
+    /// `dir="ltr"`: the text is written from left to right
+    Ltr,
+    /// `dir="rtl"`: the text is written from right to left
+    Rtl,
+}
+
+/// Lazy iterator over the tags of a tree that match a filter, in the order of
+/// the document.
+///
+/// This struct is created by [`Html::select`]. It borrows both the document
+/// and the filter, and the matches are only searched while iterating. The
+/// [`NodeRef`]s it returns only borrow the document.
+#[derive(Debug, Clone)]
+pub struct Matches<'html, 'filter> {
+    /// Filter to match
+    filter: &'filter Filter,
+    /// Tags in which the next pending node is, from the root
+    parents: Vec<&'html Html>,
+    /// Nodes left to visit, with their number of ancestors and their scope,
+    /// see [`Filter::inside`], the next one last
+    pending: Vec<(&'html Html, usize, Vec<bool>)>,
+    /// Root of the tree
+    root: &'html Html,
+}
+
+impl<'html> Matches<'html, '_> {
+    /// Clones the matched tags into an owned tree, as a list of tags in the
+    /// order of the document.
+    ///
+    /// A match nested in another match is only present once, inside the
+    /// outer one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<div><div>Inner</div></div><p>Text</p><div>Last</div>").unwrap();
+    /// let filter = Filter::new().tag_name("div");
+    ///
+    /// assert_eq!(html.select(&filter).count(), 3);
+    /// assert_eq!(html.select(&filter).to_owned_tree(), "<div><div>Inner</div></div><div>Last</div>");
+    /// ```
+    #[must_use]
+    pub fn to_owned_tree(self) -> Html {
+        let mut outer: Vec<&'html Html> = vec![];
+        for node in self {
+            if !outer
+                .last()
+                .is_some_and(|last| node.parents.iter().any(|parent| ptr::eq(*parent, *last)))
+            {
+                outer.push(node.node);
+            }
+        }
+        unwrap_vec(outer.into_iter().cloned().collect(), false)
+    }
+}
+
+impl<'html> Iterator for Matches<'html, '_> {
+    type Item = NodeRef<'html>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((node, depth, scope)) = self.pending.pop() {
+            self.parents.truncate(depth);
+            match node {
+                Html::Tag { child, .. } => {
+                    let found = self.filter.tag_allowed_in(node, &scope).then(|| NodeRef {
+                        node,
+                        parents: self.parents.clone(),
+                        root: self.root,
+                    });
+                    let child_scope = self.filter.enter_scope(node, &scope).unwrap_or(scope);
+                    self.parents.push(node);
+                    self.pending.push((child, depth.saturating_add(1), child_scope));
+                    if found.is_some() {
+                        return found;
+                    }
+                }
+                Html::Vec(vec) => {
+                    let scopes = self.filter.sibling_scopes(vec, &scope);
+                    self.pending.extend(
+                        vec.iter()
+                            .enumerate()
+                            .rev()
+                            .map(|(idx, child)| (child, depth, scopes.get(idx, &scope).to_vec())),
+                    );
+                }
+                Html::Comment(_) | Html::Doctype { .. } | Html::Empty | Html::Text(_) => (),
+            }
+        }
+        None
+    }
+}
+
+impl FusedIterator for Matches<'_, '_> {}
+
+/// Reference to a node of an [`Html`] tree, that remembers the ancestors of
+/// the node.
+///
+/// [`NodeRef`]s are obtained with [`Html::select`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeRef<'html> {
+    /// Referenced node
+    node: &'html Html,
+    /// Tags in which the node is, from the root to the closest one
+    parents: Vec<&'html Html>,
+    /// Root of the tree, to find the siblings of the nodes at the root
+    root: &'html Html,
+}
+
+impl<'html> NodeRef<'html> {
+    /// Returns an iterator over the tags that contain this node, from the
+    /// closest to the root.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<section><div><p>Text</p></div></section>").unwrap();
+    /// let paragraph = html.select(&Filter::new().tag_name("p")).next().unwrap();
+    ///
+    /// let names = paragraph
+    ///     .ancestors()
+    ///     .filter_map(|ancestor| ancestor.as_tag().map(|tag| tag.as_name().to_owned()))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(names, ["div", "section"]);
+    /// ```
+    #[must_use]
+    pub fn ancestors(&self) -> Ancestors<'html> {
+        Ancestors { parents: self.parents.clone(), root: self.root }
+    }
+
+    /// Returns the referenced node.
+    #[must_use]
+    pub const fn as_html(&self) -> &'html Html {
+        self.node
+    }
+
+    /// Returns the referenced tag, if the referenced node is a tag.
+    #[must_use]
+    pub const fn as_tag(&self) -> Option<&'html Tag> {
+        if let Html::Tag { tag, .. } = self.node { Some(tag) } else { None }
+    }
+
+    /// Returns the closest tag that matches the filter, starting with the
+    /// referenced node itself and then climbing up the ancestors.
+    ///
+    /// This mirrors the DOM `Element.closest` method, and is useful to find
+    /// the card, section or form that contains a node, without querying the
+    /// whole document again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html =
+    ///     Html::parse(r#"<div class="card"><h2>Title</h2><div><a href="/link">Link</a></div></div>"#)
+    ///         .unwrap();
+    /// let link = html.select(&Filter::new().tag_name("a")).next().unwrap();
+    ///
+    /// let card = link.closest(&Filter::new().attribute_value_contains("class", "card")).unwrap();
+    /// assert_eq!(card.as_html(), &html);
+    ///
+    /// assert_eq!(link.closest(&Filter::new().tag_name("a")).unwrap(), link);
+    /// assert!(link.closest(&Filter::new().tag_name("form")).is_none());
+    /// ```
+    #[must_use]
+    pub fn closest(&self, filter: &Filter) -> Option<Self> {
+        once(self.clone()).chain(self.ancestors()).find(|node| filter.tag_allowed(node.as_html()))
+    }
+
+    /// Returns the direction of the text of the referenced node, inherited
+    /// from the closest tag with a valid `dir` attribute.
+    ///
+    /// The values of the `dir` attribute are case-insensitive, and the invalid
+    /// ones are ignored. Returns `None` if no tag specifies the direction, in
+    /// which case the text is written from left to right.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     r#"<div dir="RTL"><p dir="sideways"><b>Text</b></p><p dir="ltr">Text</p></div>"#,
+    /// )
+    /// .unwrap();
+    /// let bold = html.select(&Filter::new().tag_name("b")).next().unwrap();
+    /// assert_eq!(bold.effective_dir(), Some(Direction::Rtl));
+    ///
+    /// let paragraph = html.select(&Filter::new().tag_name("p")).nth(1).unwrap();
+    /// assert_eq!(paragraph.effective_dir(), Some(Direction::Ltr));
+    /// ```
+    #[must_use]
+    pub fn effective_dir(&self) -> Option<Direction> {
+        once(self.clone()).chain(self.ancestors()).find_map(|node| {
+            let dir = node.as_tag()?.find_attr_value("dir")?;
+            [("auto", Direction::Auto), ("ltr", Direction::Ltr), ("rtl", Direction::Rtl)]
+                .into_iter()
+                .find_map(|(name, direction)| dir.eq_ignore_ascii_case(name).then_some(direction))
+        })
+    }
+
+    /// Returns the language of the referenced node, inherited from the
+    /// closest tag with a `lang` or `xml:lang` attribute.
+    ///
+    /// `xml:lang` takes precedence over `lang` on the same tag. An empty
+    /// value means that the language is unknown, and is returned as is.
+    /// Returns `None` if no tag specifies the language.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     r#"<html lang="en"><body><p>Hello</p><p lang="fr">Bonjour <b>le monde</b></p></body></html>"#,
+    /// )
+    /// .unwrap();
+    /// let paragraph = html.select(&Filter::new().tag_name("p")).next().unwrap();
+    /// assert_eq!(paragraph.effective_lang(), Some("en"));
+    ///
+    /// let bold = html.select(&Filter::new().tag_name("b")).next().unwrap();
+    /// assert_eq!(bold.effective_lang(), Some("fr"));
+    /// ```
+    #[must_use]
+    pub fn effective_lang(&self) -> Option<&'html str> {
+        once(self.clone()).chain(self.ancestors()).find_map(|node| {
+            let tag = node.as_tag()?;
+            tag.find_attr_value("xml:lang")
+                .or_else(|| tag.find_attr_value("lang"))
+                .map(String::as_str)
+        })
+    }
+
+    /// Returns the stable identifier of the referenced node, if it is a tag.
+    ///
+    /// See [`NodeId`] for more information.
+    #[must_use]
+    pub fn id(&self) -> Option<NodeId> {
+        self.as_tag().map(Tag::id)
+    }
+
+    /// Returns the node that directly follows the referenced node in its
+    /// parent, if any.
+    ///
+    /// Like the DOM `Node.nextSibling`, the sibling can be a text or a
+    /// comment. Use [`NodeRef::next_sibling_tag`] to skip them.
+    #[must_use]
+    pub fn next_sibling(&self) -> Option<Self> {
+        let (siblings, idx) = self.siblings()?;
+        siblings.get(idx.checked_add(1)?).map(|node| self.with_node(node))
+    }
+
+    /// Returns the first tag after the referenced node in its parent, if any,
+    /// skipping the texts and the comments.
+    ///
+    /// This mirrors the DOM `Element.nextElementSibling` property, and is
+    /// useful to find the value next to a label.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     "<table><tr><th>Name</th><td>Widget</td></tr><tr><th>Price</th> <td>42</td></tr></table>",
+    /// )
+    /// .unwrap();
+    /// let label = html.find_text("Price").pop().unwrap();
+    ///
+    /// let price = label.next_sibling_tag().unwrap();
+    /// assert_eq!(*price.as_html(), "<td>42</td>");
+    /// assert!(price.next_sibling_tag().is_none());
+    /// ```
+    #[must_use]
+    pub fn next_sibling_tag(&self) -> Option<Self> {
+        let (siblings, idx) = self.siblings()?;
+        siblings
+            .get(idx.checked_add(1)?..)?
+            .iter()
+            .find(|node| matches!(node, Html::Tag { .. }))
+            .map(|node| self.with_node(node))
+    }
+
+    /// Returns the tag that directly contains the referenced node, if any.
+    #[must_use]
+    pub fn parent(&self) -> Option<Self> {
+        self.ancestors().next()
+    }
+
+    /// Returns the node that directly precedes the referenced node in its
+    /// parent, if any.
+    ///
+    /// Like the DOM `Node.previousSibling`, the sibling can be a text or a
+    /// comment. Use [`NodeRef::prev_sibling_tag`] to skip them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<p>Intro<b>bold</b></p><p>End</p>").unwrap();
+    /// let bold = html.select(&Filter::new().tag_name("b")).next().unwrap();
+    /// assert_eq!(*bold.prev_sibling().unwrap().as_html(), "Intro");
+    ///
+    /// let end = html.find_text("End").pop().unwrap();
+    /// assert_eq!(*end.prev_sibling().unwrap().as_html(), "<p>Intro<b>bold</b></p>");
+    /// ```
+    #[must_use]
+    pub fn prev_sibling(&self) -> Option<Self> {
+        let (siblings, idx) = self.siblings()?;
+        siblings.get(idx.checked_sub(1)?).map(|node| self.with_node(node))
+    }
+
+    /// Returns the last tag before the referenced node in its parent, if any,
+    /// skipping the texts and the comments.
+    ///
+    /// This mirrors the DOM `Element.previousElementSibling` property.
+    #[must_use]
+    pub fn prev_sibling_tag(&self) -> Option<Self> {
+        let (siblings, idx) = self.siblings()?;
+        siblings
+            .get(..idx)?
+            .iter()
+            .rev()
+            .find(|node| matches!(node, Html::Tag { .. }))
+            .map(|node| self.with_node(node))
+    }
+
+    /// Returns the nodes of the parent of the referenced node, with the
+    /// position of the referenced node among them.
+    ///
+    /// Returns `None` if the referenced node is the only child of its parent,
+    /// or the whole tree.
+    fn siblings(&self) -> Option<(&'html [Html], usize)> {
+        let container = match self.parents.last() {
+            Some(Html::Tag { child, .. }) => child,
+            Some(_) | None => self.root,
+        };
+        let siblings = container.as_vec()?;
+        let idx = siblings.iter().position(|sibling| ptr::eq(sibling, self.node))?;
+        Some((siblings, idx))
+    }
+
+    /// Creates a reference to a sibling of the referenced node.
+    fn with_node(&self, node: &'html Html) -> Self {
+        Self { node, parents: self.parents.clone(), root: self.root }
+    }
+}
@@ -1,52 +1,163 @@
 //! Types of html nodes to filter
 //!
-//! Set the elements to `true` iff you want them to appear in the filtered
-//! output
+//! Set the types of the nodes that you want to appear in the filtered output,
+//! see [`NodeTypes`].
+
+use core::ops::{BitAnd, BitOr, Not, Sub};
+
+/// Set of types of html nodes, that aren't tags: the texts, the comments and
+/// the doctypes.
+///
+/// The sets are composed with the bitwise operators, and are given to
+/// [`Filter::keep_types`](crate::Filter::keep_types) and
+/// [`Filter::drop_types`](crate::Filter::drop_types).
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// let types = NodeTypes::TEXT | NodeTypes::COMMENT;
+/// assert!(types.contains(NodeTypes::TEXT));
+/// assert!(!types.contains(NodeTypes::DOCTYPE));
+/// assert_eq!(!types, NodeTypes::DOCTYPE);
+/// assert_eq!(types - NodeTypes::TEXT, NodeTypes::COMMENT);
+/// assert!((types & NodeTypes::DOCTYPE).is_empty());
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeTypes(u8);
+
+impl NodeTypes {
+    /// The texts, the comments and the doctypes
+    pub const ALL: Self = Self(0b111);
+    /// Html comments, like `<!-- some comment -->`
+    pub const COMMENT: Self = Self(0b001);
+    /// Html doctypes, like `<!DOCTYPE html>`
+    pub const DOCTYPE: Self = Self(0b010);
+    /// No type of nodes
+    pub const NONE: Self = Self(0);
+    /// Html texts, like `Hello world` in `<p>Hello world</p>`
+    pub const TEXT: Self = Self(0b100);
+
+    /// Returns the types that aren't in `self`.
+    #[must_use]
+    pub const fn complement(self) -> Self {
+        Self(!self.0 & Self::ALL.0)
+    }
+
+    /// Checks if all the types of `other` are in `self`.
+    #[must_use]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns the types of `self` that aren't in `other`.
+    #[must_use]
+    pub const fn difference(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    /// Returns the types that are both in `self` and `other`.
+    #[must_use]
+    pub const fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    /// Checks if the set contains no type.
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns the types that are in `self` or `other`.
+    #[must_use]
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl BitAnd for NodeTypes {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        self.intersection(rhs)
+    }
+}
+
+impl BitOr for NodeTypes {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        self.union(rhs)
+    }
+}
+
+impl Not for NodeTypes {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        self.complement()
+    }
+}
+
+impl Sub for NodeTypes {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self.difference(rhs)
+    }
+}
 
 /// Types of html nodes to filter
 ///
-/// Set the elements to `true` iff you want them to appear in the filtered
-/// output
+/// A type is kept in the filtered output iff it is in `kept`. The types that
+/// aren't in `explicit` were not set by the user, and the filter picks their
+/// default.
 #[derive(Default, Debug, PartialEq, Eq)]
 pub(super) struct NodeTypeFilter {
     /// Whether successive texts after a filter removes nodes should be collapse
     /// in one text or not.
     collapse: bool,
-    /// Html comment
-    ///
-    /// # Note
-    ///
-    /// By default, comments are discarded.
-    ///
-    /// # Examples
-    ///
-    /// `<!-- some comment -->`
-    comment: Option<bool>,
-    /// Html doctype tags
-    ///
-    /// # Note
-    ///
-    /// By default, doctype are discarded.
-    ///
-    /// # Examples
-    ///
-    /// `<!-- some comment -->`
-    doctype: Option<bool>,
-    /// Html text node
+    /// Types of nodes whose keep policy was set by the user.
     ///
     /// # Note
     ///
-    /// By default, texts are kept.
-    ///
-    /// # Examples
-    ///
-    /// In `<p>Hello world</p>`, `Hello world` is a text node.
-    text: Option<bool>,
-    /// Whether to trim all texts.
+    /// By default, texts are kept, and comments and doctypes are discarded,
+    /// unless the filter has no rules on the tags.
+    explicit: NodeTypes,
+    /// Types of nodes to keep, among the [`Self::explicit`] ones.
+    kept: NodeTypes,
+    /// How to trim all texts.
     ///
-    /// This will remove text segments that contain only whitespaces and
-    /// newlines.
-    trim: bool,
+    /// Unless the mode is [`TrimMode::None`], this will remove text segments
+    /// that contain only whitespaces and newlines.
+    trim: TrimMode,
+}
+
+/// Policy to trim the texts kept in the filtered output, see
+/// [`Filter::trim_text`](crate::Filter::trim_text).
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// let html = Html::parse("<p>\n  Hello,\n  world!\n</p>").unwrap();
+/// let filter = Filter::new().tag_name("p");
+///
+/// assert_eq!(html.to_filtered(&filter.trim_text(TrimMode::Collapse)), "<p>Hello, world!</p>");
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TrimMode {
+    /// Trims the texts, then collapses every sequence of ASCII whitespaces
+    /// inside them into a single space.
+    Collapse,
+    /// Trims the whitespaces at the start and at the end of the texts.
+    Edges,
+    /// Keeps the texts as they are.
+    #[default]
+    None,
 }
 
 #[expect(clippy::arbitrary_source_item_ordering, reason = "ordered by type")]
@@ -55,7 +166,24 @@ impl NodeTypeFilter {
 
     /// Returns a default [`Self`]
     pub const fn new() -> Self {
-        Self { comment: None, doctype: None, text: None, trim: false, collapse: false }
+        Self {
+            explicit: NodeTypes::NONE,
+            kept: NodeTypes::NONE,
+            trim: TrimMode::None,
+            collapse: false,
+        }
+    }
+
+    /// Returns whether the nodes of type `types` are allowed, if it was set by
+    /// the user.
+    const fn allowed(&self, types: NodeTypes) -> Option<bool> {
+        if self.explicit.contains(types) { Some(self.kept.contains(types)) } else { None }
+    }
+
+    /// Sets the authorisation of the nodes of type `types`.
+    const fn set(&mut self, types: NodeTypes, keep: bool) {
+        self.explicit = self.explicit.union(types);
+        self.kept = if keep { self.kept.union(types) } else { self.kept.difference(types) };
     }
 
     // getters
@@ -67,22 +195,27 @@ impl NodeTypeFilter {
 
     /// Checks if comments are allowed
     pub const fn comment_allowed(&self) -> Option<bool> {
-        self.comment
+        self.allowed(NodeTypes::COMMENT)
     }
 
     /// Checks if doctypes are allowed
     pub const fn doctype_allowed(&self) -> Option<bool> {
-        self.doctype
+        self.allowed(NodeTypes::DOCTYPE)
     }
 
     /// Checks if texts are allowed
     pub const fn text_allowed(&self) -> Option<bool> {
-        self.text
+        self.allowed(NodeTypes::TEXT)
+    }
+
+    /// Returns how the texts should be trimmed.
+    pub const fn as_trim(&self) -> TrimMode {
+        self.trim
     }
 
     /// Checks if texts should be trimmed, and removed if empty.
     pub const fn should_trim(&self) -> bool {
-        self.trim
+        !matches!(self.trim, TrimMode::None)
     }
 
     // setters
@@ -94,21 +227,27 @@ impl NodeTypeFilter {
 
     /// Sets the comment authorisation
     pub const fn set_comment(&mut self, comment: bool) {
-        self.comment = Some(comment);
+        self.set(NodeTypes::COMMENT, comment);
     }
 
     /// Sets the doctype authorisation
     pub const fn set_doctype(&mut self, doctype: bool) {
-        self.doctype = Some(doctype);
+        self.set(NodeTypes::DOCTYPE, doctype);
     }
 
     /// Sets the text authorisation
     pub const fn set_text(&mut self, text: bool) {
-        self.text = Some(text);
+        self.set(NodeTypes::TEXT, text);
+    }
+
+    /// Keeps exactly the nodes of type `types`, and removes the others.
+    pub const fn set_types(&mut self, types: NodeTypes) {
+        self.explicit = NodeTypes::ALL;
+        self.kept = types;
     }
 
-    /// Sets trim flag.
-    pub const fn trim(&mut self) {
-        self.trim = true;
+    /// Sets the trim mode.
+    pub const fn trim(&mut self, mode: TrimMode) {
+        self.trim = mode;
     }
 }
@@ -0,0 +1,106 @@
+//! Module to filter large trees on multiple threads, with `rayon`.
+
+use alloc::borrow::Cow;
+use core::iter::successors;
+
+use rayon::iter::{
+    IndexedParallelIterator as _, IntoParallelIterator as _, IntoParallelRefIterator as _, ParallelIterator as _
+};
+
+use super::{FilterSuccess, Walker, filter_aux};
+use crate::{Filter, Html};
+
+impl Html {
+    /// Filters html based on a defined filter, on multiple threads.
+    ///
+    /// Equivalent of [`Html::filter`], but the children of the first list of
+    /// nodes, i.e., the top-level sections of the document, or the children
+    /// of the `<body>`, are filtered in parallel, on the global `rayon`
+    /// thread pool. The nodes are then filtered sequentially inside each
+    /// branch.
+    ///
+    /// This is only faster for large documents, with many top-level
+    /// branches: filtering is CPU-bound and independent across siblings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     "<html><body><section><p>a</p></section><section><p>b</p><b>c</b></section></body></html>",
+    /// )
+    /// .unwrap();
+    /// let filter = Filter::new().tag_name("p");
+    ///
+    /// assert_eq!(html.clone().par_filter(&filter), "<p>a</p><p>b</p>");
+    /// assert_eq!(html.clone().par_filter(&filter), html.filter(&filter));
+    /// ```
+    #[must_use]
+    pub fn par_filter(self, filter: &Filter) -> Self {
+        Walker::parallel(filter).run(Cow::Owned(self))
+    }
+
+    /// Filters html based on a defined filter, on multiple threads.
+    ///
+    /// Equivalent of [`Html::par_filter`] when data is not owned.
+    #[must_use]
+    pub fn par_to_filtered(&self, filter: &Filter) -> Self {
+        Walker::parallel(filter).run(Cow::Borrowed(self))
+    }
+}
+
+impl<'walk> Walker<'walk> {
+    /// Creates a [`Walker`] that filters the children of the first
+    /// [`Html::Vec`] in parallel.
+    const fn parallel(filter: &'walk Filter) -> Self {
+        Self {
+            budget: None,
+            committed: None,
+            distances: None,
+            filter,
+            limit: None,
+            parallel: true,
+            preformatted: false,
+        }
+    }
+}
+
+/// Filters the children of an [`Html::Vec`] in parallel, and drops the empty
+/// results.
+///
+/// The descendants of the children are filtered sequentially. The tuple
+/// contains the index and the scope of the vec, see [`filter_aux`]: the
+/// scopes of the children are given by [`Filter::sibling_scopes`].
+pub fn filter_children(
+    vec: Cow<'_, Box<[Html]>>,
+    walker: &Walker<'_>,
+    (index, scope): (usize, &[bool]),
+) -> Vec<FilterSuccess> {
+    let (distances, filter, preformatted) = (walker.distances, walker.filter, walker.preformatted);
+    let scopes = filter.sibling_scopes(&vec, scope);
+    let indices = successors(Some(index.saturating_add(1)), |child_index| {
+        Some(walker.next_sibling(*child_index))
+    })
+    .take(vec.len())
+    .enumerate()
+    .collect::<Vec<_>>();
+    let filter_child = |child: Cow<'_, Html>, (idx, child_index): (usize, usize)| {
+        let sequential = Walker { distances, preformatted, ..Walker::new(filter, None, None) };
+        let rec = filter_aux(child, &sequential, child_index, scopes.get(idx, scope));
+        (!rec.html.is_empty()).then_some(rec)
+    };
+    match vec {
+        Cow::Borrowed(borrowed) => borrowed
+            .par_iter()
+            .zip(indices)
+            .filter_map(|(child, position)| filter_child(Cow::Borrowed(child), position))
+            .collect(),
+        Cow::Owned(owned) => owned
+            .into_vec()
+            .into_par_iter()
+            .zip(indices)
+            .filter_map(|(child, position)| filter_child(Cow::Owned(child), position))
+            .collect(),
+    }
+}
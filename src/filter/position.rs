@@ -0,0 +1,190 @@
+//! Module to restrict the matches of a filter to the tags at some positions
+//! among their siblings, see [`Filter::nth_child`].
+//!
+//! The position of a node is stored at the end of its scope, after the
+//! [`Filter::inside`] filters: it is `true` iff the node is a tag at a wanted
+//! position.
+
+use crate::{Filter, Html};
+
+/// Position of a tag among the tags of its parent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChildPosition {
+    /// `n`-th tag from the first one, starting at `1`
+    Nth(usize),
+    /// `n`-th tag from the last one, starting at `1`
+    NthLast(usize),
+}
+
+/// Scopes of the children of an [`Html::Vec`], see [`Filter::sibling_scopes`]
+pub struct SiblingScopes(Option<Vec<Vec<bool>>>);
+
+impl SiblingScopes {
+    /// Returns the scope of a child, given the scope of the vec.
+    pub fn get<'scope>(&'scope self, idx: usize, scope: &'scope [bool]) -> &'scope [bool] {
+        self.0.as_ref().and_then(|scopes| scopes.get(idx)).map_or(scope, Vec::as_slice)
+    }
+}
+
+/// Public API for [`Filter`] on the position of the wanted tags
+impl Filter {
+    /// Keeps only the tags that are the first tag of their parent.
+    ///
+    /// This is a short-hand for [`Filter::nth_child`] with `1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<ul><li>One</li><li>Two</li></ul><ol><li>Three</li></ol>").unwrap();
+    /// let filter = Filter::new().tag_name("li").first_child();
+    ///
+    /// assert_eq!(html.to_filtered(&filter), "<li>One</li><li>Three</li>");
+    /// ```
+    #[must_use]
+    pub fn first_child(self) -> Self {
+        self.nth_child(1)
+    }
+
+    /// Keeps only the tags that are the last tag of their parent.
+    ///
+    /// This is a short-hand for [`Filter::nth_last_child`] with `1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<ul><li>One</li><li>Two</li> </ul><ol><li>Three</li></ol>").unwrap();
+    /// let filter = Filter::new().tag_name("li").last_child();
+    ///
+    /// assert_eq!(html.to_filtered(&filter), "<li>Two</li><li>Three</li>");
+    /// ```
+    #[must_use]
+    pub fn last_child(self) -> Self {
+        self.nth_last_child(1)
+    }
+
+    /// Keeps only the tags that are the `n`-th tag of their parent, starting
+    /// at `1`, like the CSS `:nth-child(n)` selector.
+    ///
+    /// Only the tags are counted: the texts and the comments between them are
+    /// ignored. The tags at the root of the tree are the children of the
+    /// document. If this method is called several times, or with
+    /// [`Filter::nth_last_child`], the tags must be at all the positions.
+    ///
+    /// The positions are only known when the whole tree is traversed, like
+    /// with [`Html::filter`], [`Html::find_all`] or [`Html::select`]. The
+    /// methods that check the tags on their own, like [`Filter::profile`],
+    /// consider that they have no parent, so they don't match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     "<table><tr><td>Lamp</td><td>12$</td></tr><tr><td>Desk</td><td>80$</td></tr></table>",
+    /// )
+    /// .unwrap();
+    /// let prices = html.find_all(&Filter::new().tag_name("td").nth_child(2));
+    ///
+    /// assert_eq!(prices, [&"<td>12$</td>", &"<td>80$</td>"]);
+    /// ```
+    #[must_use]
+    pub fn nth_child(mut self, n: usize) -> Self {
+        self.positions.push(ChildPosition::Nth(n));
+        self
+    }
+
+    /// Keeps only the tags that are the `n`-th tag of their parent, counting
+    /// from the last one and starting at `1`, like the CSS
+    /// `:nth-last-child(n)` selector.
+    ///
+    /// See [`Filter::nth_child`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<tr><td>a</td><td>b</td><td>c</td></tr>").unwrap();
+    /// let filter = Filter::new().tag_name("td").nth_last_child(2);
+    ///
+    /// assert_eq!(html.to_filtered(&filter), "<td>b</td>");
+    /// ```
+    #[must_use]
+    pub fn nth_last_child(mut self, n: usize) -> Self {
+        self.positions.push(ChildPosition::NthLast(n));
+        self
+    }
+}
+
+/// Private methods for [`Filter`] on the position of the wanted tags
+impl Filter {
+    /// Checks if the `nth` tag among `count` sibling tags is at all the wanted
+    /// positions.
+    fn at_position(&self, nth: usize, count: usize) -> bool {
+        self.positions.iter().all(|position| match *position {
+            ChildPosition::Nth(wanted) => nth == wanted,
+            ChildPosition::NthLast(wanted) =>
+                count.checked_sub(nth).and_then(|after| after.checked_add(1)) == Some(wanted),
+        })
+    }
+
+    /// Returns the scope of the only child of a tag, given the scope of the
+    /// children of the tag without position, if the filter has rules on the
+    /// positions.
+    pub(super) fn only_child_scope(&self, mut scope: Vec<bool>) -> Vec<bool> {
+        if !self.positions.is_empty() {
+            scope.push(self.at_position(1, 1));
+        }
+        scope
+    }
+
+    /// Returns the number of flags of the scope of the nodes that are in
+    /// scope, see [`Filter::in_scope`].
+    pub(super) fn scope_len(&self) -> usize {
+        self.inside.len().saturating_add(usize::from(!self.positions.is_empty()))
+    }
+
+    /// Returns the scopes of the children of an [`Html::Vec`], given the
+    /// scope of the vec.
+    ///
+    /// The scopes only differ from the scope of the vec if the filter has
+    /// rules on the positions.
+    pub(super) fn sibling_scopes(&self, siblings: &[Html], scope: &[bool]) -> SiblingScopes {
+        if self.positions.is_empty() {
+            return SiblingScopes(None);
+        }
+        let tags = siblings.iter().map(|sibling| sibling.as_tag().is_some()).collect::<Vec<_>>();
+        self.tag_sibling_scopes(&tags, scope)
+    }
+
+    /// Returns the scopes of sibling nodes, given whether each one is a tag,
+    /// and the scope of their parent, see [`Filter::sibling_scopes`].
+    pub(crate) fn tag_sibling_scopes(&self, tags: &[bool], scope: &[bool]) -> SiblingScopes {
+        if self.positions.is_empty() {
+            return SiblingScopes(None);
+        }
+        let ancestors = self.inside.len();
+        let count = tags.iter().filter(|is_tag| **is_tag).count();
+        let mut nth = 0usize;
+        SiblingScopes(Some(
+            tags.iter()
+                .map(|is_tag| {
+                    let mut sibling_scope = scope.get(..ancestors).unwrap_or(scope).to_vec();
+                    sibling_scope.resize(ancestors, false);
+                    sibling_scope.push(
+                        *is_tag && {
+                            nth = nth.saturating_add(1);
+                            self.at_position(nth, count)
+                        },
+                    );
+                    sibling_scope
+                })
+                .collect(),
+        ))
+    }
+}
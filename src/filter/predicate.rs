@@ -0,0 +1,118 @@
+//! Module to match the tags with custom conditions, given as closures.
+
+use core::{fmt, ptr};
+
+use super::element::ElementState;
+use crate::{Html, Tag};
+
+/// Condition on the html node of a tag, see [`Filter::node_predicate`].
+///
+/// [`Filter::node_predicate`]: crate::Filter::node_predicate
+type NodePredicate = Box<dyn Fn(&Html) -> bool + Send + Sync>;
+
+/// Condition on a tag, see [`Filter::tag_predicate`].
+///
+/// [`Filter::tag_predicate`]: crate::Filter::tag_predicate
+type TagPredicate = Box<dyn Fn(&Tag) -> bool + Send + Sync>;
+
+/// Custom conditions that the wanted tags must all satisfy.
+///
+/// The closures can't be compared, so two sets of predicates are only equal if
+/// they are empty, or if they are the same closures.
+#[derive(Default)]
+pub struct Predicates {
+    /// Conditions on the html node of the tags, with their content
+    nodes: Vec<NodePredicate>,
+    /// Conditions on the tags, without their content
+    tags: Vec<TagPredicate>,
+}
+
+impl Predicates {
+    /// Checks if a tag satisfies the predicates.
+    pub fn check(&self, node: &Html) -> ElementState {
+        if self.is_empty() {
+            return ElementState::NotSpecified;
+        }
+        let Some((tag, _)) = node.as_tag() else { return ElementState::BlackListed };
+        if self.tags.iter().all(|predicate| predicate(tag))
+            && self.nodes.iter().all(|predicate| predicate(node))
+        {
+            ElementState::WhiteListed
+        } else {
+            ElementState::BlackListed
+        }
+    }
+
+    /// Checks if no predicates were given.
+    pub const fn is_empty(&self) -> bool {
+        self.nodes.is_empty() && self.tags.is_empty()
+    }
+
+    /// Checks if the predicates need the content of the tags to check them.
+    #[cfg(feature = "serialize")]
+    pub const fn needs_child(&self) -> bool {
+        !self.nodes.is_empty()
+    }
+
+    /// Creates an empty set of predicates.
+    pub const fn new() -> Self {
+        Self { nodes: vec![], tags: vec![] }
+    }
+
+    /// Adds a condition on the html node of the tags.
+    pub fn push_node(&mut self, predicate: NodePredicate) {
+        self.nodes.push(predicate);
+    }
+
+    /// Adds a condition on the tags.
+    pub fn push_tag(&mut self, predicate: TagPredicate) {
+        self.tags.push(predicate);
+    }
+
+    /// Describes every predicate, numbered in the order they were added, with
+    /// whether a tag satisfies it.
+    ///
+    /// The conditions on the tags come before the conditions on the nodes.
+    pub fn rule_results(&self, node: &Html) -> Vec<(String, bool)> {
+        let Some((tag, _)) = node.as_tag() else { return vec![] };
+        let tags = self
+            .tags
+            .iter()
+            .zip(1usize..)
+            .map(|(predicate, number)| (format!("tag predicate {number}"), predicate(tag)));
+        let nodes = self
+            .nodes
+            .iter()
+            .zip(1usize..)
+            .map(|(predicate, number)| (format!("node predicate {number}"), predicate(node)));
+        tags.chain(nodes).collect()
+    }
+}
+
+impl fmt::Debug for Predicates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Predicates")
+            .field("nodes", &self.nodes.len())
+            .field("tags", &self.tags.len())
+            .finish()
+    }
+}
+
+impl Eq for Predicates {}
+
+impl PartialEq for Predicates {
+    fn eq(&self, other: &Self) -> bool {
+        self.nodes.len() == other.nodes.len()
+            && self.tags.len() == other.tags.len()
+            && self
+                .nodes
+                .iter()
+                .zip(&other.nodes)
+                .all(|(lhs, rhs)| ptr::addr_eq(&raw const **lhs, &raw const **rhs))
+            && self
+                .tags
+                .iter()
+                .zip(&other.tags)
+                .all(|(lhs, rhs)| ptr::addr_eq(&raw const **lhs, &raw const **rhs))
+    }
+}
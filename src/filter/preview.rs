@@ -0,0 +1,113 @@
+//! Module to keep the first elements of a tree, to make previews.
+
+use alloc::borrow::Cow;
+
+use super::{Walker, filter_light, unwrap_vec};
+use crate::{Filter, Html};
+
+impl Html {
+    /// Keeps the first `n` elements that match the filter, with their
+    /// ancestors.
+    ///
+    /// The ancestors of the matched elements are kept to preserve the
+    /// structure of the document, but the other nodes, and all the nodes that
+    /// come after the `n`-th matched element, are removed. The content of the
+    /// matched elements is filtered according to the node types of the filter
+    /// (see [`Filter::comment`] for instance).
+    ///
+    /// This is useful to make previews of articles, e.g., to keep the 2 first
+    /// paragraphs of an article.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     "<article><h1>Title</h1><div><p>First</p><p>Second</p></div><p>Third</p></article>",
+    /// )
+    /// .unwrap();
+    ///
+    /// let filter = Filter::new().tag_name("p");
+    /// assert_eq!(html.first_n_elements(1, &filter), "<article><div><p>First</p></div></article>");
+    /// assert_eq!(
+    ///     html.first_n_elements(2, &filter),
+    ///     "<article><div><p>First</p><p>Second</p></div></article>"
+    /// );
+    /// assert_eq!(
+    ///     html.first_n_elements(5, &filter),
+    ///     "<article><div><p>First</p><p>Second</p></div><p>Third</p></article>"
+    /// );
+    /// assert_eq!(html.first_n_elements(0, &filter), Html::Empty);
+    /// ```
+    ///
+    /// ```
+    /// use html_filter::examples::ARTICLE_HTML;
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(ARTICLE_HTML).unwrap();
+    /// let preview = html.first_n_elements(2, &Filter::new().tag_name("li"));
+    /// assert_eq!(
+    ///     preview,
+    ///     r#"<html lang="en"><body><article><ul><li>Parse</li><li>Filter</li></ul></article></body></html>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn first_n_elements(&self, n: usize, filter: &Filter) -> Self {
+        let mut remaining = n;
+        self.first_n_elements_aux(&Walker::new(filter, None, None), &mut remaining, &[])
+    }
+
+    /// Wrapper for [`Html::first_n_elements`]
+    ///
+    /// `remaining` is the number of elements that can still be kept, and
+    /// `scope` is the scope of the node, see [`Filter::inside`].
+    fn first_n_elements_aux(
+        &self,
+        walker: &Walker<'_>,
+        remaining: &mut usize,
+        scope: &[bool],
+    ) -> Self {
+        if *remaining == 0 {
+            return Self::Empty;
+        }
+        match self {
+            Self::Tag { tag, child } if walker.filter.tag_allowed_in(self, scope) => {
+                *remaining = remaining.saturating_sub(1);
+                Self::Tag {
+                    tag: tag.to_owned(),
+                    child: Box::new(filter_light(Cow::Borrowed(&**child), &walker.enter(tag))),
+                }
+            }
+            Self::Tag { tag, child } => {
+                let child_scope = walker.filter.enter_scope(self, scope);
+                let kept = child.first_n_elements_aux(
+                    &walker.enter(tag),
+                    remaining,
+                    child_scope.as_deref().unwrap_or(scope),
+                );
+                if kept.is_empty() {
+                    Self::Empty
+                } else {
+                    Self::Tag { tag: tag.to_owned(), child: Box::new(kept) }
+                }
+            }
+            Self::Vec(vec) => {
+                let mut kept = vec![];
+                let scopes = walker.filter.sibling_scopes(vec, scope);
+                for (idx, child) in vec.iter().enumerate() {
+                    if *remaining == 0 {
+                        break;
+                    }
+                    let child_kept =
+                        child.first_n_elements_aux(walker, remaining, scopes.get(idx, scope));
+                    if !child_kept.is_empty() {
+                        kept.push(child_kept);
+                    }
+                }
+                unwrap_vec(kept, walker.filter.as_collapse())
+            }
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty | Self::Text(_) => Self::Empty,
+        }
+    }
+}
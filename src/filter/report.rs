@@ -0,0 +1,130 @@
+//! Module to explain why a filter didn't match any tag.
+
+use core::fmt;
+
+use crate::{Filter, Html, NodeRef};
+
+/// Maximum number of partial matches in a [`NoMatchReport`].
+const MAX_PARTIAL_MATCHES: usize = 10;
+
+/// Report on a filter that didn't match any tag, with the closest partial
+/// matches.
+///
+/// This is returned by [`Html::select_or_report`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoMatchReport<'html> {
+    /// Closest partial matches, the closest first
+    partial_matches: Vec<PartialMatch<'html>>,
+}
+
+impl<'html> NoMatchReport<'html> {
+    /// Returns the closest partial matches, the closest first.
+    ///
+    /// A partial match is a tag that satisfies some of the rules of the
+    /// filter, but not all of them. At most 10 partial matches are kept.
+    #[must_use]
+    pub fn as_partial_matches(&self) -> &[PartialMatch<'html>] {
+        &self.partial_matches
+    }
+}
+
+impl fmt::Display for NoMatchReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.partial_matches.is_empty() {
+            return f.write_str("No tag matched the filter, not even partially.");
+        }
+        f.write_str("No tag matched the filter. Closest partial matches:")?;
+        for partial in &self.partial_matches {
+            write!(f, "\n- {partial}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Tag that satisfies some of the rules of a filter, but not all of them.
+///
+/// See [`NoMatchReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialMatch<'html> {
+    /// Descriptions of the rules that the tag doesn't satisfy
+    failures: Vec<String>,
+    /// Partially matched tag
+    node: NodeRef<'html>,
+}
+
+impl<'html> PartialMatch<'html> {
+    /// Returns the descriptions of the rules that the tag doesn't satisfy.
+    #[must_use]
+    pub fn as_failures(&self) -> &[String] {
+        &self.failures
+    }
+
+    /// Returns the partially matched tag.
+    #[must_use]
+    pub const fn as_node(&self) -> &NodeRef<'html> {
+        &self.node
+    }
+}
+
+impl fmt::Display for PartialMatch<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(tag) = self.node.as_tag() {
+            write!(f, "{}: ", tag.to_opening_string())?;
+        }
+        f.write_str(&self.failures.join(", "))
+    }
+}
+
+impl Html {
+    /// Returns references to the tags that match the filter, or a report on
+    /// the closest partial matches if no tag matched.
+    ///
+    /// This is equivalent to [`Html::select`], but helps to debug the filters
+    /// that don't match anything: the report lists the tags that satisfy some
+    /// of the rules on the tags and attributes, with the rules they don't
+    /// satisfy. The tags that fail the fewest rules come first.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`NoMatchReport`] if no tag matched the filter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     r#"<div class="product"><span class="price">10</span></div><p class="price">Free</p>"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// let filter = Filter::new().tag_name("span").attribute_value("class", "cost");
+    /// let report = html.select_or_report(&filter).unwrap_err();
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     r#"No tag matched the filter. Closest partial matches:
+    /// - <span class="price">: the attribute 'class' has a wrong value"#
+    /// );
+    ///
+    /// let filter = Filter::new().tag_name("span").attribute_value("class", "price");
+    /// assert_eq!(html.select_or_report(&filter).unwrap().len(), 1);
+    /// ```
+    pub fn select_or_report(&self, filter: &Filter) -> Result<Vec<NodeRef<'_>>, NoMatchReport<'_>> {
+        let selected = self.select(filter).collect::<Vec<_>>();
+        if !selected.is_empty() {
+            return Ok(selected);
+        }
+        let mut partial_matches = vec![];
+        for node in self.select(&Filter::new()) {
+            if node.as_html().as_tag().is_some() {
+                let (failures, count) = filter.tag_failures(node.as_html());
+                if !failures.is_empty() && failures.len() < count {
+                    partial_matches.push(PartialMatch { failures, node });
+                }
+            }
+        }
+        partial_matches.sort_by_key(|partial| partial.failures.len());
+        partial_matches.truncate(MAX_PARTIAL_MATCHES);
+        Err(NoMatchReport { partial_matches })
+    }
+}
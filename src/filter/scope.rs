@@ -0,0 +1,101 @@
+//! Module to restrict the matches of a filter to the nodes whose ancestors
+//! satisfy other filters, see [`Filter::inside`].
+
+use crate::{Filter, Html};
+
+/// Public API for [`Filter`] on the ancestors of the wanted nodes
+impl Filter {
+    /// Keeps only the nodes that are inside a tag that matches another
+    /// filter.
+    ///
+    /// A node matches only if one of its ancestors, not the node itself,
+    /// matches `ancestor`. This can't be expressed with [`Filter::depth`],
+    /// which adds the ancestors of the matches to the output instead of
+    /// constraining them. Only the rules on the tags of `ancestor` are used,
+    /// not its node types, its depth or its output limits.
+    ///
+    /// If this method is called several times, every filter must be matched
+    /// by an ancestor, but not necessarily the same one.
+    ///
+    /// The ancestors are known by all the methods that traverse a tree, like
+    /// [`Html::filter`], [`Html::find_all`] or [`Html::split_at`]. The filters
+    /// that only check a tag on its own, like the ones given to
+    /// [`Filter::and`] or to [`Html::select_between`], consider that the tag
+    /// has no ancestors, so they don't match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     r#"<nav class="top"><a href="/">Home</a></nav><main><a href="/a">A</a></main>"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// let filter = Filter::new().tag_name("a").inside(Filter::new().class("top"));
+    /// assert_eq!(html.to_filtered(&filter), r#"<a href="/">Home</a>"#);
+    /// ```
+    #[must_use]
+    pub fn inside(mut self, ancestor: Self) -> Self {
+        self.inside.push(ancestor);
+        self
+    }
+
+    /// Keeps only the nodes that are inside a tag of the given name.
+    ///
+    /// This is a short-hand for [`Filter::inside`] with a filter on the
+    /// `name` of the tags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     "<nav><a>Home</a><ul><li><a>Blog</a></li></ul></nav><footer><a>Legal</a></footer>",
+    /// )
+    /// .unwrap();
+    ///
+    /// let links = html.find_all(&Filter::new().tag_name("a").inside_tag("nav"));
+    /// assert_eq!(links, [&"<a>Home</a>", &"<a>Blog</a>"]);
+    /// ```
+    #[must_use]
+    pub fn inside_tag<N: Into<String>>(self, name: N) -> Self {
+        self.inside(Self::new().tag_name(name))
+    }
+}
+
+/// Private methods for [`Filter`] on the ancestors of the wanted nodes
+///
+/// The scope of a node is the list of the [`Filter::inside`] filters, with
+/// whether an ancestor of the node satisfies them, followed by whether the
+/// node is at a wanted position if the filter has rules on the positions, see
+/// [`Filter::nth_child`]. The empty scope is the scope of a node without
+/// ancestors.
+impl Filter {
+    /// Returns the scope of the children of a tag, if it differs from the
+    /// scope of the tag.
+    pub(crate) fn enter_scope(&self, node: &Html, scope: &[bool]) -> Option<Vec<bool>> {
+        if self.scope_len() == 0 || node.as_tag().is_none() {
+            return None;
+        }
+        let entered = self.only_child_scope(
+            self.inside
+                .iter()
+                .enumerate()
+                .map(|(idx, ancestor)| {
+                    scope.get(idx).copied().unwrap_or(false) || ancestor.tag_allowed(node)
+                })
+                .collect(),
+        );
+        (entered.as_slice() != scope).then_some(entered)
+    }
+
+    /// Checks if the ancestors of a node satisfy all the [`Filter::inside`]
+    /// filters, and if the node is at a wanted position, given the scope of
+    /// the node.
+    pub(super) fn in_scope(&self, scope: &[bool]) -> bool {
+        scope.len() == self.scope_len() && scope.iter().all(|satisfied| *satisfied)
+    }
+}
@@ -0,0 +1,148 @@
+//! Module to score the tags of a tree with weighted rules, and to select the
+//! best candidate.
+
+use core::ptr;
+
+use crate::{Filter, Html, NodeRef};
+
+/// Weighted rules to score the tags of a tree.
+///
+/// Each tag gets the weights of all the rules it matches, and the text weight
+/// for each word it contains. The weights can be negative, to penalise some
+/// tags. This is useful when multiple candidates match and heuristics must
+/// pick one, with [`Html::best_match`].
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// let scored = ScoredFilter::new()
+///     .rule(Filter::new().tag_name("article"), 10)
+///     .rule(Filter::new().attribute_value_contains("class", "content"), 5)
+///     .rule(Filter::new().attribute_value_contains("class", "ad"), -20)
+///     .text_weight(1);
+/// assert_eq!(scored.len(), 3);
+/// ```
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ScoredFilter {
+    /// Rules and their weights
+    rules: Vec<(Filter, i64)>,
+    /// Weight of each word of the text content of the tags
+    text_weight: i64,
+}
+
+impl ScoredFilter {
+    /// Checks if the scored filter doesn't have any rules.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Returns the number of rules.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Creates a scored filter without rules.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { rules: vec![], text_weight: 0 }
+    }
+
+    /// Adds a rule: the tags that match the filter get the weight.
+    ///
+    /// Only the rules on the tags of the filter are used, e.g.
+    /// [`Filter::tag_name`], [`Filter::attribute_value`] or
+    /// [`Filter::inside`].
+    #[must_use]
+    pub fn rule(mut self, filter: Filter, weight: i64) -> Self {
+        self.rules.push((filter, weight));
+        self
+    }
+
+    /// Computes the score of a tag, given the weights of the rules it
+    /// matches, or `None` if it doesn't match any rule.
+    fn score(&self, node: &Html, weights: &[i64]) -> Option<i64> {
+        let (_, child) = node.as_tag()?;
+        (!weights.is_empty() || self.rules.is_empty()).then(|| {
+            let score = weights.iter().fold(0, |score: i64, weight| score.saturating_add(*weight));
+            let words = i64::try_from(count_words(child)).unwrap_or(i64::MAX);
+            score.saturating_add(words.saturating_mul(self.text_weight))
+        })
+    }
+
+    /// Sets the weight of each word of the text content of the tags.
+    ///
+    /// The text content of a tag contains the texts of all its descendants,
+    /// so this favours the tags with more text.
+    #[must_use]
+    pub const fn text_weight(mut self, weight: i64) -> Self {
+        self.text_weight = weight;
+        self
+    }
+}
+
+impl Html {
+    /// Returns the tag with the highest score, with its score.
+    ///
+    /// The candidates are the tags that match at least one rule of the scored
+    /// filter, or all the tags if it has no rules. When multiple candidates
+    /// have the same score, the first one in the document is returned. See
+    /// [`ScoredFilter`] to define the rules.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     r#"<div class="content ad">Buy now</div><div class="content"><p>The real article, with more words.</p></div><div>Footer</div>"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// let scored = ScoredFilter::new()
+    ///     .rule(Filter::new().attribute_value_contains("class", "content"), 5)
+    ///     .rule(Filter::new().attribute_value_contains("class", "ad"), -20)
+    ///     .text_weight(1);
+    ///
+    /// let (best, score) = html.best_match(&scored).unwrap();
+    /// assert_eq!(score, 11);
+    /// assert_eq!(best.as_html(), &r#"<div class="content"><p>The real article, with more words.</p></div>"#);
+    /// ```
+    #[must_use]
+    pub fn best_match(&self, scored: &ScoredFilter) -> Option<(NodeRef<'_>, i64)> {
+        let mut best: Option<(NodeRef<'_>, i64)> = None;
+        let mut rules = scored
+            .rules
+            .iter()
+            .map(|(filter, weight)| (self.select(filter).peekable(), *weight))
+            .collect::<Vec<_>>();
+        for node in self.select(&Filter::new()) {
+            let weights = rules
+                .iter_mut()
+                .filter_map(|(matches, weight)| {
+                    matches.next_if(|found| ptr::eq(found.as_html(), node.as_html()))?;
+                    Some(*weight)
+                })
+                .collect::<Vec<_>>();
+            if let Some(score) = scored.score(node.as_html(), &weights)
+                && best.as_ref().is_none_or(|(_, best_score)| score > *best_score)
+            {
+                best = Some((node, score));
+            }
+        }
+        best
+    }
+}
+
+/// Counts the words of the texts of a tree.
+fn count_words(html: &Html) -> usize {
+    match html {
+        Html::Text(text) => text.split_whitespace().count(),
+        Html::Tag { child, .. } => count_words(child),
+        Html::Vec(vec) => vec.iter().map(count_words).fold(0, usize::saturating_add),
+        Html::Comment(_) | Html::Doctype { .. } | Html::Empty => 0,
+    }
+}
@@ -0,0 +1,87 @@
+//! Module to convert the CSS selectors given by the bindings into filters.
+
+use crate::Filter;
+
+/// Adds the condition of an `[attr=value]` selector to a filter.
+///
+/// `lhs` is the part before the `=`, i.e., the name of the attribute,
+/// followed by the operator if there is one, like `href^`.
+///
+/// # Returns
+///
+/// `None` if the name of the attribute or the operator is invalid.
+fn attribute_condition(filter: Filter, lhs: &str, value: &str) -> Option<Filter> {
+    let (name, operator) =
+        lhs.strip_suffix(['~', '^', '$', '*']).map_or((lhs, ""), |name| lhs.split_at(name.len()));
+    if !is_identifier(name) {
+        return None;
+    }
+    match operator {
+        "" => Some(filter.attribute_value(name, value)),
+        "~" => Some(filter.attribute_value_contains(name, value)),
+        "^" => Some(filter.attribute_value_starts_with(name, value)),
+        "$" => Some(filter.attribute_value_ends_with(name, value)),
+        "*" => Some(filter.attribute_value_substring(name, value)),
+        _ => None,
+    }
+}
+
+/// Checks if a name of a selector is valid, i.e., is not empty and doesn't
+/// contain any special character.
+fn is_identifier(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|ch| ch.is_alphanumeric() || matches!(ch, '-' | '_' | ':'))
+}
+
+/// Converts a CSS compound selector into a [`Filter`].
+///
+/// The selector is a tag name, or `*`, followed by any number of `#id`,
+/// `.class`, `[attr]` and `[attr=value]` conditions, like `a.external[href]`.
+/// The `~=`, `^=`, `$=` and `*=` operators are also supported in the attribute
+/// conditions. Combinators and lists of selectors aren't supported.
+///
+/// # Returns
+///
+/// `None` if the selector is invalid.
+pub fn parse_selector(selector: &str) -> Option<Filter> {
+    let trimmed = selector.trim();
+    let (name, mut rest) = trimmed.split_at(trimmed.find(['#', '.', '[']).unwrap_or(trimmed.len()));
+    let mut filter = match name {
+        "" | "*" => Filter::new(),
+        _ if is_identifier(name) => Filter::new().tag_name(name),
+        _ => return None,
+    };
+    while !rest.is_empty() {
+        if let Some(attr) = rest.strip_prefix('[') {
+            let (condition, after) = attr.split_once(']')?;
+            filter = match condition.split_once('=') {
+                Some((lhs, value)) => attribute_condition(filter, lhs, unquote(value))?,
+                None if is_identifier(condition) => filter.attribute_present(condition),
+                None => return None,
+            };
+            rest = after;
+        } else {
+            let (prefix, after) = rest.split_at_checked(1)?;
+            let end = after.find(['#', '.', '[']).unwrap_or(after.len());
+            let (value, next) = after.split_at(end);
+            if !is_identifier(value) {
+                return None;
+            }
+            filter = if prefix == "#" {
+                filter.id(value)
+            } else {
+                filter.attribute_value_contains("class", value)
+            };
+            rest = next;
+        }
+    }
+    Some(filter)
+}
+
+/// Removes the quotes around the value of an attribute of a selector, if it
+/// has any.
+fn unquote(value: &str) -> &str {
+    ['"', '\'']
+        .into_iter()
+        .find_map(|quote| value.strip_prefix(quote)?.strip_suffix(quote))
+        .unwrap_or(value)
+}
@@ -0,0 +1,82 @@
+//! Module to split a tree in two parts, around a marker node.
+
+use super::unwrap_vec;
+use crate::{Filter, Html};
+
+impl Html {
+    /// Splits the tree in two parts: the nodes before the first tag that
+    /// matches the filter, and the nodes after it.
+    ///
+    /// The matched tag, used as a marker, is in neither of the parts. The
+    /// ancestors of the marker are duplicated in both parts, so that each
+    /// part keeps the structure of the document. If no tag matches the
+    /// filter, the whole tree is in the first part, and the second one is
+    /// empty.
+    ///
+    /// This is useful to keep the content above a fold marker, or to paginate
+    /// a document with page-break markers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     r#"<article><h1>Title</h1><p>Intro</p><hr class="fold" /><p>Rest</p></article><footer />"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// let (before, after) = html.split_at(&Filter::new().attribute_value("class", "fold"));
+    /// assert_eq!(before, "<article><h1>Title</h1><p>Intro</p></article>");
+    /// assert_eq!(after, "<article><p>Rest</p></article><footer></footer>");
+    ///
+    /// let (before, after) = html.split_at(&Filter::new().tag_name("nav"));
+    /// assert_eq!(before, html);
+    /// assert_eq!(after, Html::Empty);
+    /// ```
+    #[must_use]
+    pub fn split_at(&self, filter: &Filter) -> (Self, Self) {
+        self.split_at_aux(filter, &[]).unwrap_or_else(|| (self.clone(), Self::Empty))
+    }
+
+    /// Wrapper for [`Html::split_at`]
+    ///
+    /// `scope` is the scope of the node, see [`Filter::inside`]. Returns
+    /// `None` if no tag matched the filter in this node.
+    fn split_at_aux(&self, filter: &Filter, scope: &[bool]) -> Option<(Self, Self)> {
+        match self {
+            Self::Tag { .. } if filter.tag_allowed_in(self, scope) =>
+                Some((Self::Empty, Self::Empty)),
+            Self::Tag { tag, child } => {
+                let child_scope = filter.enter_scope(self, scope);
+                let split = child.split_at_aux(filter, child_scope.as_deref().unwrap_or(scope));
+                split.map(|(before, after)| {
+                    (Self::Tag { tag: tag.clone(), child: Box::new(before) }, Self::Tag {
+                        tag: tag.clone(),
+                        child: Box::new(after),
+                    })
+                })
+            }
+            Self::Vec(vec) => {
+                let scopes = filter.sibling_scopes(vec, scope);
+                vec.iter().enumerate().find_map(|(idx, child)| {
+                    child.split_at_aux(filter, scopes.get(idx, scope)).map(|(before, after)| {
+                        let mut before_vec = vec.get(..idx).unwrap_or_default().to_vec();
+                        before_vec.push(before);
+                        let mut after_vec = vec![after];
+                        after_vec.extend_from_slice(
+                            vec.get(idx.saturating_add(1)..).unwrap_or_default(),
+                        );
+                        (clean_vec(before_vec), clean_vec(after_vec))
+                    })
+                })
+            }
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty | Self::Text(_) => None,
+        }
+    }
+}
+
+/// Removes the empty nodes of a part, and unwraps it if possible.
+fn clean_vec(vec: Vec<Html>) -> Html {
+    unwrap_vec(vec.into_iter().filter(|node| !node.is_empty()).collect(), false)
+}
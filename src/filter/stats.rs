@@ -0,0 +1,157 @@
+//! Module to count how many tags each rule of a filter accepted and rejected.
+
+use core::fmt;
+
+use crate::{Filter, Html};
+
+/// Number of tags that a rule of a filter accepted and rejected.
+///
+/// See [`RuleStats`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleStat {
+    /// Number of tags that satisfy the rule
+    accepted: usize,
+    /// Description of the rule
+    description: String,
+    /// Number of tags that don't satisfy the rule
+    rejected: usize,
+}
+
+impl RuleStat {
+    /// Returns the number of tags that satisfy the rule.
+    #[must_use]
+    pub const fn accepted(&self) -> usize {
+        self.accepted
+    }
+
+    /// Returns the description of the rule, like `attribute 'class' is
+    /// 'price'`.
+    #[must_use]
+    pub const fn as_description(&self) -> &str {
+        self.description.as_str()
+    }
+
+    /// Returns the number of tags that don't satisfy the rule.
+    #[must_use]
+    pub const fn rejected(&self) -> usize {
+        self.rejected
+    }
+}
+
+impl fmt::Display for RuleStat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} accepted, {} rejected", self.description, self.accepted, self.rejected)
+    }
+}
+
+/// Statistics on the rules of a filter, with the number of tags that each
+/// individual rule accepted and rejected.
+///
+/// This is returned by [`Filter::profile`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RuleStats {
+    /// Number of tags that satisfy all the rules
+    matched: usize,
+    /// Statistics of every rule, in the order in which they are checked
+    rules: Vec<RuleStat>,
+    /// Number of tags of the tree
+    tags: usize,
+}
+
+impl RuleStats {
+    /// Returns the statistics of every rule on the tags.
+    ///
+    /// The rules are in the order in which they are checked: the tag names,
+    /// the attributes, the structure, the text content, the predicates, then
+    /// the combined filters. The rules are only listed if the tree has tags.
+    #[must_use]
+    pub fn as_rules(&self) -> &[RuleStat] {
+        &self.rules
+    }
+
+    /// Returns the number of tags that satisfy all the rules.
+    #[must_use]
+    pub const fn matched(&self) -> usize {
+        self.matched
+    }
+
+    /// Returns the rule that rejected the most tags, the first one in case of
+    /// equality, or `None` if no rule rejected any tag.
+    #[must_use]
+    pub fn most_rejecting(&self) -> Option<&RuleStat> {
+        self.rules.iter().filter(|rule| rule.rejected > 0).rev().max_by_key(|rule| rule.rejected)
+    }
+
+    /// Returns the number of tags of the tree.
+    #[must_use]
+    pub const fn tags(&self) -> usize {
+        self.tags
+    }
+}
+
+impl fmt::Display for RuleStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} of {} tags matched the filter.", self.matched, self.tags)?;
+        for rule in &self.rules {
+            write!(f, "\n- {rule}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Filter {
+    /// Counts how many tags of a tree each individual rule of the filter
+    /// accepted and rejected.
+    ///
+    /// This helps to find the rule of a complex filter that eliminates every
+    /// tag, and to tune the filters on real documents. Every tag is checked
+    /// against every rule on the tags: the name, each attribute, the
+    /// structure, each text content and each predicate. The rules on the
+    /// other nodes, like [`Filter::comment`], and the [`Filter::depth`] are
+    /// ignored. The rules on the ancestors and on the positions, like
+    /// [`Filter::inside`], are only used to count the matched tags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html =
+    ///     Html::parse(r#"<div class="product"><span class="price">10</span><span>Sale</span></div>"#)
+    ///         .unwrap();
+    /// let filter = Filter::new().tag_name("span").attribute_value("class", "cost");
+    ///
+    /// let stats = filter.profile(&html);
+    /// assert_eq!(stats.matched(), 0);
+    /// assert_eq!(
+    ///     stats.to_string(),
+    ///     "0 of 3 tags matched the filter.
+    /// - tag name 'span': 2 accepted, 1 rejected
+    /// - attribute 'class' is 'cost': 0 accepted, 3 rejected"
+    /// );
+    /// assert_eq!(
+    ///     stats.most_rejecting().map(RuleStat::as_description),
+    ///     Some("attribute 'class' is 'cost'")
+    /// );
+    /// ```
+    #[must_use]
+    pub fn profile(&self, html: &Html) -> RuleStats {
+        let mut stats = RuleStats { matched: html.select(self).count(), ..RuleStats::default() };
+        for node in html.iter().filter(|node| node.as_tag().is_some()) {
+            stats.tags = stats.tags.saturating_add(1);
+            for (idx, (description, accepted)) in self.tag_rules(node).into_iter().enumerate() {
+                if stats.rules.len() <= idx {
+                    stats.rules.push(RuleStat { accepted: 0, description, rejected: 0 });
+                }
+                if let Some(rule) = stats.rules.get_mut(idx) {
+                    if accepted {
+                        rule.accepted = rule.accepted.saturating_add(1);
+                    } else {
+                        rule.rejected = rule.rejected.saturating_add(1);
+                    }
+                }
+            }
+        }
+        stats
+    }
+}
@@ -0,0 +1,107 @@
+//! Module to match the tags by the text they contain.
+
+use super::element::ElementState;
+use crate::Html;
+use crate::transform::collapse_whitespace;
+
+/// Rules on the text content of the wanted tags.
+///
+/// See [`Filter::text_contains`](crate::Filter::text_contains) and
+/// [`Filter::text_equals`](crate::Filter::text_equals).
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct TextContent {
+    /// Rules that the text content must all satisfy, with their whitespace
+    /// collapsed
+    rules: Vec<TextMatch>,
+}
+
+impl TextContent {
+    /// Checks if the text content of a tag satisfies the rules.
+    pub fn check(&self, child: &Html) -> ElementState {
+        if self.rules.is_empty() {
+            return ElementState::NotSpecified;
+        }
+        let mut content = String::new();
+        push_text_content(child, &mut content);
+        let collapsed = collapse_whitespace(content.trim_ascii());
+        if self.rules.iter().all(|rule| rule.matches(&collapsed)) {
+            ElementState::WhiteListed
+        } else {
+            ElementState::BlackListed
+        }
+    }
+
+    /// Checks if no rules were given.
+    pub const fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Creates an empty set of rules.
+    pub const fn new() -> Self {
+        Self { rules: vec![] }
+    }
+
+    /// Adds a rule on the text content.
+    pub fn push(&mut self, rule: TextMatch) {
+        self.rules.push(match rule {
+            TextMatch::Contains(text) =>
+                TextMatch::Contains(collapse_whitespace(text.trim_ascii()).into_owned()),
+            TextMatch::Equals(text) =>
+                TextMatch::Equals(collapse_whitespace(text.trim_ascii()).into_owned()),
+        });
+    }
+
+    /// Describes every rule on the text content, with whether the text
+    /// content of a tag satisfies it.
+    pub fn rule_results(&self, child: &Html) -> Vec<(String, bool)> {
+        if self.rules.is_empty() {
+            return vec![];
+        }
+        let mut content = String::new();
+        push_text_content(child, &mut content);
+        let collapsed = collapse_whitespace(content.trim_ascii());
+        self.rules
+            .iter()
+            .map(|rule| {
+                let description = match rule {
+                    TextMatch::Contains(text) => format!("text contains '{text}'"),
+                    TextMatch::Equals(text) => format!("text equals '{text}'"),
+                };
+                (description, rule.matches(&collapsed))
+            })
+            .collect()
+    }
+}
+
+/// Rule on the text content of a tag.
+#[derive(Debug, PartialEq, Eq)]
+pub enum TextMatch {
+    /// The text content must contain the given string.
+    Contains(String),
+    /// The text content must be exactly the given string.
+    Equals(String),
+}
+
+impl TextMatch {
+    /// Checks if a text content, with its whitespace collapsed, satisfies the
+    /// rule.
+    fn matches(&self, content: &str) -> bool {
+        match self {
+            Self::Contains(text) => content.contains(text.as_str()),
+            Self::Equals(text) => content == text,
+        }
+    }
+}
+
+/// Appends the texts of a node and of its descendants, in the order of the
+/// document.
+///
+/// The comments and the doctypes are ignored.
+fn push_text_content(node: &Html, content: &mut String) {
+    match node {
+        Html::Text(text) => content.push_str(text),
+        Html::Tag { child, .. } => push_text_content(child, content),
+        Html::Vec(vec) => vec.iter().for_each(|child| push_text_content(child, content)),
+        Html::Comment(_) | Html::Doctype { .. } | Html::Empty => (),
+    }
+}
@@ -1,7 +1,14 @@
 //! Module to define structs to filter
 
 use super::NodeTypeFilter;
-use super::element::{BlackWhiteList, ValueAssociateHash};
+use super::combine::Combined;
+use super::element::{BlackWhiteList, ElementState, ValueAssociateHash};
+use super::like::Like;
+use super::node_type::TrimMode;
+use super::position::ChildPosition;
+use super::predicate::Predicates;
+use super::text::TextContent;
+use crate::types::html::Html;
 use crate::types::tag::Tag;
 use crate::unwrap_or;
 
@@ -32,6 +39,28 @@ use crate::unwrap_or;
 /// Filter::new().none_except_text().collapse().trim().no_tags(); // Returns text without padding
 ///                                                               // between tags and in one Html::Text
 /// ```
+///
+/// # Precedence
+///
+/// An empty filter keeps the whole tree. Then, the rules are applied as
+/// follows:
+///
+/// - The node types are set with [`Self::all`], [`Self::keep_types`] and
+///   [`Self::drop_types`] for all the types at once, or with [`Self::comment`],
+///   [`Self::doctype`] and [`Self::text`] for a single type. For every type,
+///   the last call wins, so the calls that set all the types are a base state,
+///   that the next calls override.
+/// - The types that weren't set have a default: the texts and the comments are
+///   kept inside the wanted tags, and the doctypes are removed. Outside of the
+///   wanted tags, they are only kept by a filter without rules on the tags.
+/// - The tags are all kept until a rule on the tags is given, like
+///   [`Self::tag_name`] or [`Self::attribute_name`]. A tag is then wanted if it
+///   satisfies all the rules: the names given to [`Self::tag_name`] are
+///   alternatives, and the rules that start with `except_` win over the others.
+///   [`Self::no_tags`] removes every tag that isn't explicitly wanted, and
+///   [`Self::or`] adds alternatives to all the rules at once.
+/// - The [`Self::depth`] adds the ancestors of the wanted tags, and the output
+///   limits, like [`Self::max_output_nodes`], are applied last.
 #[derive(Default, Debug, PartialEq, Eq)]
 #[expect(clippy::field_scoped_visibility_modifiers, reason = "useless")]
 pub struct Filter {
@@ -42,6 +71,10 @@ pub struct Filter {
     ///
     /// This includes attributes with or without values.
     pub(super) attrs: ValueAssociateHash,
+    /// Filters combined with the rules on the tags
+    ///
+    /// See [`Filter::and`], [`Filter::not`] and [`Filter::or`].
+    pub(super) combined: Combined,
     /// Depth in which to embed the required nodes
     ///
     /// # Examples
@@ -61,6 +94,34 @@ pub struct Filter {
     /// - If the depth were `2`, the output would have been the whole the `ul`
     ///   tag.
     pub(super) depth: usize,
+    /// Filters that the ancestors of the wanted nodes must match
+    ///
+    /// See [`Filter::inside`].
+    pub(super) inside: Vec<Self>,
+    /// Example of the structure of the wanted tags
+    ///
+    /// See [`Filter::like`].
+    pub(super) like: Option<Like>,
+    /// Maximum number of results, after which the traversal stops
+    ///
+    /// See [`Filter::limit`].
+    pub(super) limit: Option<usize>,
+    /// Maximum size of the serialised output, in bytes
+    ///
+    /// See [`Filter::max_output_bytes`].
+    pub(super) max_output_bytes: Option<usize>,
+    /// Maximum number of nodes in the output
+    ///
+    /// See [`Filter::max_output_nodes`].
+    pub(super) max_output_nodes: Option<usize>,
+    /// Positions of the wanted tags among their siblings
+    ///
+    /// See [`Filter::nth_child`].
+    pub(super) positions: Vec<ChildPosition>,
+    /// Custom conditions on the wanted tags
+    ///
+    /// See [`Filter::node_predicate`] and [`Filter::tag_predicate`].
+    pub(super) predicates: Predicates,
     /// Html tags
     ///
     /// This contains the list of tags that ought to be kept in the final html
@@ -70,6 +131,10 @@ pub struct Filter {
     ///
     /// `<a href="link" />`
     pub(super) tags: BlackWhiteList,
+    /// Rules on the text content of the wanted tags
+    ///
+    /// See [`Filter::text_contains`] and [`Filter::text_equals`].
+    pub(super) text_content: TextContent,
     /// Filter by type of html node.
     pub(super) types: NodeTypeFilter,
 }
@@ -86,6 +151,11 @@ impl Filter {
         self.depth
     }
 
+    /// Returns how the texts should be trimmed, see [`Filter::trim_text`].
+    pub(super) const fn as_trim(&self) -> TrimMode {
+        self.types.as_trim()
+    }
+
     /// Checks if comments must be kept according to the filter.
     pub(super) const fn comment_allowed(&self) -> bool {
         unwrap_or(self.types.comment_allowed(), true)
@@ -103,26 +173,107 @@ impl Filter {
 
     /// Checks if no rules were given concerning tags and attributes
     const fn is_empty(&self) -> bool {
-        self.tags.is_empty() && self.attrs.is_empty()
+        self.tags.is_empty()
+            && self.attrs.is_empty()
+            && self.like.is_none()
+            && self.text_content.is_empty()
+            && self.predicates.is_empty()
+            && self.combined.is_empty()
+    }
+
+    /// Keeps the first results of the output that fit in the output limits.
+    ///
+    /// `max_nodes` is the maximum number of results, see
+    /// [`Filter::output_limit`], and the size of the results is bounded by
+    /// [`Filter::max_output_bytes`].
+    pub(super) fn limit_output(&self, html: Html, max_nodes: Option<usize>) -> Html {
+        if self.max_output_bytes.is_none() && max_nodes.is_none() {
+            return html;
+        }
+        let mut results = vec![];
+        push_results(html, &mut results);
+        let mut bytes: usize = 0;
+        let mut kept = vec![];
+        for result in results {
+            if let Some(max) = self.max_output_bytes {
+                bytes = bytes.saturating_add(result.to_string().len());
+                if bytes > max {
+                    break;
+                }
+            }
+            if max_nodes.is_some_and(|max| kept.len() >= max) {
+                break;
+            }
+            kept.push(result);
+        }
+        if kept.len() <= 1 {
+            kept.pop().unwrap_or_default()
+        } else {
+            Html::Vec(kept.into_boxed_slice())
+        }
+    }
+
+    /// Checks if the filter needs the content of the tags to check them.
+    #[cfg(feature = "serialize")]
+    pub(crate) const fn needs_child(&self) -> bool {
+        self.like.is_some()
+            || !self.text_content.is_empty()
+            || self.predicates.needs_child()
+            || !self.combined.is_empty()
+    }
+
+    /// Checks if a node is a match of the filter, i.e., if it would be kept
+    /// on its own by the filter, and not only because it is in a matched tag.
+    ///
+    /// The whitespace texts don't match when the texts are trimmed. `scope`
+    /// is the scope of the node, i.e., the [`Filter::inside`] filters that its
+    /// ancestors satisfy.
+    pub(super) fn node_matches(&self, node: &Html, scope: &[bool]) -> bool {
+        self.in_scope(scope)
+            && match node {
+                Html::Comment(_) => self.comment_explicitly_allowed(),
+                Html::Doctype { .. } => self.doctype_allowed(),
+                Html::Tag { .. } => self.tag_state(node).is_allowed_or(self.is_empty()),
+                Html::Text(text) =>
+                    self.text_explicitly_allowed()
+                        && !(self.should_trim() && text.trim().is_empty()),
+                Html::Empty | Html::Vec(_) => false,
+            }
+    }
+
+    /// Returns the maximum number of results of the output, given the limit
+    /// requested by the caller, like `1` for [`Html::find`].
+    ///
+    /// See [`Filter::limit`] and [`Filter::max_output_nodes`].
+    pub(super) fn output_limit(&self, requested: Option<usize>) -> Option<usize> {
+        [requested, self.limit, self.max_output_nodes].into_iter().flatten().min()
     }
 
     /// Checks if texts should be trimmed, and removed if empty.
+    ///
+    /// See [`Filter::trim_text`].
     pub(super) const fn should_trim(&self) -> bool {
         self.types.should_trim()
     }
 
     /// Checks if a given tag must be kept according to the filter
-    pub(super) fn tag_allowed(&self, tag: &Tag) -> bool {
-        let name_allowed = self.tags.check(tag.as_name());
-        let attrs_allowed = self.attrs.check(tag.as_attrs());
-        name_allowed.and(&attrs_allowed).is_allowed_or(self.is_empty())
+    ///
+    /// The nodes that aren't tags are never allowed. The tag is considered
+    /// without ancestors, see [`Self::tag_allowed_in`].
+    pub(crate) fn tag_allowed(&self, node: &Html) -> bool {
+        self.tag_allowed_in(node, &[])
     }
 
-    /// Checks if a given tag has an explicit rule, rule to keep this tag
-    pub(super) fn tag_explicitly_allowed(&self, tag: &Tag) -> bool {
-        let name_allowed = self.tags.check(tag.as_name());
-        let attrs_allowed = self.attrs.check(tag.as_attrs());
-        name_allowed.and(&attrs_allowed).is_allowed_or(false)
+    /// Checks if a given tag must be kept according to the filter, given its
+    /// scope, i.e., the [`Filter::inside`] filters that its ancestors satisfy.
+    pub(crate) fn tag_allowed_in(&self, node: &Html, scope: &[bool]) -> bool {
+        self.in_scope(scope) && self.tag_state(node).is_allowed_or(self.is_empty())
+    }
+
+    /// Checks if a given tag has an explicit rule, rule to keep this tag,
+    /// given its scope.
+    pub(super) fn tag_explicitly_allowed(&self, node: &Html, scope: &[bool]) -> bool {
+        self.in_scope(scope) && self.tag_state(node).is_allowed_or(false)
     }
 
     /// Checks if a given tag has an explicit rule, rule to keep this tag
@@ -131,6 +282,84 @@ impl Filter {
             || self.attrs.is_explicitly_blacklisted(tag.as_attrs())
     }
 
+    /// Describes the rules on the tags that a tag doesn't satisfy.
+    ///
+    /// # Returns
+    ///
+    /// The descriptions of the failed rules, and the total number of rules.
+    pub(super) fn tag_failures(&self, node: &Html) -> (Vec<String>, usize) {
+        let Some((tag, child)) = node.as_tag() else { return (vec![], 0) };
+        let mut failures = self.attrs.failures(tag.as_attrs());
+        let mut count = self.attrs.len();
+        let like_state =
+            self.like.as_ref().map_or(ElementState::NotSpecified, |like| like.check(tag, child));
+        for (state, failure) in [
+            (
+                self.tags.check(tag.as_name()),
+                format!("the tag name '{}' is unwanted", tag.as_name()),
+            ),
+            (like_state, "the structure isn't similar enough to the example".to_owned()),
+            (self.text_content.check(child), "the text content doesn't match".to_owned()),
+            (self.predicates.check(node), "a predicate isn't satisfied".to_owned()),
+            (self.combined.check(node), "a combined filter isn't satisfied".to_owned()),
+        ] {
+            match state {
+                ElementState::BlackListed => {
+                    failures.push(failure);
+                    count = count.saturating_add(1);
+                }
+                ElementState::WhiteListed => count = count.saturating_add(1),
+                ElementState::NotSpecified => (),
+            }
+        }
+        (failures, count)
+    }
+
+    /// Describes every rule on the tags, with whether a tag satisfies it.
+    ///
+    /// The rules are in the order in which they are checked: the name, the
+    /// attributes, the structure, the text content, the predicates, then the
+    /// combined filters. The nodes that aren't tags satisfy no rule.
+    pub(super) fn tag_rules(&self, node: &Html) -> Vec<(String, bool)> {
+        let Some((tag, child)) = node.as_tag() else { return vec![] };
+        let mut results = vec![];
+        if self.tags.has_rules() {
+            let state = self.tags.check(tag.as_name());
+            results.push((self.tags.description(), state.is_allowed_or(true)));
+        }
+        results.extend(self.attrs.rule_results(tag.as_attrs()));
+        if let Some(like) = &self.like {
+            let state = like.check(tag, child);
+            results.push(("structure like the example".to_owned(), state.is_allowed_or(true)));
+        }
+        results.extend(self.text_content.rule_results(child));
+        results.extend(self.predicates.rule_results(node));
+        results.extend(self.combined.rule_results(node));
+        results
+    }
+
+    /// Combines the rules on the name, the attributes, the structure, the
+    /// text content and the predicates of a tag, with the combined filters.
+    ///
+    /// The nodes that aren't tags are blacklisted.
+    fn tag_state(&self, node: &Html) -> ElementState {
+        let Some((tag, child)) = node.as_tag() else { return ElementState::BlackListed };
+        let name_allowed = self.tags.check(tag.as_name());
+        let attrs_allowed = self.attrs.check(tag.as_attrs());
+        let like_allowed =
+            self.like.as_ref().map_or(ElementState::NotSpecified, |like| like.check(tag, child));
+        let text_allowed = self.text_content.check(child);
+        let predicates_allowed = self.predicates.check(node);
+        let combined_allowed = self.combined.check(node);
+        let state = name_allowed
+            .and(&attrs_allowed)
+            .and(&like_allowed)
+            .and(&text_allowed)
+            .and(&predicates_allowed)
+            .and(&combined_allowed);
+        self.combined.either(node, state)
+    }
+
     /// Checks if texts must be kept according to the filter
     pub(super) const fn text_allowed(&self) -> bool {
         unwrap_or(self.types.text_allowed(), true)
@@ -141,3 +370,16 @@ impl Filter {
         unwrap_or(self.types.text_allowed(), self.is_empty())
     }
 }
+
+/// Pushes the results of a filtered tree, after unwrapping the vecs.
+fn push_results(html: Html, results: &mut Vec<Html>) {
+    match html {
+        Html::Vec(vec) =>
+            for child in vec {
+                push_results(child, results);
+            },
+        Html::Empty => (),
+        Html::Comment(_) | Html::Doctype { .. } | Html::Tag { .. } | Html::Text(_) =>
+            results.push(html),
+    }
+}
@@ -1,15 +1,75 @@
 #![doc = include_str!("../README.md")]
 
+extern crate alloc;
+
+pub mod audit;
+mod budget;
+mod control;
+mod convert;
+#[cfg(feature = "filter")]
+pub mod debug;
+mod entities;
 mod errors;
+pub mod examples;
+#[cfg(feature = "extract")]
+pub mod extract;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "filter")]
 mod filter;
 mod parse;
+pub mod patch;
+pub mod prelude;
+#[cfg(feature = "pyo3")]
+pub mod python;
+mod transform;
 mod types;
 
+pub use crate::budget::{Budget, BudgetExceeded};
+pub use crate::control::ControlPolicy;
+pub use crate::entities::{decode_entities, encode_entities};
+#[cfg(feature = "filter")]
+pub use crate::filter::diff::{FilterDiff, filter_diff};
+#[cfg(feature = "filter")]
+pub use crate::filter::node_ref::{Ancestors, Direction, Matches, NodeRef};
+#[cfg(feature = "filter")]
+pub use crate::filter::node_type::{NodeTypes, TrimMode};
+#[cfg(feature = "filter")]
+pub use crate::filter::report::{NoMatchReport, PartialMatch};
+#[cfg(feature = "filter")]
+pub use crate::filter::scored::ScoredFilter;
+#[cfg(feature = "filter")]
+pub use crate::filter::stats::{RuleStat, RuleStats};
+#[cfg(feature = "filter")]
 pub use crate::filter::types::Filter;
+pub use crate::parse::classify::{TokenClass, classify_tokens};
+pub use crate::parse::diagnostics::{Diagnostic, DiagnosticKind};
+pub use crate::parse::error::{ParseError, ParseErrorKind};
+pub use crate::parse::options::{AttributeEscaping, AttributeOverflow, LegacyTags, ParseOptions};
+pub use crate::parse::stream::Parser;
+pub use crate::parse::tokenizer::{Token, TokenKind, Tokenizer};
+pub use crate::transform::{
+    ChangeLog, DefaultAttributes, Edit, Pipeline, SanitizePolicy, VoidPolicy
+};
+pub use crate::types::arena::{ArenaNode, HtmlArena};
+pub use crate::types::checked::{DuplicateAttributes, TagError};
+pub use crate::types::element_builder::ElementBuilder;
 pub use crate::types::html::Html;
-pub use crate::types::tag::{Attribute, Tag};
+pub use crate::types::html_ref::{AttributeRef, HtmlRef, TagRef};
+pub use crate::types::iter::{Children, IntoChildren, Iter, Tags};
+pub use crate::types::json::JsonSerializer;
+pub use crate::types::jsx::JsxSerializer;
+#[cfg(feature = "serialize")]
+pub use crate::types::lazy::{LazyHtml, LazyNode};
+pub use crate::types::node_id::NodeId;
+pub use crate::types::pretty::{AttributeWrap, PrettyOptions};
+pub use crate::types::pug::PugSerializer;
+pub use crate::types::serializer::Serializer;
+pub use crate::types::small_string::SmallString;
+pub use crate::types::tag::{Attribute, AttrsIter, Tag};
 
 /// A const equivalent of the [`Option::unwrap_or`] method.
+#[cfg(feature = "filter")]
 const fn unwrap_or(opt: Option<bool>, default: bool) -> bool {
     match opt {
         Some(val) => val,
@@ -0,0 +1,151 @@
+//! Module to parse html documents given as bytes, that aren't guaranteed to
+//! be valid UTF-8.
+//!
+//! The invalid sequences are replaced by `U+FFFD REPLACEMENT CHARACTER`, like
+//! browsers do with documents whose encoding is mislabelled, and every
+//! replacement is reported as a warning, with its position in the bytes.
+
+use core::mem::take;
+use core::str;
+
+use super::error::{ParseError, Position};
+use crate::{Html, ParseOptions};
+
+/// Character that replaces the invalid UTF-8 sequences.
+const REPLACEMENT_CHARACTER: char = '\u{fffd}';
+
+/// Decoder of the chunks of a document, that replaces the invalid UTF-8
+/// sequences.
+///
+/// The sequences that are cut at the end of a chunk are kept until the next
+/// chunk, so that the chunks can be split anywhere.
+#[derive(Debug, Default)]
+pub struct Utf8Decoder {
+    /// Number of bytes that were decoded, to report the positions of the
+    /// invalid sequences.
+    offset: usize,
+    /// Start of a sequence at the end of the previous chunk, that may be
+    /// completed by the next chunk.
+    pending: Vec<u8>,
+    /// Position of the end of the decoded text.
+    position: Position,
+}
+
+impl Utf8Decoder {
+    /// Moves the positions after a decoded `text`, that was read from `len`
+    /// bytes.
+    fn advance(&mut self, text: &str, len: usize) {
+        self.position.advance(text);
+        self.offset = self.offset.saturating_add(len);
+    }
+
+    /// Decodes a chunk of the document, and reports the invalid sequences to
+    /// the warning callback of the options.
+    ///
+    /// If the chunk isn't the `last` one, an incomplete sequence at the end of
+    /// the chunk is kept for the next one.
+    pub fn decode(&mut self, chunk: &[u8], last: bool, options: &mut ParseOptions<'_>) -> String {
+        let mut bytes = take(&mut self.pending);
+        bytes.extend_from_slice(chunk);
+        let mut decoded = String::with_capacity(bytes.len());
+        let mut parts = bytes.utf8_chunks().peekable();
+        while let Some(part) = parts.next() {
+            decoded.push_str(part.valid());
+            self.advance(part.valid(), part.valid().len());
+            let invalid = part.invalid();
+            if invalid.is_empty() {
+                continue;
+            }
+            if !last && parts.peek().is_none() && is_incomplete(invalid) {
+                self.pending.extend_from_slice(invalid);
+                break;
+            }
+            let sequence = invalid.iter().map(|byte| format!("{byte:02X}")).collect::<Vec<_>>();
+            options.warn(&format!(
+                "Replaced the invalid UTF-8 sequence [{}] at byte {} (line {}, column {}) with \
+                 U+FFFD.",
+                sequence.join(" "),
+                self.offset,
+                self.position.line(),
+                self.position.column()
+            ));
+            decoded.push(REPLACEMENT_CHARACTER);
+            self.advance(&REPLACEMENT_CHARACTER.to_string(), invalid.len());
+        }
+        decoded
+    }
+}
+
+impl Html {
+    /// Parses an html document given as bytes into a Dom tree.
+    ///
+    /// The bytes don't need to be valid UTF-8: the invalid sequences, like
+    /// the encoded surrogates or the bytes of another encoding, are replaced by
+    /// `U+FFFD REPLACEMENT CHARACTER`, as browsers do.
+    ///
+    /// See [`Html::parse_bytes_with`] to be warned of the replacements.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error when the input HTML's syntax is invalid.
+    /// The positions of the error are those of the decoded string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse_bytes(b"<p>caf\xe9</p>").unwrap();
+    /// assert_eq!(html, "<p>caf\u{fffd}</p>");
+    /// ```
+    pub fn parse_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        Self::parse_bytes_with(bytes, ParseOptions::new())
+    }
+
+    /// Parses an html document given as bytes into a Dom tree, with custom
+    /// options.
+    ///
+    /// The invalid UTF-8 sequences are replaced as in [`Html::parse_bytes`],
+    /// and every replacement is reported to the callback set with
+    /// [`ParseOptions::on_warning`], with the position of the sequence in the
+    /// bytes.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error when the input HTML's syntax is invalid.
+    /// The positions of the error are those of the decoded string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let mut warnings = vec![];
+    /// let html = Html::parse_bytes_with(
+    ///     b"<p>\n  caf\xe9 \xed\xa0\x80</p>",
+    ///     ParseOptions::new().on_warning(|warning| warnings.push(warning.to_owned())),
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(html, "<p>\n  caf\u{fffd} \u{fffd}\u{fffd}\u{fffd}</p>");
+    /// assert_eq!(warnings, [
+    ///     "Replaced the invalid UTF-8 sequence [E9] at byte 9 (line 2, column 6) with U+FFFD.",
+    ///     "Replaced the invalid UTF-8 sequence [ED] at byte 11 (line 2, column 8) with U+FFFD.",
+    ///     "Replaced the invalid UTF-8 sequence [A0] at byte 12 (line 2, column 9) with U+FFFD.",
+    ///     "Replaced the invalid UTF-8 sequence [80] at byte 13 (line 2, column 10) with U+FFFD.",
+    /// ]);
+    /// ```
+    pub fn parse_bytes_with(
+        bytes: &[u8],
+        mut options: ParseOptions<'_>,
+    ) -> Result<Self, ParseError> {
+        let input = Utf8Decoder::default().decode(bytes, true, &mut options);
+        Self::parse_with(&input, options)
+    }
+}
+
+/// Checks if an invalid sequence is the start of a valid sequence, that was
+/// cut by the end of the input.
+fn is_incomplete(invalid: &[u8]) -> bool {
+    str::from_utf8(invalid).err().is_some_and(|err| err.error_len().is_none())
+}
@@ -0,0 +1,204 @@
+//! Module to classify the parts of an html string, to highlight its syntax.
+
+use core::ops::Range;
+
+use super::tokenizer::{TokenKind, Tokenizer};
+use crate::entities::reference_len;
+use crate::types::tag::{RAW_TEXT_TAGS, has_name};
+
+/// Class of a part of an html string, returned by [`classify_tokens`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenClass {
+    /// Name of an attribute, like `href`
+    AttributeName,
+    /// Value of an attribute, without its quotes and its character
+    /// references
+    AttributeValue,
+    /// Comment, with its `<!--` and `-->` delimiters
+    Comment,
+    /// Delimiters of the tags, like `<`, `</`, `/>`, `>`, `=` and the quotes,
+    /// and the whitespace between the attributes
+    Delimiter,
+    /// Doctype, with its delimiters, like `<!DOCTYPE html>`
+    Doctype,
+    /// Character reference, like `&amp;` or `&#x27;`, in a text or in an
+    /// attribute value
+    Entity,
+    /// End of the input that couldn't be read, after a syntax error
+    Invalid,
+    /// Name of a tag, like `div`
+    TagName,
+    /// Text, including the content of the `<script>` and `<style>` tags
+    Text,
+}
+
+/// Classifies the parts of an html string, to highlight its syntax.
+///
+/// The input is read with the [`Tokenizer`], and the tags are split into
+/// their names, attributes and delimiters. The returned ranges are the byte
+/// ranges of the parts in the input: they are in the order of the input,
+/// don't overlap and cover the whole input. Two contiguous parts never have
+/// the same class.
+///
+/// The character references are only classified as [`TokenClass::Entity`] if
+/// they can be decoded, see [`decode_entities`](crate::decode_entities). The
+/// contents of the `<script>` and `<style>` tags are never decoded, so they
+/// are plain texts. If the input has a syntax error, the rest of the input,
+/// from the start of the invalid token, is [`TokenClass::Invalid`].
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// let input = r#"<a href="/">Tom &amp; Jerry</a>"#;
+/// let classes = classify_tokens(input)
+///     .into_iter()
+///     .map(|(range, class)| (&input[range], class))
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(classes, [
+///     ("<", TokenClass::Delimiter),
+///     ("a", TokenClass::TagName),
+///     (" ", TokenClass::Delimiter),
+///     ("href", TokenClass::AttributeName),
+///     ("=\"", TokenClass::Delimiter),
+///     ("/", TokenClass::AttributeValue),
+///     ("\">", TokenClass::Delimiter),
+///     ("Tom ", TokenClass::Text),
+///     ("&amp;", TokenClass::Entity),
+///     (" Jerry", TokenClass::Text),
+///     ("</", TokenClass::Delimiter),
+///     ("a", TokenClass::TagName),
+///     (">", TokenClass::Delimiter),
+/// ]);
+/// ```
+#[must_use]
+pub fn classify_tokens(input: &str) -> Vec<(Range<usize>, TokenClass)> {
+    let mut classes = vec![];
+    let mut raw = false;
+    let mut end = 0;
+    for read in Tokenizer::new(input) {
+        let Ok(token) = read else {
+            push(&mut classes, end..input.len(), TokenClass::Invalid);
+            break;
+        };
+        let span = token.span();
+        let text = input.get(span.clone()).unwrap_or_default();
+        match token.as_kind() {
+            TokenKind::Comment(_) => push(&mut classes, span.clone(), TokenClass::Comment),
+            TokenKind::Doctype { .. } => push(&mut classes, span.clone(), TokenClass::Doctype),
+            TokenKind::Text(_) if raw => push(&mut classes, span.clone(), TokenClass::Text),
+            TokenKind::Text(_) => push_text(&mut classes, text, span.start, TokenClass::Text),
+            TokenKind::CloseTag(_) | TokenKind::OpenTag { .. } =>
+                push_tag(&mut classes, text, span.start),
+        }
+        raw = matches!(
+            token.as_kind(),
+            TokenKind::OpenTag { self_closing: false, tag } if has_name(&RAW_TEXT_TAGS, tag.as_name())
+        );
+        end = span.end;
+    }
+    classes
+}
+
+/// Pushes a classified range, merging it with the previous one if they have
+/// the same class.
+fn push(classes: &mut Vec<(Range<usize>, TokenClass)>, range: Range<usize>, class: TokenClass) {
+    if range.is_empty() {
+        return;
+    }
+    if let Some((last, last_class)) = classes.last_mut()
+        && *last_class == class
+        && last.end == range.start
+    {
+        last.end = range.end;
+    } else {
+        classes.push((range, class));
+    }
+}
+
+/// Pushes the parts of a tag, that starts at the byte `offset` of the input.
+///
+/// The tag was validated by the [`Tokenizer`], so it is made of a name and of
+/// attributes, with or without values, between the delimiters.
+fn push_tag(classes: &mut Vec<(Range<usize>, TokenClass)>, tag: &str, offset: usize) {
+    let is_delimiter = |ch: char| ch.is_whitespace() || matches!(ch, '/' | '>');
+    let mut start = 0;
+    let mut name = true;
+    while let Some(rest) = tag.get(start..)
+        && let Some(ch) = rest.chars().next()
+    {
+        let (len, class) = if ch == '<' || is_delimiter(ch) {
+            let len = rest.find(|next| !is_delimiter(next) && next != '<');
+            (len.unwrap_or(rest.len()), TokenClass::Delimiter)
+        } else if name {
+            name = false;
+            (rest.find(is_delimiter).unwrap_or(rest.len()), TokenClass::TagName)
+        } else {
+            let len = rest.find(|next| is_delimiter(next) || next == '=');
+            (len.unwrap_or(rest.len()), TokenClass::AttributeName)
+        };
+        let end = start.saturating_add(len);
+        push(classes, shift(start..end, offset), class);
+        start = end.saturating_add(push_value(
+            classes,
+            rest.get(len..).unwrap_or_default(),
+            offset.saturating_add(end),
+        ));
+    }
+}
+
+/// Pushes a text, that starts at the byte `offset` of the input, with its
+/// character references.
+fn push_text(
+    classes: &mut Vec<(Range<usize>, TokenClass)>,
+    text: &str,
+    offset: usize,
+    class: TokenClass,
+) {
+    let mut start = 0;
+    while let Some(amp) = text.get(start..).and_then(|rest| rest.find('&')) {
+        let reference = start.saturating_add(amp);
+        if let Some(len) = text.get(reference..).and_then(reference_len) {
+            let end = reference.saturating_add(len);
+            push(classes, shift(start..reference, offset), class);
+            push(classes, shift(reference..end, offset), TokenClass::Entity);
+            start = end;
+        } else {
+            let end = reference.saturating_add(1);
+            push(classes, shift(start..end, offset), class);
+            start = end;
+        }
+    }
+    push(classes, shift(start..text.len(), offset), class);
+}
+
+/// Pushes the value of an attribute, if `rest` starts with one, that starts at
+/// the byte `offset` of the input.
+///
+/// # Returns
+///
+/// The number of bytes of the value, with the `=` sign and the quotes.
+fn push_value(classes: &mut Vec<(Range<usize>, TokenClass)>, rest: &str, offset: usize) -> usize {
+    let Some(quote) = rest.strip_prefix('=').and_then(|quoted| quoted.chars().next()) else {
+        return 0;
+    };
+    let value = rest.get(2..).unwrap_or_default();
+    let len = value.find(quote).unwrap_or(value.len());
+    let end = len.saturating_add(2);
+    push(classes, shift(0..2, offset), TokenClass::Delimiter);
+    push_text(
+        classes,
+        value.get(..len).unwrap_or_default(),
+        offset.saturating_add(2),
+        TokenClass::AttributeValue,
+    );
+    push(classes, shift(end..end.saturating_add(1), offset), TokenClass::Delimiter);
+    end.saturating_add(1).min(rest.len())
+}
+
+/// Shifts a range of a part of the input, that starts at the byte `offset`.
+const fn shift(range: Range<usize>, offset: usize) -> Range<usize> {
+    offset.saturating_add(range.start)..offset.saturating_add(range.end)
+}
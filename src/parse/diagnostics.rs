@@ -0,0 +1,329 @@
+//! Module to report the malformed html that the parser repaired silently, like
+//! the unclosed tags, see [`Html::parse_with_diagnostics`].
+
+use core::fmt;
+
+use super::error::{ParseError, ParseErrorKind, Position};
+use super::options::ParseOptions;
+use super::validate::validate_attributes;
+use super::{ParseState, parse_tree};
+use crate::Html;
+use crate::types::html_builder::{HtmlBuilder, TreeBuilder};
+use crate::types::tag::TagBuilder;
+
+/// Warning raised on malformed html that the parser repaired, returned by
+/// [`Html::parse_with_diagnostics`].
+///
+/// The diagnostic contains the [`DiagnosticKind`], to react programmatically
+/// to the different warnings, and the position where the malformed html was
+/// found. It is displayed like the warnings sent to the callback set with
+/// [`ParseOptions::on_warning`].
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// let (_, diagnostics) =
+///     Html::parse_with_diagnostics("<p>\n  <b>Text</p>", ParseOptions::new()).unwrap();
+/// let [diagnostic] = diagnostics.as_slice() else { panic!() };
+///
+/// assert_eq!(diagnostic.kind(), &DiagnosticKind::ClosedByParent {
+///     closing: "p".to_owned(),
+///     tag: "b".to_owned()
+/// });
+/// assert_eq!((diagnostic.line(), diagnostic.column(), diagnostic.offset()), (2, 10, 13));
+/// assert_eq!(
+///     *diagnostic,
+///     "Closed the unclosed tag 'b' at the closing tag of 'p' at byte 13 (line 2, column 10)."
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Kind of the diagnostic.
+    kind: DiagnosticKind,
+    /// Position where the malformed html was found.
+    position: Position,
+}
+
+impl Diagnostic {
+    /// Returns the column where the malformed html was found.
+    ///
+    /// The column starts at 1, and counts characters, not bytes.
+    #[must_use]
+    pub const fn column(&self) -> usize {
+        self.position.column()
+    }
+
+    /// Returns the kind of the diagnostic.
+    #[must_use]
+    pub const fn kind(&self) -> &DiagnosticKind {
+        &self.kind
+    }
+
+    /// Returns the line where the malformed html was found.
+    ///
+    /// The line starts at 1.
+    #[must_use]
+    pub const fn line(&self) -> usize {
+        self.position.line()
+    }
+
+    /// Returns the byte offset where the malformed html was found.
+    #[must_use]
+    pub const fn offset(&self) -> usize {
+        self.position.offset()
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}.", self.kind, self.position)
+    }
+}
+
+impl PartialEq<&str> for Diagnostic {
+    fn eq(&self, other: &&str) -> bool {
+        self.to_string().as_str() == *other
+    }
+}
+
+/// Kind of a [`Diagnostic`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DiagnosticKind {
+    /// A tag was closed because the input ended before its closing tag.
+    ClosedAtEnd(String),
+    /// A tag was closed by the closing tag of one of its ancestors, like the
+    /// `<b>` in `<p><b>a</p>`.
+    ClosedByParent {
+        /// Name of the ancestor whose closing tag was found
+        closing: String,
+        /// Name of the closed tag
+        tag: String,
+    },
+    /// The attributes of a tag after the maximum number were dropped, see
+    /// [`ParseOptions::max_attributes_per_tag`].
+    DroppedAttributes {
+        /// Maximum number of attributes
+        max: usize,
+        /// Name of the tag
+        tag: String,
+    },
+    /// An attribute is defined several times in the same tag.
+    DuplicateAttribute {
+        /// Name of the attribute
+        attribute: String,
+        /// Name of the tag
+        tag: String,
+    },
+    /// An invalid tag was read as text, see [`ParseOptions::recover`].
+    InvalidTagAsText(ParseErrorKind),
+    /// The name of an attribute contains a quote, like in `<p a"=b>`, see
+    /// [`ParseOptions::attribute_escaping`].
+    MismatchedQuote(String),
+    /// An attribute value isn't followed by a whitespace, like in
+    /// `<p a="b"c>`, see [`ParseOptions::attribute_escaping`].
+    MissingWhitespace(String),
+    /// An attribute value contains a `<`, or a `&` that doesn't start a
+    /// character reference, see [`ParseOptions::attribute_escaping`].
+    Unescaped {
+        /// Name of the attribute
+        attribute: String,
+        /// Unescaped character
+        character: char,
+    },
+    /// A closing tag was ignored as the tag isn't open, see
+    /// [`ParseOptions::recover`].
+    UnopenedClosingTag(String),
+    /// A `-->` was read as text as no comment is open, see
+    /// [`ParseOptions::recover`].
+    UnopenedComment,
+    /// A comment was closed because the input ended before its `-->`.
+    UnterminatedComment,
+    /// The closing tag of a void element was ignored, see
+    /// [`ParseOptions::void_policy`].
+    VoidClosed(String),
+}
+
+impl fmt::Display for DiagnosticKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ClosedAtEnd(tag) =>
+                write!(f, "Closed the unclosed tag '{tag}' at the end of the input"),
+            Self::ClosedByParent { closing, tag } =>
+                write!(f, "Closed the unclosed tag '{tag}' at the closing tag of '{closing}'"),
+            Self::DroppedAttributes { max, tag } =>
+                write!(f, "Dropped the attributes of tag '{tag}' after the first {max}"),
+            Self::DuplicateAttribute { attribute, tag } =>
+                write!(f, "Duplicate attribute '{attribute}' in tag '{tag}'"),
+            Self::InvalidTagAsText(kind) => write!(
+                f,
+                "Read an invalid tag as text ({})",
+                kind.to_string().trim_end_matches('.')
+            ),
+            Self::MismatchedQuote(attribute) =>
+                write!(f, "Mismatched quote in the name of attribute '{attribute}'"),
+            Self::MissingWhitespace(attribute) =>
+                write!(f, "Missing whitespace after the value of attribute '{attribute}'"),
+            Self::Unescaped { attribute, character } =>
+                write!(f, "Unescaped '{character}' in the value of attribute '{attribute}'"),
+            Self::UnopenedClosingTag(tag) =>
+                write!(f, "Ignored closing tag of element '{tag}' that isn't open"),
+            Self::UnopenedComment =>
+                f.write_str("Read the closing of a comment that isn't open as text"),
+            Self::UnterminatedComment =>
+                f.write_str("Closed the unterminated comment at the end of the input"),
+            Self::VoidClosed(tag) => write!(f, "Ignored closing tag of void element '{tag}'"),
+        }
+    }
+}
+
+impl Html {
+    /// Parses an html string into a Dom tree, with custom options, and returns
+    /// the diagnostics raised on the malformed html that was repaired.
+    ///
+    /// The parsing succeeds as with [`Html::parse_with`], and the diagnostics
+    /// describe what was malformed, with their positions in the input:
+    ///
+    /// - the tags closed implicitly, because a parent tag was closed or the
+    ///   input ended before their closing tag;
+    /// - the comments that weren't terminated before the end of the input;
+    /// - the attributes defined several times in the same tag;
+    /// - all the other warnings sent to the callback set with
+    ///   [`ParseOptions::on_warning`], like the closing tags of the void
+    ///   elements, or the values reported by
+    ///   [`ParseOptions::attribute_escaping`].
+    ///
+    /// See [`DiagnosticKind`] for the list of the diagnostics. They are still
+    /// sent to the callback set with [`ParseOptions::on_warning`], if any.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error when the input HTML's syntax is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let (html, diagnostics) = Html::parse_with_diagnostics(
+    ///     r#"<div><span id="a" id="b">Text</div><p>More<!-- note"#,
+    ///     ParseOptions::new(),
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(html, r#"<div><span id="a" id="b">Text</span></div><p>More<!-- note--></p>"#);
+    /// assert_eq!(diagnostics, [
+    ///     "Duplicate attribute 'id' in tag 'span' at byte 18 (line 1, column 19).",
+    ///     "Closed the unclosed tag 'span' at the closing tag of 'div' at byte 29 (line 1, column \
+    ///      30).",
+    ///     "Closed the unterminated comment at the end of the input at byte 51 (line 1, column 52).",
+    ///     "Closed the unclosed tag 'p' at the end of the input at byte 51 (line 1, column 52).",
+    /// ]);
+    /// ```
+    pub fn parse_with_diagnostics(
+        html: &str,
+        mut options: ParseOptions<'_>,
+    ) -> Result<(Self, Vec<Diagnostic>), ParseError> {
+        let mut tree = HtmlBuilder::default();
+        let mut state = ParseState { diagnose: true, ..Default::default() };
+        parse_tree(&mut tree, html, &mut options, &mut state, true)?;
+        Ok((tree.into_html_with(&options), state.diagnostics))
+    }
+}
+
+/// Reports the comment and the tags that are still open at the end of the
+/// input, from the innermost.
+///
+/// `input` is the last chunk of the input.
+pub(super) fn diagnose_end<'input, T: TreeBuilder<'input>>(
+    tree: &T,
+    input: &str,
+    state: &mut ParseState,
+    options: &mut ParseOptions<'_>,
+) {
+    if !state.diagnose {
+        return;
+    }
+    if state.comment {
+        report(DiagnosticKind::UnterminatedComment, input, input.len(), state, options);
+    }
+    for name in tree.open_tags().iter().rev() {
+        report(DiagnosticKind::ClosedAtEnd(name.to_string()), input, input.len(), state, options);
+    }
+}
+
+/// Reports the malformed html of a tag before it is pushed into the tree: the
+/// duplicate attributes, the suspicious escaping of the attribute values, and
+/// the tags that a closing tag closes implicitly.
+///
+/// `offset` is the offset of the tag in `input`. The suspicious escaping is
+/// reported on every parse, see [`ParseOptions::attribute_escaping`], and the
+/// rest only by [`Html::parse_with_diagnostics`].
+pub(super) fn diagnose_tag<'input, T: TreeBuilder<'input>>(
+    tree: &T,
+    builder: &TagBuilder<'_>,
+    input: &str,
+    offset: usize,
+    state: &mut ParseState,
+    options: &mut ParseOptions<'_>,
+) {
+    validate_attributes(builder, input, state, options);
+    if !state.diagnose {
+        return;
+    }
+    match builder {
+        TagBuilder::Close(name) if !options.is_void(name) => {
+            let open = tree.open_tags();
+            let Some(idx) = open.iter().rposition(|open_name| open_name.eq_ignore_ascii_case(name))
+            else {
+                return;
+            };
+            for unclosed in open.iter().skip(idx.saturating_add(1)).rev() {
+                let kind = DiagnosticKind::ClosedByParent {
+                    closing: name.to_string(),
+                    tag: unclosed.to_string(),
+                };
+                report(kind, input, offset, state, options);
+            }
+        }
+        TagBuilder::Open(tag) | TagBuilder::OpenClose(tag) => {
+            let attrs = tag.as_attrs();
+            for (idx, attr) in attrs.iter().enumerate() {
+                let name = attr.as_name();
+                // Only the second occurrence of a name is reported.
+                if attrs.get(..idx).is_some_and(|before| {
+                    before.iter().filter(|other| other.as_name() == name).count() == 1
+                }) {
+                    let attr_offset = name.as_ptr().addr().saturating_sub(input.as_ptr().addr());
+                    let kind = DiagnosticKind::DuplicateAttribute {
+                        attribute: name.to_owned(),
+                        tag: tag.as_name().to_owned(),
+                    };
+                    report(kind, input, attr_offset, state, options);
+                }
+            }
+        }
+        TagBuilder::Close(_) | TagBuilder::Doctype { .. } | TagBuilder::OpenComment => (),
+    }
+}
+
+/// Reports a diagnostic found at the byte `offset` of `input`: it is sent to
+/// the warning callback, and kept for [`Html::parse_with_diagnostics`].
+pub(super) fn report(
+    kind: DiagnosticKind,
+    input: &str,
+    offset: usize,
+    state: &mut ParseState,
+    options: &mut ParseOptions<'_>,
+) {
+    if !state.diagnose && !options.wants_warnings() {
+        return;
+    }
+    let diagnostic = Diagnostic { kind, position: state.locate(input, offset) };
+    options.warn(&diagnostic.to_string());
+    if state.diagnose {
+        state.diagnostics.push(diagnostic);
+    }
+}
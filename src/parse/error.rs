@@ -0,0 +1,242 @@
+//! Module to define the errors returned when an html string can't be parsed.
+
+use core::error::Error;
+use core::fmt;
+
+use crate::BudgetExceeded;
+
+/// Error returned when an html string can't be parsed.
+///
+/// The error contains the [`ParseErrorKind`], to react programmatically to
+/// the different errors, and the position where the error was detected, to
+/// show it in a diagnostic.
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// let err = Html::parse("<ul>\n  <li>Item</lu>\n</ul>").unwrap_err();
+/// assert_eq!(err.kind(), &ParseErrorKind::UnopenedTag("lu".to_owned()));
+/// assert_eq!((err.line(), err.column(), err.offset()), (2, 11, 15));
+/// assert_eq!(err.character(), Some('<'));
+/// assert_eq!(err, "Invalid closing tag: Found closing tag for 'lu' but it isn't open.");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Character that caused the error, if any.
+    character: Option<char>,
+    /// Kind of the error.
+    kind: ParseErrorKind,
+    /// Position where the error was detected.
+    position: Position,
+}
+
+impl ParseError {
+    /// Returns the character that caused the error.
+    ///
+    /// This is `None` when the error was detected at the end of the input.
+    #[must_use]
+    pub const fn character(&self) -> Option<char> {
+        self.character
+    }
+
+    /// Returns the column where the error was detected.
+    ///
+    /// The column starts at 1, and counts characters, not bytes.
+    #[must_use]
+    pub const fn column(&self) -> usize {
+        self.position.column
+    }
+
+    /// Returns the kind of the error.
+    #[must_use]
+    pub const fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+
+    /// Returns the line where the error was detected.
+    ///
+    /// The line starts at 1.
+    #[must_use]
+    pub const fn line(&self) -> usize {
+        self.position.line
+    }
+
+    /// Creates an error detected at the byte `offset` of the input.
+    pub(crate) fn new(kind: ParseErrorKind, input: &str, offset: usize) -> Self {
+        let mut position = Position::default();
+        position.advance(input.get(..offset).unwrap_or(input));
+        let character = kind.character().or_else(|| input.get(offset..)?.chars().next());
+        Self { character, kind, position }
+    }
+
+    /// Returns the byte offset where the error was detected.
+    #[must_use]
+    pub const fn offset(&self) -> usize {
+        self.position.offset
+    }
+
+    /// Shifts the position of an error detected in a chunk of the document,
+    /// that starts at the given position.
+    pub(crate) const fn shift(&mut self, start: &Position) {
+        if self.position.line == 1 {
+            self.position.column =
+                self.position.column.saturating_add(start.column).saturating_sub(1);
+        }
+        self.position.line = self.position.line.saturating_add(start.line).saturating_sub(1);
+        self.position.offset = self.position.offset.saturating_add(start.offset);
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.kind.fmt(f)
+    }
+}
+
+impl Error for ParseError {}
+
+impl PartialEq<&str> for ParseError {
+    fn eq(&self, other: &&str) -> bool {
+        self.to_string().as_str() == *other
+    }
+}
+
+/// Kind of a [`ParseError`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The budget of the parser was exceeded, see
+    /// [`ParseOptions::budget`](crate::ParseOptions::budget).
+    BudgetExceeded(BudgetExceeded),
+    /// A closing tag has attributes, like in `</p id="a">`.
+    ClosingTagAttributes,
+    /// The input contains a control character, like `\0`, with the
+    /// [`ControlPolicy::Error`](crate::ControlPolicy::Error) policy.
+    ControlCharacter(char),
+    /// A doctype has more than one attribute, like in `<!DOCTYPE a b>`.
+    DoctypeAttributes,
+    /// A doctype attribute has a value, like in `<!DOCTYPE a="b">`.
+    DoctypeValue,
+    /// A closing tag contains an invalid character, like in `</!p>`.
+    InvalidClosingTag(char),
+    /// A doctype contains an invalid character, like in `<!-a>`.
+    InvalidDoctype(char),
+    /// A tag name contains an invalid character, like in `<a:b>`.
+    InvalidTagName(char),
+    /// An attribute value doesn't start with a quote, like in `<p id=a>`.
+    MissingQuote(char),
+    /// A tag has more attributes than allowed, with the
+    /// [`AttributeOverflow::Error`](crate::AttributeOverflow::Error) policy.
+    TooManyAttributes(String),
+    /// The input ends in the middle of a tag, like in `<p`.
+    UnclosedTag,
+    /// A comment is closed, but none is open, like in `a -->`.
+    UnopenedComment,
+    /// A tag is closed, but it isn't open, like in `<p></a>`.
+    UnopenedTag(String),
+    /// A void element is closed, with the
+    /// [`VoidPolicy::Strict`](crate::VoidPolicy::Strict) policy.
+    VoidClosed(String),
+}
+
+impl ParseErrorKind {
+    /// Returns the character that caused the error, for the kinds that have
+    /// one.
+    const fn character(&self) -> Option<char> {
+        match self {
+            Self::ControlCharacter(ch)
+            | Self::InvalidClosingTag(ch)
+            | Self::InvalidDoctype(ch)
+            | Self::InvalidTagName(ch)
+            | Self::MissingQuote(ch) => Some(*ch),
+            Self::BudgetExceeded(_)
+            | Self::ClosingTagAttributes
+            | Self::DoctypeAttributes
+            | Self::DoctypeValue
+            | Self::TooManyAttributes(_)
+            | Self::UnclosedTag
+            | Self::UnopenedComment
+            | Self::UnopenedTag(_)
+            | Self::VoidClosed(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BudgetExceeded(exceeded) => exceeded.fmt(f),
+            Self::ClosingTagAttributes => f.write_str("Closing tags don't support attributes."),
+            Self::ControlCharacter(ch) =>
+                write!(f, "Invalid control character U+{:04X}.", u32::from(*ch)),
+            Self::DoctypeAttributes => f.write_str("Doctype expected at most one attribute."),
+            Self::DoctypeValue => f.write_str("Doctype attribute must not have a value."),
+            Self::InvalidClosingTag(ch) => write!(f, "Invalid character '{ch}' in closing tag."),
+            Self::InvalidDoctype(ch) => write!(f, "Invalid character '{ch}' in doctype."),
+            Self::InvalidTagName(ch) => write!(f, "Invalid character '{ch}' in tag name."),
+            Self::MissingQuote(ch) =>
+                write!(f, "Invalid character '{ch}': expected '\'' or '\"' after '=' sign."),
+            Self::TooManyAttributes(name) => write!(
+                f,
+                "Too many attributes: tag '{name}' exceeds the maximum number of attributes."
+            ),
+            Self::UnclosedTag => f.write_str("EOF: Missing closing '>'."),
+            Self::UnopenedComment => f.write_str("Tried to close unopened comment."),
+            Self::UnopenedTag(name) =>
+                write!(f, "Invalid closing tag: Found closing tag for '{name}' but it isn't open."),
+            Self::VoidClosed(name) => write!(f, "Invalid closing tag: '{name}' is a void element."),
+        }
+    }
+}
+
+/// Position in an html string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Position {
+    /// Column, starting at 1, in characters.
+    column: usize,
+    /// Line, starting at 1.
+    line: usize,
+    /// Offset in bytes.
+    offset: usize,
+}
+
+impl Position {
+    /// Moves the position after the given text.
+    pub fn advance(&mut self, text: &str) {
+        self.offset = self.offset.saturating_add(text.len());
+        if let Some((before, after)) = text.rsplit_once('\n') {
+            self.line = self.line.saturating_add(before.matches('\n').count()).saturating_add(1);
+            self.column = after.chars().count().saturating_add(1);
+        } else {
+            self.column = self.column.saturating_add(text.chars().count());
+        }
+    }
+
+    /// Returns the column, starting at 1, in characters.
+    pub const fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns the line, starting at 1.
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the offset, in bytes.
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Self { column: 1, line: 1, offset: 0 }
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "byte {} (line {}, column {})", self.offset, self.line, self.column)
+    }
+}
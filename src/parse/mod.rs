@@ -1,17 +1,34 @@
 //! Module that transforms a [`String`] into an [`Html`] tree.
 
+mod bytes;
+pub mod classify;
+pub mod diagnostics;
+pub mod error;
+pub mod options;
+mod parallel;
+mod recover;
+mod scan;
+pub mod stream;
 mod tag;
+pub mod template;
+pub mod tokenizer;
+mod validate;
+use core::mem::replace;
+use core::ops::Range;
 use core::str::Chars;
 
-use crate::Html;
-use crate::types::html_builder::HtmlBuilder;
-use crate::types::tag::TagBuilder;
+use diagnostics::{Diagnostic, DiagnosticKind, diagnose_end, diagnose_tag, report};
+use error::{ParseError, ParseErrorKind, Position};
+use options::{PLAINTEXT, ParseOptions};
+use recover::close_implied;
+use scan::skip_until;
+use template::{Placeholder, placeholder};
 
-/// Tags that cannot have a content
-///
-/// This means that they are always self-closing tags: `<meta>` and `<br>` are
-/// closed.
-const AUTO_CLOSING_TAGS: [&str; 2] = ["meta", "br"];
+use crate::budget::BudgetCounter;
+use crate::types::html_builder::{HtmlBuilder, TreeBuilder};
+use crate::types::html_ref::RefBuilder;
+use crate::types::tag::TagBuilder;
+use crate::{Html, HtmlRef};
 
 impl Html {
     /// Parses an HTML string into a Dom tree.
@@ -39,82 +56,394 @@ impl Html {
     /// let tree: Html = Html::parse(html).expect("Invalid HTML");
     /// assert_eq!(format!("{tree}"), html);
     /// ```
-    pub fn parse(html: &str) -> Result<Self, String> {
+    pub fn parse(html: &str) -> Result<Self, ParseError> {
+        Self::parse_with(html, ParseOptions::new())
+    }
+
+    /// Parses an HTML string into a Dom tree that borrows its strings from
+    /// the input.
+    ///
+    /// This avoids allocating a [`String`] for every name, attribute and text
+    /// of the document, which dominates the parsing time of large documents.
+    /// The returned [`HtmlRef`] has the same structure as the [`Html`] tree
+    /// returned by [`Html::parse`], and can be converted into it with
+    /// [`HtmlRef::into_html`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error when the input HTML's syntax is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::borrow::Cow;
+    ///
+    /// use html_filter::*;
+    ///
+    /// let input = r#"<ul><li id="first">First</li><li>Second</li></ul>"#;
+    /// let html = Html::parse_borrowed(input).unwrap();
+    /// let HtmlRef::Tag { tag, child } = &html else { panic!() };
+    /// assert_eq!(tag.as_name(), "ul");
+    /// let HtmlRef::Vec(items) = &**child else { panic!() };
+    /// assert_eq!(items.len(), 2);
+    /// assert_eq!(items[0].to_string(), r#"<li id="first">First</li>"#);
+    ///
+    /// assert_eq!(html.into_html(), Html::parse(input).unwrap());
+    /// ```
+    pub fn parse_borrowed(html: &str) -> Result<HtmlRef<'_>, ParseError> {
+        let mut tree = RefBuilder::new(html);
+        parse_tree(&mut tree, html, &mut ParseOptions::new(), &mut ParseState::default(), true)
+            .map(|_| tree.into_html_ref())
+    }
+
+    /// Parses an HTML string into a Dom tree, with custom options.
+    ///
+    /// See [`ParseOptions`] for the list of available options.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error when the input HTML's syntax is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let mut tags = 0;
+    /// let html = Html::parse_with(
+    ///     "<ul><li>First</li><li>Second</li></ul>",
+    ///     ParseOptions::new().on_tag_open(|_| tags += 1),
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(tags, 3);
+    /// assert_eq!(html, Html::parse("<ul><li>First</li><li>Second</li></ul>").unwrap());
+    /// ```
+    pub fn parse_with(html: &str, mut options: ParseOptions<'_>) -> Result<Self, ParseError> {
         let mut tree = HtmlBuilder::default();
-        tree.parse(&mut html.chars()).map(|()| tree.into_html())
+        parse_tree(&mut tree, html, &mut options, &mut ParseState::default(), true)?;
+        Ok(tree.into_html_with(&options))
     }
 }
 
-impl HtmlBuilder {
-    /// Wrapper for the [`Html::parse`] method.
-    ///
-    /// This method transforms a flow of chars into an Html tree.
-    fn parse(&mut self, chars: &mut Chars<'_>) -> Result<(), String> {
-        let mut dash_count: u32 = 0;
-        let mut style = false;
-        let mut script = false;
-        let mut comment = false;
-        while let Some(ch) = chars.next() {
-            if !comment && (style || script) {
-                if ch == '<'
-                    && let Ok(TagBuilder::Close(name)) = TagBuilder::parse(chars)
-                {
-                    if style && name == "style" {
-                        style = false;
-                        self.close_tag(&name)?;
-                        continue;
-                    }
-                    if script && name == "script" {
-                        script = false;
-                        self.close_tag(&name)?;
-                        continue;
-                    }
-                }
-                self.push_char(ch);
-            } else if ch == '-' {
-                #[expect(clippy::arithmetic_side_effects, reason = "checked")]
-                if dash_count == 2 {
-                    self.push_char('-');
-                } else {
-                    dash_count += 1;
+/// State of the parser that is kept from a chunk of the input to the next.
+#[derive(Debug, Default)]
+struct ParseState {
+    /// Whether a comment is open.
+    comment: bool,
+    /// Whether the repaired malformed html is reported, see
+    /// [`Html::parse_with_diagnostics`].
+    diagnose: bool,
+    /// Diagnostics reported so far, when [`ParseState::diagnose`] is set.
+    diagnostics: Vec<Diagnostic>,
+    /// Position of the last located offset, see [`ParseState::locate`].
+    located: Position,
+    /// Number of nodes created so far, to respect the budget.
+    nodes: usize,
+    /// Name of the open tag whose content is read as raw text, like a
+    /// `<script>` or a `<style>`, see [`ParseOptions::raw_text`].
+    raw: Option<&'static str>,
+    /// Position of the start of the input in the whole document, when it is
+    /// parsed by chunks.
+    start: Position,
+    /// Text read since the last tag, for the text hook.
+    text: String,
+}
+
+impl ParseState {
+    /// Returns the position in the whole document of the byte `offset` of
+    /// `input`, the chunk that starts at [`ParseState::start`].
+    ///
+    /// The position is found from the last located one if it is before, so
+    /// that the input is only read once when the offsets are increasing.
+    fn locate(&mut self, input: &str, offset: usize) -> Position {
+        let (from, mut position) = match self.located.offset().checked_sub(self.start.offset()) {
+            Some(from) if from <= offset => (from, self.located.clone()),
+            Some(_) | None => (0, self.start.clone()),
+        };
+        position.advance(input.get(from..offset).unwrap_or_default());
+        self.located.clone_from(&position);
+        position
+    }
+}
+
+/// Parses an HTML string, or a chunk of it, into the given tree.
+///
+/// The tree is either an owned [`HtmlBuilder`], or a [`RefBuilder`] that
+/// borrows its strings from the input.
+///
+/// # Returns
+///
+/// The number of bytes of the input that were parsed. Unless this is the
+/// `last` chunk, the parsing stops before a tag or a run of dashes that could
+/// continue in the next chunk. These bytes must be given again at the start
+/// of the next chunk.
+fn parse_tree<'input, T: TreeBuilder<'input>>(
+    tree: &mut T,
+    input: &'input str,
+    options: &mut ParseOptions<'_>,
+    state: &mut ParseState,
+    last: bool,
+) -> Result<usize, ParseError> {
+    let budget = options.as_budget();
+    let counter = budget.as_ref().map(|limits| BudgetCounter::resume(limits, state.nodes));
+    let mut attrs = vec![];
+    let mut chars = input.chars();
+    let position = |iter: &Chars<'_>| input.len().saturating_sub(iter.as_str().len());
+    let fail = |kind: ParseErrorKind, offset: usize| ParseError::new(kind, input, offset);
+    options.check_controls(input).map_err(|(offset, kind)| fail(kind, offset))?;
+    let mut dashes: Range<usize> = 0..0;
+    let text_needles: &[u8] = if options.reads_templates() { b"<-{" } else { b"<-" };
+    loop {
+        let start = position(&chars);
+        let Some(ch) = chars.next() else { break };
+        let end = start.saturating_add(ch.len_utf8());
+        check_budget(counter.as_ref(), BudgetCounter::tick).map_err(|kind| fail(kind, start))?;
+        if !state.comment
+            && let Some(raw) = state.raw
+        {
+            if ch == '<' {
+                let parsed = TagBuilder::parse(&mut chars, &mut attrs, options.reads_templates());
+                if !last && parsed.is_err() && chars.as_str().is_empty() {
+                    return Ok(stop(&mut state.nodes, counter.as_ref(), start));
                 }
-            } else if ch == '>' && dash_count == 2 {
-                if !self.close_comment() {
-                    return Err("Tried to close unopened comment.".to_owned());
+                if let Ok(TagBuilder::Close(name)) = parsed
+                    && name.eq_ignore_ascii_case(raw)
+                    && raw != PLAINTEXT
+                {
+                    state.raw = None;
+                    options.text_read(&mut state.text, true);
+                    tree.close_tag(&name).map_err(|kind| fail(kind, start))?;
+                    continue;
                 }
-                comment = false;
-                dash_count = 0;
+                // Any other tag is raw text, read again from the `<`.
+                chars = input.get(end..).unwrap_or_default().chars();
+            }
+            push_text(tree, input, start..end, false, &mut state.text, options);
+            let run = position(&chars);
+            skip_until(&mut chars, b"<");
+            push_text(tree, input, run..position(&chars), false, &mut state.text, options);
+        } else if ch == '-' {
+            if !last && dashes.is_empty() && chars.as_str().bytes().all(|byte| byte == b'-') {
+                return Ok(stop(&mut state.nodes, counter.as_ref(), start));
+            }
+            if dashes.len() == 2 {
+                // The oldest dash is pushed, so that the text stays contiguous.
+                let oldest = dashes.start..dashes.start.saturating_add(1);
+                dashes = oldest.end..end;
+                push_text(tree, input, oldest, state.comment, &mut state.text, options);
+            } else if dashes.is_empty() {
+                dashes = start..end;
             } else {
-                for _ in 0..dash_count {
-                    self.push_char('-');
+                dashes.end = end;
+            }
+        } else if ch == '>' && dashes.len() == 2 {
+            close_comment(tree, input, dashes.start..end, state, options)?;
+            dashes = end..end;
+        } else {
+            let pending = replace(&mut dashes, end..end);
+            push_text(tree, input, pending, state.comment, &mut state.text, options);
+            if state.comment {
+                tree.push_text(input, start..end);
+                skip_until(&mut chars, b"-");
+                tree.push_text(input, end..position(&chars));
+            } else if let Some(found) = template_at(input, start, last, options) {
+                let Placeholder::Closed(len) = found else {
+                    return Ok(stop(&mut state.nodes, counter.as_ref(), start));
+                };
+                let placeholder_end = start.saturating_add(len);
+                push_text(tree, input, start..placeholder_end, false, &mut state.text, options);
+                chars = input.get(placeholder_end..).unwrap_or_default().chars();
+            } else if ch == '<' {
+                let parsed = TagBuilder::parse(&mut chars, &mut attrs, options.reads_templates());
+                if !last && parsed.is_err() && chars.as_str().is_empty() {
+                    return Ok(stop(&mut state.nodes, counter.as_ref(), start));
                 }
-                dash_count = 0;
-                if comment {
-                    self.push_char(ch);
-                } else if ch == '<' {
-                    match TagBuilder::parse(chars)? {
-                        TagBuilder::Doctype { name, attr } =>
-                            self.push_node(Self::Doctype { name, attr }),
-                        TagBuilder::Open(tag) => {
-                            match tag.as_name() {
-                                "style" => style = true,
-                                "script" => script = true,
-                                _ => (),
-                            }
-                            self.push_tag(tag, false);
-                        }
-                        TagBuilder::OpenClose(tag) => self.push_tag(tag, true),
-                        TagBuilder::Close(name) => self.close_tag(&name)?,
-                        TagBuilder::OpenComment => {
-                            self.push_comment();
-                            comment = true;
-                        }
-                    }
-                } else {
-                    self.push_char(ch);
+                if let Err(kind) = &parsed
+                    && options.recovers()
+                {
+                    let invalid = DiagnosticKind::InvalidTagAsText(kind.clone());
+                    report(invalid, input, start, state, options);
+                    push_text(tree, input, start..end, false, &mut state.text, options);
+                    chars = input.get(end..).unwrap_or_default().chars();
+                    continue;
+                }
+                options.text_read(&mut state.text, false);
+                let builder = parsed.map_err(|kind| tag_error(kind, input, position(&chars)))?;
+                if !matches!(builder, TagBuilder::Close(_)) {
+                    check_budget(counter.as_ref(), BudgetCounter::visit)
+                        .map_err(|kind| fail(kind, start))?;
                 }
+                push_tag(tree, builder, input, start, options, state)?;
+            } else {
+                push_text(tree, input, start..end, false, &mut state.text, options);
+                skip_until(&mut chars, text_needles);
+                push_text(tree, input, end..position(&chars), false, &mut state.text, options);
             }
         }
-        Ok(())
     }
+    if last {
+        options.text_read(&mut state.text, state.raw.is_some());
+        diagnose_end(tree, input, state, options);
+    }
+    Ok(stop(&mut state.nodes, counter.as_ref(), input.len()))
+}
+
+/// Closes the open comment, when its closing `-->` is at the given range of
+/// the input.
+///
+/// # Errors
+///
+/// Returns an error if no comment is open, unless the invalid html is
+/// repaired: the `-->` is then read as text.
+fn close_comment<'input, T: TreeBuilder<'input>>(
+    tree: &mut T,
+    input: &'input str,
+    closing: Range<usize>,
+    state: &mut ParseState,
+    options: &mut ParseOptions<'_>,
+) -> Result<(), ParseError> {
+    if tree.close_comment() {
+        state.comment = false;
+    } else if options.recovers() {
+        report(DiagnosticKind::UnopenedComment, input, closing.start, state, options);
+        push_text(tree, input, closing, false, &mut state.text, options);
+    } else {
+        return Err(ParseError::new(ParseErrorKind::UnopenedComment, input, closing.start));
+    }
+    Ok(())
+}
+
+/// Pushes a tag, a doctype or the opening of a comment into the tree.
+///
+/// `start` is the offset of the tag in `input`, where the errors and the
+/// repaired malformed html are reported.
+fn push_tag<'input, T: TreeBuilder<'input>>(
+    tree: &mut T,
+    builder: TagBuilder<'input>,
+    input: &str,
+    start: usize,
+    options: &mut ParseOptions<'_>,
+    state: &mut ParseState,
+) -> Result<(), ParseError> {
+    let fail = |kind: ParseErrorKind| ParseError::new(kind, input, start);
+    diagnose_tag(tree, &builder, input, start, state, options);
+    let diagnostic = match builder {
+        TagBuilder::Doctype { name, attr } => {
+            tree.push_doctype(name, attr);
+            None
+        }
+        TagBuilder::Open(mut tag) if !options.is_void(tag.as_name()) => {
+            let dropped = options.limit_attributes(&mut tag).map_err(fail)?;
+            state.raw = options.raw_text(tag.as_name());
+            if options.recovers() {
+                close_implied(tree, tag.as_name());
+            }
+            tree.open_tag(tag, false, options);
+            dropped
+        }
+        TagBuilder::Open(mut tag) | TagBuilder::OpenClose(mut tag) => {
+            let dropped = options.limit_attributes(&mut tag).map_err(fail)?;
+            if options.recovers() {
+                close_implied(tree, tag.as_name());
+            }
+            tree.open_tag(tag, true, options);
+            dropped
+        }
+        TagBuilder::Close(name) if options.is_void(&name) =>
+            options.void_closed(&name).map_err(fail)?,
+        TagBuilder::Close(name) => match tree.close_tag(&name) {
+            Err(_) if options.recovers() =>
+                Some(DiagnosticKind::UnopenedClosingTag(name.into_owned())),
+            result => {
+                result.map_err(fail)?;
+                None
+            }
+        },
+        TagBuilder::OpenComment => {
+            tree.open_comment();
+            state.comment = true;
+            None
+        }
+    };
+    if let Some(kind) = diagnostic {
+        report(kind, input, start, state, options);
+    }
+    Ok(())
+}
+
+/// Pushes the text at the given range of the input into the tree.
+///
+/// If the text isn't in a comment, it is also saved in `text` if the options
+/// need to be informed of the text nodes.
+fn push_text<'input, T: TreeBuilder<'input>>(
+    tree: &mut T,
+    input: &'input str,
+    range: Range<usize>,
+    comment: bool,
+    text: &mut String,
+    options: &ParseOptions<'_>,
+) {
+    if !comment && options.wants_text() {
+        text.push_str(input.get(range.clone()).unwrap_or_default());
+    }
+    tree.push_text(input, range);
+}
+
+/// Saves the number of nodes created so far, and returns the number of bytes
+/// that were parsed.
+const fn stop(nodes: &mut usize, counter: Option<&BudgetCounter<'_>>, parsed: usize) -> usize {
+    if let Some(budget) = counter {
+        *nodes = budget.nodes();
+    }
+    parsed
+}
+
+/// Checks that the budget isn't exceeded, if there is one.
+///
+/// The `check` method is called on the budget counter, to record the work
+/// that was done.
+fn check_budget<'budget, F: Fn(&BudgetCounter<'budget>) -> bool>(
+    counter: Option<&BudgetCounter<'budget>>,
+    check: F,
+) -> Result<(), ParseErrorKind> {
+    counter
+        .filter(|budget| !check(budget))
+        .and_then(BudgetCounter::exceeded)
+        .map_or(Ok(()), |exceeded| Err(ParseErrorKind::BudgetExceeded(exceeded)))
+}
+
+/// Finds the template placeholder at the byte `start` of the input, if the
+/// placeholders are read, see [`ParseOptions::templates`].
+///
+/// An unclosed placeholder is only returned if this isn't the `last` chunk,
+/// as it could be closed in the next chunk. Otherwise, it is read as html.
+fn template_at(
+    input: &str,
+    start: usize,
+    last: bool,
+    options: &ParseOptions<'_>,
+) -> Option<Placeholder> {
+    if !options.reads_templates() {
+        return None;
+    }
+    placeholder(input.get(start..).unwrap_or_default())
+        .filter(|found| !last || matches!(found, Placeholder::Closed(_)))
+}
+
+/// Builds the error of a tag that couldn't be parsed, when the parser stopped
+/// at the byte `parsed` of the input.
+///
+/// The error is located on the last character that was read, or at the end of
+/// the input if the tag isn't closed.
+fn tag_error(kind: ParseErrorKind, input: &str, parsed: usize) -> ParseError {
+    let offset = if kind == ParseErrorKind::UnclosedTag {
+        parsed
+    } else {
+        input
+            .get(..parsed)
+            .and_then(|before| before.char_indices().next_back())
+            .map_or(parsed, |(idx, _)| idx)
+    };
+    ParseError::new(kind, input, offset)
 }
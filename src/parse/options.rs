@@ -0,0 +1,741 @@
+//! Module to define the options that customise the parsing of an html string.
+
+use core::fmt;
+
+use super::diagnostics::DiagnosticKind;
+use super::error::ParseErrorKind;
+use crate::control::{clean_controls, find_control};
+use crate::types::html_ref::TagRef;
+use crate::types::tag::{RAW_TEXT_TAGS, VOID_TAGS, has_name};
+use crate::{Budget, ControlPolicy, Tag, VoidPolicy, decode_entities};
+
+/// Legacy elements whose content is read as raw text with
+/// [`LegacyTags::Honor`].
+const LEGACY_RAW_TEXT_TAGS: [&str; 3] = ["listing", "plaintext", "xmp"];
+
+/// Legacy element whose content swallows the rest of the document with
+/// [`LegacyTags::Honor`].
+pub(super) const PLAINTEXT: &str = "plaintext";
+
+/// Callback invoked on every opening tag.
+type TagHook<'hooks> = Box<dyn FnMut(&Tag) + 'hooks>;
+
+/// Callback invoked on every text node.
+type TextHook<'hooks> = Box<dyn FnMut(&str) + 'hooks>;
+
+/// Validation of the escaping of the attribute values, set with
+/// [`ParseOptions::attribute_escaping`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeEscaping {
+    /// Accepts the attribute values as they are.
+    #[default]
+    Lenient,
+    /// Reports the suspicious escaping of the attribute values as warnings.
+    Warn,
+}
+
+/// Behaviour to adopt when a tag has more attributes than the maximum set
+/// with [`ParseOptions::max_attributes_per_tag`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeOverflow {
+    /// Returns an error, with the [`ParseErrorKind::TooManyAttributes`] kind.
+    Error,
+    /// Keeps the first attributes, up to the maximum, and drops the others
+    /// with a warning.
+    #[default]
+    KeepFirstN,
+}
+
+/// Treatment of the legacy `<plaintext>`, `<listing>` and `<xmp>` elements,
+/// set with [`ParseOptions::legacy_tags`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LegacyTags {
+    /// Honours the legacy semantics: the content of a `<plaintext>` is the
+    /// rest of the document, read as raw text, and the contents of the
+    /// `<listing>` and `<xmp>` elements are read as raw text, until their
+    /// closing tag.
+    Honor,
+    /// Treats the legacy elements as normal tags.
+    #[default]
+    Normal,
+}
+
+/// Options to customise the behaviour of [`Html::parse_with`].
+///
+/// The options use a builder pattern: start with [`ParseOptions::new`] and
+/// chain the options you need.
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// let mut links = vec![];
+/// let html = Html::parse_with(
+///     r#"<nav><a href="/home">Home</a><a href="/about">About</a></nav>"#,
+///     ParseOptions::new().on_tag_open(|tag| {
+///         if let Some(href) = tag.find_attr_value("href") {
+///             links.push(href.to_owned());
+///         }
+///     }),
+/// )
+/// .unwrap();
+///
+/// assert_eq!(links, ["/home", "/about"]);
+/// assert_eq!(html, r#"<nav><a href="/home">Home</a><a href="/about">About</a></nav>"#);
+/// ```
+///
+/// [`Html::parse_with`]: crate::Html::parse_with
+pub struct ParseOptions<'hooks> {
+    /// Validation of the escaping of the attribute values, see
+    /// [`Self::attribute_escaping`].
+    attribute_escaping: AttributeEscaping,
+    /// Budget to respect, see [`Self::budget`].
+    budget: Option<Budget<'hooks>>,
+    /// Behaviour on control characters, see [`Self::control_policy`].
+    control_policy: ControlPolicy,
+    /// Whether to decode the character references, see
+    /// [`Self::decode_entities`].
+    decode_entities: bool,
+    /// Treatment of the legacy elements, see [`Self::legacy_tags`].
+    legacy_tags: LegacyTags,
+    /// Maximum number of attributes of a tag, and behaviour when it is
+    /// exceeded, see [`Self::max_attributes_per_tag`].
+    max_attributes: Option<(usize, AttributeOverflow)>,
+    /// Whether to repair the invalid html, see [`Self::recover`].
+    recover: bool,
+    /// Callback invoked on every opening tag, see [`Self::on_tag_open`].
+    tag_open: Option<TagHook<'hooks>>,
+    /// Whether to read the template placeholders as texts, see
+    /// [`Self::templates`].
+    templates: bool,
+    /// Callback invoked on every text node, see [`Self::on_text`].
+    text: Option<TextHook<'hooks>>,
+    /// Behaviour on misused void elements, see [`Self::void_policy`].
+    void_policy: VoidPolicy,
+    /// Names of the void elements, see [`Self::void_tags`].
+    void_tags: Option<Vec<String>>,
+    /// Callback invoked on every warning, see [`Self::on_warning`].
+    warning: Option<TextHook<'hooks>>,
+}
+
+impl<'hooks> ParseOptions<'hooks> {
+    /// Returns the budget to respect, if any.
+    pub(super) const fn as_budget(&self) -> Option<Budget<'hooks>> {
+        self.budget
+    }
+
+    /// Sets the validation of the escaping of the attribute values.
+    ///
+    /// With [`AttributeEscaping::Warn`], the suspicious escaping of the
+    /// attribute values is reported as warnings, with their positions in the
+    /// document. This is a validation mode for the teams that check their html
+    /// before deploying it: the warnings are sent to the callback set with
+    /// [`Self::on_warning`], and the tree is built as usual. A warning is
+    /// raised for
+    ///
+    /// - an unescaped `<` in an attribute value;
+    /// - an unescaped `&` in an attribute value, i.e., a `&` that doesn't start
+    ///   a valid character reference, like in `?a=1&b=2`;
+    /// - a quote in an attribute name, or an attribute value that isn't
+    ///   followed by a whitespace, which are the signs of mismatched quotes,
+    ///   like in `title="say "hi""`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let mut warnings = vec![];
+    /// let html = Html::parse_with(
+    ///     "<a href='?a=1&b=2&amp;c=3'\n   title=\"say \"hi\"\">x < y</a>",
+    ///     ParseOptions::new()
+    ///         .attribute_escaping(AttributeEscaping::Warn)
+    ///         .on_warning(|warning| warnings.push(warning.to_owned())),
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(warnings, [
+    ///     "Unescaped '&' in the value of attribute 'href' at byte 13 (line 1, column 14).",
+    ///     "Missing whitespace after the value of attribute 'title' at byte 42 (line 2, column 16).",
+    ///     "Mismatched quote in the name of attribute 'hi\"\"' at byte 42 (line 2, column 16).",
+    /// ]);
+    /// assert_eq!(html.as_tag().unwrap().0.attr_count(), 3);
+    /// ```
+    #[must_use]
+    pub const fn attribute_escaping(mut self, validation: AttributeEscaping) -> Self {
+        self.attribute_escaping = validation;
+        self
+    }
+
+    /// Sets a budget to respect while parsing.
+    ///
+    /// The parsing fails if the budget is exceeded. The nodes counted by the
+    /// budget are the tags, comments and doctypes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = "<ul><li>1</li><li>2</li></ul>";
+    /// let err = Html::parse_with(html, ParseOptions::new().budget(Budget::new().max_nodes(2)));
+    /// assert_eq!(err.unwrap_err().kind(), &ParseErrorKind::BudgetExceeded(BudgetExceeded::Nodes));
+    /// assert!(Html::parse_with(html, ParseOptions::new().budget(Budget::new().max_nodes(3))).is_ok());
+    /// ```
+    #[must_use]
+    pub const fn budget(mut self, budget: Budget<'hooks>) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Checks that the input doesn't contain any control character, with the
+    /// [`ControlPolicy::Error`] policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns the position and the value of the first control character.
+    pub(super) fn check_controls(&self, input: &str) -> Result<(), (usize, ParseErrorKind)> {
+        match self.control_policy {
+            ControlPolicy::Error => find_control(input)
+                .map_or(Ok(()), |(offset, ch)| Err((offset, ParseErrorKind::ControlCharacter(ch)))),
+            ControlPolicy::Keep | ControlPolicy::Replace | ControlPolicy::Strip => Ok(()),
+        }
+    }
+
+    /// Sets the behaviour to adopt when the input contains control
+    /// characters, like `\0` or `\x1b`.
+    ///
+    /// With the default policy, [`ControlPolicy::Keep`], the control
+    /// characters are kept in the tree. With [`ControlPolicy::Replace`] and
+    /// [`ControlPolicy::Strip`], they are replaced by `U+FFFD` or removed from
+    /// the texts, the comments and the attributes, before the callbacks are
+    /// called. With [`ControlPolicy::Error`], the parsing fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = "<p title='a\0b'>c\x1bd</p>";
+    /// let replaced =
+    ///     Html::parse_with(html, ParseOptions::new().control_policy(ControlPolicy::Replace));
+    /// assert_eq!(replaced.unwrap(), "<p title='a\u{fffd}b'>c\u{fffd}d</p>");
+    ///
+    /// let stripped = Html::parse_with(html, ParseOptions::new().control_policy(ControlPolicy::Strip));
+    /// assert_eq!(stripped.unwrap(), "<p title='ab'>cd</p>");
+    ///
+    /// let err = Html::parse_with(html, ParseOptions::new().control_policy(ControlPolicy::Error));
+    /// assert_eq!(err.unwrap_err().kind(), &ParseErrorKind::ControlCharacter('\0'));
+    ///
+    /// let kept = Html::parse(html).unwrap();
+    /// assert_eq!(kept, "<p title='a&#x00;b'>c&#x1B;d</p>");
+    /// ```
+    #[must_use]
+    pub const fn control_policy(mut self, policy: ControlPolicy) -> Self {
+        self.control_policy = policy;
+        self
+    }
+
+    /// Returns the behaviour to adopt on control characters.
+    pub(crate) const fn controls(&self) -> ControlPolicy {
+        self.control_policy
+    }
+
+    /// Decodes the character references, like `&amp;` or `&#x27;`, of the
+    /// texts and of the attribute values.
+    ///
+    /// By default, the references are kept as they are, so that the tree is
+    /// displayed back byte for byte. The contents of the `<script>` and
+    /// `<style>` tags are never decoded. The callbacks receive the decoded
+    /// texts and tags. See [`decode_entities`] for the supported references.
+    ///
+    /// When displayed, the decoded `<` of the texts and the quotes of the
+    /// attribute values are encoded again, so that the output can be parsed
+    /// back. The `&` are displayed as they are: use
+    /// [`encode_entities`](crate::encode_entities) on the decoded texts that
+    /// must keep a literal reference, like `&amp;lt;`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let input = r#"<p title="Tom &amp; Jerry">1 &lt; 2&nbsp;&#x21;</p>"#;
+    /// let html = Html::parse_with(input, ParseOptions::new().decode_entities(true)).unwrap();
+    /// let (tag, child) = html.as_tag().unwrap();
+    ///
+    /// assert_eq!(tag.find_attr_value("title").unwrap(), "Tom & Jerry");
+    /// assert_eq!(child.as_text().unwrap(), "1 < 2\u{a0}!");
+    /// assert_eq!(html, "<p title=\"Tom & Jerry\">1 &lt; 2\u{a0}!</p>");
+    ///
+    /// let html = Html::parse(input).unwrap();
+    /// assert_eq!(html.as_tag().unwrap().1.as_text().unwrap(), "1 &lt; 2&nbsp;&#x21;");
+    /// assert_eq!(html, input);
+    /// ```
+    #[must_use]
+    pub const fn decode_entities(mut self, decode: bool) -> Self {
+        self.decode_entities = decode;
+        self
+    }
+
+    /// Checks if the character references must be decoded.
+    pub(crate) const fn decodes_entities(&self) -> bool {
+        self.decode_entities
+    }
+
+    /// Checks if the tag `name` is a void element, i.e., if it can't have any
+    /// content.
+    pub(super) fn is_void(&self, name: &str) -> bool {
+        self.void_tags.as_ref().map_or_else(
+            || has_name(&VOID_TAGS, name),
+            |tags| tags.iter().any(|tag| tag.eq_ignore_ascii_case(name)),
+        )
+    }
+
+    /// Sets the treatment of the legacy `<plaintext>`, `<listing>` and `<xmp>`
+    /// elements, found in archives of very old pages.
+    ///
+    /// By default, with [`LegacyTags::Normal`], they are normal tags. With
+    /// [`LegacyTags::Honor`], they are parsed like the old browsers did: the
+    /// content of a `<plaintext>` swallows the rest of the document, as it
+    /// can't be closed, and the contents of the `<listing>` and `<xmp>`
+    /// elements are raw text, like the one of a `<script>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let page = "<p>Source:</p><xmp><b>bold</b></xmp><plaintext><p>a</p></plaintext>";
+    /// let options = ParseOptions::new().legacy_tags(LegacyTags::Honor);
+    /// let html = Html::parse_with(page, options).unwrap();
+    ///
+    /// let vec = html.as_vec().unwrap();
+    /// assert_eq!(vec.len(), 3);
+    /// assert_eq!(*vec[1].as_tag().unwrap().1, Html::Text("<b>bold</b>".into()));
+    /// assert_eq!(*vec[2].as_tag().unwrap().1, Html::Text("<p>a</p></plaintext>".into()));
+    ///
+    /// let html = Html::parse(page).unwrap();
+    /// assert!(html.as_vec().unwrap()[1].as_tag().unwrap().1.as_tag().is_some());
+    /// ```
+    #[must_use]
+    pub const fn legacy_tags(mut self, treatment: LegacyTags) -> Self {
+        self.legacy_tags = treatment;
+        self
+    }
+
+    /// Applies the maximum number of attributes to a tag, depending on the
+    /// policy, and returns the diagnostic to report if attributes were
+    /// dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tag has too many attributes, with the
+    /// [`AttributeOverflow::Error`] policy.
+    pub(super) fn limit_attributes(
+        &self,
+        tag: &mut TagRef<'_>,
+    ) -> Result<Option<DiagnosticKind>, ParseErrorKind> {
+        let Some((max, overflow)) = self.max_attributes else { return Ok(None) };
+        let count = tag.as_attrs().len();
+        if count <= max {
+            return Ok(None);
+        }
+        match overflow {
+            AttributeOverflow::Error =>
+                Err(ParseErrorKind::TooManyAttributes(tag.as_name().to_owned())),
+            AttributeOverflow::KeepFirstN => {
+                tag.truncate_attrs(max);
+                Ok(Some(DiagnosticKind::DroppedAttributes { max, tag: tag.as_name().to_owned() }))
+            }
+        }
+    }
+
+    /// Sets the maximum number of attributes of a tag, and the behaviour to
+    /// adopt when a tag has more attributes.
+    ///
+    /// This protects the tree, and the filters that match the attributes of
+    /// every tag, from malicious pages that declare thousands of attributes on
+    /// a tag. With [`AttributeOverflow::KeepFirstN`], the attributes after the
+    /// first `max` ones are dropped and a warning is sent to the callback set
+    /// with [`Self::on_warning`]. With [`AttributeOverflow::Error`], the
+    /// parsing fails.
+    ///
+    /// The limit is applied before the callback set with
+    /// [`Self::on_tag_open`] is called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = r#"<p id="a" class="b" title="c">Text</p>"#;
+    ///
+    /// let mut warnings = vec![];
+    /// let tree = Html::parse_with(
+    ///     html,
+    ///     ParseOptions::new()
+    ///         .max_attributes_per_tag(2, AttributeOverflow::KeepFirstN)
+    ///         .on_warning(|warning| warnings.push(warning.to_owned())),
+    /// )
+    /// .unwrap();
+    /// assert_eq!(tree, r#"<p id="a" class="b">Text</p>"#);
+    /// assert_eq!(warnings, [
+    ///     "Dropped the attributes of tag 'p' after the first 2 at byte 0 (line 1, column 1)."
+    /// ]);
+    ///
+    /// let options = ParseOptions::new().max_attributes_per_tag(2, AttributeOverflow::Error);
+    /// assert_eq!(
+    ///     Html::parse_with(html, options).unwrap_err().kind(),
+    ///     &ParseErrorKind::TooManyAttributes("p".to_owned())
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn max_attributes_per_tag(mut self, max: usize, overflow: AttributeOverflow) -> Self {
+        self.max_attributes = Some((max, overflow));
+        self
+    }
+
+    /// Creates a default [`ParseOptions`]
+    ///
+    /// Parsing with the default options is the same as calling
+    /// [`Html::parse`](crate::Html::parse).
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            attribute_escaping: AttributeEscaping::Lenient,
+            budget: None,
+            control_policy: ControlPolicy::Keep,
+            decode_entities: false,
+            legacy_tags: LegacyTags::Normal,
+            max_attributes: None,
+            recover: false,
+            tag_open: None,
+            templates: false,
+            text: None,
+            void_policy: VoidPolicy::Warn,
+            void_tags: None,
+            warning: None,
+        }
+    }
+
+    /// Sets a callback invoked on every opening tag, as soon as it is read.
+    ///
+    /// This includes self-closing tags, but not closing tags, comments and
+    /// doctypes.
+    ///
+    /// See [`ParseOptions`] for an example.
+    #[must_use]
+    pub fn on_tag_open<F: FnMut(&Tag) + 'hooks>(mut self, callback: F) -> Self {
+        self.tag_open = Some(Box::new(callback));
+        self
+    }
+
+    /// Sets a callback invoked on every text node, as soon as it is complete.
+    ///
+    /// The contents of the `<script>` and `<style>` tags are also texts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let mut texts = vec![];
+    /// Html::parse_with(
+    ///     "<p>Hello <strong>world</strong>!</p><!-- comment -->",
+    ///     ParseOptions::new().on_text(|text| texts.push(text.to_owned())),
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(texts, ["Hello ", "world", "!"]);
+    /// ```
+    #[must_use]
+    pub fn on_text<F: FnMut(&str) + 'hooks>(mut self, callback: F) -> Self {
+        self.text = Some(Box::new(callback));
+        self
+    }
+
+    /// Sets a callback invoked on every warning raised while parsing.
+    ///
+    /// Warnings are raised on invalid html that the parser could repair, see
+    /// [`Self::void_policy`] for instance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let mut warnings = vec![];
+    /// let html = Html::parse_with(
+    ///     "<p>a<br>b</br></p>",
+    ///     ParseOptions::new().on_warning(|warning| {
+    ///         warnings.push(warning.to_owned());
+    ///     }),
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(html, "<p>a<br>b</p>");
+    /// assert_eq!(warnings, [
+    ///     "Ignored closing tag of void element 'br' at byte 9 (line 1, column 10)."
+    /// ]);
+    /// ```
+    #[must_use]
+    pub fn on_warning<F: FnMut(&str) + 'hooks>(mut self, callback: F) -> Self {
+        self.warning = Some(Box::new(callback));
+        self
+    }
+
+    /// Returns the name of the tag `name` if its content is read as raw text,
+    /// i.e., without any tag, comment or character reference.
+    pub(crate) fn raw_text(&self, name: &str) -> Option<&'static str> {
+        let legacy = match self.legacy_tags {
+            LegacyTags::Honor => LEGACY_RAW_TEXT_TAGS.as_slice(),
+            LegacyTags::Normal => &[],
+        };
+        RAW_TEXT_TAGS.iter().chain(legacy).find(|raw| raw.eq_ignore_ascii_case(name)).copied()
+    }
+
+    /// Checks if the template placeholders must be read as opaque spans.
+    pub(super) const fn reads_templates(&self) -> bool {
+        self.templates
+    }
+
+    /// Repairs the invalid html like browsers do, instead of failing.
+    ///
+    /// In the recovery mode:
+    ///
+    /// - the tags whose closing tag is optional are closed implicitly, for
+    ///   instance a `<li>` is closed by the next `<li>`, and a `<p>` is closed
+    ///   by the next block, like a `<div>` or a `<ul>`;
+    /// - the closing tags of the elements that aren't open are ignored;
+    /// - the invalid tags, like `<a:b>` or an unfinished tag at the end of the
+    ///   document, and the closings of comments that aren't open, are read as
+    ///   text.
+    ///
+    /// The ignored closing tags and the invalid tags are reported to the
+    /// callback set with [`Self::on_warning`]. The errors that aren't caused
+    /// by the syntax, like an exceeded [`Self::budget`] or the strict
+    /// [`Self::void_policy`], are still returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = "<ul><li>First<li>Second</ul><p>Text<div>Block</div></span>";
+    /// assert!(Html::parse(html).is_err());
+    ///
+    /// let mut warnings = vec![];
+    /// let tree = Html::parse_with(
+    ///     html,
+    ///     ParseOptions::new().recover(true).on_warning(|warning| warnings.push(warning.to_owned())),
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(tree, "<ul><li>First</li><li>Second</li></ul><p>Text</p><div>Block</div>");
+    /// assert_eq!(warnings, [
+    ///     "Ignored closing tag of element 'span' that isn't open at byte 51 (line 1, column 52)."
+    /// ]);
+    /// ```
+    #[must_use]
+    pub const fn recover(mut self, recover: bool) -> Self {
+        self.recover = recover;
+        self
+    }
+
+    /// Checks if the invalid html must be repaired.
+    pub(super) const fn recovers(&self) -> bool {
+        self.recover
+    }
+
+    /// Calls the tag opening callback, if it was set.
+    pub(crate) fn tag_opened(&mut self, tag: &Tag) {
+        if let Some(callback) = &mut self.tag_open {
+            callback(tag);
+        }
+    }
+
+    /// Reads the placeholders of the template languages as opaque spans, to
+    /// parse template sources and not only rendered html.
+    ///
+    /// The placeholders are delimited by `{{ }}`, `{% %}` and `{# #}`, like
+    /// in Jinja, Django, Twig, Liquid or Handlebars, or by `<% %>`, like in
+    /// ERB or EJS. Their content isn't parsed, so it can contain `<`, `>` or
+    /// quotes:
+    ///
+    /// - in the texts, a placeholder is kept in the text node;
+    /// - in the value of an attribute, a placeholder is kept in the value;
+    /// - between the attributes of a tag, or as the unquoted value of an
+    ///   attribute, a placeholder is read in the name of an attribute without
+    ///   value, up to the next whitespace, like `{% if on %}checked{% endif %}`
+    ///   or `value={{ value }}`.
+    ///
+    /// The placeholders that aren't closed are read as html. The placeholders
+    /// can't replace the name of a tag.
+    ///
+    /// When the tree is written back, the complete placeholders are written
+    /// verbatim, without escaping their content, so that the template is kept
+    /// as it was.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let template = r#"<ul>{% for item in items %}<li class="{{ item.kind or "plain" }}" {% if item.done %}checked{% endif %}>{{ item.name }} <%= a < b %></li>{% endfor %}</ul>"#;
+    /// assert_ne!(Html::parse(template).unwrap().to_string(), template);
+    ///
+    /// let html = Html::parse_with(template, ParseOptions::new().templates(true)).unwrap();
+    /// assert_eq!(html, template);
+    ///
+    /// let li = html.find(&Filter::new().tag_name("li"));
+    /// let (tag, child) = li.as_tag().unwrap();
+    /// assert_eq!(tag.find_attr_value("class").unwrap(), r#"{{ item.kind or "plain" }}"#);
+    /// assert_eq!(child.to_string(), "{{ item.name }} <%= a < b %>");
+    /// ```
+    #[must_use]
+    pub const fn templates(mut self, templates: bool) -> Self {
+        self.templates = templates;
+        self
+    }
+
+    /// Calls the text callback on the given text, if it isn't empty.
+    ///
+    /// The character references are decoded first if needed, unless the text
+    /// is `raw`, i.e., the content of a `<script>` or a `<style>`, and the
+    /// control characters are handled depending on the policy. The text is
+    /// then cleared, to be ready for the next text node.
+    pub(super) fn text_read(&mut self, text: &mut String, raw: bool) {
+        if let Some(callback) = &mut self.text
+            && !text.is_empty()
+        {
+            if self.decode_entities && !raw {
+                callback(&clean_controls(&decode_entities(text), self.control_policy));
+            } else {
+                callback(&clean_controls(text, self.control_policy));
+            }
+        }
+        text.clear();
+    }
+
+    /// Checks if the escaping of the attribute values must be validated.
+    pub(super) fn validates_attribute_values(&self) -> bool {
+        self.attribute_escaping == AttributeEscaping::Warn
+    }
+
+    /// Handles the closing tag of a void element, depending on the policy,
+    /// and returns the diagnostic to report if it was ignored with a warning.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with the strict policy.
+    pub(super) fn void_closed(&self, name: &str) -> Result<Option<DiagnosticKind>, ParseErrorKind> {
+        match self.void_policy {
+            VoidPolicy::Lenient => Ok(None),
+            VoidPolicy::Strict => Err(ParseErrorKind::VoidClosed(name.to_owned())),
+            VoidPolicy::Warn => Ok(Some(DiagnosticKind::VoidClosed(name.to_owned()))),
+        }
+    }
+
+    /// Sets the behaviour to adopt when a void element is closed, like in
+    /// `<br>text</br>`.
+    ///
+    /// With the default policy, [`VoidPolicy::Warn`], the closing tag is
+    /// ignored and a warning is sent to the callback set with
+    /// [`Self::on_warning`]. With [`VoidPolicy::Lenient`], the closing tag is
+    /// ignored silently, and with [`VoidPolicy::Strict`], the parsing fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = "<p>a<br>b</br></p>";
+    /// assert!(Html::parse_with(html, ParseOptions::new().void_policy(VoidPolicy::Strict)).is_err());
+    /// assert_eq!(
+    ///     Html::parse_with(html, ParseOptions::new().void_policy(VoidPolicy::Lenient)).unwrap(),
+    ///     "<p>a<br>b</p>"
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn void_policy(mut self, policy: VoidPolicy) -> Self {
+        self.void_policy = policy;
+        self
+    }
+
+    /// Sets the names of the void elements, i.e., the tags that can't have any
+    /// content, and are closed as soon as they are opened.
+    ///
+    /// By default, the void elements are the ones of the html specification:
+    /// `area`, `base`, `br`, `col`, `embed`, `hr`, `img`, `input`, `link`,
+    /// `meta`, `param`, `source`, `track` and `wbr`. The given list replaces
+    /// the default one, which is useful to parse XHTML, where every element
+    /// must be closed, or custom markup with its own void elements.
+    ///
+    /// The tags closed with `/>` are always self-closing, and the closing tags
+    /// of the void elements are handled by the [`Self::void_policy`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<p>a<img src='b'>c</p>").unwrap();
+    /// assert_eq!(html.as_tag().unwrap().1.as_vec().unwrap().len(), 3);
+    ///
+    /// let xhtml = "<p>a<img src='b'>c</img></p>";
+    /// let html = Html::parse_with(xhtml, ParseOptions::new().void_tags::<_, &str>([])).unwrap();
+    /// assert_eq!(html.as_tag().unwrap().1.as_vec().unwrap().len(), 2);
+    ///
+    /// let html = Html::parse_with("<icon name='a'>b", ParseOptions::new().void_tags(["icon"]));
+    /// assert_eq!(html.unwrap().as_vec().unwrap().len(), 2);
+    /// ```
+    #[must_use]
+    pub fn void_tags<I: IntoIterator<Item = S>, S: Into<String>>(mut self, tags: I) -> Self {
+        self.void_tags = Some(tags.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Checks if the texts need to be collected for the text callback.
+    pub(super) const fn wants_text(&self) -> bool {
+        self.text.is_some()
+    }
+
+    /// Checks if the warnings need to be computed for the warning callback.
+    pub(super) const fn wants_warnings(&self) -> bool {
+        self.warning.is_some()
+    }
+
+    /// Calls the warning callback, if it was set.
+    pub(super) fn warn(&mut self, warning: &str) {
+        if let Some(callback) = &mut self.warning {
+            callback(warning);
+        }
+    }
+}
+
+impl fmt::Debug for ParseOptions<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParseOptions")
+            .field("attribute_escaping", &self.attribute_escaping)
+            .field("budget", &self.budget)
+            .field("control_policy", &self.control_policy)
+            .field("decode_entities", &self.decode_entities)
+            .field("legacy_tags", &self.legacy_tags)
+            .field("max_attributes", &self.max_attributes)
+            .field("recover", &self.recover)
+            .field("tag_open", &self.tag_open.is_some())
+            .field("text", &self.text.is_some())
+            .field("void_policy", &self.void_policy)
+            .field("void_tags", &self.void_tags)
+            .field("warning", &self.warning.is_some())
+            .finish()
+    }
+}
+
+impl Default for ParseOptions<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,269 @@
+//! Module to parse large documents on multiple threads.
+//!
+//! A quick pre-scan of the document looks for the boundaries between its
+//! top-level sections, i.e., the children of the `<body>`, or the root nodes
+//! of the document when it doesn't have a `<body>`. The sections are then
+//! parsed independently and the resulting trees are merged back into one.
+
+use core::iter::once;
+use core::mem::take;
+use std::thread;
+
+use crate::errors::safe_unreachable;
+use crate::types::tag::{RAW_TEXT_TAGS, VOID_TAGS, has_name};
+use crate::{Html, ParseError};
+
+impl Html {
+    /// Parses an HTML string into a Dom tree, on multiple threads.
+    ///
+    /// This is an experimental mode for very large documents. A quick pre-scan
+    /// splits the input between the top-level sections, i.e., between the
+    /// children of the `<body>`, or between the root nodes when there is no
+    /// `<body>`. The sections are grouped in at most `threads` parts of similar
+    /// sizes, parsed on different threads, and the trees are merged.
+    ///
+    /// The pre-scan only splits the input when it can prove that the
+    /// boundaries are safe, i.e., that every tag is explicitly closed by a
+    /// matching closing tag, and that the syntax isn't ambiguous. Otherwise,
+    /// or if one of the sections fails to parse, the whole document is parsed
+    /// sequentially with [`Html::parse`]. The result is thus always the same
+    /// as the one of [`Html::parse`], and the tags are still numbered in the
+    /// order of the document (see [`NodeId`](crate::NodeId)).
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error when the input HTML's syntax is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = "<html><head><title>Report</title></head><body><h1>Sales</h1><p>Up</p><p>Down</p></\
+    ///             body></html>";
+    /// let tree = Html::parse_parallel(html, 2).unwrap();
+    /// assert_eq!(tree, Html::parse(html).unwrap());
+    ///
+    /// // The unclosed `<p>` makes the boundaries unsafe: the document is parsed sequentially.
+    /// let unclosed = "<div>First</div><p>Second<div>Third</div>";
+    /// assert_eq!(Html::parse_parallel(unclosed, 2).unwrap(), Html::parse(unclosed).unwrap());
+    /// ```
+    pub fn parse_parallel(html: &str, threads: usize) -> Result<Self, ParseError> {
+        let Some(sections) = Scanner::default().scan(html) else {
+            return Self::parse(html);
+        };
+        let cuts = sections.cuts(threads);
+        let Some(&first) = cuts.first() else {
+            return Self::parse(html);
+        };
+        let skeleton = format!(
+            "{}{}",
+            html.get(..first).unwrap_or_default(),
+            html.get(sections.end..).unwrap_or_default()
+        );
+        let parts = cuts
+            .iter()
+            .zip(cuts.iter().skip(1).chain(once(&sections.end)))
+            .map(|(&start, &end)| html.get(start..end).unwrap_or_default())
+            .collect::<Vec<_>>();
+        let (skeleton_result, parts_result) = thread::scope(|scope| {
+            #[expect(clippy::needless_collect, reason = "threads are spawned before the skeleton")]
+            let handles =
+                parts.iter().map(|part| scope.spawn(|| Self::parse(part))).collect::<Vec<_>>();
+            let skeleton_tree = Self::parse(&skeleton);
+            let part_trees = handles
+                .into_iter()
+                .map(|handle| handle.join().ok()?.ok())
+                .collect::<Option<Vec<_>>>();
+            (skeleton_tree, part_trees)
+        });
+        let (Ok(mut tree), Some(part_trees)) = (skeleton_result, parts_result) else {
+            return Self::parse(html);
+        };
+        if sections.body {
+            match tree.body_mut() {
+                Self::Tag { child, .. } => merge(child, part_trees),
+                Self::Comment(_)
+                | Self::Doctype { .. }
+                | Self::Empty
+                | Self::Text(_)
+                | Self::Vec(_) => safe_unreachable!("body_mut returns the body tag"),
+            }
+        } else {
+            merge(&mut tree, part_trees);
+        }
+        tree.renew_ids();
+        Ok(tree)
+    }
+}
+
+/// State of the pre-scan of a document, that looks for the boundaries of its
+/// top-level sections.
+#[derive(Default)]
+struct Scanner<'html> {
+    /// Depth of the `<body>` tag, while it is open.
+    body_depth: Option<usize>,
+    /// Position of the closing `</body>` tag, once it was found.
+    body_end: Option<usize>,
+    /// Positions of the opening tags of the children of the `<body>`.
+    body_starts: Vec<usize>,
+    /// Positions of the opening tags at the root of the document.
+    root_starts: Vec<usize>,
+    /// Names of the tags that are currently open.
+    stack: Vec<&'html str>,
+}
+
+impl<'html> Scanner<'html> {
+    /// Handles a closing tag found at `start`, and returns the position
+    /// after it.
+    fn close(&mut self, start: usize, rest: &str) -> Option<usize> {
+        let len = rest.find('>')?;
+        let name = rest.get(2..len)?;
+        if self.stack.pop()? != name {
+            return None;
+        }
+        if self.body_depth == Some(self.stack.len()) {
+            self.body_depth = None;
+            self.body_end = Some(start);
+        }
+        start.checked_add(len)?.checked_add(1)
+    }
+
+    /// Handles an opening tag found at `start`, and returns the position
+    /// after it, or after its closing tag for tags with raw text content.
+    fn open(&mut self, html: &'html str, start: usize) -> Option<usize> {
+        let rest = html.get(start..)?;
+        let (name, len, self_closing) = open_tag(rest)?;
+        if self.stack.is_empty() {
+            self.root_starts.push(start);
+        }
+        if self.body_depth.and_then(|depth| depth.checked_add(1)) == Some(self.stack.len()) {
+            self.body_starts.push(start);
+        }
+        let after = start.checked_add(len)?;
+        if self_closing || has_name(&VOID_TAGS, name) {
+            return Some(after);
+        }
+        if has_name(&RAW_TEXT_TAGS, name) {
+            let closing = format!("</{name}>");
+            let content_len = html.get(after..)?.find(&closing)?;
+            let end = after.checked_add(content_len)?;
+            if html.get(after..end)?.contains('<') {
+                return None;
+            }
+            return end.checked_add(closing.len());
+        }
+        if name == "body" {
+            if self.body_depth.is_some() || self.body_end.is_some() {
+                return None;
+            }
+            self.body_depth = Some(self.stack.len());
+        }
+        self.stack.push(name);
+        Some(after)
+    }
+
+    /// Pre-scans the document to find the boundaries of its top-level
+    /// sections.
+    ///
+    /// Returns `None` if the boundaries can't be proven safe, e.g., when a tag
+    /// isn't closed, when a closing tag doesn't match the open tag, or when a
+    /// text contains dashes that the parser could read as the end of a
+    /// comment, or ends with a dash that a cut would leave at the end of a
+    /// section.
+    fn scan(mut self, html: &'html str) -> Option<Sections> {
+        let mut index: usize = 0;
+        while let Some(offset) = html.get(index..)?.find('<') {
+            let start = index.checked_add(offset)?;
+            let text = html.get(index..start)?;
+            if text.contains("--") || text.ends_with('-') {
+                return None;
+            }
+            let rest = html.get(start..)?;
+            index = if let Some(comment) = rest.strip_prefix("<!--") {
+                start.checked_add(comment.find("-->")?)?.checked_add("<!---->".len())?
+            } else if rest.starts_with("<!") {
+                start.checked_add(rest.find('>')?)?.checked_add(1)?
+            } else if rest.starts_with("</") {
+                self.close(start, rest)?
+            } else {
+                self.open(html, start)?
+            };
+        }
+        if html.get(index..)?.contains("--") || !self.stack.is_empty() {
+            return None;
+        }
+        Some(match self.body_end {
+            Some(end) => Sections { body: true, end, starts: self.body_starts },
+            None => Sections { body: false, end: html.len(), starts: self.root_starts },
+        })
+    }
+}
+
+/// Boundaries of the top-level sections of a document, found by the
+/// [`Scanner`].
+struct Sections {
+    /// Whether the sections are the children of the `<body>`, or the root
+    /// nodes of the document.
+    body: bool,
+    /// End of the split content, i.e., the position of the `</body>` closing
+    /// tag, or the end of the document.
+    end: usize,
+    /// Positions of the opening tags that start a section.
+    starts: Vec<usize>,
+}
+
+impl Sections {
+    /// Chooses the positions where the document is cut, so that the threads
+    /// receive parts of similar sizes.
+    ///
+    /// The content before the first cut is left to the skeleton of the
+    /// document, so no cut is made at the first section.
+    fn cuts(&self, threads: usize) -> Vec<usize> {
+        let Some(&first) = self.starts.first().filter(|_| threads > 1) else { return vec![] };
+        let size = self.end.saturating_sub(first).checked_div(threads).unwrap_or_default();
+        let mut last = first;
+        let mut cuts = vec![];
+        for &start in self.starts.iter().skip(1) {
+            if start.saturating_sub(last) >= size {
+                cuts.push(start);
+                last = start;
+            }
+        }
+        cuts
+    }
+}
+
+/// Merges the trees of the parsed parts after the content of `tree`.
+fn merge(tree: &mut Html, mut parts: Vec<Html>) {
+    parts.insert(0, take(tree));
+    *tree = Html::concat(parts);
+}
+
+/// Parses the opening tag at the beginning of `rest`.
+///
+/// # Returns
+///
+/// The name of the tag, the length of the opening tag and whether the tag is
+/// self-closing, or `None` if the tag can't be read safely.
+fn open_tag(rest: &str) -> Option<(&str, usize, bool)> {
+    let name_len = rest
+        .get(1..)?
+        .find(|ch: char| !ch.is_ascii_alphanumeric() && ch != '-')
+        .filter(|len| *len > 0)?;
+    let name = rest.get(1..=name_len)?;
+    let mut quote = None;
+    for (idx, ch) in rest.char_indices().skip(name_len.checked_add(1)?) {
+        match (quote, ch) {
+            (Some(open), _) if open == ch => quote = None,
+            (None, '"' | '\'') => quote = Some(ch),
+            (None, '<') => return None,
+            (None, '>') => {
+                let self_closing = rest.get(..idx)?.ends_with('/');
+                return Some((name, idx.checked_add(1)?, self_closing));
+            }
+            (Some(_) | None, _) => (),
+        }
+    }
+    None
+}
@@ -0,0 +1,68 @@
+//! Module to define the browser-like rules that repair invalid html, see
+//! [`ParseOptions::recover`](crate::ParseOptions::recover).
+
+use crate::types::html_builder::TreeBuilder;
+
+/// Tags that implicitly close an open `<p>`.
+const PARAGRAPH_CLOSERS: [&str; 30] = [
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hgroup",
+    "hr",
+    "main",
+    "menu",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "ul",
+];
+
+/// Closes the open tags that are implicitly closed by the opening of the tag
+/// `name`.
+///
+/// For instance, a `<li>` closes the previous `<li>` if it is still open.
+pub fn close_implied<'input, T: TreeBuilder<'input>>(tree: &mut T, name: &str) {
+    while let Some(open) =
+        tree.current_tag().filter(|open| is_closed_by(open, name)).map(str::to_owned)
+    {
+        if tree.close_tag(&open).is_err() {
+            break;
+        }
+    }
+}
+
+/// Checks if the opening of the tag `name` implicitly closes the open tag
+/// `open`.
+fn is_closed_by(open: &str, name: &str) -> bool {
+    match open {
+        "p" => PARAGRAPH_CLOSERS.contains(&name),
+        "li" => name == "li",
+        "dd" | "dt" => matches!(name, "dd" | "dt"),
+        "option" => matches!(name, "optgroup" | "option"),
+        "optgroup" => name == "optgroup",
+        "td" | "th" => matches!(name, "tbody" | "td" | "tfoot" | "th" | "thead" | "tr"),
+        "tr" => matches!(name, "tbody" | "tfoot" | "thead" | "tr"),
+        "tbody" | "tfoot" | "thead" => matches!(name, "tbody" | "tfoot" | "thead"),
+        _ => false,
+    }
+}
@@ -0,0 +1,65 @@
+//! Module to find the next special character in the input.
+//!
+//! The parser reads the texts, the comments and the attribute values by runs
+//! of characters, until the next character that changes its state, like a `<`
+//! or a quote. With the `simd` feature, these characters are searched 8 bytes
+//! at a time, with the bytes of a word compared all at once. Otherwise, the
+//! bytes are compared one by one.
+
+use core::str::Chars;
+
+/// Word with the lowest bit of every byte set.
+#[cfg(feature = "simd")]
+const LOW_BITS: u64 = 0x0101_0101_0101_0101;
+
+/// Word with the highest bit of every byte set.
+#[cfg(feature = "simd")]
+const HIGH_BITS: u64 = 0x8080_8080_8080_8080;
+
+/// Finds the position of the first byte of `haystack` that is one of the
+/// `needles`, or the length of `haystack` if there isn't any.
+///
+/// The needles must be ASCII characters, so that the position is a character
+/// boundary.
+#[cfg(feature = "simd")]
+fn find_any(haystack: &str, needles: &[u8]) -> usize {
+    let (words, remainder) = haystack.as_bytes().as_chunks::<8>();
+    let mut offset: usize = 0;
+    for word in words {
+        #[expect(clippy::host_endian_bytes, reason = "the order of the bytes is irrelevant")]
+        let bits = u64::from_ne_bytes(*word);
+        let found = needles.iter().any(|needle| {
+            let xored = bits ^ LOW_BITS.wrapping_mul(u64::from(*needle));
+            xored.wrapping_sub(LOW_BITS) & !xored & HIGH_BITS != 0
+        });
+        if found {
+            return offset.saturating_add(find_any_scalar(word, needles));
+        }
+        offset = offset.saturating_add(word.len());
+    }
+    offset.saturating_add(find_any_scalar(remainder, needles))
+}
+
+/// Finds the position of the first byte of `haystack` that is one of the
+/// `needles`, or the length of `haystack` if there isn't any.
+///
+/// The needles must be ASCII characters, so that the position is a character
+/// boundary.
+#[cfg(not(feature = "simd"))]
+fn find_any(haystack: &str, needles: &[u8]) -> usize {
+    find_any_scalar(haystack.as_bytes(), needles)
+}
+
+/// Finds the position of the first byte of `haystack` that is one of the
+/// `needles`, by comparing the bytes one by one.
+fn find_any_scalar(haystack: &[u8], needles: &[u8]) -> usize {
+    haystack.iter().position(|byte| needles.contains(byte)).unwrap_or(haystack.len())
+}
+
+/// Consumes the characters of `chars` until the next one of the `needles`.
+///
+/// The needle itself isn't consumed.
+pub fn skip_until(chars: &mut Chars<'_>, needles: &[u8]) {
+    let rest = chars.as_str();
+    *chars = rest.split_at(find_any(rest, needles)).1.chars();
+}
@@ -0,0 +1,194 @@
+//! Module to parse an html string that is received by chunks.
+
+use core::mem::take;
+
+use super::bytes::Utf8Decoder;
+use super::error::ParseError;
+use super::{ParseState, parse_tree};
+use crate::types::html_builder::HtmlBuilder;
+use crate::{Html, ParseOptions};
+
+/// Incremental parser, that builds an [`Html`] tree from the chunks of a
+/// document.
+///
+/// This is useful when the document arrives over the network: every chunk is
+/// parsed as soon as it is received with [`Parser::feed`], without buffering
+/// the whole document. Only the end of a chunk that could continue in the next
+/// one, like an unfinished tag, is kept until the next chunk. The tree is
+/// returned by [`Parser::finish`], once the whole document was fed.
+///
+/// The chunks can be split anywhere: the resulting tree is the same as the
+/// one returned by [`Html::parse_with`] on the whole document. The callbacks
+/// of the [`ParseOptions`] are called as soon as the nodes are parsed.
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// let mut parser = Parser::new();
+/// parser.feed("<ul><li cla").unwrap();
+/// parser.feed("ss='first'>First</li><l").unwrap();
+/// parser.feed("i>Second</li></ul>").unwrap();
+/// let html = parser.finish().unwrap();
+///
+/// assert_eq!(html, r#"<ul><li class='first'>First</li><li>Second</li></ul>"#);
+/// ```
+///
+/// ```
+/// use html_filter::*;
+///
+/// let mut tags = vec![];
+/// let mut parser = Parser::with_options(
+///     ParseOptions::new().on_tag_open(|tag| tags.push(tag.as_name().to_owned())),
+/// );
+/// parser.feed("<div><p>Hello</p>").unwrap();
+/// parser.feed("<p>World</p></div>").unwrap();
+/// parser.finish().unwrap();
+///
+/// assert_eq!(tags, ["div", "p", "p"]);
+/// ```
+#[derive(Debug, Default)]
+pub struct Parser<'hooks> {
+    /// End of the previous chunks that wasn't parsed yet, because it could
+    /// continue in the next chunk.
+    buffer: String,
+    /// Decoder of the chunks fed with [`Parser::feed_bytes`].
+    decoder: Utf8Decoder,
+    /// First error that occurred, returned by all the following calls.
+    error: Option<ParseError>,
+    /// Options of the parser, see [`ParseOptions`].
+    options: ParseOptions<'hooks>,
+    /// State of the parser at the end of the parsed chunks.
+    state: ParseState,
+    /// Tree built from the parsed chunks.
+    tree: HtmlBuilder,
+}
+
+impl<'hooks> Parser<'hooks> {
+    /// Parses a new chunk of the document.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error when the document's syntax is invalid.
+    /// The parser can't be used after an error: all the following calls
+    /// return the same error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let mut parser = Parser::new();
+    /// parser.feed("<p>Unopened comment</p>").unwrap();
+    /// assert!(parser.feed("--> <p>").is_err());
+    /// assert!(parser.feed("</p>").is_err());
+    /// assert!(parser.finish().is_err());
+    /// ```
+    pub fn feed(&mut self, chunk: &str) -> Result<(), ParseError> {
+        if self.buffer.is_empty() {
+            let parsed = self.parse(chunk, false)?;
+            self.buffer.push_str(chunk.get(parsed..).unwrap_or_default());
+        } else {
+            let mut input = take(&mut self.buffer);
+            input.push_str(chunk);
+            let parsed = self.parse(&input, false)?;
+            input.drain(..parsed);
+            self.buffer = input;
+        }
+        Ok(())
+    }
+
+    /// Parses a new chunk of the document, given as bytes.
+    ///
+    /// The bytes don't need to be valid UTF-8: the invalid sequences are
+    /// replaced and reported as with [`Html::parse_bytes_with`], with their
+    /// positions in the whole document. The chunks can be split in the middle
+    /// of a character.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error when the document's syntax is invalid,
+    /// see [`Parser::feed`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let mut warnings = vec![];
+    /// let mut parser = Parser::with_options(
+    ///     ParseOptions::new().on_warning(|warning| warnings.push(warning.to_owned())),
+    /// );
+    /// parser.feed_bytes(b"<p>5 \xe2\x82").unwrap();
+    /// parser.feed_bytes(b"\xac or 5 \xa4</p>").unwrap();
+    /// let html = parser.finish().unwrap();
+    ///
+    /// assert_eq!(html, "<p>5 \u{20ac} or 5 \u{fffd}</p>");
+    /// assert_eq!(warnings, [
+    ///     "Replaced the invalid UTF-8 sequence [A4] at byte 14 (line 1, column 13) with U+FFFD."
+    /// ]);
+    /// ```
+    pub fn feed_bytes(&mut self, chunk: &[u8]) -> Result<(), ParseError> {
+        let decoded = self.decoder.decode(chunk, false, &mut self.options);
+        self.feed(&decoded)
+    }
+
+    /// Parses the end of the document, and returns the tree.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error when the document's syntax is invalid,
+    /// for instance when the last chunk ends in the middle of a tag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let mut parser = Parser::new();
+    /// parser.feed("<p>Unfinished tag</p><a href='").unwrap();
+    /// assert!(parser.finish().is_err());
+    /// ```
+    pub fn finish(mut self) -> Result<Html, ParseError> {
+        let mut input = take(&mut self.buffer);
+        input.push_str(&self.decoder.decode(&[], true, &mut self.options));
+        self.parse(&input, true)?;
+        Ok(self.tree.into_html_with(&self.options))
+    }
+
+    /// Creates a parser with the default options.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses the input into the tree, and returns the number of bytes that
+    /// were parsed.
+    ///
+    /// The positions of the errors are relative to the whole document.
+    fn parse(&mut self, input: &str, last: bool) -> Result<usize, ParseError> {
+        if let Some(error) = &self.error {
+            return Err(error.clone());
+        }
+        match parse_tree(&mut self.tree, input, &mut self.options, &mut self.state, last) {
+            Ok(parsed) => {
+                self.state.start.advance(input.get(..parsed).unwrap_or_default());
+                Ok(parsed)
+            }
+            Err(mut error) => {
+                error.shift(&self.state.start);
+                self.error = Some(error.clone());
+                Err(error)
+            }
+        }
+    }
+
+    /// Creates a parser with custom options.
+    ///
+    /// See [`ParseOptions`] for the list of available options.
+    #[must_use]
+    pub fn with_options(options: ParseOptions<'hooks>) -> Self {
+        Self { options, ..Self::default() }
+    }
+}
@@ -3,10 +3,15 @@
 //! This module is used when a <d is found in a html string. It can also mean an
 //! opening comment.
 
+use alloc::borrow::Cow;
+use core::ops::Range;
 use core::str::Chars;
 
-use super::AUTO_CLOSING_TAGS;
-use crate::types::tag::{Attribute, Tag, TagBuilder};
+use super::error::ParseErrorKind;
+use super::scan::skip_until;
+use super::template::placeholder_len;
+use crate::types::html_ref::{AttributeRef, Run, TagRef};
+use crate::types::tag::TagBuilder;
 
 /// State that informs on position of the '/' closing character.
 ///
@@ -30,35 +35,66 @@ enum Close {
     None,
 }
 
-impl TagBuilder {
+impl<'input> TagBuilder<'input> {
     /// Parses an opening tag, or an opening comment.
     ///
+    /// The names and attributes of the tag are borrowed from the input. The
+    /// attributes are collected in `attrs`, that is reused from a tag to the
+    /// next so that it ends up large enough for any tag of the document. The
+    /// returned tag then gets an exactly-sized slice of attributes.
+    ///
+    /// With `templates`, the placeholders of the template languages are read
+    /// as opaque spans, see
+    /// [`ParseOptions::templates`](crate::ParseOptions::templates). A
+    /// placeholder is kept in the value of an attribute, and a placeholder
+    /// between the attributes, or as an unquoted value, is read as an
+    /// attribute without value.
+    ///
     /// # Returns
     ///
     /// A [`TagBuilder`] that indicates the type of the tag/comment that was
     /// found.
-    pub fn parse(chars: &mut Chars<'_>) -> Result<Self, String> {
+    pub fn parse(
+        chars: &mut Chars<'input>,
+        attrs: &mut Vec<AttributeRef<'input>>,
+        templates: bool,
+    ) -> Result<Self, ParseErrorKind> {
+        let input = chars.as_str();
         let mut state = TagParsingState::default();
         let mut close = Close::None;
         let mut bang = false;
         let mut dash = false;
-        let mut tag = String::new();
-        let mut attrs = vec![];
+        let mut tag = Run::default();
+        attrs.clear();
 
-        for ch in chars.by_ref() {
+        loop {
+            let start = input.len().saturating_sub(chars.as_str().len());
+            let Some(ch) = chars.next() else { break };
+            let end = start.saturating_add(ch.len_utf8());
+            let slice = |range: Range<usize>| input.get(range).unwrap_or_default();
+            if templates
+                && matches!(ch, '{' | '<')
+                && state != TagParsingState::Name
+                && let Some(len) = placeholder_len(slice(start..input.len()))
+            {
+                let placeholder = start..start.saturating_add(len);
+                *chars = slice(placeholder.end..input.len()).chars();
+                state = state.read_placeholder(placeholder, chars);
+                continue;
+            }
             state = match (state, ch) {
                 (TagParsingState::Name, '-') if dash => return Ok(Self::OpenComment),
                 (old @ TagParsingState::Name, '-') if bang => {
                     dash = true;
                     old
                 }
-                _ if dash => return invalid_err('-', "doctype"),
+                _ if dash => return Err(ParseErrorKind::InvalidDoctype('-')),
                 // closing
                 (TagParsingState::Name | TagParsingState::AttributeNone, '>') =>
-                    return Self::return_tag(bang, close, tag, attrs),
+                    return Self::return_tag(bang, close, tag.into_cow(input), attrs),
                 (TagParsingState::AttributeName(attr), '>') => {
-                    attrs.push(Attribute::from(attr));
-                    return Self::return_tag(bang, close, tag, attrs);
+                    attrs.push(AttributeRef::NameNoValue(slice(attr..start)));
+                    return Self::return_tag(bang, close, tag.into_cow(input), attrs);
                 }
                 (old @ TagParsingState::Name, '/') if tag.is_empty() => {
                     close = Close::Before;
@@ -69,9 +105,9 @@ impl TagBuilder {
                     old
                 }
                 (TagParsingState::AttributeName(attr), '/') => {
-                    attrs.push(Attribute::from(attr));
+                    attrs.push(AttributeRef::NameNoValue(slice(attr..start)));
                     close = Close::After;
-                    TagParsingState::AttributeName(String::new())
+                    TagParsingState::AttributeName(end)
                 }
                 // name
                 (old @ TagParsingState::Name, '!') =>
@@ -79,54 +115,51 @@ impl TagBuilder {
                         bang = true;
                         old
                     } else {
-                        return invalid_err(ch, "tag name");
+                        return Err(ParseErrorKind::InvalidTagName(ch));
                     },
-                (TagParsingState::Name, ':') => return invalid_err(ch, "tag name"),
+                (TagParsingState::Name, ':') => return Err(ParseErrorKind::InvalidTagName(ch)),
                 (TagParsingState::Name, _) if ch.is_whitespace() => TagParsingState::AttributeNone,
                 (old @ TagParsingState::Name, _) => {
-                    tag.push(ch);
+                    tag.push(input, start..end);
                     old
                 }
                 // attribute none: none in progress
                 (old @ TagParsingState::AttributeNone, _) if ch.is_whitespace() => old,
-                (TagParsingState::AttributeNone, _) =>
-                    TagParsingState::AttributeName(ch.to_string()),
+                (TagParsingState::AttributeNone, _) => TagParsingState::AttributeName(start),
                 // attribute name
-                (TagParsingState::AttributeName(attr), '=') => TagParsingState::AttributeEq(attr),
+                (TagParsingState::AttributeName(attr), '=') =>
+                    TagParsingState::AttributeEq(attr..start),
                 (TagParsingState::AttributeName(attr), _) if ch.is_whitespace() => {
-                    attrs.push(Attribute::from(attr));
+                    attrs.push(AttributeRef::NameNoValue(slice(attr..start)));
                     TagParsingState::AttributeNone
                 }
-                (TagParsingState::AttributeName(mut attr), _) => {
-                    attr.push(ch);
-                    TagParsingState::AttributeName(attr)
-                }
                 // attribute after `=`
-                (TagParsingState::AttributeEq(name), quote @ ('"' | '\'')) =>
-                    TagParsingState::AttributeValue {
-                        double: quote == '"',
-                        name,
-                        value: String::new(),
-                    },
+                (TagParsingState::AttributeEq(name), quote @ ('"' | '\'')) => {
+                    skip_until(chars, value_needles(quote == '"', templates));
+                    TagParsingState::AttributeValue { double: quote == '"', name, value: end }
+                }
                 (TagParsingState::AttributeEq(_), _) =>
-                    return Err(format!(
-                        "Invalid character '{ch}': expected '\'' or '\"' after '=' sign."
-                    )),
+                    return Err(ParseErrorKind::MissingQuote(ch)),
                 // attribute value
                 (TagParsingState::AttributeValue { double, name, value }, _)
                     if double && ch == '"' || !double && ch == '\'' =>
                 {
-                    attrs.push(Attribute::NameValue { double_quote: double, name, value });
+                    attrs.push(AttributeRef::NameValue {
+                        double_quote: double,
+                        name: slice(name),
+                        value: slice(value..start),
+                    });
                     TagParsingState::AttributeNone
                 }
-
-                (TagParsingState::AttributeValue { double, name, mut value }, _) => {
-                    value.push(ch);
-                    TagParsingState::AttributeValue { double, name, value }
-                }
+                // name or value in progress
+                (
+                    old @ (TagParsingState::AttributeName(_)
+                    | TagParsingState::AttributeValue { .. }),
+                    _,
+                ) => old,
             };
         }
-        Err("EOF: Missing closing '>'.".to_owned())
+        Err(ParseErrorKind::UnclosedTag)
     }
 
     /// Builds a [`TagBuilder`] with the parsing information from
@@ -134,37 +167,35 @@ impl TagBuilder {
     fn return_tag(
         doctype: bool,
         close: Close,
-        name: String,
-        mut attrs: Vec<Attribute>,
-    ) -> Result<Self, String> {
+        name: Cow<'input, str>,
+        attrs: &mut Vec<AttributeRef<'input>>,
+    ) -> Result<Self, ParseErrorKind> {
         Ok(match (doctype, close) {
-            (true, Close::After) => return invalid_err('/', "doctype"),
-            (true, Close::Before) => return invalid_err('!', "closing tag"),
+            (true, Close::After) => return Err(ParseErrorKind::InvalidDoctype('/')),
+            (true, Close::Before) => return Err(ParseErrorKind::InvalidClosingTag('!')),
             (true, Close::None) => {
                 if attrs.len() >= 2 {
-                    return Err("Doctype expected at most one attribute.".to_owned());
+                    return Err(ParseErrorKind::DoctypeAttributes);
                 }
                 let attr = if let Some(attr) = attrs.pop() {
                     match attr {
-                        Attribute::NameNoValue(prefix_name) => Some(prefix_name),
-                        Attribute::NameValue { .. } =>
-                            return Err("Doctype attribute must not have a value.".to_owned()),
+                        AttributeRef::NameNoValue(prefix_name) => Some(prefix_name),
+                        AttributeRef::NameValue { .. } => return Err(ParseErrorKind::DoctypeValue),
                     }
                 } else {
                     None
                 };
                 Self::Doctype { name, attr }
             }
-            (false, Close::None) if AUTO_CLOSING_TAGS.contains(&name.as_str()) =>
-                Self::OpenClose(Tag::from((name, attrs.into_boxed_slice()))),
-            (false, Close::None) => Self::Open(Tag::from((name, attrs.into_boxed_slice()))),
+            (false, Close::None) => Self::Open(TagRef::from((name, attrs.drain(..).collect()))),
             (false, Close::Before) => {
                 if !attrs.is_empty() {
-                    return Err("Closing tags don't support attributes.".to_owned());
+                    return Err(ParseErrorKind::ClosingTagAttributes);
                 }
                 Self::Close(name)
             }
-            (false, Close::After) => Self::OpenClose(Tag::from((name, attrs.into_boxed_slice()))),
+            (false, Close::After) =>
+                Self::OpenClose(TagRef::from((name, attrs.drain(..).collect()))),
         })
     }
 }
@@ -191,23 +222,57 @@ enum TagParsingState {
     ///
     /// Waiting for character to continue the name, the end of the tag or a `=`
     /// sign to assign a value to this attribute.
-    AttributeName(String),
+    ///
+    /// Contains the position of the start of the name.
+    AttributeName(usize),
     /// Parser read the `=` sign after an attribute name.
     ///
     /// Waiting for a `'` or `"` to assign a value to the last attribute.
-    AttributeEq(String),
+    ///
+    /// Contains the range of the name of the attribute.
+    AttributeEq(Range<usize>),
     /// Parser currently reading the value of an attribute.
     AttributeValue {
         /// Whether the value was started with `"` or `'`.
         double: bool,
-        /// Name of the attribute, read-only.
-        name: String,
-        /// Current value, in the process of being built.
-        value: String,
+        /// Range of the name of the attribute.
+        name: Range<usize>,
+        /// Position of the start of the value.
+        value: usize,
     },
 }
 
-/// Function to format the errors for an invalid character in a given context.
-fn invalid_err<T>(ch: char, ctx: &str) -> Result<T, String> {
-    Err(format!("Invalid character '{ch}' in {ctx}."))
+impl TagParsingState {
+    /// Reads a template placeholder found inside a tag, see
+    /// [`TagBuilder::parse`].
+    ///
+    /// A placeholder between the attributes starts the name of an attribute,
+    /// and an unquoted placeholder after a `=` continues the name of the
+    /// attribute, so that the placeholder and the characters that follow it
+    /// are kept in an attribute without value.
+    ///
+    /// The characters are already moved after the `placeholder`.
+    fn read_placeholder(self, placeholder: Range<usize>, chars: &mut Chars<'_>) -> Self {
+        match self {
+            Self::AttributeNone => Self::AttributeName(placeholder.start),
+            Self::AttributeEq(name) => Self::AttributeName(name.start),
+            Self::AttributeValue { double, name, value } => {
+                skip_until(chars, value_needles(double, true));
+                Self::AttributeValue { double, name, value }
+            }
+            old @ (Self::Name | Self::AttributeName(_)) => old,
+        }
+    }
+}
+
+/// Returns the characters that can end the run of characters of an
+/// attribute value, i.e., its closing quote, and the first characters of the
+/// placeholders if the `templates` are read.
+const fn value_needles(double: bool, templates: bool) -> &'static [u8] {
+    match (double, templates) {
+        (true, false) => b"\"",
+        (true, true) => b"\"{<",
+        (false, false) => b"'",
+        (false, true) => b"'{<",
+    }
 }
@@ -0,0 +1,80 @@
+//! Module to find the placeholders of the template languages, see
+//! [`ParseOptions::templates`](crate::ParseOptions::templates).
+//!
+//! The placeholders are read as opaque spans: their content isn't parsed, so
+//! they can contain `<`, `>` or quotes. They are also written back verbatim,
+//! without escaping their content.
+
+use alloc::borrow::Cow;
+
+use crate::entities::escape;
+
+/// Opening and closing delimiters of the placeholders.
+///
+/// They are the delimiters of Jinja, Django, Twig, Liquid, Handlebars and
+/// Mustache (`{{ }}`, `{% %}` and `{# #}`), and of ERB and EJS (`<% %>`).
+const DELIMITERS: [(&str, &str); 4] = [("{{", "}}"), ("{#", "#}"), ("{%", "%}"), ("<%", "%>")];
+
+/// Placeholder found at the start of a text.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Placeholder {
+    /// Complete placeholder, with its length in bytes, delimiters included
+    Closed(usize),
+    /// Placeholder whose closing delimiter isn't in the text
+    Unclosed,
+}
+
+/// Finds the placeholder at the start of a text, if there is one.
+///
+/// A text that is only the start of an opening delimiter, like `{`, is an
+/// unclosed placeholder, as the delimiter could continue after the text.
+pub fn placeholder(text: &str) -> Option<Placeholder> {
+    if !text.is_empty()
+        && DELIMITERS
+            .iter()
+            .any(|(opening, _)| opening.len() > text.len() && opening.starts_with(text))
+    {
+        return Some(Placeholder::Unclosed);
+    }
+    let (opening, closing) = DELIMITERS.iter().find(|(opening, _)| text.starts_with(opening))?;
+    let content = text.get(opening.len()..).unwrap_or_default();
+    Some(content.find(closing).map_or(Placeholder::Unclosed, |end| {
+        Placeholder::Closed(opening.len().saturating_add(end).saturating_add(closing.len()))
+    }))
+}
+
+/// Returns the length of the complete placeholder at the start of a text, if
+/// there is one.
+pub fn placeholder_len(text: &str) -> Option<usize> {
+    match placeholder(text)? {
+        Placeholder::Closed(len) => Some(len),
+        Placeholder::Unclosed => None,
+    }
+}
+
+/// Escapes the `special` characters of a text like [`escape`], except in the
+/// complete template placeholders, that are kept verbatim.
+///
+/// This allows to write back the texts and the attribute values read with
+/// [`ParseOptions::templates`](crate::ParseOptions::templates) as they were in
+/// the input.
+pub fn escape_outside_placeholders<'text>(text: &'text str, special: &[char]) -> Cow<'text, str> {
+    if !text.contains(['{', '<']) {
+        return escape(text, special);
+    }
+    let mut escaped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(idx) = rest.find(['{', '<']) {
+        let (before, from) = rest.split_at(idx);
+        escaped.push_str(&escape(before, special));
+        let (span, after) = from.split_at(placeholder_len(from).unwrap_or(1));
+        if span.len() == 1 {
+            escaped.push_str(&escape(span, special));
+        } else {
+            escaped.push_str(span);
+        }
+        rest = after;
+    }
+    escaped.push_str(&escape(rest, special));
+    Cow::Owned(escaped)
+}
@@ -0,0 +1,255 @@
+//! Module to split an html string into tokens, without building a tree.
+//!
+//! The [`Tokenizer`] reads every tag with the same tag reader as the parser,
+//! but splits the texts, the comments and the raw texts itself, like
+//! [`Html::parse`](crate::Html::parse) with the default
+//! [`ParseOptions`](crate::ParseOptions). It doesn't check that the tags are
+//! balanced. This makes it possible to build other tree models, or syntax
+//! highlighters, on top of it.
+
+use alloc::borrow::Cow;
+use core::ops::Range;
+
+use super::error::{ParseError, ParseErrorKind};
+use super::scan::skip_until;
+use super::tag_error;
+use crate::types::html_ref::{AttributeRef, TagRef};
+use crate::types::tag::{RAW_TEXT_TAGS, TagBuilder};
+
+/// Token read by a [`Tokenizer`], with its position in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token<'input> {
+    /// Content of the token
+    kind: TokenKind<'input>,
+    /// Range of the bytes of the input that were read for this token
+    span: Range<usize>,
+}
+
+impl<'input> Token<'input> {
+    /// Returns the content of the token.
+    #[must_use]
+    pub const fn as_kind(&self) -> &TokenKind<'input> {
+        &self.kind
+    }
+
+    /// Converts the token into its content.
+    #[must_use]
+    pub fn into_kind(self) -> TokenKind<'input> {
+        self.kind
+    }
+
+    /// Returns the range of the bytes of the input that were read for this
+    /// token, delimiters included.
+    ///
+    /// The spans of the tokens of an input are contiguous: every byte of the
+    /// input is in exactly one span.
+    #[must_use]
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+/// Content of a [`Token`].
+///
+/// The names, the attributes and the texts are borrowed from the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenKind<'input> {
+    /// Closing tag, with its name
+    ///
+    /// # Examples
+    ///
+    /// `</div>`
+    CloseTag(Cow<'input, str>),
+    /// Comment, with the content between `<!--` and `-->`
+    ///
+    /// # Examples
+    ///
+    /// `<!-- comment -->`
+    Comment(&'input str),
+    /// Doctype
+    ///
+    /// # Examples
+    ///
+    /// `<!DOCTYPE html>`
+    Doctype {
+        /// Attribute of the doctype, like `html`
+        attr: Option<&'input str>,
+        /// Name of the doctype, like `DOCTYPE`
+        name: Cow<'input, str>,
+    },
+    /// Opening tag
+    ///
+    /// # Examples
+    ///
+    /// `<div id="a">` and `<br />`
+    OpenTag {
+        /// Whether the tag is closed with `/>`
+        self_closing: bool,
+        /// Name and attributes of the tag
+        tag: TagRef<'input>,
+    },
+    /// Text, including the content of the `<script>` and `<style>` tags
+    Text(&'input str),
+}
+
+/// Iterator over the [`Token`]s of an html string.
+///
+/// The tokens are read lazily, in the order of the input. The content of the
+/// `<script>` and `<style>` tags is read as a single text token, up to the
+/// closing tag.
+///
+/// Contrary to [`Html::parse`](crate::Html::parse), the tokenizer doesn't
+/// build a tree: it doesn't check that the closing tags match the opening
+/// ones, and doesn't know about the void elements. The character references
+/// aren't decoded.
+///
+/// The tokenizer always reads the syntax of the default
+/// [`ParseOptions`](crate::ParseOptions), and can't be configured: the
+/// template placeholders are read as html, the legacy `<xmp>`, `<listing>`
+/// and `<plaintext>` tags are normal tags, the invalid html isn't repaired and
+/// the control characters aren't checked. On a document that
+/// [`Html::parse`](crate::Html::parse) accepts, the tokenizer reads the same
+/// tags, texts and comments, in the same order.
+///
+/// # Errors
+///
+/// The iterator returns an error on invalid syntax, like an invalid tag name
+/// or an unclosed tag, and then stops.
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// let input = r#"<p class="a">Hi<br/></div><!-- c -->"#;
+/// let tokens = Tokenizer::new(input)
+///     .map(|token| token.map(|token| (token.span(), token.into_kind())))
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+///
+/// let TokenKind::OpenTag { tag, self_closing: false } = &tokens[0].1 else { panic!() };
+/// assert_eq!(tag.find_attr_value("class"), Some("a"));
+/// assert_eq!(tokens[0].0, 0..13);
+/// assert_eq!(tokens[1], (13..15, TokenKind::Text("Hi")));
+/// assert!(matches!(tokens[2].1, TokenKind::OpenTag { self_closing: true, .. }));
+/// assert_eq!(tokens[3], (20..26, TokenKind::CloseTag("div".into())));
+/// assert_eq!(tokens[4], (26..36, TokenKind::Comment(" c ")));
+///
+/// let err = Tokenizer::new("<p>a</p><a:b>").find_map(Result::err).unwrap();
+/// assert_eq!(err.kind(), &ParseErrorKind::InvalidTagName(':'));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Tokenizer<'input> {
+    /// Attributes of the tag being read, reused from a tag to the next
+    attrs: Vec<AttributeRef<'input>>,
+    /// Whether an error was returned, after which the iteration stops
+    failed: bool,
+    /// Input to read
+    input: &'input str,
+    /// Position of the next token in the input
+    offset: usize,
+    /// Name of the raw text tag that is open, whose content is a text
+    raw: Option<&'static str>,
+}
+
+impl<'input> Tokenizer<'input> {
+    /// Reads a comment, after its opening `<!--`.
+    fn comment(&mut self) -> TokenKind<'input> {
+        let rest = self.input.get(self.offset..).unwrap_or_default();
+        let (content, read) = rest.find("-->").map_or((rest, rest.len()), |end| {
+            (rest.get(..end).unwrap_or_default(), end.saturating_add(3))
+        });
+        self.offset = self.offset.saturating_add(read);
+        TokenKind::Comment(content)
+    }
+
+    /// Creates a tokenizer that reads the given input.
+    #[must_use]
+    pub const fn new(input: &'input str) -> Self {
+        Self { attrs: vec![], failed: false, input, offset: 0, raw: None }
+    }
+
+    /// Reads the content of a raw text tag, up to its closing tag.
+    ///
+    /// # Returns
+    ///
+    /// The text, or `None` if the closing tag comes right away.
+    fn raw_text(&mut self, name: &str) -> Option<TokenKind<'input>> {
+        let rest = self.input.get(self.offset..).unwrap_or_default();
+        let mut chars = rest.chars();
+        let end = loop {
+            skip_until(&mut chars, b"<");
+            let end = rest.len().saturating_sub(chars.as_str().len());
+            if chars.next().is_none() {
+                break end;
+            }
+            if let Ok(TagBuilder::Close(closed)) =
+                TagBuilder::parse(&mut chars, &mut self.attrs, false)
+                && closed.eq_ignore_ascii_case(name)
+            {
+                break end;
+            }
+            chars = rest.get(end.saturating_add(1)..).unwrap_or_default().chars();
+        };
+        self.offset = self.offset.saturating_add(end);
+        (end != 0).then(|| TokenKind::Text(rest.get(..end).unwrap_or_default()))
+    }
+
+    /// Reads a tag, a comment or a doctype, after its `<`.
+    fn tag(&mut self) -> Result<TokenKind<'input>, ParseError> {
+        let start = self.offset;
+        let mut chars = self.input.get(start.saturating_add(1)..).unwrap_or_default().chars();
+        let parsed = TagBuilder::parse(&mut chars, &mut self.attrs, false);
+        self.offset = self.input.len().saturating_sub(chars.as_str().len());
+        Ok(match parsed.map_err(|kind| tag_error(kind, self.input, self.offset))? {
+            TagBuilder::Close(name) => TokenKind::CloseTag(name),
+            TagBuilder::Doctype { name, attr } => TokenKind::Doctype { attr, name },
+            TagBuilder::Open(tag) => {
+                self.raw =
+                    RAW_TEXT_TAGS.into_iter().find(|raw| raw.eq_ignore_ascii_case(tag.as_name()));
+                TokenKind::OpenTag { self_closing: false, tag }
+            }
+            TagBuilder::OpenClose(tag) => TokenKind::OpenTag { self_closing: true, tag },
+            TagBuilder::OpenComment => self.comment(),
+        })
+    }
+
+    /// Reads a text, up to the next tag.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text contains the closing of a comment that
+    /// isn't open.
+    fn text(&mut self) -> Result<TokenKind<'input>, ParseError> {
+        let rest = self.input.get(self.offset..).unwrap_or_default();
+        let end = rest.find('<').unwrap_or(rest.len());
+        let text = rest.get(..end).unwrap_or_default();
+        if let Some(closing) = text.find("-->") {
+            let offset = self.offset.saturating_add(closing);
+            return Err(ParseError::new(ParseErrorKind::UnopenedComment, self.input, offset));
+        }
+        self.offset = self.offset.saturating_add(end);
+        Ok(TokenKind::Text(text))
+    }
+}
+
+impl<'input> Iterator for Tokenizer<'input> {
+    type Item = Result<Token<'input>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.offset >= self.input.len() {
+            return None;
+        }
+        let start = self.offset;
+        let raw = self.raw.take().and_then(|name| self.raw_text(name));
+        let read = match raw {
+            Some(text) => Ok(text),
+            None if self.offset >= self.input.len() => return None,
+            None if self.input.get(self.offset..).is_some_and(|rest| rest.starts_with('<')) =>
+                self.tag(),
+            None => self.text(),
+        };
+        self.failed = read.is_err();
+        Some(read.map(|kind| Token { kind, span: start..self.offset }))
+    }
+}
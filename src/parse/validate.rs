@@ -0,0 +1,69 @@
+//! Module to validate the escaping of the attribute values while parsing,
+//! see [`ParseOptions::attribute_escaping`].
+
+use super::diagnostics::{DiagnosticKind, report};
+use super::{ParseOptions, ParseState};
+use crate::entities::reference_len;
+use crate::types::html_ref::TagRef;
+use crate::types::tag::TagBuilder;
+
+/// Describes the suspicious escaping of the attributes of an opening tag.
+///
+/// The attribute names and values of the tag must be slices of `input`.
+///
+/// # Returns
+///
+/// The warnings, with the offsets in `input` where they were found, in
+/// increasing order.
+fn attribute_warnings(tag: &TagRef<'_>, input: &str) -> Vec<(usize, DiagnosticKind)> {
+    let offset_of = |slice: &str| slice.as_ptr().addr().saturating_sub(input.as_ptr().addr());
+    let mut warnings = vec![];
+    for attr in tag.as_attrs() {
+        let name = attr.as_name();
+        if name.contains(['"', '\'']) {
+            warnings.push((offset_of(name), DiagnosticKind::MismatchedQuote(name.to_owned())));
+        }
+        let Some(value) = attr.as_value() else { continue };
+        let value_start = offset_of(value);
+        for (idx, ch) in value.char_indices() {
+            let unescaped = match ch {
+                '<' => true,
+                '&' => value.get(idx..).and_then(reference_len).is_none(),
+                _ => false,
+            };
+            if unescaped {
+                let kind = DiagnosticKind::Unescaped { attribute: name.to_owned(), character: ch };
+                warnings.push((value_start.saturating_add(idx), kind));
+            }
+        }
+        let after = value_start.saturating_add(value.len()).saturating_add(1);
+        if input
+            .get(after..)
+            .and_then(|rest| rest.chars().next())
+            .is_some_and(|next| !next.is_whitespace() && next != '/' && next != '>')
+        {
+            warnings.push((after, DiagnosticKind::MissingWhitespace(name.to_owned())));
+        }
+    }
+    warnings
+}
+
+/// Reports the suspicious escaping of the attributes of an opening tag, if
+/// the options ask for it.
+///
+/// See [`attribute_warnings`] for the arguments.
+pub(super) fn validate_attributes(
+    builder: &TagBuilder<'_>,
+    input: &str,
+    state: &mut ParseState,
+    options: &mut ParseOptions<'_>,
+) {
+    if !options.validates_attribute_values() {
+        return;
+    }
+    if let TagBuilder::Open(tag) | TagBuilder::OpenClose(tag) = builder {
+        for (offset, kind) in attribute_warnings(tag, input) {
+            report(kind, input, offset, state, options);
+        }
+    }
+}
@@ -0,0 +1,299 @@
+//! Module to render the differences between two versions of a document, as
+//! plain text or coloured for the terminals.
+//!
+//! [`render_diff`] serialises both trees in a canonical form, with one node
+//! per line, and compares the lines. This makes the output independent of the
+//! formatting of the documents, so that a change-monitoring tool only reports
+//! the changes of their content.
+
+use alloc::collections::BTreeMap;
+
+use crate::Html;
+
+/// Escape code that colours the headers of the changes in cyan.
+const CYAN: &str = "\x1b[36m";
+
+/// Escape code that colours the added lines in green.
+const GREEN: &str = "\x1b[32m";
+
+/// Escape code that colours the removed lines in red.
+const RED: &str = "\x1b[31m";
+
+/// Escape code that resets the colour.
+const RESET: &str = "\x1b[0m";
+
+/// Line of the canonical serialisation of a tree.
+#[derive(Debug)]
+struct Line {
+    /// Path of the node the line belongs to, like `/ul/li[2]`
+    path: String,
+    /// Content of the line, indented with the depth of the node
+    text: String,
+}
+
+/// Change of a line between the old and the new serialisations.
+enum Change<'line> {
+    /// Line only found in the new serialisation
+    Added(&'line Line),
+    /// Line only found in the old serialisation
+    Removed(&'line Line),
+}
+
+/// Computes the changes between two lists of lines, with the greedy
+/// algorithm of Myers.
+///
+/// The common prefix and suffix are skipped first. The cost is then
+/// proportional to the number of lines between the first and the last
+/// change, times the number of changed lines.
+fn changes<'line>(old: &'line [Line], new: &'line [Line]) -> Vec<Change<'line>> {
+    let prefix =
+        old.iter().zip(new).take_while(|(before, after)| before.text == after.text).count();
+    let old_rest = old.get(prefix..).unwrap_or_default();
+    let new_rest = new.get(prefix..).unwrap_or_default();
+    let suffix = old_rest
+        .iter()
+        .rev()
+        .zip(new_rest.iter().rev())
+        .take_while(|(before, after)| before.text == after.text)
+        .count();
+    let old_changed = old_rest.get(..old_rest.len().saturating_sub(suffix)).unwrap_or_default();
+    let new_changed = new_rest.get(..new_rest.len().saturating_sub(suffix)).unwrap_or_default();
+    let offset = old_changed.len().saturating_add(new_changed.len());
+    let mut result = vec![];
+    let (mut row, mut col) = (old_changed.len(), new_changed.len());
+    for (edits, furthest) in edit_trace(old_changed, new_changed).iter().enumerate().skip(1).rev() {
+        let diagonal = row.saturating_add(offset).saturating_sub(col);
+        let added = is_addition(furthest, diagonal, edits, offset);
+        let previous = if added { diagonal.saturating_add(1) } else { diagonal.saturating_sub(1) };
+        row = furthest.get(previous).copied().unwrap_or_default();
+        col = row.saturating_add(offset).saturating_sub(previous);
+        if added {
+            result.extend(new_changed.get(col).map(Change::Added));
+        } else {
+            result.extend(old_changed.get(row).map(Change::Removed));
+        }
+    }
+    result.reverse();
+    result
+}
+
+/// Computes the furthest rows reached on every diagonal of the edit graph,
+/// for every number of edits, with the greedy algorithm of Myers.
+///
+/// The diagonal of the point at `old[row]` and `new[col]` is
+/// `row + offset - col`, where `offset` is the total number of lines. The
+/// vector at index `edits` contains the rows reached with `edits - 1` edits,
+/// and the last one is followed by the edit that reaches the end of both
+/// lists.
+fn edit_trace(old: &[Line], new: &[Line]) -> Vec<Vec<usize>> {
+    let offset = old.len().saturating_add(new.len());
+    let mut furthest = vec![0usize; offset.saturating_mul(2).saturating_add(2)];
+    let mut trace = vec![];
+    for edits in 0..=offset {
+        trace.push(furthest.clone());
+        let diagonals = offset.saturating_sub(edits)..=offset.saturating_add(edits);
+        for diagonal in diagonals.step_by(2) {
+            let mut row = if is_addition(&furthest, diagonal, edits, offset) {
+                furthest.get(diagonal.saturating_add(1)).copied().unwrap_or_default()
+            } else {
+                furthest
+                    .get(diagonal.saturating_sub(1))
+                    .copied()
+                    .unwrap_or_default()
+                    .saturating_add(1)
+            };
+            let mut col = row.saturating_add(offset).saturating_sub(diagonal);
+            while let (Some(before), Some(after)) = (old.get(row), new.get(col))
+                && before.text == after.text
+            {
+                row = row.saturating_add(1);
+                col = col.saturating_add(1);
+            }
+            if let Some(cell) = furthest.get_mut(diagonal) {
+                *cell = row;
+            }
+            if row >= old.len() && col >= new.len() {
+                return trace;
+            }
+        }
+    }
+    trace
+}
+
+/// Checks if the furthest point on a diagonal, with `edits` edits, is
+/// reached by adding a line, rather than by removing one.
+///
+/// The line is added if the diagonal above reaches further than the diagonal
+/// below, so that the removals come before the additions.
+fn is_addition(furthest: &[usize], diagonal: usize, edits: usize, offset: usize) -> bool {
+    let row = |index: usize| furthest.get(index).copied().unwrap_or_default();
+    diagonal == offset.saturating_sub(edits)
+        || (diagonal != offset.saturating_add(edits)
+            && row(diagonal.saturating_sub(1)) < row(diagonal.saturating_add(1)))
+}
+
+/// Serialises a node in the canonical form, and appends its lines.
+///
+/// The `path` is the path of the node, and `indent` the indentation of its
+/// lines.
+fn push_node(node: &Html, path: &str, indent: &str, lines: &mut Vec<Line>) {
+    let Html::Tag { tag, .. } = node else {
+        push_text(&node.to_string(), path, indent, lines);
+        return;
+    };
+    let children = node.children().collect::<Vec<_>>();
+    match children.as_slice() {
+        _ if tag.is_raw_text() => push_text(&node.to_string(), path, indent, lines),
+        [] if tag.is_void() => push_text(&format!("<{tag}>"), path, indent, lines),
+        [] => push_text(&format!("<{tag}>{}", tag.to_closing_string()), path, indent, lines),
+        [text @ Html::Text(_)] if !text.to_string().trim().contains('\n') => {
+            let content = text.to_string();
+            let line = format!("<{tag}>{}{}", content.trim(), tag.to_closing_string());
+            push_text(&line, path, indent, lines);
+        }
+        _ => {
+            push_text(&format!("<{tag}>"), path, indent, lines);
+            push_siblings(&children, path, &format!("{indent}  "), lines);
+            push_text(&tag.to_closing_string(), path, indent, lines);
+        }
+    }
+}
+
+/// Serialises the children of a node, whose path is `parent`, and appends
+/// their lines.
+///
+/// The path of a tag is the path of its parent followed by its name, and by
+/// its position among the siblings with the same name if it has any, like in
+/// `/ul/li[2]`. The texts and comments belong to their parent, or to `/` at the
+/// top level.
+fn push_siblings(siblings: &[&Html], parent: &str, indent: &str, lines: &mut Vec<Line>) {
+    let mut totals = BTreeMap::<&str, usize>::new();
+    for (tag, _) in siblings.iter().filter_map(|sibling| sibling.as_tag()) {
+        let total = totals.entry(tag.as_name()).or_default();
+        *total = total.saturating_add(1);
+    }
+    let mut positions = BTreeMap::<&str, usize>::new();
+    for sibling in siblings {
+        let Some((tag, _)) = sibling.as_tag() else {
+            push_node(sibling, if parent.is_empty() { "/" } else { parent }, indent, lines);
+            continue;
+        };
+        let name = tag.as_name();
+        let position = positions.entry(name).or_default();
+        *position = position.saturating_add(1);
+        let path = if totals.get(name).is_some_and(|total| *total > 1) {
+            format!("{parent}/{name}[{position}]")
+        } else {
+            format!("{parent}/{name}")
+        };
+        push_node(sibling, &path, indent, lines);
+    }
+}
+
+/// Appends the lines of a serialised node, trimmed and without the empty
+/// ones.
+fn push_text(text: &str, path: &str, indent: &str, lines: &mut Vec<Line>) {
+    lines.extend(
+        text.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| Line { path: path.to_owned(), text: format!("{indent}{line}") }),
+    );
+}
+
+/// Renders the diff of two versions of a document, with or without the
+/// colours. See [`render_diff`].
+fn render(old: &Html, new: &Html, coloured: bool) -> String {
+    let old_lines = serialise(old);
+    let new_lines = serialise(new);
+    let paint = |colour: &'static str| if coloured { [colour, RESET] } else { [""; 2] };
+    let mut rendered = String::new();
+    let mut header: Option<&str> = None;
+    for change in changes(&old_lines, &new_lines) {
+        let ([start, end], sign, line) = match change {
+            Change::Added(line) => (paint(GREEN), "+ ", line),
+            Change::Removed(line) => (paint(RED), "- ", line),
+        };
+        if header != Some(line.path.as_str()) {
+            header = Some(line.path.as_str());
+            let [header_start, header_end] = paint(CYAN);
+            rendered.extend([header_start, "@@ ", line.path.as_str(), " @@", header_end, "\n"]);
+        }
+        rendered.extend([start, sign, line.text.as_str(), end, "\n"]);
+    }
+    rendered
+}
+
+/// Renders a line-oriented diff of two versions of a document, coloured
+/// for the terminals.
+///
+/// This is the same as [`render_diff`], with ANSI escape codes: the removed
+/// lines are red, the added lines are green and the headers are cyan.
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::patch::render_coloured_diff;
+/// use html_filter::*;
+///
+/// let old = Html::parse("<ul><li>Apple</li><li>Pear</li></ul>").unwrap();
+/// let new = Html::parse("<ul>\n  <li>Apple</li>\n  <li>Kiwi</li>\n</ul>").unwrap();
+///
+/// assert_eq!(
+///     render_coloured_diff(&old, &new),
+///     "\x1b[36m@@ /ul/li[2] @@\x1b[0m\n\x1b[31m-   <li>Pear</li>\x1b[0m\n\x1b[32m+   \
+///      <li>Kiwi</li>\x1b[0m\n"
+/// );
+/// ```
+#[must_use]
+pub fn render_coloured_diff(old: &Html, new: &Html) -> String {
+    render(old, new, true)
+}
+
+/// Renders a line-oriented diff of two versions of a document.
+///
+/// Both trees are serialised in a canonical form: every tag, text, comment
+/// and doctype is on its own line, indented with its depth, and the texts are
+/// trimmed. A tag that only contains a single line of text is kept on one
+/// line, like `<li>Apple</li>`. The formatting of the inputs, like their
+/// indentation or their line breaks, is thus never reported as a change.
+///
+/// The removed lines start with `-`, and the added lines with `+`. The
+/// changes are attributed to the node they belong to: every time the node
+/// changes, a header gives its path, like `@@ /ul/li[2] @@`. The path of a
+/// tag lists the names of its ancestors, with its position among the
+/// siblings with the same name if it has any, and the texts and comments
+/// belong to their parent tag. The unchanged lines aren't rendered, so the
+/// result is empty if the documents have the same content.
+///
+/// The result is plain text. See [`render_coloured_diff`] to colour it for
+/// the terminals.
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::patch::render_diff;
+/// use html_filter::*;
+///
+/// let old = Html::parse("<ul><li>Apple</li><li>Pear</li></ul>").unwrap();
+/// let new = Html::parse("<ul>\n  <li>Apple</li>\n  <li>Kiwi</li>\n</ul>").unwrap();
+///
+/// assert_eq!(render_diff(&old, &new), "@@ /ul/li[2] @@\n-   <li>Pear</li>\n+   <li>Kiwi</li>\n");
+/// assert_eq!(render_diff(&old, &old), "");
+/// ```
+#[must_use]
+pub fn render_diff(old: &Html, new: &Html) -> String {
+    render(old, new, false)
+}
+
+/// Serialises a tree in the canonical form of [`render_diff`].
+fn serialise(html: &Html) -> Vec<Line> {
+    let nodes = match html {
+        Html::Vec(_) => html.children().collect(),
+        Html::Empty => vec![],
+        Html::Comment(_) | Html::Doctype { .. } | Html::Tag { .. } | Html::Text(_) => vec![html],
+    };
+    let mut lines = vec![];
+    push_siblings(&nodes, "", "", &mut lines);
+    lines
+}
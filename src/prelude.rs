@@ -0,0 +1,29 @@
+//! Re-exports of the types needed to parse, filter and read a document.
+//!
+//! Import everything with a glob to scrape a document without naming the
+//! modules of the crate:
+//!
+//! ```
+//! use html_filter::prelude::*;
+//!
+//! let html =
+//!     Html::parse(r#"<ul><li><a href="/a" title="A">A</a></li><li><a href="/b">B</a></li></ul>"#)
+//!         .unwrap();
+//!
+//! let mut links = vec![];
+//! for link in html.select(&Filter::new().tag_name("a")) {
+//!     let Some(tag) = link.as_tag() else { continue };
+//!     for (name, value) in tag.attrs() {
+//!         links.push((name.to_owned(), value.map(str::to_owned)));
+//!     }
+//! }
+//! assert_eq!(links, [
+//!     ("href".to_owned(), Some("/a".to_owned())),
+//!     ("title".to_owned(), Some("A".to_owned())),
+//!     ("href".to_owned(), Some("/b".to_owned())),
+//! ]);
+//! ```
+
+pub use crate::{Attribute, AttributeRef, AttrsIter, Html, ParseError, ParseOptions, Tag};
+#[cfg(feature = "filter")]
+pub use crate::{Filter, Matches, NodeRef, NodeTypes};
@@ -0,0 +1,274 @@
+//! Module that exposes the parser and the filters as a Python module, to use
+//! this crate from scripts and notebooks.
+//!
+//! The module is named `html_filter` and is built with
+//! [maturin](https://www.maturin.rs), with `maturin develop --features pyo3`.
+//! It defines the `parse` function, that returns an [`PyHtml`] tree, and the
+//! [`PyTag`] class for the tags found with `Html.select`.
+//!
+//! The input is read directly from the buffer of the Python string. The
+//! parsed tree is shared by the [`PyHtml`] and all the [`PyTag`]s found in it:
+//! a [`PyTag`] only stores the position of its tag in the tree, so selecting
+//! tags never copies them.
+//!
+//! # Examples
+//!
+//! ```python
+//! import html_filter
+//!
+//! html = html_filter.parse('<ul><li class="a">First</li><li>Second</li></ul>')
+//! [item] = html.select("li.a")
+//! assert item.name == "li"
+//! assert item["class"] == "a"
+//! assert item.inner_text() == "First"
+//! ```
+
+use alloc::sync::Arc;
+use core::{ptr, slice};
+
+use pyo3::exceptions::{PyKeyError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyString;
+
+use crate::errors::safe_expect;
+use crate::filter::selector::parse_selector;
+use crate::{Html, Tag};
+
+/// Html tree, exposed to Python as the `html_filter.Html` class.
+#[pyclass(frozen, module = "html_filter", name = "Html")]
+#[derive(Debug)]
+pub struct PyHtml {
+    /// Parsed tree, shared with the tags found in it
+    html: Arc<Html>,
+}
+
+#[pymethods]
+impl PyHtml {
+    /// Returns the representation of the tree in Python.
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        Ok(format!("Html({})", PyString::new(py, &self.html.to_string()).repr()?))
+    }
+
+    /// Returns the html string of the tree.
+    fn __str__(&self) -> String {
+        self.html.to_string()
+    }
+
+    /// Returns the concatenation of the texts of the tree, without the tags
+    /// and the comments.
+    fn inner_text(&self) -> String {
+        inner_text(&self.html)
+    }
+
+    /// Returns the tags of the tree that match the CSS selector, in the order
+    /// of the document.
+    ///
+    /// The selector is a tag name, or `*`, followed by any number of `#id`,
+    /// `.class`, `[attr]` and `[attr=value]` conditions, like
+    /// `a.external[href]`, with the `~=`, `^=`, `$=` and `*=` operators.
+    fn select(&self, selector: &str) -> PyResult<Vec<PyTag>> {
+        select(&self.html, &[], selector)
+    }
+}
+
+/// Tag found in a tree, with its children, exposed to Python as the
+/// `html_filter.Tag` class.
+#[pyclass(frozen, module = "html_filter", name = "Tag")]
+#[derive(Debug)]
+pub struct PyTag {
+    /// Indices of the nodes to follow from the root to find the tag, see
+    /// [`nodes`]
+    path: Box<[usize]>,
+    /// Tree in which the tag is
+    root: Arc<Html>,
+}
+
+impl PyTag {
+    /// Returns the children of the tag.
+    fn child(&self) -> &Html {
+        self.node().1
+    }
+
+    /// Returns the tag and its children.
+    fn node(&self) -> (&Tag, &Html) {
+        safe_expect!(
+            find_tag(&self.root, &self.path),
+            "The path was built from the same tree, that is never modified"
+        )
+    }
+
+    /// Returns the opening tag, with the name and the attributes.
+    fn tag(&self) -> &Tag {
+        self.node().0
+    }
+}
+
+#[pymethods]
+impl PyTag {
+    /// Checks if the tag has an attribute with the given name.
+    fn __contains__(&self, name: &str) -> bool {
+        self.tag().as_attrs().iter().any(|attr| attr.as_name() == name)
+    }
+
+    /// Returns the value of the attribute with the given name.
+    ///
+    /// # Errors
+    ///
+    /// Raises a `KeyError` if the tag doesn't have an attribute with a value
+    /// with this name.
+    fn __getitem__(&self, name: &str) -> PyResult<String> {
+        self.tag()
+            .find_attr_value(name)
+            .cloned()
+            .ok_or_else(|| PyKeyError::new_err(name.to_owned()))
+    }
+
+    /// Returns the representation of the tag in Python.
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        Ok(format!("Tag({})", PyString::new(py, &self.tag().to_opening_string()).repr()?))
+    }
+
+    /// Returns the html string of the tag, with its children.
+    fn __str__(&self) -> String {
+        let (tag, child) = self.node();
+        format!("<{tag}>{child}{}", tag.to_closing_string())
+    }
+
+    /// Returns the attributes of the tag, as a list of `(name, value)`
+    /// pairs.
+    ///
+    /// The value is `None` for the attributes without value, like `hidden`.
+    #[getter]
+    fn attrs(&self) -> Vec<(String, Option<String>)> {
+        self.tag()
+            .as_attrs()
+            .iter()
+            .map(|attr| (attr.as_name().to_owned(), attr.as_value().cloned()))
+            .collect()
+    }
+
+    /// Returns the value of the attribute with the given name, or `default`
+    /// if the tag doesn't have this attribute, or if the attribute has no
+    /// value.
+    #[pyo3(signature = (name, default = None))]
+    fn get(&self, name: &str, default: Option<String>) -> Option<String> {
+        self.tag().find_attr_value(name).cloned().or(default)
+    }
+
+    /// Returns the concatenation of the texts inside the tag, without the
+    /// tags and the comments.
+    fn inner_text(&self) -> String {
+        inner_text(self.child())
+    }
+
+    /// Returns the name of the tag.
+    #[getter]
+    fn name(&self) -> &str {
+        self.tag().as_name()
+    }
+
+    /// Returns the tags inside this tag that match the CSS selector, in the
+    /// order of the document.
+    fn select(&self, selector: &str) -> PyResult<Vec<Self>> {
+        select(&self.root, &self.path, selector)
+    }
+}
+
+/// Returns the tag at the end of a path, and its children, by following the
+/// path from the root.
+fn find_tag<'html>(root: &'html Html, path: &[usize]) -> Option<(&'html Tag, &'html Html)> {
+    let mut found = None;
+    let mut siblings = nodes(root);
+    for idx in path {
+        let (tag, child) = siblings.get(*idx)?.as_tag()?;
+        found = Some((tag, child));
+        siblings = nodes(child);
+    }
+    found
+}
+
+/// Returns the concatenation of the texts of a tree.
+fn inner_text(html: &Html) -> String {
+    let mut text = String::new();
+    html.push_texts(&mut text);
+    text
+}
+
+/// Parses an html string into a tree.
+///
+/// # Errors
+///
+/// Raises a `ValueError` if the input isn't valid html.
+#[pyfunction]
+fn parse(input: &str) -> PyResult<PyHtml> {
+    Html::parse(input)
+        .map(|html| PyHtml { html: Arc::new(html) })
+        .map_err(|err| PyValueError::new_err(err.to_string()))
+}
+
+/// Defines the `html_filter` Python module.
+///
+/// # Errors
+///
+/// Returns an error if the classes or the functions can't be added to the
+/// module.
+#[pymodule]
+pub fn html_filter(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyHtml>()?;
+    module.add_class::<PyTag>()?;
+    module.add_function(wrap_pyfunction!(parse, module)?)
+}
+
+/// Returns the nodes that follow each other at the same level of a tree.
+///
+/// These are the children of an [`Html::Vec`], and the node itself for the
+/// other nodes.
+fn nodes(html: &Html) -> &[Html] {
+    match html {
+        Html::Vec(vec) => vec,
+        Html::Empty => &[],
+        Html::Comment(_) | Html::Doctype { .. } | Html::Tag { .. } | Html::Text(_) =>
+            slice::from_ref(html),
+    }
+}
+
+/// Pushes the paths of the tags of a tree, in the order of the document.
+///
+/// `path` is the path of the nodes at the current level, see [`nodes`].
+fn push_paths<'html>(
+    html: &'html Html,
+    path: &mut Vec<usize>,
+    paths: &mut Vec<(&'html Html, Box<[usize]>)>,
+) {
+    for (idx, node) in nodes(html).iter().enumerate() {
+        if let Html::Tag { child, .. } = node {
+            path.push(idx);
+            paths.push((node, path.as_slice().into()));
+            push_paths(child, path, paths);
+            path.pop();
+        }
+    }
+}
+
+/// Returns the tags that match a CSS selector, in the tag found at `path` in
+/// the tree, or in the whole tree if `path` is empty.
+///
+/// # Errors
+///
+/// Raises a `ValueError` if the selector is invalid.
+fn select(root: &Arc<Html>, path: &[usize], selector: &str) -> PyResult<Vec<PyTag>> {
+    let filter = parse_selector(selector)
+        .ok_or_else(|| PyValueError::new_err(format!("Invalid selector '{selector}'.")))?;
+    let html = find_tag(root, path).map_or(&**root, |(_, child)| child);
+    let mut paths = vec![];
+    push_paths(html, &mut path.to_vec(), &mut paths);
+    let mut tags = paths.into_iter();
+    Ok(html
+        .select(&filter)
+        .filter_map(|node| {
+            node.as_tag()?;
+            let (_, found) = tags.find(|(tag, _)| ptr::eq(*tag, node.as_html()))?;
+            Some(PyTag { path: found, root: Arc::clone(root) })
+        })
+        .collect())
+}
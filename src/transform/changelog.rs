@@ -0,0 +1,296 @@
+//! Module to record the edits of a tree, to undo them, redo them, or replay
+//! them onto another tree.
+
+use core::mem::{replace, take};
+
+use crate::Html;
+
+/// Edit of an [`Html`] tree, applied with [`Html::apply_edit`] or recorded
+/// in a [`ChangeLog`].
+///
+/// The nodes are designated by their path, like for [`Html::reparse_node`]:
+/// the list of the indices of the children to follow from the root to reach
+/// the node, where the children of a tag are the nodes of its content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Edit {
+    /// Inserts a node at the given path, before the node that was there
+    Insert {
+        /// Path of the inserted node, that can't be empty
+        path: Vec<usize>,
+        /// Inserted node
+        node: Html,
+    },
+    /// Removes the node at the given path
+    Remove {
+        /// Path of the removed node, that can't be empty
+        path: Vec<usize>,
+    },
+    /// Replaces the node at the given path
+    Replace {
+        /// Path of the replaced node, empty for the root
+        path: Vec<usize>,
+        /// New node
+        node: Html,
+    },
+}
+
+/// Log of the edits applied to a tree, that supports undo and redo.
+///
+/// The edits are applied with [`ChangeLog::apply`], that records them with
+/// the edits that revert them. They can then be reverted with
+/// [`ChangeLog::undo`], applied again with [`ChangeLog::redo`], or applied
+/// to another tree with [`ChangeLog::replay`], to apply the same cleanup to
+/// similar documents.
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// let mut html = Html::parse("<ul><li>First</li><li>Ad</li><li>Second</li></ul>").unwrap();
+/// let mut log = ChangeLog::new();
+///
+/// log.apply(&mut html, Edit::Remove { path: vec![1] }).unwrap();
+/// log.apply(&mut html, Edit::Insert {
+///     path: vec![0],
+///     node: Html::parse("<li>Zeroth</li>").unwrap(),
+/// })
+/// .unwrap();
+/// assert_eq!(html, "<ul><li>Zeroth</li><li>First</li><li>Second</li></ul>");
+///
+/// assert!(log.undo(&mut html).unwrap());
+/// assert_eq!(html, "<ul><li>First</li><li>Second</li></ul>");
+/// assert!(log.redo(&mut html).unwrap());
+/// assert!(!log.redo(&mut html).unwrap());
+///
+/// let mut other = Html::parse("<ul><li>One</li><li>Sponsored</li><li>Two</li></ul>").unwrap();
+/// log.replay(&mut other).unwrap();
+/// assert_eq!(other, "<ul><li>Zeroth</li><li>One</li><li>Two</li></ul>");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ChangeLog {
+    /// Applied edits, with the edits that revert them, from the oldest
+    done: Vec<(Edit, Edit)>,
+    /// Undone edits, with the edits that revert them, from the newest
+    undone: Vec<(Edit, Edit)>,
+}
+
+impl ChangeLog {
+    /// Applies an edit to the tree and records it.
+    ///
+    /// The edits that were undone can't be redone anymore.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, and leaves the tree and the log untouched, if the
+    /// edit can't be applied (see [`Html::apply_edit`]).
+    pub fn apply(&mut self, html: &mut Html, mut edit: Edit) -> Result<(), String> {
+        edit.renew_ids();
+        let inverse = html.apply_edit_keeping_ids(edit.clone())?;
+        self.done.push((edit, inverse));
+        self.undone.clear();
+        Ok(())
+    }
+
+    /// Checks if there is an undone edit to redo.
+    #[must_use]
+    pub const fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    /// Checks if there is an edit to undo.
+    #[must_use]
+    pub const fn can_undo(&self) -> bool {
+        !self.done.is_empty()
+    }
+
+    /// Returns the applied edits, from the oldest to the newest, without the
+    /// undone ones.
+    pub fn edits(&self) -> impl Iterator<Item = &Edit> {
+        self.done.iter().map(|(edit, _)| edit)
+    }
+
+    /// Creates an empty log.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { done: vec![], undone: vec![] }
+    }
+
+    /// Applies again the last undone edit.
+    ///
+    /// Returns `false` if there was no edit to redo.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, and leaves the tree and the log untouched, if the
+    /// edit can't be applied, i.e., if the tree isn't the one the edit was
+    /// undone on.
+    pub fn redo(&mut self, html: &mut Html) -> Result<bool, String> {
+        let Some((edit, _)) = self.undone.last() else { return Ok(false) };
+        let inverse = html.apply_edit_keeping_ids(edit.clone())?;
+        if let Some((redone, _)) = self.undone.pop() {
+            self.done.push((redone, inverse));
+        }
+        Ok(true)
+    }
+
+    /// Applies the recorded edits to another tree, from the oldest to the
+    /// newest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if one of the edits can't be applied to the tree. The
+    /// edits before it remain applied.
+    pub fn replay(&self, html: &mut Html) -> Result<(), String> {
+        self.edits().try_for_each(|edit| html.apply_edit(edit.clone()).map(|_| ()))
+    }
+
+    /// Reverts the last applied edit.
+    ///
+    /// Returns `false` if there was no edit to undo.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, and leaves the tree and the log untouched, if the
+    /// edit can't be reverted, i.e., if the tree isn't the one the edit was
+    /// applied on.
+    pub fn undo(&mut self, html: &mut Html) -> Result<bool, String> {
+        let Some((_, inverse)) = self.done.last() else { return Ok(false) };
+        let edit = html.apply_edit_keeping_ids(inverse.clone())?;
+        if let Some((undone, _)) = self.done.pop() {
+            self.undone.push((undone, edit));
+        }
+        Ok(true)
+    }
+}
+
+impl Edit {
+    /// Gives new identifiers to the tags of the inserted node, if any.
+    fn renew_ids(&mut self) {
+        match self {
+            Self::Insert { node, .. } | Self::Replace { node, .. } => node.renew_ids(),
+            Self::Remove { .. } => (),
+        }
+    }
+}
+
+impl Html {
+    /// Applies an edit to the tree, and returns the edit that reverts it.
+    ///
+    /// The tags of the inserted node get new identifiers, so that the
+    /// identifiers of the tree stay unique (see [`NodeId`](crate::NodeId)).
+    /// To record the edits, use a [`ChangeLog`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, and leaves the tree untouched, if
+    ///
+    /// - the path doesn't lead to a node of the tree, or to the position of a
+    ///   new node for [`Edit::Insert`];
+    /// - the path of an [`Edit::Insert`] or of an [`Edit::Remove`] is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let mut html = Html::parse("<p>Hello <b>world</b></p>").unwrap();
+    /// let edit = Edit::Replace { path: vec![1], node: Html::parse("<i>you</i>").unwrap() };
+    ///
+    /// let inverse = html.apply_edit(edit).unwrap();
+    /// assert_eq!(html, "<p>Hello <i>you</i></p>");
+    /// html.apply_edit(inverse).unwrap();
+    /// assert_eq!(html, "<p>Hello <b>world</b></p>");
+    ///
+    /// assert!(html.apply_edit(Edit::Remove { path: vec![5] }).is_err());
+    /// assert_eq!(html, "<p>Hello <b>world</b></p>");
+    /// ```
+    pub fn apply_edit(&mut self, mut edit: Edit) -> Result<Edit, String> {
+        edit.renew_ids();
+        self.apply_edit_keeping_ids(edit)
+    }
+
+    /// Applies an edit to the tree, like [`Html::apply_edit`], but keeps the
+    /// identifiers of the inserted tags.
+    ///
+    /// This is used to restore the removed tags with their identifiers.
+    fn apply_edit_keeping_ids(&mut self, edit: Edit) -> Result<Edit, String> {
+        match edit {
+            Edit::Insert { mut path, node } => {
+                let idx = path.pop().ok_or("Invalid path: can't insert a node at the root.")?;
+                self.node_at_mut(&path)?.with_children(|children| {
+                    if idx > children.len() {
+                        return Err(format!("Invalid path: can't insert a node at index {idx}."));
+                    }
+                    children.insert(idx, node);
+                    Ok(())
+                })?;
+                path.push(idx);
+                Ok(Edit::Remove { path })
+            }
+            Edit::Remove { mut path } => {
+                let idx = path.pop().ok_or("Invalid path: can't remove the root.")?;
+                let node = self.node_at_mut(&path)?.with_children(|children| {
+                    if idx < children.len() {
+                        Ok(children.remove(idx))
+                    } else {
+                        Err(format!("Invalid path: no node to remove at index {idx}."))
+                    }
+                })?;
+                path.push(idx);
+                Ok(Edit::Insert { path, node })
+            }
+            Edit::Replace { mut path, node } => {
+                let Some(idx) = path.pop() else {
+                    return Ok(Edit::Replace { path, node: replace(self, node) });
+                };
+                let old = self.node_at_mut(&path)?.with_children(|children| {
+                    let target = children.get_mut(idx).ok_or_else(|| {
+                        format!("Invalid path: no node to replace at index {idx}.")
+                    })?;
+                    Ok(replace(target, node))
+                })?;
+                path.push(idx);
+                Ok(Edit::Replace { path, node: old })
+            }
+        }
+    }
+
+    /// Edits the children of the node as a [`Vec`].
+    ///
+    /// The children of a vec are its nodes, and the children of a tag are the
+    /// nodes of its content. A vec remains a vec, so that the paths of its
+    /// nodes don't change, whereas the content of a tag is simplified. A
+    /// single child that is a vec or an empty node is kept in a vec though,
+    /// as it would otherwise not be a child anymore, and the paths of the
+    /// next edits would be shifted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the node can't have children, or if the closure
+    /// fails.
+    fn with_children<T, F>(&mut self, edit: F) -> Result<T, String>
+    where F: FnOnce(&mut Vec<Self>) -> Result<T, String> {
+        let (container, keep_vec) = match self {
+            Self::Vec(_) => (self, true),
+            Self::Tag { child, .. } => (&mut **child, false),
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty | Self::Text(_) =>
+                return Err("Invalid path: the node can't have children.".to_owned()),
+        };
+        let mut children = match take(container) {
+            Self::Vec(vec) => vec.into_vec(),
+            Self::Empty => vec![],
+            node @ (Self::Comment(_) | Self::Doctype { .. } | Self::Tag { .. } | Self::Text(_)) =>
+                vec![node],
+        };
+        let result = edit(&mut children);
+        *container = match children.as_slice() {
+            [] if !keep_vec => Self::Empty,
+            [Self::Comment(_) | Self::Doctype { .. } | Self::Tag { .. } | Self::Text(_)]
+                if !keep_vec =>
+                children.pop().unwrap_or_default(),
+            _ => Self::Vec(children.into_boxed_slice()),
+        };
+        result
+    }
+}
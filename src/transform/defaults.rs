@@ -0,0 +1,172 @@
+//! Module to inject default attributes into the tags of a tree, before
+//! serialising it.
+
+use core::mem::take;
+
+use super::head::value_attr;
+use crate::{Html, Tag};
+
+/// Default attributes to add to the tags that don't have them.
+///
+/// The defaults use a builder pattern: start with [`DefaultAttributes::new`]
+/// and chain the rules you need. The defaults can then be injected in a tree
+/// with [`Html::inject_default_attributes`], or applied as a final pass when
+/// serialising a tree with [`DefaultAttributes::serialize`].
+///
+/// An attribute is only added to a tag that doesn't already have an attribute
+/// with the same name: the existing values are never overwritten.
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// let defaults = DefaultAttributes::new()
+///     .add_if("a", ("target", "_blank"), "rel", "noopener")
+///     .add("img", "loading", "lazy");
+///
+/// let html = Html::parse(
+///     r#"<a href="/a">A</a><a href="/b" target="_blank">B</a><img src="c.png" loading="eager" /><img src="d.png" />"#,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(
+///     defaults.serialize(&html),
+///     r#"<a href="/a">A</a><a href="/b" target="_blank" rel="noopener">B</a><img src="c.png" loading="eager"><img src="d.png" loading="lazy">"#
+/// );
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DefaultAttributes {
+    /// Rules to apply, in order
+    rules: Vec<DefaultRule>,
+}
+
+impl DefaultAttributes {
+    /// Adds a default attribute to every tag with the given name.
+    ///
+    /// See [`DefaultAttributes`] for usage information.
+    #[must_use]
+    pub fn add<T, N, V>(self, tag: T, name: N, value: V) -> Self
+    where
+        T: Into<String>,
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.push(tag.into(), None, name.into(), value.into())
+    }
+
+    /// Adds a default attribute to every tag with the given name, that has the
+    /// given attribute value.
+    ///
+    /// The value of the condition is compared ignoring the ASCII case.
+    ///
+    /// See [`DefaultAttributes`] for usage information.
+    #[must_use]
+    pub fn add_if<T, C, D, N, V>(self, tag: T, condition: (C, D), name: N, value: V) -> Self
+    where
+        T: Into<String>,
+        C: Into<String>,
+        D: Into<String>,
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.push(
+            tag.into(),
+            Some((condition.0.into(), condition.1.into())),
+            name.into(),
+            value.into(),
+        )
+    }
+
+    /// Adds the missing default attributes to a tag.
+    fn inject(&self, tag: &mut Tag) {
+        for rule in &self.rules {
+            if rule.applies_to(tag) && !tag.attrs.iter().any(|attr| *attr.as_name() == rule.name) {
+                let mut attrs = take(&mut tag.attrs).into_vec();
+                attrs.push(value_attr(&rule.name, &rule.value));
+                tag.attrs = attrs.into_boxed_slice();
+            }
+        }
+    }
+
+    /// Creates an empty set of default attributes.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { rules: vec![] }
+    }
+
+    /// Adds a rule.
+    fn push(
+        mut self,
+        tag: String,
+        condition: Option<(String, String)>,
+        name: String,
+        value: String,
+    ) -> Self {
+        self.rules.push(DefaultRule { condition, name, tag, value });
+        self
+    }
+
+    /// Serialises the tree, after injecting the default attributes.
+    ///
+    /// The tree itself isn't modified. See [`DefaultAttributes`] for an
+    /// example.
+    #[must_use]
+    pub fn serialize(&self, html: &Html) -> String {
+        let mut injected = html.clone();
+        injected.inject_default_attributes(self);
+        injected.to_string()
+    }
+}
+
+/// Rule to add a default attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DefaultRule {
+    /// Attribute name and value that the tag must have for the rule to apply
+    condition: Option<(String, String)>,
+    /// Name of the attribute to add
+    name: String,
+    /// Name of the tags on which the rule applies
+    tag: String,
+    /// Value of the attribute to add
+    value: String,
+}
+
+impl DefaultRule {
+    /// Checks if the rule applies to the tag.
+    fn applies_to(&self, tag: &Tag) -> bool {
+        tag.as_name() == self.tag
+            && self.condition.as_ref().is_none_or(|(name, value)| {
+                tag.find_attr_value(name).is_some_and(|found| found.eq_ignore_ascii_case(value))
+            })
+    }
+}
+
+impl Html {
+    /// Adds the default attributes to the tags that don't have them.
+    ///
+    /// See [`DefaultAttributes`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let mut html = Html::parse("<main><img src='a.png' /></main>").unwrap();
+    /// html.inject_default_attributes(&DefaultAttributes::new().add("img", "alt", ""));
+    /// assert_eq!(html, r#"<main><img src='a.png' alt=""></main>"#);
+    /// ```
+    pub fn inject_default_attributes(&mut self, defaults: &DefaultAttributes) {
+        match self {
+            Self::Tag { tag, child } => {
+                defaults.inject(tag);
+                child.inject_default_attributes(defaults);
+            }
+            Self::Vec(vec) =>
+                for child in vec {
+                    child.inject_default_attributes(defaults);
+                },
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty | Self::Text(_) => (),
+        }
+    }
+}
@@ -0,0 +1,168 @@
+//! Module to access the main parts of a document, i.e., the `<head>` and
+//! `<body>` tags.
+
+use core::mem::take;
+
+use crate::errors::{safe_expect, safe_unreachable};
+use crate::{Html, Tag};
+
+impl Html {
+    /// Returns the `<body>` tag of the document, if there is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<html><head></head><body><p>Hi</p></body></html>").unwrap();
+    /// assert_eq!(html.body().unwrap(), &"<body><p>Hi</p></body>");
+    ///
+    /// assert!(Html::parse("<p>Hi</p>").unwrap().body().is_none());
+    /// ```
+    #[must_use]
+    pub fn body(&self) -> Option<&Self> {
+        self.find_tag("body")
+    }
+
+    /// Returns the `<body>` tag of the document, and creates it if the
+    /// document doesn't have one.
+    ///
+    /// When the `<body>` is created, all the content of the document, except
+    /// the doctypes and the `<head>`, is moved inside it. It is put in the
+    /// `<html>` tag if there is one, and at the root of the document
+    /// otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let mut html = Html::parse("<!DOCTYPE html><html><head></head><p>Hi</p></html>").unwrap();
+    /// assert_eq!(html.body_mut(), &"<body><p>Hi</p></body>");
+    /// assert_eq!(html, "<!DOCTYPE html><html><head></head><body><p>Hi</p></body></html>");
+    /// ```
+    pub fn body_mut(&mut self) -> &mut Self {
+        if self.body().is_none() {
+            let root = self.document_root_mut();
+            let (mut kept, content): (Vec<_>, Vec<_>) =
+                into_nodes(take(root)).into_iter().partition(|node| match node {
+                    Self::Doctype { .. } => true,
+                    Self::Tag { tag, .. } => tag.as_name() == "head",
+                    Self::Comment(_) | Self::Empty | Self::Text(_) | Self::Vec(_) => false,
+                });
+            kept.push(new_tag("body", Self::Vec(content.into_boxed_slice())));
+            *root = Self::Vec(kept.into_boxed_slice());
+        }
+        safe_expect!(self.find_tag_mut("body"), "body was created")
+    }
+
+    /// Returns the `<html>` content of the document, or the whole document if
+    /// it doesn't have an `<html>` tag.
+    fn document_root_mut(&mut self) -> &mut Self {
+        let position = match self {
+            Self::Vec(vec) => vec.iter().position(|node| is_tag(node, "html")),
+            Self::Comment(_)
+            | Self::Doctype { .. }
+            | Self::Empty
+            | Self::Tag { .. }
+            | Self::Text(_) => None,
+        };
+        let is_html = is_tag(self, "html");
+        match (position, is_html, self) {
+            (Some(idx), _, Self::Vec(vec)) => match vec.get_mut(idx) {
+                Some(Self::Tag { child, .. }) => child,
+                _ => safe_unreachable!("position of the html tag was found"),
+            },
+            (_, true, Self::Tag { child, .. }) => child,
+            (_, _, root) => root,
+        }
+    }
+
+    /// Returns the first tag with the given name.
+    fn find_tag(&self, name: &str) -> Option<&Self> {
+        match self {
+            Self::Tag { tag, .. } if tag.as_name() == name => Some(self),
+            Self::Tag { child, .. } => child.find_tag(name),
+            Self::Vec(vec) => vec.iter().find_map(|child| child.find_tag(name)),
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty | Self::Text(_) => None,
+        }
+    }
+
+    /// Returns the first tag with the given name, mutably.
+    fn find_tag_mut(&mut self, name: &str) -> Option<&mut Self> {
+        match self {
+            Self::Tag { tag, .. } if tag.as_name() == name => Some(self),
+            Self::Tag { child, .. } => child.find_tag_mut(name),
+            Self::Vec(vec) => vec.iter_mut().find_map(|child| child.find_tag_mut(name)),
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty | Self::Text(_) => None,
+        }
+    }
+
+    /// Returns the `<head>` tag of the document, if there is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<html><head><title>Hi</title></head></html>").unwrap();
+    /// assert_eq!(html.head().unwrap(), &"<head><title>Hi</title></head>");
+    /// ```
+    #[must_use]
+    pub fn head(&self) -> Option<&Self> {
+        self.find_tag("head")
+    }
+
+    /// Returns the `<head>` tag of the document, and creates it if the
+    /// document doesn't have one.
+    ///
+    /// When the `<head>` is created, it is empty, and is put at the beginning
+    /// of the `<html>` tag if there is one, or after the doctypes of the
+    /// document otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let mut html = Html::parse("<!DOCTYPE html><html><body></body></html>").unwrap();
+    /// assert_eq!(html.head_mut(), &"<head></head>");
+    /// assert_eq!(html, "<!DOCTYPE html><html><head></head><body></body></html>");
+    ///
+    /// let mut fragment = Html::parse("<p>Hi</p>").unwrap();
+    /// fragment.head_mut();
+    /// assert_eq!(fragment, "<head></head><p>Hi</p>");
+    /// ```
+    pub fn head_mut(&mut self) -> &mut Self {
+        if self.head().is_none() {
+            let root = self.document_root_mut();
+            let mut nodes = into_nodes(take(root));
+            let idx = nodes
+                .iter()
+                .position(|node| !matches!(node, Self::Doctype { .. }))
+                .unwrap_or(nodes.len());
+            nodes.insert(idx, new_tag("head", Self::Empty));
+            *root = Self::Vec(nodes.into_boxed_slice());
+        }
+        safe_expect!(self.find_tag_mut("head"), "head was created")
+    }
+}
+
+/// Returns the nodes contained in an [`Html`], unwrapping the vecs.
+pub(super) fn into_nodes(html: Html) -> Vec<Html> {
+    match html {
+        Html::Vec(vec) => vec.into_vec(),
+        Html::Empty => vec![],
+        Html::Comment(_) | Html::Doctype { .. } | Html::Tag { .. } | Html::Text(_) => vec![html],
+    }
+}
+
+/// Checks if the node is a tag with the given name.
+fn is_tag(node: &Html, name: &str) -> bool {
+    node.as_tag().is_some_and(|(tag, _)| tag.as_name() == name)
+}
+
+/// Creates a tag without attributes.
+pub(super) fn new_tag(name: &str, child: Html) -> Html {
+    Html::Tag { tag: Tag::from((name.to_owned(), Box::default())), child: Box::new(child) }
+}
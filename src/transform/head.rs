@@ -0,0 +1,173 @@
+//! Module to insert or update the metadata of a document, in its `<head>`.
+
+use core::mem::take;
+
+use super::document::{into_nodes, new_tag};
+use crate::{Attribute, Html, Tag};
+
+impl Html {
+    /// Adds a stylesheet to the `<head>` of the document, if it isn't already
+    /// linked.
+    ///
+    /// The `<head>` is created if the document doesn't have one (see
+    /// [`Html::head_mut`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let mut html = Html::parse("<html><head></head><body></body></html>").unwrap();
+    /// html.add_stylesheet("/style.css");
+    /// html.add_stylesheet("/style.css");
+    ///
+    /// assert_eq!(
+    ///     html,
+    ///     r#"<html><head><link rel="stylesheet" href="/style.css"></head><body></body></html>"#
+    /// );
+    /// ```
+    pub fn add_stylesheet(&mut self, href: &str) {
+        let head = self.head_mut();
+        let linked = find_head_child(head, |tag| {
+            tag.as_name() == "link"
+                && tag.find_attr_value("href").is_some_and(|value| value == href)
+                && tag.find_attr_value("rel").is_some_and(|rel| {
+                    rel.split_whitespace().any(|word| word.eq_ignore_ascii_case("stylesheet"))
+                })
+        })
+        .is_some();
+        if !linked {
+            push_child(
+                head,
+                Tag::from((
+                    "link".to_owned(),
+                    vec![value_attr("rel", "stylesheet"), value_attr("href", href)]
+                        .into_boxed_slice(),
+                ))
+                .into_html(Self::Empty),
+            );
+        }
+    }
+
+    /// Sets the content of the `<meta>` tag with the given name, and creates
+    /// it if the `<head>` of the document doesn't have one.
+    ///
+    /// The `<head>` is created if the document doesn't have one (see
+    /// [`Html::head_mut`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let mut html =
+    ///     Html::parse(r#"<head><meta name="author" content="Someone" /></head>"#).unwrap();
+    /// html.set_meta("author", "Me");
+    /// html.set_meta("description", "A page");
+    ///
+    /// assert_eq!(
+    ///     html,
+    ///     r#"<head><meta name="author" content="Me"><meta name="description" content="A page"></head>"#
+    /// );
+    /// ```
+    pub fn set_meta(&mut self, name: &str, content: &str) {
+        let head = self.head_mut();
+        if let Some(Self::Tag { tag, .. }) = find_head_child(head, |tag| {
+            tag.as_name() == "meta"
+                && tag.find_attr_value("name").is_some_and(|value| value == name)
+        }) {
+            set_attr(tag, "content", content);
+        } else {
+            push_child(
+                head,
+                Tag::from((
+                    "meta".to_owned(),
+                    vec![value_attr("name", name), value_attr("content", content)]
+                        .into_boxed_slice(),
+                ))
+                .into_html(Self::Empty),
+            );
+        }
+    }
+
+    /// Sets the title of the document, and creates the `<title>` tag if the
+    /// `<head>` of the document doesn't have one.
+    ///
+    /// The `<head>` is created if the document doesn't have one (see
+    /// [`Html::head_mut`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let mut html = Html::parse("<!DOCTYPE html><p>Content</p>").unwrap();
+    /// html.set_title("First");
+    /// html.set_title("Second");
+    ///
+    /// assert_eq!(html, "<!DOCTYPE html><head><title>Second</title></head><p>Content</p>");
+    /// ```
+    pub fn set_title(&mut self, text: &str) {
+        let head = self.head_mut();
+        let new_child = Box::new(Self::Text(text.into()));
+        if let Some(Self::Tag { child, .. }) = find_head_child(head, |tag| tag.as_name() == "title")
+        {
+            *child = new_child;
+        } else {
+            push_child(head, new_tag("title", *new_child));
+        }
+    }
+}
+
+impl Tag {
+    /// Wraps the tag in an [`Html`] node, with the given child.
+    fn into_html(self, child: Html) -> Html {
+        Html::Tag { tag: self, child: Box::new(child) }
+    }
+}
+
+/// Finds a direct child of the head, that is a tag satisfying the
+/// predicate.
+fn find_head_child<F: Fn(&Tag) -> bool>(head: &mut Html, predicate: F) -> Option<&mut Html> {
+    let Html::Tag { child, .. } = head else { return None };
+    let is_wanted = |node: &Html| node.as_tag().is_some_and(|(tag, _)| predicate(tag));
+    match &mut **child {
+        Html::Vec(vec) => vec.iter_mut().find(|node| is_wanted(node)),
+        node if is_wanted(node) => Some(node),
+        Html::Comment(_)
+        | Html::Doctype { .. }
+        | Html::Empty
+        | Html::Tag { .. }
+        | Html::Text(_) => None,
+    }
+}
+
+/// Appends a node at the end of the children of a tag.
+fn push_child(parent: &mut Html, node: Html) {
+    if let Html::Tag { child, .. } = parent {
+        let mut nodes = into_nodes(take(&mut **child));
+        nodes.push(node);
+        **child = Html::Vec(nodes.into_boxed_slice());
+    }
+}
+
+/// Sets the value of an attribute of a tag, and creates it if needed.
+fn set_attr(tag: &mut Tag, name: &str, value: &str) {
+    let new_attr = value_attr(name, value);
+    if let Some(attr) = tag.attrs.iter_mut().find(|attr| attr.as_name() == name) {
+        *attr = new_attr;
+    } else {
+        let mut attrs = take(&mut tag.attrs).into_vec();
+        attrs.push(new_attr);
+        tag.attrs = attrs.into_boxed_slice();
+    }
+}
+
+/// Creates an attribute with a value, delimited by double quotes if possible.
+pub(super) fn value_attr(name: &str, value: &str) -> Attribute {
+    Attribute::NameValue {
+        double_quote: !value.contains('"'),
+        name: name.into(),
+        value: value.to_owned(),
+    }
+}
@@ -0,0 +1,93 @@
+//! Module to highlight the occurrences of a term in the texts of an html tree.
+
+use core::mem::take;
+
+use crate::{Html, Tag};
+
+impl Html {
+    /// Splits the text nodes of the tree to wrap each occurrence of the
+    /// pattern in a new element.
+    ///
+    /// The pattern is searched literally and is case-sensitive. All the other
+    /// nodes are kept untouched, and the contents of the `<script>` and
+    /// `<style>` tags are never altered. An empty pattern doesn't change the
+    /// tree.
+    ///
+    /// This is useful to highlight the terms of a search query, by wrapping
+    /// them in `<mark>` tags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let mut html = Html::parse("<p>To filter, use a filter.</p><script>filter()</script>").unwrap();
+    /// let mark = Tag::from(("mark".to_owned(), Box::default()));
+    ///
+    /// html.wrap_text_matches("filter", &mark);
+    ///
+    /// assert_eq!(
+    ///     html,
+    ///     "<p>To <mark>filter</mark>, use a <mark>filter</mark>.</p><script>filter()</script>"
+    /// );
+    /// ```
+    pub fn wrap_text_matches(&mut self, pattern: &str, tag: &Tag) {
+        if !pattern.is_empty() {
+            self.wrap_text_matches_aux(pattern, tag);
+        }
+    }
+
+    /// Wrapper for [`Html::wrap_text_matches`], once the pattern was checked
+    /// to be non-empty.
+    fn wrap_text_matches_aux(&mut self, pattern: &str, tag: &Tag) {
+        match self {
+            Self::Text(text) => {
+                let mut pieces = split_text_matches(text, pattern, tag);
+                *self = if pieces.len() == 1 {
+                    pieces.pop().unwrap_or_default()
+                } else {
+                    Self::Vec(pieces.into_boxed_slice())
+                };
+            }
+            Self::Tag { tag: parent, child } =>
+                if !parent.is_raw_text() {
+                    child.wrap_text_matches_aux(pattern, tag);
+                },
+            Self::Vec(vec) => {
+                let mut wrapped = Vec::with_capacity(vec.len());
+                for mut child in take(vec) {
+                    if let Self::Text(text) = &child {
+                        wrapped.extend(split_text_matches(text, pattern, tag));
+                    } else {
+                        child.wrap_text_matches_aux(pattern, tag);
+                        wrapped.push(child);
+                    }
+                }
+                *vec = wrapped.into_boxed_slice();
+            }
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty => (),
+        }
+    }
+}
+
+/// Splits a text into text nodes and tags that wrap the occurrences of the
+/// pattern.
+///
+/// The pattern must be non-empty.
+fn split_text_matches(text: &str, pattern: &str, tag: &Tag) -> Vec<Html> {
+    let mut pieces = vec![];
+    let mut rest = text;
+    while let Some((before, after)) = rest.split_once(pattern) {
+        if !before.is_empty() {
+            pieces.push(Html::Text(before.into()));
+        }
+        let mut wrapper = tag.clone();
+        wrapper.renew_id();
+        pieces.push(Html::Tag { tag: wrapper, child: Box::new(Html::Text(pattern.into())) });
+        rest = after;
+    }
+    if !rest.is_empty() || pieces.is_empty() {
+        pieces.push(Html::Text(rest.into()));
+    }
+    pieces
+}
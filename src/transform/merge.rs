@@ -0,0 +1,192 @@
+//! Module to combine multiple html trees into one.
+
+use crate::{Html, Tag};
+
+/// Parts of the merged document, filled document after document.
+#[derive(Default)]
+struct DocumentParts {
+    /// Content of the `<body>` tags
+    body: Vec<Html>,
+    /// First `<body>` tag, kept for its attributes
+    body_tag: Option<Tag>,
+    /// First doctype
+    doctype: Option<Html>,
+    /// Content of the `<head>` tags, without duplicates
+    head: Vec<Html>,
+    /// First `<html>` tag, kept for its attributes
+    html_tag: Option<Tag>,
+}
+
+impl DocumentParts {
+    /// Checks if a `<title>` was already added to the head.
+    fn has_title(&self) -> bool {
+        self.head.iter().any(|node| node.as_tag().is_some_and(|(tag, _)| tag.as_name() == "title"))
+    }
+
+    /// Builds the merged document.
+    fn into_html(self) -> Html {
+        let head = Html::Tag {
+            tag: Tag::from(("head".to_owned(), Box::default())),
+            child: Box::new(Html::Vec(self.head.into_boxed_slice())),
+        };
+        let body = Html::Tag {
+            tag: self.body_tag.unwrap_or_else(|| Tag::from(("body".to_owned(), Box::default()))),
+            child: Box::new(Html::Vec(self.body.into_boxed_slice())),
+        };
+        let html = Html::Tag {
+            tag: self.html_tag.unwrap_or_else(|| Tag::from(("html".to_owned(), Box::default()))),
+            child: Box::new(Html::Vec(Box::new([head, body]))),
+        };
+        match self.doctype {
+            Some(doctype) => Html::Vec(Box::new([doctype, html])),
+            None => html,
+        }
+    }
+
+    /// Adds the content of a `<body>` tag.
+    fn push_body(&mut self, node: Html) {
+        match node {
+            Html::Vec(vec) => self.body.extend(vec),
+            Html::Empty => (),
+            Html::Comment(_) | Html::Doctype { .. } | Html::Tag { .. } | Html::Text(_) =>
+                self.body.push(node),
+        }
+    }
+
+    /// Adds a node found at the root of a document.
+    fn push_document(&mut self, node: Html) {
+        match node {
+            Html::Vec(vec) => vec.into_iter().for_each(|child| self.push_document(child)),
+            Html::Doctype { .. } =>
+                if self.doctype.is_none() {
+                    self.doctype = Some(node);
+                },
+            Html::Tag { tag, child } if tag.as_name() == "html" => {
+                self.html_tag.get_or_insert(tag);
+                self.push_html_child(*child);
+            }
+            Html::Comment(_) | Html::Empty | Html::Tag { .. } | Html::Text(_) =>
+                self.push_html_child(node),
+        }
+    }
+
+    /// Adds a node of the `<head>` tag, if it isn't already present.
+    ///
+    /// Only the first `<title>` is kept.
+    fn push_head(&mut self, node: Html) {
+        match node {
+            Html::Vec(vec) => vec.into_iter().for_each(|child| self.push_head(child)),
+            Html::Empty => (),
+            Html::Text(text) if text.trim().is_empty() => (),
+            Html::Tag { tag, .. } if tag.as_name() == "title" && self.has_title() => (),
+            Html::Comment(_) | Html::Doctype { .. } | Html::Tag { .. } | Html::Text(_) =>
+                if !self.head.contains(&node) {
+                    self.head.push(node);
+                },
+        }
+    }
+
+    /// Adds a node found in a `<html>` tag, or at the root of a document
+    /// without `<html>` tag.
+    fn push_html_child(&mut self, node: Html) {
+        match node {
+            Html::Vec(vec) => vec.into_iter().for_each(|child| self.push_html_child(child)),
+            Html::Tag { tag, child } if tag.as_name() == "head" => self.push_head(*child),
+            Html::Tag { tag, child } if tag.as_name() == "body" => {
+                self.body_tag.get_or_insert(tag);
+                self.push_body(*child);
+            }
+            Html::Text(text) if text.trim().is_empty() => (),
+            Html::Comment(_)
+            | Html::Doctype { .. }
+            | Html::Empty
+            | Html::Tag { .. }
+            | Html::Text(_) => self.push_body(node),
+        }
+    }
+}
+
+impl Html {
+    /// Concatenates multiple trees, or fragments, into one.
+    ///
+    /// The fragments are put one after the other, without any restructuring.
+    /// To combine whole documents into a well-formed document, see
+    /// [`Html::merge_bodies`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let parts = vec![
+    ///     Html::parse("<h1>Report</h1>").unwrap(),
+    ///     Html::Empty,
+    ///     Html::parse("<p>First</p><p>Second</p>").unwrap(),
+    /// ];
+    /// assert_eq!(Html::concat(parts), "<h1>Report</h1><p>First</p><p>Second</p>");
+    /// ```
+    #[must_use]
+    pub fn concat(parts: Vec<Self>) -> Self {
+        let mut nodes = vec![];
+        for part in parts {
+            push_flattened(part, &mut |node| nodes.push(node));
+        }
+        if nodes.len() <= 1 {
+            nodes.pop().unwrap_or_default()
+        } else {
+            Self::Vec(nodes.into_boxed_slice())
+        }
+    }
+
+    /// Merges multiple documents into one well-formed document, with a single
+    /// doctype, `<html>`, `<head>` and `<body>`.
+    ///
+    /// - The doctype and the attributes of the `<html>` and `<body>` tags are
+    ///   taken from the first document that has them.
+    /// - The contents of the `<head>` tags are merged, without the duplicated
+    ///   nodes. Only the first `<title>` is kept.
+    /// - The contents of the `<body>` tags are concatenated, in order.
+    ///
+    /// Documents without `<html>` or `<body>` tags are treated as the content
+    /// of a `<body>`, so fragments can also be merged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let docs = vec![
+    ///     Html::parse(
+    ///         r#"<!DOCTYPE html><html lang="en"><head><meta charset="utf-8" /><title>Report</title></head><body><h1>Sales</h1></body></html>"#,
+    ///     )
+    ///     .unwrap(),
+    ///     Html::parse(
+    ///         r#"<html><head><meta charset="utf-8" /><title>Other</title></head><body><p>Up</p></body></html>"#,
+    ///     )
+    ///     .unwrap(),
+    ///     Html::parse("<p>Fragment</p>").unwrap(),
+    /// ];
+    ///
+    /// assert_eq!(
+    ///     Html::merge_bodies(docs),
+    ///     r#"<!DOCTYPE html><html lang="en"><head><meta charset="utf-8"><title>Report</title></head><body><h1>Sales</h1><p>Up</p><p>Fragment</p></body></html>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn merge_bodies(docs: Vec<Self>) -> Self {
+        let mut parts = DocumentParts::default();
+        for doc in docs {
+            parts.push_document(doc);
+        }
+        parts.into_html()
+    }
+}
+
+/// Applies the function to the non-empty nodes, after unwrapping the vecs.
+fn push_flattened<F: FnMut(Html)>(node: Html, push: &mut F) {
+    match node {
+        Html::Vec(vec) => vec.into_iter().for_each(|child| push_flattened(child, push)),
+        Html::Empty => (),
+        Html::Comment(_) | Html::Doctype { .. } | Html::Tag { .. } | Html::Text(_) => push(node),
+    }
+}
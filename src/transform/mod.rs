@@ -0,0 +1,28 @@
+//! Module to transform an [`Html`](crate::Html) tree in place.
+//!
+//! Contrary to the filters, that select the wanted nodes, the transforms
+//! modify the tree and keep all the other nodes untouched.
+
+mod changelog;
+mod defaults;
+mod document;
+mod head;
+mod highlight;
+mod merge;
+mod namespaces;
+mod pipeline;
+mod prune;
+mod reparse;
+mod sanitize;
+mod slots;
+mod strip;
+mod urls;
+mod void;
+mod whitespace;
+
+pub use changelog::{ChangeLog, Edit};
+pub use defaults::DefaultAttributes;
+pub use pipeline::Pipeline;
+pub use sanitize::SanitizePolicy;
+pub use void::VoidPolicy;
+pub use whitespace::collapse_whitespace;
@@ -0,0 +1,64 @@
+//! Module to modernise the deprecated namespaced attributes of the SVG
+//! elements.
+
+use core::mem::take;
+
+use crate::types::tag::attribute_replacement;
+use crate::{Html, Tag};
+
+impl Html {
+    /// Replaces the deprecated namespaced attributes by the attributes of
+    /// SVG 2, i.e., `xlink:href` by `href`, and `xml:lang` by `lang`.
+    ///
+    /// If a tag already has the new attribute, the deprecated one is removed,
+    /// as the new one takes precedence in the browsers. The other prefixed
+    /// attributes, like the `xmlns:xlink` declarations, are kept as is.
+    ///
+    /// To only modernise the attributes in the output, without modifying the
+    /// tree, see [`PrettyOptions::modernize_attributes`](crate::PrettyOptions::modernize_attributes).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let mut html = Html::parse(
+    ///     r##"<svg xml:lang="en"><use xlink:href="#a"/><a href="/new" xlink:href="/old">Link</a></svg>"##,
+    /// )
+    /// .unwrap();
+    /// html.modernize_attributes();
+    /// assert_eq!(
+    ///     html,
+    ///     r##"<svg lang="en"><use href="#a"></use><a href="/new">Link</a></svg>"##
+    /// );
+    /// ```
+    pub fn modernize_attributes(&mut self) {
+        match self {
+            Self::Tag { tag, child } => {
+                tag.modernize_attributes();
+                child.modernize_attributes();
+            }
+            Self::Vec(vec) => vec.iter_mut().for_each(Self::modernize_attributes),
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty | Self::Text(_) => (),
+        }
+    }
+}
+
+impl Tag {
+    /// Replaces the deprecated namespaced attributes of the tag.
+    ///
+    /// See [`Html::modernize_attributes`] for more information.
+    fn modernize_attributes(&mut self) {
+        let mut attrs = take(&mut self.attrs).into_vec();
+        let names = attrs.iter().map(|attr| attr.as_name().to_owned()).collect::<Vec<_>>();
+        attrs.retain_mut(|attr| {
+            let Some(modern) = attribute_replacement(attr.as_name()) else { return true };
+            let replaced = names.iter().any(|name| name == modern);
+            if !replaced {
+                attr.rename(modern);
+            }
+            !replaced
+        });
+        self.attrs = attrs.into_boxed_slice();
+    }
+}
@@ -0,0 +1,213 @@
+//! Module to compose the transforms, the filters and the normalisations of a
+//! tree into a reusable cleanup [`Pipeline`].
+
+use alloc::borrow::Cow;
+#[cfg(feature = "filter")]
+use core::mem::take;
+
+use super::whitespace::collapse_whitespace;
+#[cfg(feature = "filter")]
+use crate::Filter;
+use crate::control::clean_controls;
+use crate::patch::render_diff;
+use crate::{ControlPolicy, Html, SanitizePolicy};
+
+/// Step of a [`Pipeline`].
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    /// Keeps the nodes that match the filter, see [`Html::filter`]
+    #[cfg(feature = "filter")]
+    Filter(Box<Filter>),
+    /// Removes the sections with little content, see
+    /// [`Html::prune_low_content`]
+    Prune(u8),
+    /// Removes the active content, see [`Html::sanitize`]
+    Sanitize(SanitizePolicy),
+    /// Modifies the texts, in a single traversal of the tree
+    Texts(Vec<TextStep>),
+}
+
+/// Modification of the texts of a tree, that can be fused with the other
+/// ones in a single traversal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TextStep {
+    /// Removes or replaces the control characters of the texts and comments
+    Controls(ControlPolicy),
+    /// Collapses the whitespace of the texts, see
+    /// [`Html::normalize_whitespace`]
+    Whitespace,
+}
+
+/// Cleanup pipeline, that composes transforms, filters and normalisations,
+/// to apply them to many documents.
+///
+/// The steps are applied in the order they were added. The consecutive steps
+/// that only modify the texts, like [`Pipeline::normalize_whitespace`] and
+/// [`Pipeline::clean_controls`], are fused, and applied in a single traversal
+/// of the tree.
+///
+/// A cleanup pipeline is expected to be idempotent: running it on its own
+/// output mustn't change it anymore. This can be checked in the tests with
+/// [`Pipeline::check_idempotence`].
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// let pipeline = Pipeline::new()
+///     .sanitize(SanitizePolicy::Scripts)
+///     .filter(Filter::new().all(true).except_tag_name("nav"))
+///     .prune(30)
+///     .normalize_whitespace();
+///
+/// let mut html = Html::parse(
+///     "<body><nav><a href=\"/\">Home</a></nav><div><p onclick=\"track()\">\n  Some   long\n  \
+///      article.\n</p></div></body>",
+/// )
+/// .unwrap();
+/// pipeline.run(&mut html);
+/// assert_eq!(html, "<body><div><p> Some long article. </p></div></body>");
+///
+/// pipeline.check_idempotence(&html).unwrap();
+/// ```
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Pipeline {
+    /// Steps of the pipeline, in the order they are applied
+    steps: Vec<Step>,
+}
+
+impl Pipeline {
+    /// Checks that the pipeline is idempotent on a document, i.e., that
+    /// running it twice gives the same tree as running it once.
+    ///
+    /// This is a helper for the tests of the pipelines. Returns the output of
+    /// the pipeline.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with the differences between the two outputs, if
+    /// the second run changed the tree (see [`render_diff`]).
+    pub fn check_idempotence(&self, html: &Html) -> Result<Html, String> {
+        let mut once = html.clone();
+        self.run(&mut once);
+        let mut twice = once.clone();
+        self.run(&mut twice);
+        if once == twice {
+            Ok(once)
+        } else {
+            Err(format!(
+                "The pipeline isn't idempotent: the second run changed the tree.\n{}",
+                render_diff(&once, &twice)
+            ))
+        }
+    }
+
+    /// Removes or replaces the control characters of the texts and comments,
+    /// depending on the policy.
+    ///
+    /// The [`ControlPolicy::Error`] and [`ControlPolicy::Keep`] policies keep
+    /// the control characters.
+    #[must_use]
+    pub fn clean_controls(self, policy: ControlPolicy) -> Self {
+        self.push_text_step(TextStep::Controls(policy))
+    }
+
+    /// Keeps the nodes that match the filter, see [`Html::filter`].
+    #[cfg(feature = "filter")]
+    #[must_use]
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.steps.push(Step::Filter(Box::new(filter)));
+        self
+    }
+
+    /// Creates a pipeline without any step.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { steps: vec![] }
+    }
+
+    /// Collapses the whitespace of the texts, see
+    /// [`Html::normalize_whitespace`].
+    #[must_use]
+    pub fn normalize_whitespace(self) -> Self {
+        self.push_text_step(TextStep::Whitespace)
+    }
+
+    /// Removes the sections whose content score is below the threshold, see
+    /// [`Html::prune_low_content`].
+    #[must_use]
+    pub fn prune(mut self, threshold: u8) -> Self {
+        self.steps.push(Step::Prune(threshold));
+        self
+    }
+
+    /// Adds a step that modifies the texts, fused with the previous step if
+    /// it also modifies the texts.
+    fn push_text_step(mut self, step: TextStep) -> Self {
+        if let Some(Step::Texts(steps)) = self.steps.last_mut() {
+            steps.push(step);
+        } else {
+            self.steps.push(Step::Texts(vec![step]));
+        }
+        self
+    }
+
+    /// Applies the steps of the pipeline to a tree.
+    pub fn run(&self, html: &mut Html) {
+        for step in &self.steps {
+            match step {
+                #[cfg(feature = "filter")]
+                Step::Filter(filter) => *html = take(html).filter(filter),
+                Step::Prune(threshold) => html.prune_low_content(*threshold),
+                Step::Sanitize(policy) => html.sanitize(*policy),
+                Step::Texts(steps) => html.apply_text_steps(steps, false),
+            }
+        }
+    }
+
+    /// Removes the active content, like the scripts and the event handlers,
+    /// see [`Html::sanitize`].
+    #[must_use]
+    pub fn sanitize(mut self, policy: SanitizePolicy) -> Self {
+        self.steps.push(Step::Sanitize(policy));
+        self
+    }
+}
+
+impl Html {
+    /// Applies the modifications of the texts to the tree, in a single
+    /// traversal.
+    ///
+    /// `preformatted` is `true` inside the tags whose whitespace is kept.
+    fn apply_text_steps(&mut self, steps: &[TextStep], preformatted: bool) {
+        match self {
+            Self::Comment(comment) =>
+                for step in steps {
+                    if let TextStep::Controls(policy) = step
+                        && let Cow::Owned(cleaned) = clean_controls(comment, *policy)
+                    {
+                        *comment = cleaned;
+                    }
+                },
+            Self::Tag { tag, child } =>
+                child.apply_text_steps(steps, preformatted || tag.is_preformatted()),
+            Self::Text(text) =>
+                for step in steps {
+                    let modified = match step {
+                        TextStep::Controls(policy) => clean_controls(text.as_str(), *policy),
+                        TextStep::Whitespace if preformatted => continue,
+                        TextStep::Whitespace => collapse_whitespace(text.as_str()),
+                    };
+                    if let Cow::Owned(owned) = modified {
+                        *text = owned.into();
+                    }
+                },
+            Self::Vec(vec) =>
+                for child in vec {
+                    child.apply_text_steps(steps, preformatted);
+                },
+            Self::Doctype { .. } | Self::Empty => (),
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! Module to remove the boilerplate sections of a tree, like the navigation
+//! menus and the footers, depending on their amount of text.
+
+use core::mem::take;
+
+use crate::types::tag::has_name;
+use crate::{Html, Tag};
+
+/// Tags of the sections that can be pruned.
+///
+/// The `<article>` and `<main>` tags are never pruned, as they mark the
+/// content of the page.
+const CONTAINER_TAGS: [&str; 11] =
+    ["aside", "div", "footer", "form", "header", "menu", "nav", "ol", "section", "table", "ul"];
+
+/// Tags that are content, even without any text.
+const MEDIA_TAGS: [&str; 7] = ["audio", "canvas", "iframe", "img", "picture", "svg", "video"];
+
+/// Amounts of text and of markup of a subtree.
+///
+/// The statistics of a tag are computed from the statistics of its child, so
+/// that every node of the tree is only read once.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct ContentStats {
+    /// Number of characters of the texts inside links
+    link_text: usize,
+    /// Number of bytes of the tags and of the code
+    markup: usize,
+    /// Whether the subtree contains an image or a media
+    media: bool,
+    /// Number of characters of the texts, without the whitespace
+    text: usize,
+    /// Number of bytes of the texts, counted as markup inside a `<script>` or
+    /// a `<style>`
+    text_bytes: usize,
+}
+
+impl ContentStats {
+    /// Adds the statistics of a sibling subtree.
+    const fn add(&mut self, other: Self) {
+        self.link_text = self.link_text.saturating_add(other.link_text);
+        self.markup = self.markup.saturating_add(other.markup);
+        self.media |= other.media;
+        self.text = self.text.saturating_add(other.text);
+        self.text_bytes = self.text_bytes.saturating_add(other.text_bytes);
+    }
+
+    /// Checks if the subtree has a content score below the threshold, and
+    /// doesn't contain any media.
+    fn is_low_content(&self, threshold: u8) -> bool {
+        !self.media && self.score() < usize::from(threshold)
+    }
+
+    /// Computes the statistics of a text.
+    fn of_text(text: &str) -> Self {
+        let count = text.chars().filter(|ch| !ch.is_whitespace()).count();
+        Self { text: count, text_bytes: text.len(), ..Self::default() }
+    }
+
+    /// Computes the content score of the subtree, in percent.
+    ///
+    /// The score is the proportion of text in the text and the markup,
+    /// weighted by the proportion of the text that isn't in links.
+    fn score(&self) -> usize {
+        let total = self.text.saturating_add(self.markup);
+        let text_ratio = self.text.saturating_mul(100).checked_div(total).unwrap_or_default();
+        let link_density =
+            self.link_text.saturating_mul(100).checked_div(self.text).unwrap_or_default();
+        text_ratio
+            .saturating_mul(100usize.saturating_sub(link_density))
+            .checked_div(100)
+            .unwrap_or_default()
+    }
+
+    /// Computes the statistics of a tag from the statistics of its child.
+    fn wrap(mut self, tag: &Tag) -> Self {
+        let name = tag.as_name();
+        self.markup = self
+            .markup
+            .saturating_add(tag.to_opening_string().len())
+            .saturating_add(name.len().saturating_add(3));
+        self.media |= has_name(&MEDIA_TAGS, name);
+        if tag.is_raw_text() {
+            self.markup = self.markup.saturating_add(self.text_bytes);
+            self.link_text = 0;
+            self.text = 0;
+            self.text_bytes = 0;
+        }
+        if name.eq_ignore_ascii_case("a") {
+            self.link_text = self.text;
+        }
+        self
+    }
+}
+
+impl Html {
+    /// Removes the sections of the tree whose content score is below the
+    /// threshold, in percent, like the navigation menus, the footers or the
+    /// tag clouds.
+    ///
+    /// The content score of a section is the proportion of its size that is
+    /// text rather than markup, i.e., the tags with their attributes and the
+    /// content of the `<script>` and `<style>` tags, weighted by the
+    /// proportion of its text that isn't inside links. It is between `0` and
+    /// `100`: a section with only text would have a score of `100`, and a
+    /// section with only links a score of `0`. The whitespace isn't counted in
+    /// the texts.
+    ///
+    /// Only the sections are pruned, i.e., the `<div>`, `<section>`, `<nav>`,
+    /// `<header>`, `<footer>`, `<aside>`, `<form>`, `<menu>`, `<table>`,
+    /// `<ul>` and `<ol>` tags, with their content. The other tags, like the
+    /// paragraphs and the links, are kept. When a section is kept, the
+    /// sections inside it are checked in turn. The sections that contain an
+    /// image or a media are always kept.
+    ///
+    /// This is a lightweight cleanup, that only looks at the amounts of text.
+    /// Thresholds between `20` and `40` usually remove the boilerplate of
+    /// a page and keep its content.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let mut html = Html::parse(
+    ///     r#"<body><nav><a href="/">Home</a> | <a href="/blog">Blog</a></nav><div class="post"><p>The rust compiler checks the lifetimes of the references.</p></div><footer><a href="/legal">Legal</a></footer></body>"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// html.prune_low_content(30);
+    /// assert_eq!(
+    ///     html,
+    ///     r#"<body><div class="post"><p>The rust compiler checks the lifetimes of the references.</p></div></body>"#
+    /// );
+    /// ```
+    pub fn prune_low_content(&mut self, threshold: u8) {
+        self.prune_low_content_aux(threshold);
+    }
+
+    /// Wrapper for [`Html::prune_low_content`], that returns the statistics
+    /// of the subtree before it was pruned.
+    ///
+    /// The sections inside a section are pruned before the section is
+    /// checked, which gives the same tree as checking the outer sections
+    /// first, as the statistics are those of the original subtrees.
+    fn prune_low_content_aux(&mut self, threshold: u8) -> ContentStats {
+        match self {
+            Self::Tag { tag, child } => {
+                let stats = child.prune_low_content_aux(threshold).wrap(tag);
+                if has_name(&CONTAINER_TAGS, tag.as_name()) && stats.is_low_content(threshold) {
+                    *self = Self::Empty;
+                }
+                stats
+            }
+            Self::Text(text) => ContentStats::of_text(text),
+            Self::Vec(vec) => {
+                let mut stats = ContentStats::default();
+                let mut kept = take(vec).into_vec();
+                for child in &mut kept {
+                    stats.add(child.prune_low_content_aux(threshold));
+                }
+                kept.retain(|child| !child.is_empty());
+                *self = if kept.len() > 1 {
+                    Self::Vec(kept.into_boxed_slice())
+                } else {
+                    kept.pop().unwrap_or_default()
+                };
+                stats
+            }
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty => ContentStats::default(),
+        }
+    }
+}
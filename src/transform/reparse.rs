@@ -0,0 +1,114 @@
+//! Module to parse a fragment again and replace a node of the tree with it.
+
+use core::slice::from_mut;
+
+use crate::Html;
+
+/// Tags that structure a document, and that can only replace themselves.
+const DOCUMENT_TAGS: [&str; 3] = ["body", "head", "html"];
+
+impl Html {
+    /// Returns the children of the node, mutably.
+    ///
+    /// The children of a vec are its nodes, and the children of a tag are the
+    /// nodes of its content.
+    fn children_mut(&mut self) -> &mut [Self] {
+        match self {
+            Self::Vec(vec) => vec,
+            Self::Tag { child, .. } => match &mut **child {
+                Self::Vec(vec) => vec,
+                Self::Empty => &mut [],
+                node @ (Self::Comment(_)
+                | Self::Doctype { .. }
+                | Self::Tag { .. }
+                | Self::Text(_)) => from_mut(node),
+            },
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty | Self::Text(_) => &mut [],
+        }
+    }
+
+    /// Checks if the node, or one of its descendants, satisfies the predicate.
+    fn contains_node<F: Fn(&Self) -> bool>(&self, predicate: &F) -> bool {
+        predicate(self)
+            || match self {
+                Self::Tag { child, .. } => child.contains_node(predicate),
+                Self::Vec(vec) => vec.iter().any(|child| child.contains_node(predicate)),
+                Self::Comment(_) | Self::Doctype { .. } | Self::Empty | Self::Text(_) => false,
+            }
+    }
+
+    /// Returns the node at the given path, mutably.
+    ///
+    /// See [`Html::reparse_node`] for the format of the path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path doesn't lead to a node of the tree.
+    pub(super) fn node_at_mut(&mut self, path: &[usize]) -> Result<&mut Self, String> {
+        let mut node = self;
+        for (depth, idx) in path.iter().enumerate() {
+            node = node
+                .children_mut()
+                .get_mut(*idx)
+                .ok_or_else(|| format!("Invalid path: no node at index {idx} at depth {depth}."))?;
+        }
+        Ok(node)
+    }
+
+    /// Parses a fragment and replaces the node at the given path with it.
+    ///
+    /// The path is the list of the indices of the children to follow from the
+    /// root to reach the node. The children of a tag are the nodes of its
+    /// content, and the empty path designates the root.
+    ///
+    /// This is useful to update a document when only the source of one of its
+    /// sections changed, without parsing the whole document again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, and leaves the tree untouched, if
+    ///
+    /// - the path doesn't lead to a node of the tree;
+    /// - the fragment isn't valid html (see [`Html::parse`]);
+    /// - the fragment doesn't fit at the place of the node: doctypes are only
+    ///   accepted at the root of the document, and `<html>`, `<head>` and
+    ///   `<body>` tags can only replace a tag of the same name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let mut html =
+    ///     Html::parse("<main><section><p>Old</p></section><section>Other</section></main>").unwrap();
+    ///
+    /// html.reparse_node(&[0], "<section><p>New</p><p>Text</p></section>").unwrap();
+    /// assert_eq!(
+    ///     html,
+    ///     "<main><section><p>New</p><p>Text</p></section><section>Other</section></main>"
+    /// );
+    ///
+    /// assert!(html.reparse_node(&[5], "<p />").is_err());
+    /// assert!(html.reparse_node(&[1], "<body />").is_err());
+    /// assert!(html.reparse_node(&[1], "<p></p a>").is_err());
+    /// ```
+    pub fn reparse_node(&mut self, path: &[usize], new_source: &str) -> Result<(), String> {
+        let at_root = path.len() <= usize::from(matches!(self, Self::Vec(_)));
+        let node = self.node_at_mut(path)?;
+        let fragment = Self::parse(new_source).map_err(|err| err.to_string())?;
+        if !at_root && fragment.contains_node(&|child| matches!(child, Self::Doctype { .. })) {
+            return Err("Doctypes are only allowed at the root of the document.".to_owned());
+        }
+        let replaced_name = node.as_tag().map(|(tag, _)| tag.as_name());
+        if let Some(name) = DOCUMENT_TAGS.into_iter().find(|name| {
+            Some(*name) != replaced_name
+                && fragment.contains_node(&|child| {
+                    child.as_tag().is_some_and(|(tag, _)| tag.as_name() == *name)
+                })
+        }) {
+            return Err(format!("A <{name}> tag can only replace a <{name}> tag."));
+        }
+        *node = fragment;
+        Ok(())
+    }
+}
@@ -0,0 +1,151 @@
+//! Module to remove the active content of a tree, like the scripts and the
+//! event handlers, before displaying untrusted html.
+
+use core::mem::take;
+
+use crate::types::tag::has_name;
+use crate::{Attribute, Html, Tag, decode_entities};
+
+/// Attributes whose value is a url, that can run code with the
+/// `javascript:` scheme.
+const URL_ATTRIBUTES: [&str; 10] = [
+    "action",
+    "background",
+    "cite",
+    "data",
+    "formaction",
+    "href",
+    "ping",
+    "poster",
+    "src",
+    "xlink:href",
+];
+
+/// Tags that run code or embed other documents, removed with their content
+/// by every policy.
+const SCRIPT_TAGS: [&str; 8] =
+    ["applet", "base", "embed", "frame", "frameset", "iframe", "object", "script"];
+
+/// Tags that style the page or send data, removed with their content by
+/// [`SanitizePolicy::Strict`].
+const STRICT_TAGS: [&str; 4] = ["form", "link", "meta", "style"];
+
+/// Url schemes that run code when the url is opened.
+const SCRIPT_SCHEMES: [&str; 2] = ["javascript:", "vbscript:"];
+
+/// Content removed by [`Html::sanitize`].
+///
+/// The policies remove the content that could run code or change the page
+/// around the document, when untrusted html is displayed. They don't
+/// validate the rest of the document, so the output should still be
+/// displayed in a context that expects html.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SanitizePolicy {
+    /// Removes the scripts:
+    ///
+    /// - the tags that run code or embed other documents, like `<script>`,
+    ///   `<iframe>` and `<object>`, with their content, and the `<base>` tags,
+    ///   that change the target of the relative urls;
+    /// - the event handlers, like `onclick`, and the `srcdoc` attributes;
+    /// - the url attributes, like `href` and `src`, whose value has a
+    ///   `javascript:` or `vbscript:` scheme. The character references, the
+    ///   case and the whitespace ignored by the browsers, like in `java&#x09;
+    ///   script:`, don't hide the scheme.
+    #[default]
+    Scripts,
+    /// Removes the scripts, like [`SanitizePolicy::Scripts`], and the content
+    /// that styles the page or sends data: the `<style>`, `<link>`, `<meta>`
+    /// and `<form>` tags, with their content, and the `style` attributes.
+    Strict,
+}
+
+impl SanitizePolicy {
+    /// Checks if an attribute must be removed.
+    fn removes_attribute(self, attr: &Attribute) -> bool {
+        let name = attr.as_name();
+        name.get(..2).is_some_and(|prefix| prefix.eq_ignore_ascii_case("on"))
+            || name.eq_ignore_ascii_case("srcdoc")
+            || (self == Self::Strict && name.eq_ignore_ascii_case("style"))
+            || (has_name(&URL_ATTRIBUTES, name)
+                && attr.as_value().is_some_and(|url| has_script_scheme(url)))
+    }
+
+    /// Checks if a tag must be removed, with its content.
+    fn removes_tag(self, tag: &Tag) -> bool {
+        has_name(&SCRIPT_TAGS, tag.as_name())
+            || (self == Self::Strict && has_name(&STRICT_TAGS, tag.as_name()))
+    }
+}
+
+impl Html {
+    /// Removes the active content of the tree, like the scripts and the event
+    /// handlers, depending on the policy.
+    ///
+    /// This is useful before displaying untrusted html, like comments or
+    /// e-mails. See [`SanitizePolicy`] for the content removed by each
+    /// policy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let mut html = Html::parse(
+    ///     r#"<div onclick="steal()" style="color: red"><script>steal()</script><a href=" JavaScript:steal()">Link</a><a href="/home">Home</a></div>"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// html.sanitize(SanitizePolicy::Scripts);
+    /// assert_eq!(html, r#"<div style="color: red"><a>Link</a><a href="/home">Home</a></div>"#);
+    ///
+    /// html.sanitize(SanitizePolicy::Strict);
+    /// assert_eq!(html, r#"<div><a>Link</a><a href="/home">Home</a></div>"#);
+    /// ```
+    pub fn sanitize(&mut self, policy: SanitizePolicy) {
+        match self {
+            Self::Tag { tag, .. } if policy.removes_tag(tag) => *self = Self::Empty,
+            Self::Tag { tag, child } => {
+                if tag.attrs.iter().any(|attr| policy.removes_attribute(attr)) {
+                    tag.attrs = take(&mut tag.attrs)
+                        .into_vec()
+                        .into_iter()
+                        .filter(|attr| !policy.removes_attribute(attr))
+                        .collect();
+                }
+                child.sanitize(policy);
+            }
+            Self::Vec(vec) => {
+                let mut kept = take(vec).into_vec();
+                for child in &mut kept {
+                    child.sanitize(policy);
+                }
+                kept.retain(|child| !child.is_empty());
+                *self = if kept.len() > 1 {
+                    Self::Vec(kept.into_boxed_slice())
+                } else {
+                    kept.pop().unwrap_or_default()
+                };
+            }
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty | Self::Text(_) => (),
+        }
+    }
+}
+
+/// Checks if a url has a scheme that runs code, like `javascript:`.
+///
+/// The url is read like the browsers do: the character references are
+/// decoded, the leading spaces and control characters are ignored, as are the
+/// tabulations and line breaks inside the url, and the scheme is
+/// case-insensitive.
+fn has_script_scheme(url: &str) -> bool {
+    let decoded = decode_entities(url);
+    let scheme = decoded
+        .trim_start_matches(|ch: char| ch <= ' ')
+        .chars()
+        .filter(|ch| !matches!(ch, '\t' | '\n' | '\r'))
+        .take(SCRIPT_SCHEMES.iter().map(|scheme| scheme.len()).max().unwrap_or_default())
+        .collect::<String>();
+    SCRIPT_SCHEMES.iter().any(|script| {
+        scheme.get(..script.len()).is_some_and(|prefix| prefix.eq_ignore_ascii_case(script))
+    })
+}
@@ -0,0 +1,64 @@
+//! Module to fill the placeholders of a skeleton with html fragments.
+
+use core::hash::BuildHasher;
+use std::collections::HashMap;
+
+use crate::{Html, Tag};
+
+impl Html {
+    /// Replaces the placeholders of the tree with the given fragments.
+    ///
+    /// The placeholders are either `<slot name="x">` tags, or tags with a
+    /// `data-slot="x"` attribute. Each placeholder is replaced, with its
+    /// content, by the fragment associated to its name in the map. The
+    /// placeholders without an associated fragment are kept untouched, and
+    /// the inserted fragments aren't searched for other placeholders.
+    ///
+    /// Combined with the filters, this can be used as a minimal templating
+    /// system.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use html_filter::*;
+    ///
+    /// let mut html = Html::parse(
+    ///     r#"<main><slot name="title" /><div data-slot="content">Default</div><slot name="footer" /></main>"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// let mut slots = HashMap::new();
+    /// slots.insert("title", Html::parse("<h1>Title</h1>").unwrap());
+    /// slots.insert("content", Html::parse("<p>Text</p>").unwrap());
+    /// html.fill_slots(&slots);
+    ///
+    /// assert_eq!(html, r#"<main><h1>Title</h1><p>Text</p><slot name="footer"></slot></main>"#);
+    /// ```
+    pub fn fill_slots<S: BuildHasher>(&mut self, map: &HashMap<&str, Self, S>) {
+        match self {
+            Self::Tag { tag, child } => match tag.slot_name().and_then(|name| map.get(name)) {
+                Some(fragment) => {
+                    *self = fragment.clone();
+                    self.renew_ids();
+                }
+                None => child.fill_slots(map),
+            },
+            Self::Vec(vec) => vec.iter_mut().for_each(|child| child.fill_slots(map)),
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty | Self::Text(_) => (),
+        }
+    }
+}
+
+impl Tag {
+    /// Returns the name of the slot, if the tag is a placeholder.
+    fn slot_name(&self) -> Option<&str> {
+        if self.as_name() == "slot" {
+            self.find_attr_value("name")
+        } else {
+            self.find_attr_value("data-slot")
+        }
+        .map(String::as_str)
+    }
+}
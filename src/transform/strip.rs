@@ -0,0 +1,62 @@
+//! Module to remove the sections of a tree, depending on a marker attribute.
+
+use core::mem::take;
+
+use crate::Html;
+
+impl Html {
+    /// Removes the tags, with their content, whose attribute `name` satisfies
+    /// the predicate.
+    ///
+    /// The predicate is called with the value of the attribute, or with an
+    /// empty string if the attribute doesn't have a value. The tags without
+    /// the attribute are kept.
+    ///
+    /// This is useful to prune html at build time, for instance to remove
+    /// the sections of other environments, disabled features or A/B variants.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let mut html = Html::parse(
+    ///     r#"<main><div data-env="dev">Debug</div><div data-env="prod">Shop</div><p>Always</p></main>"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// html.strip_where_attr("data-env", |env| env != "prod");
+    ///
+    /// assert_eq!(html, r#"<main><div data-env="prod">Shop</div><p>Always</p></main>"#);
+    /// ```
+    pub fn strip_where_attr<F: FnMut(&str) -> bool>(&mut self, name: &str, mut predicate: F) {
+        self.strip_where_attr_aux(name, &mut predicate);
+    }
+
+    /// Wrapper for [`Html::strip_where_attr`], to borrow the predicate.
+    fn strip_where_attr_aux<F: FnMut(&str) -> bool>(&mut self, name: &str, predicate: &mut F) {
+        match self {
+            Self::Tag { tag, child } => {
+                if tag
+                    .as_attrs()
+                    .iter()
+                    .find(|attr| attr.as_name() == name)
+                    .is_some_and(|attr| predicate(attr.as_value().map_or("", String::as_str)))
+                {
+                    *self = Self::Empty;
+                } else {
+                    child.strip_where_attr_aux(name, predicate);
+                }
+            }
+            Self::Vec(vec) => {
+                let mut kept = take(vec).into_vec();
+                for child in &mut kept {
+                    child.strip_where_attr_aux(name, predicate);
+                }
+                kept.retain(|child| !child.is_empty());
+                *vec = kept.into_boxed_slice();
+            }
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty | Self::Text(_) => (),
+        }
+    }
+}
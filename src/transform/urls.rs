@@ -0,0 +1,160 @@
+//! Module to rewrite the urls referenced by an html tree.
+
+use crate::{Attribute, Html, Tag};
+
+/// Attributes that contain exactly one url.
+const URL_ATTRIBUTES: [&str; 12] = [
+    "action",
+    "background",
+    "cite",
+    "data",
+    "formaction",
+    "href",
+    "longdesc",
+    "manifest",
+    "poster",
+    "src",
+    "usemap",
+    "xlink:href",
+];
+
+impl Html {
+    /// Rewrites all the urls of the tree with the given function.
+    ///
+    /// The urls are found in the attributes that reference a resource, such
+    /// as `href`, `src` or `action`. Composite values are split and each url
+    /// is rewritten individually:
+    ///
+    /// - each candidate of a `srcset` attribute, keeping the descriptors (e.g.
+    ///   `2x` or `480w`);
+    /// - the url of the `content` attribute of a `<meta http-equiv="refresh">`,
+    ///   keeping the delay.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let mut html = Html::parse(
+    ///     r#"<a href="/home">Home</a><img src="a.png" srcset="a.png 1x, a-2.png 2x" /><meta http-equiv="refresh" content="5; url=/next" />"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// html.rewrite_urls(|url| format!("https://example.com/{}", url.trim_start_matches('/')));
+    ///
+    /// assert_eq!(
+    ///     html,
+    ///     r#"<a href="https://example.com/home">Home</a><img src="https://example.com/a.png" srcset="https://example.com/a.png 1x, https://example.com/a-2.png 2x"><meta http-equiv="refresh" content="5; url=https://example.com/next">"#
+    /// );
+    /// ```
+    pub fn rewrite_urls<F: FnMut(&str) -> String>(&mut self, mut rewrite: F) {
+        self.rewrite_urls_aux(&mut rewrite);
+    }
+
+    /// Wrapper for [`Html::rewrite_urls`], to borrow the function.
+    fn rewrite_urls_aux<F: FnMut(&str) -> String>(&mut self, rewrite: &mut F) {
+        match self {
+            Self::Tag { tag, child } => {
+                tag.rewrite_urls(rewrite);
+                child.rewrite_urls_aux(rewrite);
+            }
+            Self::Vec(vec) => vec.iter_mut().for_each(|child| child.rewrite_urls_aux(rewrite)),
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty | Self::Text(_) => (),
+        }
+    }
+}
+
+impl Tag {
+    /// Rewrites the urls of the attributes of the tag.
+    ///
+    /// See [`Html::rewrite_urls`] for more information.
+    fn rewrite_urls<F: FnMut(&str) -> String>(&mut self, rewrite: &mut F) {
+        let refresh = self.name == "meta"
+            && self
+                .find_attr_value("http-equiv")
+                .is_some_and(|value| value.eq_ignore_ascii_case("refresh"));
+        for attr in &mut self.attrs {
+            if let Attribute::NameValue { name, value, .. } = attr {
+                if name == "srcset" {
+                    *value = rewrite_srcset(value, rewrite);
+                } else if refresh && name == "content" {
+                    *value = rewrite_refresh(value, rewrite);
+                } else if URL_ATTRIBUTES.contains(&name.as_str()) {
+                    *value = rewrite(value);
+                } else {
+                    // not a url
+                }
+            }
+        }
+    }
+}
+
+/// Rewrites the url of the `content` attribute of a refresh `<meta>`.
+///
+/// The content is of the form `5; url=https://example.com`, where the url part
+/// is optional, and the `url=` prefix is optional and case-insensitive.
+fn rewrite_refresh<F: FnMut(&str) -> String>(content: &str, rewrite: &mut F) -> String {
+    let Some(sep) = content.find([';', ',']) else { return content.to_owned() };
+    let (delay, with_sep) = content.split_at(sep);
+    let mut chars = with_sep.chars();
+    let sep_char = chars.next().unwrap_or_default();
+    let after_sep = chars.as_str();
+    let trimmed = after_sep.trim_start();
+    let spaces = after_sep.strip_suffix(trimmed).unwrap_or_default();
+    let (prefix, quoted_url) = match trimmed.get(..4) {
+        Some(start) if start.eq_ignore_ascii_case("url=") => trimmed.split_at(4),
+        Some(_) | None => ("", trimmed),
+    };
+    let (quote, url) = match quoted_url.chars().next() {
+        Some(quote @ ('"' | '\'')) => (quote.to_string(), quoted_url.trim_matches(quote)),
+        Some(_) | None => (String::new(), quoted_url),
+    };
+    if url.is_empty() {
+        return content.to_owned();
+    }
+    let rewritten = rewrite(url);
+    format!("{delay}{sep_char}{spaces}{prefix}{quote}{rewritten}{quote}")
+}
+
+/// Rewrites each url candidate of a `srcset` attribute.
+///
+/// A `srcset` is a comma-separated list of candidates, each of them being a
+/// url and an optional descriptor, such as `480w` or `2x`. As urls can
+/// contain commas, a comma only ends a candidate if it is at the end of the
+/// url or after the descriptor.
+fn rewrite_srcset<F: FnMut(&str) -> String>(srcset: &str, rewrite: &mut F) -> String {
+    parse_srcset(srcset)
+        .into_iter()
+        .map(|(url, descriptor)| {
+            let rewritten = rewrite(url);
+            if descriptor.is_empty() { rewritten } else { format!("{rewritten} {descriptor}") }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Splits a `srcset` attribute into its candidates.
+///
+/// See [`rewrite_srcset`] for more information.
+fn parse_srcset(srcset: &str) -> Vec<(&str, &str)> {
+    let mut candidates = vec![];
+    let mut rest = srcset;
+    loop {
+        rest = rest.trim_start_matches(|ch: char| ch.is_whitespace() || ch == ',');
+        if rest.is_empty() {
+            return candidates;
+        }
+        let url_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let (raw_url, after_url) = rest.split_at(url_end);
+        let url = raw_url.trim_end_matches(',');
+        if url.len() == raw_url.len() {
+            let descriptor_end = after_url.find(',').unwrap_or(after_url.len());
+            let (descriptor, after_descriptor) = after_url.split_at(descriptor_end);
+            candidates.push((url, descriptor.trim()));
+            rest = after_descriptor;
+        } else {
+            candidates.push((url, ""));
+            rest = after_url;
+        }
+    }
+}
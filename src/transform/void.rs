@@ -0,0 +1,118 @@
+//! Module to deal with the void elements that were given some content.
+
+use core::mem::take;
+
+use crate::audit::{self, Warning};
+use crate::{Html, Tag};
+
+/// Behaviour to adopt when a void element, like `<br>` or `<img>`, is misused.
+///
+/// A void element is misused when it is closed, like in `<br>text</br>`, or
+/// when it has some content.
+///
+/// This policy is used both by the parser (see [`ParseOptions::void_policy`])
+/// and by [`Html::check_void_elements`].
+///
+/// [`ParseOptions::void_policy`]: crate::ParseOptions::void_policy
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum VoidPolicy {
+    /// Repairs the misuse silently.
+    Lenient,
+    /// Returns an error on misuse.
+    Strict,
+    /// Repairs the misuse, but reports it.
+    #[default]
+    Warn,
+}
+
+impl Html {
+    /// Checks that the void elements don't have any content, and repairs them
+    /// depending on the policy.
+    ///
+    /// Trees that were assembled programmatically can give some content to
+    /// void elements: `<br>` tags are then displayed before their content,
+    /// whereas the other tags are displayed with their content and a closing
+    /// tag, which isn't valid html.
+    ///
+    /// - With [`VoidPolicy::Strict`], the tree is untouched and an error is
+    ///   returned if a void element has content.
+    /// - With [`VoidPolicy::Warn`], the tree is untouched and the misused void
+    ///   elements are returned, as with [`audit::void_elements`].
+    /// - With [`VoidPolicy::Lenient`], the content of the void elements is
+    ///   hoisted, i.e., moved just after them, and the misused void elements
+    ///   are returned.
+    ///
+    /// The paths of the warnings are those of the tree before the repair.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first warning as error if the policy is
+    /// [`VoidPolicy::Strict`] and a void element has content.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let options = ParseOptions::new().void_tags(["br"]);
+    /// let mut html = Html::parse_with("<p><img src='a.png'>Caption</img></p>", options).unwrap();
+    ///
+    /// assert_eq!(
+    ///     html.check_void_elements(VoidPolicy::Strict),
+    ///     Err("Void element <img> has content. (at [0])".to_owned())
+    /// );
+    /// assert_eq!(html.check_void_elements(VoidPolicy::Warn).unwrap().len(), 1);
+    /// assert_eq!(html.as_tag().unwrap().1.as_tag().unwrap().1.as_text(), Some("Caption"));
+    ///
+    /// assert_eq!(html.check_void_elements(VoidPolicy::Lenient).unwrap().len(), 1);
+    /// assert_eq!(html, "<p><img src='a.png'>Caption</p>");
+    /// assert_eq!(html.check_void_elements(VoidPolicy::Strict), Ok(vec![]));
+    /// ```
+    pub fn check_void_elements(&mut self, policy: VoidPolicy) -> Result<Vec<Warning>, String> {
+        let warnings = audit::void_elements(self);
+        match policy {
+            VoidPolicy::Strict =>
+                if let Some(warning) = warnings.first() {
+                    return Err(warning.to_string());
+                },
+            VoidPolicy::Lenient =>
+                if !warnings.is_empty() {
+                    *self = from_nodes(hoist_void_content(take(self)));
+                },
+            VoidPolicy::Warn => (),
+        }
+        Ok(warnings)
+    }
+}
+
+/// Builds an [`Html`] from a list of nodes.
+fn from_nodes(mut nodes: Vec<Html>) -> Html {
+    if nodes.len() <= 1 {
+        nodes.pop().unwrap_or_default()
+    } else {
+        Html::Vec(nodes.into_boxed_slice())
+    }
+}
+
+/// Moves the content of the void elements just after them.
+///
+/// Returns the nodes that replace the given node.
+fn hoist_void_content(node: Html) -> Vec<Html> {
+    match node {
+        Html::Tag { tag, child } if tag.is_void() => {
+            let mut nodes = vec![void_tag(tag)];
+            nodes.extend(hoist_void_content(*child));
+            nodes
+        }
+        Html::Tag { tag, child } =>
+            vec![Html::Tag { tag, child: Box::new(from_nodes(hoist_void_content(*child))) }],
+        Html::Vec(vec) => vec.into_iter().flat_map(hoist_void_content).collect(),
+        Html::Empty => vec![],
+        Html::Comment(_) | Html::Doctype { .. } | Html::Text(_) => vec![node],
+    }
+}
+
+/// Creates a void element, without content.
+fn void_tag(tag: Tag) -> Html {
+    Html::Tag { tag, child: Box::new(Html::Empty) }
+}
@@ -0,0 +1,76 @@
+//! Module to collapse the whitespace of the texts of a tree.
+
+use alloc::borrow::Cow;
+
+use crate::Html;
+
+impl Html {
+    /// Collapses every run of whitespace of the texts into a single space.
+    ///
+    /// This is how browsers render the whitespace, so the rendering of the
+    /// document doesn't change. The texts of the `<pre>`, `<textarea>`,
+    /// `<listing>`, `<script>` and `<style>` tags, where the whitespace is
+    /// significant, are kept untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let mut html = Html::parse("<p>\n  Hello,\t\tworld!\n</p><pre>  a\n  b</pre>").unwrap();
+    /// html.normalize_whitespace();
+    /// assert_eq!(html, "<p> Hello, world! </p><pre>  a\n  b</pre>");
+    /// ```
+    pub fn normalize_whitespace(&mut self) {
+        self.normalize_whitespace_aux(false);
+    }
+
+    /// Wrapper for [`Html::normalize_whitespace`].
+    ///
+    /// `preformatted` is `true` inside the tags whose whitespace is kept.
+    fn normalize_whitespace_aux(&mut self, preformatted: bool) {
+        match self {
+            Self::Tag { tag, child } =>
+                child.normalize_whitespace_aux(preformatted || tag.is_preformatted()),
+            Self::Text(text) if !preformatted =>
+                if let Cow::Owned(collapsed) = collapse_whitespace(text.as_str()) {
+                    *text = collapsed.into();
+                },
+            Self::Vec(vec) =>
+                for child in vec {
+                    child.normalize_whitespace_aux(preformatted);
+                },
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty | Self::Text(_) => (),
+        }
+    }
+}
+
+/// Replaces every run of ASCII whitespace of a text with a single space.
+///
+/// The text is only copied if it changes.
+pub fn collapse_whitespace(text: &str) -> Cow<'_, str> {
+    let mut previous_space = false;
+    let unchanged = text.chars().all(|ch| {
+        let valid = if ch.is_ascii_whitespace() { ch == ' ' && !previous_space } else { true };
+        previous_space = ch.is_ascii_whitespace();
+        valid
+    });
+    if unchanged {
+        return Cow::Borrowed(text);
+    }
+    let mut collapsed = String::with_capacity(text.len());
+    for word in text.split(|ch: char| ch.is_ascii_whitespace()) {
+        if word.is_empty() {
+            if !collapsed.ends_with(' ') {
+                collapsed.push(' ');
+            }
+        } else {
+            collapsed.push_str(word);
+            collapsed.push(' ');
+        }
+    }
+    if !text.ends_with(|ch: char| ch.is_ascii_whitespace()) {
+        collapsed.pop();
+    }
+    Cow::Owned(collapsed)
+}
@@ -0,0 +1,252 @@
+//! Module that defines an [`HtmlArena`], a flat representation of an
+//! [`Html`] tree whose nodes are stored in a single [`Vec`].
+
+use core::iter::once;
+use core::mem::take;
+use core::ops::Range;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::html::Html;
+use super::small_string::SmallString;
+use super::tag::Tag;
+
+/// Node of an [`HtmlArena`].
+///
+/// The nodes are the same as the ones of an [`Html`] tree, except that the
+/// children of the tags are given as a range of indices in the arena, and
+/// that there are no empty nodes and no lists of nodes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ArenaNode {
+    /// Comment block, see [`Html::Comment`]
+    Comment(String),
+    /// Document tag, see [`Html::Doctype`]
+    Doctype {
+        /// Name of the tag
+        name: String,
+        /// Attribute of the tag
+        attr: Option<String>,
+    },
+    /// Tag, see [`Html::Tag`]
+    Tag {
+        /// Opening tag
+        tag: Tag,
+        /// Indices of the children of the tag in the arena
+        children: Range<usize>,
+    },
+    /// Raw text, see [`Html::Text`]
+    Text(SmallString),
+}
+
+impl ArenaNode {
+    /// Returns the indices of the children of the node in the arena.
+    ///
+    /// The range is empty if the node isn't a tag.
+    #[must_use]
+    pub fn children(&self) -> Range<usize> {
+        if let Self::Tag { children, .. } = self { children.clone() } else { 0..0 }
+    }
+}
+
+/// Flat representation of an [`Html`] tree, where the nodes live in a
+/// contiguous [`Vec`] and the children of a tag are a range of indices.
+///
+/// An [`Html`] tree allocates a [`Box`] for the content of every tag, and is
+/// traversed with recursion. An [`HtmlArena`] only allocates its nodes once,
+/// and can be traversed with loops, which is useful for deeply nested
+/// documents.
+///
+/// The nodes are stored in breadth-first order: the siblings are contiguous,
+/// and the descendants of a node always have greater indices than the node.
+/// Iterating over the nodes in reverse order thus visits the children before
+/// their parents.
+///
+/// An arena is created from a tree with [`Html::to_arena`], and converted
+/// back with [`HtmlArena::to_html`].
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// let html = Html::parse("<ul><li>First</li><li>Second</li></ul><p>End</p>").unwrap();
+/// let arena = html.to_arena();
+///
+/// let names = arena
+///     .as_nodes()
+///     .iter()
+///     .filter_map(
+///         |node| if let ArenaNode::Tag { tag, .. } = node { Some(tag.as_name()) } else { None },
+///     )
+///     .collect::<Vec<_>>();
+/// assert_eq!(names, ["ul", "p", "li", "li"]);
+///
+/// let first = arena.children(0).start;
+/// assert_eq!(arena.parent(first), Some(0));
+/// assert_eq!(arena.to_html(), html);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HtmlArena {
+    /// Nodes of the tree, in breadth-first order
+    nodes: Vec<ArenaNode>,
+    /// Index of the parent tag of each node, if any
+    parents: Vec<Option<usize>>,
+    /// Indices of the nodes at the root of the tree
+    roots: Range<usize>,
+}
+
+impl HtmlArena {
+    /// Returns all the nodes of the arena, in breadth-first order.
+    #[must_use]
+    pub fn as_nodes(&self) -> &[ArenaNode] {
+        &self.nodes
+    }
+
+    /// Returns the indices of the children of a node.
+    ///
+    /// The range is empty if the node isn't a tag or doesn't exist.
+    #[must_use]
+    pub fn children(&self, idx: usize) -> Range<usize> {
+        self.nodes.get(idx).map_or(0..0, ArenaNode::children)
+    }
+
+    /// Returns the node at the given index, if it exists.
+    #[must_use]
+    pub fn get(&self, idx: usize) -> Option<&ArenaNode> {
+        self.nodes.get(idx)
+    }
+
+    /// Checks if the arena doesn't contain any node.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the number of nodes in the arena.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the index of the tag that contains a node, if any.
+    #[must_use]
+    pub fn parent(&self, idx: usize) -> Option<usize> {
+        self.parents.get(idx).copied().flatten()
+    }
+
+    /// Pushes the siblings of a node into the arena, and returns their
+    /// indices.
+    ///
+    /// The lists of nodes are flattened, and the empty nodes are dropped. The
+    /// children of the pushed tags are added to `pending`, to be pushed later.
+    fn push_siblings<'html>(
+        &mut self,
+        html: &'html Html,
+        parent: Option<usize>,
+        pending: &mut Vec<(usize, &'html Html)>,
+    ) -> Range<usize> {
+        let start = self.nodes.len();
+        let mut stack = vec![html];
+        while let Some(current) = stack.pop() {
+            let node = match current {
+                Html::Comment(comment) => ArenaNode::Comment(comment.clone()),
+                Html::Doctype { name, attr } =>
+                    ArenaNode::Doctype { name: name.clone(), attr: attr.clone() },
+                Html::Empty => continue,
+                Html::Tag { tag, child } => {
+                    pending.push((self.nodes.len(), child));
+                    ArenaNode::Tag { tag: tag.clone(), children: 0..0 }
+                }
+                Html::Text(text) => ArenaNode::Text(text.clone()),
+                Html::Vec(vec) => {
+                    stack.extend(vec.iter().rev());
+                    continue;
+                }
+            };
+            self.nodes.push(node);
+            self.parents.push(parent);
+        }
+        start..self.nodes.len()
+    }
+
+    /// Returns the indices of the nodes at the root of the tree.
+    #[must_use]
+    pub fn roots(&self) -> Range<usize> {
+        self.roots.clone()
+    }
+
+    /// Converts the arena back into an [`Html`] tree.
+    ///
+    /// The tree is built without recursion, from the last nodes to the first
+    /// ones, as the children of a node are always after it.
+    #[must_use]
+    pub fn to_html(&self) -> Html {
+        let mut built = Vec::with_capacity(self.nodes.len());
+        built.resize_with(self.nodes.len(), Html::default);
+        for (idx, node) in self.nodes.iter().enumerate().rev() {
+            let html = match node {
+                ArenaNode::Comment(comment) => Html::Comment(comment.clone()),
+                ArenaNode::Doctype { name, attr } =>
+                    Html::Doctype { name: name.clone(), attr: attr.clone() },
+                ArenaNode::Tag { tag, children } => Html::Tag {
+                    tag: tag.clone(),
+                    child: Box::new(take_siblings(&mut built, children.clone())),
+                },
+                ArenaNode::Text(text) => Html::Text(text.clone()),
+            };
+            if let Some(slot) = built.get_mut(idx) {
+                *slot = html;
+            }
+        }
+        take_siblings(&mut built, self.roots.clone())
+    }
+}
+
+impl Html {
+    /// Converts the tree into an [`HtmlArena`], where the nodes are stored in
+    /// a contiguous [`Vec`].
+    ///
+    /// The tree is traversed without recursion, so the conversion works for
+    /// deeply nested documents. See [`HtmlArena`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<div><p>Text</p><!-- comment --></div>").unwrap();
+    /// let arena = html.to_arena();
+    /// assert_eq!(arena.len(), 4);
+    /// assert_eq!(arena.children(0), 1..3);
+    /// assert_eq!(arena.get(3), Some(&ArenaNode::Text("Text".into())));
+    /// ```
+    #[must_use]
+    pub fn to_arena(&self) -> HtmlArena {
+        let mut arena = HtmlArena::default();
+        let mut pending = vec![];
+        arena.roots = arena.push_siblings(self, None, &mut pending);
+        let mut next = 0;
+        while let Some(&(idx, child)) = pending.get(next) {
+            next = next.saturating_add(1);
+            let range = arena.push_siblings(child, Some(idx), &mut pending);
+            if let Some(ArenaNode::Tag { children, .. }) = arena.nodes.get_mut(idx) {
+                *children = range;
+            }
+        }
+        arena
+    }
+}
+
+/// Takes the trees built for a range of siblings, and groups them into a
+/// single tree.
+fn take_siblings(built: &mut [Html], range: Range<usize>) -> Html {
+    let mut siblings = built.get_mut(range).unwrap_or_default().iter_mut().map(take);
+    match (siblings.next(), siblings.len()) {
+        (None, _) => Html::Empty,
+        (Some(single), 0) => single,
+        (Some(first), _) => Html::Vec(once(first).chain(siblings).collect()),
+    }
+}
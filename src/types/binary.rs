@@ -0,0 +1,417 @@
+//! Module to serialise an [`Html`] tree into a compact binary format, and to
+//! load it back.
+//!
+//! Loading a tree from its binary format is faster than parsing the html
+//! again, as no tokenisation is needed. This is useful for the pipelines that
+//! parse a document once, store it, and filter it many times later.
+
+use core::{slice, str};
+
+use crate::{Attribute, Html, SmallString, Tag};
+
+/// Kind byte of a comment node.
+const COMMENT: u8 = 0;
+/// Kind byte of a doctype node.
+const DOCTYPE: u8 = 1;
+/// Kind byte of an empty node.
+pub const EMPTY: u8 = 2;
+/// Magic bytes at the beginning of the binary format.
+const MAGIC: &[u8; 4] = b"HFBN";
+/// Maximum nesting of the nodes when decoding, so that a malicious input
+/// can't overflow the stack.
+const MAX_DEPTH: usize = 512;
+/// Kind byte of a tag node.
+pub const TAG: u8 = 3;
+/// Kind byte of a text node.
+const TEXT: u8 = 4;
+/// Kind byte of a vec node.
+pub const VEC: u8 = 5;
+/// Current version of the binary format.
+const VERSION: u8 = 1;
+
+/// Reader that decodes the binary format.
+pub struct Reader<'bin> {
+    /// Bytes that weren't read yet
+    bytes: &'bin [u8],
+    /// Number of nodes that contain the bytes, up to [`MAX_DEPTH`]
+    depth: usize,
+}
+
+impl<'bin> Reader<'bin> {
+    /// Returns the bytes that weren't read yet.
+    pub const fn as_bytes(&self) -> &'bin [u8] {
+        self.bytes
+    }
+
+    /// Reads an attribute.
+    fn attribute(&mut self) -> Result<Attribute, String> {
+        match self.byte()? {
+            0 => Ok(Attribute::NameNoValue(self.string()?.into())),
+            kind @ (1 | 2) => Ok(Attribute::NameValue {
+                double_quote: kind == 1,
+                name: self.string()?.into(),
+                value: self.string()?.to_owned(),
+            }),
+            kind => Err(format!("Invalid binary tree: unknown attribute kind {kind}.")),
+        }
+    }
+
+    /// Reads one byte.
+    fn byte(&mut self) -> Result<u8, String> {
+        let (first, rest) = self.bytes.split_first().ok_or_else(unexpected_end)?;
+        self.bytes = rest;
+        Ok(*first)
+    }
+
+    /// Reads the given number of bytes.
+    fn bytes(&mut self, len: usize) -> Result<&'bin [u8], String> {
+        let (head, rest) = self.bytes.split_at_checked(len).ok_or_else(unexpected_end)?;
+        self.bytes = rest;
+        Ok(head)
+    }
+
+    /// Reads the header of the format, and checks the version.
+    pub fn header(&mut self) -> Result<(), String> {
+        if self.bytes(MAGIC.len()).ok() != Some(MAGIC.as_slice()) {
+            return Err("Invalid binary tree: missing magic bytes.".to_owned());
+        }
+        match self.byte()? {
+            VERSION => Ok(()),
+            version => Err(format!(
+                "Invalid binary tree: unsupported version {version}, expected {VERSION}."
+            )),
+        }
+    }
+
+    /// Decodes the payload of a node of the given kind, and checks that the
+    /// whole payload was read.
+    pub fn into_node(mut self, kind: u8) -> Result<Html, String> {
+        let node = self.payload(kind)?;
+        if self.is_empty() {
+            Ok(node)
+        } else {
+            Err("Invalid binary tree: node is shorter than its declared length.".to_owned())
+        }
+    }
+
+    /// Checks that all the bytes were read.
+    pub const fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Creates a reader on the given bytes.
+    pub const fn new(bytes: &'bin [u8]) -> Self {
+        Self { bytes, depth: 0 }
+    }
+
+    /// Reads a node.
+    pub fn node(&mut self) -> Result<Html, String> {
+        let (kind, payload) = self.node_payload()?;
+        payload.into_node(kind)
+    }
+
+    /// Reads the kind of a node, and returns a reader on its payload.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the node is nested in more than [`MAX_DEPTH`]
+    /// nodes.
+    pub fn node_payload(&mut self) -> Result<(u8, Self), String> {
+        if self.depth >= MAX_DEPTH {
+            return Err(format!(
+                "Invalid binary tree: nodes are nested in more than {MAX_DEPTH} nodes."
+            ));
+        }
+        let kind = self.byte()?;
+        let len = self.varint()?;
+        Ok((kind, Self { bytes: self.bytes(len)?, depth: self.depth.saturating_add(1) }))
+    }
+
+    /// Reads the payload of a node of the given kind.
+    fn payload(&mut self, kind: u8) -> Result<Html, String> {
+        match kind {
+            COMMENT => Ok(Html::Comment(self.string()?.to_owned())),
+            DOCTYPE => {
+                let name = self.string()?.to_owned();
+                let attr = if self.byte()? == 0 { None } else { Some(self.string()?.to_owned()) };
+                Ok(Html::Doctype { name, attr })
+            }
+            EMPTY => Ok(Html::Empty),
+            TAG => {
+                let (name, attrs) = self.tag_header()?;
+                Ok(Html::Tag { tag: Tag::from((name, attrs)), child: Box::new(self.node()?) })
+            }
+            TEXT => Ok(Html::Text(self.string()?.into())),
+            VEC => {
+                let len = self.varint()?;
+                let mut nodes = Vec::with_capacity(len.min(self.bytes.len()));
+                for _ in 0..len {
+                    nodes.push(self.node()?);
+                }
+                Ok(Html::Vec(nodes.into_boxed_slice()))
+            }
+            _ => Err(format!("Invalid binary tree: unknown node kind {kind}.")),
+        }
+    }
+
+    /// Reads a string.
+    fn string(&mut self) -> Result<&'bin str, String> {
+        let len = self.varint()?;
+        let bytes = self.bytes(len)?;
+        str::from_utf8(bytes)
+            .map_err(|_err| "Invalid binary tree: string isn't valid UTF-8.".to_owned())
+    }
+
+    /// Reads the name and the attributes of a tag.
+    pub fn tag_header(&mut self) -> Result<(SmallString, Box<[Attribute]>), String> {
+        let name = SmallString::from(self.string()?);
+        let len = self.varint()?;
+        let mut attrs = Vec::with_capacity(len.min(self.bytes.len()));
+        for _ in 0..len {
+            attrs.push(self.attribute()?);
+        }
+        Ok((name, attrs.into_boxed_slice()))
+    }
+
+    /// Reads a LEB128 varint.
+    pub fn varint(&mut self) -> Result<usize, String> {
+        let mut value: u64 = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= u64::from(byte & 0x7f).checked_shl(shift).unwrap_or_default();
+            if byte & 0x80 == 0 {
+                return usize::try_from(value)
+                    .map_err(|_err| "Invalid binary tree: length is too large.".to_owned());
+            }
+        }
+        Err("Invalid binary tree: varint is too long.".to_owned())
+    }
+}
+
+impl Html {
+    /// Loads a tree from its binary format.
+    ///
+    /// See [`Html::to_bin`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes aren't a valid binary tree, or if they
+    /// were created by another version of the format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// assert_eq!(
+    ///     Html::from_bin(b"<p>Not binary</p>").unwrap_err(),
+    ///     "Invalid binary tree: missing magic bytes."
+    /// );
+    /// ```
+    pub fn from_bin(bytes: &[u8]) -> Result<Self, String> {
+        let mut reader = Reader::new(bytes);
+        reader.header()?;
+        let node = reader.node()?;
+        if reader.is_empty() {
+            Ok(node)
+        } else {
+            Err("Invalid binary tree: unexpected bytes after the root node.".to_owned())
+        }
+    }
+
+    /// Serialises the tree into a compact binary format.
+    ///
+    /// The format is versioned: [`Html::from_bin`] rejects the trees written
+    /// by another version of the format. The identifiers of the tags aren't
+    /// stored, so loading a tree gives new identifiers.
+    ///
+    /// # Format
+    ///
+    /// The format starts with the magic bytes `HFBN` and a version byte. It is
+    /// followed by the root node. Each node is encoded as
+    ///
+    /// - a kind byte: `0` for a comment, `1` for a doctype, `2` for an empty
+    ///   node, `3` for a tag, `4` for a text and `5` for a vec;
+    /// - the length of the payload, in bytes;
+    /// - the payload, that depends on the kind:
+    ///   - comments and texts: the string;
+    ///   - doctypes: the name, and a byte `0` or `1` followed by the attribute;
+    ///   - tags: the name, the number of attributes, the attributes and the
+    ///     child node;
+    ///   - vecs: the number of nodes and the nodes.
+    ///
+    /// The lengths and numbers are encoded as LEB128 varints, and the strings
+    /// are their length followed by their UTF-8 bytes. An attribute is a
+    /// byte (`0` without value, `1` with a double-quoted value, `2` with a
+    /// single-quoted value), the name, and the value if there is one.
+    ///
+    /// The length of the payload allows to skip a node without decoding it.
+    /// The nodes nested in more than 512 nodes can't be loaded back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(r#"<!DOCTYPE html><p class='intro'>Hello <b>world</b></p>"#).unwrap();
+    ///
+    /// let bin = html.to_bin();
+    /// assert_eq!(Html::from_bin(&bin).unwrap(), html);
+    /// ```
+    #[must_use]
+    pub fn to_bin(&self) -> Vec<u8> {
+        let mut lens = vec![];
+        let len = measure_node(self, &mut lens);
+        let mut bytes = Vec::with_capacity(len.saturating_add(MAGIC.len()).saturating_add(1));
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        write_node(self, &mut lens.iter(), &mut bytes);
+        bytes
+    }
+}
+
+/// Returns the length of an encoded attribute.
+fn attribute_len(attr: &Attribute) -> usize {
+    match attr {
+        Attribute::NameNoValue(name) => string_len(name).saturating_add(1),
+        Attribute::NameValue { name, value, .. } =>
+            string_len(name).saturating_add(string_len(value)).saturating_add(1),
+    }
+}
+
+/// Returns the kind byte of a node.
+const fn kind(node: &Html) -> u8 {
+    match node {
+        Html::Comment(_) => COMMENT,
+        Html::Doctype { .. } => DOCTYPE,
+        Html::Empty => EMPTY,
+        Html::Tag { .. } => TAG,
+        Html::Text(_) => TEXT,
+        Html::Vec(_) => VEC,
+    }
+}
+
+/// Measures the lengths of the payloads of a node and of its descendants,
+/// bottom-up, and returns the length of the encoded node.
+///
+/// The lengths are pushed into `lens` in the order the nodes are written, see
+/// [`write_node`].
+fn measure_node(node: &Html, lens: &mut Vec<usize>) -> usize {
+    let idx = lens.len();
+    lens.push(0);
+    let payload = match node {
+        Html::Comment(comment) => string_len(comment),
+        Html::Doctype { name, attr } =>
+            string_len(name).saturating_add(attr.as_deref().map_or(0, string_len)).saturating_add(1),
+        Html::Empty => 0,
+        Html::Tag { tag, child } => tag
+            .as_attrs()
+            .iter()
+            .map(attribute_len)
+            .fold(string_len(tag.as_name()), usize::saturating_add)
+            .saturating_add(varint_len(tag.as_attrs().len()))
+            .saturating_add(measure_node(child, lens)),
+        Html::Text(text) => string_len(text),
+        Html::Vec(vec) => vec
+            .iter()
+            .map(|child| measure_node(child, lens))
+            .fold(varint_len(vec.len()), usize::saturating_add),
+    };
+    if let Some(len) = lens.get_mut(idx) {
+        *len = payload;
+    }
+    payload.saturating_add(varint_len(payload)).saturating_add(1)
+}
+
+/// Returns the length of an encoded string.
+const fn string_len(string: &str) -> usize {
+    string.len().saturating_add(varint_len(string.len()))
+}
+
+/// Error when the bytes end before the end of a value.
+fn unexpected_end() -> String {
+    "Invalid binary tree: unexpected end of data.".to_owned()
+}
+
+/// Returns the length of an encoded LEB128 varint.
+const fn varint_len(value: usize) -> usize {
+    let mut len = 1usize;
+    let mut rest = value >> 7u32;
+    while rest != 0 {
+        len = len.saturating_add(1);
+        rest >>= 7u32;
+    }
+    len
+}
+
+/// Writes an attribute.
+fn write_attribute(attr: &Attribute, bytes: &mut Vec<u8>) {
+    match attr {
+        Attribute::NameNoValue(name) => {
+            bytes.push(0);
+            write_string(name, bytes);
+        }
+        Attribute::NameValue { double_quote, name, value } => {
+            bytes.push(if *double_quote { 1 } else { 2 });
+            write_string(name, bytes);
+            write_string(value, bytes);
+        }
+    }
+}
+
+/// Writes a node, with its kind and the length of its payload.
+///
+/// `lens` are the lengths of the payloads of the node and of its
+/// descendants, measured by [`measure_node`].
+fn write_node(node: &Html, lens: &mut slice::Iter<'_, usize>, bytes: &mut Vec<u8>) {
+    bytes.push(kind(node));
+    write_varint(lens.next().copied().unwrap_or_default(), bytes);
+    match node {
+        Html::Comment(comment) => write_string(comment, bytes),
+        Html::Doctype { name, attr } => {
+            write_string(name, bytes);
+            if let Some(value) = attr {
+                bytes.push(1);
+                write_string(value, bytes);
+            } else {
+                bytes.push(0);
+            }
+        }
+        Html::Empty => (),
+        Html::Tag { tag, child } => {
+            write_string(tag.as_name(), bytes);
+            write_varint(tag.as_attrs().len(), bytes);
+            for attr in tag.as_attrs() {
+                write_attribute(attr, bytes);
+            }
+            write_node(child, lens, bytes);
+        }
+        Html::Text(text) => write_string(text, bytes),
+        Html::Vec(vec) => {
+            write_varint(vec.len(), bytes);
+            for child in vec {
+                write_node(child, lens, bytes);
+            }
+        }
+    }
+}
+
+/// Writes a string, with its length.
+fn write_string(string: &str, bytes: &mut Vec<u8>) {
+    write_varint(string.len(), bytes);
+    bytes.extend_from_slice(string.as_bytes());
+}
+
+/// Writes a LEB128 varint.
+fn write_varint(value: usize, bytes: &mut Vec<u8>) {
+    let mut rest = value;
+    loop {
+        let low = u8::try_from(rest & 0x7f).unwrap_or_default();
+        rest >>= 7u32;
+        if rest == 0 {
+            bytes.push(low);
+            return;
+        }
+        bytes.push(low | 0x80);
+    }
+}
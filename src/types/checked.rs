@@ -0,0 +1,190 @@
+//! Module to build tags from their parts, checking that they can be
+//! serialised and parsed back without ambiguity.
+
+use core::error::Error;
+use core::fmt;
+
+use super::small_string::SmallString;
+use super::tag::{Attribute, Tag};
+#[cfg(doc)]
+use crate::Html;
+
+/// Behaviour to adopt when a tag built with [`Tag::try_new_with`] has
+/// several attributes with the same name.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateAttributes {
+    /// Keeps all the attributes.
+    Allow,
+    /// Keeps the first attribute of each name, and drops the others, like
+    /// the browsers do when parsing.
+    KeepFirst,
+    /// Returns an error, with the [`TagError::DuplicateAttribute`] kind.
+    #[default]
+    Reject,
+}
+
+/// Error returned when a tag or a comment can't be built from its parts, see
+/// [`Tag::try_new`] and [`Html::comment`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TagError {
+    /// Two attributes have the same name, with the
+    /// [`DuplicateAttributes::Reject`] policy.
+    DuplicateAttribute(String),
+    /// An attribute name is empty.
+    EmptyAttributeName,
+    /// The tag name is empty.
+    EmptyTagName,
+    /// An attribute name contains a character that would end it when parsed,
+    /// like a space or a `=`.
+    InvalidAttributeName {
+        /// Invalid character
+        character: char,
+        /// Name of the attribute
+        name: String,
+    },
+    /// The content of a comment would close it early, like a `-->`.
+    InvalidComment(String),
+    /// The tag name contains a character that would end it when parsed, like
+    /// a space or a `:`, or doesn't start with an ASCII letter.
+    InvalidTagName(char),
+}
+
+impl fmt::Display for TagError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateAttribute(name) => write!(f, "Duplicate attribute '{name}'."),
+            Self::EmptyAttributeName => f.write_str("Attribute names can't be empty."),
+            Self::EmptyTagName => f.write_str("Tag names can't be empty."),
+            Self::InvalidAttributeName { character, name } =>
+                write!(f, "Invalid character '{character}' in attribute name '{name}'."),
+            Self::InvalidComment(content) =>
+                write!(f, "Comment content '{content}' would close the comment early."),
+            Self::InvalidTagName(ch) => write!(f, "Invalid character '{ch}' in tag name."),
+        }
+    }
+}
+
+impl Error for TagError {}
+
+impl Tag {
+    /// Builds a tag from its name and attributes, checking that it can be
+    /// serialised and parsed back to the same tag.
+    ///
+    /// The duplicate attribute names are rejected, as they make the output
+    /// ambiguous. To keep them or drop them instead, see
+    /// [`Tag::try_new_with`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if
+    ///
+    /// - the tag name is empty, doesn't start with an ASCII letter, or contains
+    ///   a whitespace, a control character, a `:` or one of `/`, `<`, `>`, `=`,
+    ///   `"`, `'` and `!`;
+    /// - an attribute name is empty or contains a whitespace, a control
+    ///   character or one of `/`, `<`, `>`, `=`, `"` and `'`;
+    /// - two attributes have the same name.
+    ///
+    /// The values of the attributes are escaped when serialised, so they can
+    /// contain any character.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let attrs = vec![
+    ///     Attribute::NameValue { double_quote: true, name: "href".into(), value: "/a".to_owned() },
+    ///     Attribute::NameNoValue("download".into()),
+    /// ];
+    /// let tag = Tag::try_new("a", attrs).unwrap();
+    /// assert_eq!(tag.to_opening_string(), r#"<a href="/a" download>"#);
+    ///
+    /// assert_eq!(Tag::try_new("my tag", vec![]), Err(TagError::InvalidTagName(' ')));
+    /// assert_eq!(
+    ///     Tag::try_new("p", vec![Attribute::from("id".to_owned()), Attribute::from("id".to_owned())]),
+    ///     Err(TagError::DuplicateAttribute("id".to_owned()))
+    /// );
+    /// ```
+    pub fn try_new<N: Into<SmallString>, I: IntoIterator<Item = Attribute>>(
+        name: N,
+        attrs: I,
+    ) -> Result<Self, TagError> {
+        Self::try_new_with(name, attrs, DuplicateAttributes::Reject)
+    }
+
+    /// Builds a tag from its name and attributes, checking that it can be
+    /// serialised, with the given behaviour for the duplicate attribute
+    /// names.
+    ///
+    /// See [`Tag::try_new`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tag name or an attribute name is invalid, or
+    /// if two attributes have the same name with the
+    /// [`DuplicateAttributes::Reject`] policy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let attrs = || ["class", "id", "class"].map(|name| Attribute::from(name.to_owned()));
+    ///
+    /// let tag = Tag::try_new_with("div", attrs(), DuplicateAttributes::KeepFirst).unwrap();
+    /// assert_eq!(tag.to_opening_string(), "<div class id>");
+    ///
+    /// let tag = Tag::try_new_with("div", attrs(), DuplicateAttributes::Allow).unwrap();
+    /// assert_eq!(tag.to_opening_string(), "<div class id class>");
+    /// ```
+    pub fn try_new_with<N: Into<SmallString>, I: IntoIterator<Item = Attribute>>(
+        name: N,
+        attrs: I,
+        duplicates: DuplicateAttributes,
+    ) -> Result<Self, TagError> {
+        let tag_name = name.into();
+        check_tag_name(tag_name.as_str())?;
+        let mut checked: Vec<Attribute> = vec![];
+        for attr in attrs {
+            check_attribute_name(attr.as_name())?;
+            if duplicates == DuplicateAttributes::Allow
+                || checked.iter().all(|old| old.as_name() != attr.as_name())
+            {
+                checked.push(attr);
+            } else if duplicates == DuplicateAttributes::Reject {
+                return Err(TagError::DuplicateAttribute(attr.as_name().to_owned()));
+            } else {
+                // The duplicate is dropped with `KeepFirst`.
+            }
+        }
+        Ok(Self::from((tag_name, checked.into_boxed_slice())))
+    }
+}
+
+/// Checks that an attribute name can be serialised and parsed back.
+fn check_attribute_name(name: &str) -> Result<(), TagError> {
+    if name.is_empty() {
+        return Err(TagError::EmptyAttributeName);
+    }
+    name.chars().find(|ch| ends_name(*ch)).map_or(Ok(()), |character| {
+        Err(TagError::InvalidAttributeName { character, name: name.to_owned() })
+    })
+}
+
+/// Checks that a tag name can be serialised and parsed back.
+fn check_tag_name(name: &str) -> Result<(), TagError> {
+    let mut chars = name.chars();
+    match chars.next() {
+        None => Err(TagError::EmptyTagName),
+        Some(first) if !first.is_ascii_alphabetic() => Err(TagError::InvalidTagName(first)),
+        Some(_) => chars
+            .find(|ch| ends_name(*ch) || matches!(ch, ':' | '!'))
+            .map_or(Ok(()), |ch| Err(TagError::InvalidTagName(ch))),
+    }
+}
+
+/// Checks if a character ends a tag or attribute name when it is parsed.
+fn ends_name(ch: char) -> bool {
+    ch.is_whitespace() || ch.is_control() || matches!(ch, '/' | '<' | '>' | '=' | '"' | '\'')
+}
@@ -0,0 +1,240 @@
+//! Module to build [`Html`] nodes in code, without formatting and parsing
+//! strings, see [`Html::tag`].
+
+use super::checked::TagError;
+use super::small_string::SmallString;
+use super::tag::{Attribute, Tag};
+use crate::Html;
+
+/// Builder of an [`Html::Tag`], with its attributes and its children.
+///
+/// This struct is created by [`Html::tag`]. The builder is converted into
+/// an [`Html`] with [`ElementBuilder::build`], [`ElementBuilder::try_build`] or
+/// [`Into::into`], so it can be given directly as a child of another
+/// builder.
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// let list = Html::tag("ul")
+///     .attr("class", "menu")
+///     .child(Html::tag("li").child(Html::text("Home")))
+///     .child(Html::tag("li").child(Html::text("About")))
+///     .build();
+///
+/// assert_eq!(list, r#"<ul class="menu"><li>Home</li><li>About</li></ul>"#);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[must_use]
+pub struct ElementBuilder {
+    /// Attributes of the tag, in the order they were added
+    attrs: Vec<Attribute>,
+    /// Children of the tag, in the order they were added
+    children: Vec<Html>,
+    /// Name of the tag
+    name: SmallString,
+}
+
+impl ElementBuilder {
+    /// Adds an attribute with a value.
+    ///
+    /// The quotes of the value are escaped when the tag is serialised, so it
+    /// can contain any character.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let link = Html::tag("a").attr("href", "/search?q=a&b").attr("title", r#"Say "hi""#);
+    /// assert_eq!(link.build(), r#"<a href="/search?q=a&b" title="Say &quot;hi&quot;"></a>"#);
+    /// ```
+    pub fn attr<N: Into<SmallString>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        self.attrs.push(Attribute::NameValue {
+            double_quote: true,
+            name: name.into(),
+            value: value.into(),
+        });
+        self
+    }
+
+    /// Adds an attribute without a value, like `disabled` in `<input
+    /// disabled>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let input = Html::tag("input").attr("type", "checkbox").attr_flag("checked");
+    /// assert_eq!(input.build(), Html::parse(r#"<input type="checkbox" checked>"#).unwrap());
+    /// ```
+    pub fn attr_flag<N: Into<SmallString>>(mut self, name: N) -> Self {
+        self.attrs.push(Attribute::NameNoValue(name.into()));
+        self
+    }
+
+    /// Builds the tag, without checking its names.
+    ///
+    /// See [`ElementBuilder::try_build`] to check that the tag can be
+    /// serialised and parsed back.
+    #[must_use]
+    pub fn build(self) -> Html {
+        let tag = Tag::from((self.name, self.attrs.into_boxed_slice()));
+        Html::Tag { tag, child: Box::new(Self::into_child(self.children)) }
+    }
+
+    /// Adds a child at the end of the content of the tag.
+    ///
+    /// The empty nodes are ignored, and the children of an [`Html::Vec`] are
+    /// added one by one, so that the built tree has the same shape as a
+    /// parsed one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<p>Parsed</p>").unwrap();
+    /// let div = Html::tag("div").child(html).child(Html::tag("p").child(Html::text("Built")));
+    /// assert_eq!(div.build(), "<div><p>Parsed</p><p>Built</p></div>");
+    /// ```
+    pub fn child<H: Into<Html>>(mut self, child: H) -> Self {
+        match child.into() {
+            Html::Empty => (),
+            Html::Vec(vec) => self.children.extend(vec),
+            node @ (Html::Comment(_) | Html::Doctype { .. } | Html::Tag { .. } | Html::Text(_)) =>
+                self.children.push(node),
+        }
+        self
+    }
+
+    /// Adds children at the end of the content of the tag.
+    ///
+    /// See [`ElementBuilder::child`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let items = ["One", "Two"].map(|item| Html::tag("li").child(Html::text(item)));
+    /// assert_eq!(Html::tag("ol").children(items).build(), "<ol><li>One</li><li>Two</li></ol>");
+    /// ```
+    pub fn children<H: Into<Html>, I: IntoIterator<Item = H>>(self, children: I) -> Self {
+        children.into_iter().fold(self, Self::child)
+    }
+
+    /// Converts the children of a tag into its child node.
+    fn into_child(mut children: Vec<Html>) -> Html {
+        if children.len() <= 1 {
+            children.pop().unwrap_or_default()
+        } else {
+            Html::Vec(children.into_boxed_slice())
+        }
+    }
+
+    /// Builds the tag, checking that it can be serialised and parsed back to
+    /// the same tag.
+    ///
+    /// The names are checked like with [`Tag::try_new`]: the duplicate
+    /// attribute names are rejected.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tag name or an attribute name is invalid, or
+    /// if two attributes have the same name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let valid = Html::tag("p").attr("id", "a").try_build();
+    /// assert_eq!(valid, Ok(Html::parse(r#"<p id="a"></p>"#).unwrap()));
+    ///
+    /// let invalid = Html::tag("p").attr("id", "a").attr("id", "b").try_build();
+    /// assert_eq!(invalid, Err(TagError::DuplicateAttribute("id".to_owned())));
+    /// ```
+    pub fn try_build(self) -> Result<Html, TagError> {
+        let tag = Tag::try_new(self.name, self.attrs)?;
+        Ok(Html::Tag { tag, child: Box::new(Self::into_child(self.children)) })
+    }
+}
+
+impl From<ElementBuilder> for Html {
+    fn from(builder: ElementBuilder) -> Self {
+        builder.build()
+    }
+}
+
+impl Html {
+    /// Creates a comment node, checking that it can be serialised and parsed
+    /// back to the same comment.
+    ///
+    /// The content of a comment isn't escaped when it is serialised, so it
+    /// can't contain a `--`, that starts the `-->` closing the comment, nor
+    /// start with a `>` or a `->`, as `<!-->` and `<!--->` are closed
+    /// comments.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the content would close the comment early.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// assert_eq!(Html::comment(" TODO ").unwrap(), "<!-- TODO -->");
+    /// assert_eq!(
+    ///     Html::comment("--><script>alert(1)</script>"),
+    ///     Err(TagError::InvalidComment("--><script>alert(1)</script>".to_owned()))
+    /// );
+    /// ```
+    pub fn comment<C: Into<String>>(content: C) -> Result<Self, TagError> {
+        let comment = content.into();
+        if comment.contains("--") || comment.starts_with('>') || comment.starts_with("->") {
+            Err(TagError::InvalidComment(comment))
+        } else {
+            Ok(Self::Comment(comment))
+        }
+    }
+
+    /// Starts to build a tag, to create a tree or to insert new nodes into a
+    /// tree without parsing them.
+    ///
+    /// See [`ElementBuilder`] to add the attributes and the children.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let div = Html::tag("div").attr("id", "x").child(Html::text("hi")).build();
+    /// assert_eq!(div, r#"<div id="x">hi</div>"#);
+    /// assert_eq!(div, Html::parse(r#"<div id="x">hi</div>"#).unwrap());
+    /// ```
+    pub fn tag<N: Into<SmallString>>(name: N) -> ElementBuilder {
+        ElementBuilder { attrs: vec![], children: vec![], name: name.into() }
+    }
+
+    /// Creates a text node.
+    ///
+    /// The text is the literal content of the node: its `<` are escaped when
+    /// it is serialised, so it never opens a tag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// assert_eq!(Html::text("1 < 2"), "1 &lt; 2");
+    /// ```
+    #[must_use]
+    pub fn text<T: Into<SmallString>>(text: T) -> Self {
+        Self::Text(text.into())
+    }
+}
@@ -0,0 +1,139 @@
+//! Module to compute a stable digest of the content of an [`Html`] tree, to
+//! validate the caches.
+
+use core::mem::take;
+
+use crate::Html;
+
+/// Offset basis of the 64-bit FNV-1a hash.
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Prime of the 64-bit FNV-1a hash.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// 64-bit FNV-1a hasher of the canonical content of a tree.
+///
+/// The algorithm is fixed, contrary to the hashers of the standard library,
+/// so the digests are the same across versions and platforms.
+struct Digest {
+    /// Hash of the bytes written so far
+    hash: u64,
+    /// Texts that weren't written yet, to merge the texts that are only
+    /// separated by comments
+    text: String,
+}
+
+impl Digest {
+    /// Writes the words of the pending texts, if any.
+    fn flush(&mut self) {
+        let text = take(&mut self.text);
+        let words = text.split_whitespace().collect::<Vec<_>>();
+        if words.is_empty() {
+            return;
+        }
+        self.write(b"t");
+        self.write_len(words.len());
+        for word in words {
+            self.write_str(word);
+        }
+    }
+
+    /// Writes a node and its descendants.
+    fn node(&mut self, node: &Html) {
+        match node {
+            Html::Comment(_) | Html::Empty => (),
+            Html::Doctype { name, attr } => {
+                self.flush();
+                self.write(b"d");
+                self.write_str(name);
+                self.write_str(attr.as_deref().unwrap_or_default());
+            }
+            Html::Tag { tag, child } => {
+                self.flush();
+                self.write(b"<");
+                self.write_str(tag.as_name());
+                let mut attrs = tag
+                    .as_attrs()
+                    .iter()
+                    .map(|attr| (attr.as_name(), attr.as_value()))
+                    .collect::<Vec<_>>();
+                attrs.sort_unstable();
+                self.write_len(attrs.len());
+                for (name, value) in attrs {
+                    self.write_str(name);
+                    match value {
+                        Some(content) => {
+                            self.write(b"=");
+                            self.write_str(content);
+                        }
+                        None => self.write(b"-"),
+                    }
+                }
+                self.node(child);
+                self.flush();
+                self.write(b">");
+            }
+            Html::Text(text) => self.text.push_str(text),
+            Html::Vec(vec) => vec.iter().for_each(|child| self.node(child)),
+        }
+    }
+
+    /// Writes bytes into the hash.
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.hash = (self.hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    /// Writes a length, followed by a separator, so that the strings can't be
+    /// confused with the ones that follow them.
+    fn write_len(&mut self, len: usize) {
+        self.write(len.to_string().as_bytes());
+        self.write(b":");
+    }
+
+    /// Writes a string, preceded by its length.
+    fn write_str(&mut self, text: &str) {
+        self.write_len(text.len());
+        self.write(text.as_bytes());
+    }
+}
+
+impl Html {
+    /// Returns a short digest of the content of the tree, to validate the
+    /// caches.
+    ///
+    /// The digest is computed on a canonical form of the tree, so that two
+    /// trees that only differ by their formatting have the same digest:
+    ///
+    /// - the comments are excluded;
+    /// - the whitespace of the texts is excluded, only their words are kept,
+    ///   and the texts only separated by comments are merged;
+    /// - the attributes are sorted by name, and their quotes are ignored.
+    ///
+    /// The digest is made of 16 hexadecimal digits. Its algorithm is fixed,
+    /// so it is the same across runs, platforms and versions of the crate.
+    /// As the trees with the same digest aren't byte-for-byte identical, it is
+    /// a weak validator: send it in a `W/"..."` `ETag` header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(r#"<p class="a" id='b'>Some   text</p>"#).unwrap();
+    /// let same = Html::parse("<p id=\"b\" class=\"a\">\n  Some text\n</p><!-- cached -->").unwrap();
+    /// let other = Html::parse(r#"<p class="a" id="b">Other text</p>"#).unwrap();
+    ///
+    /// assert_eq!(html.etag().len(), 16);
+    /// assert_eq!(html.etag(), same.etag());
+    /// assert_ne!(html.etag(), other.etag());
+    /// ```
+    #[must_use]
+    pub fn etag(&self) -> String {
+        let mut digest = Digest { hash: FNV_OFFSET, text: String::new() };
+        digest.node(self);
+        digest.flush();
+        format!("{:016x}", digest.hash)
+    }
+}
@@ -1,8 +1,22 @@
 //! Module that defines an [`Html`] tree.
 
-use core::fmt;
+#[cfg(feature = "filter")]
+use alloc::borrow::Cow;
+use core::ops::Range;
+use core::{fmt, slice};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::small_string::SmallString;
 use super::tag::Tag;
+use crate::ControlPolicy;
+#[cfg(feature = "filter")]
+use crate::TrimMode;
+use crate::control::clean_controls;
+use crate::parse::template::escape_outside_placeholders;
+#[cfg(feature = "filter")]
+use crate::transform::collapse_whitespace;
 
 /// Dom tree structure to represent the parsed html.
 ///
@@ -27,6 +41,7 @@ use super::tag::Tag;
 /// .unwrap();
 /// ```
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Html {
     /// Comment block
     ///
@@ -88,7 +103,7 @@ pub enum Html {
     /// # Examples
     ///
     /// In `a<strong>b`, `a` and `b` are [`Html::Text`] elements
-    Text(String),
+    Text(SmallString),
     /// List of nodes
     ///
     /// # Examples
@@ -149,6 +164,35 @@ impl Html {
         }
     }
 
+    /// Returns the tag and its content, if this node is a tag whose content
+    /// is raw text, like a `<script>` or a `<style>`.
+    ///
+    /// The content of these tags is read as is by the parser, without any
+    /// tag, comment or character reference. See [`Tag::is_raw_text`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(r#"<script type="module">if (a < b) run();</script>"#).unwrap();
+    /// let (tag, content) = html.as_raw_text().unwrap();
+    /// assert_eq!(tag.find_attr_value("type").unwrap(), "module");
+    /// assert_eq!(content, "if (a < b) run();");
+    ///
+    /// assert_eq!(Html::parse("<style></style>").unwrap().as_raw_text().unwrap().1, "");
+    /// assert_eq!(Html::parse("<p>text</p>").unwrap().as_raw_text(), None);
+    /// ```
+    #[must_use]
+    pub fn as_raw_text(&self) -> Option<(&Tag, &str)> {
+        let (tag, child) = self.as_tag().filter(|(tag, _)| tag.is_raw_text())?;
+        match child {
+            Self::Empty => Some((tag, "")),
+            Self::Text(text) => Some((tag, text.as_str())),
+            Self::Comment(_) | Self::Doctype { .. } | Self::Tag { .. } | Self::Vec(_) => None,
+        }
+    }
+
     /// Returns the tag, if this node is a tag.
     ///
     /// # Examples
@@ -181,7 +225,7 @@ impl Html {
     /// assert_eq!(Html::parse("<p>a</p><p>b</p>").unwrap().as_text(), None);
     /// ```
     #[must_use]
-    pub const fn as_text(&self) -> Option<&str> {
+    pub fn as_text(&self) -> Option<&str> {
         if let Self::Text(text) = self { Some(text.as_str()) } else { None }
     }
 
@@ -205,6 +249,21 @@ impl Html {
         if let Self::Vec(vec) = self { Some(vec) } else { None }
     }
 
+    /// Displays the content of a raw text tag, like `<script>`, without
+    /// encoding its texts.
+    ///
+    /// The control characters are replaced, as character references aren't
+    /// decoded in raw texts.
+    fn fmt_raw(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Text(text) =>
+                fmt.write_str(&clean_controls(text.as_str(), ControlPolicy::Replace)),
+            Self::Vec(vec) => vec.iter().try_for_each(|html| html.fmt_raw(fmt)),
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty | Self::Tag { .. } =>
+                write!(fmt, "{self}"),
+        }
+    }
+
     /// Checks if an [`Html`] tree is empty
     pub(crate) const fn is_empty(&self) -> bool {
         matches!(self, Self::Empty)
@@ -216,10 +275,59 @@ impl Html {
         Self::Empty
     }
 
+    /// Pushes the texts of the node and of its descendants.
+    #[cfg(any(feature = "extract", feature = "ffi", feature = "pyo3"))]
+    pub(crate) fn push_texts(&self, text: &mut String) {
+        match self {
+            Self::Text(content) => text.push_str(content),
+            Self::Tag { child, .. } => child.push_texts(text),
+            Self::Vec(vec) =>
+                for child in vec {
+                    child.push_texts(text);
+                },
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty => (),
+        }
+    }
+
+    /// Clones a range of sibling nodes into a new tree.
+    ///
+    /// The siblings are the nodes of an [`Html::Vec`], and any other node is
+    /// treated as a list with a single node. The range is clamped to the
+    /// number of siblings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<h1>Title</h1><p>First</p><p>Second</p><footer></footer>").unwrap();
+    /// assert_eq!(html.to_siblings(1..3), "<p>First</p><p>Second</p>");
+    /// assert_eq!(html.to_siblings(3..10), "<footer></footer>");
+    /// assert_eq!(html.to_siblings(2..2), Html::Empty);
+    /// ```
+    #[must_use]
+    pub fn to_siblings(&self, range: Range<usize>) -> Self {
+        let siblings = self.as_vec().unwrap_or_else(|| slice::from_ref(self));
+        let end = range.end.min(siblings.len());
+        let mut nodes = siblings.get(range.start.min(end)..end).unwrap_or_default().to_vec();
+        if nodes.len() <= 1 {
+            nodes.pop().unwrap_or_default()
+        } else {
+            Self::Vec(nodes.into_boxed_slice())
+        }
+    }
+
     /// Trims the texts then allocates a text [`Html`] node if it isn't empty.
-    pub(crate) fn trim_text(text: &str) -> Self {
-        let trimmed = text.trim();
-        if trimmed.is_empty() { Self::Empty } else { Self::Text(trimmed.to_owned()) }
+    ///
+    /// See [`TrimMode`] for the ways to trim the texts.
+    #[cfg(feature = "filter")]
+    pub(crate) fn trim_text(text: &str, mode: TrimMode) -> Self {
+        let trimmed = match mode {
+            TrimMode::Collapse => collapse_whitespace(text.trim()),
+            TrimMode::Edges => Cow::Borrowed(text.trim()),
+            TrimMode::None => Cow::Borrowed(text),
+        };
+        if trimmed.is_empty() { Self::Empty } else { Self::Text(trimmed.as_ref().into()) }
     }
 }
 
@@ -227,16 +335,31 @@ impl fmt::Display for Html {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Empty => "".fmt(f),
-            Self::Tag { tag, child } if tag.as_name() == "br" => write!(f, "<br>{child}"),
-            Self::Tag { tag, child } => write!(f, "<{tag}>{child}</{}>", tag.as_name()),
-            Self::Doctype { name, attr } => match (name, attr) {
-                (name_str, Some(attr_str)) => write!(f, "<!{name_str} {attr_str}>"),
-                (name_str, None) if name_str.is_empty() => write!(f, "<!>"),
-                (name_str, None) => write!(f, "<!{name_str} >"),
-            },
-            Self::Text(text) => text.fmt(f),
+            Self::Tag { tag, child } if tag.is_void() => write!(f, "<{tag}>{child}"),
+            Self::Tag { tag, child } if tag.is_raw_text() => {
+                write!(f, "<{tag}>")?;
+                child.fmt_raw(f)?;
+                write!(f, "</{}>", clean_controls(tag.as_name(), ControlPolicy::Replace))
+            }
+            Self::Tag { tag, child } => {
+                let name = clean_controls(tag.as_name(), ControlPolicy::Replace);
+                write!(f, "<{tag}>{child}</{name}>")
+            }
+            Self::Doctype { name, attr } => {
+                let name_str = clean_controls(name, ControlPolicy::Replace);
+                match attr
+                    .as_deref()
+                    .map(|attr_str| clean_controls(attr_str, ControlPolicy::Replace))
+                {
+                    Some(attr_str) => write!(f, "<!{name_str} {attr_str}>"),
+                    None if name_str.is_empty() => write!(f, "<!>"),
+                    None => write!(f, "<!{name_str} >"),
+                }
+            }
+            Self::Text(text) => escape_outside_placeholders(text, &['<']).fmt(f),
             Self::Vec(vec) => vec.iter().try_for_each(|html| html.fmt(f)),
-            Self::Comment(content) => write!(f, "<!--{content}-->"),
+            Self::Comment(content) =>
+                write!(f, "<!--{}-->", clean_controls(content, ControlPolicy::Replace)),
         }
     }
 }
@@ -1,10 +1,17 @@
 //! Module that defines a builder for the [`Html`] tree.
 
+use alloc::borrow::Cow;
 use core::mem::{replace, take};
+use core::ops::Range;
 
 use super::html::Html;
+use super::html_ref::TagRef;
+use super::small_string::SmallString;
 use super::tag::{Tag, TagType};
+use crate::control::{clean_attributes, clean_texts};
+use crate::entities::{decode_attributes, decode_texts};
 use crate::errors::safe_unreachable;
+use crate::{ControlPolicy, ParseErrorKind, ParseOptions};
 
 /// Wrapper for bool to manage visibility
 #[derive(Debug)]
@@ -96,6 +103,10 @@ pub enum HtmlBuilder {
     /// # Examples
     ///
     /// In `a<strong>b`, `a` and `b` are [`HtmlBuilder::Text`] elements
+    ///
+    /// The text is a [`String`], and not a [`SmallString`], as the parser
+    /// appends the characters to it one run at a time: it is only converted
+    /// when the tree is exported, see [`HtmlBuilder::into_html`].
     Text(String),
     /// List of nodes
     ///
@@ -107,34 +118,7 @@ pub enum HtmlBuilder {
 }
 
 impl HtmlBuilder {
-    /// Pushes a block comment into the [`HtmlBuilder`] tree
-    pub fn close_comment(&mut self) -> bool {
-        match self {
-            Self::Comment { full, .. } =>
-                if full.0 {
-                    false
-                } else {
-                    full.0 = true;
-                    true
-                },
-            Self::Text(_) | Self::Empty | Self::Doctype { .. } => false,
-            Self::Tag { full, child, .. } => full.is_open() && child.close_comment(),
-            Self::Vec(_, last) => last.close_comment(),
-        }
-    }
-
-    /// Method to find to close that last opened tag.
-    ///
-    /// This method finds the opened tag the closest to the leaves.
-    pub fn close_tag(&mut self, name: &str) -> Result<(), String> {
-        if self.close_tag_aux(name) {
-            Ok(())
-        } else {
-            Err(format!("Invalid closing tag: Found closing tag for '{name}' but it isn't open."))
-        }
-    }
-
-    /// Wrapper for [`Self::close_tag`].
+    /// Wrapper for [`TreeBuilder::close_tag`].
     ///
     /// # Returns
     ///
@@ -142,7 +126,7 @@ impl HtmlBuilder {
     pub fn close_tag_aux(&mut self, name: &str) -> bool {
         if let Self::Tag { tag, full: full @ TagType::Opened, child } = self {
             child.close_tag_aux(name)
-                || (tag.as_name() == name && {
+                || (tag.as_name().eq_ignore_ascii_case(name) && {
                     *full = TagType::Closed;
                     true
                 })
@@ -158,11 +142,6 @@ impl HtmlBuilder {
         Box::new(Self::default())
     }
 
-    /// Creates a tree for a character.
-    pub fn from_char(ch: char) -> Self {
-        Self::Text(ch.to_string())
-    }
-
     /// Exports an [`HtmlBuilder`] into an [`Html`]
     pub fn into_html(self) -> Html {
         match self {
@@ -170,15 +149,29 @@ impl HtmlBuilder {
             Self::Doctype { name, attr } => Html::Doctype { name, attr },
             Self::Empty => Html::Empty,
             Self::Tag { tag, child, .. } => Html::Tag { tag, child: Box::new(child.into_html()) },
-            Self::Text(text) => Html::Text(text),
+            Self::Text(text) => Html::Text(SmallString::from(text)),
             Self::Vec(vec, last) => {
-                let mut html_vec = vec.into_iter().map(Self::into_html).collect::<Vec<_>>();
+                let mut html_vec = Vec::with_capacity(vec.len().saturating_add(1));
+                html_vec.extend(vec.into_iter().map(Self::into_html));
                 html_vec.push(last.into_html());
                 Html::Vec(html_vec.into_boxed_slice())
             }
         }
     }
 
+    /// Exports an [`HtmlBuilder`] into an [`Html`], and decodes its texts and
+    /// handles its control characters if the options require it.
+    pub fn into_html_with(self, options: &ParseOptions<'_>) -> Html {
+        let mut html = self.into_html();
+        if options.decodes_entities() {
+            decode_texts(&mut html, options);
+        }
+        if options.controls() != ControlPolicy::Keep {
+            clean_texts(&mut html, options.controls());
+        }
+        html
+    }
+
     /// Checks if an [`HtmlBuilder`] tree is pushable.
     ///
     /// This is to check if a new node needs to be created for the next data.
@@ -194,37 +187,6 @@ impl HtmlBuilder {
         }
     }
 
-    /// Pushes one character into an [`HtmlBuilder`] tree.
-    pub fn push_char(&mut self, ch: char) {
-        match self {
-            Self::Empty => *self = Self::from_char(ch),
-            Self::Tag { child, full: TagType::Opened, .. } => child.push_char(ch),
-            Self::Doctype { .. }
-            | Self::Tag { full: TagType::Closed | TagType::SelfClosing, .. } =>
-                *self = Self::Vec(vec![take(self)], Box::from(Self::from_char(ch))),
-            Self::Text(text) => text.push(ch),
-            Self::Vec(vec, last) => {
-                if last.is_pushable(true) {
-                    return last.push_char(ch);
-                }
-                vec.push(replace(last, Self::from_char(ch)));
-            }
-            Self::Comment { content, full } => {
-                if full.0 {
-                    // This means the comment is at the root
-                    *self = Self::Vec(vec![take(self)], Box::from(Self::from_char(ch)));
-                } else {
-                    content.push(ch);
-                }
-            }
-        }
-    }
-
-    /// Pushes a block comment into the [`HtmlBuilder`] tree
-    pub fn push_comment(&mut self) {
-        self.push_node(Self::Comment { content: String::new(), full: CommentFull(false) });
-    }
-
     /// Pushes an [`HtmlBuilder`] tree into another one.
     ///
     /// This is useful to add comments or push tags for instance.
@@ -232,8 +194,10 @@ impl HtmlBuilder {
         match self {
             Self::Empty => *self = node,
             Self::Tag { child, full: TagType::Opened, .. } => child.push_node(node),
+            // A closed comment means the comment is at the root
             Self::Text(_)
             | Self::Doctype { .. }
+            | Self::Comment { full: CommentFull(true), .. }
             | Self::Tag { full: TagType::Closed | TagType::SelfClosing, .. } =>
                 *self = Self::Vec(vec![take(self)], Box::from(node)),
             Self::Vec(vec, last) => {
@@ -247,6 +211,35 @@ impl HtmlBuilder {
         }
     }
 
+    /// Pushes a run of characters into an [`HtmlBuilder`] tree.
+    ///
+    /// The characters are appended to the last text or comment if it is still
+    /// open, and form a new text node otherwise.
+    pub fn push_str(&mut self, string: &str) {
+        match self {
+            Self::Empty => *self = Self::Text(string.to_owned()),
+            Self::Tag { child, full: TagType::Opened, .. } => child.push_str(string),
+            Self::Doctype { .. }
+            | Self::Tag { full: TagType::Closed | TagType::SelfClosing, .. } =>
+                *self = Self::Vec(vec![take(self)], Box::from(Self::Text(string.to_owned()))),
+            Self::Text(text) => text.push_str(string),
+            Self::Vec(vec, last) => {
+                if last.is_pushable(true) {
+                    return last.push_str(string);
+                }
+                vec.push(replace(last, Self::Text(string.to_owned())));
+            }
+            Self::Comment { content, full } => {
+                if full.0 {
+                    // This means the comment is at the root
+                    *self = Self::Vec(vec![take(self)], Box::from(Self::Text(string.to_owned())));
+                } else {
+                    content.push_str(string);
+                }
+            }
+        }
+    }
+
     /// Pushes a tag into an [`HtmlBuilder`] tree.
     pub fn push_tag(&mut self, tag: Tag, inline: bool) {
         self.push_node(Self::Tag {
@@ -256,3 +249,117 @@ impl HtmlBuilder {
         });
     }
 }
+
+impl<'input> TreeBuilder<'input> for HtmlBuilder {
+    fn close_comment(&mut self) -> bool {
+        match self {
+            Self::Comment { full, .. } =>
+                if full.0 {
+                    false
+                } else {
+                    full.0 = true;
+                    true
+                },
+            Self::Text(_) | Self::Empty | Self::Doctype { .. } => false,
+            Self::Tag { full, child, .. } => full.is_open() && child.close_comment(),
+            Self::Vec(_, last) => last.close_comment(),
+        }
+    }
+
+    fn close_tag(&mut self, name: &str) -> Result<(), ParseErrorKind> {
+        if self.close_tag_aux(name) {
+            Ok(())
+        } else {
+            Err(ParseErrorKind::UnopenedTag(name.to_owned()))
+        }
+    }
+
+    fn current_tag(&self) -> Option<&str> {
+        match self {
+            Self::Tag { tag, full: TagType::Opened, child } =>
+                child.current_tag().or_else(|| Some(tag.as_name())),
+            Self::Vec(_, last) => last.current_tag(),
+            Self::Comment { .. }
+            | Self::Doctype { .. }
+            | Self::Empty
+            | Self::Tag { .. }
+            | Self::Text(_) => None,
+        }
+    }
+
+    fn open_comment(&mut self) {
+        self.push_node(Self::Comment { content: String::new(), full: CommentFull(false) });
+    }
+
+    fn open_tag(&mut self, tag: TagRef<'input>, inline: bool, options: &mut ParseOptions<'_>) {
+        let mut owned = tag.into_tag();
+        if options.decodes_entities() {
+            decode_attributes(&mut owned);
+        }
+        if options.controls() != ControlPolicy::Keep {
+            clean_attributes(&mut owned, options.controls());
+        }
+        options.tag_opened(&owned);
+        self.push_tag(owned, inline);
+    }
+
+    fn open_tags(&self) -> Vec<&str> {
+        match self {
+            Self::Tag { tag, full: TagType::Opened, child } => {
+                let mut tags = vec![tag.as_name()];
+                tags.extend(child.open_tags());
+                tags
+            }
+            Self::Vec(_, last) => last.open_tags(),
+            Self::Comment { .. }
+            | Self::Doctype { .. }
+            | Self::Empty
+            | Self::Tag { .. }
+            | Self::Text(_) => vec![],
+        }
+    }
+
+    fn push_doctype(&mut self, name: Cow<'input, str>, attr: Option<&'input str>) {
+        self.push_node(Self::Doctype { name: name.into_owned(), attr: attr.map(str::to_owned) });
+    }
+
+    fn push_text(&mut self, input: &'input str, range: Range<usize>) {
+        if let Some(text) = input.get(range)
+            && !text.is_empty()
+        {
+            self.push_str(text);
+        }
+    }
+}
+
+/// Tree that is built by the parser.
+///
+/// The parser reads the input and calls these methods to build either an
+/// owned [`Html`] tree, with an [`HtmlBuilder`], or a tree that borrows its
+/// strings from the input, with a [`RefBuilder`](super::html_ref::RefBuilder).
+pub trait TreeBuilder<'input> {
+    /// Closes the last opened comment.
+    ///
+    /// # Returns
+    ///
+    /// `true` iff there was an open comment to close.
+    fn close_comment(&mut self) -> bool;
+    /// Closes the last opened tag with the given name.
+    ///
+    /// The tags opened after it, and not closed yet, are closed too.
+    fn close_tag(&mut self, name: &str) -> Result<(), ParseErrorKind>;
+    /// Returns the name of the innermost tag that is still open, if any.
+    fn current_tag(&self) -> Option<&str>;
+    /// Opens a comment, to which the next texts are pushed.
+    fn open_comment(&mut self);
+    /// Opens a tag, or pushes a self-closing tag if `inline` is `true`.
+    fn open_tag(&mut self, tag: TagRef<'input>, inline: bool, options: &mut ParseOptions<'_>);
+    /// Returns the names of the tags that are still open, from the root to the
+    /// innermost.
+    fn open_tags(&self) -> Vec<&str>;
+    /// Pushes a doctype.
+    fn push_doctype(&mut self, name: Cow<'input, str>, attr: Option<&'input str>);
+    /// Pushes the text at the given range of the input, at the end of the
+    /// last text or comment if it is still open.
+    fn push_text(&mut self, input: &'input str, range: Range<usize>);
+}
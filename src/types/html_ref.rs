@@ -0,0 +1,449 @@
+//! Module that defines an [`HtmlRef`] tree, whose strings are borrowed from
+//! the parsed input.
+
+use alloc::borrow::Cow;
+use alloc::vec::Drain;
+use core::fmt;
+use core::mem::take;
+use core::ops::Range;
+
+use super::html::Html;
+use super::html_builder::TreeBuilder;
+use super::tag::{Attribute, RAW_TEXT_TAGS, Tag, VOID_TAGS, has_name};
+use crate::control::clean_controls;
+use crate::entities::escape;
+use crate::{ControlPolicy, ParseErrorKind, ParseOptions};
+
+/// Read-only view of an attribute, with a borrowed name and value.
+///
+/// This is the borrowed version of [`Attribute`]: the attributes of a
+/// [`TagRef`] are borrowed from the parsed input, and the attributes of a
+/// [`Tag`] can be viewed with [`Attribute::as_attribute_ref`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeRef<'input> {
+    /// Name of the attribute, when it doesn't have a value
+    ///
+    /// # Examples
+    ///
+    /// In `<button />`, the name of the attribute is `button`.
+    NameNoValue(&'input str),
+    /// Name of the attribute
+    ///
+    /// # Examples
+    ///
+    /// `<div id="blob"/>`
+    NameValue {
+        /// Whether double or single quotes were used to define the value
+        double_quote: bool,
+        /// Name of the attribute
+        name: &'input str,
+        /// Value of the attribute
+        value: &'input str,
+    },
+}
+
+impl<'input> AttributeRef<'input> {
+    /// Returns the name of an attribute
+    #[must_use]
+    pub const fn as_name(&self) -> &'input str {
+        match self {
+            Self::NameNoValue(name) | Self::NameValue { name, .. } => name,
+        }
+    }
+
+    /// Returns the value of an attribute
+    #[must_use]
+    pub const fn as_value(&self) -> Option<&'input str> {
+        match self {
+            Self::NameNoValue(_) => None,
+            Self::NameValue { value, .. } => Some(value),
+        }
+    }
+
+    /// Copies the attribute into an owned [`Attribute`].
+    #[must_use]
+    pub fn to_attribute(&self) -> Attribute {
+        match *self {
+            Self::NameNoValue(name) => Attribute::NameNoValue(name.into()),
+            Self::NameValue { double_quote, name, value } =>
+                Attribute::NameValue { double_quote, name: name.into(), value: value.to_owned() },
+        }
+    }
+}
+
+impl fmt::Display for AttributeRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NameNoValue(prefix_name) =>
+                write!(f, " {}", clean_controls(prefix_name, ControlPolicy::Replace)),
+            Self::NameValue { double_quote, name, value } => {
+                let del = if *double_quote { '"' } else { '\'' };
+                let clean_name = clean_controls(name, ControlPolicy::Replace);
+                write!(f, " {clean_name}={del}{}{del}", escape(value, &[del]))
+            }
+        }
+    }
+}
+
+/// Dom tree whose strings are borrowed from the parsed input.
+///
+/// This is the borrowed version of [`Html`], returned by
+/// [`Html::parse_borrowed`]. The names, the attributes and the texts are
+/// slices of the input, so parsing doesn't allocate a [`String`] for each of
+/// them. In the rare cases where a string isn't contiguous in the input, like
+/// the name of `<a/b>`, it is owned instead.
+///
+/// Use [`HtmlRef::into_html`] to get an owned [`Html`] tree.
+///
+/// # Examples
+///
+/// ```
+/// use std::borrow::Cow;
+///
+/// use html_filter::*;
+///
+/// let input = String::from("<p class='intro'>Hello</p>");
+/// let html = Html::parse_borrowed(&input).unwrap();
+/// let HtmlRef::Tag { tag, child } = &html else { panic!() };
+/// assert_eq!(tag.find_attr_value("class"), Some("intro"));
+/// assert!(matches!(**child, HtmlRef::Text(Cow::Borrowed("Hello"))));
+/// assert_eq!(html.to_string(), input);
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub enum HtmlRef<'input> {
+    /// Comment block
+    ///
+    /// # Example
+    ///
+    /// `<!-- some comment -->`
+    Comment(Cow<'input, str>),
+    /// Document tag.
+    ///
+    /// # Examples
+    ///
+    /// `<!doctype html>`
+    Doctype {
+        /// Name of the tag
+        name: Cow<'input, str>,
+        /// Attribute of the tag
+        attr: Option<&'input str>,
+    },
+    /// Empty html tree
+    #[default]
+    Empty,
+    /// Tag
+    Tag {
+        /// Opening tag
+        tag: TagRef<'input>,
+        /// Child of the tag
+        child: Box<Self>,
+    },
+    /// Raw text
+    Text(Cow<'input, str>),
+    /// List of nodes
+    Vec(Box<[Self]>),
+}
+
+impl HtmlRef<'_> {
+    /// Displays the content of a raw text tag, like `<script>`, with its
+    /// control characters replaced.
+    ///
+    /// See `Html::fmt_raw`.
+    fn fmt_raw(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Text(text) => fmt.write_str(&clean_controls(text, ControlPolicy::Replace)),
+            Self::Vec(vec) => vec.iter().try_for_each(|html| html.fmt_raw(fmt)),
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty | Self::Tag { .. } =>
+                write!(fmt, "{self}"),
+        }
+    }
+
+    /// Converts the tree into an owned [`Html`] tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let input = "<!DOCTYPE html><ul><li>1</li><!-- more --></ul>";
+    /// let html = Html::parse_borrowed(input).unwrap().into_html();
+    /// assert_eq!(html, Html::parse(input).unwrap());
+    /// ```
+    #[must_use]
+    pub fn into_html(self) -> Html {
+        match self {
+            Self::Comment(content) => Html::Comment(content.into_owned()),
+            Self::Doctype { name, attr } =>
+                Html::Doctype { name: name.into_owned(), attr: attr.map(str::to_owned) },
+            Self::Empty => Html::Empty,
+            Self::Tag { tag, child } =>
+                Html::Tag { tag: tag.into_tag(), child: Box::new(child.into_html()) },
+            Self::Text(text) => Html::Text(text.into()),
+            Self::Vec(vec) => Html::Vec(vec.into_iter().map(Self::into_html).collect()),
+        }
+    }
+}
+
+impl fmt::Display for HtmlRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => "".fmt(f),
+            Self::Tag { tag, child } if has_name(&VOID_TAGS, tag.as_name()) =>
+                write!(f, "<{tag}>{child}"),
+            Self::Tag { tag, child } if has_name(&RAW_TEXT_TAGS, tag.as_name()) => {
+                write!(f, "<{tag}>")?;
+                child.fmt_raw(f)?;
+                write!(f, "</{}>", clean_controls(tag.as_name(), ControlPolicy::Replace))
+            }
+            Self::Tag { tag, child } => {
+                let name = clean_controls(tag.as_name(), ControlPolicy::Replace);
+                write!(f, "<{tag}>{child}</{name}>")
+            }
+            Self::Doctype { name, attr } => {
+                let name_str = clean_controls(name, ControlPolicy::Replace);
+                match attr
+                    .as_deref()
+                    .map(|attr_str| clean_controls(attr_str, ControlPolicy::Replace))
+                {
+                    Some(attr_str) => write!(f, "<!{name_str} {attr_str}>"),
+                    None if name_str.is_empty() => write!(f, "<!>"),
+                    None => write!(f, "<!{name_str} >"),
+                }
+            }
+            Self::Text(text) => escape(text, &[]).fmt(f),
+            Self::Vec(vec) => vec.iter().try_for_each(|html| html.fmt(f)),
+            Self::Comment(content) =>
+                write!(f, "<!--{}-->", clean_controls(content, ControlPolicy::Replace)),
+        }
+    }
+}
+
+/// Builder of an [`HtmlRef`] tree, used by [`Html::parse_borrowed`].
+pub struct RefBuilder<'input> {
+    /// Input that is being parsed.
+    input: &'input str,
+    /// Nodes found at the root of the tree and inside the open tags, from the
+    /// root to the deepest open tag.
+    ///
+    /// The nodes are shared by all the open tags, so that this vector grows
+    /// to the size needed by the document, and the children of a tag are
+    /// moved into an exactly-sized slice when the tag is closed.
+    nodes: Vec<HtmlRef<'input>>,
+    /// Tags that are still open, from the root, with the position of their
+    /// first child in `nodes`.
+    open: Vec<(TagRef<'input>, usize)>,
+    /// Text, or comment if the boolean is `true`, that is being read at the
+    /// end of the deepest open tag.
+    pending: Option<(bool, Run)>,
+}
+
+impl<'input> RefBuilder<'input> {
+    /// Pushes the pending text or comment in the nodes of the deepest open
+    /// tag.
+    fn flush(&mut self) {
+        if let Some((comment, run)) = self.pending.take() {
+            let text = run.into_cow(self.input);
+            let node = if comment { HtmlRef::Comment(text) } else { HtmlRef::Text(text) };
+            self.nodes.push(node);
+        }
+    }
+
+    /// Exports the builder into an [`HtmlRef`], closing the tags that are
+    /// still open.
+    pub fn into_html_ref(mut self) -> HtmlRef<'input> {
+        self.flush();
+        while !self.open.is_empty() {
+            self.pop_tag();
+        }
+        from_nodes(self.nodes.drain(..))
+    }
+
+    /// Creates an empty builder for the given input.
+    pub const fn new(input: &'input str) -> Self {
+        Self { input, nodes: vec![], open: vec![], pending: None }
+    }
+
+    /// Closes the deepest open tag.
+    fn pop_tag(&mut self) {
+        if let Some((tag, start)) = self.open.pop() {
+            let node = HtmlRef::Tag { tag, child: Box::new(from_nodes(self.nodes.drain(start..))) };
+            self.nodes.push(node);
+        }
+    }
+}
+
+impl<'input> TreeBuilder<'input> for RefBuilder<'input> {
+    fn close_comment(&mut self) -> bool {
+        let open = matches!(self.pending, Some((true, _)));
+        if open {
+            self.flush();
+        }
+        open
+    }
+
+    fn close_tag(&mut self, name: &str) -> Result<(), ParseErrorKind> {
+        self.flush();
+        let position = self
+            .open
+            .iter()
+            .rposition(|(tag, _)| tag.as_name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| ParseErrorKind::UnopenedTag(name.to_owned()))?;
+        while self.open.len() > position {
+            self.pop_tag();
+        }
+        Ok(())
+    }
+
+    fn current_tag(&self) -> Option<&str> {
+        self.open.last().map(|(tag, _)| tag.as_name())
+    }
+
+    fn open_comment(&mut self) {
+        self.flush();
+        self.pending = Some((true, Run::default()));
+    }
+
+    fn open_tag(&mut self, tag: TagRef<'input>, inline: bool, _options: &mut ParseOptions<'_>) {
+        self.flush();
+        if inline {
+            self.nodes.push(HtmlRef::Tag { tag, child: Box::default() });
+        } else {
+            self.open.push((tag, self.nodes.len()));
+        }
+    }
+
+    fn open_tags(&self) -> Vec<&str> {
+        self.open.iter().map(|(tag, _)| tag.as_name()).collect()
+    }
+
+    fn push_doctype(&mut self, name: Cow<'input, str>, attr: Option<&'input str>) {
+        self.flush();
+        self.nodes.push(HtmlRef::Doctype { name, attr });
+    }
+
+    fn push_text(&mut self, input: &'input str, range: Range<usize>) {
+        if range.is_empty() {
+            return;
+        }
+        match &mut self.pending {
+            Some((_, run)) => run.push(input, range),
+            None => self.pending = Some((false, Run::Borrowed(range))),
+        }
+    }
+}
+
+/// Text read from the input, borrowed as long as its parts are contiguous.
+pub enum Run {
+    /// Contiguous text, at the given range of the input.
+    Borrowed(Range<usize>),
+    /// Text whose parts aren't contiguous in the input.
+    Owned(String),
+}
+
+impl Run {
+    /// Returns the text, borrowed from the input if possible.
+    pub fn into_cow(self, input: &str) -> Cow<'_, str> {
+        match self {
+            Self::Borrowed(range) => Cow::Borrowed(input.get(range).unwrap_or_default()),
+            Self::Owned(text) => Cow::Owned(text),
+        }
+    }
+
+    /// Checks if the text is empty.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Borrowed(range) => range.is_empty(),
+            Self::Owned(text) => text.is_empty(),
+        }
+    }
+
+    /// Appends the text at the given range of the input.
+    pub fn push(&mut self, input: &str, range: Range<usize>) {
+        match self {
+            Self::Borrowed(current) if Range::is_empty(current) => *current = range,
+            Self::Borrowed(current) if current.end == range.start => current.end = range.end,
+            Self::Borrowed(current) => {
+                let mut text = input.get(current.clone()).unwrap_or_default().to_owned();
+                text.push_str(input.get(range).unwrap_or_default());
+                *self = Self::Owned(text);
+            }
+            Self::Owned(text) => text.push_str(input.get(range).unwrap_or_default()),
+        }
+    }
+}
+
+impl Default for Run {
+    fn default() -> Self {
+        Self::Borrowed(0..0)
+    }
+}
+
+/// Tag whose name and attributes are borrowed from the parsed input.
+///
+/// This is the borrowed version of [`Tag`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagRef<'input> {
+    /// Attributes of the tag. See [`AttributeRef`].
+    attrs: Box<[AttributeRef<'input>]>,
+    /// Name of the tag.
+    name: Cow<'input, str>,
+}
+
+impl<'input> TagRef<'input> {
+    /// Returns the attributes of the tag.
+    #[must_use]
+    pub const fn as_attrs(&self) -> &[AttributeRef<'input>] {
+        &self.attrs
+    }
+
+    /// Returns the name of the tag.
+    #[must_use]
+    pub fn as_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Finds the value of the attribute of the given name.
+    ///
+    /// # Returns
+    ///
+    /// - `None` if the attribute doesn't exist or doesn't have a value.
+    /// - The value of the attribute otherwise.
+    #[must_use]
+    pub fn find_attr_value(&self, name: &str) -> Option<&'input str> {
+        self.attrs.iter().find(|attr| attr.as_name() == name).and_then(AttributeRef::as_value)
+    }
+
+    /// Converts the tag into an owned [`Tag`], with a new identifier.
+    #[must_use]
+    pub fn into_tag(self) -> Tag {
+        let attrs = self.attrs.iter().map(AttributeRef::to_attribute).collect();
+        Tag::from((self.name, attrs))
+    }
+
+    /// Keeps only the first `len` attributes of the tag.
+    pub(crate) fn truncate_attrs(&mut self, len: usize) {
+        if len < self.attrs.len() {
+            let mut attrs = take(&mut self.attrs).into_vec();
+            attrs.truncate(len);
+            self.attrs = attrs.into_boxed_slice();
+        }
+    }
+}
+
+impl<'input> From<(Cow<'input, str>, Box<[AttributeRef<'input>]>)> for TagRef<'input> {
+    fn from((name, attrs): (Cow<'input, str>, Box<[AttributeRef<'input>]>)) -> Self {
+        Self { attrs, name }
+    }
+}
+
+impl fmt::Display for TagRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&clean_controls(&self.name, ControlPolicy::Replace))?;
+        self.attrs.iter().try_for_each(|attr| attr.fmt(f))
+    }
+}
+
+/// Makes a tree from the nodes of a tag, or of the root.
+fn from_nodes<'input>(mut nodes: Drain<'_, HtmlRef<'input>>) -> HtmlRef<'input> {
+    if nodes.len() <= 1 { nodes.next().unwrap_or_default() } else { HtmlRef::Vec(nodes.collect()) }
+}
@@ -0,0 +1,229 @@
+//! Module to define the iterators over the nodes of an [`Html`] tree.
+//!
+//! The [`Html::Vec`] and [`Html::Empty`] nodes are only containers: they are
+//! never returned by the iterators, their content is returned instead.
+
+use crate::{Html, Tag};
+
+/// Iterator over the direct children of a node.
+///
+/// This struct is created by [`Html::children`] and by the [`IntoIterator`]
+/// implementation of `&Html`.
+#[derive(Debug, Clone)]
+pub struct Children<'html> {
+    /// Remaining children, the next one being at the end
+    stack: Vec<&'html Html>,
+}
+
+impl<'html> Iterator for Children<'html> {
+    type Item = &'html Html;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.stack.pop()? {
+                Html::Vec(vec) => self.stack.extend(vec.iter().rev()),
+                Html::Empty => (),
+                node @ (Html::Comment(_)
+                | Html::Doctype { .. }
+                | Html::Tag { .. }
+                | Html::Text(_)) => return Some(node),
+            }
+        }
+    }
+}
+
+impl Html {
+    /// Returns an iterator over the direct children of the node.
+    ///
+    /// The children of a tag are the nodes between its opening and closing
+    /// tags, and the children of an [`Html::Vec`], like a parsed document with
+    /// multiple top-level nodes, are its nodes. The other nodes have no
+    /// children.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<ul><li>First</li><li>Second</li></ul>").unwrap();
+    /// let items = html.children().map(Html::to_string).collect::<Vec<_>>();
+    /// assert_eq!(items, ["<li>First</li>", "<li>Second</li>"]);
+    /// ```
+    #[must_use]
+    pub fn children(&self) -> Children<'_> {
+        let stack = match self {
+            Self::Tag { child, .. } => vec![&**child],
+            Self::Vec(_) => vec![self],
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty | Self::Text(_) => vec![],
+        };
+        Children { stack }
+    }
+
+    /// Returns a depth-first iterator over the nodes of the tree.
+    ///
+    /// Every node is returned before its children, in the order of the
+    /// document, starting with the node itself, or with its first node for an
+    /// [`Html::Vec`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<p>Hello <b>world</b></p><!--end-->").unwrap();
+    /// let texts = html.iter().filter_map(Html::as_text).collect::<Vec<_>>();
+    /// assert_eq!(texts, ["Hello ", "world"]);
+    /// assert_eq!(html.iter().count(), 5);
+    /// ```
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter { stack: vec![self] }
+    }
+
+    /// Returns a depth-first iterator over the tags of the tree, with their
+    /// children.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html =
+    ///     Html::parse(r#"<nav><a href="/home">Home</a><div><a href="/about">About</a></div></nav>"#)
+    ///         .unwrap();
+    /// let links = html
+    ///     .iter_tags()
+    ///     .filter(|(tag, _)| tag.as_name() == "a")
+    ///     .filter_map(|(tag, _)| tag.find_attr_value("href"))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(links, ["/home", "/about"]);
+    /// ```
+    #[must_use]
+    pub fn iter_tags(&self) -> Tags<'_> {
+        Tags { iter: self.iter() }
+    }
+}
+
+impl IntoIterator for Html {
+    type IntoIter = IntoChildren;
+    type Item = Self;
+
+    /// Returns an iterator over the owned direct children of the node.
+    ///
+    /// See [`Html::children`] for the definition of the children.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<p>First</p><p>Second</p>").unwrap();
+    /// let paragraphs = html.into_iter().collect::<Vec<Html>>();
+    /// assert_eq!(paragraphs[1], "<p>Second</p>");
+    /// ```
+    fn into_iter(self) -> Self::IntoIter {
+        let stack = match self {
+            Self::Tag { child, .. } => vec![*child],
+            Self::Vec(_) => vec![self],
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty | Self::Text(_) => vec![],
+        };
+        IntoChildren { stack }
+    }
+}
+
+impl<'html> IntoIterator for &'html Html {
+    type IntoIter = Children<'html>;
+    type Item = &'html Html;
+
+    /// Returns an iterator over the direct children of the node, like the
+    /// [`IntoIterator`] implementation of [`Html`], see [`Html::children`].
+    ///
+    /// Use [`Html::iter`] to iterate over all the descendants.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<p>First <b>bold</b></p><p>Second</p>").unwrap();
+    /// let mut paragraphs = vec![];
+    /// for node in &html {
+    ///     paragraphs.push(node.to_string());
+    /// }
+    /// assert_eq!(paragraphs, ["<p>First <b>bold</b></p>", "<p>Second</p>"]);
+    /// ```
+    fn into_iter(self) -> Self::IntoIter {
+        self.children()
+    }
+}
+
+/// Iterator over the owned direct children of a node.
+///
+/// This struct is created by the [`IntoIterator`] implementation of [`Html`].
+#[derive(Debug, Clone)]
+pub struct IntoChildren {
+    /// Remaining children, the next one being at the end
+    stack: Vec<Html>,
+}
+
+impl Iterator for IntoChildren {
+    type Item = Html;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.stack.pop()? {
+                Html::Vec(vec) => self.stack.extend(vec.into_vec().into_iter().rev()),
+                Html::Empty => (),
+                node @ (Html::Comment(_)
+                | Html::Doctype { .. }
+                | Html::Tag { .. }
+                | Html::Text(_)) => return Some(node),
+            }
+        }
+    }
+}
+
+/// Depth-first iterator over the nodes of a tree.
+///
+/// This struct is created by [`Html::iter`].
+#[derive(Debug, Clone)]
+pub struct Iter<'html> {
+    /// Remaining nodes, the next one being at the end
+    stack: Vec<&'html Html>,
+}
+
+impl<'html> Iterator for Iter<'html> {
+    type Item = &'html Html;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.stack.pop()? {
+                Html::Vec(vec) => self.stack.extend(vec.iter().rev()),
+                Html::Empty => (),
+                node @ Html::Tag { child, .. } => {
+                    self.stack.push(child);
+                    return Some(node);
+                }
+                node @ (Html::Comment(_) | Html::Doctype { .. } | Html::Text(_)) =>
+                    return Some(node),
+            }
+        }
+    }
+}
+
+/// Depth-first iterator over the tags of a tree, with their children.
+///
+/// This struct is created by [`Html::iter_tags`].
+#[derive(Debug, Clone)]
+pub struct Tags<'html> {
+    /// Iterator over all the nodes of the tree
+    iter: Iter<'html>,
+}
+
+impl<'html> Iterator for Tags<'html> {
+    type Item = (&'html Tag, &'html Html);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.find_map(Html::as_tag)
+    }
+}
@@ -0,0 +1,223 @@
+//! Module to write an [`Html`] tree as JSON, for the tools written in other
+//! languages.
+
+use core::convert::Infallible;
+
+use crate::{Html, Serializer};
+
+/// [`Serializer`] that writes a tree as JSON, without depending on `serde`.
+///
+/// The structure of the output is stable:
+///
+/// - the output is the array of the root nodes;
+/// - a tag is an object
+///   `{"type":"tag","name":..,"attrs":[..],"children":[..]}`, where every
+///   attribute is an object `{"name":..,"value":..}`, with a `null` value for
+///   the attributes without value. The attributes are kept in the order of the
+///   document, with their duplicates;
+/// - a text is `{"type":"text","content":..}`, and a comment is
+///   `{"type":"comment","content":..}`;
+/// - a doctype is `{"type":"doctype","name":..,"attr":..}`, with a `null`
+///   attribute if it has none.
+///
+/// The strings are written as they are stored in the tree, see
+/// [`Serializer`].
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// let html = Html::parse(r#"<!DOCTYPE html><p id="a" hidden>Hi<!--c--></p>"#).unwrap();
+///
+/// let mut json = JsonSerializer::new();
+/// html.serialize(&mut json).unwrap();
+/// assert_eq!(
+///     json.into_output(),
+///     r#"[{"type":"doctype","name":"DOCTYPE","attr":"html"},{"type":"tag","name":"p","attrs":[{"name":"id","value":"a"},{"name":"hidden","value":null}],"children":[{"type":"text","content":"Hi"},{"type":"comment","content":"c"}]}]"#
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonSerializer {
+    /// Whether the attributes of the last opening tag are being written, as
+    /// its children are only started when its first child is written, or
+    /// when it is closed
+    attrs: bool,
+    /// Whether a value was already written in the array that is currently
+    /// written, for each open array, from the root
+    commas: Vec<bool>,
+    /// JSON written so far
+    output: String,
+}
+
+impl JsonSerializer {
+    /// Ends the attributes of the last opening tag, and starts its children,
+    /// in the same array level.
+    fn close_attrs(&mut self) {
+        if self.attrs {
+            self.output.push_str("],\"children\":[");
+            self.attrs = false;
+            if let Some(comma) = self.commas.last_mut() {
+                *comma = false;
+            }
+        }
+    }
+
+    /// Converts the serializer into the JSON it wrote, closing the array of
+    /// the root nodes.
+    #[must_use]
+    pub fn into_output(mut self) -> String {
+        self.output.push(']');
+        self.output
+    }
+
+    /// Creates a serializer with an empty output.
+    #[must_use]
+    pub fn new() -> Self {
+        Self { attrs: false, commas: vec![false], output: "[".to_owned() }
+    }
+
+    /// Writes a node with a content, like a text or a comment.
+    fn push_content(&mut self, kind: &str, content: &str) {
+        self.close_attrs();
+        self.separate();
+        self.output.push_str("{\"type\":\"");
+        self.output.push_str(kind);
+        self.output.push_str("\",\"content\":");
+        push_string(&mut self.output, content);
+        self.output.push('}');
+    }
+
+    /// Writes the separator before a value of the current array.
+    fn separate(&mut self) {
+        if let Some(comma) = self.commas.last_mut() {
+            if *comma {
+                self.output.push(',');
+            }
+            *comma = true;
+        }
+    }
+}
+
+impl Default for JsonSerializer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Serializer for JsonSerializer {
+    type Error = Infallible;
+
+    fn attr(&mut self, name: &str, value: Option<&str>) -> Result<(), Self::Error> {
+        self.separate();
+        self.output.push_str("{\"name\":");
+        push_string(&mut self.output, name);
+        self.output.push_str(",\"value\":");
+        push_optional_string(&mut self.output, value);
+        self.output.push('}');
+        Ok(())
+    }
+
+    fn comment(&mut self, content: &str) -> Result<(), Self::Error> {
+        self.push_content("comment", content);
+        Ok(())
+    }
+
+    fn doctype(&mut self, name: &str, attr: Option<&str>) -> Result<(), Self::Error> {
+        self.close_attrs();
+        self.separate();
+        self.output.push_str("{\"type\":\"doctype\",\"name\":");
+        push_string(&mut self.output, name);
+        self.output.push_str(",\"attr\":");
+        push_optional_string(&mut self.output, attr);
+        self.output.push('}');
+        Ok(())
+    }
+
+    fn end_tag(&mut self, _name: &str) -> Result<(), Self::Error> {
+        self.close_attrs();
+        self.commas.pop();
+        self.output.push_str("]}");
+        Ok(())
+    }
+
+    fn start_tag(&mut self, name: &str) -> Result<(), Self::Error> {
+        self.close_attrs();
+        self.separate();
+        self.output.push_str("{\"type\":\"tag\",\"name\":");
+        push_string(&mut self.output, name);
+        self.output.push_str(",\"attrs\":[");
+        self.attrs = true;
+        self.commas.push(false);
+        Ok(())
+    }
+
+    fn text(&mut self, text: &str) -> Result<(), Self::Error> {
+        self.push_content("text", text);
+        Ok(())
+    }
+}
+
+impl Html {
+    /// Writes the tree as JSON, with a [`JsonSerializer`].
+    ///
+    /// See [`JsonSerializer`] for the structure of the output, which is
+    /// stable, so that the tools written in other languages can consume the
+    /// parsed tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(r#"<a href="/">Say "hi"</a>"#).unwrap();
+    /// assert_eq!(
+    ///     html.to_json(),
+    ///     r#"[{"type":"tag","name":"a","attrs":[{"name":"href","value":"/"}],"children":[{"type":"text","content":"Say \"hi\""}]}]"#
+    /// );
+    /// assert_eq!(Html::Empty.to_json(), "[]");
+    /// ```
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let mut json = JsonSerializer::new();
+        match self.serialize(&mut json) {
+            Ok(()) => json.into_output(),
+            Err(never) => match never {},
+        }
+    }
+}
+
+/// Writes a string, or `null`, as a JSON value.
+fn push_optional_string(output: &mut String, text: Option<&str>) {
+    if let Some(content) = text {
+        push_string(output, content);
+    } else {
+        output.push_str("null");
+    }
+}
+
+/// Writes a string as a JSON string literal, escaping the quotes, the
+/// backslashes and the control characters.
+fn push_string(output: &mut String, text: &str) {
+    output.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            _ if ch < ' ' => {
+                output.push_str("\\u00");
+                let code = u32::from(ch);
+                output.extend(
+                    [code >> 4u32, code & 0xf]
+                        .into_iter()
+                        .filter_map(|digit| char::from_digit(digit, 16)),
+                );
+            }
+            _ => output.push(ch),
+        }
+    }
+    output.push('"');
+}
@@ -0,0 +1,332 @@
+//! Module to write an [`Html`] tree as JSX, to migrate html templates to
+//! React components.
+
+use core::convert::Infallible;
+
+use super::tag::{RAW_TEXT_TAGS, has_name};
+use crate::{Html, Serializer};
+
+/// Attributes whose name is different in JSX, with their JSX name.
+///
+/// The names are the ones of the React DOM properties. The event handlers,
+/// like `onclick`, aren't renamed, as their values must be functions in JSX.
+const JSX_ATTRIBUTES: [(&str, &str); 29] = [
+    ("accept-charset", "acceptCharset"),
+    ("accesskey", "accessKey"),
+    ("autocapitalize", "autoCapitalize"),
+    ("autocomplete", "autoComplete"),
+    ("autofocus", "autoFocus"),
+    ("autoplay", "autoPlay"),
+    ("charset", "charSet"),
+    ("class", "className"),
+    ("colspan", "colSpan"),
+    ("contenteditable", "contentEditable"),
+    ("crossorigin", "crossOrigin"),
+    ("datetime", "dateTime"),
+    ("enctype", "encType"),
+    ("for", "htmlFor"),
+    ("formaction", "formAction"),
+    ("http-equiv", "httpEquiv"),
+    ("inputmode", "inputMode"),
+    ("maxlength", "maxLength"),
+    ("minlength", "minLength"),
+    ("novalidate", "noValidate"),
+    ("playsinline", "playsInline"),
+    ("readonly", "readOnly"),
+    ("referrerpolicy", "referrerPolicy"),
+    ("rowspan", "rowSpan"),
+    ("spellcheck", "spellCheck"),
+    ("srcdoc", "srcDoc"),
+    ("srcset", "srcSet"),
+    ("tabindex", "tabIndex"),
+    ("usemap", "useMap"),
+];
+
+/// [`Serializer`] that writes a tree as JSX, for React components.
+///
+/// The tree is converted to the JSX syntax:
+///
+/// - the attributes whose name is different in React are renamed, like `class`
+///   to `className` and `for` to `htmlFor`. The `data-*` and `aria-*`
+///   attributes, and the event handlers, are kept as they are;
+/// - the `style` attributes are converted to objects, with their properties in
+///   camel case, like `style={{ fontSize: "1em" }}`;
+/// - the tags without children, like the void elements, are self-closing:
+///   `<br>` becomes `<br />`;
+/// - the `{`, `}`, `<` and `>` of the texts are escaped with character
+///   references, and the content of the `<script>` and `<style>` tags is
+///   written as a string literal;
+/// - the comments are written as `{/* comment */}`, and the doctypes are
+///   dropped.
+///
+/// A JSX expression must have a single root element: the serializer writes
+/// the nodes as they are, and [`Html::to_jsx`] wraps the output in a
+/// fragment, like `<>...</>`, if the tree has several root nodes.
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// let html = Html::parse(
+///     r#"<label for="name" class="field" style="font-size: 12px; -webkit-user-select: none">Name {required}<input id="name" readonly></label>"#,
+/// )
+/// .unwrap();
+///
+/// let mut jsx = JsxSerializer::new();
+/// html.serialize(&mut jsx).unwrap();
+/// assert_eq!(
+///     jsx.as_output(),
+///     r#"<label htmlFor="name" className="field" style={{ fontSize: "12px", WebkitUserSelect: "none" }}>Name &#123;required&#125;<input id="name" readOnly /></label>"#
+/// );
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct JsxSerializer {
+    /// Whether the last opening tag wasn't closed yet, as it is only closed
+    /// when its first child is written, or self-closed if it has none
+    open: bool,
+    /// JSX written so far
+    output: String,
+    /// Number of root tags
+    root_tags: usize,
+    /// Number of root nodes, except the texts that are only whitespace
+    roots: usize,
+    /// Names of the tags that are open
+    stack: Vec<String>,
+}
+
+impl JsxSerializer {
+    /// Returns the JSX written so far.
+    #[must_use]
+    pub fn as_output(&self) -> &str {
+        &self.output
+    }
+
+    /// Closes the last opening tag, before writing its first child.
+    fn close_start_tag(&mut self) {
+        if self.open {
+            self.output.push('>');
+            self.open = false;
+        }
+    }
+
+    /// Counts a node written at the root of the tree.
+    fn count_root(&mut self, tag: bool) {
+        if self.stack.is_empty() {
+            self.roots = self.roots.saturating_add(1);
+            self.root_tags = self.root_tags.saturating_add(usize::from(tag));
+        }
+    }
+
+    /// Converts the serializer into the JSX it wrote.
+    #[must_use]
+    pub fn into_output(self) -> String {
+        self.output
+    }
+
+    /// Creates a serializer with an empty output.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { open: false, output: String::new(), root_tags: 0, roots: 0, stack: vec![] }
+    }
+}
+
+impl Serializer for JsxSerializer {
+    type Error = Infallible;
+
+    fn attr(&mut self, name: &str, value: Option<&str>) -> Result<(), Self::Error> {
+        let jsx_name = JSX_ATTRIBUTES
+            .iter()
+            .find(|(html_name, _)| html_name.eq_ignore_ascii_case(name))
+            .map_or(name, |(_, jsx)| jsx);
+        self.output.push(' ');
+        self.output.push_str(jsx_name);
+        match value {
+            Some(style) if name.eq_ignore_ascii_case("style") => {
+                self.output.push_str("={");
+                self.output.push_str(&style_object(style));
+                self.output.push('}');
+            }
+            Some(content) => {
+                self.output.push_str("=\"");
+                self.output.push_str(&content.replace('"', "&quot;"));
+                self.output.push('"');
+            }
+            None => (),
+        }
+        Ok(())
+    }
+
+    fn comment(&mut self, content: &str) -> Result<(), Self::Error> {
+        self.close_start_tag();
+        self.count_root(false);
+        self.output.push_str("{/*");
+        self.output.push_str(&content.replace("*/", "* /"));
+        self.output.push_str("*/}");
+        Ok(())
+    }
+
+    fn end_tag(&mut self, name: &str) -> Result<(), Self::Error> {
+        self.stack.pop();
+        if self.open {
+            self.output.push_str(" />");
+            self.open = false;
+        } else {
+            self.output.push_str("</");
+            self.output.push_str(name);
+            self.output.push('>');
+        }
+        Ok(())
+    }
+
+    fn start_tag(&mut self, name: &str) -> Result<(), Self::Error> {
+        self.close_start_tag();
+        self.count_root(true);
+        self.output.push('<');
+        self.output.push_str(name);
+        self.open = true;
+        self.stack.push(name.to_owned());
+        Ok(())
+    }
+
+    fn text(&mut self, text: &str) -> Result<(), Self::Error> {
+        self.close_start_tag();
+        if !text.trim_ascii().is_empty() {
+            self.count_root(false);
+        }
+        if self.stack.last().is_some_and(|name| has_name(&RAW_TEXT_TAGS, name)) {
+            self.output.push('{');
+            self.output.push_str(&string_literal(text));
+            self.output.push('}');
+            return Ok(());
+        }
+        for ch in text.chars() {
+            match ch {
+                '<' => self.output.push_str("&lt;"),
+                '>' => self.output.push_str("&gt;"),
+                '{' => self.output.push_str("&#123;"),
+                '}' => self.output.push_str("&#125;"),
+                _ => self.output.push(ch),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Html {
+    /// Writes the tree as JSX, with a [`JsxSerializer`].
+    ///
+    /// See [`JsxSerializer`] for the details of the conversion. The output is
+    /// wrapped in a fragment, `<>...</>`, unless the tree has a single root
+    /// tag, as a JSX expression must have a single root element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(r#"<p class="intro">Hello<br>world</p>"#).unwrap();
+    /// assert_eq!(html.to_jsx(), r#"<p className="intro">Hello<br />world</p>"#);
+    ///
+    /// let html = Html::parse("<h1>Title</h1>\n<p>Text</p>").unwrap();
+    /// assert_eq!(html.to_jsx(), "<><h1>Title</h1>\n<p>Text</p></>");
+    /// ```
+    #[must_use]
+    pub fn to_jsx(&self) -> String {
+        let mut jsx = JsxSerializer::new();
+        match self.serialize(&mut jsx) {
+            Ok(()) if jsx.roots == 1 && jsx.root_tags == 1 => jsx.into_output(),
+            Ok(()) => format!("<>{}</>", jsx.into_output()),
+            Err(never) => match never {},
+        }
+    }
+}
+
+/// Splits the declarations of a `style` attribute on the semicolons, except
+/// the ones inside quotes or parentheses, like in `url("a;b")`.
+fn split_declarations(style: &str) -> Vec<&str> {
+    let mut declarations = vec![];
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut start = 0;
+    for (idx, ch) in style.char_indices() {
+        match (quote, ch) {
+            (Some(open), _) if ch == open => quote = None,
+            (None, '"' | '\'') => quote = Some(ch),
+            (None, '(') => depth = depth.saturating_add(1),
+            (None, ')') => depth = depth.saturating_sub(1),
+            (None, ';') if depth == 0 => {
+                declarations.push(style.get(start..idx).unwrap_or_default());
+                start = idx.saturating_add(1);
+            }
+            (Some(_) | None, _) => (),
+        }
+    }
+    declarations.push(style.get(start..).unwrap_or_default());
+    declarations
+}
+
+/// Converts a CSS property to the key of a React style object.
+///
+/// The properties are converted to camel case, with the vendor prefixes
+/// capitalised except `-ms-`, like React does: `-webkit-user-select` becomes
+/// `WebkitUserSelect`. The custom properties, like `--main-color`, are kept
+/// as quoted keys.
+fn style_key(property: &str) -> String {
+    if property.starts_with("--") {
+        return string_literal(property);
+    }
+    let lower = property.to_ascii_lowercase();
+    let (mut key, rest) = match lower.strip_prefix('-') {
+        Some(ms) if ms.starts_with("ms-") => (String::new(), ms),
+        Some(vendor) => {
+            let mut chars = vendor.chars();
+            let first = chars.next().map(|ch| ch.to_ascii_uppercase()).unwrap_or_default();
+            (first.to_string(), chars.as_str())
+        }
+        None => (String::new(), lower.as_str()),
+    };
+    let mut upper = false;
+    for ch in rest.chars() {
+        if ch == '-' {
+            upper = true;
+        } else if upper {
+            key.push(ch.to_ascii_uppercase());
+            upper = false;
+        } else {
+            key.push(ch);
+        }
+    }
+    key
+}
+
+/// Converts the value of a `style` attribute to a React style object, like
+/// `{ fontSize: "12px" }`.
+fn style_object(style: &str) -> String {
+    let properties = split_declarations(style)
+        .into_iter()
+        .filter_map(|declaration| declaration.split_once(':'))
+        .map(|(property, value)| (property.trim(), value.trim()))
+        .filter(|(property, _)| !property.is_empty())
+        .map(|(property, value)| format!("{}: {}", style_key(property), string_literal(value)))
+        .collect::<Vec<_>>();
+    if properties.is_empty() { "{}".to_owned() } else { format!("{{ {} }}", properties.join(", ")) }
+}
+
+/// Writes a string as a double-quoted JavaScript string literal.
+pub fn string_literal(text: &str) -> String {
+    let mut literal = String::with_capacity(text.len().saturating_add(2));
+    literal.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            _ => literal.push(ch),
+        }
+    }
+    literal.push('"');
+    literal
+}
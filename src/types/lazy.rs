@@ -0,0 +1,308 @@
+//! Module to query a tree stored in the binary format, without loading the
+//! whole tree.
+//!
+//! See [`Html::to_bin`] for the binary format.
+
+#[cfg(feature = "mmap")]
+use alloc::sync::Arc;
+use std::fs;
+#[cfg(feature = "mmap")]
+use std::fs::File;
+use std::path::Path;
+
+#[cfg(feature = "mmap")]
+use memmap2::Mmap;
+
+use super::binary::{EMPTY, Reader, TAG, VEC};
+#[cfg(feature = "filter")]
+use crate::Filter;
+use crate::{Html, Tag};
+
+/// Bytes of a [`LazyHtml`].
+#[derive(Debug, Clone)]
+enum Bytes {
+    /// File mapped to memory, see `LazyHtml::open_mmap`
+    #[cfg(feature = "mmap")]
+    Mapped {
+        /// Length of the header of the binary format
+        header: usize,
+        /// Mapped file, shared by the clones of the tree
+        map: Arc<Mmap>,
+    },
+    /// Bytes of the binary format, without the header
+    Owned(Vec<u8>),
+}
+
+impl Bytes {
+    /// Returns the bytes of the binary format, without the header.
+    fn as_slice(&self) -> &[u8] {
+        match self {
+            #[cfg(feature = "mmap")]
+            Self::Mapped { header, map } => map.get(*header..).unwrap_or_default(),
+            Self::Owned(bytes) => bytes,
+        }
+    }
+}
+
+impl Eq for Bytes {}
+
+impl PartialEq for Bytes {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+/// Tree stored in the binary format, that materialises its nodes on demand.
+///
+/// The bytes are read once, or mapped to memory with
+/// `LazyHtml::open_mmap` and the `mmap` feature, but the nodes are only
+/// decoded when they are needed: searching for a tag only decodes the names
+/// and attributes of the tags, and the matched subtrees. This is useful to
+/// query a specific section of a huge cached document.
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// let html = Html::parse(
+///     "<html><body><nav><a>Home</a></nav><main><h1>Title</h1><p>Long \
+///      text</p></main></body></html>",
+/// )
+/// .unwrap();
+///
+/// let lazy = LazyHtml::from_bin(html.to_bin()).unwrap();
+/// let found = lazy.select(&Filter::new().tag_name("h1")).unwrap();
+/// assert_eq!(found, ["<h1>Title</h1>"]);
+///
+/// let root = lazy.root().unwrap();
+/// assert_eq!(root.tag().unwrap().unwrap().as_name(), "html");
+/// assert_eq!(root.to_html().unwrap(), html);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LazyHtml {
+    /// Bytes of the binary format
+    bytes: Bytes,
+}
+
+impl LazyHtml {
+    /// Loads a tree from its binary format, without decoding the nodes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes don't start with the header of the
+    /// binary format, or if they were created by another version of the
+    /// format. The nodes are only checked when they are decoded.
+    pub fn from_bin(mut bytes: Vec<u8>) -> Result<Self, String> {
+        let mut reader = Reader::new(&bytes);
+        reader.header()?;
+        let header_len = bytes.len().saturating_sub(reader.as_bytes().len());
+        bytes.drain(..header_len);
+        Ok(Self { bytes: Bytes::Owned(bytes) })
+    }
+
+    /// Opens a file that contains a tree in the binary format.
+    ///
+    /// The whole file is read in memory. See [`LazyHtml::from_bin`] for more
+    /// information, and `LazyHtml::open_mmap`, with the `mmap` feature, to map
+    /// the file to memory instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read, or if it doesn't start with
+    /// the header of the binary format.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let bytes = fs::read(path.as_ref())
+            .map_err(|err| format!("Failed to read '{}': {err}", path.as_ref().display()))?;
+        Self::from_bin(bytes)
+    }
+
+    /// Maps a file that contains a tree in the binary format to memory,
+    /// instead of reading it.
+    ///
+    /// The system only loads the pages of the file that contain the decoded
+    /// nodes, so querying a section of a huge file doesn't read the whole
+    /// file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be mapped, or if it doesn't start
+    /// with the header of the binary format.
+    ///
+    /// # Safety
+    ///
+    /// The file mustn't be modified, by this process or by another one, while
+    /// the tree is alive: the changes would be seen by the tree, and reading a
+    /// truncated file crashes the process.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let path = std::env::temp_dir().join("html-filter-open-mmap.bin");
+    /// std::fs::write(&path, Html::parse("<p>Cached</p>").unwrap().to_bin()).unwrap();
+    ///
+    /// // SAFETY: the file isn't modified while it is mapped.
+    /// let lazy = unsafe { LazyHtml::open_mmap(&path) }.unwrap();
+    /// assert_eq!(lazy.to_html().unwrap(), "<p>Cached</p>");
+    /// ```
+    #[cfg(feature = "mmap")]
+    pub unsafe fn open_mmap<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let fail = |err| format!("Failed to map '{}': {err}", path.as_ref().display());
+        let file = File::open(path.as_ref()).map_err(fail)?;
+        // SAFETY: the caller guarantees that the file isn't modified while it
+        // is mapped.
+        let map = unsafe { Mmap::map(&file) }.map_err(fail)?;
+        let mut reader = Reader::new(&map);
+        reader.header()?;
+        let header = map.len().saturating_sub(reader.as_bytes().len());
+        Ok(Self { bytes: Bytes::Mapped { header, map: Arc::new(map) } })
+    }
+
+    /// Returns the root node of the tree.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the binary format of the root node is invalid.
+    pub fn root(&self) -> Result<LazyNode<'_>, String> {
+        let mut reader = Reader::new(self.bytes.as_slice());
+        let node = LazyNode::read(&mut reader)?;
+        if reader.is_empty() {
+            Ok(node)
+        } else {
+            Err("Invalid binary tree: unexpected bytes after the root node.".to_owned())
+        }
+    }
+
+    /// Returns the tags that match the filter, with their content.
+    ///
+    /// This is the equivalent of [`Html::select`], but only the matched tags
+    /// are materialised. The tags are returned in the order of the document.
+    /// Filters that depend on the content of the tags, like
+    /// [`Filter::like`], need to decode the content of each tag, and are
+    /// slower.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the binary format of a visited node is invalid.
+    #[cfg(feature = "filter")]
+    pub fn select(&self, filter: &Filter) -> Result<Vec<Html>, String> {
+        let mut found = vec![];
+        self.root()?.select_aux(filter, &[], &mut found)?;
+        Ok(found)
+    }
+
+    /// Materialises the whole tree.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the binary format is invalid.
+    pub fn to_html(&self) -> Result<Html, String> {
+        self.root()?.to_html()
+    }
+}
+
+/// Node of a [`LazyHtml`], that isn't decoded yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LazyNode<'bin> {
+    /// Kind byte of the node
+    kind: u8,
+    /// Encoded payload of the node
+    payload: &'bin [u8],
+}
+
+impl<'bin> LazyNode<'bin> {
+    /// Returns the children of the node.
+    ///
+    /// The children of a vec are its nodes, and the children of a tag are the
+    /// nodes of its content. The other nodes don't have any children.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the binary format of the node is invalid.
+    pub fn children(&self) -> Result<Vec<Self>, String> {
+        let mut reader = Reader::new(self.payload);
+        match self.kind {
+            TAG => {
+                reader.tag_header()?;
+                let child = Self::read(&mut reader)?;
+                match child.kind {
+                    VEC => child.children(),
+                    EMPTY => Ok(vec![]),
+                    _ => Ok(vec![child]),
+                }
+            }
+            VEC => {
+                let len = reader.varint()?;
+                let mut children = Vec::with_capacity(len.min(self.payload.len()));
+                for _ in 0..len {
+                    children.push(Self::read(&mut reader)?);
+                }
+                Ok(children)
+            }
+            _ => Ok(vec![]),
+        }
+    }
+
+    /// Reads the next node, without decoding it.
+    fn read(reader: &mut Reader<'bin>) -> Result<Self, String> {
+        let (kind, payload) = reader.node_payload()?;
+        Ok(Self { kind, payload: payload.as_bytes() })
+    }
+
+    /// Wrapper for [`LazyHtml::select`]
+    ///
+    /// `scope` is the scope of the node, see [`Filter::inside`].
+    #[cfg(feature = "filter")]
+    fn select_aux(
+        &self,
+        filter: &Filter,
+        scope: &[bool],
+        found: &mut Vec<Html>,
+    ) -> Result<(), String> {
+        let child_scope = if self.kind == TAG {
+            let mut reader = Reader::new(self.payload);
+            let tag = Tag::from(reader.tag_header()?);
+            let child = if filter.needs_child() { reader.node()? } else { Html::Empty };
+            let node = Html::Tag { tag, child: Box::new(child) };
+            if filter.tag_allowed_in(&node, scope) {
+                found.push(self.to_html()?);
+            }
+            filter.enter_scope(&node, scope)
+        } else {
+            None
+        };
+        let inner_scope = child_scope.as_deref().unwrap_or(scope);
+        let children = self.children()?;
+        let tags = children.iter().map(|child| child.kind == TAG).collect::<Vec<_>>();
+        let scopes = filter.tag_sibling_scopes(&tags, inner_scope);
+        for (idx, child) in children.iter().enumerate() {
+            child.select_aux(filter, scopes.get(idx, inner_scope), found)?;
+        }
+        Ok(())
+    }
+
+    /// Decodes the name and the attributes of the node, if it is a tag,
+    /// without decoding its content.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the binary format of the tag is invalid.
+    pub fn tag(&self) -> Result<Option<Tag>, String> {
+        if self.kind == TAG {
+            Reader::new(self.payload).tag_header().map(|header| Some(Tag::from(header)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Materialises the node and its descendants.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the binary format of the node is invalid.
+    pub fn to_html(&self) -> Result<Html, String> {
+        Reader::new(self.payload).into_node(self.kind)
+    }
+}
@@ -1,5 +1,22 @@
 //! Module to define the types needed to make an Html Dom tree.
 
+pub mod arena;
+#[cfg(feature = "serialize")]
+pub mod binary;
+pub mod checked;
+pub mod element_builder;
+pub mod etag;
 pub mod html;
 pub mod html_builder;
+pub mod html_ref;
+pub mod iter;
+pub mod json;
+pub mod jsx;
+#[cfg(feature = "serialize")]
+pub mod lazy;
+pub mod node_id;
+pub mod pretty;
+pub mod pug;
+pub mod serializer;
+pub mod small_string;
 pub mod tag;
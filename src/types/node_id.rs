@@ -0,0 +1,85 @@
+//! Module to define the identifiers of the nodes of a tree.
+
+use core::fmt;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::Html;
+
+/// Identifier of the next created node.
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Stable identifier of a tag.
+///
+/// Each tag receives a new identifier when it is created, either by the
+/// parser or by a transform. Identifiers are increasing, in the order of
+/// creation, so the tags of a parsed document are numbered in the order of
+/// the document, and the tags of fragments parsed later in the session have
+/// greater identifiers.
+///
+/// The identifier of a tag is kept when the tree is modified, or when the tag
+/// is moved. This allows to reference a tag across edits, whereas its position
+/// in the tree can change.
+///
+/// The identifiers are unique among all the tags created by the process, but
+/// cloning a tag or a tree also clones the identifiers. The transforms that
+/// insert copies of a subtree, like [`Html::fill_slots`] and
+/// [`Html::wrap_text_matches`], and the edits that insert a node, with
+/// [`Html::apply_edit`] or a [`ChangeLog`](crate::ChangeLog), thus give new
+/// identifiers to the inserted tags, so that the identifiers of a tree stay
+/// unique. A tag removed by an edit gets its identifier back when the edit is
+/// undone with [`ChangeLog::undo`](crate::ChangeLog::undo).
+///
+/// The identifiers aren't taken into account when comparing tags.
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// let mut html = Html::parse("<div><p>First</p><p>Second</p></div>").unwrap();
+/// let second = html.select(&Filter::new().tag_name("p")).nth(1).unwrap().id().unwrap();
+///
+/// html.strip_where_attr("never", |_| true);
+/// html.wrap_text_matches("First", &Tag::from(("b".to_owned(), Box::default())));
+///
+/// assert_eq!(html.node_by_id(second).unwrap().as_html(), &"<p>Second</p>");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    /// Returns the identifier as an integer.
+    #[must_use]
+    pub const fn as_usize(self) -> usize {
+        self.0
+    }
+
+    /// Creates a new identifier, greater than all the previous ones.
+    pub(crate) fn next() -> Self {
+        Self(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl Html {
+    /// Gives new identifiers to the tags of the tree, in the order of the
+    /// document.
+    pub(crate) fn renew_ids(&mut self) {
+        match self {
+            Self::Tag { tag, child } => {
+                tag.renew_id();
+                child.renew_ids();
+            }
+            Self::Vec(vec) =>
+                for child in vec {
+                    child.renew_ids();
+                },
+            Self::Comment(_) | Self::Doctype { .. } | Self::Empty | Self::Text(_) => (),
+        }
+    }
+}
+
+impl fmt::Display for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
@@ -0,0 +1,458 @@
+//! Module to display an [`Html`] tree re-indented, to make minified documents
+//! readable.
+
+use core::iter::repeat_n;
+use core::mem::take;
+
+use super::tag::Tag;
+use crate::control::clean_controls;
+use crate::entities::escape;
+use crate::{ControlPolicy, Html};
+
+/// Phrasing elements, that are displayed on the same lines as the adjacent
+/// texts, as a line break between them would add a space to the rendered
+/// page.
+const PHRASING_TAGS: [&str; 30] = [
+    "a", "abbr", "b", "bdi", "bdo", "br", "button", "cite", "code", "data", "dfn", "em", "i",
+    "img", "input", "kbd", "label", "mark", "q", "s", "samp", "small", "span", "strong", "sub",
+    "sup", "time", "u", "var", "wbr",
+];
+
+/// Behaviour to adopt for the attributes of the opening tags, when
+/// pretty-printing a tree with [`Html::to_pretty_string`].
+///
+/// When the attributes are wrapped, every attribute is displayed on its own
+/// line, indented one level deeper than the tag, and the closing `>` is on
+/// its own line.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeWrap {
+    /// Wraps the attributes of every tag that has attributes.
+    Always,
+    /// Never wraps the attributes, even if the opening tag is too long.
+    Never,
+    /// Wraps the attributes of the opening tags that don't fit in the maximum
+    /// line length.
+    #[default]
+    OnOverflow,
+}
+
+/// Options to customise the output of [`Html::to_pretty_string`].
+///
+/// The options use a builder pattern: start with [`PrettyOptions::new`] and
+/// chain the options you need. By default, the tree is indented with 2
+/// spaces per level, the lines are wrapped after 80 characters, and the
+/// attributes are wrapped with [`AttributeWrap::OnOverflow`].
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// let html = Html::parse(r#"<div id="menu" class="dark"><p>Menu</p></div>"#).unwrap();
+///
+/// let options = PrettyOptions::new().indent(4).wrap_attributes(AttributeWrap::Always);
+/// assert_eq!(
+///     html.to_pretty_string(&options),
+///     "<div\n    id=\"menu\"\n    class=\"dark\"\n>\n    <p>Menu</p>\n</div>\n"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrettyOptions {
+    /// Number of spaces per level of indentation, see [`Self::indent`].
+    indent: usize,
+    /// Maximum number of characters of a line, see
+    /// [`Self::max_line_length`].
+    max_line_length: usize,
+    /// Whether to modernise the namespaced attributes, see
+    /// [`Self::modernize_attributes`].
+    modernize_attributes: bool,
+    /// Behaviour for the attributes, see [`Self::wrap_attributes`].
+    wrap_attributes: AttributeWrap,
+}
+
+impl PrettyOptions {
+    /// Sets the number of spaces per level of indentation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<ul><li>First</li><li>Second</li></ul>").unwrap();
+    /// assert_eq!(
+    ///     html.to_pretty_string(&PrettyOptions::new().indent(1)),
+    ///     "<ul>\n <li>First</li>\n <li>Second</li>\n</ul>\n"
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn indent(mut self, width: usize) -> Self {
+        self.indent = width;
+        self
+    }
+
+    /// Sets the maximum number of characters of a line, indentation
+    /// included.
+    ///
+    /// A tag whose content is inline is displayed on a single line if it
+    /// fits, and the texts that don't fit are wrapped between their words. A
+    /// word longer than the maximum is never split, so the lines can still be
+    /// longer than the maximum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<p>A short sentence, then a longer one.</p>").unwrap();
+    /// assert_eq!(
+    ///     html.to_pretty_string(&PrettyOptions::new().max_line_length(20)),
+    ///     "<p>\n  A short sentence,\n  then a longer one.\n</p>\n"
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn max_line_length(mut self, length: usize) -> Self {
+        self.max_line_length = length;
+        self
+    }
+
+    /// Replaces the deprecated namespaced attributes of the SVG elements in
+    /// the output, like `xlink:href` by `href`, without modifying the tree.
+    ///
+    /// See [`Html::modernize_attributes`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(r##"<svg><use xlink:href="#a"/></svg>"##).unwrap();
+    /// assert_eq!(
+    ///     html.to_pretty_string(&PrettyOptions::new().modernize_attributes(true)),
+    ///     "<svg>\n  <use href=\"#a\"></use>\n</svg>\n"
+    /// );
+    /// assert_eq!(
+    ///     html.to_pretty_string(&PrettyOptions::new()),
+    ///     "<svg>\n  <use xlink:href=\"#a\"></use>\n</svg>\n"
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn modernize_attributes(mut self, modernize: bool) -> Self {
+        self.modernize_attributes = modernize;
+        self
+    }
+
+    /// Creates the default options.
+    ///
+    /// The tree is indented with 2 spaces per level, the lines are wrapped
+    /// after 80 characters and the attributes are wrapped with
+    /// [`AttributeWrap::OnOverflow`].
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            indent: 2,
+            max_line_length: 80,
+            modernize_attributes: false,
+            wrap_attributes: AttributeWrap::OnOverflow,
+        }
+    }
+
+    /// Sets the behaviour for the attributes of the opening tags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(r#"<a href="/home" title="Back to the home page">Home</a>"#).unwrap();
+    ///
+    /// let options = PrettyOptions::new().max_line_length(30);
+    /// assert_eq!(
+    ///     html.to_pretty_string(&options),
+    ///     "<a\n  href=\"/home\"\n  title=\"Back to the home page\"\n>\n  Home\n</a>\n"
+    /// );
+    /// assert_eq!(
+    ///     html.to_pretty_string(&options.wrap_attributes(AttributeWrap::Never)),
+    ///     "<a href=\"/home\" title=\"Back to the home page\">\n  Home\n</a>\n"
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn wrap_attributes(mut self, wrap: AttributeWrap) -> Self {
+        self.wrap_attributes = wrap;
+        self
+    }
+}
+
+impl Default for PrettyOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Writer of the lines of a pretty-printed tree.
+struct Printer<'options> {
+    /// Options of the output
+    options: &'options PrettyOptions,
+    /// Lines written so far, each ending with a line break
+    output: String,
+}
+
+impl Printer<'_> {
+    /// Writes words separated by spaces, wrapped to fit in the maximum line
+    /// length.
+    fn fill(&mut self, words: &[String], depth: usize) {
+        let mut line = String::new();
+        for word in words {
+            if !line.is_empty() && !self.fits(depth, &format!("{line} {word}")) {
+                self.line(depth, &line);
+                line.clear();
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        if !line.is_empty() {
+            self.line(depth, &line);
+        }
+    }
+
+    /// Checks if a line fits in the maximum line length, once indented at
+    /// the given depth.
+    fn fits(&self, depth: usize, line: &str) -> bool {
+        self.width(depth).saturating_add(line.chars().count()) <= self.options.max_line_length
+    }
+
+    /// Writes a run of inline siblings on as few lines as possible.
+    ///
+    /// A run with a single tag is written like a block, so that its
+    /// attributes can be wrapped.
+    fn inline(&mut self, run: &[&Html], depth: usize) {
+        if let [Html::Tag { tag, child }] = run {
+            return self.tag(tag, child, depth);
+        }
+        self.fill(&Words::of(run), depth);
+    }
+
+    /// Writes a line, indented at the given depth.
+    fn line(&mut self, depth: usize, line: &str) {
+        self.output.extend(repeat_n(' ', self.width(depth)));
+        self.output.push_str(line);
+        self.output.push('\n');
+    }
+
+    /// Writes a node and its descendants, indented at the given depth.
+    fn node(&mut self, node: &Html, depth: usize) {
+        match node {
+            Html::Tag { tag, .. } if tag.is_preformatted() => self.line(depth, &node.to_string()),
+            Html::Tag { tag, child } => self.tag(tag, child, depth),
+            Html::Text(_) | Html::Vec(_) => self.siblings(&content(node), depth),
+            Html::Comment(_) | Html::Doctype { .. } => self.line(depth, &node.to_string()),
+            Html::Empty => (),
+        }
+    }
+
+    /// Writes the opening tag, wrapping its attributes if needed.
+    fn open_tag(&mut self, tag: &Tag, depth: usize) {
+        let open = format!("<{tag}>");
+        let wrap = !tag.attrs.is_empty()
+            && match self.options.wrap_attributes {
+                AttributeWrap::Always => true,
+                AttributeWrap::Never => false,
+                AttributeWrap::OnOverflow => !self.fits(depth, &open),
+            };
+        if !wrap {
+            return self.line(depth, &open);
+        }
+        self.line(depth, &format!("<{}", clean_controls(tag.as_name(), ControlPolicy::Replace)));
+        for attr in &tag.attrs {
+            self.line(depth.saturating_add(1), attr.to_string().trim_start());
+        }
+        self.line(depth, ">");
+    }
+
+    /// Writes sibling nodes: the runs of inline nodes share their lines, and
+    /// the other nodes are written on their own lines.
+    fn siblings(&mut self, nodes: &[&Html], depth: usize) {
+        for chunk in nodes.split_inclusive(|node| !is_inline(node)) {
+            let (run, block) = match chunk.split_last() {
+                Some((last, run)) if !is_inline(last) => (run, Some(*last)),
+                Some(_) | None => (chunk, None),
+            };
+            self.inline(run, depth);
+            if let Some(node) = block {
+                self.node(node, depth);
+            }
+        }
+    }
+
+    /// Writes a tag and its descendants.
+    ///
+    /// A tag whose content is only made of inline nodes is written on one
+    /// line if it fits and if its attributes aren't wrapped.
+    fn tag(&mut self, tag: &Tag, child: &Html, depth: usize) {
+        let close = tag.to_closing_string();
+        let nodes = content(child);
+        if !tag.is_void() && nodes.iter().all(|node| is_inline(node)) {
+            let line = format!("<{tag}>{}{close}", Words::of(&nodes).join(" "));
+            let wraps =
+                !tag.attrs.is_empty() && self.options.wrap_attributes == AttributeWrap::Always;
+            if !wraps && self.fits(depth, &line) {
+                return self.line(depth, &line);
+            }
+        }
+        self.open_tag(tag, depth);
+        if tag.is_void() {
+            return;
+        }
+        self.siblings(&nodes, depth.saturating_add(1));
+        self.line(depth, &close);
+    }
+
+    /// Returns the number of spaces of the indentation at the given depth.
+    const fn width(&self, depth: usize) -> usize {
+        self.options.indent.saturating_mul(depth)
+    }
+}
+
+/// Words of a run of inline nodes, between which the lines can be broken.
+///
+/// The lines are only broken where the texts have whitespace, so the markup
+/// of the phrasing elements is glued to the adjacent texts.
+#[derive(Default)]
+struct Words {
+    /// Word that is being read
+    current: String,
+    /// Words read so far
+    words: Vec<String>,
+}
+
+impl Words {
+    /// Returns the words of a run of inline nodes.
+    fn of(nodes: &[&Html]) -> Vec<String> {
+        let mut words = Self::default();
+        for node in nodes {
+            words.push(node);
+        }
+        words.split();
+        words.words
+    }
+
+    /// Pushes a node and its descendants.
+    ///
+    /// The whitespace of the texts is collapsed, except inside the
+    /// preformatted tags, that are pushed as they are.
+    fn push(&mut self, node: &Html) {
+        match node {
+            Html::Tag { tag, child } if !tag.is_preformatted() => {
+                self.current.push_str(&tag.to_opening_string());
+                if !tag.is_void() {
+                    self.push(child);
+                    self.current.push_str(&tag.to_closing_string());
+                }
+            }
+            Html::Text(text) => {
+                let escaped = escape(text.as_str(), &['<']);
+                if escaped.starts_with(|ch: char| ch.is_ascii_whitespace()) {
+                    self.split();
+                }
+                let mut words = escaped.split_ascii_whitespace();
+                self.current.push_str(words.next().unwrap_or_default());
+                for word in words {
+                    self.split();
+                    self.current.push_str(word);
+                }
+                if escaped.ends_with(|ch: char| ch.is_ascii_whitespace()) {
+                    self.split();
+                }
+            }
+            Html::Vec(_) => node.children().for_each(|child| self.push(child)),
+            Html::Comment(_) | Html::Doctype { .. } | Html::Tag { .. } =>
+                self.current.push_str(&node.to_string()),
+            Html::Empty => (),
+        }
+    }
+
+    /// Ends the current word, where a text has whitespace.
+    fn split(&mut self) {
+        if !self.current.is_empty() {
+            self.words.push(take(&mut self.current));
+        }
+    }
+}
+
+impl Html {
+    /// Displays the tree re-indented, with one node per line.
+    ///
+    /// Contrary to the [`Display`](core::fmt::Display) implementation, that
+    /// reproduces the original spacing, the tree is re-indented with the
+    /// [`PrettyOptions`]: this is useful to get a readable output from a
+    /// minified input. The texts and the phrasing elements, like `<b>` or
+    /// `<a>`, share their lines, as a line break between them would add a
+    /// space to the rendered page: a tag whose content is only made of them
+    /// is displayed on one line if it fits, like `<p>Some <b>bold</b>
+    /// text</p>`. Every other node is displayed on its own line. The
+    /// whitespace of the texts is collapsed and the empty texts are removed,
+    /// as they aren't significant in the rendered page.
+    ///
+    /// The content of the preformatted tags, like `<pre>` or `<textarea>`,
+    /// whose whitespace is significant, is displayed as it is. See
+    /// [`Tag::is_preformatted`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(
+    ///     "<!DOCTYPE html><html><body><h1>Title</h1><ul><li>First</li><li>Second</li></ul></body></\
+    ///      html>",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(
+    ///     html.to_pretty_string(&PrettyOptions::new()),
+    ///     "<!DOCTYPE html>
+    /// <html>
+    ///   <body>
+    ///     <h1>Title</h1>
+    ///     <ul>
+    ///       <li>First</li>
+    ///       <li>Second</li>
+    ///     </ul>
+    ///   </body>
+    /// </html>
+    /// "
+    /// );
+    /// ```
+    #[must_use]
+    pub fn to_pretty_string(&self, options: &PrettyOptions) -> String {
+        let mut printer = Printer { options, output: String::new() };
+        if options.modernize_attributes {
+            let mut modern = self.clone();
+            modern.modernize_attributes();
+            printer.node(&modern, 0);
+        } else {
+            printer.node(self, 0);
+        }
+        printer.output
+    }
+}
+
+/// Returns the nodes of the content of a tag, or of the root.
+fn content(node: &Html) -> Vec<&Html> {
+    match node {
+        Html::Vec(_) => node.children().collect(),
+        Html::Empty => vec![],
+        Html::Comment(_) | Html::Doctype { .. } | Html::Tag { .. } | Html::Text(_) => vec![node],
+    }
+}
+
+/// Checks if a node is inline, i.e., a text or a phrasing element, that
+/// shares its lines with its inline siblings.
+fn is_inline(node: &Html) -> bool {
+    match node {
+        Html::Tag { tag, .. } =>
+            PHRASING_TAGS.iter().any(|name| name.eq_ignore_ascii_case(tag.as_name())),
+        Html::Text(_) => true,
+        Html::Comment(_) | Html::Doctype { .. } | Html::Empty | Html::Vec(_) => false,
+    }
+}
@@ -0,0 +1,384 @@
+//! Module to write an [`Html`] tree as Pug, the indentation-based template
+//! language formerly known as Jade.
+
+use core::convert::Infallible;
+use core::iter::repeat_n;
+
+use super::jsx::string_literal;
+use super::tag::{PREFORMATTED_TAGS, RAW_TEXT_TAGS, VOID_TAGS, has_name};
+use crate::{Html, Serializer};
+
+/// Number of spaces per level of indentation.
+const INDENT: usize = 2;
+
+/// Content of a preformatted tag, like `<pre>`, that is written as html in a
+/// block of text when the tag is closed.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct Block {
+    /// Number of open tags, the preformatted one included
+    depth: usize,
+    /// Html of the content read so far
+    html: String,
+    /// Whether the last opening tag of the content isn't closed yet, as it
+    /// still waits for its attributes
+    open: bool,
+}
+
+impl Block {
+    /// Closes the last opening tag of the content, if it is still open.
+    fn close_start_tag(&mut self) {
+        if self.open {
+            self.html.push('>');
+            self.open = false;
+        }
+    }
+}
+
+/// Opening tag whose line wasn't written yet, as it waits for its attributes
+/// and for an inline text.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct PendingTag {
+    /// Attributes that aren't written with the shorthands, already formatted
+    attrs: Vec<String>,
+    /// Classes, written with the `.class` shorthand
+    classes: Vec<String>,
+    /// Identifier, written with the `#id` shorthand
+    id: Option<String>,
+    /// Name of the tag
+    name: String,
+}
+
+impl PendingTag {
+    /// Formats the tag for the start of its line, like `a.button(href="/")`.
+    ///
+    /// The `div` name is omitted when a shorthand is written, like in Pug.
+    fn to_line(&self) -> String {
+        let mut line = String::new();
+        if self.name != "div" || (self.id.is_none() && self.classes.is_empty()) {
+            line.push_str(&self.name);
+        }
+        if let Some(id) = &self.id {
+            line.push('#');
+            line.push_str(id);
+        }
+        for class in &self.classes {
+            line.push('.');
+            line.push_str(class);
+        }
+        if !self.attrs.is_empty() {
+            line.push('(');
+            line.push_str(&self.attrs.join(" "));
+            line.push(')');
+        }
+        line
+    }
+}
+
+/// [`Serializer`] that writes a tree as Pug.
+///
+/// Every node is written on its own line, indented with 2 spaces per level:
+///
+/// - the tags are written with their name, followed by the `#id` and `.class`
+///   shorthands when the values are valid identifiers, then by the other
+///   attributes between parentheses, like `a.button(href="/")`. The name of the
+///   `div` tags is omitted when they have a shorthand;
+/// - a text on a single line that is the first child of a tag is written after
+///   the tag, like `p Hello`, and the other texts are written on their own
+///   lines, after a `|`. The texts that only contain whitespace are dropped;
+/// - the content of the `<script>` and `<style>` tags is written as a block of
+///   text, after a `.`. So is the content of the other preformatted tags, like
+///   `<pre>` and `<textarea>`, as html and with its whitespace preserved: the
+///   leading whitespace of its first line, that Pug would read as the
+///   indentation of the block, is written with character references;
+/// - the comments are written after `//`, and the doctypes as `doctype html`.
+///
+/// The texts and the attribute values are written as they are stored in the
+/// tree, as Pug doesn't escape the plain texts. The `#{`, `#[` and `!{` of the
+/// texts are escaped, so that Pug doesn't interpolate them, and the attribute
+/// values that contain character references are written unescaped with `!=`.
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// let html = Html::parse(
+///     r#"<!DOCTYPE html><div id="menu" class="nav dark"><a href="/" class="item">Home</a><p>Welcome, <b>guest</b></p><input disabled></div>"#,
+/// )
+/// .unwrap();
+///
+/// let mut pug = PugSerializer::new();
+/// html.serialize(&mut pug).unwrap();
+/// assert_eq!(
+///     pug.as_output(),
+///     r#"doctype html
+/// #menu.nav.dark
+///   a.item(href="/") Home
+///   p Welcome,
+///     b guest
+///   input(disabled)
+/// "#
+/// );
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PugSerializer {
+    /// Content of the preformatted tag that is open, if any
+    block: Option<Block>,
+    /// Pug written so far
+    output: String,
+    /// Opening tag whose line wasn't written yet
+    pending: Option<PendingTag>,
+    /// Names of the tags that are open
+    stack: Vec<String>,
+}
+
+impl PugSerializer {
+    /// Returns the Pug written so far.
+    ///
+    /// The last opening tag is only written when the next node starts, or
+    /// when it is closed.
+    #[must_use]
+    pub fn as_output(&self) -> &str {
+        &self.output
+    }
+
+    /// Returns the depth of the nodes that are written next.
+    const fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Writes the pending opening tag, if any, followed by an inline text.
+    fn flush(&mut self, inline: Option<&str>) {
+        let Some(pending) = self.pending.take() else { return };
+        let mut line = pending.to_line();
+        if let Some(text) = inline {
+            line.push_str(text);
+        }
+        self.line(self.depth().saturating_sub(1), &line);
+    }
+
+    /// Converts the serializer into the Pug it wrote.
+    #[must_use]
+    pub fn into_output(mut self) -> String {
+        self.flush(None);
+        self.output
+    }
+
+    /// Writes a line, indented at the given depth.
+    fn line(&mut self, depth: usize, line: &str) {
+        self.output.extend(repeat_n(' ', INDENT.saturating_mul(depth)));
+        self.output.push_str(line);
+        self.output.push('\n');
+    }
+
+    /// Creates a serializer with an empty output.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { block: None, output: String::new(), pending: None, stack: vec![] }
+    }
+
+    /// Writes the content of a preformatted tag as a block of text, at the
+    /// given depth, preserving its whitespace.
+    ///
+    /// The line break right after the opening tag is dropped, as html
+    /// ignores it. Pug reads the indentation of the block on its first line,
+    /// so the other leading line breaks and the indentation of the first line
+    /// are written as character references.
+    fn write_block(&mut self, depth: usize, html: &str) {
+        let body = html.strip_prefix('\n').unwrap_or(html).trim_end_matches('\n');
+        if body.is_empty() {
+            return self.flush(None);
+        }
+        self.flush(Some("."));
+        let content = body.trim_start_matches('\n');
+        let mut lines = content.split('\n');
+        let first = lines.next().unwrap_or_default();
+        let text = first.trim_start();
+        let indent = first.get(..first.len().saturating_sub(text.len())).unwrap_or_default();
+        let mut encoded = "&#10;".repeat(body.len().saturating_sub(content.len()));
+        encoded.extend(indent.chars().map(|ch| format!("&#{};", u32::from(ch))));
+        encoded.push_str(text);
+        self.line(depth, &encoded);
+        for line in lines {
+            if line.trim().is_empty() {
+                self.output.push('\n');
+            } else {
+                self.line(depth, line);
+            }
+        }
+    }
+}
+
+impl Serializer for PugSerializer {
+    type Error = Infallible;
+
+    fn attr(&mut self, name: &str, value: Option<&str>) -> Result<(), Self::Error> {
+        if let Some(block) = self.block.as_mut().filter(|block| block.open) {
+            block.html.push(' ');
+            block.html.push_str(name);
+            if let Some(content) = value {
+                let quoted = content.replace('"', "&quot;");
+                block.html.push_str("=\"");
+                block.html.push_str(&escape_interpolation(&quoted));
+                block.html.push('"');
+            }
+            return Ok(());
+        }
+        let Some(pending) = &mut self.pending else { return Ok(()) };
+        match value {
+            Some(id) if name == "id" && pending.id.is_none() && is_identifier(id) =>
+                pending.id = Some(id.to_owned()),
+            Some(classes)
+                if name == "class"
+                    && pending.classes.is_empty()
+                    && !classes.trim().is_empty()
+                    && classes.split_whitespace().all(is_identifier) =>
+                pending.classes = classes.split_whitespace().map(str::to_owned).collect(),
+            Some(content) => {
+                let operator = if content.contains('&') { "!=" } else { "=" };
+                pending.attrs.push(format!("{name}{operator}{}", string_literal(content)));
+            }
+            None => pending.attrs.push(name.to_owned()),
+        }
+        Ok(())
+    }
+
+    fn comment(&mut self, content: &str) -> Result<(), Self::Error> {
+        if let Some(block) = &mut self.block {
+            block.close_start_tag();
+            block.html.push_str("<!--");
+            block.html.push_str(&escape_interpolation(content));
+            block.html.push_str("-->");
+            return Ok(());
+        }
+        self.flush(None);
+        let depth = self.depth();
+        let mut lines = content.lines().map(str::trim).filter(|line| !line.is_empty());
+        let first = format!("// {}", lines.next().unwrap_or_default());
+        self.line(depth, first.trim_end());
+        for line in lines {
+            self.line(depth.saturating_add(1), line);
+        }
+        Ok(())
+    }
+
+    fn doctype(&mut self, _name: &str, attr: Option<&str>) -> Result<(), Self::Error> {
+        self.flush(None);
+        let line = attr.map_or_else(|| "doctype".to_owned(), |kind| format!("doctype {kind}"));
+        self.line(self.depth(), &line);
+        Ok(())
+    }
+
+    fn end_tag(&mut self, name: &str) -> Result<(), Self::Error> {
+        match self.block.take() {
+            Some(block) if block.depth == self.depth() =>
+                self.write_block(block.depth, &block.html),
+            Some(mut block) => {
+                block.close_start_tag();
+                if !has_name(&VOID_TAGS, name) {
+                    block.html.push_str("</");
+                    block.html.push_str(name);
+                    block.html.push('>');
+                }
+                self.block = Some(block);
+            }
+            None => self.flush(None),
+        }
+        self.stack.pop();
+        Ok(())
+    }
+
+    fn start_tag(&mut self, name: &str) -> Result<(), Self::Error> {
+        if let Some(block) = &mut self.block {
+            block.close_start_tag();
+            block.html.push('<');
+            block.html.push_str(name);
+            block.open = true;
+        } else {
+            self.flush(None);
+            self.pending = Some(PendingTag { name: name.to_owned(), ..PendingTag::default() });
+            if has_name(&PREFORMATTED_TAGS, name) && !has_name(&RAW_TEXT_TAGS, name) {
+                self.block =
+                    Some(Block { depth: self.depth().saturating_add(1), ..Block::default() });
+            }
+        }
+        self.stack.push(name.to_owned());
+        Ok(())
+    }
+
+    fn text(&mut self, text: &str) -> Result<(), Self::Error> {
+        if let Some(block) = &mut self.block {
+            block.close_start_tag();
+            block.html.push_str(&escape_interpolation(text));
+            return Ok(());
+        }
+        let depth = self.depth();
+        if self.stack.last().is_some_and(|name| has_name(&RAW_TEXT_TAGS, name)) {
+            self.flush(Some("."));
+            for line in dedent(text) {
+                self.line(depth, line);
+            }
+            return Ok(());
+        }
+        let escaped = escape_interpolation(text);
+        let mut lines = escaped.lines().map(str::trim).filter(|line| !line.is_empty());
+        let first = lines.next();
+        if self.pending.is_some()
+            && let Some(inline) = first
+            && lines.clone().next().is_none()
+        {
+            self.flush(Some(&format!(" {inline}")));
+            return Ok(());
+        }
+        self.flush(None);
+        for line in first.into_iter().chain(lines) {
+            self.line(depth, &format!("| {line}"));
+        }
+        Ok(())
+    }
+}
+
+impl Html {
+    /// Writes the tree as Pug, with a [`PugSerializer`].
+    ///
+    /// See [`PugSerializer`] for the details of the conversion.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<ul><li>First</li><li>Second</li></ul>").unwrap();
+    /// assert_eq!(html.to_pug(), "ul\n  li First\n  li Second\n");
+    /// ```
+    #[must_use]
+    pub fn to_pug(&self) -> String {
+        let mut pug = PugSerializer::new();
+        match self.serialize(&mut pug) {
+            Ok(()) => pug.into_output(),
+            Err(never) => match never {},
+        }
+    }
+}
+
+/// Returns the non-empty lines of a text, without their common indentation.
+fn dedent(text: &str) -> Vec<&str> {
+    let lines = text.lines().filter(|line| !line.trim().is_empty()).collect::<Vec<_>>();
+    let indent = lines
+        .iter()
+        .map(|line| line.len().saturating_sub(line.trim_start().len()))
+        .min()
+        .unwrap_or_default();
+    lines.into_iter().map(|line| line.get(indent..).unwrap_or(line).trim_end()).collect()
+}
+
+/// Escapes the sequences that Pug interpolates in the plain texts.
+fn escape_interpolation(text: &str) -> String {
+    text.replace("#{", "\\#{").replace("#[", "\\#[").replace("!{", "\\!{")
+}
+
+/// Checks if a value can be written with the `#id` and `.class` shorthands.
+fn is_identifier(value: &str) -> bool {
+    value.chars().next().is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && value.chars().all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+}
@@ -0,0 +1,184 @@
+//! Module to define the [`Serializer`] trait, to write an [`Html`] tree in
+//! other formats.
+
+use crate::Html;
+
+/// Sink that receives the nodes of an [`Html`] tree, to write it in a custom
+/// format.
+///
+/// [`Html::serialize`] walks the tree in the order of the document and calls
+/// the methods of the serializer for every node:
+///
+/// - [`Serializer::start_tag`] for the opening tag, followed by a call to
+///   [`Serializer::attr`] for each of its attributes, then the calls for its
+///   children, then [`Serializer::end_tag`], even for the void elements;
+/// - [`Serializer::text`] and [`Serializer::comment`] for the texts and the
+///   comments;
+/// - [`Serializer::doctype`] for the doctypes, which are ignored by default.
+///
+/// The strings are given as they are stored in the tree, without escaping:
+/// the serializer must escape them for its format. Note that the character
+/// references of the input are only decoded in the tree with
+/// [`ParseOptions::decode_entities`](crate::ParseOptions::decode_entities).
+///
+/// The walk stops at the first error returned by the serializer.
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// /// Writes the tree as XML, with self-closing empty tags.
+/// #[derive(Default)]
+/// struct Xml {
+///     output: String,
+///     open: bool,
+/// }
+///
+/// impl Xml {
+///     fn close_start_tag(&mut self) {
+///         if self.open {
+///             self.output.push('>');
+///             self.open = false;
+///         }
+///     }
+/// }
+///
+/// impl Serializer for Xml {
+///     type Error = core::convert::Infallible;
+///
+///     fn attr(&mut self, name: &str, value: Option<&str>) -> Result<(), Self::Error> {
+///         let value = encode_entities(value.unwrap_or(name));
+///         self.output.push_str(&format!(" {name}=\"{value}\""));
+///         Ok(())
+///     }
+///
+///     fn comment(&mut self, content: &str) -> Result<(), Self::Error> {
+///         self.close_start_tag();
+///         self.output.push_str(&format!("<!--{content}-->"));
+///         Ok(())
+///     }
+///
+///     fn end_tag(&mut self, name: &str) -> Result<(), Self::Error> {
+///         if self.open {
+///             self.output.push_str("/>");
+///             self.open = false;
+///         } else {
+///             self.output.push_str(&format!("</{name}>"));
+///         }
+///         Ok(())
+///     }
+///
+///     fn start_tag(&mut self, name: &str) -> Result<(), Self::Error> {
+///         self.close_start_tag();
+///         self.output.push_str(&format!("<{name}"));
+///         self.open = true;
+///         Ok(())
+///     }
+///
+///     fn text(&mut self, text: &str) -> Result<(), Self::Error> {
+///         self.close_start_tag();
+///         self.output.push_str(&encode_entities(text));
+///         Ok(())
+///     }
+/// }
+///
+/// let html = Html::parse("<p>Tom & Jerry<br><input disabled></p>").unwrap();
+/// let mut xml = Xml::default();
+/// html.serialize(&mut xml).unwrap();
+/// assert_eq!(xml.output, r#"<p>Tom &amp; Jerry<br/><input disabled="disabled"/></p>"#);
+/// ```
+pub trait Serializer {
+    /// Error returned by the serializer, to stop the walk.
+    type Error;
+
+    /// Receives an attribute of the last opened tag, with its value if it has
+    /// one.
+    ///
+    /// # Errors
+    ///
+    /// The error is returned by [`Html::serialize`].
+    fn attr(&mut self, name: &str, value: Option<&str>) -> Result<(), Self::Error>;
+
+    /// Receives a comment, with the content between `<!--` and `-->`.
+    ///
+    /// # Errors
+    ///
+    /// The error is returned by [`Html::serialize`].
+    fn comment(&mut self, content: &str) -> Result<(), Self::Error>;
+
+    /// Receives a doctype, with its name, like `DOCTYPE`, and its attribute,
+    /// like `html`.
+    ///
+    /// The doctypes are ignored by default.
+    ///
+    /// # Errors
+    ///
+    /// The error is returned by [`Html::serialize`].
+    fn doctype(&mut self, _name: &str, _attr: Option<&str>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Receives the end of a tag, after its children.
+    ///
+    /// # Errors
+    ///
+    /// The error is returned by [`Html::serialize`].
+    fn end_tag(&mut self, name: &str) -> Result<(), Self::Error>;
+
+    /// Receives the start of a tag, before its attributes.
+    ///
+    /// # Errors
+    ///
+    /// The error is returned by [`Html::serialize`].
+    fn start_tag(&mut self, name: &str) -> Result<(), Self::Error>;
+
+    /// Receives a text, including the content of the `<script>` and `<style>`
+    /// tags.
+    ///
+    /// # Errors
+    ///
+    /// The error is returned by [`Html::serialize`].
+    fn text(&mut self, text: &str) -> Result<(), Self::Error>;
+}
+
+impl Html {
+    /// Walks the tree in the order of the document, and sends its nodes to
+    /// a [`Serializer`].
+    ///
+    /// This allows to write the tree in other formats, like XML or JSX, or in
+    /// a custom binary encoding, without walking the tree again.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error returned by the serializer, after which the
+    /// walk stops.
+    ///
+    /// # Examples
+    ///
+    /// See [`Serializer`].
+    #[cfg_attr(
+        feature = "serde",
+        expect(
+            clippy::same_name_method,
+            reason = "the serde method is only called through its trait"
+        )
+    )]
+    pub fn serialize<S: Serializer>(&self, serializer: &mut S) -> Result<(), S::Error> {
+        match self {
+            Self::Comment(content) => serializer.comment(content),
+            Self::Doctype { name, attr } => serializer.doctype(name, attr.as_deref()),
+            Self::Empty => Ok(()),
+            Self::Tag { tag, child } => {
+                serializer.start_tag(tag.as_name())?;
+                for attr in tag.as_attrs() {
+                    serializer.attr(attr.as_name(), attr.as_value().map(String::as_str))?;
+                }
+                child.serialize(serializer)?;
+                serializer.end_tag(tag.as_name())
+            }
+            Self::Text(text) => serializer.text(text),
+            Self::Vec(vec) => vec.iter().try_for_each(|node| node.serialize(serializer)),
+        }
+    }
+}
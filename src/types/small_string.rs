@@ -0,0 +1,253 @@
+//! Module to define a string that is stored inline when it is short.
+
+use alloc::borrow::Cow;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+use core::{fmt, str};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Maximum length of a string stored inline, in bytes.
+const INLINE_CAPACITY: usize = 22;
+
+/// Storage of a [`SmallString`].
+#[derive(Clone)]
+enum Repr {
+    /// Long string, allocated on the heap.
+    Heap(Box<str>),
+    /// Short string, stored inline.
+    Inline {
+        /// Bytes of the string, followed by zeros.
+        bytes: [u8; INLINE_CAPACITY],
+        /// Length of the string, in bytes.
+        len: u8,
+    },
+}
+
+/// String that is stored inline, without any allocation, when it is short.
+///
+/// The tag names, the attribute names and the texts of a document are often
+/// only a few bytes long, like `div`, `class` or the whitespace between two
+/// tags. Storing them inline avoids an allocation for each of them. Strings
+/// longer than 22 bytes are stored on the heap, like a [`Box<str>`]. Either
+/// way, a [`SmallString`] takes as much space as a [`String`].
+///
+/// A [`SmallString`] dereferences to a [`prim@str`], so it can be used like a
+/// [`String`] in most cases. Use [`String::from`] to get an owned
+/// [`String`].
+///
+/// # Examples
+///
+/// ```
+/// use html_filter::*;
+///
+/// let html = Html::parse("<div class='a'>Hello</div>").unwrap();
+/// let (tag, child) = html.as_tag().unwrap();
+/// let name: &SmallString = &tag.name;
+/// assert_eq!(name, "div");
+/// assert_eq!(name.len(), 3);
+/// assert!(name.is_inline());
+/// assert_eq!(child.as_text(), Some("Hello"));
+///
+/// let long = SmallString::from("a string that is too long to be inline");
+/// assert!(!long.is_inline());
+/// assert_eq!(String::from(long), "a string that is too long to be inline");
+/// ```
+#[derive(Clone)]
+pub struct SmallString(Repr);
+
+impl SmallString {
+    /// Returns the string as a [`prim@str`].
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match &self.0 {
+            Repr::Heap(heap) => heap,
+            Repr::Inline { bytes, len } => bytes
+                .get(..usize::from(*len))
+                .and_then(|inline| str::from_utf8(inline).ok())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Checks if the string is stored inline, i.e., without any allocation.
+    #[must_use]
+    pub const fn is_inline(&self) -> bool {
+        matches!(self.0, Repr::Inline { .. })
+    }
+
+    /// Creates an empty string.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(Repr::Inline { bytes: [0; INLINE_CAPACITY], len: 0 })
+    }
+
+    /// Appends a string at the end.
+    ///
+    /// The string moves to the heap when it becomes too long to be inline. A
+    /// long string is reallocated at every call, so prefer building it with a
+    /// [`String`] when it is appended many times.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let mut string = SmallString::from("short");
+    /// string.push_str(" and still short");
+    /// assert!(string.is_inline());
+    /// string.push_str(", but now too long");
+    /// assert!(!string.is_inline());
+    /// assert_eq!(string, "short and still short, but now too long");
+    /// ```
+    pub fn push_str(&mut self, string: &str) {
+        if let Repr::Inline { bytes, len } = &mut self.0 {
+            let end = usize::from(*len).saturating_add(string.len());
+            if let Some(dest) = bytes.get_mut(usize::from(*len)..end)
+                && let Ok(new_len) = u8::try_from(end)
+            {
+                dest.copy_from_slice(string.as_bytes());
+                *len = new_len;
+                return;
+            }
+        }
+        let mut heap = String::with_capacity(self.len().saturating_add(string.len()));
+        heap.push_str(self.as_str());
+        heap.push_str(string);
+        self.0 = Repr::Heap(heap.into_boxed_str());
+    }
+}
+
+impl AsRef<str> for SmallString {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for SmallString {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Debug for SmallString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+impl Default for SmallString {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for SmallString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for SmallString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::from)
+    }
+}
+
+impl fmt::Display for SmallString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+impl Eq for SmallString {}
+
+impl From<&str> for SmallString {
+    fn from(string: &str) -> Self {
+        let mut small = Self::new();
+        small.push_str(string);
+        small
+    }
+}
+
+impl From<Cow<'_, str>> for SmallString {
+    fn from(string: Cow<'_, str>) -> Self {
+        match string {
+            Cow::Borrowed(borrowed) => Self::from(borrowed),
+            Cow::Owned(owned) => Self::from(owned),
+        }
+    }
+}
+
+impl From<SmallString> for String {
+    fn from(small: SmallString) -> Self {
+        match small.0 {
+            Repr::Heap(heap) => heap.into_string(),
+            Repr::Inline { .. } => small.as_str().to_owned(),
+        }
+    }
+}
+
+impl From<String> for SmallString {
+    fn from(string: String) -> Self {
+        if string.len() <= INLINE_CAPACITY {
+            Self::from(string.as_str())
+        } else {
+            Self(Repr::Heap(string.into_boxed_str()))
+        }
+    }
+}
+
+impl Hash for SmallString {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl Ord for SmallString {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl PartialEq for SmallString {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl PartialEq<&str> for SmallString {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for SmallString {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<str> for SmallString {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialOrd for SmallString {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for SmallString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
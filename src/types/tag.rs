@@ -1,7 +1,40 @@
 //! Module to define the tag data structure.
 
-use core::fmt;
+use alloc::borrow::Cow;
 use core::hash::Hash;
+use core::iter::FusedIterator;
+use core::{fmt, slice};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::html_ref::{AttributeRef, TagRef};
+use super::node_id::NodeId;
+use super::small_string::SmallString;
+use crate::ControlPolicy;
+use crate::control::clean_controls;
+use crate::parse::template::escape_outside_placeholders;
+
+/// Namespaced attributes of the SVG elements that are deprecated, with the
+/// attributes that replace them.
+const LEGACY_ATTRIBUTES: [(&str, &str); 2] = [("xlink:href", "href"), ("xml:lang", "lang")];
+
+/// Tags whose whitespace is rendered as is, and must be kept.
+pub const PREFORMATTED_TAGS: [&str; 5] = ["listing", "pre", "script", "style", "textarea"];
+
+/// Tags whose content is raw text, without any tag or character reference.
+pub const RAW_TEXT_TAGS: [&str; 2] = ["script", "style"];
+
+/// Void elements, i.e., tags that can't have any content.
+///
+/// They are always self-closing tags: `<meta>` and `<br>` are closed when
+/// parsed, and are serialised without a closing tag. The list can be
+/// overridden for the parsing with
+/// [`ParseOptions::void_tags`](crate::ParseOptions::void_tags).
+pub const VOID_TAGS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
 
 /// Name and optionally a value for an attribute of a tag.
 ///
@@ -14,13 +47,14 @@ use core::hash::Hash;
     reason = "hash on enum doesn't depend of variant data"
 )]
 #[derive(Debug, Hash, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Attribute {
     /// Name of the attribute, when it doesn't have a value
     ///
     /// # Examples
     ///
     /// In `<button />`, the name of the attribute is `button`.
-    NameNoValue(String),
+    NameNoValue(SmallString),
     /// Name of the attribute
     ///
     /// # Examples
@@ -41,7 +75,7 @@ pub enum Attribute {
         /// # Note
         ///
         /// Attribute names can have prefixes, like in `<a xlink:href="link"/>`
-        name: String,
+        name: SmallString,
         /// Value of the attribute
         ///
         /// # Examples
@@ -53,14 +87,101 @@ pub enum Attribute {
 }
 
 impl Attribute {
+    /// Returns a read-only view of the attribute, that borrows its name and
+    /// its value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(r#"<td colspan="2" hidden></td>"#).unwrap();
+    /// let attrs = html.as_tag().unwrap().0.as_attrs();
+    ///
+    /// let colspan: AttributeRef<'_> = attrs[0].as_attribute_ref();
+    /// assert_eq!((colspan.as_name(), colspan.as_value()), ("colspan", Some("2")));
+    /// assert_eq!(attrs[1].as_attribute_ref(), AttributeRef::NameNoValue("hidden"));
+    /// assert_eq!(colspan.to_attribute(), attrs[0]);
+    /// ```
+    #[must_use]
+    pub fn as_attribute_ref(&self) -> AttributeRef<'_> {
+        match self {
+            Self::NameNoValue(name) => AttributeRef::NameNoValue(name),
+            Self::NameValue { double_quote, name, value } =>
+                AttributeRef::NameValue { double_quote: *double_quote, name, value },
+        }
+    }
+
+    /// Returns the local name of an attribute, i.e., its name without its
+    /// prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(r##"<use xlink:href="#icon" width="2"/>"##).unwrap();
+    /// let attrs = html.as_tag().unwrap().0.as_attrs();
+    /// assert_eq!(attrs[0].as_local_name(), "href");
+    /// assert_eq!(attrs[1].as_local_name(), "width");
+    /// ```
+    #[must_use]
+    pub fn as_local_name(&self) -> &str {
+        let name = self.as_name();
+        name.split_once(':').map_or(name, |(_, local)| local)
+    }
+
+    /// Returns the name that replaces the name of an attribute in SVG 2, or
+    /// its name if it isn't deprecated.
+    ///
+    /// The `xlink:href` attribute is replaced by `href`, and `xml:lang` by
+    /// `lang`. See also
+    /// [`Html::modernize_attributes`](crate::Html::modernize_attributes).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(r##"<use xlink:href="#icon" xlink:title="Icon"/>"##).unwrap();
+    /// let attrs = html.as_tag().unwrap().0.as_attrs();
+    /// assert_eq!(attrs[0].as_modern_name(), "href");
+    /// assert_eq!(attrs[1].as_modern_name(), "xlink:title");
+    /// ```
+    #[must_use]
+    pub fn as_modern_name(&self) -> &str {
+        let name = self.as_name();
+        attribute_replacement(name).unwrap_or(name)
+    }
+
     /// Returns the name of an attribute
     #[must_use]
-    pub const fn as_name(&self) -> &String {
+    pub fn as_name(&self) -> &str {
         match self {
             Self::NameNoValue(name) | Self::NameValue { name, .. } => name,
         }
     }
 
+    /// Returns the prefix of the name of an attribute, if it has one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html =
+    ///     Html::parse(r#"<svg xmlns:xlink="http://www.w3.org/1999/xlink" xml:lang="en" width="2"/>"#)
+    ///         .unwrap();
+    /// let attrs = html.as_tag().unwrap().0.as_attrs();
+    /// assert_eq!(attrs[0].as_prefix(), Some("xmlns"));
+    /// assert_eq!(attrs[1].as_prefix(), Some("xml"));
+    /// assert_eq!(attrs[2].as_prefix(), None);
+    /// ```
+    #[must_use]
+    pub fn as_prefix(&self) -> Option<&str> {
+        self.as_name().split_once(':').map(|(prefix, _)| prefix)
+    }
+
     /// Returns the value of an attribute
     #[must_use]
     pub const fn as_value(&self) -> Option<&String> {
@@ -77,26 +198,66 @@ impl Attribute {
             Self::NameValue { value, .. } => Some(value),
         }
     }
+
+    /// Renames the attribute.
+    pub(crate) fn rename(&mut self, new_name: &str) {
+        match self {
+            Self::NameNoValue(name) | Self::NameValue { name, .. } => *name = new_name.into(),
+        }
+    }
 }
 
 impl From<String> for Attribute {
     fn from(name: String) -> Self {
-        Self::NameNoValue(name)
+        Self::NameNoValue(name.into())
     }
 }
 
 impl fmt::Display for Attribute {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::NameNoValue(prefix_name) => write!(f, " {prefix_name}"),
-            Self::NameValue { double_quote, name, value } => write!(f, " {name}").and_then(|()| {
+            Self::NameNoValue(prefix_name) =>
+                write!(f, " {}", clean_controls(prefix_name, ControlPolicy::Replace)),
+            Self::NameValue { double_quote, name, value } => {
+                write!(f, " {}", clean_controls(name, ControlPolicy::Replace))?;
                 let del = if *double_quote { '"' } else { '\'' };
-                write!(f, "={del}{value}{del}")
-            }),
+                write!(f, "={del}{}{del}", escape_outside_placeholders(value, &[del]))
+            }
         }
     }
 }
 
+/// Iterator over the names and the values of the attributes of a [`Tag`].
+///
+/// This struct is created by [`Tag::attrs`].
+#[derive(Debug, Clone)]
+pub struct AttrsIter<'tag> {
+    /// Remaining attributes
+    attrs: slice::Iter<'tag, Attribute>,
+}
+
+impl DoubleEndedIterator for AttrsIter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.attrs.next_back().map(name_value)
+    }
+}
+
+impl ExactSizeIterator for AttrsIter<'_> {}
+
+impl FusedIterator for AttrsIter<'_> {}
+
+impl<'tag> Iterator for AttrsIter<'tag> {
+    type Item = (&'tag str, Option<&'tag str>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.attrs.next().map(name_value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.attrs.size_hint()
+    }
+}
+
 /// Tag structure, with its name and attributes
 ///
 /// # Examples
@@ -115,17 +276,25 @@ impl fmt::Display for Attribute {
 /// let value: String = tag.into_attr_value("href").unwrap();
 /// assert_eq!(&value, "https://crates.io");
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[expect(clippy::partial_pub_fields, reason = "identifiers are only set on creation")]
 pub struct Tag {
     /// Attributes of the tag. See [`Attribute`].
     pub attrs: Box<[Attribute]>,
+    /// Stable identifier of the tag. See [`NodeId`].
+    ///
+    /// The identifier isn't serialised: a deserialised tag receives a new
+    /// identifier, as if it was just created.
+    #[cfg_attr(feature = "serde", serde(skip, default = "NodeId::next"))]
+    id: NodeId,
     /// Name of the tag.
     ///
     /// # Examples
     ///
     /// - `<div id="blob">` as name `div`
     /// - `<>` as an empty name
-    pub name: String,
+    pub name: SmallString,
 }
 
 impl Tag {
@@ -161,10 +330,51 @@ impl Tag {
     /// assert_eq!(html.as_tag().unwrap().0.as_name(), "div");
     /// ```
     #[must_use]
-    pub const fn as_name(&self) -> &str {
+    pub fn as_name(&self) -> &str {
         self.name.as_str()
     }
 
+    /// Returns the number of attributes of the tag
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<div id='blob' enabled><p /></div>").unwrap();
+    /// let (div, child) = html.as_tag().unwrap();
+    /// assert_eq!(div.attr_count(), 2);
+    /// assert_eq!(child.as_tag().unwrap().0.attr_count(), 0);
+    /// ```
+    #[must_use]
+    pub const fn attr_count(&self) -> usize {
+        self.attrs.len()
+    }
+
+    /// Returns an iterator over the names and the values of the attributes
+    /// of the tag, in the order of the document.
+    ///
+    /// The value is `None` for the attributes without a value, like
+    /// `disabled` in `<input disabled>`. See [`Tag::as_attrs`] for the
+    /// attributes themselves, and [`Attribute::as_attribute_ref`] for a
+    /// read-only view of each of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(r#"<input type="checkbox" checked>"#).unwrap();
+    /// let (input, _) = html.as_tag().unwrap();
+    ///
+    /// let attrs: Vec<(&str, Option<&str>)> = input.attrs().collect();
+    /// assert_eq!(attrs, [("type", Some("checkbox")), ("checked", None)]);
+    /// ```
+    #[must_use]
+    pub fn attrs(&self) -> AttrsIter<'_> {
+        AttrsIter { attrs: self.attrs.iter() }
+    }
+
     /// Finds the value of the attribute of the given name
     ///
     /// # Returns
@@ -190,6 +400,24 @@ impl Tag {
             .and_then(|attr| attr.as_value())
     }
 
+    /// Returns the stable identifier of the tag
+    ///
+    /// See [`NodeId`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<div><p /></div>").unwrap();
+    /// let (div, child) = html.as_tag().unwrap();
+    /// assert!(div.id() < child.as_tag().unwrap().0.id());
+    /// ```
+    #[must_use]
+    pub const fn id(&self) -> NodeId {
+        self.id
+    }
+
     /// Finds the value of the attribute of the given name
     ///
     /// # Returns
@@ -225,29 +453,157 @@ impl Tag {
     pub fn into_attr_value<T: AsRef<str>>(self, name: T) -> Option<String> {
         self.attrs.into_iter().find(|attr| attr.as_name() == name.as_ref())?.into_value()
     }
+
+    /// Checks if the whitespace of the content of the tag is significant,
+    /// like in `<pre>` or `<textarea>`.
+    ///
+    /// The whitespace of these tags is kept when the texts are collapsed or
+    /// trimmed, and their content is displayed as it is by
+    /// [`Html::to_pretty_string`](crate::Html::to_pretty_string).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<PRE>  a</PRE><p>  b</p>").unwrap();
+    /// let tags = html.iter_tags().map(|(tag, _)| tag.is_preformatted()).collect::<Vec<_>>();
+    /// assert_eq!(tags, [true, false]);
+    /// ```
+    #[must_use]
+    pub fn is_preformatted(&self) -> bool {
+        has_name(&PREFORMATTED_TAGS, self.as_name())
+    }
+
+    /// Checks if the content of the tag is raw text, like in `<script>` and
+    /// `<style>`.
+    ///
+    /// The content of these tags is read as is by the parser, without any
+    /// tag, comment or character reference, and isn't escaped when
+    /// displayed. See also [`Html::as_raw_text`](crate::Html::as_raw_text).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<script>a < b</script><p>c</p>").unwrap();
+    /// let tags = html.iter_tags().map(|(tag, _)| tag.is_raw_text()).collect::<Vec<_>>();
+    /// assert_eq!(tags, [true, false]);
+    /// ```
+    #[must_use]
+    pub fn is_raw_text(&self) -> bool {
+        has_name(&RAW_TEXT_TAGS, self.as_name())
+    }
+
+    /// Checks if the tag is a void element, i.e., a tag that can't have any
+    /// content, like `<br>` or `<img>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse("<img src='a.png' /><p></p>").unwrap();
+    /// let nodes = html.as_vec().unwrap();
+    /// assert!(nodes[0].as_tag().unwrap().0.is_void());
+    /// assert!(!nodes[1].as_tag().unwrap().0.is_void());
+    /// ```
+    #[must_use]
+    pub fn is_void(&self) -> bool {
+        has_name(&VOID_TAGS, self.as_name())
+    }
+
+    /// Gives a new identifier to the tag, as if it was just created.
+    pub(crate) fn renew_id(&mut self) {
+        self.id = NodeId::next();
+    }
+
+    /// Serialises the attributes of the tag, separated by spaces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(r#"<div id="x" class='y' hidden>Content</div>"#).unwrap();
+    /// assert_eq!(html.as_tag().unwrap().0.to_attrs_string(), r#"id="x" class='y' hidden"#);
+    /// ```
+    #[must_use]
+    pub fn to_attrs_string(&self) -> String {
+        let mut attrs = self.attrs.iter().map(ToString::to_string).collect::<String>();
+        attrs.drain(..usize::from(!attrs.is_empty()));
+        attrs
+    }
+
+    /// Serialises the closing tag.
+    ///
+    /// The control characters of the name are replaced by `U+FFFD`, as in
+    /// the opening tag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(r#"<div id="x"><p>Long content</p></div>"#).unwrap();
+    /// assert_eq!(html.as_tag().unwrap().0.to_closing_string(), "</div>");
+    /// ```
+    #[must_use]
+    pub fn to_closing_string(&self) -> String {
+        format!("</{}>", clean_controls(&self.name, ControlPolicy::Replace))
+    }
+
+    /// Serialises the opening tag, without the content and the closing tag.
+    ///
+    /// This is useful to print a tag in a log message, without its whole
+    /// subtree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_filter::*;
+    ///
+    /// let html = Html::parse(r#"<div id="x" class="y"><p>Long content</p></div>"#).unwrap();
+    /// assert_eq!(html.as_tag().unwrap().0.to_opening_string(), r#"<div id="x" class="y">"#);
+    /// ```
+    #[must_use]
+    pub fn to_opening_string(&self) -> String {
+        format!("<{self}>")
+    }
 }
 
-impl From<(String, Box<[Attribute]>)> for Tag {
-    fn from((name, attrs): (String, Box<[Attribute]>)) -> Self {
-        Self { attrs, name }
+impl<T: Into<SmallString>> From<(T, Box<[Attribute]>)> for Tag {
+    fn from((name, attrs): (T, Box<[Attribute]>)) -> Self {
+        Self { attrs, id: NodeId::next(), name: name.into() }
     }
 }
 
+impl PartialEq for Tag {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.attrs == other.attrs
+    }
+}
+
+impl Eq for Tag {}
+
 impl fmt::Display for Tag {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(&self.name)?;
+        f.write_str(&clean_controls(&self.name, ControlPolicy::Replace))?;
         self.attrs.iter().try_for_each(|attr| attr.fmt(f))
     }
 }
 
 /// Builder returns by the parser when run on a tag.
-pub enum TagBuilder {
+///
+/// The names and attributes are borrowed from the input.
+pub enum TagBuilder<'input> {
     /// Closing tag
     ///
     /// # Examples
     ///
     /// `</,>` and `</div>`
-    Close(String),
+    Close(Cow<'input, str>),
     /// Document tag
     ///
     /// # Examples
@@ -259,13 +615,13 @@ pub enum TagBuilder {
         /// # Examples
         ///
         /// From the example above, the name is `doctype`.
-        name: String,
+        name: Cow<'input, str>,
         /// Attribute of the document tag.
         ///
         /// # Examples
         ///
         /// From the example above, the name is `html`.
-        attr: Option<String>,
+        attr: Option<&'input str>,
     },
     /// Opening tag
     ///
@@ -274,7 +630,7 @@ pub enum TagBuilder {
     /// # Examples
     ///
     /// `<div>` and `<>` and `<div id="blob" enabled>`
-    Open(Tag),
+    Open(TagRef<'input>),
     /// Self-closing tag.
     ///
     /// Contains a `/` at the end of the tag declaration.
@@ -282,7 +638,7 @@ pub enum TagBuilder {
     /// # Examples
     ///
     /// `<p />` and `<div id="blob" enabled />`
-    OpenClose(Tag),
+    OpenClose(TagRef<'input>),
     /// Opening block comment
     ///
     /// # Examples
@@ -333,3 +689,22 @@ impl TagType {
         matches!(self, Self::Opened)
     }
 }
+
+/// Returns the name of the attribute that replaces a deprecated namespaced
+/// attribute, if the attribute `name` is deprecated.
+///
+/// See [`Attribute::as_modern_name`].
+pub fn attribute_replacement(name: &str) -> Option<&'static str> {
+    LEGACY_ATTRIBUTES.iter().find(|(legacy, _)| *legacy == name).map(|(_, modern)| *modern)
+}
+
+/// Checks if a list of tag names contains a name, ignoring the ASCII case,
+/// as the html tag names are case-insensitive.
+pub fn has_name(names: &[&str], name: &str) -> bool {
+    names.iter().any(|tag| tag.eq_ignore_ascii_case(name))
+}
+
+/// Returns the name and the value of an attribute, for [`AttrsIter`].
+fn name_value(attr: &Attribute) -> (&str, Option<&str>) {
+    (attr.as_name(), attr.as_value().map(String::as_str))
+}
@@ -0,0 +1,52 @@
+use std::fs::read_to_string;
+
+use html_filter::*;
+
+#[test]
+fn arena_deep() {
+    let depth = 1_000;
+    let input = format!("{}text{}", "<div>".repeat(depth), "</div>".repeat(depth));
+    let html = Html::parse(&input).unwrap_or_else(|err| panic!("{err}"));
+    let arena = html.to_arena();
+    assert_eq!(arena.len(), depth + 1);
+
+    let mut idx = arena.len() - 1;
+    let mut ancestors = 0;
+    while let Some(parent) = arena.parent(idx) {
+        assert_eq!(arena.children(parent), idx..idx + 1);
+        ancestors += 1;
+        idx = parent;
+    }
+    assert_eq!(ancestors, depth);
+    assert_eq!(arena.roots(), 0..1);
+
+    let back = arena.to_html();
+    assert_eq!(back.to_string(), input);
+}
+
+#[test]
+fn arena_empty() {
+    let arena = Html::Empty.to_arena();
+    assert!(arena.is_empty());
+    assert_eq!(arena.roots(), 0..0);
+    assert!(arena.get(0).is_none());
+    assert_eq!(arena.to_html(), Html::Empty);
+}
+
+#[test]
+fn arena_round_trip() {
+    let content = read_to_string("tests/data/index.html").expect("file exists");
+    let html = Html::parse(&content).unwrap_or_else(|err| panic!("{err}"));
+    let arena = html.to_arena();
+    assert_eq!(arena.to_html(), html);
+
+    for (idx, node) in arena.as_nodes().iter().enumerate() {
+        for child in node.children() {
+            assert!(child > idx);
+            assert_eq!(arena.parent(child), Some(idx));
+        }
+    }
+    let roots = arena.roots();
+    assert!(roots.clone().all(|root| arena.parent(root).is_none()));
+    assert!(roots.len() > 1);
+}
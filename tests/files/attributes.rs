@@ -0,0 +1,65 @@
+use core::fmt::Write as _;
+
+use html_filter::*;
+
+fn many_attributes(count: usize) -> String {
+    let mut html = "<div".to_owned();
+    for index in 0..count {
+        write!(html, " data-{index}=\"{index}\"").expect("infallible");
+    }
+    html.push_str(">Text</div>");
+    html
+}
+
+#[test]
+fn attributes_error() {
+    let options = ParseOptions::new().max_attributes_per_tag(100, AttributeOverflow::Error);
+    let err = Html::parse_with(&format!("<p>\n{}</p>", many_attributes(101)), options)
+        .expect_err("Expected too many attributes");
+    assert_eq!(err.kind(), &ParseErrorKind::TooManyAttributes("div".to_owned()));
+    assert_eq!((err.line(), err.column()), (2, 1));
+    assert_eq!(err, "Too many attributes: tag 'div' exceeds the maximum number of attributes.");
+
+    let limit = ParseOptions::new().max_attributes_per_tag(100, AttributeOverflow::Error);
+    assert_eq!(
+        Html::parse_with(&many_attributes(100), limit).map(|html| html.to_string()),
+        Ok(many_attributes(100))
+    );
+}
+
+#[test]
+fn attributes_keep_first() {
+    let mut warnings = vec![];
+    let mut opened = vec![];
+    let html = Html::parse_with(
+        &format!("{}<span a b c></span>", many_attributes(5000)),
+        ParseOptions::new()
+            .max_attributes_per_tag(2, AttributeOverflow::KeepFirstN)
+            .on_tag_open(|tag| opened.push(tag.as_attrs().len()))
+            .on_warning(|warning| warnings.push(warning.to_owned())),
+    )
+    .unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(html, r#"<div data-0="0" data-1="1">Text</div><span a b></span>"#);
+    assert_eq!(opened, [2, 2]);
+    assert_eq!(warnings, [
+        "Dropped the attributes of tag 'div' after the first 2 at byte 0 (line 1, column 1).",
+        "Dropped the attributes of tag 'span' after the first 2 at byte 82795 (line 1, column \
+         82796)."
+    ]);
+}
+
+#[test]
+fn attributes_stream() {
+    let input = many_attributes(10);
+    let mut parser = Parser::with_options(
+        ParseOptions::new().max_attributes_per_tag(3, AttributeOverflow::KeepFirstN),
+    );
+    for chunk in input.as_bytes().chunks(7) {
+        parser.feed_bytes(chunk).unwrap_or_else(|err| panic!("{err}"));
+    }
+    let html = parser.finish().unwrap_or_else(|err| panic!("{err}"));
+    let (tag, _) = html.as_tag().expect("Expected a <div>");
+    assert_eq!(tag.as_attrs().len(), 3);
+    assert_eq!(tag.find_attr_value("data-2").map(String::as_str), Some("2"));
+    assert_eq!(tag.find_attr_value("data-3"), None);
+}
@@ -0,0 +1,32 @@
+use std::fs::read_to_string;
+
+use html_filter::*;
+
+#[test]
+fn audit_index() {
+    let content = read_to_string("tests/data/index.html").expect("file exists");
+    let tree = Html::parse(&content).unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(audit::structure(&tree), []);
+}
+
+#[test]
+fn audit_assembled_tree() {
+    let rows = Html::parse("<tr><td>1</td></tr><td>2</td>").unwrap_or_else(|err| panic!("{err}"));
+    let mut tree = Html::concat(vec![
+        Html::parse("<table><tbody></tbody></table>").unwrap_or_else(|err| panic!("{err}")),
+        Html::parse("<label><input /><select><option>A</option></select></label>")
+            .unwrap_or_else(|err| panic!("{err}")),
+    ]);
+    tree.reparse_node(&[0, 0], &format!("<tbody>{rows}</tbody>"))
+        .unwrap_or_else(|err| panic!("{err}"));
+
+    let warnings = audit::structure(&tree).iter().map(ToString::to_string).collect::<Vec<_>>();
+    assert_eq!(warnings, [
+        "<td> must be directly inside <tr>, not <tbody>. (at [0, 0, 1])",
+        "<input> mustn't be inside the interactive tag <label>. (at [1, 0])",
+        "<select> mustn't be inside the interactive tag <label>. (at [1, 1])",
+    ]);
+
+    tree.reparse_node(&[0, 0, 1], "<tr><td>2</td></tr>").unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(audit::structure(&tree).len(), 2);
+}
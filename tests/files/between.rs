@@ -0,0 +1,33 @@
+use html_filter::*;
+
+const INPUT: &str = "<article>
+<h2>First</h2>
+<p>One</p>
+<div><h2>Nested</h2><p>Inner</p></div>
+<h2>Second</h2>
+<p>Two</p>
+</article>";
+
+fn between(start: &Filter, end: &Filter) -> String {
+    Html::parse(INPUT).expect("Invalid html").select_between(start, end).to_string()
+}
+
+#[test]
+fn between_last_section() {
+    let second = Filter::new().tag_name("h2").text_equals("Second");
+    assert_eq!(between(&second, &Filter::new().tag_name("h2")), "\n<p>Two</p>\n");
+    assert_eq!(between(&Filter::new().tag_name("article"), &Filter::new()), "");
+    assert_eq!(between(&Filter::new().tag_name("table"), &Filter::new()), "");
+}
+
+#[test]
+fn between_same_level() {
+    let heading = Filter::new().tag_name("h2");
+    assert_eq!(
+        between(&heading, &heading),
+        "\n<p>One</p>\n<div><h2>Nested</h2><p>Inner</p></div>\n"
+    );
+    let nested = Filter::new().tag_name("h2").text_equals("Nested");
+    assert_eq!(between(&nested, &heading), "<p>Inner</p>");
+    assert_eq!(between(&heading, &Filter::new().tag_name("div")), "\n<p>One</p>\n");
+}
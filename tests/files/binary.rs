@@ -0,0 +1,76 @@
+use std::fs::read_to_string;
+
+use html_filter::*;
+
+macro_rules! test_binary_errors {
+    ($($name:ident: $bytes:expr => $err:expr)*) => {
+        $(
+            #[test]
+            fn $name() {
+                assert_eq!(Html::from_bin($bytes).expect_err("invalid"), $err);
+            }
+        )*
+    };
+}
+
+test_binary_errors!(
+
+binary_attribute: b"HFBN\x01\x03\x04\x01p\x01\x07" => "Invalid binary tree: unknown attribute kind 7."
+binary_end: b"HFBN\x01\x04\x05ab" => "Invalid binary tree: unexpected end of data."
+binary_kind: b"HFBN\x01\x09\x00" => "Invalid binary tree: unknown node kind 9."
+binary_magic: b"HFB" => "Invalid binary tree: missing magic bytes."
+binary_shorter: b"HFBN\x01\x04\x03\x01ab" => "Invalid binary tree: node is shorter than its declared length."
+binary_trailing: b"HFBN\x01\x02\x00\x00" => "Invalid binary tree: unexpected bytes after the root node."
+binary_utf8: b"HFBN\x01\x04\x02\x01\xff" => "Invalid binary tree: string isn't valid UTF-8."
+binary_varint: b"HFBN\x01\x02\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff" => "Invalid binary tree: varint is too long."
+binary_version: b"HFBN\x02" => "Invalid binary tree: unsupported version 2, expected 1."
+
+);
+
+#[test]
+fn binary_index_round_trip() {
+    let content = read_to_string("tests/data/index.html").expect("index.html exists");
+    let html = Html::parse(&content).unwrap_or_else(|err| panic!("{err}"));
+    let bin = html.to_bin();
+    let loaded = Html::from_bin(&bin).unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(loaded, html);
+    assert_eq!(loaded.to_string(), html.to_string());
+}
+
+#[test]
+fn binary_nodes_round_trip() {
+    let html = Html::Vec(
+        vec![
+            Html::Empty,
+            Html::Comment(" \u{e9} ".to_owned()),
+            Html::Doctype { name: "doctype".to_owned(), attr: None },
+            Html::Tag {
+                tag: Tag::from((
+                    "x".repeat(200),
+                    vec![Attribute::NameNoValue("a".into()), Attribute::NameValue {
+                        double_quote: false,
+                        name: "b".into(),
+                        value: "\"".to_owned(),
+                    }]
+                    .into_boxed_slice(),
+                )),
+                child: Box::new(Html::Text("t".repeat(20_000).into())),
+            },
+        ]
+        .into_boxed_slice(),
+    );
+    assert_eq!(Html::from_bin(&html.to_bin()).unwrap_or_else(|err| panic!("{err}")), html);
+}
+
+#[test]
+fn binary_depth() {
+    let nested = |depth: usize| {
+        (0..depth).fold(Html::text("a"), |child, _| Html::tag("div").child(child).build())
+    };
+    let deep = nested(500);
+    assert_eq!(Html::from_bin(&deep.to_bin()).unwrap_or_else(|err| panic!("{err}")), deep);
+    assert_eq!(
+        Html::from_bin(&nested(512).to_bin()).map(|_| ()),
+        Err("Invalid binary tree: nodes are nested in more than 512 nodes.".to_owned())
+    );
+}
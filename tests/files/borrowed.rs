@@ -0,0 +1,122 @@
+extern crate alloc;
+use alloc::borrow::Cow;
+use std::fs::read_to_string;
+
+use html_filter::*;
+
+const INPUTS: [&str; 23] = [
+    "",
+    "Only text",
+    "<!DOCTYPE html><html lang=\"en\"><head><title>A</title></head><body></body></html>",
+    "<a/b>c",
+    "<a b/c>d</a>",
+    "a--b",
+    "a---b",
+    "<p>a-",
+    "<!-- a --- b -->",
+    "<!-- unclosed - comment",
+    "<!-- a --><p>b</p><!-- c --><!-- d -->",
+    "<p>a<br>b</br>c</p>",
+    "<script>if (a < b) { c--; }</script><p>d</p>",
+    "<style>p > a { color: red; }</style>",
+    "<div><p>x</div>y",
+    "<div id='a' class=\"b c\" hidden data-x=\"'\">z</div>",
+    "</p>",
+    "a-->",
+    "<p",
+    "<a b=c>",
+    "<p>caf\u{e9} \u{1f980}</p>",
+    "<a x y><b z>t</b><br c/></a><p d='e'/>",
+    "<div><div><div>a</div>b</div>c<p>d</p></div>e",
+];
+
+fn assert_same(input: &str) {
+    let borrowed = Html::parse_borrowed(input);
+    let owned = Html::parse(input);
+    assert_eq!(
+        borrowed.as_ref().map(ToString::to_string),
+        owned.as_ref().map(ToString::to_string),
+        "{input}"
+    );
+    assert_eq!(borrowed.map(HtmlRef::into_html), owned, "{input}");
+}
+
+fn owned_strings(html: &HtmlRef<'_>) -> usize {
+    match html {
+        HtmlRef::Comment(Cow::Owned(_)) | HtmlRef::Text(Cow::Owned(_)) => 1,
+        HtmlRef::Tag { child, .. } => owned_strings(child),
+        HtmlRef::Vec(vec) => vec.iter().map(owned_strings).sum(),
+        HtmlRef::Comment(Cow::Borrowed(_))
+        | HtmlRef::Doctype { .. }
+        | HtmlRef::Empty
+        | HtmlRef::Text(Cow::Borrowed(_)) => 0,
+    }
+}
+
+#[test]
+fn borrowed_attr_count() {
+    let html = Html::parse("<a x y='1' z><b>t</b><i w /></a>").expect("valid html");
+    let (outer, child) = html.as_tag().expect("tag");
+    assert_eq!(outer.attr_count(), 3);
+    let counts = child
+        .as_vec()
+        .expect("two children")
+        .iter()
+        .map(|node| node.as_tag().expect("tag").0.attr_count())
+        .collect::<Vec<_>>();
+    assert_eq!(counts, [0, 1]);
+}
+
+#[test]
+fn borrowed_attribute_view() {
+    let input = r#"<div id='a' class="b c" hidden data-x="'" data-y='"'>z</div>"#;
+    let HtmlRef::Tag { tag: tag_ref, .. } = Html::parse_borrowed(input).expect("valid html") else {
+        panic!("expected a tag")
+    };
+    let html = Html::parse(input).expect("valid html");
+    let (tag, _) = html.as_tag().expect("tag");
+    let views = tag.as_attrs().iter().map(Attribute::as_attribute_ref).collect::<Vec<_>>();
+    assert_eq!(views, tag_ref.as_attrs());
+    for (view, attr) in views.iter().zip(tag.as_attrs()) {
+        assert_eq!(view.to_string(), attr.to_string());
+        assert_eq!(&view.to_attribute(), attr);
+    }
+    let pairs = views.iter().map(|view| (view.as_name(), view.as_value())).collect::<Vec<_>>();
+    assert!(tag.attrs().eq(pairs.iter().copied()));
+}
+
+#[test]
+fn borrowed_attribute_view_delimiter() {
+    let html = Html::parse(r#"<a title="say &quot;hi&quot;" alt='it&#39;s'>z</a>"#)
+        .unwrap_or_else(|err| panic!("{err}"));
+    let (tag, _) = html.as_tag().expect("tag");
+    let views = tag.as_attrs().iter().map(|attr| attr.as_attribute_ref().to_string());
+    assert!(views.eq([r#" title="say &quot;hi&quot;""#, " alt='it&#39;s'"]));
+}
+
+#[test]
+fn borrowed_index() {
+    let content = read_to_string("tests/data/index.html").expect("file exists");
+    assert_same(&content);
+    let html = Html::parse_borrowed(&content).expect("valid html");
+    assert_eq!(owned_strings(&html), 0);
+}
+
+#[test]
+fn borrowed_inputs() {
+    for input in INPUTS {
+        assert_same(input);
+    }
+}
+
+#[test]
+fn borrowed_non_contiguous() {
+    let tag_html = Html::parse_borrowed("<a/b>").expect("valid html");
+    let HtmlRef::Tag { tag, .. } = tag_html else { panic!("expected a tag") };
+    assert_eq!(tag.as_name(), "ab");
+
+    let html = Html::parse_borrowed("<p>a<br>b</br>c</p>").expect("valid html");
+    let HtmlRef::Tag { child, .. } = html else { panic!("expected a tag") };
+    let HtmlRef::Vec(nodes) = *child else { panic!("expected nodes") };
+    assert!(matches!(nodes.last(), Some(HtmlRef::Text(Cow::Owned(text))) if text == "bc"));
+}
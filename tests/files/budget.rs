@@ -0,0 +1,86 @@
+use core::sync::atomic::AtomicBool;
+use core::time::Duration;
+use std::fs::read_to_string;
+use std::time::Instant;
+
+use html_filter::*;
+
+fn index() -> String {
+    read_to_string("tests/data/index.html").expect("Missing tests/data/index.html")
+}
+
+#[test]
+fn budget_parse() {
+    let content = index();
+    let past = Instant::now().checked_sub(Duration::from_secs(1)).expect("valid instant");
+    let cancel = AtomicBool::new(true);
+    for (budget, err) in [
+        (Budget::new().deadline(past), BudgetExceeded::Deadline),
+        (Budget::new().cancel(&cancel), BudgetExceeded::Cancelled),
+        (Budget::new().max_nodes(10), BudgetExceeded::Nodes),
+    ] {
+        assert_eq!(
+            Html::parse_with(&content, ParseOptions::new().budget(budget))
+                .map_err(|parse_err| parse_err.kind().clone()),
+            Err(ParseErrorKind::BudgetExceeded(err))
+        );
+    }
+    let budget = Budget::new().timeout(Duration::from_mins(1)).max_nodes(1000);
+    assert_eq!(
+        Html::parse_with(&content, ParseOptions::new().budget(budget)),
+        Html::parse(&content)
+    );
+}
+
+#[test]
+fn budget_filter() {
+    let tree = Html::parse(&index()).unwrap_or_else(|err| panic!("{err}"));
+    let filter = Filter::new().tag_name("li").depth(1);
+    let past = Instant::now().checked_sub(Duration::from_secs(1)).expect("valid instant");
+    assert_eq!(
+        tree.try_to_filtered(&filter, &Budget::new().deadline(past)),
+        Err(BudgetExceeded::Deadline)
+    );
+    assert_eq!(tree.try_to_found(&filter, &Budget::new().max_nodes(5)), Err(BudgetExceeded::Nodes));
+    let budget = Budget::new().timeout(Duration::from_mins(1));
+    assert_eq!(tree.try_to_filtered(&filter, &budget), Ok(tree.to_filtered(&filter)));
+    assert_eq!(tree.try_to_found(&filter, &budget), Ok(tree.to_found(&filter)));
+    assert_eq!(tree.clone().try_find(&filter, &budget), Ok(tree.to_found(&filter)));
+    assert_eq!(tree.clone().try_filter(&filter, &budget), Ok(tree.to_filtered(&filter)));
+    let err = tree.try_filter(&filter, &Budget::new().max_nodes(5)).expect_err("too small");
+    assert_eq!(err.to_string(), "Budget exceeded: maximum number of nodes was reached.");
+    assert_eq!(BudgetExceeded::Cancelled.to_string(), "Budget exceeded: operation was cancelled.");
+}
+
+#[test]
+fn budget_filter_limit() {
+    let list = format!("<ul>{}</ul>", "<li>item</li>".repeat(1000));
+    let tree = Html::parse(&list).unwrap_or_else(|err| panic!("{err}"));
+    let budget = Budget::new().max_nodes(20);
+    let filter = Filter::new().tag_name("li");
+    assert_eq!(tree.try_to_filtered(&filter, &budget), Err(BudgetExceeded::Nodes));
+    assert_eq!(
+        tree.try_to_filtered(&filter.limit(3), &budget),
+        Ok(Html::parse("<li>item</li><li>item</li><li>item</li>")
+            .unwrap_or_else(|err| panic!("{err}")))
+    );
+    assert_eq!(
+        tree.try_to_found(&Filter::new().tag_name("li"), &budget),
+        Ok(Html::parse("<li>item</li>").unwrap_or_else(|err| panic!("{err}")))
+    );
+}
+
+#[test]
+fn budget_filter_linear() {
+    for size in [10, 50, 100] {
+        let deep = format!("{}<p>found</p>{}", "<div>x".repeat(size), "</div>".repeat(size));
+        let tree = Html::parse(&deep).unwrap_or_else(|err| panic!("{err}"));
+        // Each level is a tag, a list of two nodes and a text.
+        let nodes = 3 * size + 2;
+        for depth in [0, 1, 5, size] {
+            let filter = Filter::new().tag_name("p").depth(depth);
+            let budget = Budget::new().max_nodes(2 * nodes);
+            assert_eq!(tree.try_to_filtered(&filter, &budget), Ok(tree.to_filtered(&filter)));
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use html_filter::*;
+
+fn parse_warned(bytes: &[u8]) -> (String, Vec<String>) {
+    let mut warnings = vec![];
+    let html = Html::parse_bytes_with(
+        bytes,
+        ParseOptions::new().on_warning(|warning| warnings.push(warning.to_owned())),
+    )
+    .unwrap_or_else(|err| panic!("{err}"));
+    (html.to_string(), warnings)
+}
+
+fn stream_warned(chunks: &[&[u8]]) -> (String, Vec<String>) {
+    let mut warnings = vec![];
+    let mut parser = Parser::with_options(
+        ParseOptions::new().on_warning(|warning| warnings.push(warning.to_owned())),
+    );
+    for chunk in chunks {
+        parser.feed_bytes(chunk).unwrap_or_else(|err| panic!("{err}"));
+    }
+    let html = parser.finish().unwrap_or_else(|err| panic!("{err}")).to_string();
+    (html, warnings)
+}
+
+#[test]
+fn bytes_error_position() {
+    let err = Html::parse_bytes(b"<p>\xff\xfe</a>").expect_err("Expected an unopened tag");
+    assert_eq!((err.line(), err.column()), (1, 6));
+}
+
+#[test]
+fn bytes_invalid() {
+    let (html, warnings) = parse_warned(b"<p title='\xc0\xaf'>\n\xf8</p>");
+    assert_eq!(html, "<p title='\u{fffd}\u{fffd}'>\n\u{fffd}</p>");
+    assert_eq!(warnings, [
+        "Replaced the invalid UTF-8 sequence [C0] at byte 10 (line 1, column 11) with U+FFFD.",
+        "Replaced the invalid UTF-8 sequence [AF] at byte 11 (line 1, column 12) with U+FFFD.",
+        "Replaced the invalid UTF-8 sequence [F8] at byte 15 (line 2, column 1) with U+FFFD.",
+    ]);
+}
+
+#[test]
+fn bytes_stream_split() {
+    let input = "<p>\u{20ac}\u{1f600} caf\u{e9}</p>".as_bytes();
+    let expected = Html::parse_bytes(input).unwrap_or_else(|err| panic!("{err}")).to_string();
+    for split in 0..=input.len() {
+        let chunks = input.split_at(split);
+        assert_eq!(
+            stream_warned(&<[_; 2]>::from(chunks)),
+            (expected.clone(), vec![]),
+            "split at {split}"
+        );
+    }
+    let bytewise = input.chunks(1).collect::<Vec<_>>();
+    assert_eq!(stream_warned(&bytewise), (expected, vec![]));
+}
+
+#[test]
+fn bytes_surrogate() {
+    let (html, warnings) = parse_warned(b"<p>\xed\xa0\xbd\xed\xb8\x80</p>");
+    assert_eq!(html, format!("<p>{}</p>", "\u{fffd}".repeat(6)));
+    assert_eq!(warnings.len(), 6);
+    assert_eq!(
+        warnings.first().map(String::as_str),
+        Some("Replaced the invalid UTF-8 sequence [ED] at byte 3 (line 1, column 4) with U+FFFD.")
+    );
+}
+
+#[test]
+fn bytes_truncated() {
+    let truncated: &[u8] = b"<p>a</p>\xf0\x9f\x98";
+    let expected = ("<p>a</p>\u{fffd}".to_owned(), vec![
+        "Replaced the invalid UTF-8 sequence [F0 9F 98] at byte 8 (line 1, column 9) with U+FFFD."
+            .to_owned(),
+    ]);
+    assert_eq!(parse_warned(truncated), expected);
+    assert_eq!(stream_warned(&[truncated]), expected);
+}
+
+#[test]
+fn bytes_valid() {
+    let input = "<div class=\"caf\u{e9}\"><p>\u{1f600}</p><!-- \u{20ac} --></div>";
+    let (html, warnings) = parse_warned(input.as_bytes());
+    assert_eq!(html, Html::parse(input).unwrap_or_else(|err| panic!("{err}")).to_string());
+    assert!(warnings.is_empty());
+}
@@ -0,0 +1,130 @@
+use core::iter;
+
+use html_filter::*;
+
+fn parse(input: &str) -> Html {
+    Html::parse(input).unwrap_or_else(|err| panic!("{err}"))
+}
+
+#[test]
+fn changelog_errors() {
+    let mut html = parse("<p>a</p><p>b</p>");
+    let mut log = ChangeLog::new();
+
+    log.apply(&mut html, Edit::Remove { path: vec![] }).expect_err("invalid edit");
+    log.apply(&mut html, Edit::Insert { path: vec![], node: parse("c") })
+        .expect_err("invalid edit");
+    log.apply(&mut html, Edit::Insert { path: vec![3], node: parse("c") })
+        .expect_err("invalid edit");
+    log.apply(&mut html, Edit::Remove { path: vec![0, 0, 0] }).expect_err("invalid edit");
+    log.apply(&mut html, Edit::Replace { path: vec![4], node: parse("c") })
+        .expect_err("invalid edit");
+    assert!(!log.can_undo());
+    assert_eq!(html, "<p>a</p><p>b</p>");
+
+    log.apply(&mut html, Edit::Remove { path: vec![1] }).expect("valid path");
+    let mut other = parse("text");
+    log.undo(&mut other).expect_err("text can't have children");
+    assert!(log.can_undo());
+    assert!(log.undo(&mut html).expect("same tree"));
+    assert!(!log.undo(&mut html).expect("nothing to undo"));
+    assert_eq!(html, "<p>a</p><p>b</p>");
+}
+
+#[test]
+fn changelog_root_vec() {
+    let mut html = parse("<p>a</p><p>b</p>");
+    let mut log = ChangeLog::new();
+
+    log.apply(&mut html, Edit::Remove { path: vec![0] }).expect("valid path");
+    log.apply(&mut html, Edit::Remove { path: vec![0] }).expect("valid path");
+    assert_eq!(html, "");
+    log.apply(&mut html, Edit::Insert { path: vec![0], node: parse("<i>c</i>") })
+        .expect("valid path");
+    log.apply(&mut html, Edit::Insert { path: vec![0, 0], node: parse("<b>d</b>") })
+        .expect("valid path");
+    assert_eq!(html, "<i><b>d</b>c</i>");
+
+    while log.undo(&mut html).expect("same tree") {}
+    assert_eq!(html, "<p>a</p><p>b</p>");
+    assert!(log.can_redo());
+    assert_eq!(log.edits().count(), 0);
+
+    while log.redo(&mut html).expect("same tree") {}
+    assert_eq!(html, "<i><b>d</b>c</i>");
+    assert_eq!(log.edits().count(), 4);
+}
+
+#[test]
+fn changelog_clears_redo() {
+    let mut html = parse("<div><p>a</p></div>");
+    let mut log = ChangeLog::new();
+
+    log.apply(&mut html, Edit::Replace { path: vec![0], node: parse("<p>b</p>") })
+        .expect("valid path");
+    assert!(log.undo(&mut html).expect("same tree"));
+    assert!(log.can_redo());
+
+    log.apply(&mut html, Edit::Replace { path: vec![], node: parse("<span />") })
+        .expect("valid path");
+    assert!(!log.can_redo());
+    assert_eq!(log.edits().collect::<Vec<_>>(), [&Edit::Replace {
+        path: vec![],
+        node: parse("<span />")
+    }]);
+
+    let mut other = parse("<section />");
+    log.replay(&mut other).expect("root exists");
+    assert_eq!(other, html);
+}
+
+#[test]
+fn changelog_nested_vec() {
+    let original = parse("<ul><li>1</li><li>2</li></ul>");
+    let mut html = original.clone();
+    let mut log = ChangeLog::new();
+    let node = parse("<li>a</li><li>b</li>");
+    log.apply(&mut html, Edit::Replace { path: vec![0], node }).expect("valid replace");
+    log.apply(&mut html, Edit::Remove { path: vec![1] }).expect("valid remove");
+    assert_eq!(html, "<ul><li>a</li><li>b</li></ul>");
+    while log.undo(&mut html).expect("valid undo") {}
+    assert_eq!(html, original);
+}
+
+#[test]
+fn changelog_undo_all() {
+    let nodes = [
+        parse("<li>a</li><li>b</li>"),
+        parse("<b>c</b>"),
+        Html::Text("d".into()),
+        Html::Empty,
+        parse("<p><i>e</i>f</p>"),
+    ];
+    let mut seed = 7u64;
+    let mut next = |bound: u64| {
+        seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+        usize::try_from((seed >> 33u64).checked_rem(bound).expect("positive bound"))
+            .expect("small bound")
+    };
+    for _ in 0..200u32 {
+        let original = parse("<ul><li>1</li><li>2<b>3</b></li></ul><p>4</p>");
+        let mut html = original.clone();
+        let mut log = ChangeLog::new();
+        for _ in 0..8u32 {
+            let len = next(3).saturating_add(1);
+            let path = iter::repeat_with(|| next(3)).take(len).collect::<Vec<_>>();
+            let node = nodes.get(next(5)).cloned().unwrap_or_default();
+            let edit = match next(3) {
+                0 => Edit::Insert { path, node },
+                1 => Edit::Remove { path },
+                _ => Edit::Replace { path, node },
+            };
+            drop(log.apply(&mut html, edit));
+        }
+        let edited = html.clone();
+        while log.undo(&mut html).expect("valid undo") {}
+        assert_eq!(html, original, "{log:?}");
+        while log.redo(&mut html).expect("valid redo") {}
+        assert_eq!(html, edited, "{log:?}");
+    }
+}
@@ -0,0 +1,53 @@
+use html_filter::*;
+
+/// Builds an attribute with a value.
+fn value(name: &str, value: &str) -> Attribute {
+    Attribute::NameValue { double_quote: true, name: name.into(), value: value.to_owned() }
+}
+
+#[test]
+fn checked_errors() {
+    assert_eq!(Tag::try_new("", []), Err(TagError::EmptyTagName));
+    assert_eq!(Tag::try_new("1p", []), Err(TagError::InvalidTagName('1')));
+    assert_eq!(Tag::try_new("svg:rect", []), Err(TagError::InvalidTagName(':')));
+    assert_eq!(Tag::try_new("p>", []), Err(TagError::InvalidTagName('>')));
+    assert_eq!(Tag::try_new("p", [value("", "a")]), Err(TagError::EmptyAttributeName));
+    let err = Tag::try_new("p", [value("a=b", "c")]).expect_err("invalid attribute name");
+    assert_eq!(err, TagError::InvalidAttributeName { character: '=', name: "a=b".to_owned() });
+    assert_eq!(err.to_string(), "Invalid character '=' in attribute name 'a=b'.");
+    let duplicate = Tag::try_new("p", [value("id", "a"), value("id", "b")]).expect_err("duplicate");
+    assert_eq!(duplicate.to_string(), "Duplicate attribute 'id'.");
+}
+
+#[test]
+fn checked_keep_first() {
+    let tag = Tag::try_new_with(
+        "p",
+        [value("id", "a"), value("class", "b"), value("id", "c")],
+        DuplicateAttributes::KeepFirst,
+    )
+    .unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(tag.find_attr_value("id").map(String::as_str), Some("a"));
+    assert_eq!(tag.attr_count(), 2);
+}
+
+#[test]
+fn checked_round_trip() {
+    let tag = Tag::try_new("custom-element", [
+        value("xlink:href", "#a"),
+        value("title", "Say \"hi\" & <bye>"),
+        Attribute::from("hidden".to_owned()),
+    ])
+    .unwrap_or_else(|err| panic!("{err}"));
+    let html = Html::Tag { tag, child: Box::new(Html::Text("Content".into())) };
+    let rendered = html.to_string();
+    let parsed = Html::parse(&rendered).unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(parsed, rendered);
+    assert_eq!(
+        parsed
+            .as_tag()
+            .and_then(|(parsed_tag, _)| parsed_tag.find_attr_value("title"))
+            .map(String::as_str),
+        Some("Say &quot;hi&quot; & <bye>")
+    );
+}
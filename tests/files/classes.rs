@@ -0,0 +1,55 @@
+use html_filter::*;
+
+macro_rules! test_classes {
+    ($($name:ident: $filter:expr => $expect:expr)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let html = Html::parse(INPUT).unwrap_or_else(|err| panic!("{err}"));
+                assert_eq!(html.to_filtered(&$filter), $expect);
+            }
+        )*
+    };
+}
+
+/// Classes mangled by different build tools.
+const INPUT: &str = concat!(
+    r#"<p class="card">1</p>"#,
+    r#"<p class="card__x1y2z">2</p>"#,
+    r#"<p class="card--AbCd">3</p>"#,
+    r#"<p class="card-title">4</p>"#,
+    r#"<p class="card_a1">5</p>"#,
+    r#"<p class="cards">6</p>"#,
+    r#"<p class="old card-9f8e7">7</p>"#,
+    r#"<p>8</p>"#,
+);
+
+test_classes!(
+
+class: Filter::new().class("card") =>
+r#"<p class="card">1</p>"#
+
+class_word: Filter::new().class("old") =>
+r#"<p class="old card-9f8e7">7</p>"#
+
+except_class: Filter::new().tag_name("p").except_class("card").except_class("old") =>
+r#"<p class="card__x1y2z">2</p><p class="card--AbCd">3</p><p class="card-title">4</p><p class="card_a1">5</p><p class="cards">6</p><p>8</p>"#
+
+hashed: Filter::new().class_matches_hashed("card") =>
+r#"<p class="card">1</p><p class="card__x1y2z">2</p><p class="card--AbCd">3</p><p class="old card-9f8e7">7</p>"#
+
+prefix: Filter::new().class_matches_prefix("card") =>
+r#"<p class="card">1</p><p class="card__x1y2z">2</p><p class="card--AbCd">3</p><p class="card-title">4</p><p class="card_a1">5</p><p class="cards">6</p><p class="old card-9f8e7">7</p>"#
+
+prefix_none: Filter::new().class_matches_prefix("ol") =>
+r#"<p class="old card-9f8e7">7</p>"#
+
+);
+
+#[test]
+fn class_whitespace() {
+    let html = Html::parse("<p class=\"\tcard\n shadow \">1</p><p class=\"card-shadow\">2</p>")
+        .unwrap_or_else(|err| panic!("{err}"));
+    let filter = Filter::new().class("card").class("shadow");
+    assert_eq!(html.to_filtered(&filter), "<p class=\"\tcard\n shadow \">1</p>");
+}
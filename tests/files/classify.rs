@@ -0,0 +1,83 @@
+use std::fs::read_to_string;
+
+use html_filter::*;
+
+fn classify(input: &str) -> Vec<(&str, TokenClass)> {
+    classify_tokens(input)
+        .into_iter()
+        .map(|(range, class)| (input.get(range).expect("Invalid range"), class))
+        .collect()
+}
+
+#[test]
+fn classify_attributes() {
+    assert_eq!(classify("<input disabled value='a &quot;b&quot; &unknown;'/>"), [
+        ("<", TokenClass::Delimiter),
+        ("input", TokenClass::TagName),
+        (" ", TokenClass::Delimiter),
+        ("disabled", TokenClass::AttributeName),
+        (" ", TokenClass::Delimiter),
+        ("value", TokenClass::AttributeName),
+        ("='", TokenClass::Delimiter),
+        ("a ", TokenClass::AttributeValue),
+        ("&quot;", TokenClass::Entity),
+        ("b", TokenClass::AttributeValue),
+        ("&quot;", TokenClass::Entity),
+        (" &unknown;", TokenClass::AttributeValue),
+        ("'/>", TokenClass::Delimiter),
+    ]);
+}
+
+#[test]
+fn classify_index() {
+    let content = read_to_string("tests/data/index.html").expect("file exists");
+    let classes = classify_tokens(&content);
+    let mut end = 0;
+    let mut previous = None;
+    for (range, class) in &classes {
+        assert_eq!(range.start, end);
+        assert!(!range.is_empty());
+        assert_ne!(previous, Some(*class));
+        end = range.end;
+        previous = Some(*class);
+    }
+    assert_eq!(end, content.len());
+    assert!(classes.iter().all(|(_, class)| *class != TokenClass::Invalid));
+}
+
+#[test]
+fn classify_invalid() {
+    assert_eq!(classify("<p>a</p><a:b>c</a:b>"), [
+        ("<", TokenClass::Delimiter),
+        ("p", TokenClass::TagName),
+        (">", TokenClass::Delimiter),
+        ("a", TokenClass::Text),
+        ("</", TokenClass::Delimiter),
+        ("p", TokenClass::TagName),
+        (">", TokenClass::Delimiter),
+        ("<a:b>c</a:b>", TokenClass::Invalid),
+    ]);
+}
+
+#[test]
+fn classify_nodes() {
+    assert_eq!(
+        classify("<!DOCTYPE html>\n<!-- a &amp; b --><script>a &amp;&& b</script>&#x27;&"),
+        [
+            ("<!DOCTYPE html>", TokenClass::Doctype),
+            ("\n", TokenClass::Text),
+            ("<!-- a &amp; b -->", TokenClass::Comment),
+            ("<", TokenClass::Delimiter),
+            ("script", TokenClass::TagName),
+            (">", TokenClass::Delimiter),
+            ("a &amp;&& b", TokenClass::Text),
+            ("</", TokenClass::Delimiter),
+            ("script", TokenClass::TagName),
+            (">", TokenClass::Delimiter),
+            ("&#x27;", TokenClass::Entity),
+            ("&", TokenClass::Text),
+        ]
+    );
+    assert_eq!(classify("<SCRIPT>&amp;</SCRIPT>").get(3), Some(&("&amp;", TokenClass::Text)));
+    assert_eq!(classify(""), []);
+}
@@ -0,0 +1,63 @@
+use html_filter::*;
+
+const HTML: &str = r#"<nav><a href="/">Home</a><a>Top</a></nav><main><img src="x.png" alt="x" /><img alt="y" /><p class="note">Text</p></main>"#;
+
+/// Returns the names of the tags that the filter matches in [`HTML`].
+fn matched(filter: &Filter) -> Vec<String> {
+    let html = Html::parse(HTML).unwrap_or_else(|err| panic!("{err}"));
+    html.find_all(filter)
+        .into_iter()
+        .filter_map(|node| node.as_tag().map(|(tag, _)| tag.as_name().to_owned()))
+        .collect()
+}
+
+#[test]
+fn combine_and() {
+    let filter = Filter::new()
+        .attribute_present("alt")
+        .and(Filter::new().attribute_present("src").or(Filter::new().tag_name("p")));
+    assert_eq!(matched(&filter), ["img"]);
+    assert_eq!(matched(&Filter::new().and(Filter::new().tag_name("a"))), ["a", "a"]);
+}
+
+#[test]
+fn combine_groups() {
+    let links = Filter::new().tag_name("a").attribute_present("href");
+    let images = Filter::new().tag_name("img").attribute_present("src");
+    assert_eq!(matched(&Filter::new().or(links).or(images)), ["a", "img"]);
+    assert_eq!(matched(&Filter::new().tag_name("a").tag_name("img").attribute_present("src")), [
+        "img"
+    ]);
+}
+
+#[test]
+fn combine_not() {
+    assert_eq!(matched(&Filter::new().not(Filter::new().tag_name("img"))), [
+        "nav", "a", "a", "main", "p"
+    ]);
+    assert_eq!(
+        matched(&Filter::new().tag_name("img").not(Filter::new().attribute_present("src"))),
+        ["img"]
+    );
+    assert!(matched(&Filter::new().tag_name("p").not(Filter::new().class("note"))).is_empty());
+}
+
+#[test]
+fn combine_or() {
+    let filter =
+        Filter::new().tag_name("p").or(Filter::new().tag_name("a").attribute_present("href"));
+    assert_eq!(matched(&filter), ["a", "p"]);
+    assert!(matched(&Filter::new().or(Filter::new().tag_name("table"))).is_empty());
+}
+
+#[test]
+fn combine_stats() {
+    let html = Html::parse(HTML).unwrap_or_else(|err| panic!("{err}"));
+    let stats =
+        Filter::new().tag_name("img").not(Filter::new().attribute_present("src")).profile(&html);
+    assert_eq!(stats.matched(), 1);
+    assert_eq!(
+        stats.as_rules().last().map(ToString::to_string).as_deref(),
+        Some("not filter 1: 6 accepted, 1 rejected")
+    );
+}
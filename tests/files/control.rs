@@ -0,0 +1,109 @@
+use html_filter::*;
+
+const INPUT: &str = "<p title='a\0b'>c\x1bd<!--e\x7ff--></p><script>g\0h</script>";
+
+fn parse(policy: ControlPolicy) -> Result<Html, ParseError> {
+    Html::parse_with(INPUT, ParseOptions::new().control_policy(policy))
+}
+
+#[test]
+fn control_decoded() {
+    let options = ParseOptions::new().decode_entities(true).control_policy(ControlPolicy::Strip);
+    let html =
+        Html::parse_with("<p>a&#x1;b&#0;c</p>", options).unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(html, "<p>ab\u{fffd}c</p>");
+}
+
+#[test]
+fn control_error() {
+    let err = parse(ControlPolicy::Error).expect_err("Expected a control character");
+    assert_eq!(err.kind(), &ParseErrorKind::ControlCharacter('\0'));
+    assert_eq!((err.line(), err.column(), err.offset()), (1, 12, 11));
+    assert_eq!(err, "Invalid control character U+0000.");
+
+    let mut parser = Parser::with_options(ParseOptions::new().control_policy(ControlPolicy::Error));
+    parser.feed("<p>\n  Valid\n").expect("No control character");
+    let chunk_err = parser.feed("  a\x1b</p>").expect_err("Expected a control character");
+    assert_eq!((chunk_err.line(), chunk_err.column(), chunk_err.character()), (3, 4, Some('\x1b')));
+
+    let whitespace = "<p>\t\n\r\x0c</p>";
+    assert_eq!(
+        Html::parse_with(whitespace, ParseOptions::new().control_policy(ControlPolicy::Error))
+            .map(|html| html.to_string()),
+        Ok(whitespace.to_owned())
+    );
+}
+
+#[test]
+fn control_hooks() {
+    let mut texts = vec![];
+    let mut titles = vec![];
+    parse_with_hooks(&mut texts, &mut titles);
+    assert_eq!(texts, ["c\u{fffd}d", "g\u{fffd}h"]);
+    assert_eq!(titles, ["a\u{fffd}b"]);
+}
+
+#[test]
+fn control_names() {
+    let input = "<!doc\0type ht\x01ml><a\x02b da\x03ta-x='y' hid\x04den>c</a\x02b>";
+    let expected = "<!doc\u{fffd}type ht\u{fffd}ml><a\u{fffd}b da\u{fffd}ta-x='y' \
+                    hid\u{fffd}den>c</a\u{fffd}b>";
+    let html = Html::parse(input).unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(html, expected);
+    assert_eq!(
+        Html::parse_borrowed(input).map(|borrowed| borrowed.to_string()),
+        Ok(expected.to_owned())
+    );
+    let (tag, _) = html.as_vec().and_then(|nodes| nodes.last()?.as_tag()).expect("Expected a tag");
+    assert_eq!(tag.to_closing_string(), "</a\u{fffd}b>");
+    assert!(
+        !html
+            .to_pretty_string(&PrettyOptions::default())
+            .contains(|ch: char| ch.is_control() && ch != '\n')
+    );
+}
+
+#[test]
+fn control_keep() {
+    let html = parse(ControlPolicy::Keep).unwrap_or_else(|err| panic!("{err}"));
+    let (tag, child) =
+        html.as_vec().and_then(|nodes| nodes.first()?.as_tag()).expect("Expected a <p>");
+    assert_eq!(tag.find_attr_value("title").map(String::as_str), Some("a\0b"));
+    assert_eq!(child.as_vec().and_then(|nodes| nodes.first()?.as_text()), Some("c\x1bd"));
+    assert_eq!(
+        html,
+        "<p title='a&#x00;b'>c&#x1B;d<!--e\u{fffd}f--></p><script>g\u{fffd}h</script>"
+    );
+    assert_eq!(
+        Html::parse_borrowed(INPUT).map(|borrowed| borrowed.to_string()),
+        Ok(html.to_string())
+    );
+}
+
+#[test]
+fn control_replace() {
+    assert_eq!(
+        parse(ControlPolicy::Replace).map(|html| html.to_string()),
+        Ok("<p title='a\u{fffd}b'>c\u{fffd}d<!--e\u{fffd}f--></p><script>g\u{fffd}h</script>"
+            .to_owned())
+    );
+}
+
+#[test]
+fn control_strip() {
+    assert_eq!(
+        parse(ControlPolicy::Strip).map(|html| html.to_string()),
+        Ok("<p title='ab'>cd<!--ef--></p><script>gh</script>".to_owned())
+    );
+}
+
+fn parse_with_hooks(texts: &mut Vec<String>, titles: &mut Vec<String>) {
+    Html::parse_with(
+        INPUT,
+        ParseOptions::new()
+            .control_policy(ControlPolicy::Replace)
+            .on_text(|text| texts.push(text.to_owned()))
+            .on_tag_open(|tag| titles.extend(tag.find_attr_value("title").cloned())),
+    )
+    .unwrap_or_else(|err| panic!("{err}"));
+}
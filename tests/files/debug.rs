@@ -0,0 +1,57 @@
+use html_filter::debug::*;
+
+fn outcomes(report: &Replay) -> Vec<(Stage, Outcome)> {
+    report.stages().iter().map(|result| (result.stage(), result.outcome().clone())).collect()
+}
+
+#[test]
+fn replay_comment_first() {
+    // The parser used to panic on a comment directly followed by a tag.
+    let report = replay(b"<!-- comment --><p>Text</p>");
+    assert!(report.is_ok(), "{report}");
+    assert_eq!(report.output(), Some("<p>Text</p>"));
+    assert_eq!(report, replay(b"<!-- comment --><p>Text</p>"));
+}
+
+#[test]
+fn replay_failed() {
+    let report = replay(b"<ul><li>Item</lu></ul>");
+    assert!(!report.is_ok());
+    assert_eq!(report.output(), None);
+    let failed = Outcome::Failed(
+        "Invalid closing tag: Found closing tag for 'lu' but it isn't open.".to_owned(),
+    );
+    assert_eq!(outcomes(&report), [
+        (Stage::Parse, failed),
+        (Stage::Filter, Outcome::Skipped),
+        (Stage::Serialize, Outcome::Skipped),
+        (Stage::Reparse, Outcome::Skipped),
+    ]);
+    assert_eq!(report.failure().map(StageResult::stage), Some(Stage::Parse));
+}
+
+#[test]
+fn replay_invalid_utf8() {
+    let report = replay(b"<p>a\xffb</p>");
+    assert_eq!(report.input(), "<p>a\u{fffd}b</p>");
+    assert_eq!(report.output(), Some("<p>a\u{fffd}b</p>"));
+}
+
+#[test]
+fn replay_passed() {
+    let input = b"<!DOCTYPE html><main><p id='b'>c<!-- a --><br>d</p></main>";
+    let report = replay(input);
+    assert!(report.is_ok());
+    assert_eq!(report.failure(), None);
+    assert_eq!(report.output(), Some("<!DOCTYPE html><main><p id='b'>c<br>d</p></main>"));
+    assert!(report.stages().iter().all(|result| result.outcome() == &Outcome::Passed));
+    assert_eq!(
+        report.to_string(),
+        "input: \"<!DOCTYPE html><main><p id=\\'b\\'>c<!-- a --><br>d</p></main>\"
+parse: passed
+filter: passed
+serialize: passed
+reparse: passed
+output: \"<!DOCTYPE html><main><p id=\\'b\\'>c<br>d</p></main>\""
+    );
+}
@@ -0,0 +1,38 @@
+use html_filter::*;
+
+macro_rules! test_defaults {
+    ($($name:ident: $html:expr => $expect:expr)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let defaults = DefaultAttributes::new()
+                    .add_if("a", ("target", "_blank"), "rel", "noopener")
+                    .add("img", "loading", "lazy")
+                    .add("img", "decoding", "async");
+                let html = Html::parse($html).unwrap_or_else(|err| panic!("{err}"));
+                assert_eq!(defaults.serialize(&html), $expect);
+            }
+        )*
+    };
+}
+
+test_defaults!(
+
+defaults_condition: r#"<a target="_BLANK">a</a><a target="_self">b</a><a>c</a>"# =>
+r#"<a target="_BLANK" rel="noopener">a</a><a target="_self">b</a><a>c</a>"#
+
+defaults_existing: r#"<a target="_blank" rel="nofollow">a</a><img loading="eager" decoding />"# =>
+r#"<a target="_blank" rel="nofollow">a</a><img loading="eager" decoding>"#
+
+defaults_nested: "<div><p><img src='a.png' /></p></div>" =>
+r#"<div><p><img src='a.png' loading="lazy" decoding="async"></p></div>"#
+
+);
+
+#[test]
+fn defaults_serialize_keeps_tree() {
+    let html = Html::parse("<img />").unwrap_or_else(|err| panic!("{err}"));
+    let defaults = DefaultAttributes::new().add("img", "alt", "");
+    assert_eq!(defaults.serialize(&html), r#"<img alt="">"#);
+    assert_eq!(html, "<img>");
+}
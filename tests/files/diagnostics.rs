@@ -0,0 +1,89 @@
+use html_filter::*;
+
+/// Returns the diagnostics reported on the input by
+/// [`Html::parse_with_diagnostics`].
+fn diagnostics(input: &str) -> Vec<Diagnostic> {
+    Html::parse_with_diagnostics(input, ParseOptions::new()).unwrap_or_else(|err| panic!("{err}")).1
+}
+
+#[test]
+fn diagnostics_callback() {
+    let mut forwarded = vec![];
+    let (_, warnings) = Html::parse_with_diagnostics(
+        "<p><br></br>",
+        ParseOptions::new().on_warning(|warning| forwarded.push(warning.to_owned())),
+    )
+    .unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(warnings.len(), 2);
+    assert_eq!(forwarded, warnings.iter().map(ToString::to_string).collect::<Vec<_>>());
+}
+
+#[test]
+fn diagnostics_comment() {
+    assert_eq!(diagnostics("a<!-- b\nc"), [
+        "Closed the unterminated comment at the end of the input at byte 9 (line 2, column 2)."
+    ]);
+}
+
+#[test]
+fn diagnostics_duplicates() {
+    assert_eq!(diagnostics(r#"<input a b="1" a="2" a/>"#), ["Duplicate attribute 'a' in tag \
+                                                             'input' at byte 15 (line 1, column \
+                                                             16)."]);
+    assert!(diagnostics(r#"<p a="1"></p><p a="2"></p>"#).is_empty());
+}
+
+#[test]
+fn diagnostics_escaping() {
+    let (_, warnings) = Html::parse_with_diagnostics(
+        r#"<img alt="Q&A" alt>"#,
+        ParseOptions::new().attribute_escaping(AttributeEscaping::Warn),
+    )
+    .unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(warnings, [
+        "Unescaped '&' in the value of attribute 'alt' at byte 11 (line 1, column 12).",
+        "Duplicate attribute 'alt' in tag 'img' at byte 15 (line 1, column 16).",
+    ]);
+}
+
+#[test]
+fn diagnostics_kinds() {
+    let found = diagnostics("<p>\n</br><b>a</p>");
+    let kinds = found.iter().map(Diagnostic::kind).collect::<Vec<_>>();
+    assert_eq!(kinds, [
+        &DiagnosticKind::VoidClosed("br".to_owned()),
+        &DiagnosticKind::ClosedByParent { closing: "p".to_owned(), tag: "b".to_owned() },
+    ]);
+    let positions = found
+        .iter()
+        .map(|diagnostic| (diagnostic.offset(), diagnostic.line(), diagnostic.column()))
+        .collect::<Vec<_>>();
+    assert_eq!(positions, [(4, 2, 1), (13, 2, 10)]);
+}
+
+#[test]
+fn diagnostics_parse_with() {
+    let mut count = 0u32;
+    Html::parse_with("<div><p>a</div><!--", ParseOptions::new().on_warning(|_| count += 1))
+        .unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn diagnostics_unclosed() {
+    assert_eq!(diagnostics("<ul><li><b>a</ul>\n<div><p>"), [
+        "Closed the unclosed tag 'b' at the closing tag of 'ul' at byte 12 (line 1, column 13).",
+        "Closed the unclosed tag 'li' at the closing tag of 'ul' at byte 12 (line 1, column 13).",
+        "Closed the unclosed tag 'p' at the end of the input at byte 26 (line 2, column 9).",
+        "Closed the unclosed tag 'div' at the end of the input at byte 26 (line 2, column 9).",
+    ]);
+}
+
+#[test]
+fn diagnostics_valid() {
+    let input = "<div><img src='a'><br/><p>Text</p></div>";
+    let (html, warnings) = Html::parse_with_diagnostics(input, ParseOptions::new())
+        .unwrap_or_else(|err| panic!("{err}"));
+    assert!(warnings.is_empty());
+    assert_eq!(html, Html::parse(input).unwrap_or_else(|err| panic!("{err}")));
+}
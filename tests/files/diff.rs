@@ -0,0 +1,43 @@
+use html_filter::*;
+
+/// Returns the serialisations of the nodes.
+fn strings(nodes: &[NodeRef<'_>]) -> Vec<String> {
+    nodes.iter().map(|node| node.as_html().to_string()).collect()
+}
+
+#[test]
+fn diff_duplicates() {
+    let old = Html::parse("<p>a</p><p>a</p><p>b</p>").unwrap_or_else(|err| panic!("{err}"));
+    let new =
+        Html::parse("<div><p>a</p></div><p>b</p><p>b</p>").unwrap_or_else(|err| panic!("{err}"));
+    let diff = filter_diff(&old, &new, &Filter::new().tag_name("p"));
+    assert_eq!(strings(diff.as_removed()), ["<p>a</p>"]);
+    assert_eq!(strings(diff.as_added()), ["<p>b</p>"]);
+    let kept = diff.as_kept().iter().map(|(old_node, new_node)| {
+        assert_eq!(old_node.as_html(), new_node.as_html());
+        let parent = new_node.parent()?;
+        parent.as_tag().map(|tag| tag.as_name().to_owned())
+    });
+    assert_eq!(kept.collect::<Vec<_>>(), [Some("div".to_owned()), None]);
+}
+
+#[test]
+fn diff_attributes() {
+    let old = Html::parse(r#"<a href="/a">A</a><a href="/b">B</a>"#)
+        .unwrap_or_else(|err| panic!("{err}"));
+    let new = Html::parse(r#"<a href="/a2">A</a><a href="/b">B</a>"#)
+        .unwrap_or_else(|err| panic!("{err}"));
+    let diff = filter_diff(&old, &new, &Filter::new().tag_name("a"));
+    assert_eq!(strings(diff.as_removed()), [r#"<a href="/a">A</a>"#]);
+    assert_eq!(strings(diff.as_added()), [r#"<a href="/a2">A</a>"#]);
+}
+
+#[test]
+fn diff_unchanged() {
+    let old = Html::parse("<ul><li>1</li><li>2</li></ul>").unwrap_or_else(|err| panic!("{err}"));
+    let new = Html::parse("<ol><li>2</li><li>1</li></ol>").unwrap_or_else(|err| panic!("{err}"));
+    let diff = filter_diff(&old, &new, &Filter::new().tag_name("li"));
+    assert!(diff.is_unchanged());
+    assert_eq!(diff.as_kept().len(), 2);
+    assert!(!filter_diff(&old, &new, &Filter::new().tag_name("ul")).is_unchanged());
+}
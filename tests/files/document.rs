@@ -0,0 +1,44 @@
+use std::fs::read_to_string;
+
+use html_filter::*;
+
+#[test]
+fn document_index() {
+    let content = read_to_string("tests/data/index.html").expect("file exists");
+    let mut tree = Html::parse(&content).unwrap_or_else(|err| panic!("{err}"));
+    let original = tree.clone();
+
+    let head = tree.head().expect("index has a head").to_string();
+    assert!(head.starts_with("<head>") && head.contains("<title>Test HTML</title>"), "{head}");
+    assert!(tree.body().is_some_and(|body| body.to_string().contains("<h1>Test Page</h1>")));
+
+    tree.head_mut();
+    tree.body_mut();
+    assert_eq!(tree, original);
+}
+
+#[test]
+fn document_create_both() {
+    let mut tree =
+        Html::parse("<!DOCTYPE html><!-- c --><p>1</p>text").unwrap_or_else(|err| panic!("{err}"));
+    tree.body_mut();
+    tree.head_mut();
+    assert_eq!(tree, "<!DOCTYPE html><head></head><body><!-- c --><p>1</p>text</body>");
+}
+
+#[test]
+fn document_head_helpers() {
+    let content = read_to_string("tests/data/index.html").expect("file exists");
+    let mut tree = Html::parse(&content).unwrap_or_else(|err| panic!("{err}"));
+
+    tree.set_title("New title");
+    tree.set_meta("viewport", "width=500");
+    tree.add_stylesheet("main.css");
+
+    let head = tree.head().expect("index has a head").to_string();
+    assert!(head.contains("<title>New title</title>"), "{head}");
+    assert!(!head.contains("Test HTML"), "{head}");
+    assert!(head.contains(r#"<meta name="viewport" content="width=500">"#), "{head}");
+    assert!(head.ends_with(r#"<link rel="stylesheet" href="main.css"></head>"#), "{head}");
+    assert_eq!(tree.to_string().matches("<head>").count(), 1);
+}
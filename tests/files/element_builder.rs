@@ -0,0 +1,62 @@
+use html_filter::*;
+
+#[test]
+fn element_builder_comment() {
+    for content in ["a --> b", "a--", "--!>", ">", "->", "<!-- a"] {
+        assert_eq!(Html::comment(content), Err(TagError::InvalidComment(content.to_owned())));
+    }
+    for content in ["", " a > b ", "a-b", "a-", "<!- a"] {
+        let comment = Html::comment(content).unwrap_or_else(|err| panic!("{err}"));
+        assert_eq!(Html::parse(&comment.to_string()), Ok(comment));
+    }
+}
+
+#[test]
+fn element_builder_nested() {
+    let built = Html::tag("nav")
+        .attr("class", "top")
+        .child(Html::comment(" links ").unwrap_or_else(|err| panic!("{err}")))
+        .child(Html::tag("ul").children([("/", "Home"), ("/blog", "Blog")].map(|(href, name)| {
+            Html::tag("li").child(Html::tag("a").attr("href", href).child(Html::text(name)))
+        })))
+        .child(Html::tag("input").attr("type", "search").attr_flag("disabled"))
+        .build();
+    let parsed = Html::parse(
+        r#"<nav class="top"><!-- links --><ul><li><a href="/">Home</a></li><li><a href="/blog">Blog</a></li></ul><input type="search" disabled></nav>"#,
+    )
+    .unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(built, parsed);
+    assert_eq!(Html::parse(&built.to_string()), Ok(parsed));
+}
+
+#[test]
+fn element_builder_splice() {
+    let html =
+        Html::parse("<ul><li>One</li><li>Two</li></ul>").unwrap_or_else(|err| panic!("{err}"));
+    let items = html.find_all(&Filter::new().tag_name("li")).into_iter().cloned();
+    let spliced = Html::tag("ol")
+        .children(items)
+        .child(Html::tag("li").child(Html::text("Three")))
+        .child(Html::Empty)
+        .build();
+    assert_eq!(spliced, "<ol><li>One</li><li>Two</li><li>Three</li></ol>");
+    let (_, child) = spliced.as_tag().expect("a tag");
+    assert_eq!(child.as_vec().map(<[Html]>::len), Some(3));
+
+    let empty = Html::tag("p").child(Html::Empty).build();
+    assert_eq!(empty.as_tag().map(|(_, content)| content.clone()), Some(Html::Empty));
+    let text = Html::tag("p").child(Html::text("a <b> c")).build();
+    assert_eq!(text, "<p>a &lt;b> c</p>");
+    assert_eq!(
+        Html::parse(&text.to_string()).map(|reparsed| reparsed.as_tag().is_some()),
+        Ok(true)
+    );
+}
+
+#[test]
+fn element_builder_try_build() {
+    assert_eq!(Html::tag("my tag").try_build(), Err(TagError::InvalidTagName(' ')));
+    assert_eq!(Html::tag("p").attr("", "a").try_build(), Err(TagError::EmptyAttributeName));
+    let checked = Html::tag("p").attr("id", "a").child(Html::text("b")).try_build();
+    assert_eq!(checked, Ok(Html::tag("p").attr("id", "a").child(Html::text("b")).into()));
+}
@@ -0,0 +1,117 @@
+use std::fs::read_to_string;
+
+use html_filter::*;
+
+fn decoded(input: &str) -> Html {
+    Html::parse_with(input, ParseOptions::new().decode_entities(true))
+        .unwrap_or_else(|err| panic!("{err}"))
+}
+
+#[test]
+fn attribute_values() {
+    let html = decoded(r#"<a href="/search?q=a&amp;b=c" title='&quot;&#39;' hidden>link</a>"#);
+    let (tag, _) = html.as_tag().expect("Expected a tag");
+    assert_eq!(tag.find_attr_value("href").expect("Missing href"), "/search?q=a&b=c");
+    assert_eq!(tag.find_attr_value("title").expect("Missing title"), "\"'");
+    assert_eq!(html, r#"<a href="/search?q=a&b=c" title='"&#39;' hidden>link</a>"#);
+}
+
+#[test]
+fn decode_helper() {
+    assert_eq!(decode_entities("no reference"), "no reference");
+    assert_eq!(decode_entities("&AMP; &Amp; &amp;amp;"), "&AMP; &Amp; &amp;");
+    assert_eq!(decode_entities("&#65;&#x42;&#X43;&#x;&#;&#12a;"), "ABC&#x;&#;&#12a;");
+    assert_eq!(decode_entities("&#x110000; &#xD800;"), "\u{fffd} \u{fffd}");
+    assert_eq!(decode_entities("&&lt;&"), "&<&");
+    assert_eq!(decode_entities("&mdash;&hellip;&copy;&alpha;"), "\u{2014}\u{2026}\u{a9}\u{3b1}");
+}
+
+#[test]
+fn default_parsers() {
+    let input = r#"<a href="x?a=1&amp;b=2">a &amp; b &lt; c&nbsp;</a>"#;
+    let html = Html::parse(input).expect("Invalid html");
+    assert_eq!(Html::parse_with(input, ParseOptions::default()), Ok(html.clone()));
+    assert_eq!(Html::parse_borrowed(input).expect("Invalid html").into_html(), html);
+    let mut parser = Parser::new();
+    parser.feed(input).expect("Invalid html");
+    assert_eq!(parser.finish(), Ok(html));
+    assert_eq!(format!("{:?}", ParseOptions::default()), format!("{:?}", ParseOptions::new()));
+}
+
+#[test]
+fn disabled_by_default() {
+    let input = r#"<p title="&lt;">&amp;&nbsp;</p>"#;
+    let html = Html::parse(input).expect("Invalid html");
+    assert_eq!(html.as_tag().expect("Expected a tag").1.as_text(), Some("&amp;&nbsp;"));
+    assert_eq!(html, input);
+    assert_eq!(Html::parse_with(input, ParseOptions::new().decode_entities(false)), Ok(html));
+}
+
+#[test]
+fn encode_helper() {
+    assert_eq!(encode_entities("plain text"), "plain text");
+    assert_eq!(encode_entities("a < b && c > 'd'"), "a &lt; b &amp;&amp; c &gt; &#39;d&#39;");
+    let text = "<p class=\"a\">Tom & 'Jerry'</p>";
+    assert_eq!(decode_entities(&encode_entities(text)), text);
+}
+
+#[test]
+fn hooks() {
+    let mut texts = vec![];
+    let mut titles = vec![];
+    Html::parse_with(
+        "<p title='&euro;'>1&nbsp;&euro;</p><script>a &amp;&amp; b</script>",
+        ParseOptions::new()
+            .decode_entities(true)
+            .on_text(|text| texts.push(text.to_owned()))
+            .on_tag_open(|tag| titles.extend(tag.find_attr_value("title").cloned())),
+    )
+    .expect("Invalid html");
+    assert_eq!(texts, ["1\u{a0}\u{20ac}", "a &amp;&amp; b"]);
+    assert_eq!(titles, ["\u{20ac}"]);
+}
+
+#[test]
+fn index_unchanged() {
+    let content = read_to_string("tests/data/index.html").expect("Missing tests/data/index.html");
+    let html = Html::parse(&content).expect("Invalid html");
+    assert_eq!(decoded(&content).to_string(), html.to_string());
+}
+
+#[test]
+fn raw_text() {
+    let input =
+        "<style>a::after { content: '&amp;'; }</style><script>f('&lt;', \"&quot;\")</script>";
+    let html = decoded(input);
+    assert_eq!(html, input);
+}
+
+#[test]
+fn round_trip() {
+    let input = "<p>1 &lt; 2 &amp;&amp; 3 &gt; 2</p><p>&lt;b&gt;not bold&lt;/b&gt;</p>";
+    let html = decoded(input);
+    let texts = html
+        .as_vec()
+        .expect("Expected two tags")
+        .iter()
+        .filter_map(|node| {
+            let (_, child) = node.as_tag()?;
+            child.as_text()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(texts, ["1 < 2 && 3 > 2", "<b>not bold</b>"]);
+    assert_eq!(html, "<p>1 &lt; 2 && 3 > 2</p><p>&lt;b>not bold&lt;/b></p>");
+    assert_eq!(decoded(&html.to_string()), html);
+}
+
+#[test]
+fn round_trip_references() {
+    for input in [
+        r#"<a href="x?a=1&amp;b=2">a &amp; b &lt; c</a>"#,
+        "<p title='a &amp;quot; b'>&amp;lt;b&amp;gt;</p>",
+        r#"<a href="?a=1&amp;amp;b=2&copy=3">&amp;amp; &amp;#39; & &amp</a>"#,
+        "<p title='&amp;#x27;&#x27;'>&amp;&amp;nbsp;&nbsp;</p>",
+    ] {
+        assert_eq!(Html::parse(input).expect("Invalid html").to_string(), input);
+    }
+}
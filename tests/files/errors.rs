@@ -17,6 +17,22 @@ macro_rules! make_err_test {
     };
 }
 
+#[test]
+fn error_positions() {
+    for (html, kind, line, column, offset, character) in [
+        ("<p>\n<a\n", ParseErrorKind::UnclosedTag, 3, 1, 7, None),
+        ("<p>\ncaf\u{e9} -->", ParseErrorKind::UnopenedComment, 2, 6, 10, Some('-')),
+        ("<p>\n  <a href=x>", ParseErrorKind::MissingQuote('x'), 2, 11, 14, Some('x')),
+        ("<div>\n<p></span>", ParseErrorKind::UnopenedTag("span".to_owned()), 2, 4, 9, Some('<')),
+        ("<!doc />", ParseErrorKind::InvalidDoctype('/'), 1, 8, 7, Some('/')),
+    ] {
+        let err = Html::parse(html).expect_err("Invalid html");
+        assert_eq!(err.kind(), &kind, "{html}");
+        assert_eq!((err.line(), err.column(), err.offset()), (line, column, offset), "{html}");
+        assert_eq!(err.character(), character, "{html}");
+    }
+}
+
 make_err_test!(
 
 bang_closing: "</!doc h>" => "Invalid character '!' in closing tag."
@@ -0,0 +1,65 @@
+use html_filter::*;
+
+/// Returns the warnings raised while parsing the input, with the validation
+/// of the escaping of the attribute values.
+fn warnings(input: &str) -> Vec<String> {
+    let mut warnings = vec![];
+    Html::parse_with(
+        input,
+        ParseOptions::new()
+            .attribute_escaping(AttributeEscaping::Warn)
+            .on_warning(|warning| warnings.push(warning.to_owned())),
+    )
+    .unwrap_or_else(|err| panic!("{err}"));
+    warnings
+}
+
+#[test]
+fn escaping_ampersands() {
+    assert_eq!(
+        warnings(r#"<a href="/?a=1&amp;b=2&#38;c=3&#x26;d=4">x & y</a><img alt="Q&A" src="&">"#),
+        [
+            "Unescaped '&' in the value of attribute 'alt' at byte 61 (line 1, column 62).",
+            "Unescaped '&' in the value of attribute 'src' at byte 70 (line 1, column 71).",
+        ]
+    );
+    assert_eq!(warnings("<p title='a &amp b'>").len(), 1);
+}
+
+#[test]
+fn escaping_lenient() {
+    let mut count = 0u32;
+    let html = Html::parse_with(
+        r#"<p title="<b>" data-x="a"b">&</p>"#,
+        ParseOptions::new().on_warning(|_| count += 1),
+    )
+    .unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(count, 0);
+    assert_eq!(html.as_tag().map(|(tag, _)| tag.attr_count()), Some(3));
+}
+
+#[test]
+fn escaping_quotes_and_brackets() {
+    assert_eq!(warnings(r#"<div class='a' title="<b>"></div><br data-x='a'b'/>"#), [
+        "Unescaped '<' in the value of attribute 'title' at byte 22 (line 1, column 23).",
+        "Missing whitespace after the value of attribute 'data-x' at byte 47 (line 1, column 48).",
+        "Mismatched quote in the name of attribute 'b'' at byte 47 (line 1, column 48).",
+    ]);
+    assert!(warnings("<p a='x'/><p b='y'>").is_empty());
+}
+
+#[test]
+fn escaping_stream() {
+    let mut warnings = vec![];
+    let mut parser = Parser::with_options(
+        ParseOptions::new()
+            .attribute_escaping(AttributeEscaping::Warn)
+            .on_warning(|warning| warnings.push(warning.to_owned())),
+    );
+    parser.feed("<div>\n<p>text</p>\n<a hr").unwrap_or_else(|err| panic!("{err}"));
+    parser.feed("ef='?x&y'>link</a></div>").unwrap_or_else(|err| panic!("{err}"));
+    parser.finish().unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(warnings, [
+        "Unescaped '&' in the value of attribute 'href' at byte 29 (line 3, column 12)."
+    ]);
+}
@@ -0,0 +1,45 @@
+use html_filter::*;
+
+fn etag(input: &str) -> String {
+    Html::parse(input).expect("Invalid html").etag()
+}
+
+#[test]
+fn etag_differences() {
+    let base = etag(r#"<div><p id="a">Some text</p></div>"#);
+    assert_ne!(base, etag(r#"<div><p id="b">Some text</p></div>"#));
+    assert_ne!(base, etag("<div><p>Some text</p></div>"));
+    assert_ne!(base, etag(r#"<div><p id="a">Some</p>text</div>"#));
+    assert_ne!(base, etag(r#"<section><p id="a">Some text</p></section>"#));
+    assert_ne!(etag("<input disabled>"), etag("<input disabled=''>"));
+}
+
+#[test]
+fn etag_formatting() {
+    let base = etag(
+        r#"<!DOCTYPE html><ul class="list" id="items"><li>First item</li><li>Second</li></ul>"#,
+    );
+    assert_eq!(
+        base,
+        etag(
+            "<!DOCTYPE html>\n<ul id='items' class='list'>\n  <li>\n    First\n    item\n  \
+             </li>\n  <li>Sec<!-- split -->ond</li>\n</ul>\n"
+        )
+    );
+    let pretty =
+        Html::parse(r#"<ul class="list" id="items"><li>First item</li><li>Second</li></ul>"#)
+            .expect("Invalid html")
+            .to_pretty_string(&PrettyOptions::new());
+    assert_eq!(
+        etag(&pretty),
+        etag(r#"<ul class="list" id="items"><li>First item</li><li>Second</li></ul>"#)
+    );
+}
+
+#[test]
+fn etag_stable() {
+    assert_eq!(etag(""), "cbf29ce484222325");
+    assert_eq!(etag("<p>Hello</p>"), etag("<p>Hello</p>"));
+    assert_eq!(etag("<p>Hello</p>").len(), 16);
+    assert!(etag("<p>Hello</p>").chars().all(|ch| ch.is_ascii_hexdigit()));
+}
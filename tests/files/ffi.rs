@@ -0,0 +1,135 @@
+#![expect(
+    clippy::semicolon_inside_block,
+    reason = "rustfmt expands the blocks with a statement, which conflicts with \
+              semicolon_outside_block"
+)]
+
+use core::ffi::CStr;
+use core::ptr::{null, null_mut};
+
+use html_filter::ffi::*;
+use html_filter::*;
+
+const INPUT: &CStr = c"<ul><li class='a b'>First</li><li id=\"second\" data-x='1'>Second</li></ul>";
+
+fn select(selector: &CStr) -> Option<String> {
+    // SAFETY: the input is NUL-terminated.
+    let html = unsafe { hf_parse(INPUT.as_ptr()) };
+    assert!(!html.is_null(), "Invalid html");
+    // SAFETY: the tree is alive and the selector is NUL-terminated.
+    let found = unsafe { hf_filter_selector(html, selector.as_ptr()) };
+    let output = (!found.is_null()).then(|| {
+        // SAFETY: the tree is alive.
+        let text = unsafe { hf_inner_text(found) };
+        // SAFETY: the text was returned by the module and isn't released.
+        let owned = unsafe { CStr::from_ptr(text) }.to_str().expect("Invalid UTF-8").to_owned();
+        // SAFETY: the text was returned by the module and isn't released.
+        unsafe { hf_free_string(text) };
+        owned
+    });
+    // SAFETY: the tree was returned by the module and isn't released.
+    unsafe { hf_free(found) };
+    // SAFETY: the tree was returned by the module and isn't released.
+    unsafe { hf_free(html) };
+    output
+}
+
+/// Converts a type of the C ABI into its declaration in the header.
+fn c_type(rust: &str) -> &'static str {
+    match rust.trim() {
+        "*const Html" => "const HfHtml *",
+        "*mut Html" => "HfHtml *",
+        "*const c_char" => "const char *",
+        "*mut c_char" => "char *",
+        other => panic!("{other} has no C type in the test"),
+    }
+}
+
+/// Builds the declarations of the header from the `extern "C"` functions of
+/// the module.
+fn rust_declarations() -> Vec<String> {
+    let source = include_str!("../../src/ffi.rs");
+    let mut declarations = source
+        .split("pub unsafe extern \"C\" fn ")
+        .skip(1)
+        .map(|function| {
+            let (signature, _) = function.split_once('{').expect("function without body");
+            let (name, tail) = signature.split_once('(').expect("function without arguments");
+            let (args, output) = tail.rsplit_once(')').expect("unclosed arguments");
+            let params = args
+                .split(',')
+                .filter(|arg| !arg.trim().is_empty())
+                .map(|arg| {
+                    let (arg_name, ty) = arg.split_once(':').expect("argument without type");
+                    format!("{}{}", c_type(ty), arg_name.trim())
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            let ret = output.trim().strip_prefix("->").map_or("void ", c_type);
+            format!("{ret}{name}({params});")
+        })
+        .collect::<Vec<_>>();
+    declarations.sort_unstable();
+    declarations
+}
+
+#[test]
+fn ffi_header() {
+    let header = include_str!("../../include/html_filter.h");
+    let mut declarations = header
+        .lines()
+        .filter(|line| line.ends_with(");") && !line.starts_with([' ', '/', '*']))
+        .map(str::to_owned)
+        .collect::<Vec<_>>();
+    declarations.sort_unstable();
+    assert_eq!(declarations, rust_declarations());
+}
+
+#[test]
+fn ffi_null() {
+    // SAFETY: null pointers are accepted.
+    assert!(unsafe { hf_parse(null()) }.is_null());
+    // SAFETY: null pointers are accepted.
+    assert!(unsafe { hf_filter_selector(null(), c"p".as_ptr()) }.is_null());
+    // SAFETY: null pointers are accepted.
+    assert!(unsafe { hf_inner_text(null()) }.is_null());
+    // SAFETY: null pointers are accepted.
+    unsafe { hf_free(null_mut()) };
+    // SAFETY: null pointers are accepted.
+    unsafe { hf_free_string(null_mut()) };
+    // SAFETY: the input is NUL-terminated.
+    assert!(unsafe { hf_parse(c"<p>--></p>".as_ptr()) }.is_null());
+    // SAFETY: the input is NUL-terminated.
+    assert!(unsafe { hf_parse(c"\xff".as_ptr()) }.is_null());
+}
+
+#[test]
+fn ffi_selectors() {
+    assert_eq!(select(c"li").as_deref(), Some("FirstSecond"));
+    assert_eq!(select(c"*").as_deref(), Some("FirstSecond"));
+    assert_eq!(select(c"li.b").as_deref(), Some("First"));
+    assert_eq!(select(c".a.b").as_deref(), Some("First"));
+    assert_eq!(select(c"#second").as_deref(), Some("Second"));
+    assert_eq!(select(c"li[data-x]").as_deref(), Some("Second"));
+    assert_eq!(select(c"[data-x='1']").as_deref(), Some("Second"));
+    assert_eq!(select(c"[data-x=2]").as_deref(), Some(""));
+    assert_eq!(select(c"[class~=b]").as_deref(), Some("First"));
+    assert_eq!(select(c"[id^='sec']").as_deref(), Some("Second"));
+    assert_eq!(select(c"[id$=ond]").as_deref(), Some("Second"));
+    assert_eq!(select(c"[class*=' ']").as_deref(), Some("First"));
+    assert_eq!(select(c"p").as_deref(), Some(""));
+    for invalid in [c"li >", c"li[", c"li.", c"[=a]", c"[^=a]", c"[id|=a]", c"ul li", c"li,p"] {
+        assert_eq!(select(invalid), None, "{invalid:?}");
+    }
+}
+
+#[test]
+fn ffi_text() {
+    let html = Html::parse("<p>a<!-- b --><b>c</b></p>").expect("Invalid html");
+    // SAFETY: the tree is alive.
+    let text = unsafe { hf_inner_text(&raw const html) };
+    // SAFETY: the text was returned by the module and isn't released.
+    assert_eq!(unsafe { CStr::from_ptr(text) }, c"ac");
+    // SAFETY: the text was returned by the module and isn't released.
+    unsafe { hf_free_string(text) };
+}
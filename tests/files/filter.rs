@@ -35,6 +35,15 @@ r#"<div class="some_class some_other_class"> Secret </div>
 contains_both: Filter::new().attribute_value_contains("class", "some_other_class").attribute_value_contains("class", "some_class") =>
 r#"<div class="some_class some_other_class"> Secret </div>"#
 
+starts_with: Filter::new().attribute_value_starts_with("class", "some_class2") =>
+r#"<div class="some_class2 some_other_class">Secret</div>"#
+
+ends_with: Filter::new().attribute_value_ends_with("id", "2").tag_name("input") =>
+r#"<input radio type="radio" name="radio" id="radio2" />"#
+
+substring: Filter::new().attribute_value_substring("class", "class2") =>
+r#"<div class="some_class2 some_other_class">Secret</div>"#
+
 radio: Filter::new().attribute_value("type", "radio").attribute_name("radio") =>
 r#"<input radio type="radio" name="radio" id="radio1" /><input radio type="radio" name="radio" id="radio2" />"#
 
@@ -338,4 +347,46 @@ r##"
 attribute_no_value: Filter::new().attribute_name("charset") =>
 ""
 
+max_attributes: Filter::new().tag_name("input").max_attributes(1) =>
+r#"<input enabled /><input type="date" /><input type="file" />"#
+
+no_attributes: Filter::new().tag_name("input").no_attributes() =>
+""
+
+global_id: Filter::new().id("radio2") =>
+r#"<input radio type="radio" name="radio" id="radio2" />"#
+
+global_hidden: Filter::new().tag_name("input").hidden(true) =>
+""
+
+limit: Filter::new().tag_name("input").limit(1) =>
+r#"<input type="sub\mit" id="name" name="name" />"#
+
+max_output_bytes: Filter::new().tag_name("input").max_output_bytes(60) =>
+r#"<input type="sub\mit" id="name" name="name" />"#
+
+max_output_nodes: Filter::new().tag_name("input").max_output_nodes(2) =>
+r#"<input type="sub\mit" id="name" name="name" /><input type='sub"mit' value="Submit" />"#
+
 );
+
+#[test]
+fn global_hidden_kept() {
+    let html = Html::parse(r#"<input hidden name="a"><input name="b"><p hidden>c</p>"#)
+        .unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(
+        html.to_filtered(&Filter::new().tag_name("input").hidden(true)),
+        r#"<input hidden name="a">"#
+    );
+    assert_eq!(
+        html.to_filtered(&Filter::new().tag_name("input").hidden(false)),
+        r#"<input name="b">"#
+    );
+}
+
+#[test]
+fn no_attributes_kept() {
+    let html = Html::parse(r#"<input><input type="date"><br><input disabled>"#)
+        .unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(html.to_filtered(&Filter::new().tag_name("input").no_attributes()), "<input>");
+}
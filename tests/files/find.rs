@@ -37,3 +37,134 @@ r##"
 find_failure: Filter::new().tag_name("azerty") => ""
 
 );
+
+/// Returns the first node of a filtered output, as documented by
+/// [`Html::find`].
+fn first(html: Html) -> Html {
+    if let Html::Vec(vec) = html {
+        vec.into_iter().map(first).find(|elt| *elt != Html::Empty).unwrap_or_default()
+    } else {
+        html
+    }
+}
+
+#[test]
+fn find_early_exit() {
+    let content = read_to_string("tests/data/index.html").expect("Missing tests/data/index.html");
+    let tree = Html::parse(&content).unwrap_or_else(|err| panic!("{err}"));
+    let filters = [
+        Filter::new(),
+        Filter::new().tag_name("li"),
+        Filter::new().tag_name("li").depth(1),
+        Filter::new().tag_name("td").depth(2),
+        Filter::new().tag_name("input").depth(3).comment(false),
+        Filter::new().attribute_name("enabled").depth(1),
+        Filter::new().attribute_value("type", "radio").trim(),
+        Filter::new().no_tags().none_except_text().collapse(),
+        Filter::new().none_except_comment(),
+        Filter::new().tag_name("p").doctype(true),
+    ];
+    for filter in &filters {
+        let expected = first(tree.to_filtered(filter));
+        assert_eq!(tree.to_found(filter), expected, "{filter:?}");
+        assert_eq!(tree.clone().find(filter), expected, "{filter:?}");
+    }
+}
+
+#[test]
+fn find_limit() {
+    let content = read_to_string("tests/data/index.html").expect("Missing tests/data/index.html");
+    let tree = Html::parse(&content).unwrap_or_else(|err| panic!("{err}"));
+    let filters = [
+        || Filter::new(),
+        || Filter::new().tag_name("li"),
+        || Filter::new().tag_name("li").depth(1),
+        || Filter::new().tag_name("td").depth(2),
+        || Filter::new().tag_name("input").depth(3).comment(false),
+        || Filter::new().no_tags().none_except_text().collapse(),
+        || Filter::new().tag_name("p").doctype(true),
+    ];
+    for filter in filters {
+        for limit in [0, 1, 2, 5] {
+            let expected = tree.to_filtered(&filter().max_output_nodes(limit));
+            assert_eq!(tree.to_filtered(&filter().limit(limit)), expected, "{limit}");
+            assert_eq!(tree.clone().filter(&filter().limit(limit)), expected, "{limit}");
+        }
+        assert_eq!(tree.to_found(&filter().limit(3)), tree.to_found(&filter()));
+    }
+}
+
+#[test]
+fn find_all_flat() {
+    let content = read_to_string("tests/data/index.html").expect("Missing tests/data/index.html");
+    let tree = Html::parse(&content).unwrap_or_else(|err| panic!("{err}"));
+    let filters = [
+        Filter::new().tag_name("li"),
+        Filter::new().tag_name("input"),
+        Filter::new().attribute_value("type", "radio"),
+        Filter::new().tag_name("azerty"),
+    ];
+    for filter in &filters {
+        let found = tree.find_all(filter);
+        let selected = tree.select(filter).collect::<Vec<_>>();
+        assert_eq!(found.len(), selected.len(), "{filter:?}");
+        for (node, node_ref) in found.iter().zip(&selected) {
+            assert_eq!(*node, node_ref.as_html(), "{filter:?}");
+        }
+        let owned = tree.clone().into_find_all(filter);
+        assert_eq!(owned.iter().collect::<Vec<_>>(), found, "{filter:?}");
+    }
+}
+
+#[test]
+fn find_all_nested() {
+    let tree = Html::parse("<div class='a'>1<div class='a'>2<p class='a'>3</p></div></div>")
+        .unwrap_or_else(|err| panic!("{err}"));
+    let filter = Filter::new().attribute_value("class", "a");
+    let expected = [
+        "<div class='a'>1<div class='a'>2<p class='a'>3</p></div></div>",
+        "<div class='a'>2<p class='a'>3</p></div>",
+        "<p class='a'>3</p>",
+    ];
+    let found = tree.find_all(&filter).into_iter().map(Html::to_string).collect::<Vec<_>>();
+    assert_eq!(found, expected);
+    let owned = tree.into_find_all(&filter).iter().map(Html::to_string).collect::<Vec<_>>();
+    assert_eq!(owned, expected);
+}
+
+#[test]
+fn find_all_node_types() {
+    let tree = Html::parse("<!DOCTYPE html><p>a<!--b--> <i>c</i></p>")
+        .unwrap_or_else(|err| panic!("{err}"));
+    let texts = tree.find_all(&Filter::new().no_tags().none_except_text());
+    assert_eq!(texts, [&"a", &" ", &"c"]);
+    let trimmed = tree.find_all(&Filter::new().no_tags().none_except_text().trim());
+    assert_eq!(trimmed, [&"a", &"c"]);
+    let comments = tree.clone().into_find_all(&Filter::new().no_tags().none_except_comment());
+    assert_eq!(comments, ["<!--b-->"]);
+    let doctypes = tree.find_all(&Filter::new().no_tags().none_except_doctype());
+    assert_eq!(doctypes, [&"<!DOCTYPE html>"]);
+    assert_eq!(tree.find_all(&Filter::new().tag_name("i")), [&"<i>c</i>"]);
+    assert_eq!(tree.find_all(&Filter::new()).len(), tree.iter().count());
+}
+
+#[test]
+fn find_count_any() {
+    let content = read_to_string("tests/data/index.html").expect("Missing tests/data/index.html");
+    let tree = Html::parse(&content).unwrap_or_else(|err| panic!("{err}"));
+    let filters = [
+        Filter::new(),
+        Filter::new().tag_name("li"),
+        Filter::new().tag_name("input"),
+        Filter::new().attribute_value("type", "radio"),
+        Filter::new().no_tags().none_except_text().trim(),
+        Filter::new().none_except_comment(),
+        Filter::new().tag_name("azerty"),
+    ];
+    for filter in &filters {
+        let found = tree.find_all(filter).len();
+        assert_eq!(tree.count(filter), found, "{filter:?}");
+        assert_eq!(tree.any(filter), found > 0, "{filter:?}");
+    }
+    assert!(!Html::Empty.any(&Filter::new()));
+}
@@ -0,0 +1,37 @@
+use html_filter::*;
+
+macro_rules! test_highlight {
+    ($($name:ident: $html:expr, $pattern:expr => $expect:expr)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let mut html = Html::parse($html).unwrap_or_else(|err| panic!("{err}"));
+                let tag = Tag::from(("mark".to_owned(), vec![Attribute::from("hl".to_owned())].into_boxed_slice()));
+                html.wrap_text_matches($pattern, &tag);
+                assert_eq!(html, $expect);
+            }
+        )*
+    };
+}
+
+test_highlight!(
+
+highlight_whole_text: "<p>term</p>", "term" =>
+"<p><mark hl>term</mark></p>"
+
+highlight_consecutive: "<p>aaa</p>", "aa" =>
+"<p><mark hl>aa</mark>a</p>"
+
+highlight_siblings: "<p>a term<b>term</b>term a</p>", "term" =>
+"<p>a <mark hl>term</mark><b><mark hl>term</mark></b><mark hl>term</mark> a</p>"
+
+highlight_raw_text: "<style>.term {}</style><!-- term --><p title=\"term\">no</p>", "term" =>
+"<style>.term {}</style><!-- term --><p title=\"term\">no</p>"
+
+highlight_empty_pattern: "<p>term</p>", "" =>
+"<p>term</p>"
+
+highlight_root_text: "a term", "term" =>
+"a <mark hl>term</mark>"
+
+);
@@ -0,0 +1,40 @@
+use std::fs::read_to_string;
+
+use html_filter::*;
+
+#[test]
+fn hooks_index() {
+    let content = read_to_string("tests/data/index.html").expect("Missing tests/data/index.html");
+    let mut names = vec![];
+    let mut texts = vec![];
+    let tree = Html::parse_with(
+        &content,
+        ParseOptions::new()
+            .on_tag_open(|tag| names.push(tag.as_name().to_owned()))
+            .on_text(|text| texts.push(text.trim().to_owned())),
+    )
+    .unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(tree, Html::parse(&content).unwrap_or_else(|err| panic!("{err}")));
+    assert_eq!(names.iter().filter(|name| *name == "input").count(), 8);
+    assert_eq!(names.first().map(String::as_str), Some("html"));
+    assert!(texts.iter().any(|text| text == "Test HTML"));
+    assert!(texts.iter().any(|text| text.starts_with("@keyframes a")));
+    assert!(texts.iter().any(|text| text.starts_with("(()=> {")));
+}
+
+#[test]
+fn hooks_dashes() {
+    let mut texts = vec![];
+    Html::parse_with(
+        "a-b--c<!-- d--- -->e---f",
+        ParseOptions::new().on_text(|text| texts.push(text.to_owned())),
+    )
+    .unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(texts, ["a-b--c", "e---f"]);
+    assert_eq!(
+        format!("{:?}", ParseOptions::new()),
+        "ParseOptions { attribute_escaping: Lenient, budget: None, control_policy: Keep, \
+         decode_entities: false, legacy_tags: Normal, max_attributes: None, recover: false, \
+         tag_open: false, text: false, void_policy: Warn, void_tags: None, warning: false }"
+    );
+}
@@ -0,0 +1,227 @@
+//! Conformance harness against the fixtures of html5lib-tests.
+//!
+//! The tokenizer fixtures (`*.test`) are run through [`Tokenizer`], and the
+//! tree-construction fixtures (`*.dat`) through [`Html::parse`]. A sample of
+//! the fixtures is vendored in `tests/data/html5lib`. Set `HTML5LIB_TESTS` to
+//! a checkout of <https://github.com/html5lib/html5lib-tests> to also run the
+//! whole suite and print its pass rates.
+
+use std::env;
+use std::fs::{read_dir, read_to_string};
+use std::path::{Path, PathBuf};
+
+use html_filter::*;
+use serde_json::{Map, Value, json};
+
+/// Results of the fixtures of a file.
+#[derive(Debug, Default)]
+struct Report {
+    failed: Vec<String>,
+    passed: usize,
+    skipped: usize,
+}
+
+impl Report {
+    fn push(&mut self, description: String, passed: bool) {
+        if passed {
+            self.passed = self.passed.saturating_add(1);
+        } else {
+            self.failed.push(description);
+        }
+    }
+
+    fn skip(&mut self) {
+        self.skipped = self.skipped.saturating_add(1);
+    }
+
+    fn summary(&self, name: &str) -> String {
+        let total = self.passed.saturating_add(self.failed.len());
+        let rate = self.passed.saturating_mul(100).checked_div(total).unwrap_or_default();
+        format!("{name}: {}/{total} passed ({rate}%), {} skipped", self.passed, self.skipped)
+    }
+}
+
+/// Pushes a token in the html5lib format, merging the adjacent characters.
+fn push_token(tokens: &mut Vec<Value>, token: Value) {
+    if let Some(Value::Array(last)) = tokens.last_mut()
+        && last.first() == Some(&json!("Character"))
+        && token.get(0) == Some(&json!("Character"))
+        && let (Some(Value::String(text)), Some(Value::String(more))) =
+            (last.get_mut(1), token.get(1))
+    {
+        text.push_str(more);
+        return;
+    }
+    tokens.push(token);
+}
+
+/// Converts the tokens of an input to the html5lib format, or `None` if the
+/// tokenizer fails.
+fn html5lib_tokens(input: &str) -> Option<Vec<Value>> {
+    let mut tokens = vec![];
+    for token in Tokenizer::new(input) {
+        let value = match token.ok()?.into_kind() {
+            TokenKind::CloseTag(name) => json!(["EndTag", name]),
+            TokenKind::Comment(content) => json!(["Comment", content]),
+            TokenKind::Doctype { attr, .. } => json!(["DOCTYPE", attr, null, null, true]),
+            TokenKind::OpenTag { self_closing, tag } => {
+                let mut attrs = Map::new();
+                for attr in tag.as_attrs() {
+                    let value = decode_entities(attr.as_value().unwrap_or_default()).into_owned();
+                    attrs.entry(attr.as_name()).or_insert(Value::String(value));
+                }
+                if self_closing {
+                    json!(["StartTag", tag.as_name(), attrs, true])
+                } else {
+                    json!(["StartTag", tag.as_name(), attrs])
+                }
+            }
+            TokenKind::Text(text) => json!(["Character", decode_entities(text)]),
+        };
+        push_token(&mut tokens, value);
+    }
+    Some(tokens)
+}
+
+/// Runs the tokenizer fixtures of a `.test` file.
+fn run_tokenizer(content: &str) -> Report {
+    let mut report = Report::default();
+    let fixtures: Value = serde_json::from_str(content).expect("valid json fixtures");
+    for test in fixtures.get("tests").and_then(Value::as_array).into_iter().flatten() {
+        let description = test.get("description").and_then(Value::as_str).unwrap_or_default();
+        let (Some(input), Some(Value::Array(expected))) =
+            (test.get("input").and_then(Value::as_str), test.get("output"))
+        else {
+            report.skip();
+            continue;
+        };
+        let data_state = test
+            .get("initialStates")
+            .and_then(Value::as_array)
+            .is_none_or(|states| states.iter().any(|state| state == "Data state"));
+        if !data_state || test.get("doubleEscaped").is_some() {
+            report.skip();
+            continue;
+        }
+        let passed = html5lib_tokens(input).is_some_and(|tokens| tokens == *expected);
+        report.push(description.to_owned(), passed);
+    }
+    report
+}
+
+/// Writes a tree in the format of the tree-construction fixtures.
+fn push_tree(html: &Html, depth: usize, lines: &mut Vec<String>) {
+    let indent = format!("| {}", "  ".repeat(depth));
+    match html {
+        Html::Comment(content) => lines.push(format!("{indent}<!-- {content} -->")),
+        Html::Doctype { attr, .. } =>
+            lines.push(format!("{indent}<!DOCTYPE {}>", attr.as_deref().unwrap_or_default())),
+        Html::Empty => (),
+        Html::Tag { tag, child } => {
+            lines.push(format!("{indent}<{}>", tag.as_name()));
+            let mut attrs = tag
+                .as_attrs()
+                .iter()
+                .map(|attr| {
+                    let value = attr.as_value().map_or("", String::as_str);
+                    format!("{indent}  {}=\"{}\"", attr.as_name(), decode_entities(value))
+                })
+                .collect::<Vec<_>>();
+            attrs.sort();
+            lines.extend(attrs);
+            push_tree(child, depth.saturating_add(1), lines);
+        }
+        Html::Text(text) => lines.push(format!("{indent}\"{}\"", decode_entities(text.as_str()))),
+        Html::Vec(vec) => vec.iter().for_each(|node| push_tree(node, depth, lines)),
+    }
+}
+
+/// Runs the tree-construction fixtures of a `.dat` file.
+fn run_tree_construction(content: &str) -> Report {
+    let mut report = Report::default();
+    for test in content.split("#data\n").skip(1) {
+        let mut sections = vec![("#data", String::new())];
+        for line in test.lines() {
+            if line.starts_with('#') {
+                sections.push((line, String::new()));
+                continue;
+            }
+            if let Some((_, section)) = sections.last_mut() {
+                section.push_str(line);
+                section.push('\n');
+            }
+        }
+        let section = |name: &str| {
+            sections.iter().find(|(header, _)| *header == name).map(|(_, lines)| lines.as_str())
+        };
+        let (Some(data), Some(document)) = (section("#data"), section("#document")) else {
+            report.skip();
+            continue;
+        };
+        if section("#document-fragment").is_some() {
+            report.skip();
+            continue;
+        }
+        let input = data.strip_suffix('\n').unwrap_or(data);
+        let passed = Html::parse(input).is_ok_and(|html| {
+            let mut lines = vec![];
+            push_tree(&html, 0, &mut lines);
+            lines.join("\n") == document.trim_end_matches('\n')
+        });
+        report.push(input.to_owned(), passed);
+    }
+    report
+}
+
+/// Runs the fixtures of the files of a directory with the given extension.
+fn run_dir(dir: &Path, extension: &str, run: fn(&str) -> Report) -> Vec<(String, Report)> {
+    let mut paths = read_dir(dir)
+        .map(|entries| entries.filter_map(|entry| Some(entry.ok()?.path())).collect::<Vec<_>>())
+        .unwrap_or_default();
+    paths.sort();
+    paths
+        .into_iter()
+        .filter(|path| path.extension().is_some_and(|ext| ext == extension))
+        .map(|path| {
+            let content = read_to_string(&path).expect("readable fixture");
+            (path.display().to_string(), run(&content))
+        })
+        .collect()
+}
+
+#[test]
+fn html5lib_sample_tokenizer() {
+    let reports = run_dir(Path::new("tests/data/html5lib/tokenizer"), "test", run_tokenizer);
+    let [(_, report)] = reports.as_slice() else { panic!("one tokenizer fixture file") };
+    assert_eq!(report.skipped, 1);
+    assert_eq!(report.failed, [
+        "Correct Doctype uppercase",
+        "Uppercase start tag name",
+        "Unquoted attribute value",
+        "Less-than sign in text",
+        "Unfinished end tag"
+    ]);
+    assert_eq!(report.passed, 14);
+}
+
+#[test]
+fn html5lib_sample_tree_construction() {
+    let reports =
+        run_dir(Path::new("tests/data/html5lib/tree-construction"), "dat", run_tree_construction);
+    let [(_, report)] = reports.as_slice() else { panic!("one tree fixture file") };
+    assert_eq!(report.skipped, 1);
+    assert_eq!(report.passed, 3);
+    assert_eq!(report.failed, ["<html><body><!-- c --><br></body></html>", "<p>One<p>Two"]);
+}
+
+#[test]
+#[expect(clippy::print_stdout, reason = "report of the pass rates")]
+fn html5lib_suite() {
+    let Some(root) = env::var_os("HTML5LIB_TESTS").map(PathBuf::from) else { return };
+    let reports = run_dir(&root.join("tokenizer"), "test", run_tokenizer)
+        .into_iter()
+        .chain(run_dir(&root.join("tree-construction"), "dat", run_tree_construction));
+    for (name, report) in reports {
+        println!("{}", report.summary(&name));
+    }
+}
@@ -0,0 +1,128 @@
+use html_filter::*;
+
+const HTML: &str = r#"<nav><a href="/">Home</a><ul><li><a href="/blog">Blog</a></li></ul></nav><main><a href="/a">A</a><table><tr><td>1</td><td><a href="/b">B</a></td></tr></table></main>"#;
+
+const LINKS: &str = "<nav><a href='/1'>1</a></nav><a href='/2'>2</a>";
+
+/// Parses [`HTML`].
+fn html() -> Html {
+    Html::parse(HTML).unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// Returns the filter on the links of [`LINKS`] inside the `<nav>`.
+fn nav_links() -> Filter {
+    Filter::new().tag_name("a").inside_tag("nav")
+}
+
+/// Parses [`LINKS`].
+fn links() -> Html {
+    Html::parse(LINKS).unwrap_or_else(|err| panic!("{err}"))
+}
+
+#[test]
+fn inside_best_match() {
+    let scored = ScoredFilter::new().rule(nav_links(), 1);
+    let html = links();
+    let (best, score) = html.best_match(&scored).expect("the first link matches");
+    assert_eq!(best.as_html(), &"<a href='/1'>1</a>");
+    assert_eq!(score, 1);
+    let outside = ScoredFilter::new().rule(Filter::new().tag_name("a").inside_tag("main"), 1);
+    assert!(html.best_match(&outside).is_none());
+}
+
+#[test]
+fn inside_count() {
+    let html = html();
+    assert_eq!(html.count(&Filter::new().tag_name("a")), 4);
+    assert_eq!(html.count(&Filter::new().tag_name("a").inside_tag("nav")), 2);
+    assert_eq!(html.count(&Filter::new().tag_name("a").inside_tag("ul")), 1);
+    assert!(html.any(&Filter::new().tag_name("a").inside_tag("td")));
+    assert!(!html.any(&Filter::new().tag_name("nav").inside_tag("nav")));
+}
+
+#[test]
+fn inside_filter() {
+    let html = html();
+    assert_eq!(
+        html.to_filtered(&Filter::new().tag_name("a").inside_tag("nav")),
+        r#"<a href="/">Home</a><a href="/blog">Blog</a>"#
+    );
+    assert_eq!(
+        html.to_filtered(&Filter::new().tag_name("td").inside_tag("table")),
+        r#"<td>1</td><td><a href="/b">B</a></td>"#
+    );
+    assert_eq!(
+        html.to_found(&Filter::new().tag_name("a").inside_tag("main")),
+        r#"<a href="/a">A</a>"#
+    );
+}
+
+#[test]
+fn inside_depth() {
+    assert_eq!(
+        html().to_filtered(&Filter::new().tag_name("a").inside_tag("ul").depth(1)),
+        r#"<li><a href="/blog">Blog</a></li>"#
+    );
+}
+
+#[test]
+fn inside_find_all() {
+    let html = html();
+    let filter = Filter::new().tag_name("a").inside_tag("main").inside_tag("td");
+    assert_eq!(html.find_all(&filter), [&r#"<a href="/b">B</a>"#]);
+    assert_eq!(html.clone().into_find_all(&filter), [r#"<a href="/b">B</a>"#]);
+    let cells = Filter::new().inside_tag("tr");
+    assert_eq!(html.find_all(&cells).len(), 5);
+    assert_eq!(html.into_find_all(&cells).len(), 5);
+}
+
+#[test]
+fn inside_first_n_elements() {
+    assert_eq!(links().first_n_elements(2, &nav_links()), "<nav><a href='/1'>1</a></nav>");
+}
+
+#[test]
+fn inside_lazy_select() {
+    let lazy = LazyHtml::from_bin(links().to_bin()).unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(lazy.select(&nav_links()), Ok(vec![links().find(&nav_links())]));
+}
+
+#[test]
+fn inside_profile() {
+    let stats = nav_links().profile(&links());
+    assert_eq!(stats.matched(), 1);
+    assert!(stats.to_string().starts_with("1 of 3 tags matched the filter."));
+}
+
+#[test]
+fn inside_select() {
+    let html = html();
+    let filter = Filter::new().tag_name("a").inside(Filter::new().tag_name("td"));
+    let links = html.select(&filter).collect::<Vec<_>>();
+    assert_eq!(links.len(), 1);
+    assert_eq!(
+        links
+            .first()
+            .and_then(NodeRef::parent)
+            .and_then(|parent| parent.as_tag())
+            .map(Tag::as_name),
+        Some("td")
+    );
+}
+
+#[test]
+fn inside_split_at() {
+    let (before, after) = links().split_at(&nav_links());
+    assert_eq!(before, "<nav></nav>");
+    assert_eq!(after, "<nav></nav><a href='/2'>2</a>");
+}
+
+#[test]
+fn inside_texts() {
+    let html = html();
+    assert_eq!(
+        html.to_filtered(&Filter::new().no_tags().none_except_text().inside_tag("nav")),
+        "HomeBlog"
+    );
+    assert_eq!(html.count(&Filter::new().no_tags().none_except_text().inside_tag("td")), 2);
+}
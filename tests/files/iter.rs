@@ -0,0 +1,85 @@
+use std::fs::read_to_string;
+
+use html_filter::*;
+
+fn index() -> Html {
+    let content = read_to_string("tests/data/index.html").expect("file exists");
+    Html::parse(&content).unwrap_or_else(|err| panic!("{err}"))
+}
+
+#[test]
+fn iter_children() {
+    let tree = Html::parse("<!DOCTYPE html>text<ul><li>a</li><li><b>b</b></li></ul><!--c-->")
+        .unwrap_or_else(|err| panic!("{err}"));
+    let top = tree.children().map(Html::to_string).collect::<Vec<_>>();
+    assert_eq!(top, [
+        "<!DOCTYPE html>",
+        "text",
+        "<ul><li>a</li><li><b>b</b></li></ul>",
+        "<!--c-->"
+    ]);
+
+    let list = tree.children().nth(2).expect("three top-level nodes");
+    let items = list.children().map(Html::to_string).collect::<Vec<_>>();
+    assert_eq!(items, ["<li>a</li>", "<li><b>b</b></li>"]);
+
+    let text = tree.children().nth(1).expect("two top-level nodes");
+    assert_eq!(text.children().count(), 0);
+    assert_eq!(Html::Empty.children().count(), 0);
+    assert_eq!(Html::parse("<p></p>").map(|html| html.children().count()), Ok(0));
+}
+
+#[test]
+fn iter_depth_first() {
+    let tree = Html::parse("<div><p>a<b>b</b>c</p><br><!--d--></div>e")
+        .unwrap_or_else(|err| panic!("{err}"));
+    let nodes = tree
+        .iter()
+        .map(|node| match node {
+            Html::Tag { tag, .. } => tag.as_name().to_owned(),
+            Html::Comment(comment) => format!("<!--{comment}-->"),
+            Html::Text(text) => text.to_string(),
+            Html::Doctype { .. } | Html::Empty | Html::Vec(_) => panic!("unexpected {node}"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(nodes, ["div", "p", "a", "b", "b", "c", "br", "<!--d-->", "e"]);
+
+    assert_eq!(tree.iter().count(), nodes.len());
+}
+
+#[test]
+fn iter_into_children() {
+    let tree = Html::parse("<p>a</p>b<p>c</p>").unwrap_or_else(|err| panic!("{err}"));
+    let expected = tree.children().map(Html::to_string).collect::<Vec<_>>();
+    let mut borrowed = vec![];
+    for node in &tree {
+        borrowed.push(node.to_string());
+    }
+    assert_eq!(borrowed, expected);
+    let owned = tree.into_iter().map(|node| node.to_string()).collect::<Vec<_>>();
+    assert_eq!(owned, expected);
+
+    let paragraph = Html::parse("<p>a<b>b</b></p>").unwrap_or_else(|err| panic!("{err}"));
+    let children = paragraph.into_iter().map(|node| node.to_string()).collect::<Vec<_>>();
+    assert_eq!(children, ["a", "<b>b</b>"]);
+}
+
+#[test]
+fn iter_tags_index() {
+    let tree = index();
+    let filter = Filter::new().tag_name("input");
+    let selected = tree.select(&filter);
+    let inputs = tree
+        .iter_tags()
+        .filter(|(tag, _)| tag.as_name() == "input")
+        .map(|(tag, child)| Html::Tag { tag: tag.clone(), child: Box::new(child.clone()) })
+        .collect::<Vec<_>>();
+    let expected = selected.map(|node| node.as_html().clone()).collect::<Vec<_>>();
+    assert_eq!(inputs, expected);
+
+    let title = tree
+        .iter_tags()
+        .find(|(tag, _)| tag.as_name() == "title")
+        .and_then(|(_, child)| child.as_text());
+    assert_eq!(title, Some("Test HTML"));
+}
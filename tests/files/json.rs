@@ -0,0 +1,53 @@
+use std::fs::read_to_string;
+
+use html_filter::*;
+use serde_json::{Value, json};
+
+fn parse_json(html: &Html) -> Value {
+    serde_json::from_str(&html.to_json()).unwrap_or_else(|err| panic!("{err}"))
+}
+
+#[test]
+fn json_escapes() {
+    let html = Html::parse("<p title='a\"b\\c'>line\nnext\ttab\u{1}</p>")
+        .unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(
+        html.to_json(),
+        r#"[{"type":"tag","name":"p","attrs":[{"name":"title","value":"a\"b\\c"}],"children":[{"type":"text","content":"line\nnext\ttab\u0001"}]}]"#
+    );
+    assert_eq!(
+        parse_json(&html),
+        json!([{"type": "tag", "name": "p", "attrs": [{"name": "title", "value": "a\"b\\c"}], "children": [{"type": "text", "content": "line\nnext\ttab\u{1}"}]}])
+    );
+}
+
+#[test]
+fn json_index() {
+    let content = read_to_string("tests/data/index.html").expect("file exists");
+    let html = Html::parse(&content).unwrap_or_else(|err| panic!("{err}"));
+    let value = parse_json(&html);
+    let roots = value.as_array().expect("array of roots");
+    assert!(!roots.is_empty());
+    assert!(roots.iter().all(|root| root.get("type").is_some()));
+}
+
+#[test]
+fn json_nested() {
+    let html = Html::parse("<!doctype><ul><li>a</li><li><b>b</b>c</li></ul><br>text")
+        .unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(
+        parse_json(&html),
+        json!([
+            {"type": "doctype", "name": "doctype", "attr": null},
+            {"type": "tag", "name": "ul", "attrs": [], "children": [
+                {"type": "tag", "name": "li", "attrs": [], "children": [{"type": "text", "content": "a"}]},
+                {"type": "tag", "name": "li", "attrs": [], "children": [
+                    {"type": "tag", "name": "b", "attrs": [], "children": [{"type": "text", "content": "b"}]},
+                    {"type": "text", "content": "c"}
+                ]}
+            ]},
+            {"type": "tag", "name": "br", "attrs": [], "children": []},
+            {"type": "text", "content": "text"}
+        ])
+    );
+}
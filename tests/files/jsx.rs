@@ -0,0 +1,44 @@
+use html_filter::*;
+
+fn jsx(input: &str) -> String {
+    Html::parse(input).expect("Invalid html").to_jsx()
+}
+
+#[test]
+fn jsx_attributes() {
+    assert_eq!(
+        jsx(
+            r#"<td colspan="2" CLASS="a" data-id="1" aria-label='Say "hi"' onclick="go()" tabindex="0">x</td>"#
+        ),
+        r#"<td colSpan="2" className="a" data-id="1" aria-label="Say &quot;hi&quot;" onclick="go()" tabIndex="0">x</td>"#
+    );
+    assert_eq!(jsx("<input disabled>"), "<input disabled />");
+}
+
+#[test]
+fn jsx_raw_text() {
+    assert_eq!(
+        jsx("<style>p { color: \"red\" }\n</style><div></div><!-- a */ b -->"),
+        r#"<><style>{"p { color: \"red\" }\n"}</style><div />{/* a * / b */}</>"#
+    );
+    assert_eq!(jsx("<!DOCTYPE html><p>{a} > b</p>"), "<p>&#123;a&#125; &gt; b</p>");
+}
+
+#[test]
+fn jsx_roots() {
+    assert_eq!(jsx("\n<main><p>a</p><p>b</p></main>\n"), "\n<main><p>a</p><p>b</p></main>\n");
+    assert_eq!(jsx("<p>a</p><p>b</p>"), "<><p>a</p><p>b</p></>");
+    assert_eq!(jsx("text"), "<>text</>");
+    assert_eq!(jsx(""), "<></>");
+}
+
+#[test]
+fn jsx_style() {
+    assert_eq!(
+        jsx(
+            r#"<div style="background: url('a;b.png'); -ms-transition: none; --main-color: #fff;">x</div>"#
+        ),
+        r##"<div style={{ background: "url('a;b.png')", msTransition: "none", "--main-color": "#fff" }}>x</div>"##
+    );
+    assert_eq!(jsx(r#"<div style=""></div>"#), "<div style={{}} />");
+}
@@ -0,0 +1,114 @@
+use std::env::temp_dir;
+use std::fs::{read_to_string, remove_file, write};
+
+use html_filter::*;
+
+/// Returns the binary format of index.html.
+fn index_bin() -> (Html, Vec<u8>) {
+    let content = read_to_string("tests/data/index.html").expect("index.html exists");
+    let html = Html::parse(&content).unwrap_or_else(|err| panic!("{err}"));
+    let bin = html.to_bin();
+    (html, bin)
+}
+
+#[test]
+fn lazy_children() {
+    let html = Html::parse("<ul><li>1</li><li></li></ul><!-- end -->")
+        .unwrap_or_else(|err| panic!("{err}"));
+    let lazy = LazyHtml::from_bin(html.to_bin()).unwrap_or_else(|err| panic!("{err}"));
+    let root = lazy.root().unwrap_or_else(|err| panic!("{err}"));
+    assert!(root.tag().unwrap_or_else(|err| panic!("{err}")).is_none());
+    let children = root.children().unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(children.len(), 2);
+    let list = children.first().expect("two children");
+    let items = list.children().unwrap_or_else(|err| panic!("{err}"));
+    let texts = items
+        .iter()
+        .map(|item| item.children().map(|nodes| nodes.len()))
+        .collect::<Result<Vec<_>, _>>();
+    assert_eq!(texts, Ok(vec![1, 0]));
+    let comment = children.get(1).expect("two children");
+    assert_eq!(comment.children(), Ok(vec![]));
+    assert_eq!(comment.to_html(), Ok(Html::Comment(" end ".to_owned())));
+}
+
+#[test]
+fn lazy_errors() {
+    assert_eq!(
+        LazyHtml::from_bin(b"HFBN".to_vec()),
+        Err("Invalid binary tree: unexpected end of data.".to_owned())
+    );
+    let trailing =
+        LazyHtml::from_bin(b"HFBN\x01\x02\x00\x00".to_vec()).unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(
+        trailing.to_html(),
+        Err("Invalid binary tree: unexpected bytes after the root node.".to_owned())
+    );
+    let missing = LazyHtml::open("tests/data/missing.bin").expect_err("missing file");
+    assert!(missing.starts_with("Failed to read 'tests/data/missing.bin': "), "{missing}");
+}
+
+#[test]
+fn lazy_open() {
+    let (html, bin) = index_bin();
+    let path = temp_dir().join("html-filter-lazy-open.bin");
+    write(&path, bin).expect("temp dir is writable");
+    let lazy = LazyHtml::open(&path);
+    remove_file(&path).expect("file was created");
+    let loaded = lazy.unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(loaded.to_html(), Ok(html));
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn lazy_open_mmap() {
+    let (html, bin) = index_bin();
+    let path = temp_dir().join("html-filter-lazy-open-mmap.bin");
+    write(&path, &bin).expect("temp dir is writable");
+    // SAFETY: the file is only removed after the tree is dropped.
+    let mapped = unsafe { LazyHtml::open_mmap(&path) }.unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(mapped.to_html(), Ok(html));
+    assert_eq!(Ok(&mapped), LazyHtml::from_bin(bin).as_ref());
+    drop(mapped);
+    remove_file(&path).expect("file was created");
+
+    // SAFETY: the file doesn't exist.
+    let missing = unsafe { LazyHtml::open_mmap("tests/data/missing.bin") }.expect_err("missing");
+    assert!(missing.starts_with("Failed to map 'tests/data/missing.bin': "), "{missing}");
+}
+
+#[test]
+fn lazy_positions() {
+    let html =
+        Html::parse("<table><tr><td>a</td><td>b</td><td>c</td></tr></table><p>1</p><p>2</p>")
+            .unwrap_or_else(|err| panic!("{err}"));
+    let lazy = LazyHtml::from_bin(html.to_bin()).unwrap_or_else(|err| panic!("{err}"));
+    for filter in [
+        Filter::new().tag_name("td").nth_child(2),
+        Filter::new().tag_name("td").nth_last_child(1),
+        Filter::new().tag_name("p").first_child(),
+        Filter::new().tag_name("td").nth_child(2).inside_tag("tr"),
+        Filter::new().tag_name("tr").nth_child(1),
+    ] {
+        let found = html.find_all(&filter).into_iter().cloned().collect::<Vec<_>>();
+        assert_eq!(lazy.select(&filter), Ok(found), "{filter:?}");
+    }
+    let second = Filter::new().tag_name("td").nth_child(2);
+    assert_eq!(lazy.select(&second), Ok(vec![Html::parse("<td>b</td>").unwrap_or_default()]));
+}
+
+#[test]
+fn lazy_select() {
+    let (html, bin) = index_bin();
+    let lazy = LazyHtml::from_bin(bin).unwrap_or_else(|err| panic!("{err}"));
+    for filter in [
+        Filter::new().tag_name("li"),
+        Filter::new().attribute_value("type", "radio"),
+        Filter::new()
+            .like(&Html::parse("<label>Text</label>").unwrap_or_else(|err| panic!("{err}")), 0),
+    ] {
+        let expected = html.select(&filter).map(|node| node.as_html().clone()).collect::<Vec<_>>();
+        assert!(!expected.is_empty());
+        assert_eq!(lazy.select(&filter), Ok(expected));
+    }
+}
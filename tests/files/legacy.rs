@@ -0,0 +1,58 @@
+use html_filter::*;
+
+/// Parses the html with the legacy semantics of the legacy elements.
+fn parse_legacy(html: &str) -> Html {
+    Html::parse_with(html, ParseOptions::new().legacy_tags(LegacyTags::Honor))
+        .unwrap_or_else(|err| panic!("{err}"))
+}
+
+#[test]
+fn legacy_listing_xmp_raw_text() {
+    let html = parse_legacy("<listing><p>a &amp; b</p></listing><xmp><!-- c --></xmp><p>d</p>");
+    let Html::Vec(vec) = html else { panic!("three root nodes") };
+    let [listing, xmp, para] = &*vec else { panic!("three root nodes") };
+    assert_eq!(
+        listing.as_tag().map(|(_, child)| child),
+        Some(&Html::Text("<p>a &amp; b</p>".into()))
+    );
+    assert_eq!(xmp.as_tag().map(|(_, child)| child), Some(&Html::Text("<!-- c -->".into())));
+    assert_eq!(*para, "<p>d</p>");
+}
+
+#[test]
+fn legacy_normal_by_default() {
+    let page = "<listing><b>a</b></listing><plaintext><i>b</i></plaintext><p>c</p>";
+    let html = Html::parse(page).unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(html, page);
+    assert_eq!(html.clone().filter(&Filter::new().tag_name("b")), "<b>a</b>");
+    assert_eq!(html.filter(&Filter::new().tag_name("p")), "<p>c</p>");
+}
+
+#[test]
+fn legacy_plaintext_swallows_document() {
+    let html =
+        parse_legacy("<div>Before<plaintext>A <b>bold</b> end</plaintext></div><p>After</p>");
+    assert_eq!(
+        html,
+        "<div>Before<plaintext>A &lt;b>bold&lt;/b> \
+         end&lt;/plaintext>&lt;/div>&lt;p>After&lt;/p></plaintext></div>"
+    );
+    let rendered = html.to_string();
+    assert_eq!(Html::parse(&rendered).unwrap_or_else(|err| panic!("{err}")), rendered);
+    let filtered = html.filter(&Filter::new().tag_name("plaintext"));
+    assert_eq!(
+        filtered.as_tag().map(|(_, child)| child),
+        Some(&Html::Text("A <b>bold</b> end</plaintext></div><p>After</p>".into()))
+    );
+    assert!(parse_legacy("<plaintext>").as_tag().is_some());
+}
+
+#[test]
+fn legacy_script_keeps_tags() {
+    let html = Html::parse("<script>if (a<b) { x = '<p>'; }</script>")
+        .unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(
+        html.as_tag().map(|(_, child)| child),
+        Some(&Html::Text("if (a<b) { x = '<p>'; }".into()))
+    );
+}
@@ -0,0 +1,46 @@
+use html_filter::*;
+
+macro_rules! test_like {
+    ($($name:ident: $example:expr, $tolerance:expr => $expect:expr)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let html = Html::parse(INPUT).unwrap_or_else(|err| panic!("{err}"));
+                let example = Html::parse($example).unwrap_or_else(|err| panic!("{err}"));
+                assert_eq!(html.to_filtered(&Filter::new().like(&example, $tolerance)), $expect);
+            }
+        )*
+    };
+}
+
+/// List of products, with mangled classes and slight variations.
+const INPUT: &str = r#"<ul>
+    <li class="a1"><h3>Phone</h3><span>10</span></li>
+    <li class="b2"><h4>Case</h4><span>5</span></li>
+    <li class="c3"><h3>Cable</h3><span>2</span><!-- sale --></li>
+    <li class="d4"><h3>Charger</h3></li>
+</ul>"#;
+
+test_like!(
+
+like_exact: "<li><h3>Name</h3><!-- ignored --><span>Price</span></li>", 0 =>
+r#"<li class="a1"><h3>Phone</h3><span>10</span></li><li class="c3"><h3>Cable</h3><span>2</span><!-- sale --></li>"#
+
+like_rename: "<li><h3>Name</h3><span>Price</span></li>", 1 =>
+r#"<li class="a1"><h3>Phone</h3><span>10</span></li><li class="b2"><h4>Case</h4><span>5</span></li><li class="c3"><h3>Cable</h3><span>2</span><!-- sale --></li>"#
+
+like_delete: "<li><h3>Name</h3><span>Price</span></li>", 2 =>
+r#"<li class="a1"><h3>Phone</h3><span>10</span></li><li class="b2"><h4>Case</h4><span>5</span></li><li class="c3"><h3>Cable</h3><span>2</span><!-- sale --></li><li class="d4"><h3>Charger</h3></li>"#
+
+like_no_tag: "Only text", 10 =>
+""
+
+);
+
+#[test]
+fn like_combined() {
+    let html = Html::parse(INPUT).unwrap_or_else(|err| panic!("{err}"));
+    let example = Html::parse("<li><h3>Name</h3></li>").unwrap_or_else(|err| panic!("{err}"));
+    let filter = Filter::new().like(&example, 2).attribute_value("class", "a1");
+    assert_eq!(html.to_filtered(&filter), r#"<li class="a1"><h3>Phone</h3><span>10</span></li>"#);
+}
@@ -0,0 +1,65 @@
+use html_filter::extract::{self, LinkKind};
+use html_filter::*;
+
+macro_rules! test_classify {
+    ($($name:ident: $tag:literal $href:literal, $base:expr => $kind:ident)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let source = format!(r#"<{} href="{}"></{}>"#, $tag, $href, $tag);
+                let html = Html::parse(&source).unwrap_or_else(|err| panic!("{err}"));
+                let link = extract::links(&html).pop().expect("one link");
+                assert_eq!(link.classify($base), LinkKind::$kind);
+            }
+        )*
+    };
+}
+
+test_classify!(
+
+classify_anchor: "a" "#section", None => Anchor
+classify_asset_extension: "a" "https://other.org/a.PNG?size=2", None => Asset
+classify_asset_link: "link" "/style", None => Asset
+classify_empty: "a" "", None => Anchor
+classify_external: "a" "https://other.org/page", Some("https://example.com") => External
+classify_external_no_base: "a" "https://example.com/page", None => External
+classify_internal_absolute: "a" "https://user@EXAMPLE.com:443/page", Some("https://example.com/") => Internal
+classify_internal_relative: "area" "../page.html", Some("https://example.com/") => Internal
+classify_javascript: "a" "JavaScript:void(0)", None => Javascript
+classify_protocol_relative: "a" "//cdn.example.com/page", Some("https://example.com") => External
+classify_tel: "a" "tel:+33100000000", None => Tel
+
+);
+
+#[test]
+fn links_extraction() {
+    let html = Html::parse(
+        r#"<head><link rel="stylesheet" href="a.css" /></head><a href="/x" rel="nofollow">X <i>y</i></a><a name="z">z</a><img href="/no" />"#,
+    )
+    .unwrap_or_else(|err| panic!("{err}"));
+    let links = extract::links(&html);
+    assert_eq!(links.len(), 2);
+    let link = links.get(1).expect("two links");
+    assert_eq!(link.as_tag(), "a");
+    assert_eq!(link.as_href(), "/x");
+    assert_eq!(link.as_rel(), Some("nofollow"));
+    assert_eq!(link.as_text(), "X y");
+    assert_eq!(links.first().expect("two links").as_tag(), "link");
+}
+
+#[test]
+fn links_rel_flags() {
+    let html = Html::parse(
+        r#"<a href="/a">A</a><a href="/b" rel=" noreferrer  EXTERNAL ">B</a><a href="/c" rel="sponsored">C</a>"#,
+    )
+    .unwrap_or_else(|err| panic!("{err}"));
+    let links = extract::links(&html);
+    let flags = links.iter().map(extract::Link::as_rel_flags).collect::<Vec<_>>();
+    assert!(flags.first().expect("three links").is_empty());
+    let second = flags.get(1).expect("three links");
+    assert!(second.contains(extract::Rel::Noreferrer));
+    assert!(second.contains(extract::Rel::External));
+    assert!(!second.contains(extract::Rel::Nofollow));
+    let followable = links.iter().map(extract::Link::is_followable).collect::<Vec<_>>();
+    assert_eq!(followable, [true, true, false]);
+}
@@ -0,0 +1,78 @@
+use html_filter::*;
+
+fn markdown(input: &str) -> String {
+    Html::parse(input).unwrap_or_else(|err| panic!("{err}")).to_markdown()
+}
+
+#[test]
+fn markdown_code() {
+    assert_eq!(
+        markdown(
+            "<p>Run <code>cargo   test</code> or <code>a`b</code>.</p><pre><code \
+             class=\"language-rust\">\nfn main() {\n    let x = 1 &lt; 2;\n}\n</code></pre>"
+        ),
+        "Run `cargo test` or ``a`b``.\n\n```rust\nfn main() {\n    let x = 1 < 2;\n}\n```\n"
+    );
+}
+
+#[test]
+fn markdown_escapes() {
+    assert_eq!(
+        markdown("<p>- not *a* list &amp; [link]</p><p># title_name</p>"),
+        "\\- not \\*a\\* list & \\[link\\]\n\n\\# title\\_name\n"
+    );
+}
+
+#[test]
+fn markdown_inline() {
+    assert_eq!(
+        markdown(
+            r#"<p>A <em> spaced </em>word, <strong>bold</strong>, <del>old</del><br>next <a href="/a b" title="T">link</a> <img src="/i.png" alt="pic"> <a>plain</a></p>"#
+        ),
+        "A *spaced* word, **bold**, ~~old~~\\\nnext [link](</a b> \"T\") ![pic](/i.png) plain\n"
+    );
+}
+
+#[test]
+fn markdown_non_breaking_spaces() {
+    assert_eq!(
+        markdown("<p>10\u{a0}\u{a0}km  <code>a\u{a0}b</code></p>"),
+        "10\u{a0}\u{a0}km `a\u{a0}b`\n"
+    );
+}
+
+#[test]
+fn markdown_lists() {
+    assert_eq!(
+        markdown(
+            "<ul>\n  <li>One</li>\n  <li>Two<ol \
+             start=\"3\"><li>Three</li><li>Four</li></ol></li>\n</ul><ol><li>Single</li></ol>"
+        ),
+        "- One\n- Two\n  3. Three\n  4. Four\n\n1. Single\n"
+    );
+}
+
+#[test]
+fn markdown_structure() {
+    assert_eq!(
+        markdown(
+            "<!DOCTYPE html><html><head><title>T</title><style>p \
+             {}</style></head><body><h2>Part</h2><div>Intro<p>Text</p></\
+             div><hr><blockquote><p>Quote</p><p>More</p></blockquote><script>x()</script></body></\
+             html>"
+        ),
+        "## Part\n\nIntro\n\nText\n\n---\n\n> Quote\n>\n> More\n"
+    );
+    assert_eq!(Html::Empty.to_markdown(), "");
+}
+
+#[test]
+fn markdown_table() {
+    assert_eq!(
+        markdown(
+            "<table><thead><tr><th>Name</th><th>Value</th></tr></thead><tbody><tr><td>a|b</\
+             td><td><b>1</b></td></tr><tr><td>c</td></tr></tbody></table>"
+        ),
+        "| Name | Value |\n| --- | --- |\n| a\\|b | **1** |\n| c |  |\n"
+    );
+}
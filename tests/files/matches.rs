@@ -1,9 +1,6 @@
 use html_filter::*;
 
-#[test]
-#[expect(clippy::shadow_unrelated, reason = "long chain of match")]
-fn manual() {
-    let html = r#"
+const INPUT: &str = r#"
 <!DOCTYPE html>
 <html lang="en">
 <head>
@@ -15,7 +12,25 @@ fn manual() {
 </body>
 </html>
     "#;
-    let tree = Html::parse(html).expect("failed to parse");
+
+#[test]
+fn iterators() {
+    let tree = Html::parse(INPUT).expect("failed to parse");
+    let title = tree
+        .children()
+        .find(|node| node.as_tag().is_some_and(|(tag, _)| tag.as_name() == "html"))
+        .and_then(|html| {
+            html.children().find_map(Html::as_tag).filter(|(tag, _)| tag.as_name() == "head")
+        })
+        .and_then(|(_, head)| head.iter_tags().find(|(tag, _)| tag.as_name() == "title"))
+        .and_then(|(_, child)| child.as_text());
+    assert_eq!(title, Some("Document"));
+}
+
+#[test]
+#[expect(clippy::shadow_unrelated, reason = "long chain of match")]
+fn manual() {
+    let tree = Html::parse(INPUT).expect("failed to parse");
     if let Html::Vec(vec) = &tree {
         for elt in vec {
             if let Html::Tag { tag, child, .. } = elt {
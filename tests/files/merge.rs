@@ -0,0 +1,41 @@
+use std::fs::read_to_string;
+
+use html_filter::*;
+
+#[test]
+fn merge_index() {
+    let content = read_to_string("tests/data/index.html").expect("file exists");
+    let tree = Html::parse(&content).unwrap_or_else(|err| panic!("{err}"));
+    let merged = Html::merge_bodies(vec![tree.clone(), tree]).to_string();
+
+    assert!(
+        merged.starts_with(
+            r#"<!><html lang="en"><head><!-- prettier-ignore --><meta charset="UTF-8">"#
+        ),
+        "{merged}"
+    );
+
+    for (pattern, count) in
+        [("<html", 1), ("<head>", 1), ("<title>", 1), ("<body>", 1), ("<h1>", 2)]
+    {
+        assert_eq!(merged.matches(pattern).count(), count, "{pattern}");
+    }
+}
+
+#[test]
+fn merge_fragments() {
+    let parts = vec![
+        Html::parse("<title>A</title>text").unwrap_or_else(|err| panic!("{err}")),
+        Html::parse("<body class=\"b\"><p>1</p></body>").unwrap_or_else(|err| panic!("{err}")),
+    ];
+    assert_eq!(
+        Html::merge_bodies(parts),
+        "<html><head></head><body class=\"b\"><title>A</title>text<p>1</p></body></html>"
+    );
+}
+
+#[test]
+fn concat_empty() {
+    assert_eq!(Html::concat(vec![]), Html::Empty);
+    assert_eq!(Html::concat(vec![Html::Empty, Html::Text("a".into())]), "a");
+}
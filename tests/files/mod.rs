@@ -1,17 +1,170 @@
+/// Test the flat representation of the trees.
+pub mod arena;
+/// Test the maximum number of attributes of the tags.
+pub mod attributes;
+/// Test the audits of the structure of the trees.
+pub mod audit;
+/// Test the selection of the nodes between two markers.
+pub mod between;
+/// Test the binary format of the trees.
+pub mod binary;
+/// Test the parsing into a tree that borrows the input.
+pub mod borrowed;
+/// Test the budgets of the parser and of the filters.
+pub mod budget;
+/// Test the parsing of bytes that aren't valid UTF-8.
+pub mod bytes;
+/// Test the undo and redo of the edits.
+pub mod changelog;
+/// Test the construction of tags from their parts.
+pub mod checked;
+/// Test the fuzzy matching of the classes.
+pub mod classes;
+/// Test the classification of the tokens for syntax highlighting.
+pub mod classify;
+/// Test the combination of filters.
+pub mod combine;
+/// Test the handling of the control characters.
+pub mod control;
+/// Test the replay of inputs through the whole pipeline.
+pub mod debug;
+/// Test the injection of the default attributes.
+pub mod defaults;
+/// Test the reports of the repaired malformed html.
+pub mod diagnostics;
+/// Test the comparison of the matches on two documents.
+pub mod diff;
+/// Test the accessors of the head and body of a document.
+pub mod document;
+/// Test the construction of trees with the element builder.
+pub mod element_builder;
+/// Test the decoding and encoding of the character references.
+pub mod entities;
 /// Test expected parsing errors.
 pub mod errors;
+/// Test the validation of the escaping of the attribute values.
+pub mod escaping;
+/// Test the digests of the content of the trees.
+pub mod etag;
+/// Test the C interface.
+#[cfg(feature = "ffi")]
+pub mod ffi;
 /// Test filters on index.html.
 pub mod filter;
 /// Test finders on index.html.
 pub mod find;
 /// Test no filter keeps html intact.
 pub mod full;
+/// Test the highlighting of terms in the texts.
+pub mod highlight;
+/// Test the parse-time callbacks.
+pub mod hooks;
+/// Test the conformance with the html5lib-tests fixtures.
+pub mod html5lib;
+/// Test the restriction of the matches to the descendants of other tags.
+pub mod inside;
+/// Test the iterators over the nodes of the trees.
+pub mod iter;
+/// Test the conversion of the trees to JSON.
+pub mod json;
+/// Test the conversion of the trees to JSX.
+pub mod jsx;
+/// Test the lazy loading of the binary trees.
+pub mod lazy;
+/// Test the treatment of the legacy elements.
+pub mod legacy;
+/// Test the matching of the tags similar to an example.
+pub mod like;
+/// Test the extraction and classification of the links.
+pub mod links;
+/// Test the conversion of the trees to Markdown.
+pub mod markdown;
 /// Test that ana html is parsed correctly.
 pub mod matches;
+/// Test the concatenation and merging of documents.
+pub mod merge;
+/// Test the namespaced attributes of the SVG elements.
+pub mod namespaces;
+/// Test the navigation from the selected nodes to their ancestors.
+pub mod node_ref;
+/// Test the sets of node types kept by the filters.
+pub mod node_types;
+/// Test the filtering on multiple threads.
+#[cfg(feature = "rayon")]
+pub mod par_filter;
+/// Test the parsing on multiple threads.
+pub mod parallel;
+/// Test the rendering of the diffs between two documents.
+pub mod patch;
+/// Test the cleanup pipelines.
+pub mod pipeline;
+/// Test the positions of the wanted tags among their siblings.
+pub mod position;
+/// Test the custom predicates of the filters.
+pub mod predicates;
+/// Test the reading of the documents with the prelude.
+pub mod prelude;
+/// Test the re-indentation of the trees.
+pub mod pretty;
+/// Test the pruning of the sections with little content.
+pub mod prune;
+/// Test the conversion of the trees to Pug.
+pub mod pug;
+/// Test the Python module.
+#[cfg(feature = "pyo3")]
+pub mod python;
+/// Test the access to the contents of the raw text tags.
+pub mod raw_text;
+/// Test the repair of invalid html.
+pub mod recover;
+/// Test the regular expressions in the filters.
+#[cfg(feature = "regex")]
+pub mod regex;
+/// Test the extraction of the regions delimited by comments.
+pub mod regions;
+/// Test the parsing of fragments to replace nodes.
+pub mod reparse;
+/// Test the reports on the filters without matches.
+pub mod report;
+/// Test the extraction of the resources with their spans.
+pub mod resources;
+/// Test the parsing of long runs of characters.
+pub mod runs;
+/// Test the removal of the active content.
+pub mod sanitize;
+/// Test the scoring of the candidates.
+pub mod scored;
+/// Test the serialisation of the trees with serde.
+#[cfg(feature = "serde")]
+pub mod serde;
+/// Test the custom serializers.
+pub mod serializer;
+/// Test the filling of the placeholders.
+pub mod slots;
+/// Test the inline storage of the short strings.
+pub mod small_string;
+/// Test the splitting of a tree around a marker.
+pub mod split;
+/// Test the statistics of the rules of the filters.
+pub mod stats;
+/// Test the parsing of a document received by chunks.
+pub mod stream;
 /// Test filters on a smaller string.
 pub mod strings;
+/// Test the stripping of the sections with a marker attribute.
+pub mod strip;
+/// Test the template placeholders.
+pub mod templates;
+/// Test the filters on the text content of the tags.
+pub mod text_content;
+/// Test the tokens read by the tokenizer.
+pub mod tokenizer;
 /// Test the trimming mechanism.
 pub mod trim;
+/// Test the rewriting of urls.
+pub mod urls;
+/// Test the misuse of void elements.
+pub mod void;
 
 use core::fmt::Debug;
 use std::fs;
@@ -76,10 +229,13 @@ fn format_html(html: &str) -> String {
         }
         formatted = out;
     }
-    handle_auto_closing(&formatted)
-        .replace(" >", ">")
-        .replace("> </meta>", ">")
-        .replace("> </br>", ">")
+    let closed = handle_auto_closing(&formatted).replace(" >", ">");
+    [
+        "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param",
+        "source", "track", "wbr",
+    ]
+    .iter()
+    .fold(closed, |acc, void| acc.replace(&format!("> </{void}>"), ">"))
 }
 
 fn test_maker<T: Debug>(
@@ -0,0 +1,56 @@
+use html_filter::*;
+
+/// SVG document with deprecated namespaced attributes.
+const SVG: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" xml:lang="en"><use xlink:href="#icon" x="1"></use><a href="/new" xlink:href="/old">Link</a></svg>"##;
+
+/// Parses the SVG document.
+fn svg() -> Html {
+    Html::parse(SVG).unwrap_or_else(|err| panic!("{err}"))
+}
+
+#[test]
+fn namespaces_filter_either_form() {
+    let html = svg();
+    let by_legacy = html.to_filtered(&Filter::new().attribute_value("xlink:href", "#icon"));
+    let by_modern = html.to_filtered(&Filter::new().attribute_value("href", "#icon"));
+    assert_eq!(by_legacy, r##"<use xlink:href="#icon" x="1"></use>"##);
+    assert_eq!(by_modern, by_legacy);
+
+    let links = html.to_filtered(&Filter::new().attribute_value("href", "/new"));
+    assert_eq!(links, r#"<a href="/new" xlink:href="/old">Link</a>"#);
+    let shadowed = html.to_filtered(&Filter::new().attribute_value("href", "/old"));
+    assert_eq!(shadowed, "");
+
+    let lang = html.to_filtered(&Filter::new().attribute_value("lang", "en"));
+    assert_eq!(lang.as_tag().map(|(tag, _)| tag.as_name()), Some("svg"));
+    let present = html.to_filtered(&Filter::new().tag_name("use").attribute_present("href"));
+    assert_eq!(present, by_legacy);
+}
+
+#[test]
+fn namespaces_modernize() {
+    let mut html = svg();
+    let pretty = html
+        .to_pretty_string(&PrettyOptions::new().modernize_attributes(true).max_line_length(200));
+    html.modernize_attributes();
+    assert_eq!(
+        html,
+        r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" lang="en"><use href="#icon" x="1"></use><a href="/new">Link</a></svg>"##
+    );
+    assert_eq!(pretty, html.to_pretty_string(&PrettyOptions::new().max_line_length(200)));
+    let twice = html.clone();
+    html.modernize_attributes();
+    assert_eq!(html, twice);
+}
+
+#[test]
+fn namespaces_round_trip() {
+    let html = svg();
+    assert_eq!(html, SVG);
+    let (svg_tag, _) = html.as_tag().unwrap_or_else(|| panic!("svg root"));
+    let prefixes = svg_tag.as_attrs().iter().map(Attribute::as_prefix).collect::<Vec<_>>();
+    assert_eq!(prefixes, [None, Some("xmlns"), Some("xml")]);
+    let local = svg_tag.as_attrs().iter().map(Attribute::as_local_name).collect::<Vec<_>>();
+    assert_eq!(local, ["xmlns", "xlink", "lang"]);
+    assert_eq!(svg_tag.find_attr_value("xml:lang").map(String::as_str), Some("en"));
+}
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+use std::fs::read_to_string;
+
+use html_filter::*;
+
+fn index() -> Html {
+    let content = read_to_string("tests/data/index.html").expect("file exists");
+    Html::parse(&content).unwrap_or_else(|err| panic!("{err}"))
+}
+
+#[test]
+fn node_ref_ancestors() {
+    let tree = index();
+    let filter = Filter::new().attribute_value("type", "radio");
+    let radios = tree.select(&filter).collect::<Vec<_>>();
+    assert_eq!(radios.len(), 2);
+
+    let names = radios
+        .get(1)
+        .expect("two radios")
+        .ancestors()
+        .filter_map(|ancestor| ancestor.as_tag().map(|tag| tag.as_name().to_owned()))
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["form", "section", "main", "body", "html"]);
+}
+
+#[test]
+fn node_ref_closest() {
+    let tree = index();
+    let radio =
+        tree.select(&Filter::new().attribute_value("id", "radio1")).next().expect("radio exists");
+
+    let section = radio.closest(&Filter::new().tag_name("section")).expect("radio in section");
+    let title = section.as_html().to_found(&Filter::new().tag_name("h2"));
+    assert_eq!(title, "<h2>Forms with Various Inputs</h2>");
+
+    assert_eq!(radio.closest(&Filter::new().tag_name("input")), Some(radio.clone()));
+    assert!(radio.closest(&Filter::new().tag_name("header")).is_none());
+}
+
+#[test]
+fn node_ref_effective_lang_dir() {
+    let tree = Html::parse(
+        r#"<div lang="en" dir="rtl"><p xml:lang="de" lang="fr"><i>a</i></p><p lang="" dir="LTR"><b>b</b></p></div><span>c</span>"#,
+    )
+    .unwrap_or_else(|err| panic!("{err}"));
+    let langs = tree
+        .select(&Filter::new().tag_name("i").tag_name("b").tag_name("span"))
+        .map(|node| (node.effective_lang(), node.effective_dir()))
+        .collect::<Vec<_>>();
+    assert_eq!(langs, [
+        (Some("de"), Some(Direction::Rtl)),
+        (Some(""), Some(Direction::Ltr)),
+        (None, None)
+    ]);
+}
+
+#[test]
+fn node_ref_find_text() {
+    let tree = index();
+    let options = tree.find_text("Option");
+    let names = options
+        .iter()
+        .filter_map(|found| found.as_tag().map(|tag| tag.as_name().to_owned()))
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["label", "label"]);
+
+    let form = options
+        .first()
+        .and_then(|found| found.closest(&Filter::new().tag_name("form")))
+        .expect("labels in form");
+    assert_eq!(
+        form.parent().and_then(|section| section.as_tag().map(Tag::as_name)),
+        Some("section")
+    );
+
+    assert!(tree.find_text("not in the page").is_empty());
+}
+
+#[test]
+fn node_ref_ids() {
+    let tree = index();
+    let ids = tree.select(&Filter::new()).filter_map(|node| node.id()).collect::<Vec<_>>();
+    assert!(ids.is_sorted(), "{ids:?}");
+
+    let radio =
+        tree.find_text("Option 2").pop().and_then(|label| label.id()).expect("label exists");
+    let mut filtered = tree.to_filtered(&Filter::new().tag_name("form"));
+    assert_eq!(
+        filtered.node_by_id(radio).map(|label| label.as_html().to_string()),
+        Some(r#"<label for="radio2">Option 2</label>"#.to_owned())
+    );
+
+    filtered.strip_where_attr("for", |value| value == "radio2");
+    assert!(filtered.node_by_id(radio).is_none());
+}
+
+#[test]
+fn node_ref_ids_inserted() {
+    let unique = |html: &Html| {
+        let mut ids = html.select(&Filter::new()).filter_map(|node| node.id()).collect::<Vec<_>>();
+        let count = ids.len();
+        ids.sort_unstable();
+        ids.dedup();
+        ids.len() == count
+    };
+    let parse = |input: &str| Html::parse(input).unwrap_or_else(|err| panic!("{err}"));
+
+    let mut html = parse("<p>a b a</p>");
+    html.wrap_text_matches("a", &Tag::from(("mark".to_owned(), Box::default())));
+    assert!(unique(&html));
+
+    let mut page = parse(r#"<slot name="nav"></slot><main><slot name="nav"></slot></main>"#);
+    page.fill_slots(&HashMap::from([("nav", parse("<nav><a>Home</a></nav>"))]));
+    assert!(unique(&page));
+
+    let mut list = parse("<ul><li>First</li><li>Second</li></ul>");
+    let second = list.select(&Filter::new().tag_name("li")).nth(1).and_then(|node| node.id());
+    let items = list.as_tag().and_then(|(_, child)| child.as_vec());
+    let copy = items.and_then(|nodes| nodes.get(1)).cloned().expect("second item");
+    list.apply_edit(Edit::Insert { path: vec![0], node: copy }).expect("valid path");
+    assert!(unique(&list));
+
+    let mut log = ChangeLog::new();
+    log.apply(&mut list, Edit::Remove { path: vec![2] }).expect("valid path");
+    assert!(log.undo(&mut list).expect("valid tree"));
+    assert_eq!(
+        list.select(&Filter::new().tag_name("li")).nth(2).and_then(|node| node.id()),
+        second
+    );
+}
+
+#[test]
+fn node_ref_matches() {
+    let tree = index();
+    for filter in [
+        Filter::new().tag_name("li"),
+        Filter::new().tag_name("section"),
+        Filter::new().tag_name("div").tag_name("section"),
+        Filter::new().tag_name("blink"),
+    ] {
+        assert_eq!(tree.select(&filter).count(), tree.count(&filter), "{filter:?}");
+        let outer = tree
+            .select(&filter)
+            .filter(|node| node.parent().and_then(|parent| parent.closest(&filter)).is_none())
+            .map(|node| node.as_html().to_string())
+            .collect::<String>();
+        assert_eq!(tree.select(&filter).to_owned_tree().to_string(), outer, "{filter:?}");
+    }
+
+    let items = Filter::new().tag_name("li");
+    let mut matches = tree.select(&items);
+    let first = matches.next().expect("a list item");
+    assert_eq!(first.parent().and_then(|parent| parent.as_tag().map(Tag::as_name)), Some("ul"));
+    assert_eq!(matches.count(), tree.count(&items) - 1);
+}
+
+#[test]
+fn node_ref_siblings() {
+    let tree = Html::parse(
+        "<dl><dt>Name</dt><dd>Widget</dd><!-- price --><dt>Price</dt>\n<dd>42</dd></dl><p>End</p>",
+    )
+    .unwrap_or_else(|err| panic!("{err}"));
+    let price = tree.find_text("Price").pop().expect("price label exists");
+
+    assert_eq!(*price.next_sibling().expect("whitespace").as_html(), "\n");
+    assert_eq!(*price.next_sibling_tag().expect("value").as_html(), "<dd>42</dd>");
+    assert_eq!(*price.prev_sibling().expect("comment").as_html(), "<!-- price -->");
+    assert_eq!(*price.prev_sibling_tag().expect("name").as_html(), "<dd>Widget</dd>");
+
+    let value = price.next_sibling_tag().expect("value");
+    assert_eq!(value.parent(), price.parent());
+    assert!(value.next_sibling().is_none());
+
+    let list = price.parent().expect("list");
+    assert_eq!(*list.next_sibling().expect("paragraph").as_html(), "<p>End</p>");
+    assert!(list.prev_sibling().is_none());
+
+    let alone = Html::parse("<p><b>alone</b></p>").unwrap_or_else(|err| panic!("{err}"));
+    let bold = alone.select(&Filter::new().tag_name("b")).next().expect("bold exists");
+    assert!(bold.next_sibling().is_none());
+    assert!(bold.prev_sibling_tag().is_none());
+}
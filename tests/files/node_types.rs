@@ -0,0 +1,82 @@
+use html_filter::*;
+
+const HTML: &str = "<!doctype html><div> a <!-- b --><p> c <!-- d --></p></div>";
+
+/// Returns the output of the filter on [`HTML`].
+fn filtered(filter: &Filter) -> String {
+    Html::parse(HTML).unwrap_or_else(|err| panic!("{err}")).to_filtered(filter).to_string()
+}
+
+#[test]
+fn node_types_drop() {
+    assert_eq!(
+        filtered(&Filter::new().tag_name("p").drop_types(NodeTypes::COMMENT)),
+        "<!doctype html> a <p> c </p>"
+    );
+    assert_eq!(filtered(&Filter::new().tag_name("p").drop_types(NodeTypes::ALL)), "<p></p>");
+}
+
+#[test]
+fn node_types_keep() {
+    assert_eq!(filtered(&Filter::new().tag_name("p").keep_types(NodeTypes::NONE)), "<p></p>");
+    assert_eq!(
+        filtered(&Filter::new().tag_name("p").keep_types(NodeTypes::COMMENT | NodeTypes::DOCTYPE)),
+        "<!doctype html><!-- b --><p><!-- d --></p>"
+    );
+    assert_eq!(
+        filtered(&Filter::new().no_tags().keep_types(NodeTypes::TEXT)),
+        filtered(&Filter::new().no_tags().none_except_text())
+    );
+}
+
+#[test]
+fn node_types_override() {
+    assert_eq!(
+        filtered(&Filter::new().tag_name("p").keep_types(NodeTypes::TEXT).comment(true)),
+        " a <!-- b --><p> c <!-- d --></p>"
+    );
+    assert_eq!(
+        filtered(&Filter::new().tag_name("p").comment(true).keep_types(NodeTypes::TEXT)),
+        " a <p> c </p>"
+    );
+}
+
+#[test]
+fn node_types_set() {
+    let types = NodeTypes::TEXT | NodeTypes::COMMENT;
+    assert!(types.contains(NodeTypes::COMMENT));
+    assert!(!types.contains(NodeTypes::ALL));
+    assert_eq!(!types, NodeTypes::DOCTYPE);
+    assert_eq!(!NodeTypes::ALL, NodeTypes::NONE);
+    assert_eq!(types & NodeTypes::TEXT, NodeTypes::TEXT);
+    assert_eq!(NodeTypes::ALL - types, NodeTypes::DOCTYPE);
+    assert!(NodeTypes::default().is_empty());
+}
+
+#[test]
+fn node_types_shims() {
+    assert_eq!(Filter::new().all_except_text(), Filter::new().drop_types(NodeTypes::TEXT));
+    assert_eq!(Filter::new().all_except_comment(), Filter::new().all(true).comment(false));
+    assert_eq!(Filter::new().none_except_doctype(), Filter::new().keep_types(NodeTypes::DOCTYPE));
+    assert_eq!(Filter::new().none_except_comment(), Filter::new().all(false).comment(true));
+    assert_eq!(Filter::new().all(true), Filter::new().keep_types(NodeTypes::ALL));
+}
+
+#[test]
+fn node_types_precedence() {
+    assert_eq!(filtered(&Filter::new().all(false).text(true)), "<div> a <p> c </p></div>");
+    assert_eq!(filtered(&Filter::new().text(true).all(false)), "<div><p></p></div>");
+    assert_eq!(
+        Filter::new().all(true).tag_name("p").comment(false),
+        Filter::new().comment(false).tag_name("p").all(true).comment(false)
+    );
+    assert_eq!(filtered(&Filter::new().tag_name("p")), "<p> c <!-- d --></p>");
+    assert_eq!(
+        filtered(&Filter::new().tag_name("p").doctype(true)),
+        "<!doctype html><p> c <!-- d --></p>"
+    );
+    assert_eq!(
+        filtered(&Filter::new().tag_name("div").tag_name("p").except_tag_name("div")),
+        "<p> c <!-- d --></p>"
+    );
+}
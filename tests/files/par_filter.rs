@@ -0,0 +1,42 @@
+use core::fmt::Write as _;
+use std::fs::read_to_string;
+
+use html_filter::*;
+
+fn filters() -> Vec<Filter> {
+    vec![
+        Filter::new().tag_name("input"),
+        Filter::new().tag_name("li").depth(1),
+        Filter::new().attribute_name("id").all(true),
+        Filter::new().no_tags().all_except_comment(),
+        Filter::new().all(true).except_tag_name("section").trim(),
+        Filter::new().tag_name("h2").max_output_nodes(2),
+        Filter::new().tag_name("nothing"),
+        Filter::new().tag_name("section").nth_child(2),
+        Filter::new().tag_name("p").last_child().depth(1),
+    ]
+}
+
+#[test]
+fn par_filter_index() {
+    let content = read_to_string("tests/data/index.html").expect("file exists");
+    let html = Html::parse(&content).unwrap_or_else(|err| panic!("{err}"));
+    for filter in filters() {
+        let expected = html.to_filtered(&filter);
+        assert_eq!(html.par_to_filtered(&filter), expected, "{filter:?}");
+        assert_eq!(html.clone().par_filter(&filter), expected, "{filter:?}");
+    }
+}
+
+#[test]
+fn par_filter_siblings() {
+    let mut input = String::new();
+    for idx in 0u32..200u32 {
+        write!(input, "<section id=\"s{idx}\"><p>{idx}</p><!-- {idx} --></section>")
+            .expect("writing to a string");
+    }
+    let html = Html::parse(&input).unwrap_or_else(|err| panic!("{err}"));
+    for filter in filters() {
+        assert_eq!(html.clone().par_filter(&filter), html.to_filtered(&filter), "{filter:?}");
+    }
+}
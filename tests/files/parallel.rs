@@ -0,0 +1,82 @@
+use core::fmt::Write as _;
+use std::fs::read_to_string;
+
+use html_filter::*;
+
+fn assert_same(html: &str) {
+    let expected = Html::parse(html);
+    for threads in [0, 1, 2, 3, 8] {
+        let tree = Html::parse_parallel(html, threads);
+        assert_eq!(tree, expected, "{threads} threads on {html}");
+        if let Ok(parsed) = tree {
+            let mut ids = vec![];
+            push_ids(&parsed, &mut ids);
+            assert!(ids.is_sorted(), "{threads} threads on {html}");
+        }
+    }
+}
+
+fn push_ids(html: &Html, ids: &mut Vec<NodeId>) {
+    match html {
+        Html::Tag { tag, child } => {
+            ids.push(tag.id());
+            push_ids(child, ids);
+        }
+        Html::Vec(vec) => vec.iter().for_each(|child| push_ids(child, ids)),
+        Html::Comment(_) | Html::Doctype { .. } | Html::Empty | Html::Text(_) => (),
+    }
+}
+
+#[test]
+fn parallel_index() {
+    let content = read_to_string("tests/data/index.html").expect("file exists");
+    assert_same(&content);
+}
+
+#[test]
+fn parallel_large_body() {
+    let mut html = String::from(
+        "<!DOCTYPE html><html><head><style>p { color: red; }</style></head><body class=\"main\">\n",
+    );
+    for idx in 0u32..200u32 {
+        write!(
+            html,
+            "<section id=\"s{idx}\"><h2>Title {idx}</h2><p>Some <b>text</b><br>here</p><img \
+             src='{idx}.png' /></section>\n<!-- end of {idx} -->\n"
+        )
+        .expect("writing to a string");
+        if idx.is_multiple_of(50) {
+            html.push_str("<script>let x = 1 > 0;</script>Loose text\n");
+        }
+    }
+    html.push_str("</body></html>");
+    assert_same(&html);
+}
+
+#[test]
+fn parallel_fragments() {
+    assert_same("<p>First</p>Text<p>Second</p><div><p>Third</p></div>");
+    assert_same("<!DOCTYPE html><h1>A</h1><p>B</p><p>C</p>");
+    assert_same("<body><p>Single</p></body>");
+    assert_same("<body></body><p>After</p>");
+    assert_same("");
+}
+
+#[test]
+fn parallel_fallback() {
+    assert_same("<div>First</div><p>Second<div>Third</div>");
+    assert_same("<p>First</p><p>a -- b</p><p>Third</p>");
+    assert_same("<p>First</p><script>if (a < b) {}</script><p>Third</p>");
+    assert_same("<body><p>A</p></body><body><p>B</p></body>");
+    assert_same("<p>First</p><p>Second</div><p>Third</p>");
+    assert_same("<p>First</p><p class=\"a<b\">Second</p><p>Third</p>");
+    assert_same("<p>First</p><p>Second</p>Third<");
+    assert_same("<p>First</p><p>Second</p><!-- unclosed");
+}
+
+#[test]
+fn parallel_trailing_dash() {
+    assert_same("<br>-<br>");
+    assert_same("<p></p>-<p></p>");
+    assert_same("<body><p>A</p>a -<p>B</p></body>");
+}
@@ -0,0 +1,78 @@
+use html_filter::patch::{render_coloured_diff, render_diff};
+use html_filter::*;
+
+fn diff(old: &str, new: &str) -> String {
+    let old_html = Html::parse(old).expect("Invalid old html");
+    let new_html = Html::parse(new).expect("Invalid new html");
+    render_diff(&old_html, &new_html)
+}
+
+#[test]
+fn patch_attributes() {
+    assert_eq!(
+        diff(
+            r#"<div><a href="/a">Link</a><img src="a.png" /></div>"#,
+            r#"<div><a href="/b">Link</a><img src="a.png" alt="A" /></div>"#
+        ),
+        "@@ /div/a @@\n-   <a href=\"/a\">Link</a>\n@@ /div/img @@\n-   <img src=\"a.png\">\n@@ \
+         /div/a @@\n+   <a href=\"/b\">Link</a>\n@@ /div/img @@\n+   <img src=\"a.png\" \
+         alt=\"A\">\n"
+    );
+}
+
+#[test]
+fn patch_colours() {
+    let old = Html::parse("<p>Old</p>").expect("Invalid html");
+    let new = Html::parse("<p>New</p>").expect("Invalid html");
+    assert_eq!(
+        render_coloured_diff(&old, &new),
+        "\x1b[36m@@ /p @@\x1b[0m\n\x1b[31m- <p>Old</p>\x1b[0m\n\x1b[32m+ <p>New</p>\x1b[0m\n"
+    );
+}
+
+#[test]
+fn patch_formatting() {
+    assert_eq!(
+        diff(
+            "<ul><li>Apple</li><li>Pear</li></ul>",
+            "<ul>\n    <li>\n        Apple\n    </li>\n    <li>Pear</li>\n</ul>\n"
+        ),
+        ""
+    );
+}
+
+#[test]
+fn patch_large() {
+    let items = "<li>Item</li>".repeat(5000);
+    assert_eq!(
+        diff(
+            &format!("<p>Old</p><ul>{items}</ul><p>End</p>"),
+            &format!("<p>New</p><ul>{items}</ul><p>Last</p>")
+        ),
+        "@@ /p[1] @@\n- <p>Old</p>\n+ <p>New</p>\n@@ /p[2] @@\n- <p>End</p>\n+ <p>Last</p>\n"
+    );
+}
+
+#[test]
+fn patch_nested() {
+    assert_eq!(
+        diff(
+            "<body><p>Intro</p><ul><li>Apple</li><li>Pear</li><li>Plum</li></ul></body>",
+            "<body><p>Intro</p><ul><li>Apple</li><li>Plum</li><li>Kiwi</li></ul><!--end--></body>"
+        ),
+        "@@ /body/ul/li[2] @@\n-     <li>Pear</li>\n@@ /body/ul/li[3] @@\n+     <li>Kiwi</li>\n@@ \
+         /body @@\n+   <!--end-->\n"
+    );
+}
+
+#[test]
+fn patch_texts() {
+    assert_eq!(
+        diff(
+            "<p>First line\nSecond line<b>bold</b> end</p>Top",
+            "<p>First line\nThird line<b>bold</b> end</p>Top"
+        ),
+        "@@ /p @@\n-   Second line\n+   Third line\n"
+    );
+    assert_eq!(diff("Top", "<p>Top</p>"), "@@ / @@\n- Top\n@@ /p @@\n+ <p>Top</p>\n");
+}
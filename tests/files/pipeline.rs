@@ -0,0 +1,65 @@
+use html_filter::*;
+
+fn parse(input: &str) -> Html {
+    Html::parse(input).unwrap_or_else(|err| panic!("{err}"))
+}
+
+#[test]
+fn pipeline_empty() {
+    let html = parse("<p>  a\tb  </p>");
+    let output = Pipeline::new().check_idempotence(&html).expect("no steps");
+    assert_eq!(output, html);
+}
+
+#[test]
+fn pipeline_not_idempotent() {
+    let pipeline = Pipeline::new().normalize_whitespace().clean_controls(ControlPolicy::Strip);
+    let mut html = parse("<p>a \u{1} b</p>");
+    pipeline.run(&mut html);
+    assert_eq!(html, "<p>a  b</p>");
+
+    let err = pipeline.check_idempotence(&parse("<p>a \u{1} b</p>")).expect_err("two spaces");
+    assert!(err.starts_with("The pipeline isn't idempotent"));
+
+    let fixed = Pipeline::new().clean_controls(ControlPolicy::Strip).normalize_whitespace();
+    let output = fixed.check_idempotence(&parse("<p>a \u{1} b</p>")).expect("idempotent");
+    assert_eq!(output, "<p>a b</p>");
+}
+
+#[test]
+fn pipeline_texts() {
+    let pipeline = Pipeline::new().clean_controls(ControlPolicy::Replace).normalize_whitespace();
+    let html = parse(
+        "<div>\n  <p>Hello \u{7}\n world</p>\n  <!-- a\u{7} -->\n  <pre>  keep\n  \
+         this</pre><textarea>a  b</textarea>\n</div>",
+    );
+    let output = pipeline.check_idempotence(&html).expect("idempotent");
+    assert_eq!(
+        output,
+        "<div> <p>Hello \u{fffd} world</p> <!-- a\u{fffd} --> <pre>  keep\n  \
+         this</pre><textarea>a  b</textarea> </div>"
+    );
+}
+
+#[test]
+fn pipeline_sanitize() {
+    let pipeline = Pipeline::new().sanitize(SanitizePolicy::Strict).normalize_whitespace();
+    let html = parse(
+        r#"<div style="color:red"><style>p {}</style><p onclick="track()">Some   text</p><iframe src="/ad"></iframe></div>"#,
+    );
+    let output = pipeline.check_idempotence(&html).expect("idempotent");
+    assert_eq!(output, "<div><p>Some text</p></div>");
+}
+
+#[test]
+fn pipeline_steps() {
+    let pipeline = Pipeline::new()
+        .filter(Filter::new().all(true).except_tag_name("script"))
+        .normalize_whitespace()
+        .prune(40);
+    let html = parse(
+        r#"<main><script>track()</script><ul><li><a href="/a">A</a></li><li><a href="/b">B</a></li></ul><p>A    paragraph with enough text to be kept.</p></main>"#,
+    );
+    let output = pipeline.check_idempotence(&html).expect("idempotent");
+    assert_eq!(output, "<main><p>A paragraph with enough text to be kept.</p></main>");
+}
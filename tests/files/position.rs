@@ -0,0 +1,81 @@
+use html_filter::*;
+
+const HTML: &str = "<table><tr><th>Name</th><th>Price</th></tr><tr><td>Lamp</td> <td>12$</td><!-- \
+                    sale --><td>Yes</td></tr><tr><td>Desk</td><td>80$</td></tr></table>";
+
+/// Parses [`HTML`].
+fn html() -> Html {
+    Html::parse(HTML).unwrap_or_else(|err| panic!("{err}"))
+}
+
+#[test]
+fn position_filter() {
+    let html = html();
+    assert_eq!(
+        html.to_filtered(&Filter::new().tag_name("td").nth_child(2)),
+        "<td>12$</td><td>80$</td>"
+    );
+    assert_eq!(
+        html.to_filtered(&Filter::new().tag_name("td").last_child()),
+        "<td>Yes</td><td>80$</td>"
+    );
+    assert_eq!(
+        html.to_filtered(&Filter::new().tag_name("tr").first_child()),
+        "<tr><th>Name</th><th>Price</th></tr>"
+    );
+    assert_eq!(html.to_found(&Filter::new().tag_name("td").first_child()), "<td>Lamp</td>");
+    assert_eq!(
+        html.to_filtered(&Filter::new().nth_child(3)),
+        "<td>Yes</td><tr><td>Desk</td><td>80$</td></tr>"
+    );
+}
+
+#[test]
+fn position_combined() {
+    let html = html();
+    assert_eq!(
+        html.to_filtered(&Filter::new().tag_name("td").nth_child(2).nth_last_child(2)),
+        "<td>12$</td>"
+    );
+    assert_eq!(
+        html.to_filtered(&Filter::new().tag_name("td").first_child().last_child()),
+        Html::Empty
+    );
+    assert_eq!(html.to_filtered(&Filter::new().tag_name("td").nth_child(0)), Html::Empty);
+    assert_eq!(
+        html.to_filtered(&Filter::new().tag_name("td").first_child().inside_tag("table")),
+        "<td>Lamp</td><td>Desk</td>"
+    );
+    assert_eq!(
+        html.to_filtered(&Filter::new().tag_name("td").last_child().depth(1)),
+        "<tr><td>Lamp</td> <td>12$</td><!-- sale \
+         --><td>Yes</td></tr><tr><td>Desk</td><td>80$</td></tr>"
+    );
+}
+
+#[test]
+fn position_find_all() {
+    let html = html();
+    let filter = Filter::new().tag_name("td").nth_child(2);
+    assert_eq!(html.find_all(&filter), [&"<td>12$</td>", &"<td>80$</td>"]);
+    assert_eq!(html.count(&filter), 2);
+    assert_eq!(html.clone().into_find_all(&filter), ["<td>12$</td>", "<td>80$</td>"]);
+    let selected = html.select(&filter).map(|node| node.as_html().to_string()).collect::<Vec<_>>();
+    assert_eq!(selected, ["<td>12$</td>", "<td>80$</td>"]);
+    assert!(!html.any(&Filter::new().tag_name("th").nth_child(3)));
+}
+
+#[test]
+fn position_only_child() {
+    let html = Html::parse("<ul><li><b>alone</b></li></ul><p>a</p><p>b</p>")
+        .unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(
+        html.to_filtered(&Filter::new().tag_name("b").first_child().last_child()),
+        "<b>alone</b>"
+    );
+    assert_eq!(html.to_filtered(&Filter::new().tag_name("p").nth_child(3)), "<p>b</p>");
+    assert_eq!(
+        html.to_filtered(&Filter::new().tag_name("ul").first_child()),
+        "<ul><li><b>alone</b></li></ul>"
+    );
+}
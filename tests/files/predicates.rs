@@ -0,0 +1,66 @@
+use html_filter::*;
+
+const INPUT: &str = r#"<nav>
+    <a href="https://example.com/docs" class="link" title="Docs">Docs</a>
+    <a href="https://example.org/">Other</a>
+    <ul><li>1</li></ul>
+    <ol><li>1</li><li>2</li><li>3</li></ol>
+</nav>"#;
+
+fn filtered(filter: &Filter) -> String {
+    Html::parse(INPUT).expect("Invalid html").to_filtered(filter).to_string()
+}
+
+fn item_count(node: &Html) -> usize {
+    node.as_tag().map_or(0, |(_, child)| child.find_all(&Filter::new().tag_name("li")).len())
+}
+
+#[test]
+fn node_predicate() {
+    assert_eq!(
+        filtered(
+            &Filter::new()
+                .tag_name("ol")
+                .tag_name("ul")
+                .node_predicate(|node| item_count(node) > 2)
+        ),
+        "<ol><li>1</li><li>2</li><li>3</li></ol>"
+    );
+    assert_eq!(
+        filtered(&Filter::new().tag_name("ul").node_predicate(|node| item_count(node) > 2)),
+        ""
+    );
+    let html = Html::parse(INPUT).expect("Invalid html");
+    let lists = html.find_all(&Filter::new().node_predicate(|node| item_count(node) >= 1));
+    assert_eq!(lists.len(), 3);
+}
+
+#[test]
+fn predicates_report() {
+    let html = Html::parse(INPUT).expect("Invalid html");
+    let filter = Filter::new().tag_name("ol").tag_predicate(|tag| tag.attr_count() > 0);
+    let report = html.select_or_report(&filter).expect_err("The list has no attributes");
+    assert_eq!(report.as_partial_matches().len(), 3);
+    assert!(report.to_string().ends_with("<ol>: a predicate isn't satisfied"));
+}
+
+#[test]
+fn tag_predicate() {
+    let example = Filter::new().tag_predicate(|tag| {
+        tag.find_attr_value("href").is_some_and(|href| href.starts_with("https://example.com/"))
+    });
+    assert_eq!(
+        filtered(&example),
+        r#"<a href="https://example.com/docs" class="link" title="Docs">Docs</a>"#
+    );
+    let many_attributes = Filter::new().tag_predicate(|tag| tag.attr_count() > 2);
+    assert_eq!(filtered(&many_attributes), filtered(&example));
+    assert_eq!(
+        filtered(&Filter::new().tag_predicate(|tag| tag.attr_count() > 0).except_tag_name("a")),
+        ""
+    );
+    let both = Filter::new()
+        .tag_predicate(|tag| tag.as_name() == "a")
+        .tag_predicate(|tag| tag.attr_count() == 1);
+    assert_eq!(filtered(&both), r#"<a href="https://example.org/">Other</a>"#);
+}
@@ -0,0 +1,34 @@
+use std::fs::read_to_string;
+
+use html_filter::prelude::*;
+
+#[test]
+fn prelude_attrs() {
+    let content = read_to_string("tests/data/index.html").expect("file exists");
+    let html = Html::parse(&content).unwrap_or_else(|err| panic!("{err}"));
+    let mut count = 0usize;
+    for node in html.select(&Filter::new()) {
+        let Some(tag) = node.as_tag() else { continue };
+        let attrs = tag.attrs();
+        assert_eq!(attrs.len(), tag.attr_count());
+        for ((name, value), attr) in attrs.clone().zip(tag.as_attrs()) {
+            assert_eq!(name, attr.as_name());
+            assert_eq!(value, attr.as_value().map(String::as_str));
+            if value.is_none() {
+                count = count.saturating_add(1);
+            }
+        }
+        assert!(attrs.rev().eq(tag.attrs().collect::<Vec<_>>().into_iter().rev()));
+    }
+    assert!(count > 0, "index.html has attributes without values");
+}
+
+#[test]
+fn prelude_scrape() {
+    let html = Html::parse(r#"<input type="checkbox" checked><label for="a">A</label>"#)
+        .unwrap_or_else(|err| panic!("{err}"));
+    let filter = Filter::new().tag_name("input");
+    let input = html.select(&filter).next().expect("an input");
+    let attrs = input.as_tag().map(|tag| tag.attrs().collect::<Vec<_>>());
+    assert_eq!(attrs, Some(vec![("type", Some("checkbox")), ("checked", None)]));
+}
@@ -0,0 +1,67 @@
+use html_filter::*;
+
+fn pretty(input: &str, options: &PrettyOptions) -> String {
+    Html::parse(input).expect("Invalid html").to_pretty_string(options)
+}
+
+#[test]
+fn pretty_empty() {
+    assert_eq!(pretty("", &PrettyOptions::new()), "");
+    assert_eq!(pretty("<div></div><p>  </p>", &PrettyOptions::new()), "<div></div>\n<p></p>\n");
+}
+
+#[test]
+fn pretty_idempotent() {
+    let input = r#"<html><head><title>Page</title><meta charset="utf-8" /></head><body><nav><a href="/home">Home</a> <a href="/about">About us</a></nav><p>Some text that is long enough to be wrapped on multiple lines with a small maximum.</p></body></html>"#;
+    let options = PrettyOptions::new().max_line_length(40);
+    let once = pretty(input, &options);
+    assert_eq!(pretty(&once, &options), once);
+    assert_eq!(once.lines().filter(|line| line.len() > 40).count(), 0);
+}
+
+#[test]
+fn pretty_inline() {
+    assert_eq!(
+        pretty(
+            r#"<div><p>A <a href="/x">link</a>, then <code>code</code>.</p></div>"#,
+            &PrettyOptions::new()
+        ),
+        "<div>\n  <p>A <a href=\"/x\">link</a>, then <code>code</code>.</p>\n</div>\n"
+    );
+    assert_eq!(
+        pretty("<p>One <em>two three</em>four</p>", &PrettyOptions::new().max_line_length(12)),
+        "<p>\n  One\n  <em>two\n  three</em>four\n</p>\n"
+    );
+    assert_eq!(
+        pretty("<div><listing>  a\n  b</listing></div>", &PrettyOptions::new()),
+        "<div>\n  <listing>  a\n  b</listing>\n</div>\n"
+    );
+}
+
+#[test]
+fn pretty_mixed_content() {
+    assert_eq!(
+        pretty("<p>Hello <b>dear</b>\n\n   world<br/><!-- note --></p>", &PrettyOptions::new()),
+        "<p>\n  Hello <b>dear</b> world<br>\n  <!-- note -->\n</p>\n"
+    );
+}
+
+#[test]
+fn pretty_preformatted() {
+    assert_eq!(
+        pretty(
+            "<div><pre>  a\n    b</pre><script>if (a && b) {}</script></div>",
+            &PrettyOptions::new()
+        ),
+        "<div>\n  <pre>  a\n    b</pre>\n  <script>if (a && b) {}</script>\n</div>\n"
+    );
+}
+
+#[test]
+fn pretty_void_attributes() {
+    let options = PrettyOptions::new().indent(0).max_line_length(20);
+    assert_eq!(
+        pretty(r#"<img src="image.png" alt="An image"/><hr noshade>"#, &options),
+        "<img\nsrc=\"image.png\"\nalt=\"An image\"\n>\n<hr noshade>\n"
+    );
+}
@@ -0,0 +1,48 @@
+use html_filter::*;
+
+const INPUT: &str = r#"<body>
+<header><ul class="menu"><li><a href="/">Home</a></li><li><a href="/blog">Blog</a></li></ul></header>
+<main>
+    <div class="post"><p>Ownership rules make the memory management of rust programs safe without a collector.</p></div>
+    <div class="tags"><a href="/t/rust">rust</a> <a href="/t/memory">memory</a></div>
+    <div class="figure"><img src="/diagram.png"></div>
+    <div class="empty"></div>
+</main>
+<script>track("visit");</script>
+</body>"#;
+
+fn pruned(threshold: u8) -> String {
+    let mut html = Html::parse(INPUT).expect("Invalid html");
+    html.prune_low_content(threshold);
+    html.to_string()
+}
+
+#[test]
+fn prune_boilerplate() {
+    let output = pruned(30);
+    assert!(!output.contains("<header>"));
+    assert!(!output.contains("class=\"tags\""));
+    assert!(!output.contains("class=\"empty\""));
+    assert!(output.contains("Ownership rules"));
+    assert!(output.contains("<main>"));
+    assert!(output.contains("<script>"));
+}
+
+#[test]
+fn prune_media_and_thresholds() {
+    assert!(pruned(100).contains(r#"<img src="/diagram.png">"#));
+    assert!(!pruned(100).contains("Ownership rules"));
+    assert_eq!(pruned(0), Html::parse(INPUT).expect("Invalid html").to_string());
+}
+
+#[test]
+fn prune_single_node() {
+    let mut html = Html::parse(
+        r#"<div><nav><a href="/">Home</a></nav><p>The borrow checker rejects the dangling references.</p></div>"#,
+    )
+    .expect("Invalid html");
+    html.prune_low_content(30);
+    assert_eq!(html, "<div><p>The borrow checker rejects the dangling references.</p></div>");
+    let (_, child) = html.as_tag().expect("Expected a <div>");
+    assert!(child.as_tag().is_some(), "Expected the <p> alone, not a list: {child:?}");
+}
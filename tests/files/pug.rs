@@ -0,0 +1,47 @@
+use html_filter::*;
+
+fn pug(input: &str) -> String {
+    Html::parse(input).expect("Invalid html").to_pug()
+}
+
+#[test]
+fn pug_attributes() {
+    assert_eq!(
+        pug(
+            r#"<div class="a 1b" id="main" title='Say "hi"'></div><a href="/?a=1&amp;b=2" class="">x</a>"#
+        ),
+        r#"#main(class="a 1b" title="Say \"hi\"")
+a(href!="/?a=1&amp;b=2" class="") x
+"#
+    );
+}
+
+#[test]
+fn pug_comments_and_raw_text() {
+    assert_eq!(
+        pug("<head><style>\n    p {\n      color: red;\n    }\n  </style></head><!-- a\n  b -->"),
+        "head\n  style.\n    p {\n      color: red;\n    }\n// a\n  b\n"
+    );
+}
+
+#[test]
+fn pug_preformatted() {
+    assert_eq!(
+        pug("<div><pre>\n  fn main() {\n\n      <b class=\"k\">let</b> a = 1;<br>\n  }</pre></div>"),
+        "div\n  pre.\n    &#32;&#32;fn main() {\n\n          <b class=\"k\">let</b> a = 1;<br>\n      }\n"
+    );
+    assert_eq!(
+        pug("<textarea rows=\"2\">a\n  #{b}</textarea>"),
+        "textarea(rows=\"2\").\n  a\n    \\#{b}\n"
+    );
+    assert_eq!(pug("<pre></pre>"), "pre\n");
+    assert_eq!(pug("<pre>\n\n\n a\n b</pre>"), "pre.\n  &#10;&#10;&#32;a\n   b\n");
+}
+
+#[test]
+fn pug_texts() {
+    assert_eq!(
+        pug("<p>\n  Hello #{name},\n  welcome\n</p><p><b>Bold</b> text</p>Top"),
+        "p\n  | Hello \\#{name},\n  | welcome\np\n  b Bold\n  | text\n| Top\n"
+    );
+}
@@ -0,0 +1,83 @@
+use core::ffi::CStr;
+
+use html_filter::python::html_filter;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use pyo3::wrap_pymodule;
+
+fn run(code: &CStr) {
+    Python::initialize();
+    Python::attach(|py| {
+        let globals = PyDict::new(py);
+        globals.set_item("html_filter", wrap_pymodule!(html_filter)(py))?;
+        py.run(code, Some(&globals), None)
+    })
+    .unwrap_or_else(|err| panic!("{err}"));
+}
+
+#[test]
+fn python_attributes() {
+    run(c"
+tag = html_filter.parse('<a href=\"/a\" hidden>Link</a>').select('a')[0]
+assert tag.name == 'a'
+assert tag.attrs == [('href', '/a'), ('hidden', None)]
+assert tag['href'] == '/a'
+assert tag.get('title') is None
+assert tag.get('hidden', 'no value') == 'no value'
+assert 'hidden' in tag and 'title' not in tag
+try:
+    tag['title']
+    raise AssertionError('Expected a KeyError')
+except KeyError:
+    pass
+");
+}
+
+#[test]
+fn python_errors() {
+    run(c"
+for input in ['<p></a>', '<p', 'a -->']:
+    try:
+        html_filter.parse(input)
+        raise AssertionError(f'Expected an error for {input!r}')
+    except ValueError:
+        pass
+try:
+    html_filter.parse('<p></p>').select('p > a')
+    raise AssertionError('Expected an error for the selector')
+except ValueError as err:
+    assert str(err) == \"Invalid selector 'p > a'.\"
+");
+}
+
+#[test]
+fn python_select() {
+    run(c"
+html = html_filter.parse('<ul><li class=\"a b\">First</li><li id=\"second\">Second <b>item</b></li></ul>')
+assert [str(item) for item in html.select('li')] == [
+    '<li class=\"a b\">First</li>',
+    '<li id=\"second\">Second <b>item</b></li>',
+]
+assert [item.inner_text() for item in html.select('li.b')] == ['First']
+[second] = html.select('#second')
+assert repr(second) == 'Tag(\\'<li id=\"second\">\\')'
+assert [str(bold) for bold in second.select('b')] == ['<b>item</b>']
+assert html.inner_text() == 'FirstSecond item'
+assert html.select('p') == []
+assert repr(html_filter.parse('<p>a</p>')) == \"Html('<p>a</p>')\"
+");
+}
+
+#[test]
+fn python_select_nested() {
+    run(c"
+html = html_filter.parse('<div>a<p>b<i>c</i></p>d<p><i>e</i><i>f</i></p></div><i>g</i>')
+assert [str(item) for item in html.select('i')] == ['<i>c</i>', '<i>e</i>', '<i>f</i>', '<i>g</i>']
+[first, second] = html.select('p')
+assert [item.inner_text() for item in second.select('i')] == ['e', 'f']
+assert [str(item) for item in first.select('i')] == ['<i>c</i>']
+[div] = html.select('div')
+assert [str(item) for item in div.select('i')] == ['<i>c</i>', '<i>e</i>', '<i>f</i>']
+assert second.select('p') == []
+");
+}
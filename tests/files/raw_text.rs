@@ -0,0 +1,46 @@
+use html_filter::*;
+
+#[test]
+fn raw_text_json_ld() {
+    let html = Html::parse(
+        r#"<html><head><script type="application/ld+json">{"name": "<b>A</b> &amp; B"}</script><style>a > b {}</style></head><body><script type="application/ld+json"></script><p>Not <b>raw</b></p></body></html>"#,
+    )
+    .unwrap_or_else(|err| panic!("{err}"));
+    let filter = Filter::new().raw_text_tags().attribute_value("type", "application/ld+json");
+    let blocks = html
+        .select(&filter)
+        .filter_map(|node| node.as_html().as_raw_text().map(|(_, content)| content))
+        .collect::<Vec<_>>();
+    assert_eq!(blocks, [r#"{"name": "<b>A</b> &amp; B"}"#, ""]);
+
+    let styles = html.to_filtered(&Filter::new().raw_text_tags().except_tag_name("script"));
+    assert_eq!(styles, "<style>a > b {}</style>");
+    assert!(
+        html.iter_tags()
+            .filter(|(tag, _)| !tag.is_raw_text())
+            .all(|(tag, _)| tag.as_name() != "style")
+    );
+}
+
+#[test]
+fn raw_text_uppercase() {
+    let input = "<SCRIPT>if (a < b && c) {}</script><Style>a > b {}</STYLE><BR><p>x</p>";
+    let html = Html::parse(input).unwrap_or_else(|err| panic!("{err}"));
+    let tags =
+        html.iter_tags().map(|(tag, _)| (tag.is_raw_text(), tag.is_void())).collect::<Vec<_>>();
+    assert_eq!(tags, [(true, false), (true, false), (false, true), (false, false)]);
+    let expected = "<SCRIPT>if (a < b && c) {}</SCRIPT><Style>a > b {}</Style><BR><p>x</p>";
+    assert_eq!(html, expected);
+    assert_eq!(
+        Html::parse("<DIV>a</div>").map(|div| div.to_string()),
+        Ok("<DIV>a</DIV>".to_owned())
+    );
+    assert_eq!(
+        Html::parse_borrowed(input).map(|borrowed| borrowed.to_string()),
+        Ok(expected.to_owned())
+    );
+    assert_eq!(
+        html.to_jsx(),
+        "<><SCRIPT>{\"if (a < b && c) {}\"}</SCRIPT><Style>{\"a > b {}\"}</Style><BR /><p>x</p></>"
+    );
+}
@@ -0,0 +1,93 @@
+use std::fs::read_to_string;
+
+use html_filter::*;
+
+macro_rules! test_recover {
+    ($($name:ident: $html:expr => $expected:expr, $warnings:expr)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let mut warnings = vec![];
+                let tree = Html::parse_with(
+                    $html,
+                    ParseOptions::new()
+                        .recover(true)
+                        .on_warning(|warning| warnings.push(warning.to_owned())),
+                )
+                .unwrap_or_else(|err| panic!("{err}"));
+                assert_eq!(tree, $expected);
+                let expected_warnings: &[&str] = &$warnings;
+                assert_eq!(warnings, expected_warnings);
+            }
+        )*
+    };
+}
+
+fn recovered(html: &str) -> Html {
+    Html::parse_with(html, ParseOptions::new().recover(true)).unwrap_or_else(|err| panic!("{err}"))
+}
+
+#[test]
+fn recover_budget() {
+    let options = ParseOptions::new().recover(true).budget(Budget::new().max_nodes(1));
+    let err = Html::parse_with("<p>a<p>b", options).expect_err("Budget exceeded");
+    assert_eq!(err.kind(), &ParseErrorKind::BudgetExceeded(BudgetExceeded::Nodes));
+}
+
+#[test]
+fn recover_stream() {
+    let input = "<ul><li>a<li>b</ul></div><p>c<p id=d>e-->f<g:h>";
+    let expected = recovered(input);
+    for size in 1..=input.len() {
+        let mut parser = Parser::with_options(ParseOptions::new().recover(true));
+        for chunk in input.as_bytes().chunks(size) {
+            parser.feed(str::from_utf8(chunk).expect("ASCII input")).expect("Recovered");
+        }
+        assert_eq!(parser.finish().expect("Recovered").to_string(), expected.to_string());
+    }
+}
+
+#[test]
+fn recover_valid() {
+    let content = read_to_string("tests/data/index.html").expect("Missing tests/data/index.html");
+    let html = Html::parse(&content).expect("Invalid html");
+    assert_eq!(recovered(&content).to_string(), html.to_string());
+}
+
+test_recover!(
+
+recover_list: "<ul><li>a<li>b<li>c</ul>" =>
+"<ul><li>a</li><li>b</li><li>c</li></ul>", []
+
+recover_nested_list: "<ul><li>a<ul><li>b<li>c</ul><li>d</ul>" =>
+"<ul><li>a<ul><li>b</li><li>c</li></ul></li><li>d</li></ul>", []
+
+recover_paragraphs: "<p>a<p>b<h1>c</h1><p>d<span>e</span>" =>
+"<p>a</p><p>b</p><h1>c</h1><p>d<span>e</span></p>", []
+
+recover_definitions: "<dl><dt>a<dd>b<dt>c<dd>d</dl>" =>
+"<dl><dt>a</dt><dd>b</dd><dt>c</dt><dd>d</dd></dl>", []
+
+recover_table: "<table><tr><td>a<td>b<tr><th>c</table>" =>
+"<table><tr><td>a</td><td>b</td></tr><tr><th>c</th></tr></table>", []
+
+recover_select: "<select><optgroup><option>a<option>b<optgroup><option>c</select>" =>
+"<select><optgroup><option>a</option><option>b</option></optgroup><optgroup><option>c</option></optgroup></select>", []
+
+recover_orphan: "<div>a</span></div></div>b" =>
+"<div>a</div>b",
+["Ignored closing tag of element 'span' that isn't open at byte 6 (line 1, column 7).", "Ignored closing tag of element 'div' that isn't open at byte 19 (line 1, column 20)."]
+
+recover_invalid_tag: "<p>1 <a:b> 2</p>" =>
+"<p>1 &lt;a:b> 2</p>", ["Read an invalid tag as text (Invalid character ':' in tag name) at byte 5 (line 1, column 6)."]
+
+recover_unclosed_tag: "<p>a</p><a href='b" =>
+"<p>a</p>&lt;a href='b", ["Read an invalid tag as text (EOF: Missing closing '>') at byte 8 (line 1, column 9)."]
+
+recover_comment: "<p>a --> b</p>" =>
+"<p>a --> b</p>", ["Read the closing of a comment that isn't open as text at byte 5 (line 1, column 6)."]
+
+recover_void: "<p>a<br>b</br></p>" =>
+"<p>a<br>b</p>", ["Ignored closing tag of void element 'br' at byte 9 (line 1, column 10)."]
+
+);
@@ -0,0 +1,54 @@
+use html_filter::*;
+
+const LINKS: &str = r#"<ul><li><a href="https://example.com/report.pdf">Report</a></li><li><a href="https://example.com/REPORT.PDF">Upper</a></li><li><a href="/notes.pdf">Notes</a></li><li><a download>No value</a></li></ul>"#;
+
+#[test]
+fn regex_combined() {
+    let html = Html::parse(LINKS).expect("Invalid html");
+    let filter = Filter::new()
+        .tag_name("a")
+        .attribute_value_regex("href", r"(?i)\.pdf$")
+        .expect("Invalid regex")
+        .except_attribute_value("href", "/notes.pdf");
+    assert_eq!(
+        html.to_filtered(&filter),
+        r#"<a href="https://example.com/report.pdf">Report</a><a href="https://example.com/REPORT.PDF">Upper</a>"#
+    );
+}
+
+#[test]
+fn regex_equality() {
+    let filter =
+        |pattern| Filter::new().attribute_value_regex("href", pattern).expect("Invalid regex");
+    assert_eq!(filter("^a"), filter("^a"));
+    assert_ne!(filter("^a"), filter("^b"));
+}
+
+#[test]
+fn regex_errors() {
+    let err =
+        Filter::new().attribute_value_regex("href", "[a-").expect_err("Invalid regex accepted");
+    assert!(err.to_string().contains("unclosed character class"));
+}
+
+#[test]
+fn regex_find_text() {
+    let html = Html::parse(LINKS).expect("Invalid html");
+    let found = html.find_text_regex("(?i)^report$").expect("Invalid regex");
+    let texts = found.iter().map(|node| node.as_html().to_string()).collect::<Vec<_>>();
+    assert_eq!(texts, [r#"<a href="https://example.com/report.pdf">Report</a>"#]);
+
+    let unanchored = html.find_text_regex("o").expect("Invalid regex");
+    assert_eq!(unanchored.len(), 3);
+    let err = html.find_text_regex("[a-").expect_err("Invalid regex accepted");
+    assert!(err.to_string().contains("unclosed character class"));
+}
+
+#[test]
+fn regex_unanchored() {
+    let html = Html::parse(LINKS).expect("Invalid html");
+    let notes = Filter::new().attribute_value_regex("href", "notes").expect("Invalid regex");
+    assert_eq!(html.find_all(&notes), [&r#"<a href="/notes.pdf">Notes</a>"#]);
+    let download = Filter::new().attribute_value_regex("download", ".*").expect("Invalid regex");
+    assert_eq!(html.find_all(&download).len(), 0);
+}
@@ -0,0 +1,36 @@
+use html_filter::*;
+
+const INPUT: &str = "<body>
+<!-- content:start -->
+<h1>Title</h1>
+<!-- content:end -->
+<section><!--content:start--><p>First</p><p>Second</p><!--content:end--></section>
+<div><!-- content:start --><p>Unclosed</p></div><!-- content:end -->
+<!-- content:start --><!-- content:end -->
+</body>";
+
+fn regions(start: &str, end: &str) -> Vec<String> {
+    let html = Html::parse(INPUT).expect("Invalid html");
+    extract::regions(&html, start, end).iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn regions_markers() {
+    assert_eq!(regions("content:start", "content:end"), [
+        "\n<h1>Title</h1>\n",
+        "<p>First</p><p>Second</p>",
+        ""
+    ]);
+    assert_eq!(regions("content:end", "content:start").len(), 1);
+    assert!(regions("content:start", "missing").is_empty());
+}
+
+#[test]
+fn regions_siblings() {
+    let html = Html::parse("<li>1</li><li>2</li><li>3</li>").expect("Invalid html");
+    assert_eq!(html.to_siblings(0..2), "<li>1</li><li>2</li>");
+    assert_eq!(html.to_siblings(3..3), Html::Empty);
+    let single = Html::parse("<li>1</li>").expect("Invalid html");
+    assert_eq!(single.to_siblings(0..5), single);
+    assert_eq!(single.to_siblings(1..5), Html::Empty);
+}
@@ -0,0 +1,51 @@
+use html_filter::*;
+
+macro_rules! test_reparse {
+    ($($name:ident: $path:expr, $source:expr => $expect:expr)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let mut html = Html::parse(DOCUMENT).unwrap_or_else(|err| panic!("{err}"));
+                let expect: Result<&str, &str> = $expect;
+                match (html.reparse_node(&$path, $source), expect) {
+                    (Ok(()), Ok(expected)) => assert_eq!(html, expected),
+                    (Err(err), Err(expected)) => {
+                        assert_eq!(err, expected);
+                        assert_eq!(html, DOCUMENT);
+                    }
+                    (res, _) => panic!("Unexpected result {res:?} for {html}"),
+                }
+            }
+        )*
+    };
+}
+
+const DOCUMENT: &str = "<!DOCTYPE html><html><head></head><body><p>1</p><p>2</p></body></html>";
+
+test_reparse!(
+
+reparse_paragraph: [1, 1, 1], "<p>two</p><p>three</p>" =>
+Ok("<!DOCTYPE html><html><head></head><body><p>1</p><p>two</p><p>three</p></body></html>")
+
+reparse_body: [1, 1], "<body>Empty</body>" =>
+Ok("<!DOCTYPE html><html><head></head><body>Empty</body></html>")
+
+reparse_doctype: [0], "<!DOCTYPE other>" =>
+Ok("<!DOCTYPE other><html><head></head><body><p>1</p><p>2</p></body></html>")
+
+reparse_root: [], "<p>all</p>" =>
+Ok("<p>all</p>")
+
+reparse_text: [1, 1, 0, 0], "<b>1</b>" =>
+Ok("<!DOCTYPE html><html><head></head><body><p><b>1</b></p><p>2</p></body></html>")
+
+reparse_invalid_path: [1, 3], "<p />" =>
+Err("Invalid path: no node at index 3 at depth 1.")
+
+reparse_nested_doctype: [1, 0], "<!DOCTYPE html><head></head>" =>
+Err("Doctypes are only allowed at the root of the document.")
+
+reparse_misplaced_head: [1, 1, 0], "<head></head>" =>
+Err("A <head> tag can only replace a <head> tag.")
+
+);
@@ -0,0 +1,65 @@
+use html_filter::*;
+
+/// Form with a few inputs.
+const INPUT: &str = r#"<form><input type="text" name="a" required /><input type="radio" name="b" /><button type="submit" disabled>Go</button></form>"#;
+
+/// Returns the display of the report of the filter.
+fn report(filter: &Filter) -> String {
+    let html = Html::parse(INPUT).unwrap_or_else(|err| panic!("{err}"));
+    html.select_or_report(filter)
+        .map_or_else(|report| report.to_string(), |nodes| panic!("{} nodes matched", nodes.len()))
+}
+
+#[test]
+fn report_closest_first() {
+    let filter = Filter::new()
+        .tag_name("input")
+        .attribute_value("type", "checkbox")
+        .attribute_name("required")
+        .except_attribute_name("disabled");
+    assert_eq!(
+        report(&filter),
+        r#"No tag matched the filter. Closest partial matches:
+- <input type="text" name="a" required>: the attribute 'type' has a wrong value
+- <input type="radio" name="b">: the attribute 'type' has a wrong value, the attribute 'required' is missing
+- <form>: the attribute 'type' is missing, the attribute 'required' is missing, the tag name 'form' is unwanted"#
+    );
+}
+
+#[test]
+fn report_like_and_count() {
+    let example = Html::parse("<button><b>Go</b></button>").unwrap_or_else(|err| panic!("{err}"));
+    let filter = Filter::new().like(&example, 0).max_attributes(1);
+    assert_eq!(
+        report(&filter),
+        "No tag matched the filter. Closest partial matches:
+- <form>: the structure isn't similar enough to the example"
+    );
+}
+
+#[test]
+fn report_matches() {
+    let html = Html::parse(INPUT).unwrap_or_else(|err| panic!("{err}"));
+    let nodes = html.select_or_report(&Filter::new().attribute_name("disabled")).expect("matches");
+    assert_eq!(nodes.len(), 1);
+}
+
+#[test]
+fn report_nothing() {
+    let filter = Filter::new().tag_name("table");
+    assert_eq!(report(&filter), "No tag matched the filter, not even partially.");
+    let html = Html::parse(INPUT).unwrap_or_else(|err| panic!("{err}"));
+    let err = html.select_or_report(&filter).expect_err("no table");
+    assert!(err.as_partial_matches().is_empty());
+}
+
+#[test]
+fn report_partial_accessors() {
+    let html = Html::parse(INPUT).unwrap_or_else(|err| panic!("{err}"));
+    let err = html
+        .select_or_report(&Filter::new().tag_name("button").attribute_value("type", "reset"))
+        .expect_err("no reset button");
+    let partial = err.as_partial_matches().first().expect("button partially matches");
+    assert_eq!(partial.as_failures(), ["the attribute 'type' has a wrong value"]);
+    assert_eq!(partial.as_node().as_tag().map(Tag::as_name), Some("button"));
+}
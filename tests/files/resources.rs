@@ -0,0 +1,70 @@
+use html_filter::extract::{Resource, resources_with_spans};
+use html_filter::*;
+
+fn resources(source: &str) -> Vec<Resource> {
+    resources_with_spans(source).expect("Invalid html")
+}
+
+#[test]
+fn resources_attributes() {
+    let source = r#"<!DOCTYPE html>
+<html>
+<head>
+    <link rel="stylesheet" href="style.css">
+    <script src="app.js">let img = '<img src="fake.png">';</script>
+</head>
+<body>
+    <a href="/page">Page</a>
+    <IMG SRC="photo.jpg?w=1&amp;h=2" src="">
+    <video poster="poster.png"><source src="movie.mp4"><track src="subs.vtt"></video>
+    <object data="doc.pdf"></object>
+    <iframe src='frame.html'></iframe>
+</body>
+</html>"#;
+    let found = resources(source);
+    let urls = found
+        .iter()
+        .map(|resource| (resource.as_tag(), resource.as_attr(), resource.as_url()))
+        .collect::<Vec<_>>();
+    assert_eq!(urls, [
+        ("link", "href", "style.css"),
+        ("script", "src", "app.js"),
+        ("img", "src", "photo.jpg?w=1&amp;h=2"),
+        ("video", "poster", "poster.png"),
+        ("source", "src", "movie.mp4"),
+        ("track", "src", "subs.vtt"),
+        ("object", "data", "doc.pdf"),
+        ("iframe", "src", "frame.html"),
+    ]);
+    for resource in &found {
+        assert_eq!(source.get(resource.span()), Some(resource.as_url()));
+    }
+}
+
+#[test]
+fn resources_errors() {
+    let err = resources_with_spans(r#"<img src="a.png"><a:b>"#).expect_err("Invalid html accepted");
+    assert_eq!(err.kind(), &ParseErrorKind::InvalidTagName(':'));
+    assert_eq!(resources("<p>No resources</p>"), []);
+}
+
+#[test]
+fn resources_srcset() {
+    let source = r#"<picture><source srcset="
+        data:image/png;base64,AAAA 1x,
+        large.webp 800w ,small.webp,, tiny.webp,
+    "><img srcset="only.png"></picture>"#;
+    let found = resources(source);
+    let urls = found.iter().map(Resource::as_url).collect::<Vec<_>>();
+    assert_eq!(urls, [
+        "data:image/png;base64,AAAA",
+        "large.webp",
+        "small.webp",
+        "tiny.webp",
+        "only.png"
+    ]);
+    for resource in &found {
+        assert_eq!(source.get(resource.span()), Some(resource.as_url()));
+        assert_eq!(resource.as_attr(), "srcset");
+    }
+}
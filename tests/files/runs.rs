@@ -0,0 +1,26 @@
+use html_filter::*;
+
+#[test]
+fn runs_round_trip() {
+    for padding in 0..16 {
+        let pad = "x".repeat(padding);
+        let html = format!(
+            "<p title=\"{pad}caf\u{e9} 'quoted' value\" data-x='{pad}a \"b\" c'>{pad}Some long \
+             text, caf\u{e9} and \u{1f980}, with a - dash and a > sign.</p><!-- {pad}a long \
+             comment - with - dashes --><script>{pad}if (a > b) {{ c - d }}</script>{pad}"
+        );
+        let tree = Html::parse(&html).expect("valid html");
+        assert_eq!(tree.to_string(), html, "padding of {padding}");
+    }
+}
+
+#[test]
+fn runs_text_hook() {
+    let mut texts = vec![];
+    Html::parse_with(
+        "<p>A rather long first text - with a dash</p><p>Second</p>",
+        ParseOptions::new().on_text(|text| texts.push(text.to_owned())),
+    )
+    .expect("valid html");
+    assert_eq!(texts, ["A rather long first text - with a dash", "Second"]);
+}
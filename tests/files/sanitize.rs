@@ -0,0 +1,48 @@
+use html_filter::*;
+
+fn sanitized(input: &str, policy: SanitizePolicy) -> String {
+    let mut html = Html::parse(input).unwrap_or_else(|err| panic!("{err}"));
+    html.sanitize(policy);
+    html.to_string()
+}
+
+#[test]
+fn sanitize_event_handlers() {
+    assert_eq!(
+        sanitized(
+            r#"<img src="a.png" ONERROR="steal()" alt="a"><p onmouseover='x()'>Text</p>"#,
+            SanitizePolicy::Scripts
+        ),
+        r#"<img src="a.png" alt="a"><p>Text</p>"#
+    );
+}
+
+#[test]
+fn sanitize_obfuscated_schemes() {
+    for url in [
+        "javascript:alert(1)",
+        "  JaVaScRiPt:alert(1)",
+        "java&#x09;script:alert(1)",
+        "java\nscript:alert(1)",
+        "&#106;avascript:alert(1)",
+        "javascript&colon;alert(1)",
+        "vbscript:msgbox(1)",
+    ] {
+        let input = format!(r#"<a href="{url}">Link</a>"#);
+        assert_eq!(sanitized(&input, SanitizePolicy::Scripts), "<a>Link</a>", "{url}");
+    }
+    assert_eq!(
+        sanitized(r#"<a href="/javascript:help">Help</a>"#, SanitizePolicy::Scripts),
+        r#"<a href="/javascript:help">Help</a>"#
+    );
+}
+
+#[test]
+fn sanitize_strict() {
+    let input = r#"<head><meta http-equiv="refresh" content="0"><link rel="stylesheet" href="a.css"></head><body><form action="/post"><input name="a"></form><p style="color: red">Text</p><object data="a.swf"></object></body>"#;
+    assert_eq!(
+        sanitized(input, SanitizePolicy::Scripts),
+        r#"<head><meta http-equiv="refresh" content="0"><link rel="stylesheet" href="a.css"></head><body><form action="/post"><input name="a"></form><p style="color: red">Text</p></body>"#
+    );
+    assert_eq!(sanitized(input, SanitizePolicy::Strict), "<head></head><body><p>Text</p></body>");
+}
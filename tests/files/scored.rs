@@ -0,0 +1,63 @@
+use html_filter::*;
+
+/// Page with multiple candidates for the main content.
+const INPUT: &str = r#"<nav class="menu"><a href="/">Home</a></nav><section class="main"><p>Short.</p></section><section class="main"><p>A longer paragraph with many words.</p></section><aside>Many many many many many many many words</aside>"#;
+
+/// Returns the html of the best match, with its score.
+fn best(scored: &ScoredFilter) -> Option<(String, i64)> {
+    let html = Html::parse(INPUT).unwrap_or_else(|err| panic!("{err}"));
+    html.best_match(scored).map(|(node, score)| (node.as_html().to_string(), score))
+}
+
+#[test]
+fn scored_empty() {
+    let scored = ScoredFilter::new();
+    assert!(scored.is_empty());
+    assert_eq!(
+        best(&scored),
+        Some((r#"<nav class="menu"><a href="/">Home</a></nav>"#.to_owned(), 0))
+    );
+}
+
+#[test]
+fn scored_no_candidate() {
+    assert_eq!(best(&ScoredFilter::new().rule(Filter::new().tag_name("table"), 1)), None);
+}
+
+#[test]
+fn scored_text_only() {
+    assert_eq!(
+        best(&ScoredFilter::new().text_weight(1)),
+        Some(("<aside>Many many many many many many many words</aside>".to_owned(), 8))
+    );
+}
+
+#[test]
+fn scored_tie_and_text() {
+    let rules = ScoredFilter::new().rule(Filter::new().attribute_value("class", "main"), 100);
+    assert_eq!(
+        best(&rules),
+        Some((r#"<section class="main"><p>Short.</p></section>"#.to_owned(), 100))
+    );
+    assert_eq!(
+        best(&rules.text_weight(2)),
+        Some((
+            r#"<section class="main"><p>A longer paragraph with many words.</p></section>"#
+                .to_owned(),
+            112
+        ))
+    );
+}
+
+#[test]
+fn scored_negative() {
+    let scored = ScoredFilter::new()
+        .rule(Filter::new().tag_name("section"), 5)
+        .rule(Filter::new().tag_name("nav"), 3)
+        .rule(Filter::new().attribute_value("class", "main"), -10);
+    assert_eq!(scored.len(), 3);
+    assert_eq!(
+        best(&scored),
+        Some((r#"<nav class="menu"><a href="/">Home</a></nav>"#.to_owned(), 3))
+    );
+}
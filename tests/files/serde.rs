@@ -0,0 +1,44 @@
+use html_filter::*;
+use serde_json::{from_str, json, to_string, to_value};
+
+#[test]
+fn serde_json_format() {
+    let html = Html::parse(r#"<p id="a" hidden>Text</p><!-- c -->"#).expect("Invalid html");
+    assert_eq!(
+        to_value(&html).expect("Invalid json"),
+        json!({ "Vec": [
+            { "Tag": {
+                "tag": {
+                    "attrs": [
+                        { "NameValue": { "double_quote": true, "name": "id", "value": "a" } },
+                        { "NameNoValue": "hidden" },
+                    ],
+                    "name": "p",
+                },
+                "child": { "Text": "Text" },
+            } },
+            { "Comment": " c " },
+        ] })
+    );
+    assert_eq!(to_value(Html::new()).expect("Invalid json"), json!("Empty"));
+}
+
+#[test]
+fn serde_round_trip() {
+    let input = r#"<!DOCTYPE html><html><head><title>Page</title></head><body><div class='box' data-long-attribute-name="some value that is long enough"><img src="a.png" /><p>Some text that is long enough not to be inlined</p></div></body></html>"#;
+    let html = Html::parse(input).expect("Invalid html");
+    let json = to_string(&html).expect("Invalid json");
+    let decoded = from_str::<Html>(&json).expect("Invalid json");
+    assert_eq!(decoded.to_string(), html.to_string());
+}
+
+#[test]
+fn serde_new_ids() {
+    let html = Html::parse("<div><p>Text</p></div>").expect("Invalid html");
+    let decoded = from_str::<Html>(&to_string(&html).expect("Invalid json")).expect("Invalid json");
+    let id = html.as_tag().expect("Not a tag").0.id();
+    let decoded_id = decoded.as_tag().expect("Not a tag").0.id();
+    assert!(decoded_id > id);
+    let err = from_str::<Html>(r#"{ "Text": 1 }"#).expect_err("Invalid text accepted");
+    assert!(err.to_string().starts_with("invalid type: integer `1`, expected a string"));
+}
@@ -0,0 +1,115 @@
+use core::convert::Infallible;
+
+use html_filter::*;
+
+/// Records the calls of the walk, one per line.
+#[derive(Default)]
+struct Calls {
+    doctypes: bool,
+    lines: Vec<String>,
+}
+
+impl Serializer for Calls {
+    type Error = Infallible;
+
+    fn attr(&mut self, name: &str, value: Option<&str>) -> Result<(), Self::Error> {
+        self.lines.push(format!("attr {name} {}", value.unwrap_or("-")));
+        Ok(())
+    }
+
+    fn comment(&mut self, content: &str) -> Result<(), Self::Error> {
+        self.lines.push(format!("comment {content}"));
+        Ok(())
+    }
+
+    fn doctype(&mut self, name: &str, attr: Option<&str>) -> Result<(), Self::Error> {
+        if self.doctypes {
+            self.lines.push(format!("doctype {name} {}", attr.unwrap_or("-")));
+        }
+        Ok(())
+    }
+
+    fn end_tag(&mut self, name: &str) -> Result<(), Self::Error> {
+        self.lines.push(format!("end {name}"));
+        Ok(())
+    }
+
+    fn start_tag(&mut self, name: &str) -> Result<(), Self::Error> {
+        self.lines.push(format!("start {name}"));
+        Ok(())
+    }
+
+    fn text(&mut self, text: &str) -> Result<(), Self::Error> {
+        self.lines.push(format!("text {text}"));
+        Ok(())
+    }
+}
+
+/// Fails on the tags with a given name.
+struct Forbid(&'static str, usize);
+
+impl Serializer for Forbid {
+    type Error = String;
+
+    fn attr(&mut self, _name: &str, _value: Option<&str>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn comment(&mut self, _content: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn end_tag(&mut self, _name: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn start_tag(&mut self, name: &str) -> Result<(), Self::Error> {
+        if name == self.0 {
+            return Err(format!("<{name}> is forbidden"));
+        }
+        self.1 = self.1.saturating_add(1);
+        Ok(())
+    }
+
+    fn text(&mut self, _text: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+fn calls(input: &str, doctypes: bool) -> Vec<String> {
+    let html = Html::parse(input).expect("Invalid html");
+    let mut calls = Calls { doctypes, lines: vec![] };
+    html.serialize(&mut calls).expect("Infallible");
+    calls.lines
+}
+
+#[test]
+fn serializer_doctype() {
+    let input = "<!DOCTYPE html><p>Hi</p>";
+    assert_eq!(calls(input, false), ["start p", "text Hi", "end p"]);
+    assert_eq!(calls(input, true), ["doctype DOCTYPE html", "start p", "text Hi", "end p"]);
+}
+
+#[test]
+fn serializer_error() {
+    let html =
+        Html::parse("<div><p>a</p><form><input></form><span>b</span></div>").expect("Invalid html");
+    let mut forbid = Forbid("form", 0);
+    let err = html.serialize(&mut forbid).expect_err("Form not found");
+    assert_eq!(err, "<form> is forbidden");
+    assert_eq!(forbid.1, 2);
+}
+
+#[test]
+fn serializer_order() {
+    assert_eq!(calls(r#"<a href="/" hidden>Link<br></a><!--end-->"#, true), [
+        "start a",
+        "attr href /",
+        "attr hidden -",
+        "text Link",
+        "start br",
+        "end br",
+        "end a",
+        "comment end"
+    ]);
+}
@@ -0,0 +1,33 @@
+use std::collections::HashMap;
+
+use html_filter::*;
+
+#[test]
+fn slots_filtered_skeleton() {
+    let page = Html::parse(
+        r#"<html><body><nav>Menu</nav><article data-slot="article">Old</article><footer><slot name="year">2024</slot></footer></body></html>"#,
+    )
+    .unwrap_or_else(|err| panic!("{err}"));
+    let mut skeleton = page.to_filtered(&Filter::new().tag_name("article").tag_name("footer"));
+
+    let mut slots = HashMap::new();
+    slots.insert(
+        "article",
+        Html::parse("<p>New</p><p>Text</p>").unwrap_or_else(|err| panic!("{err}")),
+    );
+    slots.insert("year", Html::Text("2025".into()));
+    skeleton.fill_slots(&slots);
+
+    assert_eq!(skeleton, "<p>New</p><p>Text</p><footer>2025</footer>");
+}
+
+#[test]
+fn slots_not_recursive() {
+    let mut html = Html::parse(r#"<slot name="a" />"#).unwrap_or_else(|err| panic!("{err}"));
+    let slots = HashMap::from([(
+        "a",
+        Html::parse(r#"<slot name="a" />"#).unwrap_or_else(|err| panic!("{err}")),
+    )]);
+    html.fill_slots(&slots);
+    assert_eq!(html, r#"<slot name="a"></slot>"#);
+}
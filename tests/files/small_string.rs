@@ -0,0 +1,67 @@
+use html_filter::*;
+
+#[test]
+fn small_string_boundary() {
+    let inline = "x".repeat(22);
+    let heap = "x".repeat(23);
+    assert!(SmallString::from(inline.as_str()).is_inline());
+    assert!(SmallString::from(inline.clone()).is_inline());
+    assert!(!SmallString::from(heap.as_str()).is_inline());
+    assert!(!SmallString::from(heap.clone()).is_inline());
+    assert_eq!(String::from(SmallString::from(inline.clone())), inline);
+    assert_eq!(String::from(SmallString::from(heap.clone())), heap);
+}
+
+#[test]
+fn small_string_multibyte() {
+    let mut string = SmallString::new();
+    for _ in 0..4u32 {
+        string.push_str("caf\u{e9}");
+    }
+    assert!(string.is_inline());
+    string.push_str("\u{1f980}");
+    assert!(!string.is_inline());
+    assert_eq!(string, format!("{}\u{1f980}", "caf\u{e9}".repeat(4)));
+}
+
+#[test]
+fn small_string_size() {
+    assert_eq!(size_of::<SmallString>(), size_of::<String>());
+}
+
+#[test]
+fn small_string_tree() {
+    let html = Html::parse(
+        "<section data-a-very-long-attribute-name='v'>\n  <p>short</p>\n  <p>A text that is too \
+         long to be inline.</p>\n</section>",
+    )
+    .expect("valid html");
+    let (section, content) = html.as_tag().expect("section");
+    assert!(section.name.is_inline());
+    let attr = section.as_attrs().first().expect("one attribute");
+    assert_eq!(attr.as_name(), "data-a-very-long-attribute-name");
+    let nodes = content.as_vec().expect("several nodes");
+    let texts = nodes
+        .iter()
+        .filter_map(|node| match node {
+            Html::Text(text) => Some(text),
+            Html::Tag { child, .. } => match &**child {
+                Html::Text(text) => Some(text),
+                Html::Comment(_)
+                | Html::Doctype { .. }
+                | Html::Empty
+                | Html::Tag { .. }
+                | Html::Vec(_) => None,
+            },
+            Html::Comment(_) | Html::Doctype { .. } | Html::Empty | Html::Vec(_) => None,
+        })
+        .map(|text| (text.as_str(), text.is_inline()))
+        .collect::<Vec<_>>();
+    assert_eq!(texts, [
+        ("\n  ", true),
+        ("short", true),
+        ("\n  ", true),
+        ("A text that is too long to be inline.", false),
+        ("\n", true),
+    ]);
+}
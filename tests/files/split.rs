@@ -0,0 +1,34 @@
+use html_filter::*;
+
+macro_rules! test_split {
+    ($($name:ident: $html:expr, $filter:expr => $before:expr, $after:expr)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let html = Html::parse($html).unwrap_or_else(|err| panic!("{err}"));
+                let (before, after) = html.split_at(&$filter);
+                assert_eq!(before, $before);
+                assert_eq!(after, $after);
+            }
+        )*
+    };
+}
+
+test_split!(
+
+split_nested: "<body><main><div><p>1</p><br /><p>2</p></div><p>3</p></main></body>", Filter::new().tag_name("br") =>
+"<body><main><div><p>1</p></div></main></body>", "<body><main><div><p>2</p></div><p>3</p></main></body>"
+
+split_first_match: "<p>1</p><hr /><p>2</p><hr /><p>3</p>", Filter::new().tag_name("hr") =>
+"<p>1</p>", "<p>2</p><hr><p>3</p>"
+
+split_first_node: "<hr /><p>1</p>", Filter::new().tag_name("hr") =>
+"", "<p>1</p>"
+
+split_last_node: "<div><p>1</p><hr /></div>", Filter::new().tag_name("hr") =>
+"<div><p>1</p></div>", "<div></div>"
+
+split_marker_content: "<p>1</p><div class=\"page\">ignored</div>text", Filter::new().attribute_value("class", "page") =>
+"<p>1</p>", "text"
+
+);
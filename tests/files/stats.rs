@@ -0,0 +1,84 @@
+use html_filter::*;
+
+/// Form with a few inputs.
+const INPUT: &str = r#"<form><input type="text" name="a" required /><input type="radio" name="b" /><button type="submit" disabled>Go</button></form>"#;
+
+/// Returns the statistics of the filter on the input.
+fn profile(filter: &Filter) -> RuleStats {
+    filter.profile(&Html::parse(INPUT).unwrap_or_else(|err| panic!("{err}")))
+}
+
+/// Returns the accepted and rejected counts of the rules.
+fn counts(stats: &RuleStats) -> Vec<(&str, usize, usize)> {
+    stats
+        .as_rules()
+        .iter()
+        .map(|rule| (rule.as_description(), rule.accepted(), rule.rejected()))
+        .collect()
+}
+
+#[test]
+fn stats_attributes() {
+    let stats = profile(
+        &Filter::new()
+            .attribute_value("type", "text")
+            .attribute_present("name")
+            .except_attribute_name("disabled")
+            .max_attributes(3),
+    );
+    assert_eq!(stats.tags(), 4);
+    assert_eq!(stats.matched(), 1);
+    assert_eq!(counts(&stats), [
+        ("at most 3 attributes", 4, 0),
+        ("attribute 'type' is 'text'", 1, 3),
+        ("attribute 'name' is present", 2, 2),
+        ("no attribute 'disabled' that has no value", 3, 1),
+    ]);
+    assert_eq!(
+        stats.most_rejecting().map(RuleStat::as_description),
+        Some("attribute 'type' is 'text'")
+    );
+}
+
+#[test]
+fn stats_empty() {
+    let stats = profile(&Filter::new());
+    assert_eq!((stats.tags(), stats.matched()), (4, 4));
+    assert!(stats.as_rules().is_empty());
+    assert_eq!(stats.most_rejecting(), None);
+
+    let text = Html::parse("text").unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(
+        Filter::new().tag_name("p").profile(&text).to_string(),
+        "0 of 0 tags matched the filter."
+    );
+}
+
+#[test]
+fn stats_names_text_and_predicates() {
+    let stats = profile(
+        &Filter::new()
+            .tag_name("button")
+            .tag_name("input")
+            .except_tag_name("form")
+            .text_contains("Go")
+            .tag_predicate(|tag| tag.attr_count() > 1)
+            .node_predicate(|node| node.as_tag().is_some()),
+    );
+    assert_eq!(stats.matched(), 1);
+    assert_eq!(
+        stats.to_string(),
+        "1 of 4 tags matched the filter.
+- tag name 'button' or 'input', tag name not 'form': 3 accepted, 1 rejected
+- text contains 'Go': 2 accepted, 2 rejected
+- tag predicate 1: 3 accepted, 1 rejected
+- node predicate 1: 4 accepted, 0 rejected"
+    );
+}
+
+#[test]
+fn stats_no_tags() {
+    let example = Html::parse("<input>").unwrap_or_else(|err| panic!("{err}"));
+    let stats = profile(&Filter::new().no_tags().like(&example, 0));
+    assert_eq!(counts(&stats), [("no tags", 0, 4), ("structure like the example", 2, 2)]);
+}
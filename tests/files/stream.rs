@@ -0,0 +1,102 @@
+use std::fs::read_to_string;
+
+use html_filter::*;
+
+const INPUTS: [&str; 17] = [
+    "",
+    "Only text",
+    "<!DOCTYPE html><html lang=\"en\"><head><title>A</title></head><body></body></html>",
+    "a---b--c-",
+    "<!-- a --- b -- c -->d",
+    "<!-- unclosed - comment",
+    "<p>a<br>b</br>c</p>",
+    "<script>if (a < b) { c--; }</script><p>d</p>",
+    "<style>p > a { color: red; }</style>",
+    "<div><p>x</div>y",
+    "<div id='a' class=\"b c>\" hidden data-x=\"'\">z</div>",
+    "</p>",
+    "a-->",
+    "<p",
+    "<a b=c>",
+    "<p>caf\u{e9} \u{1f980}</p>",
+    "<ul>\n  <li>caf\u{e9}</li>\n  <li id=a>\n</ul>",
+];
+
+fn chunks(input: &str, size: usize) -> Vec<&str> {
+    let mut chunks = vec![];
+    let mut rest = input;
+    while !rest.is_empty() {
+        let mut end = size.min(rest.len());
+        while !rest.is_char_boundary(end) {
+            end = end.saturating_add(1);
+        }
+        let (chunk, tail) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = tail;
+    }
+    chunks
+}
+
+fn stream(input: &str, size: usize) -> Result<Html, ParseError> {
+    let mut parser = Parser::new();
+    for chunk in chunks(input, size) {
+        parser.feed(chunk)?;
+    }
+    parser.finish()
+}
+
+#[test]
+fn stream_budget() {
+    let input = "<ul><li>1</li><li>2</li><li>3</li></ul>";
+    let mut parser = Parser::with_options(ParseOptions::new().budget(Budget::new().max_nodes(3)));
+    let result = chunks(input, 5).into_iter().try_for_each(|chunk| parser.feed(chunk));
+    assert_eq!(
+        result.map_err(|err| err.kind().clone()),
+        Err(ParseErrorKind::BudgetExceeded(BudgetExceeded::Nodes))
+    );
+}
+
+#[test]
+fn stream_hooks() {
+    let input = "<div>First text<p>Second - text</p><!-- comment -->Third</div>";
+    let mut expected = (vec![], vec![]);
+    Html::parse_with(
+        input,
+        ParseOptions::new()
+            .on_tag_open(|tag| expected.0.push(tag.as_name().to_owned()))
+            .on_text(|text| expected.1.push(text.to_owned())),
+    )
+    .expect("valid html");
+    for size in 1..8 {
+        let mut found = (vec![], vec![]);
+        let mut parser = Parser::with_options(
+            ParseOptions::new()
+                .on_tag_open(|tag| found.0.push(tag.as_name().to_owned()))
+                .on_text(|text| found.1.push(text.to_owned())),
+        );
+        for chunk in chunks(input, size) {
+            parser.feed(chunk).expect("valid html");
+        }
+        parser.finish().expect("valid html");
+        assert_eq!(found, expected, "chunks of {size} bytes");
+    }
+}
+
+#[test]
+fn stream_index() {
+    let content = read_to_string("tests/data/index.html").expect("file exists");
+    let expected = Html::parse(&content);
+    for size in [1, 2, 3, 7, 64, 4096] {
+        assert_eq!(stream(&content, size), expected, "chunks of {size} bytes");
+    }
+}
+
+#[test]
+fn stream_inputs() {
+    for input in INPUTS {
+        let expected = Html::parse(input);
+        for size in 1..=input.len().max(1) {
+            assert_eq!(stream(input, size), expected, "{input} in chunks of {size} bytes");
+        }
+    }
+}
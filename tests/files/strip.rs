@@ -0,0 +1,30 @@
+use html_filter::*;
+
+macro_rules! test_strip {
+    ($($name:ident: $html:expr => $expect:expr)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let mut html = Html::parse($html).unwrap_or_else(|err| panic!("{err}"));
+                html.strip_where_attr("data-feature", |feature| feature.is_empty() || feature == "off");
+                assert_eq!(html, $expect);
+            }
+        )*
+    };
+}
+
+test_strip!(
+
+strip_nested: r#"<div data-feature="on"><p data-feature="off">a</p><p>b</p></div>"# =>
+r#"<div data-feature="on"><p>b</p></div>"#
+
+strip_no_value: "<p data-feature>a</p><p>b</p>" =>
+"<p>b</p>"
+
+strip_root: r#"<div data-feature="off"><p>a</p></div>"# =>
+""
+
+strip_other_attribute: r#"<div data-other="off" feature="off">a</div>"# =>
+r#"<div data-other="off" feature="off">a</div>"#
+
+);
@@ -0,0 +1,57 @@
+use html_filter::*;
+
+fn parse(input: &str) -> Html {
+    Html::parse_with(input, ParseOptions::new().templates(true))
+        .unwrap_or_else(|err| panic!("{err}"))
+}
+
+#[test]
+fn templates_attributes() {
+    let html = parse(
+        r#"<input value={{ user.name }} {% if disabled %}disabled{% endif %} data-{{ key }}="1" title='<%= t("it's") %>'>"#,
+    );
+    let (tag, _) = html.as_tag().expect("Not a tag");
+    let names = tag.as_attrs().iter().map(Attribute::as_name).collect::<Vec<_>>();
+    assert_eq!(names, [
+        "value={{ user.name }}",
+        "{% if disabled %}disabled{% endif %}",
+        "data-{{ key }}",
+        "title"
+    ]);
+    assert_eq!(tag.find_attr_value("title").map(String::as_str), Some(r#"<%= t("it's") %>"#));
+}
+
+#[test]
+fn templates_disabled() {
+    let html = Html::parse(r#"<p title="{{ a "b" }}">x</p>"#).expect("Invalid html");
+    assert_eq!(html.as_tag().expect("Not a tag").0.as_attrs().len(), 3);
+    let templated = parse(r#"<p title="{{ a "b" }}">x</p>"#);
+    assert_eq!(templated.as_tag().expect("Not a tag").0.as_attrs().len(), 1);
+}
+
+#[test]
+fn templates_stream() {
+    let input = r#"<p title="{{ a "b" }}">{{ x }} {% if a --> b %}{{ a < b }}{% endif %}</p>"#;
+    for split in 1..input.len() {
+        let mut parser = Parser::with_options(ParseOptions::new().templates(true));
+        let (first, second) = input.split_at(split);
+        parser.feed(first).unwrap_or_else(|err| panic!("{err}"));
+        parser.feed(second).unwrap_or_else(|err| panic!("{err}"));
+        let html = parser.finish().unwrap_or_else(|err| panic!("{err}"));
+        assert_eq!(html, parse(input), "split at {split}");
+    }
+}
+
+#[test]
+fn templates_texts() {
+    let input = "<p>{% if a --> b %}{{ x < y }}<%# <p> %>{# {{ #} {unclosed</p>";
+    let html = parse(input);
+    assert_eq!(html.to_string(), input);
+    assert_eq!(
+        html.as_tag().expect("Not a tag").1.to_string(),
+        "{% if a --> b %}{{ x < y }}<%# <p> %>{# {{ #} {unclosed"
+    );
+    let err = Html::parse_with("<p><% x", ParseOptions::new().templates(true))
+        .expect_err("Unclosed placeholders are read as html");
+    assert_eq!(err.kind(), &ParseErrorKind::UnclosedTag);
+}
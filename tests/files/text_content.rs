@@ -0,0 +1,50 @@
+use html_filter::*;
+
+const INPUT: &str = r#"<div class="cart">
+    <button id="btn-a1b2">
+        Add to
+        <b>cart</b>
+    </button>
+    <button id="btn-c3d4">Add to wishlist</button>
+    <p>Add <!-- promo -->to cart now</p>
+</div>"#;
+
+fn filtered(filter: &Filter) -> String {
+    Html::parse(INPUT).expect("Invalid html").to_filtered(filter).to_string()
+}
+
+#[test]
+fn text_contains() {
+    let filter = Filter::new().tag_name("button").text_contains("Add to cart");
+    assert!(filtered(&filter).starts_with(r#"<button id="btn-a1b2">"#));
+    assert!(!filtered(&filter).contains("wishlist"));
+    assert_eq!(
+        filtered(&Filter::new().tag_name("p").text_contains("to   cart")),
+        "<p>Add <!-- promo -->to cart now</p>"
+    );
+    assert_eq!(filtered(&Filter::new().tag_name("button").text_contains("add to")), "");
+}
+
+#[test]
+fn text_equals() {
+    assert_eq!(
+        filtered(&Filter::new().text_equals("Add to wishlist").tag_name("button")),
+        r#"<button id="btn-c3d4">Add to wishlist</button>"#
+    );
+    assert_eq!(filtered(&Filter::new().tag_name("p").text_equals("Add to cart")), "");
+    assert_eq!(
+        filtered(&Filter::new().tag_name("button").text_contains("Add").text_contains("wish")),
+        r#"<button id="btn-c3d4">Add to wishlist</button>"#
+    );
+}
+
+#[test]
+fn text_report() {
+    let html = Html::parse(INPUT).expect("Invalid html");
+    let filter = Filter::new().tag_name("button").text_equals("Buy");
+    let report = html.select_or_report(&filter).expect_err("No button is labelled Buy");
+    assert_eq!(report.as_partial_matches().len(), 2);
+    assert!(
+        report.to_string().ends_with(r#"<button id="btn-c3d4">: the text content doesn't match"#)
+    );
+}
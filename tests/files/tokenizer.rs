@@ -0,0 +1,158 @@
+extern crate alloc;
+use alloc::borrow::Cow;
+use std::fs::read_to_string;
+
+use html_filter::*;
+
+fn kinds(input: &str) -> Vec<TokenKind<'_>> {
+    Tokenizer::new(input)
+        .map(|token| token.map(Token::into_kind))
+        .collect::<Result<_, _>>()
+        .unwrap_or_else(|err| panic!("{err}"))
+}
+
+#[test]
+fn tokenizer_errors() {
+    let mut unclosed = Tokenizer::new("<p>a</p><p class='b'");
+    let err = unclosed.find_map(Result::err).expect("Expected an unclosed tag");
+    assert_eq!(err.kind(), &ParseErrorKind::UnclosedTag);
+    assert_eq!(unclosed.next(), None);
+
+    let comment_err =
+        Tokenizer::new("a\nb --> c").find_map(Result::err).expect("Expected an unopened comment");
+    assert_eq!(comment_err.kind(), &ParseErrorKind::UnopenedComment);
+    assert_eq!((comment_err.line(), comment_err.column(), comment_err.offset()), (2, 3, 4));
+
+    let parse_err = Html::parse("<p>a</p><a:b>").expect_err("Expected an invalid tag name");
+    let token_err = Tokenizer::new("<p>a</p><a:b>").find_map(Result::err);
+    assert_eq!(token_err, Some(parse_err));
+}
+
+#[test]
+fn tokenizer_index() {
+    let content = read_to_string("tests/data/index.html").expect("file exists");
+    let tokens = Tokenizer::new(&content)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_or_else(|err| panic!("{err}"));
+    let mut end = 0;
+    for token in &tokens {
+        assert_eq!(token.span().start, end, "{token:?}");
+        end = token.span().end;
+    }
+    assert_eq!(end, content.len());
+
+    let opened = tokens
+        .iter()
+        .filter_map(|token| match token.as_kind() {
+            TokenKind::OpenTag { tag, .. } => Some(tag.as_name().to_owned()),
+            TokenKind::CloseTag(_)
+            | TokenKind::Comment(_)
+            | TokenKind::Doctype { .. }
+            | TokenKind::Text(_) => None,
+        })
+        .collect::<Vec<_>>();
+    let tree = Html::parse(&content).unwrap_or_else(|err| panic!("{err}"));
+    let tags = tree.iter_tags().map(|(tag, _)| tag.as_name().to_owned()).collect::<Vec<_>>();
+    assert_eq!(opened, tags);
+}
+
+/// Returns the tags, texts, comments and doctypes of a tree, in the order of
+/// the document, with the adjacent texts merged.
+fn tree_nodes(html: &Html, nodes: &mut Vec<String>) {
+    match html {
+        Html::Comment(comment) => nodes.push(format!("<!--{comment}-->")),
+        Html::Doctype { name, attr } => nodes.push(format!("<!{name} {attr:?}>")),
+        Html::Empty => (),
+        Html::Tag { tag, child } => {
+            nodes.push(format!("<{tag}>"));
+            tree_nodes(child, nodes);
+        }
+        Html::Text(text) => push_text(nodes, text),
+        Html::Vec(vec) =>
+            for child in vec {
+                tree_nodes(child, nodes);
+            },
+    }
+}
+
+/// Returns the tags, texts, comments and doctypes read by the tokenizer, like
+/// [`tree_nodes`].
+fn token_nodes(input: &str) -> Result<Vec<String>, ParseError> {
+    let mut nodes = vec![];
+    for token in Tokenizer::new(input) {
+        match token?.into_kind() {
+            TokenKind::CloseTag(_) => (),
+            TokenKind::Comment(comment) => nodes.push(format!("<!--{comment}-->")),
+            TokenKind::Doctype { name, attr } => nodes.push(format!("<!{name} {attr:?}>")),
+            TokenKind::OpenTag { tag, .. } => nodes.push(format!("<{}>", tag.into_tag())),
+            TokenKind::Text(text) => push_text(&mut nodes, text),
+        }
+    }
+    Ok(nodes)
+}
+
+fn push_text(nodes: &mut Vec<String>, text: &str) {
+    if let Some(last) = nodes.last_mut()
+        && !last.starts_with('<')
+    {
+        last.push_str(text);
+    } else {
+        nodes.push(text.to_owned());
+    }
+}
+
+#[test]
+fn tokenizer_parity() {
+    let index = read_to_string("tests/data/index.html").expect("file exists");
+    for input in [
+        index.as_str(),
+        "<SCRIPT>if (a < b) { c('</p>') }</Script><style>p > a {}</style>",
+        "<p title='a > b' hidden>x &amp; y<br>z<img src=a.png /></p>",
+        "<!DOCTYPE html><!-- a -- b --><div><b>c</b> d</div>",
+        "<div>{{ a < b }}</div>",
+        "<p>a</p><a:b>",
+        "a\nb --> c",
+        "<p class='b'",
+    ] {
+        let tokens = token_nodes(input);
+        match Html::parse(input) {
+            Ok(html) => {
+                let mut nodes = vec![];
+                tree_nodes(&html, &mut nodes);
+                assert_eq!(tokens, Ok(nodes), "{input}");
+            }
+            Err(err) => assert_eq!(tokens, Err(err), "{input}"),
+        }
+    }
+}
+
+#[test]
+fn tokenizer_raw_text() {
+    assert_eq!(kinds("<script>if (a < b) { c('</p>') }</script><style></style>"), [
+        TokenKind::OpenTag { self_closing: false, tag: tag_ref("script") },
+        TokenKind::Text("if (a < b) { c('</p>') }"),
+        TokenKind::CloseTag("script".into()),
+        TokenKind::OpenTag { self_closing: false, tag: tag_ref("style") },
+        TokenKind::CloseTag("style".into()),
+    ]);
+    assert_eq!(kinds("<style>a < b"), [
+        TokenKind::OpenTag { self_closing: false, tag: tag_ref("style") },
+        TokenKind::Text("a < b"),
+    ]);
+}
+
+#[test]
+fn tokenizer_unbalanced() {
+    assert_eq!(kinds("</a><!DOCTYPE html><b/>text<!-- open"), [
+        TokenKind::CloseTag("a".into()),
+        TokenKind::Doctype { attr: Some("html"), name: "DOCTYPE".into() },
+        TokenKind::OpenTag { self_closing: true, tag: tag_ref("b") },
+        TokenKind::Text("text"),
+        TokenKind::Comment(" open"),
+    ]);
+    assert_eq!(kinds(""), []);
+}
+
+fn tag_ref(name: &str) -> TagRef<'_> {
+    TagRef::from((Cow::Borrowed(name), Box::default()))
+}
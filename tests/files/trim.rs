@@ -1,4 +1,4 @@
-use html_filter::{Filter, Html};
+use html_filter::{Filter, Html, TrimMode};
 
 use crate::files::strings::INPUT;
 use crate::files::test_maker;
@@ -8,7 +8,7 @@ fn trim() {
     test_maker(
         "trim",
         "<!DOCTYPE html><!-- comment 1 --><html>A first text<!-- first comment \
-         --><p>A<strong>first</strong>text<!-- second comment --><img></img></p></html>",
+         --><p>A<strong>first</strong>text<!-- second comment --><img></p></html>",
         &Html::parse(INPUT).expect("failed to parse").filter(&Filter::new().trim()),
         "",
         false,
@@ -51,3 +51,54 @@ fn remove_empty() {
     assert_eq!(th_tag.name, "th");
     assert_eq!(*th_child, Html::Empty);
 }
+
+#[test]
+fn trim_preformatted() {
+    let html = Html::parse(
+        "<div>\n  <p>  a  b </p>\n  <pre>  x\n    y</pre><pre><b> z  </b>\n</pre></div>",
+    )
+    .unwrap_or_else(|err| panic!("{err}"));
+    let expected = "<div><p>a b</p><pre>  x\n    y</pre><pre><b> z  </b>\n</pre></div>";
+    for mode in [TrimMode::Collapse, TrimMode::Edges] {
+        let filter = Filter::new().tag_name("div").trim_text(mode);
+        let kept = if mode == TrimMode::Edges {
+            expected.replace("a b", "a  b")
+        } else {
+            expected.to_owned()
+        };
+        assert_eq!(html.to_filtered(&filter), kept.as_str(), "{mode:?}");
+        assert_eq!(html.clone().filter(&filter), kept.as_str(), "{mode:?}");
+    }
+    let bold = Filter::new().tag_name("b").trim_text(TrimMode::Collapse);
+    assert_eq!(html.to_filtered(&bold), "<b> z  </b>");
+    assert_eq!(html.first_n_elements(1, &bold), "<div><pre><b> z  </b></pre></div>");
+}
+
+#[test]
+fn trim_modes() {
+    let html = Html::parse("<div>\n  <p>  Some\n\t spaced   text </p>\n  <p> \u{a0}</p>\n</div>")
+        .expect("failed to parse");
+    for (mode, expected, texts) in [
+        (
+            TrimMode::None,
+            "<div>\n  <p>  Some\n\t spaced   text </p>\n  <p> \u{a0}</p>\n</div>",
+            "\n    Some\n\t spaced   text \n   \u{a0}\n",
+        ),
+        (
+            TrimMode::Edges,
+            "<div><p>Some\n\t spaced   text</p><p></p></div>",
+            "Some\n\t spaced   text",
+        ),
+        (TrimMode::Collapse, "<div><p>Some spaced text</p><p></p></div>", "Some spaced text"),
+    ] {
+        let filter = Filter::new().tag_name("div").trim_text(mode);
+        assert_eq!(html.to_filtered(&filter), expected, "{mode:?}");
+        assert_eq!(html.clone().filter(&filter), expected, "{mode:?}");
+        let text_filter = Filter::new().no_tags().none_except_text().trim_text(mode).collapse();
+        assert_eq!(html.to_filtered(&text_filter), texts, "{mode:?}");
+    }
+    assert_eq!(
+        html.to_filtered(&Filter::new().tag_name("div").trim()),
+        html.to_filtered(&Filter::new().tag_name("div").trim_text(TrimMode::Edges))
+    );
+}
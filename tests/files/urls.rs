@@ -0,0 +1,42 @@
+use html_filter::*;
+
+macro_rules! test_rewrite {
+    ($($name:ident: $html:expr => $expect:expr)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let mut html = Html::parse($html).unwrap_or_else(|err| panic!("{err}"));
+                html.rewrite_urls(|url| format!("[{url}]"));
+                assert_eq!(html, $expect);
+            }
+        )*
+    };
+}
+
+test_rewrite!(
+
+rewrite_attributes: r##"<form action="/send"><a href="#" id="link">a</a><!-- <a href="x"> --></form>"## =>
+r#"<form action="[/send]"><a href="[#]" id="link">a</a><!-- <a href="x"> --></form>"#
+
+rewrite_srcset_width: r#"<img srcset="small.jpg 480w, large.jpg 1080w" />"# =>
+r#"<img srcset="[small.jpg] 480w, [large.jpg] 1080w">"#
+
+rewrite_srcset_no_descriptor: r#"<img srcset=" a.png,  b.png 2x,, c.png" />"# =>
+r#"<img srcset="[a.png], [b.png] 2x, [c.png]">"#
+
+rewrite_srcset_data_url: r#"<img srcset="data:image/png;base64,AAAA 1x, b.png 2x" />"# =>
+r#"<img srcset="[data:image/png;base64,AAAA] 1x, [b.png] 2x">"#
+
+rewrite_refresh_quoted: r#"<meta http-equiv="Refresh" content="0;URL='/next'" />"# =>
+r#"<meta http-equiv="Refresh" content="0;URL='[/next]'">"#
+
+rewrite_refresh_no_prefix: r#"<meta http-equiv="refresh" content="3, /next" />"# =>
+r#"<meta http-equiv="refresh" content="3, [/next]">"#
+
+rewrite_refresh_no_url: r#"<meta http-equiv="refresh" content="3" /><meta http-equiv="refresh" content="3; " />"# =>
+r#"<meta http-equiv="refresh" content="3"><meta http-equiv="refresh" content="3; ">"#
+
+rewrite_meta_not_refresh: r#"<meta name="description" content="1; url=/x" /><meta content="1; url=/x" />"# =>
+r#"<meta name="description" content="1; url=/x"><meta content="1; url=/x">"#
+
+);
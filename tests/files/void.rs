@@ -0,0 +1,109 @@
+use std::fs::read_to_string;
+
+use html_filter::*;
+
+#[test]
+fn void_display() {
+    let input = r#"<p>a<br class="x"><img src="b" alt="c"><hr id="d">e<wbr></p>"#;
+    let html = Html::parse(input).unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(html.to_string(), input);
+    let borrowed = Html::parse_borrowed(input).unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(borrowed.to_string(), input);
+    let built = Html::tag("br").attr("class", "x").child(Html::text("y")).build();
+    assert_eq!(built.to_string(), r#"<br class="x">y"#);
+}
+
+#[test]
+fn void_index() {
+    let content = read_to_string("tests/data/index.html").expect("file exists");
+    let mut tree = Html::parse(&content).unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(tree.check_void_elements(VoidPolicy::Strict), Ok(vec![]));
+}
+
+#[test]
+fn void_assembled_br() {
+    let br = Tag::from(("br".to_owned(), Box::default()));
+    let mut tree = Html::Tag {
+        tag: Tag::from(("p".to_owned(), Box::default())),
+        child: Box::new(Html::Tag { tag: br, child: Box::new(Html::Text("text".into())) }),
+    };
+    assert_eq!(tree, "<p><br>text</p>");
+
+    let warnings = tree.check_void_elements(VoidPolicy::Lenient).expect("lenient never fails");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings.first().map(audit::Warning::as_path), Some([0].as_slice()));
+    assert_eq!(tree, "<p><br>text</p>");
+    assert!(
+        tree.as_tag()
+            .is_some_and(|(_, child)| child.as_vec().is_some_and(|nodes| nodes.len() == 2))
+    );
+}
+
+#[test]
+fn void_parse_policies() {
+    let html = "<head><meta charset='utf-8'></meta></head><p>1<br>2</br>3</p>";
+    let expected = "<head><meta charset='utf-8'></head><p>1<br>23</p>";
+
+    let mut warnings = vec![];
+    let tree = Html::parse_with(
+        html,
+        ParseOptions::new().on_warning(|warning| warnings.push(warning.to_owned())),
+    );
+    assert_eq!(tree.as_ref().map(ToString::to_string), Ok(expected.to_owned()));
+    assert_eq!(warnings, [
+        "Ignored closing tag of void element 'meta' at byte 28 (line 1, column 29).",
+        "Ignored closing tag of void element 'br' at byte 51 (line 1, column 52)."
+    ]);
+
+    let lenient = Html::parse_with(html, ParseOptions::new().void_policy(VoidPolicy::Lenient));
+    assert_eq!(lenient.as_ref().map(ToString::to_string), Ok(expected.to_owned()));
+
+    let strict = Html::parse_with(html, ParseOptions::new().void_policy(VoidPolicy::Strict));
+    assert_eq!(
+        strict.map_err(|err| err.kind().clone()),
+        Err(ParseErrorKind::VoidClosed("meta".to_owned()))
+    );
+}
+
+#[test]
+fn void_parse_spec_list() {
+    let html = "<p><img src='a.png'>a<input type='text'>b<hr>c<wbr>d</p><table><col><col></table>";
+    let tree = Html::parse(html).unwrap_or_else(|err| panic!("{err}"));
+    let (_, child) =
+        tree.as_vec().and_then(|nodes| nodes.first()?.as_tag()).expect("Expected a <p>");
+    let children = child.as_vec().expect("Expected the children of the <p>");
+    assert_eq!(children.len(), 8);
+    assert!(
+        children.iter().step_by(2).all(|node| node.as_tag().is_some_and(|(tag, _)| tag.is_void()))
+    );
+    assert_eq!(
+        Html::parse_parallel(html, 2).map(|parsed| parsed.to_string()),
+        Ok(tree.to_string())
+    );
+}
+
+#[test]
+fn void_tags_custom() {
+    let html = "<icon name='a'>b</icon><br>c";
+    let strict = ParseOptions::new().void_tags(["icon"]).void_policy(VoidPolicy::Strict);
+    assert_eq!(
+        Html::parse_with(html, strict).map_err(|err| err.kind().clone()),
+        Err(ParseErrorKind::VoidClosed("icon".to_owned()))
+    );
+
+    let tree =
+        Html::parse_with("<icon name='a'>b<br>c</br>", ParseOptions::new().void_tags(["icon"]))
+            .unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(tree, "<icon name='a'></icon>b<br>c");
+    assert_eq!(tree.as_vec().and_then(|nodes| nodes.get(2)?.as_tag()?.1.as_text()), Some("c"));
+}
+
+#[test]
+fn void_tags_empty() {
+    let xhtml = "<p>a<img src='b'>c</img><br/></p>";
+    let options = ParseOptions::new().void_tags(Vec::<String>::new());
+    let tree = Html::parse_with(xhtml, options).unwrap_or_else(|err| panic!("{err}"));
+    let (_, child) = tree.as_tag().expect("Expected a <p>");
+    assert_eq!(child.as_vec().map(<[Html]>::len), Some(3));
+    assert_eq!(tree, "<p>a<img src='b'>c<br></p>");
+}